@@ -5,7 +5,7 @@
 //!
 //! - **Workspace mode**: Launch a full tmux workspace from `AXEL.md`
 //! - **Shell mode**: Launch a single shell (e.g., `axel claude`)
-//! - **Session management**: List, create, and kill tmux sessions
+//! - **Session management**: List, create, and kill sessions
 //! - **Skill management**: Create, import, fork, link, and remove skills
 //!
 //! # Examples
@@ -14,19 +14,49 @@
 //! axel                    # Launch workspace from AXEL.md
 //! axel claude             # Launch just the claude shell
 //! axel -p tmux_cc         # Launch with iTerm2 integration
+//! axel --only claude      # Launch just the claude cell of the grid
 //! axel -k                 # Kill current workspace
 //! axel -w feat/auth       # Create worktree + launch workspace there
+//! axel pr 1234            # Launch workspace on GitHub PR #1234
+//! axel open api           # Launch a known workspace from anywhere
+//! axel claude --prompt-file task.md  # Start claude on a task file (- for stdin)
+//! axel run claude "fix the build"  # Run a prompt once, print the reply
+//! axel ci smoke --junit report.xml  # Run a grid's panes headless (CI)
+//! axel --attach-in kitty  # Launch workspace and attach in a new kitty window
 //! axel session list       # List running axel sessions
 //! axel session new        # Create a new session (same as axel)
 //! axel session join foo   # Attach to session "foo"
+//! axel attach             # Pick a session to attach to
+//! axel up                 # Start every workspace in ~/.config/axel/fleet.yaml
+//! axel down               # Kill the fleet's sessions
 //! axel session kill foo   # Kill session named "foo"
+//! axel session env foo    # Show axel variables for session "foo"
+//! axel session record foo # Record session "foo" (axel session play to replay)
+//! axel broadcast "run the tests"  # Send to every AI pane (or prefix B)
+//! axel send foo:claude "fix the build" --wait  # Prompt one pane, print reply
+//! axel worktree ls        # List worktrees with their sessions
+//! axel status             # Session, panes, skills and server of this workspace
+//! axel dash               # Dashboard of sessions, events and usage
+//! axel transcript claude  # Show the claude pane's recorded output
+//! axel audit              # Review the diffs of the files agents changed
+//! axel checkpoints ls     # List checkpoints of agents' turns (diff, restore)
 //! axel skill list         # List available skills
 //! axel skill import ./    # Import skills from directory
+//! axel prompt ls          # List named prompts (prompt: "@name" in panes)
+//! axel upgrade-config     # Migrate a legacy manifest to the current schema
+//! axel doctor             # Check tmux, agent CLIs, ports and the manifest
+//! axel completions zsh    # Print a shell completion script
 //! ```
 
 use std::path::PathBuf;
 
-use clap::{Parser, Subcommand};
+use axel_core::Terminal;
+use clap::{
+    Parser, Subcommand,
+    builder::{PossibleValuesParser, TypedValueParser},
+};
+
+use crate::commands::completions::CompletionShell;
 
 /// Axel CLI - AI-assisted development workspace manager.
 ///
@@ -54,6 +84,43 @@ pub struct Cli {
     #[arg(short = 'p', long = "profile", value_name = "PROFILE")]
     pub profile: Option<String>,
 
+    /// Launch only the named panes from the grid (comma-separated).
+    ///
+    /// Remaining panes are packed into a compact grid. If a single AI pane
+    /// remains, it runs directly in the current shell without tmux.
+    #[arg(long = "only", value_name = "PANES", value_delimiter = ',')]
+    pub only: Vec<String>,
+
+    /// Attach in a new window of the given terminal instead of this shell.
+    ///
+    /// Applies to workspace launches and `session join`.
+    #[arg(
+        long = "attach-in",
+        value_name = "TERMINAL",
+        value_parser = PossibleValuesParser::new(["iterm", "terminal", "wezterm", "kitty"])
+            .try_map(|s| s.parse::<Terminal>()),
+        global = true
+    )]
+    pub attach_in: Option<Terminal>,
+
+    /// Keep pane scrollback instead of clearing each pane before its header.
+    ///
+    /// Useful for seeing exactly what a pane received. Panes can also set
+    /// `clear: false` in the manifest.
+    #[arg(long = "no-clear", global = true)]
+    pub no_clear: bool,
+
+    /// Print how long each launch phase took (config load, skill install,
+    /// session setup, per-pane command send, ...).
+    #[arg(long = "profile-timing", global = true)]
+    pub profile_timing: bool,
+
+    /// Set a `{{var}}` placeholder used in skill files (repeatable).
+    ///
+    /// Overrides the manifest's `skills_vars:`.
+    #[arg(long = "skill-var", value_name = "KEY=VALUE", global = true)]
+    pub skill_vars: Vec<String>,
+
     /// Kill a workspace session (uses current tmux session if no name given)
     #[arg(
         short = 'k',
@@ -95,6 +162,10 @@ pub struct Cli {
     #[arg(long = "prompt", value_name = "TEXT")]
     pub prompt: Option<String>,
 
+    /// Read the prompt from a file (`-` for stdin) instead of --prompt.
+    #[arg(long = "prompt-file", value_name = "PATH", conflicts_with = "prompt")]
+    pub prompt_file: Option<String>,
+
     /// Create/use git worktree for branch and launch workspace from there.
     ///
     /// If the branch doesn't exist, it will be created from the default branch.
@@ -156,7 +227,7 @@ pub enum Commands {
         action: SkillCommands,
     },
 
-    /// Manage tmux sessions (list, create, kill).
+    /// Manage sessions (list, create, kill).
     ///
     /// Sessions are tmux workspaces created by axel. Use these commands
     /// to list running sessions, create new ones, or kill existing ones.
@@ -166,6 +237,180 @@ pub enum Commands {
         action: SessionCommands,
     },
 
+    /// Manage git worktrees (list, add, remove, prune).
+    ///
+    /// Shows each worktree of the repository with its branch, the axel
+    /// session launched from it and whether it has uncommitted changes.
+    /// Worktrees whose session is gone can be cleaned up with `prune`.
+    #[command(visible_alias = "worktrees")]
+    Worktree {
+        #[command(subcommand)]
+        action: WorktreeCommands,
+    },
+
+    /// List the workspaces axel knows about.
+    ///
+    /// Every workspace launched or initialized with axel is remembered in
+    /// `~/.local/share/axel/registry.json`, so `axel open` can launch it
+    /// from any directory.
+    #[command(visible_alias = "workspaces")]
+    Workspace {
+        #[command(subcommand)]
+        action: WorkspaceCommands,
+    },
+
+    /// Attach to a running session, picking it interactively.
+    ///
+    /// Without a name, shows a fuzzy-searchable list of axel sessions with a
+    /// preview of each one's directory, branch and panes.
+    Attach {
+        /// Session to attach to (default: pick one)
+        name: Option<String>,
+
+        /// Offer all sessions, not just axel sessions
+        #[arg(short, long)]
+        all: bool,
+    },
+
+    /// Start every workspace listed in `~/.config/axel/fleet.yaml`.
+    ///
+    /// Each workspace (a directory or manifest, optionally with a `grid`)
+    /// gets its own tmux session, started in the background. Sessions that
+    /// are already running are left alone; attach with `axel attach`.
+    Up,
+
+    /// Kill the sessions of every workspace in `~/.config/axel/fleet.yaml`.
+    Down {
+        /// Keep skill symlinks instead of cleaning them up
+        #[arg(long)]
+        keep_skills: bool,
+    },
+
+    /// Send the same prompt to every AI pane in a session.
+    ///
+    /// Claude, Codex and OpenCode panes all get the prompt at once, so their
+    /// answers can be compared side by side. Inside a workspace, `prefix B`
+    /// does the same from a tmux prompt. Same as `axel session broadcast`.
+    Broadcast {
+        /// Prompt to send (`-` reads it from stdin)
+        text: String,
+
+        /// Session to target (default: current tmux session)
+        #[arg(short, long)]
+        session: Option<String>,
+
+        /// Only send to these drivers or pane names (comma-separated)
+        #[arg(long, value_delimiter = ',')]
+        panes: Vec<String>,
+    },
+
+    /// Send a prompt to an AI pane of a running session.
+    ///
+    /// The target is a session with a single AI pane, `session:pane` to pick
+    /// a pane by name or driver, or a tmux pane ID like `%5`. Multi-line
+    /// prompts are pasted as one input.
+    Send {
+        /// Session, `session:pane` or tmux pane ID
+        #[arg(value_name = "TARGET")]
+        target: String,
+
+        /// Prompt to send
+        prompt: String,
+
+        /// Wait for the agent to finish and print its reply (needs the
+        /// session's event server)
+        #[arg(long)]
+        wait: bool,
+
+        /// Seconds to wait for the reply
+        #[arg(long, value_name = "SECS", default_value = "600", requires = "wait")]
+        timeout: u64,
+    },
+
+    /// Launch a known workspace from any directory.
+    ///
+    /// Matches the name against the workspaces in the registry (see
+    /// `axel workspaces ls`): an exact workspace or directory name wins,
+    /// otherwise the name is matched fuzzily and several matches are
+    /// offered in a picker. Without a name, picks from all of them.
+    Open {
+        /// Workspace name, or part of it
+        name: Option<String>,
+    },
+
+    /// Launch the workspace on a GitHub pull request.
+    ///
+    /// Reads the PR with the GitHub CLI (`gh`), fetches its head branch,
+    /// opens it in a worktree and launches the workspace there, with the
+    /// PR's title, description and changed files as the AI panes' prompt.
+    /// Same as `axel -w pr/<number>`.
+    Pr {
+        /// Pull request number or URL
+        #[arg(value_name = "PR")]
+        pr: String,
+    },
+
+    /// Run a prompt once in an AI pane and print the reply.
+    ///
+    /// Launches the pane's agent non-interactively (`claude -p`, `codex
+    /// exec`, `opencode run`) with its skills, model and tools, waits for it
+    /// to finish and writes its reply to stdout. Skills are cleaned up
+    /// afterwards. Exits non-zero if the agent fails. Usable from scripts
+    /// and CI.
+    Run {
+        /// AI pane to run (from AXEL.md panes section)
+        pane: String,
+
+        /// Prompt to run (default: the pane's prompt)
+        prompt: Option<String>,
+
+        /// Read the prompt from a file (`-` for stdin)
+        #[arg(long, value_name = "PATH", conflicts_with = "prompt")]
+        prompt_file: Option<String>,
+    },
+
+    /// Run every pane of a grid once, headless, and report the results.
+    ///
+    /// Panes run one after the other without tmux: AI panes run their
+    /// prompt non-interactively (like `axel run`) and custom panes run their
+    /// `command`. Panes with nothing to run are skipped. Output is captured,
+    /// panes running past the timeout or over their `budget:` are killed, and
+    /// the results can be written as JUnit XML or JSON. Exits non-zero if any
+    /// pane fails.
+    Ci {
+        /// Grid to run (from AXEL.md grids section)
+        #[arg(default_value = "default")]
+        grid: String,
+
+        /// Seconds each pane may run before it is killed
+        #[arg(long, default_value = "600")]
+        timeout: u64,
+
+        /// Agentic turns for AI panes without their own `max_turns` or
+        /// budget of turns
+        #[arg(long, value_name = "N")]
+        max_turns: Option<u32>,
+
+        /// Write a JUnit XML report
+        #[arg(long, value_name = "PATH")]
+        junit: Option<PathBuf>,
+
+        /// Write a JSON report
+        #[arg(long, value_name = "PATH")]
+        json: Option<PathBuf>,
+    },
+
+    /// Manage named prompts (list, show, create).
+    ///
+    /// Panes reference a named prompt with `prompt: "@name"`. Prompts come
+    /// from the manifest's `prompts:` section and from
+    /// `~/.config/axel/prompts/<name>.md`; the manifest wins on name clashes.
+    #[command(visible_alias = "prompts")]
+    Prompt {
+        #[command(subcommand)]
+        action: PromptCommands,
+    },
+
     /// Query available layouts from workspace AXEL.md.
     ///
     /// Lists pane definitions and grid layouts from the manifest file.
@@ -176,6 +421,161 @@ pub enum Commands {
         action: LayoutCommands,
     },
 
+    /// Print where a driver installs skills for this workspace.
+    ///
+    /// Intended for editors and GUIs that need to locate installed skills.
+    WhereSkills {
+        /// Driver name (claude, codex, opencode, antigravity)
+        driver: String,
+    },
+
+    /// Migrate an old-format manifest to the current schema.
+    ///
+    /// Rewrites legacy `shells:`, `profiles:` and `agents:` sections to
+    /// `layouts.panes`, `layouts.grids` and `skills`, reporting each change.
+    /// A plain `barrel.yaml` next to the manifest path is converted to `AXEL.md`.
+    /// The original file is kept as a `.bak` backup.
+    UpgradeConfig {
+        /// Print the migrated manifest instead of writing it
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Check the manifest for problems.
+    Config {
+        #[command(subcommand)]
+        action: ConfigCommands,
+    },
+
+    /// Check that the environment is ready for axel.
+    ///
+    /// Checks the tmux version and the features axel turns on
+    /// (`allow-passthrough`, `extended-keys`), the claude, codex and opencode
+    /// CLIs, that the config directories are writable, that the event
+    /// server's port is free and that the manifest is valid, printing a fix
+    /// for each problem. Exits non-zero if any errors are found.
+    Doctor {
+        /// Event server port to check
+        #[arg(short, long, default_value = "4318")]
+        port: u16,
+    },
+
+    /// Show a summary of the current workspace.
+    ///
+    /// Its running session, each pane's foreground command, last hook event
+    /// and token usage, the skills installed for each driver, the event
+    /// server's port and the checkout's branch and worktree.
+    Status {
+        /// Output in JSON format (for programmatic access)
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Print a shell completion script.
+    ///
+    /// Completes subcommands and flags, plus registered workspaces, running
+    /// sessions, skills and grids. For example, add
+    /// `source <(axel completions bash)` to `~/.bashrc`, or write
+    /// `axel completions fish` to `~/.config/fish/completions/axel.fish`.
+    Completions {
+        /// Shell to complete in
+        #[arg(value_enum)]
+        shell: CompletionShell,
+    },
+
+    /// Show token usage and estimated cost of the workspace's AI panes.
+    ///
+    /// Totals the OTEL token counters recorded in the event log per pane and
+    /// model. Prices default to published list prices and can be overridden
+    /// in `~/.config/axel/pricing.toml`.
+    Usage {
+        /// Path to the JSONL event log (defaults to `.axel/events.jsonl` in the workspace)
+        #[arg(short, long)]
+        log: Option<PathBuf>,
+
+        /// Output in JSON format (for programmatic access)
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Review the files agents changed, with their diffs.
+    ///
+    /// The event server captures the git diff of every file a Claude pane
+    /// edits or writes and logs it as a `file_diff` event. Lists each file
+    /// changed with the last diff captured of it: everything changed since
+    /// the last commit.
+    Audit {
+        /// Only files changed by this pane
+        #[arg(short, long)]
+        pane: Option<String>,
+
+        /// Only changes at or after this time (RFC 3339)
+        #[arg(long, value_name = "TIME")]
+        since: Option<chrono::DateTime<chrono::Utc>>,
+
+        /// List the files changed without their diffs
+        #[arg(long)]
+        stat: bool,
+
+        /// Path to the JSONL event log (defaults to `.axel/events.jsonl` in the workspace)
+        #[arg(short, long)]
+        log: Option<PathBuf>,
+
+        /// Output in JSON format (for programmatic access)
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Roll back agents' turns from their checkpoints.
+    ///
+    /// With `checkpoints: true` in the manifest, the event server commits
+    /// the working tree to `refs/axel/checkpoints/<branch>` each time an AI
+    /// pane finishes a turn, without touching the branch or the index.
+    #[command(visible_alias = "checkpoint")]
+    Checkpoints {
+        #[command(subcommand)]
+        action: CheckpointCommands,
+    },
+
+    /// Show the recorded output of an AI pane.
+    ///
+    /// Every AI pane's output is recorded to `.axel/transcripts/<pane>.log`
+    /// in the workspace, one timestamped line at a time; each new session
+    /// archives the previous session's. Without a pane, lists the recorded
+    /// transcripts.
+    Transcript {
+        /// Pane name (e.g. claude)
+        pane: Option<String>,
+
+        /// Show an archived transcript: 1 for the previous session, 2 for the
+        /// one before, ...
+        #[arg(long, value_name = "N", requires = "pane", conflicts_with = "follow")]
+        previous: Option<usize>,
+
+        /// Keep printing output as it is recorded
+        #[arg(short, long, requires = "pane")]
+        follow: bool,
+
+        /// Record stdin into this transcript (run by tmux for each AI pane)
+        #[arg(long, value_name = "PATH", hide = true, conflicts_with = "pane")]
+        record: Option<PathBuf>,
+
+        /// Session named in the recording's header
+        #[arg(long, hide = true, requires = "record")]
+        session: Option<String>,
+    },
+
+    /// Open the operator dashboard.
+    ///
+    /// A terminal UI listing axel sessions and their panes, with the event
+    /// server's live events and token usage per pane. Sessions can be
+    /// attached to, sent a prompt, or killed from it.
+    Dash {
+        /// Event server port (default: the first session's server, or 4318)
+        #[arg(short, long)]
+        port: Option<u16>,
+    },
+
     /// Run the axel event server.
     ///
     /// Starts an HTTP server that receives Claude Code hook events and OTEL
@@ -193,6 +593,30 @@ pub enum Commands {
         /// Path to the JSONL log file
         #[arg(short, long, default_value = ".axel/events.jsonl")]
         log: PathBuf,
+
+        /// Also index events in a SQLite database for `GET /events` queries
+        #[arg(long, value_name = "PATH")]
+        db: Option<PathBuf>,
+
+        /// Serve Prometheus metrics on `GET /metrics`
+        #[arg(long)]
+        metrics: bool,
+
+        /// Rotate the log once it grows past this many MB (0 disables)
+        #[arg(long, value_name = "MB", default_value = "100")]
+        rotate_mb: u64,
+
+        /// Also rotate the log after this many hours
+        #[arg(long, value_name = "HOURS")]
+        rotate_hours: Option<u64>,
+
+        /// Keep at most this many compressed log archives
+        #[arg(long, value_name = "N", default_value = "10")]
+        keep_archives: usize,
+
+        /// Delete log archives older than this many days
+        #[arg(long, value_name = "DAYS")]
+        keep_days: Option<u64>,
     },
 }
 
@@ -208,7 +632,11 @@ pub enum SkillCommands {
     /// Shows skill name, location, and description. Local skills override
     /// global skills with the same name.
     #[command(visible_alias = "ls")]
-    List,
+    List {
+        /// Output in JSON format (for programmatic access)
+        #[arg(long)]
+        json: bool,
+    },
 
     /// Create a new skill interactively.
     ///
@@ -246,6 +674,137 @@ pub enum SkillCommands {
         name: String,
     },
 
+    /// Sync a shared skills repository.
+    ///
+    /// Clones the repository into `~/.config/axel/skills/.remotes/<name>`,
+    /// or pulls it if already synced. Its skills (from its `skills/`
+    /// directory, or its root) are then available to every workspace,
+    /// ranked below the manifest's own skill directories. Without a URL,
+    /// pulls every synced repository.
+    Sync {
+        /// Git URL of the skills repository
+        url: Option<String>,
+
+        /// Directory name for the clone (default: the repository name)
+        #[arg(long, requires = "url")]
+        name: Option<String>,
+    },
+
+    /// Compare the workspace's skills against `skills.lock`.
+    ///
+    /// Reports skills whose content changed, that the manifest now uses but
+    /// were never locked, or that were locked but are gone. Exits non-zero
+    /// if any skill differs.
+    Status {
+        /// Output in JSON format (for programmatic access)
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Bundle skills into a tarball for sharing outside git.
+    ///
+    /// Skills are resolved like `axel skill show` does and packed with the
+    /// supporting files of their directory. Install the bundle with
+    /// `axel skill install`.
+    Pack {
+        /// Skills to pack
+        #[arg(required = true)]
+        names: Vec<String>,
+
+        /// Bundle to write
+        #[arg(short, long, default_value = "skills.tar.gz")]
+        output: PathBuf,
+    },
+
+    /// Install the skills of a bundle made with `axel skill pack`.
+    ///
+    /// Skills go to the global skills directory (`~/.config/axel/skills`),
+    /// or to the workspace's `./skills` with `--local`. The bundle is
+    /// validated first; skills that already exist are refused unless
+    /// `--force` is given.
+    Install {
+        /// Bundle to install
+        bundle: PathBuf,
+
+        /// Install into the workspace's `./skills` instead
+        #[arg(long)]
+        local: bool,
+
+        /// Replace skills that already exist
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Show which copy of a skill is used and where it gets installed.
+    ///
+    /// Resolves the skill through the skill directories in priority order,
+    /// then prints its source (and any copies it shadows), frontmatter,
+    /// effective model and tools, and the installed file for each driver
+    /// whose panes use it.
+    Show {
+        /// Skill name
+        name: String,
+
+        /// Also print the skill's content, with `{{var}}` placeholders filled in
+        #[arg(long)]
+        content: bool,
+
+        /// Output in JSON format (for programmatic access)
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Check skills for problems before agents use them.
+    ///
+    /// Validates frontmatter, checks `tools:` against the drivers whose
+    /// panes use each skill, flags prompts that take a large share of the
+    /// model's context window, duplicate skill names across directories and
+    /// unknown `includes:`. Exits non-zero on errors (or, with `--strict`,
+    /// on warnings), for use in CI.
+    Lint {
+        /// Skills to lint (default: every skill in the skill directories)
+        names: Vec<String>,
+
+        /// Fail on warnings as well as errors
+        #[arg(long)]
+        strict: bool,
+
+        /// Output in JSON format (for programmatic access)
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Re-lock the workspace's skills as they are now.
+    ///
+    /// Rewrites `skills.lock` next to AXEL.md with the current content hash
+    /// of every skill the manifest uses.
+    Update,
+
+    /// Show the skills a skill includes via its `includes:` frontmatter.
+    ///
+    /// Included skills are installed alongside the skill that lists them.
+    /// Reports include cycles and missing skills.
+    Deps {
+        /// Skill to inspect (default: every skill that has includes)
+        name: Option<String>,
+
+        /// Render the full include tree instead of a flat list
+        #[arg(long)]
+        tree: bool,
+    },
+
+    /// Benchmark skill installation with generated skills.
+    #[command(hide = true)]
+    Bench {
+        /// Number of skills to generate
+        #[arg(short = 'n', long, default_value_t = 500)]
+        count: usize,
+
+        /// Driver to install for
+        #[arg(long, default_value = "claude")]
+        driver: String,
+    },
+
     /// Remove an skill.
     ///
     /// If the skill exists in both local and global locations, prompts
@@ -256,6 +815,31 @@ pub enum SkillCommands {
     },
 }
 
+/// Named prompt subcommands.
+#[derive(Subcommand)]
+pub enum PromptCommands {
+    /// List named prompts with where they are defined.
+    #[command(visible_alias = "ls")]
+    List {
+        /// Output in JSON format (for programmatic access)
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Print the text of a named prompt.
+    Show {
+        /// Prompt name (with or without the leading `@`)
+        name: String,
+    },
+
+    /// Create a global prompt in `~/.config/axel/prompts/` and open it in
+    /// your `$EDITOR`.
+    New {
+        /// Prompt name (letters, digits, `-` and `_`)
+        name: String,
+    },
+}
+
 /// Layout listing subcommands.
 ///
 /// Query available panes and grids defined in the workspace AXEL.md manifest.
@@ -272,19 +856,136 @@ pub enum LayoutCommands {
     },
 }
 
+/// Workspace registry subcommands.
+#[derive(Subcommand)]
+pub enum WorkspaceCommands {
+    /// List known workspaces, most recently used first.
+    #[command(visible_alias = "ls")]
+    List {
+        /// Output in JSON format (for programmatic access)
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+/// Checkpoint subcommands.
+///
+/// Act on the checkpoints of the branch checked out in the current
+/// directory. Checkpoints are named by (a prefix of) their commit.
+#[derive(Subcommand)]
+pub enum CheckpointCommands {
+    /// List checkpoints, newest first, with the pane whose turn they end.
+    #[command(visible_alias = "ls")]
+    List {
+        /// Output in JSON format (for programmatic access)
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Show what a turn changed.
+    Diff {
+        /// Checkpoint of the turn (default: the newest)
+        checkpoint: Option<String>,
+
+        /// List the files changed without their diffs
+        #[arg(long)]
+        stat: bool,
+    },
+
+    /// Put the working tree back as it was after a turn.
+    ///
+    /// The current state is checkpointed first, so the restore can be
+    /// undone by restoring that checkpoint. Files added since are deleted;
+    /// files git ignores, the index and `HEAD` are left alone.
+    Restore {
+        /// Checkpoint to restore
+        checkpoint: String,
+    },
+}
+
+/// Worktree management subcommands.
+///
+/// Worktrees are created as siblings of the repository
+/// (`myproject-feat-auth/` for `feat/auth`), like `axel -w` does.
+#[derive(Subcommand)]
+pub enum WorktreeCommands {
+    /// List worktrees with their branch, axel session and dirty status.
+    #[command(visible_alias = "ls")]
+    List {
+        /// Output in JSON format (for programmatic access)
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Create a worktree for a branch without launching a workspace.
+    ///
+    /// The branch is checked out from the remote if it exists there, or
+    /// created from the default branch otherwise.
+    Add {
+        /// Branch to check out
+        branch: String,
+    },
+
+    /// Remove the worktree for a branch.
+    ///
+    /// Refuses while an axel session is running in it. The branch is kept.
+    Rm {
+        /// Branch whose worktree to remove
+        branch: String,
+
+        /// Remove even if there are uncommitted changes
+        #[arg(short, long)]
+        force: bool,
+    },
+
+    /// Remove worktrees that no axel session is running in.
+    ///
+    /// The main working tree is never touched. Worktrees with uncommitted
+    /// changes are kept unless `--force` is given.
+    Prune {
+        /// Only list the worktrees that would be removed
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Also remove worktrees with uncommitted changes
+        #[arg(short, long)]
+        force: bool,
+
+        /// Skip confirmation prompt
+        #[arg(long = "confirm")]
+        confirm: bool,
+    },
+}
+
+/// Manifest inspection subcommands.
+#[derive(Subcommand)]
+pub enum ConfigCommands {
+    /// Validate AXEL.md and report every problem at once.
+    ///
+    /// Checks for grid cells referencing undefined panes, overlapping cells,
+    /// widths or heights summing over 100%, missing skills, and paths that
+    /// don't exist. Exits non-zero if any errors are found.
+    Validate {
+        /// Output the problems in JSON format (for programmatic access)
+        #[arg(long)]
+        json: bool,
+    },
+}
+
 /// Session management subcommands.
 ///
-/// Manage axel tmux sessions - list running workspaces, create new ones,
+/// Manage axel sessions - list running workspaces, create new ones,
 /// or kill existing sessions.
 #[derive(Subcommand)]
 pub enum SessionCommands {
-    /// List all running axel sessions.
+    /// List all running axel sessions, whatever their backend.
     ///
-    /// Shows session name, working directory, window count, and attachment status.
-    /// Use `--all` to include non-axel tmux sessions.
+    /// Shows session name, working directory, git branch and changed files,
+    /// pane count, and attachment status.
+    /// Use `--all` to include non-axel sessions.
     #[command(visible_alias = "ls")]
     List {
-        /// Show all tmux sessions, not just axel sessions
+        /// Show all sessions, not just axel sessions
         #[arg(short, long)]
         all: bool,
 
@@ -336,10 +1037,23 @@ pub enum SessionCommands {
         #[arg(long, value_name = "TEXT")]
         prompt: Option<String>,
 
+        /// Read the startup prompt from a file (`-` for stdin).
+        #[arg(long, value_name = "PATH", conflicts_with = "prompt")]
+        prompt_file: Option<String>,
+
         /// Create/use git worktree for branch and launch workspace from there.
         #[arg(short = 'w', long = "worktree", value_name = "BRANCH")]
         worktree: Option<String>,
 
+        /// Launch only the named panes from the grid (comma-separated).
+        #[arg(
+            long,
+            value_name = "PANES",
+            value_delimiter = ',',
+            conflicts_with = "pane"
+        )]
+        only: Vec<String>,
+
         /// Create a tmux session even for single pane launch.
         /// By default, single panes run directly without tmux.
         #[arg(long)]
@@ -355,6 +1069,157 @@ pub enum SessionCommands {
         name: String,
     },
 
+    /// Show or set a session's environment variables.
+    ///
+    /// With no variables, prints the axel-managed ones (AXEL_MANIFEST,
+    /// AXEL_PORT, AXEL_PANE_ID). `KEY` prints that variable; `KEY=VALUE` sets it.
+    Env {
+        /// Name of the session
+        name: String,
+
+        /// Variables to print (`KEY`) or set (`KEY=VALUE`)
+        #[arg(value_name = "KEY[=VALUE]")]
+        vars: Vec<String>,
+    },
+
+    /// Send the same input to every AI pane in a session.
+    ///
+    /// The text is pasted into each pane as one input and followed by Enter.
+    Broadcast {
+        /// Text to send (`-` reads it from stdin)
+        text: String,
+
+        /// Session to target (default: current tmux session)
+        #[arg(short, long)]
+        session: Option<String>,
+
+        /// Only send to these drivers or pane names (comma-separated)
+        #[arg(long, value_delimiter = ',')]
+        panes: Vec<String>,
+    },
+
+    /// Move focus to the next AI pane of a session.
+    ///
+    /// Skips shells and other non-AI panes, wrapping around at the end and
+    /// switching windows when needed. Inside a workspace, `prefix Tab` and
+    /// `prefix BTab` (Shift-Tab) do the same.
+    Focus {
+        /// Session to target (default: current tmux session)
+        #[arg(short, long)]
+        session: Option<String>,
+
+        /// Go to the previous AI pane instead
+        #[arg(long)]
+        prev: bool,
+    },
+
+    /// Respawn AI panes whose process exits.
+    ///
+    /// Checks the session's AI panes until the session ends. A pane that
+    /// died, or dropped back to its shell, is restarted with its original
+    /// command via `respawn-pane`. If the session has an event server port,
+    /// a `PaneRespawned` event is posted for each restart.
+    Watch {
+        /// Session to watch (default: current tmux session)
+        #[arg(short, long)]
+        session: Option<String>,
+
+        /// Seconds between checks
+        #[arg(long, default_value_t = 5)]
+        interval: u64,
+    },
+
+    /// Apply AXEL.md changes to a running session.
+    ///
+    /// Re-reads the manifest the session was created from, creates panes
+    /// added since, re-installs skills and refreshes pane titles and colors.
+    /// Nothing is killed; panes no longer in the manifest are only reported.
+    Reload {
+        /// Session to reload (default: current tmux session)
+        #[arg(short, long)]
+        session: Option<String>,
+
+        /// Grid layout the session was launched with
+        #[arg(long, value_name = "GRID")]
+        grid: Option<String>,
+    },
+
+    /// Save a running session to `.axel/snapshot.json`.
+    ///
+    /// Records each window's layout and each pane's title, working
+    /// directory, command and scrollback, so `axel session restore` can
+    /// recreate the workspace later (e.g. after a reboot).
+    Snapshot {
+        /// Session to save (default: current tmux session)
+        #[arg(short, long)]
+        session: Option<String>,
+
+        /// Snapshot file (default: `.axel/snapshot.json` in the workspace)
+        #[arg(short, long, value_name = "PATH")]
+        file: Option<PathBuf>,
+
+        /// Lines of scrollback to keep per pane (0 to skip scrollback)
+        #[arg(long, default_value_t = 2000)]
+        scrollback: usize,
+    },
+
+    /// Recreate a session saved with `axel session snapshot`.
+    ///
+    /// Panes come back in their directories with their scrollback. Panes
+    /// named in the manifest are restarted with their command and skills;
+    /// other programs are not restarted.
+    Restore {
+        /// Snapshot file (default: `.axel/snapshot.json` in the workspace)
+        #[arg(short, long, value_name = "PATH")]
+        file: Option<PathBuf>,
+
+        /// Name for the restored session (default: the saved session's name)
+        #[arg(long, value_name = "NAME")]
+        session_name: Option<String>,
+    },
+
+    /// Record a session's panes as asciicast files.
+    ///
+    /// Each pane's output is recorded with its timing to
+    /// `.axel/recordings/<session>-<pane>-<time>.cast` in the workspace,
+    /// until `--stop`. Recordings play back with `axel session play` or
+    /// asciinema.
+    Record {
+        /// Session to record (default: current tmux session)
+        name: Option<String>,
+
+        /// Only record these drivers or pane names (comma-separated;
+        /// default: the AI panes)
+        #[arg(long, value_delimiter = ',')]
+        panes: Vec<String>,
+
+        /// Stop recording
+        #[arg(long)]
+        stop: bool,
+
+        /// Record stdin into this recording (run by tmux for each pane)
+        #[arg(long, value_name = "PATH", hide = true, requires = "name", conflicts_with_all = ["panes", "stop"])]
+        pipe: Option<PathBuf>,
+
+        /// Transcript to keep writing while recording
+        #[arg(long, value_name = "PATH", hide = true, requires = "pipe")]
+        transcript: Option<PathBuf>,
+    },
+
+    /// Replay a recording made with `axel session record`.
+    Play {
+        /// Recording (.cast file)
+        file: PathBuf,
+
+        /// Playback speed multiplier
+        #[arg(long, default_value_t = 1.0)]
+        speed: f64,
+
+        /// Longest pause between outputs, in seconds
+        #[arg(long, value_name = "SECS")]
+        idle_limit: Option<f64>,
+    },
+
     /// Kill a running workspace session.
     ///
     /// Equivalent to `axel -k <name>`. Terminates all panes, closes the tmux
@@ -0,0 +1,129 @@
+//! Audit command for axel.
+//!
+//! Provides `axel audit`, which lists the files agents changed according to
+//! the `file_diff` events in a workspace's event log, with the last diff
+//! captured of each (see [`axel_core::audit`]).
+
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use axel_core::{
+    audit::{AuditEntry, FILE_DIFF_EVENT, audit},
+    server::{EventQuery, TimestampedEvent},
+};
+use chrono::{DateTime, Utc};
+use colored::Colorize;
+
+use crate::display_path;
+
+/// Print the files changed according to the event log at `log_path`, by
+/// `pane` and since `since` if given, with their diffs unless `stat`
+pub fn show_audit(
+    log_path: &Path,
+    pane: Option<String>,
+    since: Option<DateTime<Utc>>,
+    base_dir: &Path,
+    stat: bool,
+    json: bool,
+) -> Result<()> {
+    let content = match std::fs::read_to_string(log_path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(e) => {
+            return Err(e).with_context(|| format!("Failed to read {}", display_path(log_path)));
+        }
+    };
+    let query = EventQuery {
+        since,
+        pane,
+        event_type: Some(FILE_DIFF_EVENT.to_string()),
+        limit: None,
+    };
+    let events: Vec<TimestampedEvent> = content
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .filter(|event| query.matches(event))
+        .collect();
+    let entries = audit(&events);
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&entries)?);
+        return Ok(());
+    }
+
+    if entries.is_empty() {
+        println!(
+            "{}",
+            format!("No file changes recorded in {}", display_path(log_path)).dimmed()
+        );
+        return Ok(());
+    }
+
+    for (i, entry) in entries.iter().enumerate() {
+        if i > 0 && !stat {
+            println!();
+        }
+        print_header(entry, base_dir);
+        if stat {
+            continue;
+        }
+        if entry.diff.is_empty() {
+            println!("{}", "No changes since the last commit".dimmed());
+        }
+        for line in entry.diff.lines() {
+            println!("{}", color_diff_line(line));
+        }
+    }
+
+    let (added, removed) = entries
+        .iter()
+        .fold((0, 0), |(a, r), e| (a + e.added, r + e.removed));
+    if !stat {
+        println!();
+    }
+    println!(
+        "{} {} file{} changed, {} {}",
+        "Total:".bold(),
+        entries.len(),
+        if entries.len() == 1 { "" } else { "s" },
+        format!("+{}", added).green(),
+        format!("-{}", removed).red()
+    );
+    Ok(())
+}
+
+/// File, panes, number of changes and line counts of an entry
+fn print_header(entry: &AuditEntry, base_dir: &Path) {
+    let path = match entry.file_path.strip_prefix(base_dir) {
+        Ok(relative) => relative.display().to_string(),
+        Err(_) => display_path(&entry.file_path),
+    };
+    println!(
+        "{} {} {} {}",
+        path.bold(),
+        format!(
+            "{}, {} change{}",
+            entry.panes.join(", "),
+            entry.changes,
+            if entry.changes == 1 { "" } else { "s" }
+        )
+        .dimmed(),
+        format!("+{}", entry.added).green(),
+        format!("-{}", entry.removed).red()
+    );
+}
+
+/// A line of a unified diff, colored like `git diff`
+fn color_diff_line(line: &str) -> String {
+    if line.starts_with("+++") || line.starts_with("---") || line.starts_with("diff ") {
+        line.bold().to_string()
+    } else if line.starts_with("@@") {
+        line.cyan().to_string()
+    } else if line.starts_with('+') {
+        line.green().to_string()
+    } else if line.starts_with('-') {
+        line.red().to_string()
+    } else {
+        line.to_string()
+    }
+}
@@ -0,0 +1,108 @@
+//! Checkpoint commands for axel.
+//!
+//! Provides `axel checkpoints ls|diff|restore` over the checkpoint commits
+//! the event server makes of agents' turns in the current checkout (see
+//! [`axel_core::checkpoint`]).
+
+use std::{path::PathBuf, process::Command};
+
+use anyhow::{Context, Result, bail};
+use axel_core::checkpoint::{find_checkpoint, list_checkpoints, restore_checkpoint, short};
+use chrono::Local;
+use colored::Colorize;
+
+/// Directory of the checkout the commands act on
+fn current_dir() -> Result<PathBuf> {
+    std::env::current_dir().context("Failed to read the current directory")
+}
+
+/// List the checkpoints of the current branch, newest first
+pub fn checkpoint_list(json_output: bool) -> Result<()> {
+    let checkpoints = list_checkpoints(&current_dir()?)?;
+
+    if json_output {
+        println!("{}", serde_json::to_string_pretty(&checkpoints)?);
+        return Ok(());
+    }
+
+    if checkpoints.is_empty() {
+        println!(
+            "{}",
+            "No checkpoints; set checkpoints: true in the manifest to make them".dimmed()
+        );
+        return Ok(());
+    }
+
+    use comfy_table::{Table, presets::NOTHING};
+
+    let mut table = Table::new();
+    table.load_preset(NOTHING);
+
+    for checkpoint in &checkpoints {
+        table.add_row(vec![
+            short(&checkpoint.commit).yellow().to_string(),
+            checkpoint
+                .time
+                .with_timezone(&Local)
+                .format("%Y-%m-%d %H:%M:%S")
+                .to_string()
+                .dimmed()
+                .to_string(),
+            checkpoint.label.blue().to_string(),
+        ]);
+    }
+
+    println!("{table}");
+
+    Ok(())
+}
+
+/// Show what the turn of `checkpoint` (the newest without one) changed
+pub fn checkpoint_diff(checkpoint: Option<&str>, stat: bool) -> Result<()> {
+    let dir = current_dir()?;
+    let checkpoint = find_checkpoint(&dir, checkpoint)?;
+    let Some(parent) = &checkpoint.parent else {
+        bail!(
+            "Checkpoint {} has no parent to diff against",
+            short(&checkpoint.commit)
+        );
+    };
+
+    let mut command = Command::new("git");
+    command.arg("diff");
+    if stat {
+        command.arg("--stat");
+    }
+    let status = command
+        .args([parent, &checkpoint.commit])
+        .current_dir(&dir)
+        .status()
+        .context("Failed to execute git")?;
+    if !status.success() {
+        bail!("git diff failed");
+    }
+    Ok(())
+}
+
+/// Put the working tree back as it was after the turn of `checkpoint`
+pub fn checkpoint_restore(checkpoint: &str) -> Result<()> {
+    let dir = current_dir()?;
+    let checkpoint = find_checkpoint(&dir, Some(checkpoint))?;
+    let saved = restore_checkpoint(&dir, &checkpoint)?;
+
+    println!(
+        "{} {} {} ({})",
+        "✔".green(),
+        "Restored checkpoint".dimmed(),
+        short(&checkpoint.commit).yellow(),
+        checkpoint.label
+    );
+    if let Some(saved) = saved {
+        println!(
+            "  {} {}",
+            "Undo with axel checkpoints restore".dimmed(),
+            short(&saved.commit)
+        );
+    }
+    Ok(())
+}
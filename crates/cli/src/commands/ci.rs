@@ -0,0 +1,160 @@
+//! Headless CI runs for axel.
+//!
+//! Provides `axel ci`, which runs every pane of a grid once without tmux and
+//! reports the results as JUnit XML or JSON (see `axel_core::ci`).
+
+use std::{path::Path, time::Duration};
+
+use anyhow::{Context, Result, bail};
+use axel_core::{
+    PaneConfig,
+    ci::{CaseResult, CaseStatus, CiReport, apply_budget, run_case},
+    config::{expand_path, load_config},
+    tmux::{install_pane_mcp, one_shot_command},
+};
+use colored::Colorize;
+
+use crate::commands::{
+    session::install_pane_skills,
+    skill::{cleanup_skills, format_cleaned_drivers},
+};
+
+/// Limits applied to every pane of the run
+pub struct CiLimits {
+    /// Time a pane may run before it is killed
+    pub timeout: Duration,
+    /// Agentic turns for AI panes without their own `max_turns` or budget
+    /// of turns
+    pub max_turns: Option<u32>,
+}
+
+/// Run each pane of `grid` in order and report the results.
+///
+/// Fails if any pane fails or times out, after the reports are written.
+pub fn ci(
+    manifest_path: &Path,
+    grid: &str,
+    limits: &CiLimits,
+    junit: Option<&Path>,
+    json: Option<&Path>,
+    skill_vars: &[String],
+) -> Result<()> {
+    let mut config = load_config(manifest_path)?;
+    config.set_skills_vars(skill_vars)?;
+    let mut panes = config.resolve_panes(Some(grid));
+    if panes.is_empty() {
+        bail!("Grid '{}' not found or has no panes", grid);
+    }
+
+    let workspace_dir = config.workspace_dir();
+    let install_dir = match &workspace_dir {
+        Some(dir) => dir.clone(),
+        None => std::env::current_dir()?,
+    };
+
+    let mut report = CiReport {
+        grid: grid.to_string(),
+        cases: Vec::new(),
+    };
+    for pane in &mut panes {
+        // The budget can add flags to the command, so it's applied first
+        let budget = apply_budget(&mut pane.config, limits.max_turns);
+        let command = match &pane.config {
+            PaneConfig::Custom(c) => c.command.clone().context("no command to run"),
+            PaneConfig::Claude(c)
+            | PaneConfig::Codex(c)
+            | PaneConfig::Opencode(c)
+            | PaneConfig::Antigravity(c)
+            | PaneConfig::External(c) => {
+                c.prompt
+                    .as_deref()
+                    .context("no prompt to run")
+                    .and_then(|prompt| {
+                        one_shot_command(&pane.config, workspace_dir.as_deref(), prompt)
+                    })
+            }
+        };
+        let (command, budget) = match command.and_then(|command| Ok((command, budget?))) {
+            Ok(run) => run,
+            Err(e) => {
+                eprintln!(
+                    "{} {} {}",
+                    "-".dimmed(),
+                    pane.name,
+                    format!("({})", e).dimmed()
+                );
+                report
+                    .cases
+                    .push(CaseResult::skipped(&pane.name, e.to_string()));
+                continue;
+            }
+        };
+
+        install_pane_skills(&config, &pane.config, &install_dir);
+        if let Some(ref dir) = workspace_dir {
+            install_pane_mcp(&pane.config, dir);
+        }
+
+        eprintln!("{} {}", "▶".cyan(), pane.name.bold());
+        let dir = pane
+            .path()
+            .map(expand_path)
+            .unwrap_or_else(|| ".".to_string());
+        let case = run_case(
+            &pane.name,
+            &command,
+            Path::new(&dir),
+            limits.timeout,
+            budget.as_ref(),
+        );
+        print_case(&case);
+        report.cases.push(case);
+    }
+
+    let cleaned = cleanup_skills(&install_dir);
+    if !cleaned.is_empty() {
+        eprintln!(
+            "{} {} {} artifacts",
+            "✔".green(),
+            "Cleaned".dimmed(),
+            format_cleaned_drivers(&cleaned)
+        );
+    }
+
+    if let Some(path) = junit {
+        std::fs::write(path, report.to_junit())
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+    }
+    if let Some(path) = json {
+        std::fs::write(path, serde_json::to_string_pretty(&report)? + "\n")
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+    }
+
+    let failed = report.count(CaseStatus::Failed) + report.count(CaseStatus::TimedOut);
+    eprintln!(
+        "\n{} passed, {} failed, {} skipped",
+        report.count(CaseStatus::Passed),
+        failed,
+        report.count(CaseStatus::Skipped)
+    );
+    if !report.passed() {
+        bail!("{} of {} panes failed", failed, report.cases.len());
+    }
+    Ok(())
+}
+
+/// Print the outcome of a pane, with its output when it failed
+fn print_case(case: &CaseResult) {
+    let time = format!("({:.1}s)", case.duration_secs).dimmed();
+    let message = case.message.as_deref().unwrap_or_default();
+    match case.status {
+        CaseStatus::Passed => eprintln!("{} {} {}", "✔".green(), case.name, time),
+        CaseStatus::Skipped => {}
+        CaseStatus::Failed | CaseStatus::TimedOut => {
+            eprintln!("{} {} {} {}", "✘".red(), case.name, message.red(), time);
+            for line in case.output.lines() {
+                eprintln!("  {}", line.dimmed());
+            }
+        }
+    }
+}
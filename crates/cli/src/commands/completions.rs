@@ -0,0 +1,242 @@
+//! Shell completions for axel.
+//!
+//! `axel completions <shell>` prints clap_complete's registration script for
+//! bash, zsh or fish. The script calls back into axel with `COMPLETE` set,
+//! and clap_complete answers from the clap definition of the CLI:
+//! subcommands, flags and possible values. The only custom part is names
+//! known at runtime, which [`command`] attaches to their arguments:
+//! registered workspaces, running sessions, the workspace's skills and
+//! grids.
+
+use std::path::Path;
+
+use axel_core::{backend::list_all_sessions, config::load_config, registry::Registry};
+use clap::{Command, CommandFactory, ValueEnum};
+use clap_complete::{
+    engine::{ArgValueCandidates, CompletionCandidate},
+    env::{Bash, EnvCompleter, Fish, Zsh},
+};
+
+use crate::{cli::Cli, commands::skill::skill_names};
+
+/// Environment variable the registration scripts set to request completions
+pub const COMPLETE_VAR: &str = "COMPLETE";
+
+/// Shells `axel completions` writes scripts for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum CompletionShell {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+/// Print the completion script for `shell`
+pub fn completions(shell: CompletionShell) -> anyhow::Result<()> {
+    let completer: &dyn EnvCompleter = match shell {
+        CompletionShell::Bash => &Bash,
+        CompletionShell::Zsh => &Zsh,
+        CompletionShell::Fish => &Fish,
+    };
+    completer.write_registration(COMPLETE_VAR, "axel", "axel", "axel", &mut std::io::stdout())?;
+    Ok(())
+}
+
+/// Names only known at runtime, completed for some arguments
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Dynamic {
+    Workspaces,
+    Sessions,
+    Skills,
+    Grids,
+}
+
+/// Runtime names completed for argument `arg` of the subcommand at `path`
+/// (`["skill", "show"]`, empty for `axel` itself)
+fn dynamic_values(path: &[String], arg: &str) -> Option<Dynamic> {
+    let path: Vec<&str> = path.iter().map(String::as_str).collect();
+    match (path.as_slice(), arg) {
+        (_, "grid") => Some(Dynamic::Grids),
+        (_, "session") | ([], "kill") => Some(Dynamic::Sessions),
+        (["open"], "name") => Some(Dynamic::Workspaces),
+        (["attach"], "name") | (["session", "join" | "kill" | "env" | "record"], "name") => {
+            Some(Dynamic::Sessions)
+        }
+        (["skill", "show" | "fork" | "link" | "rm" | "deps"], "name")
+        | (["skill", "pack" | "lint"], "names") => Some(Dynamic::Skills),
+        _ => None,
+    }
+}
+
+/// Current names for `kind`; failures just mean nothing to complete
+fn dynamic_names(kind: Dynamic, manifest_path: &Path, base_dir: &Path) -> Vec<String> {
+    match kind {
+        Dynamic::Workspaces => axel_core::registry::registry_path()
+            .and_then(|path| Registry::load(&path).ok())
+            .map(|registry| {
+                registry
+                    .workspaces
+                    .into_iter()
+                    .filter(|w| w.manifest.exists())
+                    .map(|w| w.name)
+                    .collect()
+            })
+            .unwrap_or_default(),
+        Dynamic::Sessions => list_all_sessions()
+            .into_iter()
+            .filter(|session| session.working_dir.is_some())
+            .map(|session| session.name)
+            .collect(),
+        Dynamic::Skills => skill_names(manifest_path, base_dir),
+        Dynamic::Grids => load_config(manifest_path)
+            .map(|config| {
+                let mut grids: Vec<String> = config.layouts.grids.into_keys().collect();
+                grids.sort();
+                grids
+            })
+            .unwrap_or_default(),
+    }
+}
+
+/// The CLI definition, with runtime names of the workspace at
+/// `manifest_path` attached to the arguments that take them
+pub fn command(manifest_path: &Path, base_dir: &Path) -> Command {
+    with_dynamic(Cli::command(), &[], manifest_path, base_dir)
+}
+
+fn with_dynamic(
+    command: Command,
+    path: &[String],
+    manifest_path: &Path,
+    base_dir: &Path,
+) -> Command {
+    command
+        .mut_args(|arg| match dynamic_values(path, arg.get_id().as_str()) {
+            Some(kind) => {
+                let (manifest_path, base_dir) =
+                    (manifest_path.to_path_buf(), base_dir.to_path_buf());
+                arg.add(ArgValueCandidates::new(move || {
+                    dynamic_names(kind, &manifest_path, &base_dir)
+                        .into_iter()
+                        .map(CompletionCandidate::new)
+                        .collect()
+                }))
+            }
+            None => arg,
+        })
+        .mut_subcommands(|sub| {
+            let mut path = path.to_vec();
+            path.push(sub.get_name().to_string());
+            with_dynamic(sub, &path, manifest_path, base_dir)
+        })
+}
+
+/// `--manifest-path` of the command line being completed, which the
+/// registration scripts pass after `--`
+pub fn completing_manifest_path() -> Option<String> {
+    let mut args = std::env::args().skip_while(|arg| arg != "--").skip(2);
+    while let Some(arg) = args.next() {
+        if arg == "-m" || arg == "--manifest-path" {
+            return args.next();
+        }
+        if let Some(path) = arg.strip_prefix("--manifest-path=") {
+            return Some(path.to_string());
+        }
+    }
+    None
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+
+    /// Completions for the last of `words`, the command line after `axel`
+    /// with the word being completed last
+    fn complete(words: &[&str], manifest_path: &Path) -> Vec<String> {
+        let base_dir = manifest_path.parent().unwrap();
+        let args: Vec<std::ffi::OsString> = std::iter::once("axel")
+            .chain(words.iter().copied())
+            .map(Into::into)
+            .collect();
+        let index = args.len() - 1;
+        clap_complete::engine::complete(&mut command(manifest_path, base_dir), args, index, None)
+            .unwrap()
+            .into_iter()
+            .map(|candidate| candidate.get_value().to_string_lossy().into_owned())
+            .collect()
+    }
+
+    fn manifest(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(name);
+        std::fs::remove_dir_all(&dir).ok();
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("AXEL.md");
+        std::fs::write(
+            &path,
+            r#"---
+workspace: demo
+layouts:
+  panes:
+    - type: claude
+    - type: shell
+  grids:
+    default:
+      claude:
+        col: 0
+      shell:
+        col: 1
+    review:
+      claude:
+        col: 0
+---
+"#,
+        )
+        .unwrap();
+        path
+    }
+
+    #[test]
+    fn test_complete_subcommands() {
+        let path = manifest("axel-test-complete-subcommands");
+
+        let candidates = complete(&["sk"], &path);
+        assert_eq!(candidates, vec!["skill"]);
+        let candidates = complete(&["skill", "f"], &path);
+        assert_eq!(candidates, vec!["fork"]);
+        assert!(!complete(&[""], &path).iter().any(|c| c.starts_with("__")));
+
+        std::fs::remove_dir_all(path.parent().unwrap()).ok();
+    }
+
+    #[test]
+    fn test_complete_flags() {
+        let path = manifest("axel-test-complete-flags");
+
+        let candidates = complete(&["--manifest-"], &path);
+        assert_eq!(candidates, vec!["--manifest-path"]);
+        let candidates = complete(&["completions", "--"], &path);
+        assert!(candidates.contains(&"--help".to_string()));
+        let candidates = complete(&["completions", ""], &path);
+        assert_eq!(candidates[..3], ["bash", "zsh", "fish"]);
+
+        std::fs::remove_dir_all(path.parent().unwrap()).ok();
+    }
+
+    #[test]
+    fn test_complete_grid_names() {
+        let path = manifest("axel-test-complete-grids");
+
+        let candidates = complete(&["ci", ""], &path);
+        assert_eq!(candidates[..2], ["default", "review"]);
+        assert!(candidates[2..].iter().all(|c| c.starts_with("--")));
+        let candidates = complete(&["session", "new", "--grid", "re"], &path);
+        assert_eq!(candidates, vec!["review"]);
+
+        std::fs::remove_dir_all(path.parent().unwrap()).ok();
+    }
+}
@@ -0,0 +1,160 @@
+//! Manifest maintenance commands for axel.
+//!
+//! Provides `axel upgrade-config`, which migrates legacy manifests
+//! (`shells:`/`profiles:`/`agents:`) to the current schema, and
+//! `axel config validate`, which reports every problem in a manifest.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Result, bail};
+use axel_core::{
+    Error,
+    config::{
+        Severity, YAML_MANIFEST_FILE, is_yaml_manifest, migrate_manifest, migrate_yaml_manifest,
+        validate_manifest,
+    },
+};
+use colored::Colorize;
+
+use crate::display_path;
+
+/// Legacy plain-YAML manifest name, picked up when no AXEL.md exists
+const LEGACY_MANIFEST: &str = "barrel.yaml";
+
+/// Migrate the manifest at `manifest_path` (or a sibling `barrel.yaml`) to
+/// the current schema, reporting each transformation.
+pub fn upgrade_config(manifest_path: &Path, dry_run: bool) -> Result<()> {
+    let source = find_source(manifest_path)?;
+    let content = std::fs::read_to_string(&source)?;
+    // A legacy plain-YAML manifest becomes an AXEL.md; axel.yaml stays YAML
+    let keep_yaml = source.file_name().is_some_and(|n| n == YAML_MANIFEST_FILE);
+    let migration = if keep_yaml {
+        migrate_yaml_manifest(&content)?
+    } else {
+        migrate_manifest(&content)?
+    };
+
+    if migration.is_up_to_date() {
+        eprintln!(
+            "{} {} is already up to date",
+            "✔".green(),
+            display_path(&source)
+        );
+        return Ok(());
+    }
+
+    for change in &migration.changes {
+        eprintln!("{} {}", "✔".green(), change);
+    }
+    for warning in &migration.warnings {
+        eprintln!("{} {}", "⚠".yellow(), warning);
+    }
+
+    if dry_run {
+        print!("{}", migration.content);
+        return Ok(());
+    }
+
+    let is_yaml = !keep_yaml && is_yaml_manifest(&source);
+    let target = if is_yaml {
+        source.with_file_name("AXEL.md")
+    } else {
+        source.clone()
+    };
+    if is_yaml && target.exists() {
+        anyhow::bail!(
+            "{} already exists; remove it or pass -m {} to migrate it instead",
+            display_path(&target),
+            display_path(&target)
+        );
+    }
+
+    let mut backup = source.clone().into_os_string();
+    backup.push(".bak");
+    let backup = PathBuf::from(backup);
+    std::fs::copy(&source, &backup)?;
+    if is_yaml {
+        std::fs::remove_file(&source)?;
+    }
+    std::fs::write(&target, &migration.content)?;
+
+    eprintln!(
+        "{} {} {} (backup at {})",
+        "✔".green(),
+        "Wrote".dimmed(),
+        display_path(&target),
+        display_path(&backup)
+    );
+    Ok(())
+}
+
+/// Resolve the file to migrate: the manifest itself, or a legacy
+/// `barrel.yaml` in the same directory when no AXEL.md exists yet.
+fn find_source(manifest_path: &Path) -> Result<PathBuf> {
+    if manifest_path.exists() {
+        return Ok(manifest_path.to_path_buf());
+    }
+    let legacy = manifest_path.with_file_name(LEGACY_MANIFEST);
+    if legacy.exists() {
+        return Ok(legacy);
+    }
+    anyhow::bail!(
+        "No manifest found at {} (or {})",
+        display_path(manifest_path),
+        display_path(&legacy)
+    )
+}
+
+/// Validate the manifest, printing every problem found.
+///
+/// With `json_output`, prints the problems as a JSON array instead.
+/// Fails with [`Error::InvalidManifest`] if any errors (not just warnings)
+/// are reported.
+pub fn validate_config(manifest_path: &Path, json_output: bool) -> Result<()> {
+    if !manifest_path.exists() {
+        anyhow::bail!("No manifest found at {}", display_path(manifest_path));
+    }
+    let issues = validate_manifest(manifest_path)?;
+    let errors = issues
+        .iter()
+        .filter(|i| i.severity == Severity::Error)
+        .count();
+
+    if json_output {
+        println!("{}", serde_json::to_string_pretty(&issues)?);
+        if errors > 0 {
+            bail!(Error::InvalidManifest(errors));
+        }
+        return Ok(());
+    }
+
+    if issues.is_empty() {
+        eprintln!("{} {} is valid", "✔".green(), display_path(manifest_path));
+        return Ok(());
+    }
+
+    for issue in &issues {
+        match issue.severity {
+            Severity::Error => eprintln!("{} {}", "✘".red(), issue),
+            Severity::Warning => eprintln!("{} {}", "⚠".yellow(), issue),
+        }
+    }
+
+    let warnings = issues.len() - errors;
+    eprintln!(
+        "{}",
+        format!(
+            "{} error{}, {} warning{} in {}",
+            errors,
+            if errors == 1 { "" } else { "s" },
+            warnings,
+            if warnings == 1 { "" } else { "s" },
+            display_path(manifest_path)
+        )
+        .dimmed()
+    );
+    if errors > 0 {
+        bail!(Error::InvalidManifest(errors));
+    }
+    Ok(())
+}
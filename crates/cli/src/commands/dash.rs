@@ -0,0 +1,550 @@
+//! Operator dashboard for axel.
+//!
+//! `axel dash` shows the running axel sessions with their panes, follows
+//! the event server's SSE stream and shows token usage per pane, all in one
+//! terminal view. From there a session can be attached to, sent a prompt,
+//! or killed.
+//!
+//! Keys: ↑/↓ (or j/k) select a session, Enter attach, p send a prompt to
+//! its AI panes, x kill it, q quit.
+
+use std::{
+    collections::VecDeque,
+    path::{Path, PathBuf},
+    sync::mpsc::{self, Receiver, Sender},
+    time::{Duration, Instant},
+};
+
+use anyhow::Result;
+use axel_core::{
+    config::load_config,
+    server::{ServerClient, ServerConfig, TimestampedEvent, UsageReport},
+    tmux::{
+        AXEL_MANIFEST_ENV, PaneStatus, SessionInfo, attach_session, broadcast_targets,
+        detach_session, get_environment, has_session, kill_session, list_pane_status, list_panes,
+        list_sessions, send_text,
+    },
+};
+use ratatui::{
+    DefaultTerminal, Frame,
+    crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers},
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, List, ListItem, ListState, Paragraph, Row, Table},
+};
+
+use crate::commands::skill::cleanup_skills;
+
+/// Events kept for the event log
+const EVENT_HISTORY: usize = 200;
+
+/// How often sessions, panes and usage are refreshed
+const REFRESH_INTERVAL: Duration = Duration::from_secs(2);
+
+/// How long to wait before reconnecting to the event server
+const RECONNECT_INTERVAL: Duration = Duration::from_secs(3);
+
+/// How long to wait for a key press before redrawing
+const TICK: Duration = Duration::from_millis(250);
+
+/// Run the dashboard until the user quits.
+///
+/// Events and usage come from the server on `port`; without one, from the
+/// first session that runs a server, or the default port.
+pub fn dash(port: Option<u16>) -> Result<()> {
+    let port = port
+        .or_else(|| {
+            list_sessions(true)
+                .unwrap_or_default()
+                .iter()
+                .find_map(|session| session.port)
+        })
+        .unwrap_or(ServerConfig::default().port);
+    let client = ServerClient::new(port);
+
+    let (tx, rx) = mpsc::channel();
+    std::thread::spawn(move || follow_events(client, tx));
+
+    let mut dashboard = Dashboard::new(client);
+    dashboard.refresh();
+
+    let mut terminal = ratatui::init();
+    let result = dashboard.run(&mut terminal, &rx);
+    ratatui::restore();
+    result
+}
+
+/// What the event thread reports
+enum Feed {
+    Connected,
+    Event(Box<TimestampedEvent>),
+    Disconnected,
+}
+
+/// Forward server events to the dashboard, reconnecting whenever the
+/// server goes away. Returns once the dashboard has quit.
+fn follow_events(client: ServerClient, tx: Sender<Feed>) {
+    loop {
+        if let Ok(events) = client.events() {
+            if tx.send(Feed::Connected).is_err() {
+                return;
+            }
+            // Events that fail to parse are skipped; a read error ends the stream
+            for event in events {
+                match event {
+                    Ok(event) => {
+                        if tx.send(Feed::Event(Box::new(event))).is_err() {
+                            return;
+                        }
+                    }
+                    Err(e) if e.is::<std::io::Error>() => break,
+                    Err(_) => {}
+                }
+            }
+        }
+        if tx.send(Feed::Disconnected).is_err() {
+            return;
+        }
+        std::thread::sleep(RECONNECT_INTERVAL);
+    }
+}
+
+/// What the footer is doing
+enum Mode {
+    Normal,
+    /// Typing a prompt for the selected session's AI panes
+    Prompt(String),
+    /// Waiting for y/n before killing the selected session
+    ConfirmKill,
+}
+
+/// An action that needs the terminal back
+enum Action {
+    Quit,
+    Attach(String),
+}
+
+struct Dashboard {
+    client: ServerClient,
+    sessions: Vec<SessionInfo>,
+    list: ListState,
+    panes: Vec<PaneStatus>,
+    events: VecDeque<TimestampedEvent>,
+    usage: Option<UsageReport>,
+    connected: bool,
+    mode: Mode,
+    /// Result of the last action, shown in the footer
+    message: Option<String>,
+    refreshed: Instant,
+}
+
+impl Dashboard {
+    fn new(client: ServerClient) -> Self {
+        Self {
+            client,
+            sessions: Vec::new(),
+            list: ListState::default().with_selected(Some(0)),
+            panes: Vec::new(),
+            events: VecDeque::new(),
+            usage: None,
+            connected: false,
+            mode: Mode::Normal,
+            message: None,
+            refreshed: Instant::now(),
+        }
+    }
+
+    fn run(&mut self, terminal: &mut DefaultTerminal, rx: &Receiver<Feed>) -> Result<()> {
+        loop {
+            while let Ok(feed) = rx.try_recv() {
+                match feed {
+                    Feed::Connected => self.connected = true,
+                    Feed::Disconnected => self.connected = false,
+                    Feed::Event(event) => {
+                        self.events.push_front(*event);
+                        self.events.truncate(EVENT_HISTORY);
+                    }
+                }
+            }
+            if self.refreshed.elapsed() >= REFRESH_INTERVAL {
+                self.refresh();
+            }
+
+            terminal.draw(|frame| self.draw(frame))?;
+
+            if !event::poll(TICK)? {
+                continue;
+            }
+            let Event::Key(key) = event::read()? else {
+                continue;
+            };
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+            if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+                return Ok(());
+            }
+
+            match self.handle_key(key.code) {
+                Some(Action::Quit) => return Ok(()),
+                Some(Action::Attach(name)) => {
+                    // Hand the terminal to tmux until the user detaches
+                    ratatui::restore();
+                    let attached = attach_session(&name);
+                    *terminal = ratatui::init();
+                    self.message = Some(match attached {
+                        Ok(()) => format!("Detached from {}", name),
+                        Err(e) => format!("Failed to attach to {}: {}", name, e),
+                    });
+                    self.refresh();
+                }
+                None => {}
+            }
+        }
+    }
+
+    fn selected(&self) -> Option<&SessionInfo> {
+        self.sessions.get(self.list.selected()?)
+    }
+
+    /// Reload sessions, the selected session's panes and token usage
+    fn refresh(&mut self) {
+        let selected = self.selected().map(|session| session.name.clone());
+        self.sessions = list_sessions(true).unwrap_or_default();
+        let index = selected
+            .and_then(|name| self.sessions.iter().position(|s| s.name == name))
+            .unwrap_or(0)
+            .min(self.sessions.len().saturating_sub(1));
+        self.list.select(Some(index));
+        self.load_panes();
+        self.usage = self.client.usage().ok();
+        self.refreshed = Instant::now();
+    }
+
+    fn load_panes(&mut self) {
+        self.panes = self
+            .selected()
+            .and_then(|session| list_pane_status(&session.name).ok())
+            .unwrap_or_default();
+    }
+
+    fn select(&mut self, offset: isize) {
+        if self.sessions.is_empty() {
+            return;
+        }
+        let current = self.list.selected().unwrap_or(0) as isize;
+        let last = self.sessions.len() as isize - 1;
+        self.list
+            .select(Some((current + offset).clamp(0, last) as usize));
+        self.load_panes();
+    }
+
+    fn handle_key(&mut self, code: KeyCode) -> Option<Action> {
+        match &mut self.mode {
+            Mode::Prompt(text) => match code {
+                KeyCode::Enter => {
+                    let text = std::mem::take(text);
+                    self.mode = Mode::Normal;
+                    if !text.trim().is_empty() {
+                        self.send_prompt(&text);
+                    }
+                }
+                KeyCode::Esc => self.mode = Mode::Normal,
+                KeyCode::Backspace => {
+                    text.pop();
+                }
+                KeyCode::Char(c) => text.push(c),
+                _ => {}
+            },
+            Mode::ConfirmKill => {
+                self.mode = Mode::Normal;
+                if matches!(code, KeyCode::Char('y') | KeyCode::Char('Y')) {
+                    self.kill_selected();
+                }
+            }
+            Mode::Normal => {
+                self.message = None;
+                match code {
+                    KeyCode::Char('q') | KeyCode::Esc => return Some(Action::Quit),
+                    KeyCode::Up | KeyCode::Char('k') => self.select(-1),
+                    KeyCode::Down | KeyCode::Char('j') => self.select(1),
+                    KeyCode::Enter | KeyCode::Char('a') => {
+                        return self
+                            .selected()
+                            .map(|session| Action::Attach(session.name.clone()));
+                    }
+                    KeyCode::Char('p') if self.selected().is_some() => {
+                        self.mode = Mode::Prompt(String::new());
+                    }
+                    KeyCode::Char('x') if self.selected().is_some() => {
+                        self.mode = Mode::ConfirmKill;
+                    }
+                    KeyCode::Char('r') => self.refresh(),
+                    _ => {}
+                }
+            }
+        }
+        None
+    }
+
+    /// Send `text` to the AI panes of the selected session, like
+    /// `axel session broadcast`
+    fn send_prompt(&mut self, text: &str) {
+        let Some(name) = self.selected().map(|session| session.name.clone()) else {
+            return;
+        };
+        let config = get_environment(&name, AXEL_MANIFEST_ENV)
+            .and_then(|manifest| load_config(Path::new(&manifest)).ok());
+        let panes = list_panes(&name).unwrap_or_default();
+        let targets = broadcast_targets(&panes, config.as_ref(), &[]);
+
+        let sent: Vec<&str> = targets
+            .iter()
+            .filter(|pane| send_text(&pane.id, text).is_ok())
+            .map(|pane| pane.title.as_str())
+            .collect();
+        self.message = Some(if sent.is_empty() {
+            format!("No AI panes to send to in {}", name)
+        } else {
+            format!("Sent to {}", sent.join(", "))
+        });
+    }
+
+    /// Kill the selected session and clean up its skills, like `axel -k`
+    fn kill_selected(&mut self) {
+        let Some(session) = self.selected().cloned() else {
+            return;
+        };
+        detach_session(&session.name).ok();
+        if let Some(dir) = &session.working_dir {
+            cleanup_skills(&PathBuf::from(dir));
+        }
+        self.message = Some(match kill_session(&session.name) {
+            Ok(()) if !has_session(&session.name) => format!("Killed {}", session.name),
+            _ => format!("Failed to kill {}", session.name),
+        });
+        self.refresh();
+    }
+
+    // =========================================================================
+    // Drawing
+    // =========================================================================
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [main, footer] =
+            Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(frame.area());
+        let [left, right] =
+            Layout::horizontal([Constraint::Percentage(30), Constraint::Percentage(70)])
+                .areas(main);
+        let [panes, usage, events] = Layout::vertical([
+            Constraint::Percentage(30),
+            Constraint::Percentage(25),
+            Constraint::Min(0),
+        ])
+        .areas(right);
+
+        self.draw_sessions(frame, left);
+        self.draw_panes(frame, panes);
+        self.draw_usage(frame, usage);
+        self.draw_events(frame, events);
+        self.draw_footer(frame, footer);
+    }
+
+    fn draw_sessions(&mut self, frame: &mut Frame, area: Rect) {
+        let items: Vec<ListItem> = self
+            .sessions
+            .iter()
+            .map(|session| {
+                let mut spans = vec![Span::raw(session.name.clone())];
+                if session.attached {
+                    spans.push(" (attached)".green());
+                }
+                if let Some(git) = &session.git {
+                    spans.push(format!(" {}", git.branch).cyan());
+                }
+                ListItem::new(Line::from(spans))
+            })
+            .collect();
+
+        let block = Block::bordered().title(format!(" Sessions ({}) ", self.sessions.len()));
+        if items.is_empty() {
+            frame.render_widget(
+                Paragraph::new("No axel sessions running".dark_gray()).block(block),
+                area,
+            );
+            return;
+        }
+        let list = List::new(items)
+            .block(block)
+            .highlight_style(Style::new().add_modifier(Modifier::REVERSED))
+            .highlight_symbol("❯ ");
+        frame.render_stateful_widget(list, area, &mut self.list);
+    }
+
+    fn draw_panes(&self, frame: &mut Frame, area: Rect) {
+        let rows = self.panes.iter().map(|pane| {
+            let state = if pane.dead {
+                "exited".red()
+            } else {
+                pane.current_command.clone().green()
+            };
+            Row::new(vec![
+                Span::raw(pane.id.clone()).dark_gray(),
+                Span::raw(pane.title.clone()),
+                Span::raw(pane.window_name.clone()).dark_gray(),
+                state,
+            ])
+        });
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(5),
+                Constraint::Fill(2),
+                Constraint::Fill(1),
+                Constraint::Fill(1),
+            ],
+        )
+        .header(Row::new(["id", "pane", "window", "running"]).dark_gray())
+        .block(Block::bordered().title(" Panes "));
+        frame.render_widget(table, area);
+    }
+
+    fn draw_usage(&self, frame: &mut Frame, area: Rect) {
+        let block = Block::bordered().title(" Token usage ");
+        let Some(usage) = self.usage.as_ref().filter(|usage| !usage.panes.is_empty()) else {
+            let text = if self.usage.is_some() {
+                "No token usage recorded yet"
+            } else {
+                "Event server not reachable"
+            };
+            frame.render_widget(Paragraph::new(text.dark_gray()).block(block), area);
+            return;
+        };
+
+        let mut rows: Vec<Row> = usage
+            .panes
+            .iter()
+            .map(|pane| {
+                Row::new(vec![
+                    self.pane_label(&pane.pane_id),
+                    pane.tokens.input_tokens.to_string(),
+                    pane.tokens.output_tokens.to_string(),
+                    (pane.tokens.cache_read_tokens + pane.tokens.cache_creation_tokens).to_string(),
+                    format!("${:.2}", pane.cost_usd),
+                ])
+            })
+            .collect();
+        rows.push(
+            Row::new(vec![
+                "total".to_string(),
+                usage.tokens.input_tokens.to_string(),
+                usage.tokens.output_tokens.to_string(),
+                (usage.tokens.cache_read_tokens + usage.tokens.cache_creation_tokens).to_string(),
+                format!("${:.2}", usage.cost_usd),
+            ])
+            .bold(),
+        );
+
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Fill(2),
+                Constraint::Fill(1),
+                Constraint::Fill(1),
+                Constraint::Fill(1),
+                Constraint::Fill(1),
+            ],
+        )
+        .header(Row::new(["pane", "input", "output", "cache", "cost"]).dark_gray())
+        .block(block);
+        frame.render_widget(table, area);
+    }
+
+    fn draw_events(&self, frame: &mut Frame, area: Rect) {
+        let status = if self.connected {
+            format!(" Events (port {}) ", self.client.port()).into()
+        } else {
+            Line::from(vec![
+                " Events ".into(),
+                format!("(not connected to port {}) ", self.client.port()).red(),
+            ])
+        };
+        let lines: Vec<Line> = self
+            .events
+            .iter()
+            .take(area.height.saturating_sub(2) as usize)
+            .map(|event| {
+                Line::from(vec![
+                    event
+                        .timestamp
+                        .with_timezone(&chrono::Local)
+                        .format("%H:%M:%S ")
+                        .to_string()
+                        .dark_gray(),
+                    format!("{} ", self.pane_label(&event.pane_id)).yellow(),
+                    Span::styled(
+                        event.event_type.clone(),
+                        Style::new().fg(event_color(&event.event_type)),
+                    ),
+                    format!(" {}", event_detail(event)).dark_gray(),
+                ])
+            })
+            .collect();
+        frame.render_widget(
+            Paragraph::new(lines).block(Block::bordered().title(status)),
+            area,
+        );
+    }
+
+    fn draw_footer(&self, frame: &mut Frame, area: Rect) {
+        let session = self.selected().map(|s| s.name.as_str()).unwrap_or_default();
+        let line = match &self.mode {
+            Mode::Prompt(text) => Line::from(vec![
+                format!("Prompt for {}: ", session).bold(),
+                text.clone().into(),
+                "▏".into(),
+            ]),
+            Mode::ConfirmKill => format!("Kill session '{}'? (y/n)", session).yellow().into(),
+            Mode::Normal => match &self.message {
+                Some(message) => message.clone().into(),
+                None => "↑/↓ select  enter attach  p prompt  x kill  r refresh  q quit"
+                    .dark_gray()
+                    .into(),
+            },
+        };
+        frame.render_widget(Paragraph::new(line), area);
+    }
+
+    /// Session name for an axel pane ID, or its first characters
+    fn pane_label(&self, pane_id: &str) -> String {
+        self.sessions
+            .iter()
+            .find(|session| session.axel_pane_id.as_deref() == Some(pane_id))
+            .map(|session| session.name.clone())
+            .unwrap_or_else(|| pane_id.chars().take(8).collect())
+    }
+}
+
+/// Color for an event type: attention-worthy events stand out
+fn event_color(event_type: &str) -> Color {
+    match event_type {
+        "PermissionRequest" | "Notification" => Color::Yellow,
+        "Stop" | "SubagentStop" | "SessionEnd" => Color::Green,
+        "PaneRespawned" | "unknown_hook" => Color::Red,
+        t if t.starts_with("otel_") => Color::DarkGray,
+        _ => Color::Blue,
+    }
+}
+
+/// Short description of an event's payload, such as the tool it used
+fn event_detail(event: &TimestampedEvent) -> String {
+    ["tool_name", "message", "response_text"]
+        .iter()
+        .find_map(|key| event.event.get(*key).and_then(|v| v.as_str()))
+        .unwrap_or_default()
+        .lines()
+        .next()
+        .unwrap_or_default()
+        .to_string()
+}
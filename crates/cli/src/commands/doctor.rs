@@ -0,0 +1,56 @@
+//! Doctor command for axel.
+//!
+//! Provides `axel doctor`, which checks the environment a workspace needs
+//! (see [`axel_core::doctor`]) and prints how to fix what's wrong.
+
+use std::path::Path;
+
+use anyhow::{Result, bail};
+use axel_core::doctor::{CheckStatus, run_checks};
+use colored::Colorize;
+
+/// Run the checks for the workspace at `manifest_path`, printing each
+/// result and the fixes for failed ones.
+///
+/// Fails if any check found an error.
+pub fn doctor(manifest_path: &Path, port: u16) -> Result<()> {
+    let checks = run_checks(manifest_path, port);
+    for check in &checks {
+        let marker = match check.status {
+            CheckStatus::Ok => "✔".green(),
+            CheckStatus::Warning => "⚠".yellow(),
+            CheckStatus::Error => "✘".red(),
+        };
+        eprintln!("{} {}", marker, check.message);
+        if let Some(fix) = &check.fix {
+            eprintln!("  {} {}", "→".dimmed(), fix.dimmed());
+        }
+    }
+
+    let count = |status| checks.iter().filter(|c| c.status == status).count();
+    let (errors, warnings) = (count(CheckStatus::Error), count(CheckStatus::Warning));
+    eprintln!();
+    if errors == 0 && warnings == 0 {
+        eprintln!("{}", "Everything looks good".dimmed());
+        return Ok(());
+    }
+    eprintln!(
+        "{}",
+        format!(
+            "{} error{}, {} warning{}",
+            errors,
+            if errors == 1 { "" } else { "s" },
+            warnings,
+            if warnings == 1 { "" } else { "s" }
+        )
+        .dimmed()
+    );
+    if errors > 0 {
+        bail!(
+            "axel doctor found {} problem{}",
+            errors,
+            if errors == 1 { "" } else { "s" }
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,114 @@
+//! Fleet commands for axel.
+//!
+//! Provides `axel up` and `axel down`, which start and kill every workspace
+//! listed in `~/.config/axel/fleet.yaml` (see `axel_core::fleet`).
+
+use std::path::Path;
+
+use anyhow::{Result, bail};
+use axel_core::{
+    config::load_config,
+    fleet::{FleetConfig, fleet_path, load_fleet},
+    tmux::has_session,
+};
+use colored::Colorize;
+
+use crate::{
+    commands::session::{do_kill_workspace, launch_detached},
+    display_path,
+};
+
+/// Read the fleet file, failing with a hint when there is none
+fn read_fleet() -> Result<FleetConfig> {
+    let Some(path) = fleet_path() else {
+        bail!("Could not find home directory");
+    };
+    if !path.exists() {
+        bail!(
+            "No fleet defined: list workspaces under `workspaces:` in {}",
+            display_path(&path)
+        );
+    }
+    let fleet = load_fleet(&path)?;
+    if fleet.workspaces.is_empty() {
+        bail!("No workspaces listed in {}", display_path(&path));
+    }
+    Ok(fleet)
+}
+
+/// Start every workspace of the fleet in its own background session.
+///
+/// Workspaces that fail to start are reported and skipped; the command
+/// fails if any did.
+pub fn up() -> Result<()> {
+    let fleet = read_fleet()?;
+    let start_dir = std::env::current_dir()?;
+
+    let mut failed = 0;
+    for workspace in &fleet.workspaces {
+        let manifest = workspace.manifest_path();
+        let result = launch_detached(&manifest, workspace.grid.as_deref());
+        // Each launch moves into its workspace
+        std::env::set_current_dir(&start_dir)?;
+        match result {
+            Ok((session, true)) => println!(
+                "{} {} {} {}",
+                "✔".green(),
+                "Started".dimmed(),
+                session.blue(),
+                display_path(&manifest).dimmed()
+            ),
+            Ok((session, false)) => println!(
+                "{} {} {}",
+                "✔".green(),
+                "Already running".dimmed(),
+                session.blue()
+            ),
+            Err(e) => {
+                failed += 1;
+                eprintln!(
+                    "{} {}: {}",
+                    "✘".red(),
+                    display_path(&manifest),
+                    format!("{:#}", e).red()
+                );
+            }
+        }
+    }
+
+    if failed > 0 {
+        bail!(
+            "{} of {} workspaces failed to start",
+            failed,
+            fleet.workspaces.len()
+        );
+    }
+    println!(
+        "\n{} Attach with {}",
+        "Fleet is up.".green(),
+        "axel attach".bold()
+    );
+    Ok(())
+}
+
+/// Kill the sessions of every workspace of the fleet.
+pub fn down(workspaces_dir: &Path, keep_skills: bool) -> Result<()> {
+    let fleet = read_fleet()?;
+
+    // Each workspace's session is looked up on the tmux server it uses
+    let mut running = 0;
+    for workspace in &fleet.workspaces {
+        if let Ok(config) = load_config(&workspace.manifest_path()) {
+            config.tmux.use_server();
+        }
+        let session = workspace.session_name();
+        if has_session(&session) {
+            running += 1;
+            do_kill_workspace(workspaces_dir, &session, keep_skills, false, None, true)?;
+        }
+    }
+    if running == 0 {
+        println!("{}", "No fleet sessions running".dimmed());
+    }
+    Ok(())
+}
@@ -32,7 +32,7 @@ impl From<&PaneConfig> for PaneInfo {
             PaneConfig::Claude(c) => (c.color.clone(), true),
             PaneConfig::Codex(c) => (c.color.clone(), true),
             PaneConfig::Opencode(c) => (c.color.clone(), true),
-            PaneConfig::Antigravity(c) => (c.color.clone(), true),
+            PaneConfig::Antigravity(c) | PaneConfig::External(c) => (c.color.clone(), true),
             PaneConfig::Custom(c) => (c.color.clone(), false),
         };
 
@@ -102,6 +102,9 @@ pub struct GridCellInfo {
     /// Color override (if specified)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub color: Option<String>,
+    /// Window name (if not the first window)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub window: Option<String>,
 }
 
 impl GridInfo {
@@ -110,6 +113,10 @@ impl GridInfo {
             GridType::Tmux => "tmux",
             GridType::TmuxCC => "tmux_cc",
             GridType::Shell => "shell",
+            GridType::Zellij => "zellij",
+            GridType::WezTerm => "wezterm",
+            GridType::Kitty => "kitty",
+            GridType::WindowsTerminal => "windows_terminal",
         };
 
         let cells: Vec<GridCellInfo> = grid
@@ -122,6 +129,7 @@ impl GridInfo {
                 width: cell.width,
                 height: cell.height,
                 color: cell.color.clone(),
+                window: cell.window.clone(),
             })
             .collect();
 
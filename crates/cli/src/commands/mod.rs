@@ -1,4 +1,18 @@
+pub mod audit;
+pub mod checkpoint;
+pub mod ci;
+pub mod completions;
+pub mod config;
+pub mod dash;
+pub mod doctor;
+pub mod fleet;
 pub mod layout;
+pub mod prompt;
 pub mod server;
 pub mod session;
 pub mod skill;
+pub mod status;
+pub mod transcript;
+pub mod usage;
+pub mod workspace;
+pub mod worktree;
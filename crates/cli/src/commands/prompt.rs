@@ -0,0 +1,107 @@
+//! Named prompt commands for axel.
+//!
+//! Provides `axel prompt ls|show|new` over the prompts panes reference as
+//! `prompt: "@name"`: the manifest's `prompts:` section and the files in
+//! `~/.config/axel/prompts/`.
+
+use std::path::Path;
+
+use anyhow::{Context, Result, bail};
+use axel_core::config::{
+    NamedPrompt, PromptSource, global_prompts, load_config, prompt_ref, prompts_dir,
+};
+use colored::Colorize;
+
+use crate::display_path;
+
+/// Named prompts of the workspace, or only the global ones without a manifest
+fn workspace_prompts(manifest_path: &Path) -> Result<Vec<NamedPrompt>> {
+    if manifest_path.exists() {
+        return Ok(load_config(manifest_path)?.prompts());
+    }
+    Ok(prompts_dir()
+        .map(|dir| global_prompts(&dir))
+        .unwrap_or_default())
+}
+
+/// List every named prompt with where it is defined
+pub fn list_prompts(manifest_path: &Path, json: bool) -> Result<()> {
+    let prompts = workspace_prompts(manifest_path)?;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&prompts)?);
+        return Ok(());
+    }
+
+    if prompts.is_empty() {
+        println!("{}", "No prompts found".dimmed());
+        return Ok(());
+    }
+
+    use comfy_table::{Table, presets::NOTHING};
+
+    let mut table = Table::new();
+    table.load_preset(NOTHING);
+    for prompt in &prompts {
+        let source = match prompt.source {
+            PromptSource::Manifest => "manifest".yellow().to_string(),
+            PromptSource::Global => "global".purple().to_string(),
+        };
+        let first_line = prompt.text.lines().next().unwrap_or_default();
+        table.add_row(vec![
+            format!("@{}", prompt.name).green().to_string(),
+            source,
+            first_line.dimmed().to_string(),
+        ]);
+    }
+    println!("{table}");
+
+    Ok(())
+}
+
+/// Print the text of prompt `name` (with or without the `@`)
+pub fn show_prompt(name: &str, manifest_path: &Path) -> Result<()> {
+    let name = name.strip_prefix('@').unwrap_or(name);
+    let prompts = workspace_prompts(manifest_path)?;
+    let Some(prompt) = prompts.iter().find(|p| p.name == name) else {
+        bail!("Prompt '@{}' not found (see axel prompt ls)", name);
+    };
+    println!("{}", prompt.text);
+    Ok(())
+}
+
+/// Create global prompt `name` and open it in `$EDITOR`
+pub fn new_prompt(name: &str) -> Result<()> {
+    let name = name.strip_prefix('@').unwrap_or(name);
+    if prompt_ref(&format!("@{}", name)) != Some(name) {
+        bail!(
+            "Invalid prompt name '{}': use letters, digits, '-' and '_'",
+            name
+        );
+    }
+
+    let dir = prompts_dir().context("Could not find home directory")?;
+    let path = dir.join(format!("{}.md", name));
+    if path.exists() {
+        bail!(
+            "Prompt '@{}' already exists at {}",
+            name,
+            display_path(&path)
+        );
+    }
+    std::fs::create_dir_all(&dir)?;
+    std::fs::write(&path, "Describe the task here.\n")?;
+
+    println!(
+        "{} {} {} {}",
+        "✔".green(),
+        "Created".dimmed(),
+        display_path(&path),
+        format!("(use it with prompt: \"@{}\")", name).dimmed()
+    );
+
+    let editor = std::env::var("EDITOR").unwrap_or_else(|_| "code".to_string());
+    std::process::Command::new(editor).arg(&path).status()?;
+
+    Ok(())
+}
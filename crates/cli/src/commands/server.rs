@@ -1,9 +1,16 @@
 //! Server command for running the axel event server.
 
-use std::path::PathBuf;
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    time::Duration,
+};
 
 use anyhow::Result;
-use axel_core::server::{ServerConfig, run_server};
+use axel_core::{
+    config::load_config,
+    server::{EventStorage, LogRetention, ServerConfig, run_server},
+};
 use clap::Args;
 
 /// Server command arguments
@@ -20,18 +27,133 @@ pub struct ServerArgs {
     /// Path to the JSONL log file
     #[arg(short, long, default_value = ".axel/events.jsonl")]
     pub log: PathBuf,
+
+    /// Also index events in a SQLite database for `GET /events` queries
+    #[arg(long, value_name = "PATH")]
+    pub db: Option<PathBuf>,
+
+    /// Serve Prometheus metrics on `GET /metrics`
+    #[arg(long)]
+    pub metrics: bool,
+
+    /// Rotate the log once it grows past this many MB (0 disables)
+    #[arg(long, value_name = "MB", default_value = "100")]
+    pub rotate_mb: u64,
+
+    /// Also rotate the log after this many hours
+    #[arg(long, value_name = "HOURS")]
+    pub rotate_hours: Option<u64>,
+
+    /// Keep at most this many compressed log archives
+    #[arg(long, value_name = "N", default_value = "10")]
+    pub keep_archives: usize,
+
+    /// Delete log archives older than this many days
+    #[arg(long, value_name = "DAYS")]
+    pub keep_days: Option<u64>,
 }
 
 /// Run the server command
-pub async fn run(args: ServerArgs) -> Result<()> {
+///
+/// Notifications and webhooks follow the `notifications:` section of the
+/// workspace manifest, when there is one, agent replies are routed
+/// between the session's panes per its `orchestrator:` section, panes
+/// reporting under their name are interrupted when they go over their
+/// `budget:` and reported when they are `stuck:`, their tool calls are
+/// decided by its `policies:`, and their turns checkpointed with
+/// `checkpoints: true`.
+pub async fn run(args: ServerArgs, manifest_path: &Path) -> Result<()> {
+    let (notifications, orchestrator, budgets, stuck, watched, policies, checkpoints) =
+        if manifest_path.exists() {
+            let manifest = load_config(manifest_path)?;
+            let (budgets, stuck) = (manifest.budgets(), manifest.stuck_thresholds());
+            let (watched, checkpoints) = (manifest.watched_panes(), manifest.checkpointed_panes());
+            (
+                manifest.notifications,
+                manifest.orchestrator,
+                budgets,
+                stuck,
+                watched,
+                manifest.policies,
+                checkpoints,
+            )
+        } else {
+            (
+                None,
+                None,
+                HashMap::new(),
+                HashMap::new(),
+                Vec::new(),
+                Vec::new(),
+                Vec::new(),
+            )
+        };
+
     let config = ServerConfig {
         port: args.port,
         session: args.session.unwrap_or_default(),
         log_path: args.log,
+        storage: args.db.map_or(EventStorage::Jsonl, EventStorage::Sqlite),
+        metrics: args.metrics,
+        retention: LogRetention {
+            max_size: (args.rotate_mb > 0).then(|| args.rotate_mb * 1024 * 1024),
+            max_age: args.rotate_hours.map(|h| Duration::from_secs(h * 3600)),
+            max_archives: Some(args.keep_archives),
+            archive_max_age: args.keep_days.map(|d| Duration::from_secs(d * 86400)),
+        },
+        notifications,
+        pane_names: watched
+            .into_iter()
+            .map(|name| (name.clone(), name))
+            .collect(),
+        orchestrator,
+        budgets,
+        stuck,
+        policies,
+        checkpoints,
     };
 
     eprintln!("Starting axel event server on port {}", config.port);
     eprintln!("Logging to: {:?}", config.log_path);
+    if let EventStorage::Sqlite(path) = &config.storage {
+        eprintln!("Indexing events in: {:?}", path);
+    }
+    if config.notifications.is_some() {
+        eprintln!("Notifications enabled");
+    }
+    if !config.policies.is_empty() {
+        eprintln!(
+            "Deciding tool calls with {} policies",
+            config.policies.len()
+        );
+    }
+    if !config.checkpoints.is_empty() {
+        eprintln!("Checkpointing turns of {}", config.checkpoints.join(", "));
+    }
+    if let Some(orchestrator) = &config.orchestrator {
+        if config.session.is_empty() {
+            eprintln!("Orchestrator needs --session to route replies between panes");
+        } else {
+            eprintln!(
+                "Routing replies between roles: {}",
+                orchestrator
+                    .roles
+                    .keys()
+                    .cloned()
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+        }
+    }
+    if !config.budgets.is_empty() {
+        eprintln!("Enforcing budgets of {} panes", config.budgets.len());
+    }
+    if !config.stuck.is_empty() {
+        eprintln!("Watching {} panes for stalls", config.stuck.len());
+    }
+    if config.metrics {
+        eprintln!("Serving Prometheus metrics on /metrics");
+    }
     if !config.session.is_empty() {
         eprintln!("Monitoring tmux session: {}", config.session);
     } else {
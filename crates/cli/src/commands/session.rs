@@ -1,23 +1,48 @@
 //! Session management commands for axel.
 //!
 //! This module handles tmux session lifecycle:
-//! - Listing running sessions
-//! - Launching workspaces (shell, tmux, tmux_cc modes)
+//! - Listing running sessions of every backend
+//! - Reading and setting session environment variables
+//! - Broadcasting input to AI panes, or sending a prompt to one
+//! - Watching AI panes and respawning them when they exit
+//! - Reloading a running session after manifest changes
+//! - Saving sessions to snapshots and restoring them
+//! - Recording panes as asciicast files and playing them back
+//! - Launching workspaces (shell, tmux, tmux_cc, zellij, wezterm, kitty modes)
+//! - Starting the orchestrator that routes replies between role panes
 //! - Killing sessions with cleanup
 
-use std::path::{Path, PathBuf};
+use std::{
+    collections::{BTreeSet, HashMap},
+    path::{Path, PathBuf},
+    sync::mpsc::{self, Receiver, RecvTimeoutError},
+    time::Duration,
+};
 
-use anyhow::Result;
+use anyhow::{Context, Result, bail};
 use axel_core::{
-    GridType, PaneConfig,
-    claude::ClaudeCommand,
-    config::{expand_path, load_config},
-    drivers, generate_hooks_settings, git, settings_path,
+    Error, GridType, PaneConfig, ShellRun, Terminal, Timings, WorkspaceConfig,
+    backend::{
+        Backend, BackendKind, find_session_backend, forget_session, get_backend, list_all_sessions,
+        record_session,
+    },
+    config::{expand_path, load_config, manifest_in},
+    drivers, generate_hooks_settings, git, platform,
+    recording::{Asciicast, AsciicastHeader, AsciicastWriter, record_pane, recording_path},
+    registry::register_workspace,
+    server::ServerClient,
+    settings_path, shell,
     tmux::{
-        AXEL_MANIFEST_ENV, AXEL_PANE_ID_ENV, AXEL_PORT_ENV, NewSession, OtelConfig, SetOption,
-        attach_session, create_workspace as tmux_create_workspace, detach_session, get_environment,
-        has_session, kill_session, list_sessions, set_environment,
+        AXEL_ENV_VARS, AXEL_MANIFEST_ENV, AXEL_PANE_ID_ENV, AXEL_PORT_ENV, NewSession, NewWindow,
+        OtelConfig, PaneWatchdog, SNAPSHOT_FILE, SelectPane, SessionInfo, SetOption, Snapshot,
+        attach_session, broadcast_targets, create_workspace as tmux_create_workspace,
+        current_session, format_environment, get_environment, get_pane_id, has_session,
+        install_pane_mcp, list_panes, list_sessions, next_ai_pane, one_shot_command, pane_command,
+        pane_session, pane_size, parse_env_assignment, paste_text, pipe_pane, reload_workspace,
+        render_skills, report_skill_install, restore_snapshot, select_window, set_environment,
+        tmux_command, tmux_program, transcript_command,
     },
+    transcript::{open_transcript, transcript_path},
     write_settings,
 };
 use colored::Colorize;
@@ -25,19 +50,27 @@ use colored::Colorize;
 use crate::{
     commands::skill::{cleanup_skills, format_cleaned_drivers},
     display_path,
+    picker::FuzzyPicker,
 };
 
+/// Window running the orchestrator's event server
+const ORCHESTRATOR_WINDOW: &str = "orchestrator";
+
 // =============================================================================
 // Session Listing
 // =============================================================================
 
-/// List running tmux sessions.
+/// List the running sessions of every installed backend.
 ///
-/// If `axel_only` is true, only shows sessions created by axel
-/// (identified by the AXEL_MANIFEST environment variable).
+/// If `axel_only` is true, only shows sessions created by axel (those with a
+/// known workspace). Sessions in a git checkout show its branch, distance
+/// from upstream and changed files.
 /// If `json_output` is true, outputs JSON format for programmatic access.
 pub fn do_list_sessions(axel_only: bool, json_output: bool) -> Result<()> {
-    let sessions = list_sessions(axel_only)?;
+    let mut sessions = list_all_sessions();
+    if axel_only {
+        sessions.retain(|session| session.working_dir.is_some());
+    }
 
     if json_output {
         let json = serde_json::to_string_pretty(&sessions)?;
@@ -49,7 +82,7 @@ pub fn do_list_sessions(axel_only: bool, json_output: bool) -> Result<()> {
         if axel_only {
             println!("{}", "No axel sessions running".dimmed());
         } else {
-            println!("{}", "No tmux sessions running".dimmed());
+            println!("{}", "No sessions running".dimmed());
         }
         return Ok(());
     }
@@ -72,13 +105,23 @@ pub fn do_list_sessions(axel_only: bool, json_output: bool) -> Result<()> {
             .map(|d| display_path(Path::new(d)))
             .unwrap_or_else(|| "-".to_string());
 
-        let panes_label = if session.panes == 1 { "pane" } else { "panes" };
+        let git = session
+            .git
+            .as_ref()
+            .map(format_git_status)
+            .unwrap_or_default();
+
+        // Backends that can't count panes report none
+        let panes = match session.panes {
+            0 => "-".to_string(),
+            1 => "1 pane".to_string(),
+            n => format!("{} panes", n),
+        };
         table.add_row(vec![
             session.name.blue().to_string(),
             location.dimmed().to_string(),
-            format!("{} {}", session.panes, panes_label)
-                .dimmed()
-                .to_string(),
+            git,
+            panes.dimmed().to_string(),
             attached,
         ]);
     }
@@ -88,6 +131,597 @@ pub fn do_list_sessions(axel_only: bool, json_output: bool) -> Result<()> {
     Ok(())
 }
 
+/// Format a checkout's state for the session list, e.g. `feat/auth ↑2 ↓1 3 changed`
+pub(crate) fn format_git_status(status: &git::RepoStatus) -> String {
+    let mut parts = vec![status.branch.cyan().to_string()];
+    if status.ahead > 0 {
+        parts.push(format!("↑{}", status.ahead).green().to_string());
+    }
+    if status.behind > 0 {
+        parts.push(format!("↓{}", status.behind).red().to_string());
+    }
+    if status.dirty > 0 {
+        parts.push(format!("{} changed", status.dirty).yellow().to_string());
+    }
+    parts.join(" ")
+}
+
+/// Attach to `name`, or pick a running session interactively.
+///
+/// The picker lists the axel sessions of every backend (all sessions with
+/// `all`) and previews the highlighted one's directory, branch and panes.
+pub fn session_attach(name: Option<&str>, all: bool, attach_in: Option<Terminal>) -> Result<()> {
+    if let Some(name) = name {
+        let Some(backend) = find_session_backend(name) else {
+            bail!(Error::SessionNotFound(name.to_string()));
+        };
+        return attach_backend(&*backend, name, attach_in);
+    }
+
+    let mut sessions = list_all_sessions();
+    if !all {
+        sessions.retain(|session| session.working_dir.is_some());
+    }
+    let name = match sessions.as_slice() {
+        [] => {
+            let kind = if all { "" } else { "axel " };
+            println!("{}", format!("No {}sessions running", kind).dimmed());
+            return Ok(());
+        }
+        [session] => session.name.clone(),
+        _ => {
+            let labels: Vec<String> = sessions
+                .iter()
+                .map(|session| {
+                    let attached = if session.attached { " (attached)" } else { "" };
+                    format!("{}{}", session.name, attached)
+                })
+                .collect();
+            let picked = FuzzyPicker::new("Session:", &labels, |i| session_preview(&sessions[i]))
+                .interact()?;
+            match picked {
+                Some(i) => sessions[i].name.clone(),
+                None => return Ok(()),
+            }
+        }
+    };
+
+    match find_session_backend(&name) {
+        Some(backend) => attach_backend(&*backend, &name, attach_in),
+        None => bail!(Error::SessionNotFound(name)),
+    }
+}
+
+/// Preview lines for a session in the picker: directory, branch and panes
+fn session_preview(session: &SessionInfo) -> Vec<String> {
+    let mut lines = Vec::new();
+    if let Some(dir) = &session.working_dir {
+        lines.push(display_path(Path::new(dir)));
+    }
+    if let Some(git) = &session.git {
+        lines.push(format_git_status(git));
+    }
+    let panes: Vec<String> = list_panes(&session.name)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|pane| pane.title)
+        .collect();
+    lines.push(format!(
+        "{} {}: {}",
+        session.panes,
+        if session.panes == 1 { "pane" } else { "panes" },
+        panes.join(", ")
+    ));
+    lines
+}
+
+// =============================================================================
+// Session Environment and Input
+// =============================================================================
+
+/// Print or set environment variables on a running session.
+///
+/// `KEY=VALUE` arguments are set; bare `KEY` arguments are printed. With no
+/// arguments, prints the axel-managed variables.
+pub fn session_env(name: &str, vars: &[String]) -> Result<()> {
+    if !has_session(name) {
+        bail!(Error::SessionNotFound(name.to_string()));
+    }
+
+    let (assignments, keys): (Vec<&String>, Vec<&String>) =
+        vars.iter().partition(|v| v.contains('='));
+
+    for assignment in assignments {
+        let (key, value) = parse_env_assignment(assignment)?;
+        set_environment(name, key, value)?;
+        eprintln!("{} {} {}", "✔".green(), "Set".dimmed(), key);
+    }
+
+    let keys: Vec<&str> = if vars.is_empty() {
+        AXEL_ENV_VARS.to_vec()
+    } else {
+        keys.iter().map(|k| k.as_str()).collect()
+    };
+    let values: Vec<_> = keys
+        .iter()
+        .map(|key| (*key, get_environment(name, key)))
+        .collect();
+    print!("{}", format_environment(&values));
+    Ok(())
+}
+
+/// Send `text` to the AI panes of a session (the current one by default).
+///
+/// A non-empty `only` restricts the targets to those driver types or pane
+/// names. `-` reads the text from stdin. The text is pasted as one input,
+/// so a multi-line prompt reaches every pane whole.
+pub fn session_broadcast(text: &str, session: Option<&str>, only: &[String]) -> Result<()> {
+    let Some(session) = session.map(str::to_string).or_else(current_session) else {
+        bail!(Error::NotInSession);
+    };
+    if !has_session(&session) {
+        bail!(Error::SessionNotFound(session));
+    }
+
+    let config = get_environment(&session, AXEL_MANIFEST_ENV)
+        .and_then(|manifest| load_config(Path::new(&manifest)).ok());
+    let panes = list_panes(&session)?;
+    let targets = broadcast_targets(&panes, config.as_ref(), only);
+
+    if targets.is_empty() {
+        bail!(Error::NoMatchingPanes(session));
+    }
+
+    let text = if text == "-" {
+        let mut input = String::new();
+        std::io::Read::read_to_string(&mut std::io::stdin(), &mut input)?;
+        input.trim_end().to_string()
+    } else {
+        text.to_string()
+    };
+    if text.is_empty() {
+        bail!("Nothing to broadcast");
+    }
+
+    for pane in &targets {
+        paste_text(&pane.id, &text)?;
+        eprintln!("{} {} {}", "✔".green(), "Sent to".dimmed(), pane.title);
+    }
+    Ok(())
+}
+
+/// Move focus to the next (or with `prev`, the previous) AI pane of a session
+/// (the current one by default), switching windows when needed.
+pub fn session_focus(session: Option<&str>, prev: bool) -> Result<()> {
+    let Some(session) = session.map(str::to_string).or_else(current_session) else {
+        bail!(Error::NotInSession);
+    };
+    if !has_session(&session) {
+        bail!(Error::SessionNotFound(session));
+    }
+
+    let config = get_environment(&session, AXEL_MANIFEST_ENV)
+        .and_then(|manifest| load_config(Path::new(&manifest)).ok());
+    let panes = list_panes(&session)?;
+    let targets = broadcast_targets(&panes, config.as_ref(), &[]);
+    let active = get_pane_id(&session)?;
+    let Some(pane) = next_ai_pane(&targets, &active, prev) else {
+        bail!(Error::NoMatchingPanes(session));
+    };
+
+    select_window(&pane.id)?;
+    SelectPane::new().target(&pane.id).run()?;
+    eprintln!("{} {} {}", "✔".green(), "Focused".dimmed(), pane.title);
+    Ok(())
+}
+
+/// Send a prompt to one AI pane and optionally wait for its reply.
+///
+/// `target` is a session (when it has a single AI pane), `session:pane` to
+/// pick a pane by name or driver, or a tmux pane ID like `%5`. The prompt
+/// is pasted as one input, so multi-line prompts stay together. With
+/// `wait`, blocks until the session's event server reports the agent's
+/// `Stop` hook and prints its last message, giving up after `timeout`.
+pub fn session_send(target: &str, prompt: &str, wait: bool, timeout: Duration) -> Result<()> {
+    let (session, pane, label) = if target.starts_with('%') {
+        let session =
+            pane_session(target).ok_or_else(|| anyhow::anyhow!("Pane '{}' not found", target))?;
+        (session, target.to_string(), target.to_string())
+    } else {
+        let (session, name) = match target.split_once(':') {
+            Some((session, name)) => (session, Some(name.to_string())),
+            None => (target, None),
+        };
+        if !has_session(session) {
+            bail!(Error::SessionNotFound(session.to_string()));
+        }
+        let config = get_environment(session, AXEL_MANIFEST_ENV)
+            .and_then(|manifest| load_config(Path::new(&manifest)).ok());
+        let panes = list_panes(session)?;
+        let targets = broadcast_targets(&panes, config.as_ref(), name.as_slice());
+        match targets.as_slice() {
+            [] => bail!(Error::NoMatchingPanes(session.to_string())),
+            [pane] => (session.to_string(), pane.id.clone(), pane.title.clone()),
+            _ => bail!(Error::AmbiguousPane {
+                session: session.to_string(),
+                panes: targets.into_iter().map(|pane| pane.title).collect(),
+            }),
+        }
+    };
+
+    // Subscribe before sending so a quick reply isn't missed
+    let replies = if wait {
+        let Some(port) = get_environment(&session, AXEL_PORT_ENV).and_then(|p| p.parse().ok())
+        else {
+            bail!(
+                "Session '{}' has no event server to report when the agent is done",
+                session
+            );
+        };
+        let pane_id = get_environment(&session, AXEL_PANE_ID_ENV);
+        Some(wait_for_stop(ServerClient::new(port), pane_id)?)
+    } else {
+        None
+    };
+
+    paste_text(&pane, prompt)?;
+    eprintln!(
+        "{} {} {} ({})",
+        "✔".green(),
+        "Sent to".dimmed(),
+        label,
+        session
+    );
+
+    if let Some(replies) = replies {
+        match replies.recv_timeout(timeout) {
+            Ok(Some(reply)) => println!("{}", reply),
+            Ok(None) => eprintln!(
+                "{} {}",
+                "!".yellow(),
+                "Agent stopped without a reply".dimmed()
+            ),
+            Err(RecvTimeoutError::Timeout) => bail!(
+                "Timed out after {}s waiting for the agent in '{}' to finish",
+                timeout.as_secs(),
+                session
+            ),
+            Err(RecvTimeoutError::Disconnected) => {
+                bail!("Event server closed the connection before the agent finished")
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Follow the event server in the background and report the reply of the
+/// first `Stop` hook from `pane_id` (any pane when the session has none).
+fn wait_for_stop(
+    client: ServerClient,
+    pane_id: Option<String>,
+) -> Result<Receiver<Option<String>>> {
+    let events = client.events()?;
+    let (tx, rx) = mpsc::channel();
+    std::thread::spawn(move || {
+        let stop = events.filter_map(Result::ok).find(|event| {
+            event.hook_name() == Some("Stop")
+                && pane_id.as_ref().is_none_or(|id| *id == event.pane_id)
+        });
+        if let Some(event) = stop {
+            tx.send(event.stop_reply()).ok();
+        }
+    });
+    Ok(rx)
+}
+
+/// Watch a session's AI panes, respawning any whose process exits.
+///
+/// Runs until the session ends, checking every `interval` seconds.
+pub fn session_watch(session: Option<&str>, interval: u64) -> Result<()> {
+    let Some(session) = session.map(str::to_string).or_else(current_session) else {
+        bail!(Error::NotInSession);
+    };
+    if !has_session(&session) {
+        bail!(Error::SessionNotFound(session));
+    }
+    let Some(manifest) = get_environment(&session, AXEL_MANIFEST_ENV) else {
+        anyhow::bail!("Session '{}' was not created by axel", session);
+    };
+    let config = load_config(Path::new(&manifest))?;
+
+    eprintln!(
+        "{} {} {} {}",
+        "✔".green(),
+        "Watching".dimmed(),
+        session,
+        "(Ctrl-C to stop)".dimmed()
+    );
+    let mut watchdog = PaneWatchdog::new();
+    while has_session(&session) {
+        for name in watchdog.check(&session, &config)? {
+            eprintln!("{} {} {}", "⚠".yellow(), "Respawned".dimmed(), name);
+        }
+        std::thread::sleep(std::time::Duration::from_secs(interval));
+    }
+    eprintln!("{} Session '{}' ended", "!".yellow(), session);
+    Ok(())
+}
+
+/// Apply the session's manifest to it again, adding new panes.
+pub fn session_reload(
+    session: Option<&str>,
+    grid: Option<&str>,
+    profile_timing: bool,
+) -> Result<()> {
+    let Some(session) = session.map(str::to_string).or_else(current_session) else {
+        bail!(Error::NotInSession);
+    };
+    if !has_session(&session) {
+        bail!(Error::SessionNotFound(session));
+    }
+    let Some(manifest) = get_environment(&session, AXEL_MANIFEST_ENV) else {
+        anyhow::bail!("Session '{}' was not created by axel", session);
+    };
+
+    let mut timings = Timings::start();
+    let config = load_config(Path::new(&manifest))?;
+    timings.lap("config load");
+
+    let summary = reload_workspace(&session, &config, grid, &mut timings)?;
+    for name in &summary.added {
+        eprintln!("{} {} {}", "✔".green(), "Added".dimmed(), name);
+    }
+    if !summary.updated.is_empty() {
+        eprintln!(
+            "{} {} {}",
+            "✔".green(),
+            "Refreshed".dimmed(),
+            summary.updated.join(", ")
+        );
+    }
+    for name in &summary.orphaned {
+        eprintln!(
+            "{} {} {}",
+            "⚠".yellow(),
+            name,
+            "is not in the manifest (left running)".dimmed()
+        );
+    }
+    if profile_timing {
+        print_timings(&timings);
+    }
+    Ok(())
+}
+
+// =============================================================================
+// Snapshots
+// =============================================================================
+
+/// Save a session to a snapshot file.
+///
+/// Without `file`, the snapshot goes to `.axel/snapshot.json` next to the
+/// session's manifest, or in `base_dir` for sessions without one.
+pub fn session_snapshot(
+    session: Option<&str>,
+    file: Option<&Path>,
+    scrollback: usize,
+    base_dir: &Path,
+) -> Result<()> {
+    let Some(session) = session.map(str::to_string).or_else(current_session) else {
+        bail!(Error::NotInSession);
+    };
+    if !has_session(&session) {
+        bail!(Error::SessionNotFound(session));
+    }
+
+    let snapshot = Snapshot::capture(&session, scrollback)?;
+    let path = match file {
+        Some(file) => file.to_path_buf(),
+        None => snapshot
+            .manifest
+            .as_deref()
+            .and_then(Path::parent)
+            .unwrap_or(base_dir)
+            .join(SNAPSHOT_FILE),
+    };
+    snapshot.save(&path)?;
+
+    eprintln!(
+        "{} {} {} ({} windows, {} panes) to {}",
+        "✔".green(),
+        "Saved".dimmed(),
+        session,
+        snapshot.windows.len(),
+        snapshot.pane_count(),
+        display_path(&path)
+    );
+    Ok(())
+}
+
+/// Recreate a session from a snapshot file and attach to it.
+pub fn session_restore(
+    file: Option<&Path>,
+    session_name: Option<&str>,
+    base_dir: &Path,
+    attach_in: Option<Terminal>,
+    profile_timing: bool,
+) -> Result<()> {
+    let path = file
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| base_dir.join(SNAPSHOT_FILE));
+    let mut timings = Timings::start();
+    let snapshot = Snapshot::load(&path)?;
+    let session = session_name.unwrap_or(&snapshot.session);
+
+    let config = match &snapshot.manifest {
+        Some(manifest) if manifest.exists() => Some(load_config(manifest)?),
+        Some(manifest) => {
+            eprintln!(
+                "{} {} {}",
+                "⚠".yellow(),
+                "Manifest not found, panes will not be restarted:".dimmed(),
+                display_path(manifest)
+            );
+            None
+        }
+        None => None,
+    };
+    timings.lap("config load");
+
+    let restarted = restore_snapshot(&snapshot, session, config.as_ref(), &mut timings)?;
+    eprintln!(
+        "{} {} {} ({} panes, {} restarted)",
+        "✔".green(),
+        "Restored".dimmed(),
+        session,
+        snapshot.pane_count(),
+        restarted.len()
+    );
+    if profile_timing {
+        print_timings(&timings);
+    }
+
+    attach(session, false, attach_in)
+}
+
+// =============================================================================
+// Recordings
+// =============================================================================
+
+/// Start or stop recording a session's panes as asciicast files.
+///
+/// Records the AI panes by default; a non-empty `only` picks panes by name
+/// or driver, including non-AI ones. Each pane's output is piped (tmux
+/// `pipe-pane`) to `axel session record --pipe`, which keeps writing the AI
+/// panes' transcripts alongside. `stop` puts the transcript pipes back and
+/// closes the others.
+pub fn session_record(
+    session: Option<&str>,
+    only: &[String],
+    stop: bool,
+    base_dir: &Path,
+) -> Result<()> {
+    let Some(session) = session.map(str::to_string).or_else(current_session) else {
+        bail!(Error::NotInSession);
+    };
+    if !has_session(&session) {
+        bail!(Error::SessionNotFound(session));
+    }
+
+    let config = get_environment(&session, AXEL_MANIFEST_ENV)
+        .and_then(|manifest| load_config(Path::new(&manifest)).ok());
+    let workspace_dir = config
+        .as_ref()
+        .and_then(WorkspaceConfig::workspace_dir)
+        .unwrap_or_else(|| base_dir.to_path_buf());
+    let panes = list_panes(&session)?;
+    let ai = broadcast_targets(&panes, config.as_ref(), &[]);
+    let targets: Vec<_> = if only.is_empty() {
+        ai.clone()
+    } else {
+        let matched = broadcast_targets(&panes, config.as_ref(), only);
+        panes
+            .into_iter()
+            .filter(|pane| only.contains(&pane.title) || matched.iter().any(|m| m.id == pane.id))
+            .collect()
+    };
+    if targets.is_empty() {
+        bail!(Error::NoMatchingPanes(session));
+    }
+
+    let axel = std::env::current_exe()?.to_string_lossy().into_owned();
+    for pane in &targets {
+        let transcript = ai
+            .iter()
+            .any(|a| a.id == pane.id)
+            .then(|| transcript_path(&workspace_dir, &pane.title));
+
+        if stop {
+            match &transcript {
+                Some(path) => {
+                    pipe_pane(&pane.id, Some(&transcript_command(&axel, path, &session)))?
+                }
+                None => pipe_pane(&pane.id, None)?,
+            }
+            eprintln!(
+                "{} {} {}",
+                "✔".green(),
+                "Stopped recording".dimmed(),
+                pane.title
+            );
+            continue;
+        }
+
+        let (width, height) = pane_size(&pane.id)?;
+        let path = recording_path(&workspace_dir, &session, &pane.title);
+        AsciicastHeader::new(width, height, Some(format!("{} {}", session, pane.title)))
+            .create_file(&path)?;
+        let mut command = format!(
+            "exec {} session record {} --pipe {}",
+            shell::quote(&axel),
+            shell::quote(&session),
+            shell::quote(&path.to_string_lossy())
+        );
+        if let Some(transcript) = &transcript {
+            command.push_str(" --transcript ");
+            command.push_str(&shell::quote(&transcript.to_string_lossy()));
+        }
+        pipe_pane(&pane.id, Some(&command))?;
+        eprintln!(
+            "{} {} {} to {}",
+            "✔".green(),
+            "Recording".dimmed(),
+            pane.title,
+            display_path(&path)
+        );
+    }
+    if !stop {
+        eprintln!(
+            "{}",
+            format!("Stop with: axel session record {} --stop", session).dimmed()
+        );
+    }
+    Ok(())
+}
+
+/// Record stdin into the asciicast file at `cast` and, for AI panes, the
+/// transcript at `transcript` (run by tmux `pipe-pane`)
+pub fn record_pipe(session: &str, cast: &Path, transcript: Option<&Path>) -> Result<()> {
+    let transcript = transcript
+        .map(|path| open_transcript(path, &format!("Session {}", session)))
+        .transpose()?;
+    record_pane(
+        std::io::stdin().lock(),
+        transcript,
+        Some(AsciicastWriter::append(cast)?),
+    )
+}
+
+/// Replay an asciicast recording in this terminal.
+///
+/// `speed` multiplies the playback speed; `idle_limit` caps pauses between
+/// outputs, in seconds.
+pub fn session_play(file: &Path, speed: f64, idle_limit: Option<f64>) -> Result<()> {
+    if speed <= 0.0 {
+        bail!("Speed must be positive");
+    }
+    let cast = Asciicast::load(file)?;
+    if let Some(title) = &cast.header.title {
+        eprintln!(
+            "{} {} {} ({}x{}, {:.0}s)",
+            "▶".green(),
+            "Playing".dimmed(),
+            title,
+            cast.header.width,
+            cast.header.height,
+            cast.duration() / speed
+        );
+    }
+    cast.play(&mut std::io::stdout().lock(), speed, idle_limit)?;
+    println!();
+    Ok(())
+}
+
 // =============================================================================
 // Session Killing
 // =============================================================================
@@ -98,7 +732,8 @@ pub fn do_kill_all_sessions(
     keep_skills: bool,
     skip_confirm: bool,
 ) -> Result<()> {
-    let sessions = list_sessions(true)?; // true = axel_only
+    let mut sessions = list_all_sessions();
+    sessions.retain(|session| session.working_dir.is_some());
 
     if sessions.is_empty() {
         println!("{}", "No axel sessions running".dimmed());
@@ -137,8 +772,9 @@ pub fn do_kill_all_sessions(
 
     let mut killed = 0;
     for session in &sessions {
-        // Detach clients first to avoid issues
-        detach_session(&session.name)?;
+        let Some(backend) = find_session_backend(&session.name) else {
+            continue;
+        };
 
         // Clean up skills if not keeping them
         if !keep_skills && let Some(ref working_dir) = session.working_dir {
@@ -147,7 +783,8 @@ pub fn do_kill_all_sessions(
         }
 
         // Kill the session
-        if kill_session(&session.name).is_ok() {
+        if backend.kill_session(&session.name).is_ok() {
+            forget_session(&session.name).ok();
             killed += 1;
             println!("{} {} {}", "✔".green(), "Killed".dimmed(), session.name);
         } else {
@@ -170,22 +807,19 @@ pub fn do_kill_workspace(
     worktree_branch: Option<&str>,
     skip_confirm: bool,
 ) -> Result<()> {
-    let resolved_name = if has_session(name) {
-        name.to_string()
-    } else {
-        let sessions = list_sessions(false).unwrap_or_default();
-        let matched = sessions
-            .iter()
-            .find(|session| session.axel_pane_id.as_deref() == Some(name))
-            .map(|session| session.name.clone());
-
-        if let Some(found) = matched {
-            found
-        } else {
-            eprintln!("{} Session '{}' not found", "✘".red(), name);
-            eprintln!();
-            let _ = do_list_sessions(false, false);
-            return Ok(());
+    let (backend, resolved_name) = match find_session_backend(name) {
+        Some(backend) => (backend, name.to_string()),
+        None => {
+            // Sessions launched from the macOS app are also found by pane ID
+            let matched = list_sessions(false)
+                .unwrap_or_default()
+                .into_iter()
+                .find(|session| session.axel_pane_id.as_deref() == Some(name))
+                .map(|session| session.name);
+            match matched {
+                Some(found) => (get_backend(BackendKind::Tmux), found),
+                None => bail!(Error::SessionNotFound(name.to_string())),
+            }
         }
     };
 
@@ -193,7 +827,11 @@ pub fn do_kill_workspace(
         use dialoguer::{Confirm, theme::ColorfulTheme};
         let theme = ColorfulTheme::default();
         let confirmed = Confirm::with_theme(&theme)
-            .with_prompt(format!("Kill session '{}'?", resolved_name))
+            .with_prompt(format!(
+                "Kill {} session '{}'?",
+                backend.name(),
+                resolved_name
+            ))
             .default(true)
             .interact()?;
 
@@ -203,29 +841,30 @@ pub fn do_kill_workspace(
         }
     }
 
+    // tmux sessions carry their manifest, which is gone once they're killed
+    let session_manifest = get_environment(&resolved_name, AXEL_MANIFEST_ENV).map(PathBuf::from);
+    backend.kill_session(&resolved_name)?;
+    let recorded_dir = forget_session(&resolved_name).ok().flatten();
+
     // Skip skill cleanup for worktree sessions - the worktree directory
     // may be pruned anyway, and we don't want to accidentally clean the main repo
     let cleaned = if !keep_skills && worktree_branch.is_none() {
-        let session_manifest =
-            get_environment(&resolved_name, AXEL_MANIFEST_ENV).map(PathBuf::from);
-        let config_path = workspaces_dir.join(&resolved_name).join("AXEL.md");
-        let local_config = std::env::current_dir().ok().map(|d| d.join("AXEL.md"));
-
-        let cfg = session_manifest
-            .and_then(|p| load_config(&p).ok())
-            .or_else(|| load_config(&config_path).ok())
-            .or_else(|| local_config.and_then(|p| load_config(&p).ok()));
-
-        cfg.and_then(|c| c.workspace_dir())
+        let workspace_dir = recorded_dir.or_else(|| {
+            let config_path = manifest_in(&workspaces_dir.join(&resolved_name));
+            let local_config = std::env::current_dir().ok().map(|d| manifest_in(&d));
+            session_manifest
+                .and_then(|p| load_config(&p).ok())
+                .or_else(|| load_config(&config_path).ok())
+                .or_else(|| local_config.and_then(|p| load_config(&p).ok()))
+                .and_then(|c| c.workspace_dir())
+        });
+        workspace_dir
             .map(|dir| cleanup_skills(&dir))
             .unwrap_or_default()
     } else {
         Vec::new()
     };
 
-    detach_session(&resolved_name)?;
-    kill_session(&resolved_name)?;
-
     println!(
         "{} {} {}",
         "✔".green(),
@@ -279,6 +918,25 @@ pub fn do_kill_workspace(
 // Session Launching
 // =============================================================================
 
+/// Command-line flags shared by the grid and manifest launch paths.
+#[derive(Debug, Default)]
+pub struct LaunchOptions<'a> {
+    /// Restrict the grid to the named cells (`--only`)
+    pub only: &'a [String],
+    /// Attach in a new terminal window instead of this shell (`--attach-in`)
+    pub attach_in: Option<Terminal>,
+    /// Keep pane scrollback instead of clearing it (`--no-clear`)
+    pub no_clear: bool,
+    /// Print how long each launch phase took (`--profile-timing`)
+    pub profile_timing: bool,
+    /// Skill placeholder values (`--skill-var`)
+    pub skill_vars: &'a [String],
+    /// Put ahead of every AI pane's prompt (`axel pr`, `-w pr/<number>`)
+    pub prompt: Option<&'a str>,
+    /// The worktree was just created, so run its `worktree.setup` first
+    pub worktree_setup: bool,
+}
+
 /// Launch a specific grid layout by name.
 ///
 /// This allows launching a non-default grid from `axel session new --grid <name>`.
@@ -290,35 +948,63 @@ pub fn launch_grid_by_name(
     session_name: Option<&str>,
     pane_id: Option<&str>,
     server_port: Option<u16>,
+    opts: &LaunchOptions,
 ) -> Result<()> {
+    let LaunchOptions {
+        only,
+        no_clear,
+        skill_vars,
+        prompt,
+        worktree_setup,
+        ..
+    } = *opts;
+
     if !config_path.exists() {
-        eprintln!(
-            "{}",
-            format!("Manifest not found: {}", config_path.display()).red()
-        );
-        std::process::exit(1);
+        bail!(Error::ManifestNotFound(config_path.to_path_buf()));
     }
 
     // Use provided port or default to 4318
     let port = server_port.unwrap_or(4318);
 
+    let mut timings = Timings::start();
+    let mut config = load_config(config_path)?;
+    register(&config);
+    config.set_skills_vars(skill_vars)?;
+    timings.lap("config load");
+
     // If port is provided (macOS app mode), start embedded server in background thread
     if server_port.is_some() {
-        start_embedded_server(port, pane_id)?;
+        start_embedded_server(port, pane_id, &config.workspace, &config)?;
+    }
+    if no_clear {
+        config.disable_clear();
+    }
+    if let Some(prompt) = prompt {
+        config.seed_prompt(prompt);
     }
-
-    let config = load_config(config_path)?;
 
     // Validate grid exists
     if !config.layouts.grids.contains_key(grid_name) {
-        let available: Vec<&str> = config.layouts.grids.keys().map(|s| s.as_str()).collect();
-        eprintln!(
-            "{} Grid '{}' not found. Available grids: {}",
-            "✘".red(),
-            grid_name,
-            available.join(", ")
-        );
-        std::process::exit(1);
+        bail!(Error::GridNotFound {
+            name: grid_name.to_string(),
+            available: config.layouts.grids.keys().cloned().collect(),
+        });
+    }
+
+    if !only.is_empty() {
+        config.retain_grid_cells(Some(grid_name), only)?;
+    }
+    let single_pane = !only.is_empty() && is_single_ai_pane(&config, Some(grid_name));
+    if worktree_setup {
+        let in_terminal = single_pane || config.grid_type(Some(grid_name)) == GridType::Shell;
+        prepare_worktree_setup(&mut config, Some(grid_name), in_terminal)?;
+    }
+    if single_pane {
+        return launch_shell_mode(&config, Some(grid_name));
+    }
+    // The macOS app runs its own server, which doesn't route replies
+    if pane_id.is_none() {
+        wire_orchestrator(&mut config)?;
     }
 
     // Configure hooks/OTEL for AI panes if pane_id is provided (macOS app mode)
@@ -332,7 +1018,7 @@ pub fn launch_grid_by_name(
                 .iter()
                 .any(|p| matches!(p.config, PaneConfig::Claude(_)));
             if has_claude {
-                let hooks_settings = generate_hooks_settings(port, pane_id);
+                let hooks_settings = generate_hooks_settings(port, pane_id, &config.hooks);
                 let hooks_path = settings_path(install_dir);
                 if write_settings(&hooks_settings, &hooks_path).is_ok() {
                     eprintln!(
@@ -361,34 +1047,32 @@ pub fn launch_grid_by_name(
             .unwrap_or_else(|| config.workspace.clone())
     });
 
-    if has_session(&session) {
-        println!(
-            "{}",
-            format!("Attaching to existing session: {}", session).blue()
-        );
-        return match grid_type {
-            GridType::TmuxCC => {
-                std::process::Command::new("tmux")
-                    .args(["-CC", "attach-session", "-t", &session])
-                    .status()?;
-                Ok(())
-            }
-            _ => attach_session(&session),
-        };
-    }
-
     // Create OTEL config if pane_id is provided (macOS app mode)
     let otel_config = pane_id.map(|id| OtelConfig {
         port,
         pane_id: id.to_string(),
     });
 
-    match grid_type {
-        GridType::Shell => launch_shell_mode(&config, Some(grid_name)),
-        GridType::TmuxCC => {
-            launch_tmux_cc_mode_with_grid(config_path, &config, grid_name, &session, otel_config)
-        }
-        GridType::Tmux => launch_tmux_mode_with_grid(&config, grid_name, &session, otel_config),
+    match config.backend_kind(Some(grid_name)) {
+        Some(kind) => launch_backend_mode(
+            kind,
+            &config,
+            Some(grid_name),
+            &session,
+            otel_config,
+            opts,
+            &mut timings,
+        ),
+        None if grid_type == GridType::Shell => launch_shell_mode(&config, Some(grid_name)),
+        None => launch_tmux_cc_mode_with_grid(
+            config_path,
+            &config,
+            grid_name,
+            &session,
+            otel_config,
+            opts,
+            &mut timings,
+        ),
     }
 }
 
@@ -399,23 +1083,24 @@ fn launch_tmux_cc_mode_with_grid(
     grid_name: &str,
     session_name: &str,
     otel_config: Option<OtelConfig>,
+    opts: &LaunchOptions,
+    timings: &mut Timings,
 ) -> Result<()> {
+    let attach_in = opts.attach_in;
     if has_session(session_name) {
         println!(
             "{}",
             format!("Attaching to existing session (CC mode): {}", session_name).blue()
         );
-        std::process::Command::new("tmux")
-            .args(["-CC", "attach-session", "-t", session_name])
-            .status()?;
-        return Ok(());
+        return attach(session_name, true, attach_in);
     }
 
-    tmux_create_workspace(session_name, config, Some(grid_name), otel_config)?;
+    tmux_create_workspace(session_name, config, Some(grid_name), otel_config, timings)?;
 
     // Tag session with manifest path
     let manifest_str = config_path.to_string_lossy();
     set_environment(session_name, AXEL_MANIFEST_ENV, &manifest_str).ok();
+    start_orchestrator(session_name, config)?;
 
     println!(
         "{} {} {} (grid: {})",
@@ -424,60 +1109,32 @@ fn launch_tmux_cc_mode_with_grid(
         session_name,
         grid_name
     );
-
-    std::process::Command::new("tmux")
-        .args(["-CC", "attach-session", "-t", session_name])
-        .status()?;
-
-    Ok(())
-}
-
-/// Launch in standard tmux mode with a specific grid.
-fn launch_tmux_mode_with_grid(
-    config: &axel_core::WorkspaceConfig,
-    grid_name: &str,
-    session_name: &str,
-    otel_config: Option<OtelConfig>,
-) -> Result<()> {
-    if has_session(session_name) {
-        println!(
-            "{}",
-            format!("Attaching to existing session: {}", session_name).blue()
-        );
-        attach_session(session_name)?;
-        return Ok(());
-    }
-
-    tmux_create_workspace(session_name, config, Some(grid_name), otel_config)?;
-
-    // Tag session with manifest path
-    if let Some(ref manifest_path) = config.manifest_path {
-        let manifest_str = manifest_path.to_string_lossy();
-        set_environment(session_name, AXEL_MANIFEST_ENV, &manifest_str).ok();
+    if opts.profile_timing {
+        print_timings(timings);
     }
 
-    println!(
-        "{} {} {} (grid: {})",
-        "✔".green(),
-        "Created tmux session".dimmed(),
-        session_name,
-        grid_name
-    );
-    attach_session(session_name)?;
-
-    Ok(())
+    attach(session_name, true, attach_in)
 }
 
 /// Launch a workspace from a manifest file.
 ///
 /// This is the main launch path when running `axel` with an `AXEL.md` present.
-pub fn launch_from_manifest(config_path: &Path, profile: Option<&str>) -> Result<()> {
+pub fn launch_from_manifest(
+    config_path: &Path,
+    profile: Option<&str>,
+    opts: &LaunchOptions,
+) -> Result<()> {
+    let LaunchOptions {
+        only,
+        no_clear,
+        skill_vars,
+        prompt,
+        worktree_setup,
+        ..
+    } = *opts;
+
     if !config_path.exists() {
-        eprintln!(
-            "{}",
-            format!("Manifest not found: {}", config_path.display()).red()
-        );
-        std::process::exit(1);
+        bail!(Error::ManifestNotFound(config_path.to_path_buf()));
     }
 
     let session_name = config_path
@@ -486,108 +1143,165 @@ pub fn launch_from_manifest(config_path: &Path, profile: Option<&str>) -> Result
         .map(|n| n.to_string_lossy().to_string())
         .unwrap_or_default();
 
-    let config = load_config(config_path)?;
+    let mut timings = Timings::start();
+    let mut config = load_config(config_path)?;
+    register(&config);
+    config.set_skills_vars(skill_vars)?;
+    timings.lap("config load");
+    if no_clear {
+        config.disable_clear();
+    }
+    if let Some(prompt) = prompt {
+        config.seed_prompt(prompt);
+    }
+
+    if !only.is_empty() {
+        config.retain_grid_cells(profile, only)?;
+    }
+    let single_pane = !only.is_empty() && is_single_ai_pane(&config, profile);
+    if worktree_setup {
+        let in_terminal = single_pane || config.grid_type(profile) == GridType::Shell;
+        prepare_worktree_setup(&mut config, profile, in_terminal)?;
+    }
+    if single_pane {
+        return launch_shell_mode(&config, profile);
+    }
+    wire_orchestrator(&mut config)?;
+
     let grid_type = config.grid_type(profile);
 
-    if !session_name.is_empty() && has_session(&session_name) {
-        // Check if this session belongs to a different workspace
-        let current_manifest = config_path.to_path_buf();
+    let session_name = if session_name.is_empty() {
+        config.workspace.clone()
+    } else {
+        session_name
+    };
 
-        if let Some(existing_manifest) = get_environment(&session_name, AXEL_MANIFEST_ENV) {
-            let existing_path = PathBuf::from(&existing_manifest);
-            if existing_path != current_manifest {
-                eprintln!(
-                    "{} A session named '{}' already exists for a different workspace:",
-                    "✘".red(),
-                    session_name
-                );
-                eprintln!(
-                    "  {} {}",
-                    "existing:".dimmed(),
-                    display_path(&existing_path)
-                );
-                eprintln!(
-                    "  {} {}",
-                    "current: ".dimmed(),
-                    display_path(&current_manifest)
-                );
-                eprintln!();
-                eprintln!(
-                    "{}",
-                    "To fix this, update the 'workspace' field in your AXEL.md to use a unique name.".yellow()
-                );
-                std::process::exit(1);
-            }
+    // A tmux session of the same name may run another workspace
+    if let Some(existing_manifest) = get_environment(&session_name, AXEL_MANIFEST_ENV) {
+        let existing_path = PathBuf::from(&existing_manifest);
+        if existing_path != config_path {
+            bail!(Error::SessionConflict {
+                name: session_name,
+                existing: existing_path,
+            });
         }
-
-        println!(
-            "{}",
-            format!("Attaching to existing session: {}", session_name).blue()
-        );
-        return match grid_type {
-            GridType::TmuxCC => {
-                std::process::Command::new("tmux")
-                    .args(["-CC", "attach-session", "-t", &session_name])
-                    .status()?;
-                Ok(())
-            }
-            _ => attach_session(&session_name),
-        };
     }
 
-    match grid_type {
-        GridType::Shell => launch_shell_mode(&config, profile),
-        GridType::TmuxCC => launch_tmux_cc_mode(config_path, &config, profile),
-        GridType::Tmux => launch_tmux_mode(&config, profile),
+    match config.backend_kind(profile) {
+        Some(kind) => launch_backend_mode(
+            kind,
+            &config,
+            profile,
+            &session_name,
+            None,
+            opts,
+            &mut timings,
+        ),
+        None if grid_type == GridType::Shell => launch_shell_mode(&config, profile),
+        None => launch_tmux_cc_mode(config_path, &config, profile, opts, &mut timings),
     }
 }
 
-/// Launch in shell mode (no tmux, just run the first shell).
+/// Launch in shell mode: run the grid's panes in this terminal, without a
+/// multiplexer.
+///
+/// The grid's `run:` key selects the panes:
+/// - `first` (default): exec the first pane; the others are skipped with a
+///   warning
+/// - `pick`: ask which pane to run, then exec it
+/// - `jobs`: run every pane with a command as a background job, prefixing
+///   each line of its output with the pane name, and wait for all of them
 fn launch_shell_mode(config: &axel_core::WorkspaceConfig, profile: Option<&str>) -> Result<()> {
-    use std::os::unix::process::CommandExt;
-
     let panes = config.resolve_panes(profile);
-    let index = config.load_index();
+    let index = config.load_index()?;
 
     if panes.is_empty() {
         anyhow::bail!("No shells defined in profile");
     }
 
-    let first_pane = &panes[0];
+    let pane = match config.grid_shell_run(profile) {
+        _ if panes.len() == 1 => &panes[0],
+        ShellRun::First => {
+            let skipped: Vec<&str> = panes[1..].iter().map(|p| p.name.as_str()).collect();
+            eprintln!(
+                "{} Running {} only, skipping {} {}",
+                "⚠".yellow(),
+                panes[0].name,
+                skipped.join(", "),
+                "(set `run: jobs` or `run: pick` on the grid)".dimmed()
+            );
+            &panes[0]
+        }
+        ShellRun::Pick => {
+            let names: Vec<String> = panes.iter().map(|p| p.name.clone()).collect();
+            let picked = FuzzyPicker::new("Pane:", &names, |i| {
+                pane_command(&panes[i].config, None, None, None, None)
+                    .map(|cmd| vec![cmd.dimmed().to_string()])
+                    .unwrap_or_default()
+            })
+            .interact()?;
+            match picked {
+                Some(i) => &panes[i],
+                None => return Ok(()),
+            }
+        }
+        ShellRun::Jobs => return run_shell_jobs(config, &panes, index.as_ref()),
+    };
+
+    let work_dir = prepare_shell_pane(config, pane);
+    let command = pane_command(
+        &pane.config,
+        config.workspace_dir().as_deref(),
+        index.as_ref(),
+        None,
+        None,
+    );
+
+    if let Some(ref dir) = work_dir {
+        std::env::set_current_dir(dir)?;
+    }
+
+    let err = match command {
+        Some(cmd) => platform::exec(std::process::Command::new("sh").arg("-c").arg(&cmd)),
+        None => platform::exec(&mut std::process::Command::new(platform::user_shell())),
+    };
+    Err(err.into())
+}
 
-    let work_dir = first_pane
+/// Install skills, the index file and MCP servers for a pane run in shell
+/// mode, and return its working directory
+fn prepare_shell_pane(
+    config: &axel_core::WorkspaceConfig,
+    pane: &axel_core::ResolvedPane,
+) -> Option<PathBuf> {
+    let work_dir = pane
         .path()
         .map(|p| PathBuf::from(expand_path(p)))
         .or_else(|| config.workspace_dir());
 
     if let Some(ref workspace_dir) = work_dir {
-        let (driver_name, skill_names) = match &first_pane.config {
+        let (driver_name, skill_names) = match &pane.config {
             PaneConfig::Claude(c) => ("claude", &c.skills),
             PaneConfig::Codex(c) => ("codex", &c.skills),
             PaneConfig::Opencode(c) => ("opencode", &c.skills),
             PaneConfig::Antigravity(c) => ("antigravity", &c.skills),
+            PaneConfig::External(c) => (c.pane_type.as_str(), &c.skills),
             PaneConfig::Custom(_) => ("", &Vec::new()),
         };
 
         if !skill_names.is_empty()
             && let Some(driver) = drivers::get_driver(driver_name)
         {
-            let skill_paths = config.resolve_skills(skill_names);
-            if let Some(count) = driver
-                .install_skills(workspace_dir, &skill_paths)
-                .ok()
-                .filter(|&c| c > 0)
-            {
-                let skills_word = if count == 1 { "skill" } else { "skills" };
-                eprintln!(
-                    "{} {} {} {} for {}",
-                    "✔".green(),
-                    "Installed".dimmed(),
-                    count,
-                    skills_word,
-                    driver.name()
-                );
-            }
+            let skill_paths = render_skills(
+                config,
+                config.resolve_skills(skill_names),
+                workspace_dir,
+                driver.name(),
+            );
+            report_skill_install(
+                driver.name(),
+                driver.install_skills(workspace_dir, &skill_paths),
+            );
         }
 
         // Install index file (CLAUDE.md, AGENTS.md, etc.) for the driver
@@ -596,31 +1310,159 @@ fn launch_shell_mode(config: &axel_core::WorkspaceConfig, profile: Option<&str>)
             && driver.install_index(config, workspace_dir).unwrap_or(false)
         {
             eprintln!(
-                "{} {} {} symlink",
+                "{} {} index in {}",
                 "✔".green(),
-                "Created".dimmed(),
+                "Installed".dimmed(),
                 filename
             );
         }
     }
 
-    let command = build_pane_command(&first_pane.config, index.as_ref(), None);
+    if let Some(dir) = config.workspace_dir() {
+        install_pane_mcp(&pane.config, &dir);
+    }
 
-    if let Some(ref dir) = work_dir {
-        std::env::set_current_dir(dir)?;
+    work_dir
+}
+
+/// Run every pane with a command as a background job, prefixing each line
+/// of its output with the pane name, until all of them exit.
+///
+/// The skills installed for the jobs are cleaned up however they end.
+fn run_shell_jobs(
+    config: &axel_core::WorkspaceConfig,
+    panes: &[axel_core::ResolvedPane],
+    index: Option<&axel_core::WorkspaceIndex>,
+) -> Result<()> {
+    let mut work_dirs = BTreeSet::new();
+    let result = run_jobs(config, panes, index, &mut work_dirs);
+
+    for dir in work_dirs {
+        let cleaned = cleanup_skills(&dir);
+        if !cleaned.is_empty() {
+            eprintln!(
+                "{} {} {} artifacts",
+                "✔".green(),
+                "Cleaned".dimmed(),
+                format_cleaned_drivers(&cleaned)
+            );
+        }
     }
+    result
+}
 
-    if let Some(cmd) = command {
-        let err = std::process::Command::new("sh").arg("-c").arg(&cmd).exec();
-        Err(err.into())
-    } else {
-        let shell = std::env::var("SHELL").unwrap_or_else(|_| "sh".to_string());
-        let err = std::process::Command::new(&shell).exec();
-        Err(err.into())
+/// Start the jobs of [`run_shell_jobs`] and wait for them, recording the
+/// directories skills were installed in.
+///
+/// If a job fails to start, the ones already running are killed.
+fn run_jobs(
+    config: &axel_core::WorkspaceConfig,
+    panes: &[axel_core::ResolvedPane],
+    index: Option<&axel_core::WorkspaceIndex>,
+    work_dirs: &mut BTreeSet<PathBuf>,
+) -> Result<()> {
+    use std::{
+        io::{BufRead, BufReader, Read},
+        process::Stdio,
+    };
+
+    const COLORS: [colored::Color; 6] = [
+        colored::Color::Cyan,
+        colored::Color::Magenta,
+        colored::Color::Green,
+        colored::Color::Yellow,
+        colored::Color::Blue,
+        colored::Color::Red,
+    ];
+
+    let width = panes.iter().map(|p| p.name.len()).max().unwrap_or(0);
+    let mut jobs: Vec<(&str, std::process::Child)> = Vec::new();
+    let mut printers: Vec<std::thread::JoinHandle<()>> = Vec::new();
+
+    for (i, pane) in panes.iter().enumerate() {
+        let work_dir = prepare_shell_pane(config, pane);
+        work_dirs.extend(work_dir.clone());
+        let Some(command) = pane_command(
+            &pane.config,
+            config.workspace_dir().as_deref(),
+            index,
+            None,
+            None,
+        ) else {
+            eprintln!(
+                "{} {} {}",
+                "·".dimmed(),
+                pane.name,
+                "has no command, skipped".dimmed()
+            );
+            continue;
+        };
+
+        let mut cmd = std::process::Command::new("sh");
+        cmd.arg("-c")
+            .arg(&command)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+        if let Some(dir) = work_dir {
+            cmd.current_dir(dir);
+        }
+        let mut child = match cmd.spawn() {
+            Ok(child) => child,
+            Err(e) => {
+                for (_, mut job) in jobs {
+                    let _ = job.kill();
+                    let _ = job.wait();
+                }
+                for printer in printers {
+                    let _ = printer.join();
+                }
+                return Err(e).with_context(|| format!("Failed to start pane '{}'", pane.name));
+            }
+        };
+
+        let prefix = format!("{:>width$} │", pane.name)
+            .color(COLORS[i % COLORS.len()])
+            .to_string();
+        let outputs: [Box<dyn Read + Send>; 2] = [
+            Box::new(child.stdout.take().expect("piped stdout")),
+            Box::new(child.stderr.take().expect("piped stderr")),
+        ];
+        for output in outputs {
+            let prefix = prefix.clone();
+            printers.push(std::thread::spawn(move || {
+                for line in BufReader::new(output).lines().map_while(Result::ok) {
+                    println!("{} {}", prefix, line);
+                }
+            }));
+        }
+        jobs.push((pane.name.as_str(), child));
+    }
+
+    if jobs.is_empty() {
+        bail!("No panes with a command to run");
+    }
+
+    let mut failed = Vec::new();
+    for (name, mut child) in jobs {
+        match child.wait() {
+            Ok(status) if status.success() => {}
+            Ok(status) => failed.push(format!("{} ({})", name, status)),
+            Err(e) => failed.push(format!("{} ({})", name, e)),
+        }
     }
+    for printer in printers {
+        let _ = printer.join();
+    }
+
+    if !failed.is_empty() {
+        bail!("Panes exited with errors: {}", failed.join(", "));
+    }
+    Ok(())
 }
 
 /// Launch a specific pane by name from the manifest.
+#[allow(clippy::too_many_arguments)]
 pub fn launch_pane_by_name(
     manifest_path: &Path,
     pane_name: &str,
@@ -629,18 +1471,25 @@ pub fn launch_pane_by_name(
     server_port: Option<u16>,
     use_tmux: bool,
     session_name: Option<&str>,
+    skill_vars: &[String],
+    worktree_setup: bool,
 ) -> Result<()> {
     // Use provided port or default to 4318
     let port = server_port.unwrap_or(4318);
 
+    let mut config = load_config(manifest_path)?;
+    register(&config);
+    config.set_skills_vars(skill_vars)?;
+    if worktree_setup {
+        prepare_worktree_setup(&mut config, None, true)?;
+    }
+
     // If port is provided (macOS app mode), start embedded server in background thread
     // The server will automatically terminate when this process exits
     if server_port.is_some() {
-        start_embedded_server(port, pane_id)?;
+        start_embedded_server(port, pane_id, pane_name, &config)?;
     }
-
-    let config = load_config(manifest_path)?;
-    let index = config.load_index();
+    let index = config.load_index()?;
 
     let pane_config = config
         .layouts
@@ -664,53 +1513,13 @@ pub fn launch_pane_by_name(
     let current_dir = std::env::current_dir().ok();
 
     if let Some(ref install_dir) = current_dir {
-        let (driver_name, skill_names) = match pane_config {
-            PaneConfig::Claude(c) => ("claude", &c.skills),
-            PaneConfig::Codex(c) => ("codex", &c.skills),
-            PaneConfig::Opencode(c) => ("opencode", &c.skills),
-            PaneConfig::Antigravity(c) => ("antigravity", &c.skills),
-            PaneConfig::Custom(_) => ("", &Vec::new()),
-        };
-
-        if !skill_names.is_empty()
-            && let Some(driver) = drivers::get_driver(driver_name)
-        {
-            let skill_paths = config.resolve_skills(skill_names);
-            if let Some(count) = driver
-                .install_skills(install_dir, &skill_paths)
-                .ok()
-                .filter(|&c| c > 0)
-            {
-                let skills_word = if count == 1 { "skill" } else { "skills" };
-                eprintln!(
-                    "{} {} {} {} for {}",
-                    "✔".green(),
-                    "Installed".dimmed(),
-                    count,
-                    skills_word,
-                    driver.name()
-                );
-            }
-        }
-
-        // Install index file (CLAUDE.md, AGENTS.md, etc.) for the driver
-        if let Some(driver) = drivers::get_driver(driver_name)
-            && let Some(filename) = driver.index_filename()
-            && driver.install_index(&config, install_dir).unwrap_or(false)
-        {
-            eprintln!(
-                "{} {} {} symlink",
-                "✔".green(),
-                "Created".dimmed(),
-                filename
-            );
-        }
+        install_pane_skills(&config, pane_config, install_dir);
 
         // Configure Claude hooks if pane_id is provided (for macOS app integration)
         if matches!(pane_config, PaneConfig::Claude(_))
             && let Some(pane_id) = pane_id
         {
-            let hooks_settings = generate_hooks_settings(port, pane_id);
+            let hooks_settings = generate_hooks_settings(port, pane_id, &config.hooks);
             let hooks_path = settings_path(install_dir);
             if write_settings(&hooks_settings, &hooks_path).is_ok() {
                 eprintln!(
@@ -724,7 +1533,17 @@ pub fn launch_pane_by_name(
         }
     }
 
-    let command = build_pane_command(pane_config, index.as_ref(), prompt_override);
+    if let Some(dir) = config.workspace_dir() {
+        install_pane_mcp(pane_config, &dir);
+    }
+
+    let command = pane_command(
+        pane_config,
+        config.workspace_dir().as_deref(),
+        index.as_ref(),
+        None,
+        prompt_override,
+    );
 
     // Get the driver for this pane type to check OTEL support
     let driver_name = match pane_config {
@@ -732,13 +1551,13 @@ pub fn launch_pane_by_name(
         PaneConfig::Codex(_) => "codex",
         PaneConfig::Opencode(_) => "opencode",
         PaneConfig::Antigravity(_) => "antigravity",
+        PaneConfig::External(c) => c.pane_type.as_str(),
         PaneConfig::Custom(_) => "",
     };
 
     // If --tmux is specified, create a tmux session instead of running directly
     if use_tmux {
-        let base_cmd =
-            command.unwrap_or_else(|| std::env::var("SHELL").unwrap_or_else(|_| "sh".to_string()));
+        let base_cmd = command.unwrap_or_else(platform::user_shell);
 
         // Generate session name if not provided
         let session = if let Some(name) = session_name {
@@ -759,7 +1578,7 @@ pub fn launch_pane_by_name(
                         // Use environment variables (Claude, OpenCode)
                         let env_prefix: String = otel_vars
                             .iter()
-                            .map(|(k, v)| format!("{}={}", k, v))
+                            .map(|(k, v)| format!("{}={}", k, shell::quote(v)))
                             .collect::<Vec<_>>()
                             .join(" ");
                         eprintln!(
@@ -772,7 +1591,7 @@ pub fn launch_pane_by_name(
                     } else if !otel_args.is_empty() {
                         // Use CLI arguments (Codex)
                         // Insert OTEL args after the command name but before the prompt
-                        let args_str = otel_args.join(" ");
+                        let args_str = shell::join(&otel_args);
                         eprintln!(
                             "{} {} OTEL telemetry for {}",
                             "✔".green(),
@@ -837,12 +1656,12 @@ pub fn launch_pane_by_name(
             && let Some(hook_cmd) = driver.tmux_bell_hook_command(port, &session)
         {
             // Enable bell monitoring on the window
-            let _ = std::process::Command::new("tmux")
+            let _ = tmux_command()
                 .args(["set-option", "-t", &session, "monitor-bell", "on"])
                 .status();
 
             // Set up the alert-bell hook
-            let _ = std::process::Command::new("tmux")
+            let _ = tmux_command()
                 .args(["set-hook", "-t", &session, "alert-bell", &hook_cmd])
                 .status();
 
@@ -892,7 +1711,7 @@ pub fn launch_pane_by_name(
 
             if !otel_args.is_empty() {
                 // Append CLI args to the command (Codex)
-                let args_str = otel_args.join(" ");
+                let args_str = shell::join(&otel_args);
                 // Insert OTEL args after the command name but before the prompt
                 if let Some(space_idx) = cmd.find(' ') {
                     let (cmd_name, rest) = cmd.split_at(space_idx);
@@ -926,7 +1745,7 @@ pub fn launch_pane_by_name(
         process.status()
     } else {
         eprintln!("{}", "No command built, falling back to shell".red());
-        let shell = std::env::var("SHELL").unwrap_or_else(|_| "sh".to_string());
+        let shell = platform::user_shell();
         std::process::Command::new(&shell).status()
     };
 
@@ -946,6 +1765,129 @@ pub fn launch_pane_by_name(
     Ok(())
 }
 
+/// Run `prompt` (or the pane's own prompt) once in AI pane `pane_name`
+/// without its interactive UI, for scripts and CI.
+///
+/// The agent's reply goes to stdout and axel's messages to stderr. Skills
+/// are installed for the run and cleaned up afterwards; the command fails
+/// if the agent exits with an error.
+pub fn run_pane(
+    manifest_path: &Path,
+    pane_name: &str,
+    prompt: Option<&str>,
+    skill_vars: &[String],
+) -> Result<()> {
+    let mut config = load_config(manifest_path)?;
+    config.set_skills_vars(skill_vars)?;
+
+    let Some(pane_config) = config
+        .layouts
+        .panes
+        .iter()
+        .find(|p| p.pane_type() == pane_name)
+    else {
+        bail!(
+            "Pane '{}' not found in manifest. Available panes: {}",
+            pane_name,
+            config
+                .layouts
+                .panes
+                .iter()
+                .map(|p| p.pane_type())
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+    };
+    let (PaneConfig::Claude(ai) | PaneConfig::Codex(ai) | PaneConfig::Opencode(ai)) = pane_config
+    else {
+        bail!(
+            "Pane '{}' has no one-shot mode (claude, codex and opencode panes do)",
+            pane_name
+        );
+    };
+    let Some(prompt) = prompt.or(ai.prompt.as_deref()) else {
+        bail!(
+            "No prompt for pane '{}': pass one, or use --prompt-file",
+            pane_name
+        );
+    };
+
+    let install_dir = std::env::current_dir()?;
+    install_pane_skills(&config, pane_config, &install_dir);
+    let workspace_dir = config.workspace_dir();
+    if let Some(ref dir) = workspace_dir {
+        install_pane_mcp(pane_config, dir);
+    }
+
+    let command = one_shot_command(pane_config, workspace_dir.as_deref(), prompt)?;
+    let status = std::process::Command::new("sh")
+        .arg("-c")
+        .arg(&command)
+        .stdin(std::process::Stdio::null())
+        .status();
+
+    let cleaned = cleanup_skills(&install_dir);
+    if !cleaned.is_empty() {
+        eprintln!(
+            "{} {} {} artifacts",
+            "✔".green(),
+            "Cleaned".dimmed(),
+            format_cleaned_drivers(&cleaned)
+        );
+    }
+
+    let status = status.context("Failed to start the agent")?;
+    if !status.success() {
+        bail!("{} exited with {}", pane_config.actual_type(), status);
+    }
+    Ok(())
+}
+
+/// Install a single pane's skills and index file into `install_dir`
+pub(crate) fn install_pane_skills(
+    config: &WorkspaceConfig,
+    pane_config: &PaneConfig,
+    install_dir: &Path,
+) {
+    let (driver_name, skill_names) = match pane_config {
+        PaneConfig::Claude(c) => ("claude", &c.skills),
+        PaneConfig::Codex(c) => ("codex", &c.skills),
+        PaneConfig::Opencode(c) => ("opencode", &c.skills),
+        PaneConfig::Antigravity(c) => ("antigravity", &c.skills),
+        PaneConfig::External(c) => (c.pane_type.as_str(), &c.skills),
+        // Custom panes have no driver
+        PaneConfig::Custom(_) => return,
+    };
+
+    if !skill_names.is_empty()
+        && let Some(driver) = drivers::get_driver(driver_name)
+    {
+        let skill_paths = render_skills(
+            config,
+            config.resolve_skills(skill_names),
+            install_dir,
+            driver.name(),
+        );
+        report_skill_install(
+            driver.name(),
+            driver.install_skills(install_dir, &skill_paths),
+        );
+    }
+
+    // Install index file (CLAUDE.md, AGENTS.md, etc.) for the driver
+    if let Some(driver) = drivers::get_driver(driver_name)
+        && let Some(filename) = driver.index_filename()
+        && driver.install_index(config, install_dir).unwrap_or(false)
+    {
+        eprintln!(
+            "{} {} index in {}",
+            "✔".green(),
+            "Installed".dimmed(),
+            filename
+        );
+    }
+}
+
 /// Generate a unique session name for a shell.
 ///
 /// Format: `{workspace}-{shell}-{index}` where index increments to avoid collisions.
@@ -981,7 +1923,10 @@ fn launch_tmux_cc_mode(
     config_path: &Path,
     config: &axel_core::WorkspaceConfig,
     profile: Option<&str>,
+    opts: &LaunchOptions,
+    timings: &mut Timings,
 ) -> Result<()> {
+    let attach_in = opts.attach_in;
     let session_name = config_path
         .parent()
         .and_then(|p| p.file_name())
@@ -993,153 +1938,357 @@ fn launch_tmux_cc_mode(
             "{}",
             format!("Attaching to existing session (CC mode): {}", session_name).blue()
         );
-        std::process::Command::new("tmux")
-            .args(["-CC", "attach-session", "-t", &session_name])
-            .status()?;
-        return Ok(());
+        return attach(&session_name, true, attach_in);
     }
 
-    tmux_create_workspace(&session_name, config, profile, None)?;
+    tmux_create_workspace(&session_name, config, profile, None, timings)?;
+    start_orchestrator(&session_name, config)?;
     println!(
         "{} {} {}",
         "✔".green(),
         "Created tmux session (CC mode)".dimmed(),
         config.workspace
     );
+    if opts.profile_timing {
+        print_timings(timings);
+    }
 
-    std::process::Command::new("tmux")
-        .args(["-CC", "attach-session", "-t", &session_name])
-        .status()?;
-
-    Ok(())
+    attach(&session_name, true, attach_in)
 }
 
-/// Launch in standard tmux mode.
-fn launch_tmux_mode(config: &axel_core::WorkspaceConfig, profile: Option<&str>) -> Result<()> {
-    let session_name = config
-        .manifest_path
-        .as_ref()
-        .and_then(|p| p.parent())
+/// Create the tmux session of the workspace at `config_path` in the
+/// background, for `axel up`.
+///
+/// Returns the session's name and whether it was created; a session that is
+/// already running is left alone. Only tmux grids can start this way.
+pub fn launch_detached(config_path: &Path, grid: Option<&str>) -> Result<(String, bool)> {
+    if !config_path.exists() {
+        bail!(Error::ManifestNotFound(config_path.to_path_buf()));
+    }
+    let mut config = load_config(config_path)?;
+    register(&config);
+    config.tmux.use_server();
+    if let Some(name) = grid
+        && !config.layouts.grids.contains_key(name)
+    {
+        bail!(Error::GridNotFound {
+            name: name.to_string(),
+            available: config.layouts.grids.keys().cloned().collect(),
+        });
+    }
+
+    let session_name = config_path
+        .parent()
         .and_then(|p| p.file_name())
         .map(|n| n.to_string_lossy().to_string())
         .unwrap_or_else(|| config.workspace.clone());
-
     if has_session(&session_name) {
+        if let Some(existing) = get_environment(&session_name, AXEL_MANIFEST_ENV)
+            && Path::new(&existing) != config_path
+        {
+            bail!(Error::SessionConflict {
+                name: session_name,
+                existing: PathBuf::from(existing),
+            });
+        }
+        return Ok((session_name, false));
+    }
+
+    let grid_type = config.grid_type(grid);
+    if !matches!(grid_type, GridType::Tmux | GridType::TmuxCC)
+        || config
+            .backend_kind(grid)
+            .is_some_and(|kind| kind != BackendKind::Tmux)
+    {
+        bail!(
+            "Workspace '{}' doesn't launch a tmux grid, so it can't start in the background",
+            config.workspace
+        );
+    }
+
+    // Panes without a `path` start in the current directory
+    if let Some(dir) = config.workspace_dir() {
+        std::env::set_current_dir(dir)?;
+    }
+    wire_orchestrator(&mut config)?;
+    get_backend(BackendKind::Tmux).create_workspace(
+        &session_name,
+        &config,
+        grid,
+        None,
+        &mut Timings::start(),
+    )?;
+    set_environment(
+        &session_name,
+        AXEL_MANIFEST_ENV,
+        &config_path.to_string_lossy(),
+    )
+    .ok();
+    if let Some(dir) = config.workspace_dir() {
+        record_session(&session_name, &dir).ok();
+    }
+    start_orchestrator(&session_name, &config)?;
+    Ok((session_name, true))
+}
+
+/// Launch in the backend of `kind`, attaching to an existing session of the
+/// same name instead of creating one.
+///
+/// tmux sessions also start the orchestrator.
+fn launch_backend_mode(
+    kind: BackendKind,
+    config: &axel_core::WorkspaceConfig,
+    grid: Option<&str>,
+    session_name: &str,
+    otel_config: Option<OtelConfig>,
+    opts: &LaunchOptions,
+    timings: &mut Timings,
+) -> Result<()> {
+    let backend = get_backend(kind);
+    if backend.has_session(session_name) {
         println!(
             "{}",
             format!("Attaching to existing session: {}", session_name).blue()
         );
-        attach_session(&session_name)?;
-        return Ok(());
+        return attach_backend(&*backend, session_name, opts.attach_in);
     }
 
-    tmux_create_workspace(&session_name, config, profile, None)?;
+    backend.create_workspace(session_name, config, grid, otel_config, timings)?;
+    if let Some(dir) = config.workspace_dir() {
+        record_session(session_name, &dir).ok();
+    }
+    if kind == BackendKind::Tmux {
+        start_orchestrator(session_name, config)?;
+    }
     println!(
         "{} {} {}",
         "✔".green(),
-        "Created tmux session".dimmed(),
-        config.workspace
+        format!("Created {} session", backend.name()).dimmed(),
+        session_name
     );
-    attach_session(&session_name)?;
-
-    Ok(())
+    if opts.profile_timing {
+        print_timings(timings);
+    }
+    attach_backend(&*backend, session_name, opts.attach_in)
 }
 
 // =============================================================================
 // Helpers
 // =============================================================================
 
-/// Build the command string for a given pane config.
+/// Attach to a session, in this terminal or in a new window of `attach_in`.
 ///
-/// If `prompt_override` is provided, it takes precedence over the prompt
-/// defined in the pane config or the workspace index.
-fn build_pane_command(
-    pane_config: &PaneConfig,
-    index: Option<&axel_core::WorkspaceIndex>,
-    prompt_override: Option<&str>,
-) -> Option<String> {
-    match pane_config {
-        PaneConfig::Claude(c) => {
-            let mut cmd = ClaudeCommand::new();
-            if let Some(model) = &c.model {
-                cmd = cmd.model(model);
-            }
-            if !c.allowed_tools.is_empty() {
-                cmd = cmd.allowed_tools(c.allowed_tools.clone());
-            }
-            if !c.disallowed_tools.is_empty() {
-                cmd = cmd.disallowed_tools(c.disallowed_tools.clone());
-            }
-            if let Some(prompt) = prompt_override.or(c.prompt.as_deref()) {
-                cmd = cmd.prompt(prompt);
-            }
-            for arg in &c.args {
-                cmd = cmd.extra_arg(arg);
-            }
-            Some(cmd.build())
+/// `control_mode` attaches with `-CC` for iTerm2 integration.
+pub fn attach(session: &str, control_mode: bool, attach_in: Option<Terminal>) -> Result<()> {
+    let program = tmux_program();
+    let mut command: Vec<&str> = program.iter().map(String::as_str).collect();
+    if control_mode {
+        command.push("-CC");
+    }
+    command.extend(["attach-session", "-t", session]);
+
+    match attach_in {
+        Some(terminal) => {
+            terminal.open(&command)?;
+            eprintln!(
+                "{} {} {} in {}",
+                "✔".green(),
+                "Attaching".dimmed(),
+                session,
+                terminal
+            );
+            Ok(())
         }
-        PaneConfig::Codex(c) => {
-            let mut parts = vec!["codex".to_string()];
-            if let Some(model) = &c.model {
-                parts.push("-m".to_string());
-                parts.push(model.clone());
-            }
-            for arg in &c.args {
-                parts.push(arg.clone());
-            }
-            if let Some(prompt) = prompt_override.or(c.prompt.as_deref()) {
-                let escaped = prompt.replace('\'', "'\\''");
-                parts.push(format!("'{}'", escaped));
-            } else if let Some(idx) = index {
-                let escaped = idx.to_initial_prompt().replace('\'', "'\\''");
-                parts.push(format!("'{}'", escaped));
-            }
-            Some(parts.join(" "))
+        None if control_mode => {
+            std::process::Command::new(command[0])
+                .args(&command[1..])
+                .status()?;
+            Ok(())
         }
-        PaneConfig::Opencode(c) => {
-            let mut parts = vec!["opencode".to_string()];
-            if let Some(model) = &c.model {
-                parts.push("-m".to_string());
-                parts.push(model.clone());
-            }
-            for arg in &c.args {
-                parts.push(arg.clone());
-            }
-            if let Some(prompt) = prompt_override.or(c.prompt.as_deref()) {
-                let escaped = prompt.replace('\'', "'\\''");
-                parts.push(format!("'{}'", escaped));
-            } else if let Some(idx) = index {
-                let escaped = idx.to_initial_prompt().replace('\'', "'\\''");
-                parts.push(format!("'{}'", escaped));
-            }
-            Some(parts.join(" "))
+        None => attach_session(session),
+    }
+}
+
+/// Attach to a backend session, here or in a new `attach_in` window.
+fn attach_backend(backend: &dyn Backend, session: &str, attach_in: Option<Terminal>) -> Result<()> {
+    let command = backend.attach_command(session);
+    let command: Vec<&str> = command.iter().map(String::as_str).collect();
+
+    match attach_in {
+        Some(terminal) => {
+            terminal.open(&command)?;
+            eprintln!(
+                "{} {} {} in {}",
+                "✔".green(),
+                "Attaching".dimmed(),
+                session,
+                terminal
+            );
         }
-        PaneConfig::Antigravity(c) => {
-            let mut parts = vec!["antigravity".to_string()];
-            if let Some(model) = &c.model {
-                parts.push("-m".to_string());
-                parts.push(model.clone());
-            }
-            for arg in &c.args {
-                parts.push(arg.clone());
-            }
-            if let Some(prompt) = prompt_override.or(c.prompt.as_deref()) {
-                let escaped = prompt.replace('\'', "'\\''");
-                parts.push(format!("'{}'", escaped));
-            } else if let Some(idx) = index {
-                let escaped = idx.to_initial_prompt().replace('\'', "'\\''");
-                parts.push(format!("'{}'", escaped));
-            }
-            Some(parts.join(" "))
+        None => {
+            std::process::Command::new(command[0])
+                .args(&command[1..])
+                .status()?;
         }
-        PaneConfig::Custom(c) => c.command.clone(),
     }
+    Ok(())
+}
+
+/// Print the `--profile-timing` summary to stderr.
+fn print_timings(timings: &Timings) {
+    eprintln!();
+    eprintln!("{}", "Launch timing".bold());
+    eprint!("{}", timings.report());
+}
+
+/// Whether the grid resolves to exactly one AI pane (launched without tmux).
+/// Run `worktree.setup` for a worktree this launch created.
+///
+/// When the launch runs in this terminal, the commands run here before the
+/// pane's command. Otherwise they run in the grid's first pane and the other
+/// panes wait for them (see [`WorkspaceConfig::schedule_worktree_setup`]).
+fn prepare_worktree_setup(
+    config: &mut WorkspaceConfig,
+    grid_name: Option<&str>,
+    in_terminal: bool,
+) -> Result<()> {
+    let Some(setup) = config.worktree.setup_command() else {
+        return Ok(());
+    };
+
+    if !in_terminal {
+        let marker = std::env::temp_dir().join(format!("axel-setup-{}", std::process::id()));
+        std::fs::remove_file(&marker).ok();
+        config.schedule_worktree_setup(grid_name, &marker);
+        return Ok(());
+    }
+
+    eprintln!("{} {}", "Running worktree setup:".dimmed(), setup);
+    let mut command = std::process::Command::new("sh");
+    command.arg("-c").arg(&setup);
+    if let Some(dir) = config.workspace_dir() {
+        command.current_dir(dir);
+    }
+    let status = command.status()?;
+    if !status.success() {
+        eprintln!(
+            "{} Worktree setup failed ({}); launching anyway",
+            "!".yellow(),
+            status
+        );
+    }
+    Ok(())
+}
+
+/// Remember the workspace for `axel open`. The registry is a convenience,
+/// so failing to write it doesn't stop the launch.
+fn register(config: &WorkspaceConfig) {
+    if let Some(ref manifest) = config.manifest_path {
+        register_workspace(&config.workspace, manifest).ok();
+    }
+}
+
+/// Point the role panes of the manifest's `orchestrator:` and the watched
+/// panes (`budget:` or `stuck:`) at the session's event server.
+fn wire_orchestrator(config: &mut WorkspaceConfig) -> Result<()> {
+    if config.event_server_port().is_none() {
+        return Ok(());
+    }
+    let dir = config.workspace_dir().unwrap_or_else(|| PathBuf::from("."));
+    config.wire_orchestrator(&dir)
+}
+
+/// Run `axel server` for the manifest's `orchestrator:` in a background
+/// window of the new session, so replies are routed between role panes and
+/// watched panes are interrupted over budget and reported when stuck.
+///
+/// The server stops with the session. Its port is recorded in the session
+/// (`AXEL_PORT`) for `axel send --wait` and `axel dash`.
+fn start_orchestrator(session_name: &str, config: &WorkspaceConfig) -> Result<()> {
+    let (Some(port), Some(manifest)) = (config.event_server_port(), &config.manifest_path) else {
+        return Ok(());
+    };
+
+    let axel = std::env::current_exe()?;
+    let command = format!(
+        "{} --manifest-path {} server --port {} --session {}",
+        shell::quote(&axel.to_string_lossy()),
+        shell::quote(&manifest.to_string_lossy()),
+        port,
+        shell::quote(session_name)
+    );
+    let dir = config.workspace_dir().unwrap_or_else(|| PathBuf::from("."));
+    NewWindow::new()
+        .target(session_name)
+        .name(ORCHESTRATOR_WINDOW)
+        .start_directory(&dir.to_string_lossy())
+        .command(&command)
+        .run()?;
+    set_environment(session_name, AXEL_PORT_ENV, &port.to_string()).ok();
+
+    if let Some(orchestrator) = &config.orchestrator {
+        eprintln!(
+            "{} {} replies between {} on port {}",
+            "✔".green(),
+            "Routing".dimmed(),
+            orchestrator
+                .roles
+                .keys()
+                .cloned()
+                .collect::<Vec<_>>()
+                .join(", "),
+            port
+        );
+    }
+    let watched = config.watched_panes();
+    if !watched.is_empty() {
+        eprintln!(
+            "{} {} {} on port {}",
+            "✔".green(),
+            "Watching".dimmed(),
+            watched.join(", "),
+            port
+        );
+    }
+    let policed = config.policed_panes();
+    if !policed.is_empty() {
+        eprintln!(
+            "{} {} tool calls of {} with {} policies",
+            "✔".green(),
+            "Deciding".dimmed(),
+            policed.join(", "),
+            config.policies.len()
+        );
+    }
+    let checkpointed = config.checkpointed_panes();
+    if !checkpointed.is_empty() {
+        eprintln!(
+            "{} {} turns of {} on port {}",
+            "✔".green(),
+            "Checkpointing".dimmed(),
+            checkpointed.join(", "),
+            port
+        );
+    }
+    Ok(())
+}
+
+fn is_single_ai_pane(config: &axel_core::WorkspaceConfig, grid_name: Option<&str>) -> bool {
+    let panes = config.resolve_panes(grid_name);
+    panes.len() == 1 && panes[0].config.is_ai()
 }
 
 /// Start the event server in a background thread.
 /// The server will automatically terminate when this process exits.
-fn start_embedded_server(port: u16, pane_id: Option<&str>) -> Result<()> {
-    use axel_core::server::{ServerConfig, run_server};
+///
+/// `pane_name` labels the pane's desktop notifications.
+fn start_embedded_server(
+    port: u16,
+    pane_id: Option<&str>,
+    pane_name: &str,
+    config: &WorkspaceConfig,
+) -> Result<()> {
+    use axel_core::server::{EventStorage, LogRetention, ServerConfig, run_server};
 
     // Create log path in current directory
     let log_path = std::env::current_dir()
@@ -1157,6 +2306,21 @@ fn start_embedded_server(port: u16, pane_id: Option<&str>) -> Result<()> {
         // Use pane_id as the session name - this enables tmux send-keys for outbox responses
         session: pane_id.map(|s| s.to_string()).unwrap_or_default(),
         log_path,
+        storage: EventStorage::Jsonl,
+        retention: LogRetention::default(),
+        metrics: false,
+        notifications: config.notifications.clone(),
+        pane_names: pane_id
+            .map(|id| HashMap::from([(id.to_string(), pane_name.to_string())]))
+            .unwrap_or_default(),
+        orchestrator: None,
+        budgets: HashMap::new(),
+        stuck: HashMap::new(),
+        policies: config.policies.clone(),
+        checkpoints: pane_id
+            .filter(|_| config.checkpoints)
+            .map(|id| vec![id.to_string()])
+            .unwrap_or_default(),
     };
 
     let pane_display = pane_id
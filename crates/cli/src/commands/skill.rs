@@ -7,12 +7,27 @@
 //! - Forking global skills to local
 //! - Linking global skills to local
 //! - Removing skills
+//! - Syncing shared skill repositories
+//! - Locking skill versions in `skills.lock`
+//! - Packing skills into bundles and installing them
+//! - Showing where a skill comes from and where it is installed
+//! - Linting skills
+//! - Benchmarking skill installation
 
 use std::path::{Path, PathBuf};
 
-use anyhow::Result;
-use axel_core::{config::load_config, drivers};
+use anyhow::{Result, bail};
+use axel_core::{
+    Error, PaneConfig,
+    config::{
+        DRIVER_SKILLS_DIR, RENDERED_SKILLS_DIR, SKILLS_LOCK_FILE, Severity, Skill, SkillCache,
+        SkillDrift, SkillsLock, install_bundle, list_remotes, load_config, pack_skills,
+        read_bundle, remote_for_path, render_template, sync_remote, with_skill_cache,
+    },
+    drivers, platform,
+};
 use colored::Colorize;
+use serde::Serialize;
 
 use crate::{display_path, home_dir};
 
@@ -102,103 +117,34 @@ fn global_skill_dirs() -> Vec<PathBuf> {
 }
 
 /// Metadata for a discovered skill, used for listing.
+#[derive(Serialize)]
 struct SkillInfo {
     /// Skill name (directory name or file stem)
     name: String,
     /// First non-empty, non-heading line from the skill file (truncated to 60 chars)
     description: String,
     /// Full path to the skill file
-    #[allow(dead_code)]
     path: PathBuf,
     /// Location label for display (workspace name or "global")
     location: String,
 }
 
-/// Find all skills in a directory.
+/// Skills in a directory, from the skill cache.
 ///
 /// Discovers skills in two formats:
 /// - Directory format: `<name>/SKILL.md`
 /// - File format: `<name>.md` (excluding `index.md`)
-fn find_skills_in_dir(dir: &Path, location: &str) -> Vec<SkillInfo> {
-    let mut skills = Vec::new();
-
-    let entries = match std::fs::read_dir(dir) {
-        Ok(e) => e,
-        Err(_) => return skills,
-    };
-
-    for entry in entries.flatten() {
-        let path = entry.path();
-
-        let (skill_name, skill_path) = if path.is_dir() {
-            let skill_file = path.join("SKILL.md");
-            if skill_file.exists() {
-                let name = path
-                    .file_name()
-                    .map(|n| n.to_string_lossy().to_string())
-                    .unwrap_or_default();
-                (name, skill_file)
-            } else {
-                continue;
-            }
-        } else if path.is_file() && path.extension().is_some_and(|ext| ext == "md") {
-            if path.file_name().is_some_and(|n| n == "index.md") {
-                continue;
-            }
-            let name = path
-                .file_stem()
-                .map(|n| n.to_string_lossy().to_string())
-                .unwrap_or_default();
-            (name, path)
-        } else {
-            continue;
-        };
-
-        if skill_name.is_empty() {
-            continue;
-        }
-
-        let description = std::fs::read_to_string(&skill_path)
-            .ok()
-            .and_then(|content| {
-                let content = if content.starts_with("---") {
-                    content
-                        .find("\n---")
-                        .map(|i| &content[i + 4..])
-                        .unwrap_or(&content)
-                } else {
-                    &content
-                };
-
-                content
-                    .lines()
-                    .find(|l| !l.trim().is_empty() && !l.starts_with('#'))
-                    .or_else(|| {
-                        content
-                            .lines()
-                            .find(|l| l.starts_with('#'))
-                            .map(|l| l.trim_start_matches('#').trim())
-                    })
-                    .map(|s| {
-                        let s = s.trim();
-                        if s.len() > 60 {
-                            format!("{}...", &s[..57])
-                        } else {
-                            s.to_string()
-                        }
-                    })
-            })
-            .unwrap_or_else(|| "No description".to_string());
-
-        skills.push(SkillInfo {
-            name: skill_name,
-            description,
-            path: skill_path,
+fn find_skills_in_dir(cache: &mut SkillCache, dir: &Path, location: &str) -> Vec<SkillInfo> {
+    cache
+        .skills(dir)
+        .iter()
+        .map(|skill| SkillInfo {
+            name: skill.name.clone(),
+            description: skill.description.clone(),
+            path: skill.path.clone(),
             location: location.to_string(),
-        });
-    }
-
-    skills
+        })
+        .collect()
 }
 
 // =============================================================================
@@ -229,14 +175,13 @@ pub fn format_cleaned_drivers(cleaned: &[&str]) -> String {
     }
 }
 
-/// List all available skills (local and global)
-pub fn list_skills(manifest_path: &Path, base_dir: &Path) -> Result<()> {
-    let mut all_skills: Vec<SkillInfo> = Vec::new();
-    let mut seen_names: std::collections::HashSet<String> = std::collections::HashSet::new();
-
+/// Directories skills are found in, with their location label, in order of
+/// precedence: the manifest's skill directories, or the local and global
+/// skills without a manifest
+fn skill_sources(manifest_path: &Path, base_dir: &Path) -> Result<Vec<(PathBuf, String)>> {
     let global_dir = global_skills_dir().ok();
 
-    let skill_sources: Vec<(PathBuf, String)> = if manifest_path.exists() {
+    let sources = if manifest_path.exists() {
         let cfg = load_config(manifest_path)?;
         cfg.skills_dirs()
             .into_iter()
@@ -248,6 +193,8 @@ pub fn list_skills(manifest_path: &Path, base_dir: &Path) -> Result<()> {
                         .unwrap_or_else(|| "local".to_string())
                 } else if global_dir.as_ref().is_some_and(|g| &dir == g) {
                     "global".to_string()
+                } else if let Some(remote) = remote_for_path(&dir) {
+                    format!("remote:{}", remote.name)
                 } else {
                     display_path(&dir)
                 };
@@ -269,14 +216,47 @@ pub fn list_skills(manifest_path: &Path, base_dir: &Path) -> Result<()> {
         }
         sources
     };
+    Ok(sources)
+}
+
+/// Discover every skill available to the workspace; a skill name found in
+/// several directories is taken from the first one
+fn discover_skills(manifest_path: &Path, base_dir: &Path) -> Result<Vec<SkillInfo>> {
+    let mut all_skills: Vec<SkillInfo> = Vec::new();
+    let mut seen_names: std::collections::HashSet<String> = std::collections::HashSet::new();
 
-    for (dir, location) in &skill_sources {
-        for skill in find_skills_in_dir(dir, location) {
-            if !seen_names.contains(&skill.name) {
-                seen_names.insert(skill.name.clone());
-                all_skills.push(skill);
+    let sources = skill_sources(manifest_path, base_dir)?;
+    with_skill_cache(|cache| {
+        for (dir, location) in &sources {
+            for skill in find_skills_in_dir(cache, dir, location) {
+                if !seen_names.contains(&skill.name) {
+                    seen_names.insert(skill.name.clone());
+                    all_skills.push(skill);
+                }
             }
         }
+    });
+    Ok(all_skills)
+}
+
+/// Names of the skills available to the workspace (for shell completion)
+pub fn skill_names(manifest_path: &Path, base_dir: &Path) -> Vec<String> {
+    discover_skills(manifest_path, base_dir)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|skill| skill.name)
+        .collect()
+}
+
+/// List all available skills (local and global)
+///
+/// If `json_output` is true, prints the skills as a JSON array instead.
+pub fn list_skills(manifest_path: &Path, base_dir: &Path, json_output: bool) -> Result<()> {
+    let all_skills = discover_skills(manifest_path, base_dir)?;
+
+    if json_output {
+        println!("{}", serde_json::to_string_pretty(&all_skills)?);
+        return Ok(());
     }
 
     if all_skills.is_empty() {
@@ -412,15 +392,13 @@ pub fn import_skill(path: &str) -> Result<()> {
     };
 
     if !expanded_path.exists() {
-        eprintln!("{} Path not found: {}", "✘".red(), path);
-        std::process::exit(1);
+        bail!(Error::PathNotFound(expanded_path));
     }
 
     // Skip symlinks
     let metadata = expanded_path.symlink_metadata()?;
     if metadata.file_type().is_symlink() {
-        eprintln!("{} Cannot import symlinks", "✘".red());
-        std::process::exit(1);
+        bail!(Error::SymlinkImport);
     }
 
     // If it's a directory, import all .md files in it
@@ -446,8 +424,7 @@ pub fn import_skill(path: &str) -> Result<()> {
         }
 
         if count == 0 {
-            eprintln!("{} No .md files found in directory", "✘".red());
-            std::process::exit(1);
+            bail!(Error::NoSkillFiles);
         }
 
         return Ok(());
@@ -513,23 +490,16 @@ fn import_single_skill(source_path: &Path) -> Result<()> {
 }
 
 /// Fork (copy) a global skill to the current workspace
-pub fn fork_skill(name: &str, manifest_path: &Path, base_dir: &Path) -> Result<()> {
+pub fn fork_skill(name: &str, base_dir: &Path) -> Result<()> {
     let global = SkillPath::global(name)?;
     let local = SkillPath::local(name, base_dir);
 
     if !global.exists() {
-        eprintln!("{}", format!("Global skill '{}' not found", name).red());
-        eprintln!();
-        let _ = list_skills(manifest_path, base_dir);
-        std::process::exit(1);
+        bail!(Error::GlobalSkillNotFound(name.to_string()));
     }
 
     if local.exists() {
-        eprintln!(
-            "{}",
-            format!("Skill '{}' already exists in workspace", name).red()
-        );
-        std::process::exit(1);
+        bail!(Error::SkillExists(name.to_string()));
     }
 
     std::fs::create_dir_all(&local.dir)?;
@@ -546,32 +516,21 @@ pub fn fork_skill(name: &str, manifest_path: &Path, base_dir: &Path) -> Result<(
 }
 
 /// Link (symlink) a global skill to the current workspace
-pub fn link_skill(name: &str, manifest_path: &Path, base_dir: &Path) -> Result<()> {
+pub fn link_skill(name: &str, base_dir: &Path) -> Result<()> {
     let global = SkillPath::global(name)?;
     let local = SkillPath::local(name, base_dir);
 
     if !global.exists() {
-        eprintln!("{}", format!("Global skill '{}' not found", name).red());
-        eprintln!();
-        let _ = list_skills(manifest_path, base_dir);
-        std::process::exit(1);
+        bail!(Error::GlobalSkillNotFound(name.to_string()));
     }
 
     if local.exists() {
-        eprintln!(
-            "{}",
-            format!("Skill '{}' already exists in workspace", name).red()
-        );
-        std::process::exit(1);
+        bail!(Error::SkillExists(name.to_string()));
     }
 
     std::fs::create_dir_all(base_dir.join(SKILLS_DIR))?;
 
-    #[cfg(unix)]
-    std::os::unix::fs::symlink(&global.dir, &local.dir)?;
-
-    #[cfg(windows)]
-    std::os::windows::fs::symlink_dir(&global.dir, &local.dir)?;
+    platform::symlink(&global.dir, &local.dir)?;
 
     println!(
         "{} {} {} -> {}",
@@ -585,7 +544,7 @@ pub fn link_skill(name: &str, manifest_path: &Path, base_dir: &Path) -> Result<(
 }
 
 /// Remove a skill
-pub fn rm_skill(name: &str, manifest_path: &Path, base_dir: &Path) -> Result<()> {
+pub fn rm_skill(name: &str, base_dir: &Path) -> Result<()> {
     use dialoguer::{Confirm, Select, theme::ColorfulTheme};
 
     let theme = ColorfulTheme::default();
@@ -617,10 +576,7 @@ pub fn rm_skill(name: &str, manifest_path: &Path, base_dir: &Path) -> Result<()>
     } else if global.exists() {
         global
     } else {
-        eprintln!("{}", format!("Skill '{}' not found", name).red());
-        eprintln!();
-        let _ = list_skills(manifest_path, base_dir);
-        std::process::exit(1);
+        bail!(Error::SkillNotFound(name.to_string()));
     };
 
     let confirmed = Confirm::with_theme(&theme)
@@ -643,3 +599,567 @@ pub fn rm_skill(name: &str, manifest_path: &Path, base_dir: &Path) -> Result<()>
 
     Ok(())
 }
+
+/// Clone or pull a shared skills repository, or pull every synced one.
+pub fn sync_skills(url: Option<&str>, name: Option<&str>) -> Result<()> {
+    let urls: Vec<(String, Option<&str>)> = match url {
+        Some(url) => vec![(url.to_string(), name)],
+        None => list_remotes()
+            .into_iter()
+            .filter_map(|remote| remote.url)
+            .map(|url| (url, None))
+            .collect(),
+    };
+    if urls.is_empty() {
+        println!(
+            "{}",
+            "No skill repositories synced; run `axel skill sync <git-url>`".dimmed()
+        );
+        return Ok(());
+    }
+
+    for (url, name) in urls {
+        let outcome = sync_remote(&url, name)?;
+        let remote = &outcome.remote;
+        let commit = remote
+            .commit
+            .as_deref()
+            .map(|c| &c[..7.min(c.len())])
+            .unwrap_or("?");
+        let action = if outcome.cloned {
+            "Cloned"
+        } else if outcome.changed() {
+            "Updated"
+        } else {
+            "Up to date"
+        };
+        println!(
+            "{} {} {} {} {}",
+            "✔".green(),
+            action.dimmed(),
+            remote.name,
+            format!("@ {}", commit).dimmed(),
+            display_path(&remote.skills_dir())
+        );
+    }
+
+    Ok(())
+}
+
+/// Report skills that differ from the workspace's `skills.lock`.
+///
+/// Fails with [`Error::SkillDrift`] if any skill differs.
+pub fn skill_status(manifest_path: &Path, json: bool) -> Result<()> {
+    let (config, workspace_dir) = load_workspace(manifest_path)?;
+    let Some(locked) = SkillsLock::load(&workspace_dir)? else {
+        anyhow::bail!(
+            "No {} in {}; create one with `axel skill update`",
+            SKILLS_LOCK_FILE,
+            display_path(&workspace_dir)
+        );
+    };
+    let current = config.current_skills_lock()?;
+    let drift = locked.drift(&current);
+
+    if json {
+        let entries: Vec<serde_json::Value> = drift
+            .iter()
+            .map(|(name, drift)| {
+                let mut entry = serde_json::to_value(drift).unwrap_or_default();
+                entry["name"] = name.clone().into();
+                entry
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&entries)?);
+    } else if drift.is_empty() {
+        println!(
+            "{} {} {} skills match {}",
+            "✔".green(),
+            "All".dimmed(),
+            locked.skills.len(),
+            SKILLS_LOCK_FILE
+        );
+    } else {
+        for (name, drift) in &drift {
+            match drift {
+                SkillDrift::Modified { current, .. } => println!(
+                    "{} {} {}",
+                    "~ modified".yellow(),
+                    name,
+                    current.source.dimmed()
+                ),
+                SkillDrift::Added { current } => {
+                    println!(
+                        "{} {} {}",
+                        "+ added   ".green(),
+                        name,
+                        current.source.dimmed()
+                    )
+                }
+                SkillDrift::Removed { locked } => {
+                    println!("{} {} {}", "- removed ".red(), name, locked.source.dimmed())
+                }
+            }
+        }
+        println!();
+        println!(
+            "{}",
+            "Run `axel skill update` to lock the current versions".dimmed()
+        );
+    }
+
+    if !drift.is_empty() {
+        bail!(Error::SkillDrift(drift.len()));
+    }
+    Ok(())
+}
+
+/// Rewrite `skills.lock` with the skills as they are on disk.
+pub fn skill_update(manifest_path: &Path) -> Result<()> {
+    let (config, workspace_dir) = load_workspace(manifest_path)?;
+    let current = config.current_skills_lock()?;
+    let changed = match SkillsLock::load(&workspace_dir)? {
+        Some(locked) => locked.drift(&current).len(),
+        None => current.skills.len(),
+    };
+    current.save(&workspace_dir)?;
+
+    println!(
+        "{} {} {} skills in {} ({} changed)",
+        "✔".green(),
+        "Locked".dimmed(),
+        current.skills.len(),
+        SKILLS_LOCK_FILE,
+        changed
+    );
+    Ok(())
+}
+
+/// Pack skills `names` into the bundle `output`.
+///
+/// Names are resolved through the manifest's skill directories, or the
+/// workspace's `./skills` and the global directory without a manifest.
+pub fn skill_pack(
+    names: &[String],
+    output: &Path,
+    manifest_path: &Path,
+    base_dir: &Path,
+) -> Result<()> {
+    let config = if manifest_path.exists() {
+        Some(load_config(manifest_path)?)
+    } else {
+        None
+    };
+
+    let mut paths = Vec::new();
+    for name in names {
+        let path = match &config {
+            Some(config) => config.find_skill(name),
+            None => [
+                SkillPath::local(name, base_dir).skill_file(),
+                base_dir.join(SKILLS_DIR).join(format!("{}.md", name)),
+                SkillPath::global(name)?.skill_file(),
+            ]
+            .into_iter()
+            .find(|p| p.exists()),
+        };
+        match path {
+            Some(path) => paths.push(path),
+            None => anyhow::bail!("Skill '{}' not found", name),
+        }
+    }
+
+    let packed = pack_skills(&paths, output)?;
+    println!(
+        "{} {} {} {} to {}",
+        "✔".green(),
+        "Packed".dimmed(),
+        packed.len(),
+        if packed.len() == 1 { "skill" } else { "skills" },
+        display_path(output)
+    );
+    Ok(())
+}
+
+/// Install the skills in `bundle` into the global skills directory, or the
+/// workspace's `./skills` with `local`.
+pub fn skill_install(bundle: &Path, local: bool, force: bool, base_dir: &Path) -> Result<()> {
+    let skills = read_bundle(bundle)?;
+    let target = if local {
+        base_dir.join(SKILLS_DIR)
+    } else {
+        global_skills_dir()?
+    };
+    install_bundle(&skills, &target, force)?;
+
+    for skill in &skills {
+        println!(
+            "{} {} {}",
+            "✔".green(),
+            "Installed".dimmed(),
+            display_path(&target.join(&skill.name).join(SKILL_FILE))
+        );
+    }
+    Ok(())
+}
+
+/// Where a skill is installed for one driver
+#[derive(Debug, Serialize)]
+struct SkillInstall {
+    driver: &'static str,
+    path: PathBuf,
+    installed: bool,
+}
+
+/// Resolved view of a skill, as printed by `axel skill show`
+#[derive(Debug, Serialize)]
+struct SkillShow {
+    name: String,
+    source: PathBuf,
+    /// Lower-priority copies that are ignored
+    shadowed: Vec<PathBuf>,
+    frontmatter: Option<String>,
+    description: String,
+    model: Option<String>,
+    tools: Option<Vec<String>>,
+    includes: Vec<String>,
+    tags: Vec<String>,
+    /// Drivers the skill has frontmatter overrides for
+    overrides: Vec<String>,
+    installs: Vec<SkillInstall>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    content: Option<String>,
+}
+
+/// Show which copy of skill `name` is used, its frontmatter and effective
+/// settings, and where each driver installs it.
+pub fn skill_show(name: &str, content: bool, json: bool, manifest_path: &Path) -> Result<()> {
+    let (config, workspace_dir) = load_workspace(manifest_path)?;
+    let mut candidates = config.skill_candidates(name).into_iter();
+    let Some(source) = candidates.next() else {
+        anyhow::bail!("Skill '{}' not found in any skill directory", name);
+    };
+    let skill = Skill::from_file(&source)?;
+    let raw = std::fs::read_to_string(&source)?;
+    let frontmatter = raw
+        .strip_prefix("---")
+        .and_then(|rest| rest.find("\n---").map(|end| rest[..end].trim().to_string()));
+
+    // The skill's own model wins; otherwise each pane uses its own
+    let model = skill.model.clone().or_else(|| {
+        let models: Vec<&str> = config
+            .layouts
+            .panes
+            .iter()
+            .filter_map(|pane| match pane {
+                PaneConfig::Claude(c)
+                | PaneConfig::Codex(c)
+                | PaneConfig::Opencode(c)
+                | PaneConfig::Antigravity(c)
+                | PaneConfig::External(c) => Some(c),
+                PaneConfig::Custom(_) => None,
+            })
+            .filter(|c| config.selects_skill(&c.skills, name))
+            .filter_map(|c| c.model.as_deref())
+            .collect();
+        (!models.is_empty()).then(|| models.join(", "))
+    });
+
+    let installs = config
+        .skill_drivers(name)
+        .into_iter()
+        .filter_map(drivers::get_driver)
+        .map(|driver| {
+            let path = driver.installed_skill_path(&workspace_dir, name);
+            let installed = is_installed(&path, &source, name, &workspace_dir);
+            SkillInstall {
+                driver: driver.name(),
+                path,
+                installed,
+            }
+        })
+        .collect();
+
+    let show = SkillShow {
+        name: name.to_string(),
+        source,
+        shadowed: candidates.collect(),
+        frontmatter,
+        description: skill.description,
+        model,
+        tools: skill.tools,
+        includes: skill.includes,
+        tags: skill.tags,
+        overrides: skill.drivers.into_keys().collect(),
+        installs,
+        content: content.then(|| render_template(&raw, &config.skills_vars).0),
+    };
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&show)?);
+        return Ok(());
+    }
+
+    println!("{}", show.name.green().bold());
+    println!("  {:<12}{}", "Source".dimmed(), display_path(&show.source));
+    for shadowed in &show.shadowed {
+        println!(
+            "  {:<12}{} {}",
+            "Shadows".dimmed(),
+            display_path(shadowed),
+            "(ignored)".dimmed()
+        );
+    }
+    println!("  {:<12}{}", "Description".dimmed(), show.description);
+    println!(
+        "  {:<12}{}",
+        "Model".dimmed(),
+        show.model.as_deref().unwrap_or("tool default")
+    );
+    println!(
+        "  {:<12}{}",
+        "Tools".dimmed(),
+        show.tools
+            .as_ref()
+            .map(|t| t.join(", "))
+            .unwrap_or_else(|| "all".to_string())
+    );
+    if !show.includes.is_empty() {
+        println!("  {:<12}{}", "Includes".dimmed(), show.includes.join(", "));
+    }
+    if !show.tags.is_empty() {
+        println!("  {:<12}{}", "Tags".dimmed(), show.tags.join(", "));
+    }
+    if !show.overrides.is_empty() {
+        println!(
+            "  {:<12}{}",
+            "Overrides".dimmed(),
+            show.overrides.join(", ")
+        );
+    }
+
+    if let Some(frontmatter) = &show.frontmatter {
+        println!();
+        println!("{}", "Frontmatter".bold());
+        for line in frontmatter.lines() {
+            println!("  {}", line.dimmed());
+        }
+    }
+
+    println!();
+    println!("{}", "Installed".bold());
+    if show.installs.is_empty() {
+        println!("  {}", "Not used by any pane in the manifest".dimmed());
+    }
+    for install in &show.installs {
+        let status = if install.installed {
+            "✔".green()
+        } else {
+            "✘ not installed".yellow()
+        };
+        println!(
+            "  {:<12}{} {}",
+            install.driver,
+            display_path(&install.path),
+            status
+        );
+    }
+
+    if let Some(content) = &show.content {
+        println!();
+        println!("{}", content.trim_end());
+    }
+    Ok(())
+}
+
+/// Whether the installed file at `path` is skill `name` from `source`.
+///
+/// Symlinks must point at `source` or at its rendered copy; merged rules
+/// files must have a section for the skill.
+fn is_installed(path: &Path, source: &Path, name: &str, workspace_dir: &Path) -> bool {
+    let canonical = |p: &Path| p.canonicalize().unwrap_or_else(|_| p.to_path_buf());
+    match std::fs::read_link(path) {
+        Ok(target) => {
+            target == canonical(source)
+                || target.starts_with(canonical(workspace_dir).join(RENDERED_SKILLS_DIR))
+                || target.starts_with(canonical(workspace_dir).join(DRIVER_SKILLS_DIR))
+        }
+        Err(_) => std::fs::read_to_string(path)
+            .is_ok_and(|content| content.contains(&format!("## {}\n", name))),
+    }
+}
+
+/// Lint the workspace's skills, or only `names`, printing every problem.
+///
+/// Fails with [`Error::LintFailed`] on errors, or on warnings with `strict`.
+pub fn skill_lint(names: &[String], strict: bool, json: bool, manifest_path: &Path) -> Result<()> {
+    let (config, _) = load_workspace(manifest_path)?;
+    let issues = config.lint_skills(names);
+    let errors = issues
+        .iter()
+        .filter(|i| i.severity == Severity::Error)
+        .count();
+    let warnings = issues.len() - errors;
+    let failed = errors > 0 || (strict && warnings > 0);
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&issues)?);
+    } else if issues.is_empty() {
+        eprintln!("{} {} no problems found", "✔".green(), "Skills:".dimmed());
+    } else {
+        let mut current: Option<&str> = None;
+        for issue in &issues {
+            if current != Some(issue.skill.as_str()) {
+                current = Some(&issue.skill);
+                eprintln!(
+                    "{} {}",
+                    issue.skill.bold(),
+                    display_path(&issue.path).dimmed()
+                );
+            }
+            let marker = match issue.severity {
+                Severity::Error => "✘".red(),
+                Severity::Warning => "⚠".yellow(),
+            };
+            eprintln!("  {} {}", marker, issue.message);
+        }
+        eprintln!(
+            "{}",
+            format!(
+                "{} error{}, {} warning{}",
+                errors,
+                if errors == 1 { "" } else { "s" },
+                warnings,
+                if warnings == 1 { "" } else { "s" },
+            )
+            .dimmed()
+        );
+    }
+
+    if failed {
+        bail!(Error::LintFailed { errors, warnings });
+    }
+    Ok(())
+}
+
+/// Load the manifest and its directory, failing if there is none
+fn load_workspace(manifest_path: &Path) -> Result<(axel_core::WorkspaceConfig, PathBuf)> {
+    if !manifest_path.exists() {
+        anyhow::bail!("No AXEL.md found at {}", display_path(manifest_path));
+    }
+    let config = load_config(manifest_path)?;
+    let workspace_dir = config.workspace_dir().unwrap_or_else(|| PathBuf::from("."));
+    Ok((config, workspace_dir))
+}
+
+/// Show the skills each skill includes (transitively).
+///
+/// With `tree`, renders the full include tree; otherwise prints a flat list
+/// per skill. Without a name, covers every skill that declares `includes:`.
+/// Fails with [`Error::IncludeCycle`] if any include cycle is found.
+pub fn skill_deps(name: Option<&str>, tree: bool, manifest_path: &Path) -> Result<()> {
+    use axel_core::{SkillNode, SkillNodeStatus};
+
+    if !manifest_path.exists() {
+        anyhow::bail!("No AXEL.md found at {}", display_path(manifest_path));
+    }
+    let config = load_config(manifest_path)?;
+
+    let roots: Vec<SkillNode> = match name {
+        Some(name) => vec![config.skill_tree(name)],
+        None => config
+            .find_all_skills()
+            .iter()
+            .map(|path| config.skill_tree(&skill_name_from_path(path)))
+            .filter(|node| !node.children.is_empty())
+            .collect(),
+    };
+
+    if roots.is_empty() {
+        println!("{}", "No skills with includes".dimmed());
+        return Ok(());
+    }
+
+    for root in &roots {
+        if tree {
+            print!("{}", root.render());
+        } else {
+            let mut deps = Vec::new();
+            let mut stack: Vec<&SkillNode> = root.children.iter().rev().collect();
+            while let Some(node) = stack.pop() {
+                if node.status != SkillNodeStatus::Cycle && !deps.contains(&node.name) {
+                    deps.push(node.name.clone());
+                }
+                stack.extend(node.children.iter().rev());
+            }
+            println!("{}: {}", root.name.bold(), deps.join(", "));
+        }
+    }
+
+    let cycles: Vec<_> = roots.iter().flat_map(|root| root.cycles()).collect();
+    if !cycles.is_empty() {
+        eprintln!();
+        for cycle in &cycles {
+            eprintln!("{} Include cycle: {}", "✘".red(), cycle.join(" → "));
+        }
+        bail!(Error::IncludeCycle(cycles.len()));
+    }
+
+    Ok(())
+}
+
+/// Print the directory where `driver_name` installs skills for the workspace.
+pub fn where_skills(driver_name: &str, workspace_dir: &Path) -> Result<()> {
+    let Some(dir) = drivers::driver_skills_dir(driver_name, workspace_dir) else {
+        let names: Vec<_> = drivers::all_drivers().iter().map(|d| d.name()).collect();
+        anyhow::bail!(
+            "Unknown driver '{}' (expected one of: {})",
+            driver_name,
+            names.join(", ")
+        );
+    };
+    println!("{}", dir.display());
+    Ok(())
+}
+
+/// Time resolving and installing `count` generated skills for a driver.
+///
+/// Diagnostic command; runs in a scratch directory under the system temp dir.
+pub fn skill_bench(count: usize, driver_name: &str) -> Result<()> {
+    let Some(driver) = drivers::get_driver(driver_name) else {
+        anyhow::bail!("Unknown driver '{}'", driver_name);
+    };
+    let dir = std::env::temp_dir().join(format!("axel-skill-bench-{}", std::process::id()));
+
+    eprintln!(
+        "{} {} skills for {}...",
+        "Benchmarking".dimmed(),
+        count,
+        driver.name()
+    );
+    let report = drivers::bench_install(driver.as_ref(), count, &dir)?;
+
+    println!("{:<10} {:>10.2?}", "resolve", report.resolve);
+    println!("{:<10} {:>10.2?}", "install", report.install);
+    println!("{:<10} {:>10.2?}", "total", report.total());
+    println!(
+        "{:<10} {:>10.0} skills/s ({} installed)",
+        "throughput",
+        report.throughput(),
+        report.installed
+    );
+    Ok(())
+}
+
+/// Skill name for a skill file: the directory name for `<name>/SKILL.md`,
+/// otherwise the file stem.
+fn skill_name_from_path(path: &Path) -> String {
+    let named = if path.file_name().is_some_and(|n| n == SKILL_FILE) {
+        path.parent().and_then(|p| p.file_name())
+    } else {
+        path.file_stem()
+    };
+    named
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
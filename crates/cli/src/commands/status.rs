@@ -0,0 +1,311 @@
+//! Workspace status command for axel.
+//!
+//! Provides `axel status`, a one-screen summary of the current workspace:
+//! its running session and panes (from tmux), each pane's last hook event
+//! and token usage (from the event log), the skill artifacts installed for
+//! each driver, the event server and the checkout.
+
+use std::{
+    net::{SocketAddr, TcpStream},
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use anyhow::Result;
+use axel_core::{
+    config::load_config,
+    drivers,
+    git::{self, RepoStatus},
+    server::{Pricing, TimestampedEvent, UsageTracker},
+    tmux::{SessionInfo, list_pane_status, list_sessions},
+};
+use chrono::{DateTime, Local, Utc};
+use colored::Colorize;
+use serde::Serialize;
+
+use crate::{commands::session::format_git_status, display_path};
+
+/// How long to wait when checking whether the event server is up
+const SERVER_CONNECT_TIMEOUT: Duration = Duration::from_millis(200);
+
+/// Everything `axel status` reports
+#[derive(Debug, Serialize)]
+struct WorkspaceStatus {
+    workspace: String,
+    manifest: PathBuf,
+    /// Axel session launched from this workspace
+    session: Option<SessionInfo>,
+    panes: Vec<PaneState>,
+    skills: Vec<SkillArtifacts>,
+    server: Option<ServerStatus>,
+    git: Option<RepoStatus>,
+    /// Whether the workspace is a linked worktree rather than the main checkout
+    linked_worktree: bool,
+}
+
+/// One pane of the workspace
+#[derive(Debug, Serialize)]
+struct PaneState {
+    name: String,
+    /// Foreground process, when the session is running
+    command: Option<String>,
+    /// The pane's process exited
+    dead: bool,
+    /// Latest hook event logged for the pane
+    last_event: Option<String>,
+    last_event_at: Option<DateTime<Utc>>,
+    tokens: u64,
+    cost_usd: f64,
+}
+
+/// Skills installed in the workspace for one driver
+#[derive(Debug, Serialize)]
+struct SkillArtifacts {
+    driver: String,
+    dir: PathBuf,
+    skills: usize,
+}
+
+/// The workspace's event server
+#[derive(Debug, Serialize)]
+struct ServerStatus {
+    port: u16,
+    running: bool,
+}
+
+/// Print a summary of the workspace at `manifest_path`
+pub fn status(manifest_path: &Path, json: bool) -> Result<()> {
+    let config = load_config(manifest_path)?;
+    let workspace_dir = config
+        .workspace_dir()
+        .or_else(|| manifest_path.parent().map(Path::to_path_buf))
+        .unwrap_or_else(|| PathBuf::from("."));
+    let canonical = |path: &Path| path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+
+    let session = list_sessions(true)
+        .unwrap_or_default()
+        .into_iter()
+        .find(|session| {
+            session
+                .working_dir
+                .as_deref()
+                .is_some_and(|dir| canonical(Path::new(dir)) == canonical(&workspace_dir))
+        });
+
+    let events = read_events(&workspace_dir.join(".axel/events.jsonl"));
+    let usage = UsageTracker::from_events(&events).report(&Pricing::load()?);
+
+    let mut panes: Vec<PaneState> = match &session {
+        Some(session) => list_pane_status(&session.name)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|pane| PaneState {
+                name: pane.title,
+                command: Some(pane.current_command),
+                dead: pane.dead,
+                last_event: None,
+                last_event_at: None,
+                tokens: 0,
+                cost_usd: 0.0,
+            })
+            .collect(),
+        None => {
+            // Panes of the default grid, or every pane without one
+            let mut names: Vec<String> = config
+                .resolve_panes(None)
+                .into_iter()
+                .map(|pane| pane.name)
+                .collect();
+            if names.is_empty() {
+                names = config
+                    .layouts
+                    .panes
+                    .iter()
+                    .map(|pane| pane.pane_type().to_string())
+                    .collect();
+            }
+            names
+                .into_iter()
+                .map(|name| PaneState {
+                    name,
+                    command: None,
+                    dead: false,
+                    last_event: None,
+                    last_event_at: None,
+                    tokens: 0,
+                    cost_usd: 0.0,
+                })
+                .collect()
+        }
+    };
+    for pane in &mut panes {
+        if let Some(event) = events
+            .iter()
+            .rev()
+            .find(|e| e.pane_id == pane.name && e.hook_name().is_some())
+        {
+            pane.last_event = event.hook_name().map(str::to_string);
+            pane.last_event_at = Some(event.timestamp);
+        }
+        if let Some(pane_usage) = usage.panes.iter().find(|p| p.pane_id == pane.name) {
+            pane.tokens = pane_usage.tokens.total();
+            pane.cost_usd = pane_usage.cost_usd;
+        }
+    }
+
+    let skills = drivers::all_drivers()
+        .iter()
+        .filter_map(|driver| {
+            let dir = driver.skills_dir(&workspace_dir);
+            let count = std::fs::read_dir(&dir).ok()?.count();
+            (count > 0).then(|| SkillArtifacts {
+                driver: driver.name().to_string(),
+                dir,
+                skills: count,
+            })
+        })
+        .collect();
+
+    let server = session
+        .as_ref()
+        .and_then(|s| s.port)
+        .or_else(|| config.event_server_port())
+        .map(|port| ServerStatus {
+            port,
+            running: TcpStream::connect_timeout(
+                &SocketAddr::from(([127, 0, 0, 1], port)),
+                SERVER_CONNECT_TIMEOUT,
+            )
+            .is_ok(),
+        });
+
+    let git = git::status(&workspace_dir).ok();
+    let linked_worktree = git::worktrees(&workspace_dir)
+        .unwrap_or_default()
+        .iter()
+        .any(|worktree| !worktree.main && canonical(&worktree.path) == canonical(&workspace_dir));
+
+    let status = WorkspaceStatus {
+        workspace: config.workspace.clone(),
+        manifest: manifest_path.to_path_buf(),
+        session,
+        panes,
+        skills,
+        server,
+        git,
+        linked_worktree,
+    };
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&status)?);
+        return Ok(());
+    }
+    print_status(&status);
+    Ok(())
+}
+
+/// Events logged at `path`; a missing log has none
+fn read_events(path: &Path) -> Vec<TimestampedEvent> {
+    std::fs::read_to_string(path)
+        .unwrap_or_default()
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect()
+}
+
+fn print_status(status: &WorkspaceStatus) {
+    use comfy_table::{Table, presets::NOTHING};
+
+    let mut summary = Table::new();
+    summary.load_preset(NOTHING);
+    summary.add_row(vec![
+        "workspace".dimmed().to_string(),
+        status.workspace.bold().to_string(),
+        display_path(&status.manifest).dimmed().to_string(),
+    ]);
+    let session = match &status.session {
+        Some(session) => {
+            let attached = if session.attached {
+                " (attached)".green().to_string()
+            } else {
+                String::new()
+            };
+            format!("{}{}", session.name.blue(), attached)
+        }
+        None => "not running".dimmed().to_string(),
+    };
+    summary.add_row(vec!["session".dimmed().to_string(), session]);
+    if let Some(server) = &status.server {
+        let state = if server.running {
+            "listening".green()
+        } else {
+            "not running".dimmed()
+        };
+        summary.add_row(vec![
+            "server".dimmed().to_string(),
+            format!("port {} {}", server.port, state),
+        ]);
+    }
+    if let Some(git) = &status.git {
+        let kind = if status.linked_worktree {
+            "(linked worktree)"
+        } else {
+            "(main checkout)"
+        };
+        summary.add_row(vec![
+            "git".dimmed().to_string(),
+            format!("{} {}", format_git_status(git), kind.dimmed()),
+        ]);
+    }
+    println!("{summary}");
+
+    if !status.panes.is_empty() {
+        let mut panes = Table::new();
+        panes.load_preset(NOTHING);
+        panes.set_header(
+            ["pane", "command", "last event", "tokens", "cost"].map(|h| h.dimmed().to_string()),
+        );
+        for pane in &status.panes {
+            let command = match (&pane.command, pane.dead) {
+                (_, true) => "exited".red().to_string(),
+                (Some(command), false) => command.clone(),
+                (None, false) => "-".dimmed().to_string(),
+            };
+            let last_event = match (&pane.last_event, pane.last_event_at) {
+                (Some(name), Some(at)) => format!(
+                    "{} {}",
+                    name,
+                    at.with_timezone(&Local)
+                        .format("%H:%M:%S")
+                        .to_string()
+                        .dimmed()
+                ),
+                _ => "-".dimmed().to_string(),
+            };
+            panes.add_row(vec![
+                pane.name.yellow().to_string(),
+                command,
+                last_event,
+                pane.tokens.to_string(),
+                format!("${:.2}", pane.cost_usd),
+            ]);
+        }
+        println!("\n{panes}");
+    }
+
+    if status.skills.is_empty() {
+        println!("\n{}", "No skills installed in the workspace".dimmed());
+    } else {
+        let mut skills = Table::new();
+        skills.load_preset(NOTHING);
+        skills.set_header(["driver", "skills", "directory"].map(|h| h.dimmed().to_string()));
+        for artifacts in &status.skills {
+            skills.add_row(vec![
+                artifacts.driver.green().to_string(),
+                artifacts.skills.to_string(),
+                display_path(&artifacts.dir).dimmed().to_string(),
+            ]);
+        }
+        println!("\n{skills}");
+    }
+}
@@ -0,0 +1,166 @@
+//! Transcript command for axel.
+//!
+//! Provides `axel transcript`, which lists the AI pane transcripts recorded
+//! in a workspace and prints (or follows) one of them. Tmux also runs it
+//! with `--record` to do the recording (see [`axel_core::transcript`]).
+
+use std::{
+    io::{Read, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use anyhow::{Context, Result, bail};
+use axel_core::transcript::{
+    record_transcript, transcript_archives, transcript_panes, transcript_path,
+};
+use chrono::{DateTime, Local};
+use colored::Colorize;
+
+use crate::display_path;
+
+/// How often `--follow` checks for new output
+const FOLLOW_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Record stdin into the transcript at `path` (run by tmux `pipe-pane`)
+pub fn record(path: &Path, session: Option<&str>) -> Result<()> {
+    let label = match session {
+        Some(session) => format!("Session {}", session),
+        None => "Recording".to_string(),
+    };
+    record_transcript(std::io::stdin().lock(), path, &label)
+}
+
+/// Print the transcript of `pane` in the workspace at `base_dir`, or list
+/// the recorded transcripts when no pane is given.
+///
+/// `previous` picks an archived transcript (1 for the previous session);
+/// `follow` keeps printing lines as they are recorded.
+pub fn show_transcript(
+    base_dir: &Path,
+    pane: Option<&str>,
+    previous: Option<usize>,
+    follow: bool,
+) -> Result<()> {
+    let Some(pane) = pane else {
+        return list_transcripts(base_dir);
+    };
+
+    let path = match previous {
+        None | Some(0) => transcript_path(base_dir, pane),
+        Some(n) => {
+            let archives = transcript_archives(base_dir, pane);
+            match archives.get(n - 1) {
+                Some(path) => path.clone(),
+                None => bail!(
+                    "Pane '{}' has {} archived transcript{}",
+                    pane,
+                    archives.len(),
+                    if archives.len() == 1 { "" } else { "s" }
+                ),
+            }
+        }
+    };
+    if !path.exists() {
+        let recorded = transcript_panes(base_dir);
+        if recorded.is_empty() {
+            bail!("No transcripts recorded in {}", display_path(base_dir));
+        }
+        bail!(
+            "No transcript for pane '{}' (recorded: {})",
+            pane,
+            recorded.join(", ")
+        );
+    }
+
+    if follow {
+        follow_transcript(&path)
+    } else {
+        let content = std::fs::read(&path)
+            .with_context(|| format!("Failed to read {}", display_path(&path)))?;
+        std::io::stdout().write_all(&content)?;
+        Ok(())
+    }
+}
+
+/// List the panes with transcripts, with their size, last update and
+/// number of archives
+fn list_transcripts(base_dir: &Path) -> Result<()> {
+    let panes = transcript_panes(base_dir);
+    if panes.is_empty() {
+        println!(
+            "{}",
+            format!("No transcripts recorded in {}", display_path(base_dir)).dimmed()
+        );
+        return Ok(());
+    }
+
+    use comfy_table::{Table, presets::NOTHING};
+
+    let mut table = Table::new();
+    table.load_preset(NOTHING);
+    table.set_header(["pane", "size", "updated", "archived"].map(|h| h.dimmed().to_string()));
+
+    for pane in &panes {
+        let path = transcript_path(base_dir, pane);
+        let archives = transcript_archives(base_dir, pane);
+        let (size, updated) = match std::fs::metadata(&path) {
+            Ok(metadata) => (
+                format_size(metadata.len()),
+                metadata
+                    .modified()
+                    .map(|time| {
+                        DateTime::<Local>::from(time)
+                            .format("%Y-%m-%d %H:%M")
+                            .to_string()
+                    })
+                    .unwrap_or_default(),
+            ),
+            Err(_) => ("-".dimmed().to_string(), "-".dimmed().to_string()),
+        };
+        table.add_row(vec![
+            pane.yellow().to_string(),
+            size,
+            updated,
+            archives.len().to_string(),
+        ]);
+    }
+
+    println!("{table}");
+    Ok(())
+}
+
+/// Print the transcript at `path`, then keep printing what is appended
+/// until interrupted. A transcript replaced by a new session is followed
+/// from its start.
+fn follow_transcript(path: &PathBuf) -> Result<()> {
+    let mut stdout = std::io::stdout();
+    let mut position = 0;
+    let mut buf = Vec::new();
+    loop {
+        if let Ok(mut file) = std::fs::File::open(path) {
+            let len = file.metadata()?.len();
+            if len < position {
+                position = 0;
+            }
+            if len > position {
+                file.seek(SeekFrom::Start(position))?;
+                buf.clear();
+                file.read_to_end(&mut buf)?;
+                position += buf.len() as u64;
+                stdout.write_all(&buf)?;
+                stdout.flush()?;
+            }
+        }
+        std::thread::sleep(FOLLOW_INTERVAL);
+    }
+}
+
+/// Human-readable file size
+fn format_size(bytes: u64) -> String {
+    match bytes {
+        0..1024 => format!("{} B", bytes),
+        1024..1_048_576 => format!("{:.1} KB", bytes as f64 / 1024.0),
+        _ => format!("{:.1} MB", bytes as f64 / 1_048_576.0),
+    }
+}
@@ -0,0 +1,103 @@
+//! Token usage command for axel.
+//!
+//! Provides `axel usage`, which totals the OTEL token counters in a
+//! workspace's event log and prints cost estimates per pane and model.
+
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use axel_core::server::{Pricing, TimestampedEvent, UsageTracker};
+use colored::Colorize;
+
+use crate::display_path;
+
+/// Print token usage and estimated cost from the event log at `log_path`
+pub fn show_usage(log_path: &Path, json: bool) -> Result<()> {
+    let content = match std::fs::read_to_string(log_path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(e) => {
+            return Err(e).with_context(|| format!("Failed to read {}", display_path(log_path)));
+        }
+    };
+    let events: Vec<TimestampedEvent> = content
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect();
+    let report = UsageTracker::from_events(&events).report(&Pricing::load()?);
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        return Ok(());
+    }
+
+    if report.panes.is_empty() {
+        println!(
+            "{}",
+            format!("No token usage recorded in {}", display_path(log_path)).dimmed()
+        );
+        return Ok(());
+    }
+
+    use comfy_table::{Table, presets::NOTHING};
+
+    let mut table = Table::new();
+    table.load_preset(NOTHING);
+    table.set_header(
+        [
+            "pane",
+            "model",
+            "input",
+            "output",
+            "cache read",
+            "cache write",
+            "cost",
+        ]
+        .map(|h| h.dimmed().to_string()),
+    );
+
+    for pane in &report.panes {
+        for (i, model) in pane.models.iter().enumerate() {
+            let pane_id = if i == 0 {
+                pane.pane_id.yellow().to_string()
+            } else {
+                String::new()
+            };
+            let cost = match model.cost_usd {
+                Some(cost) => format!("${:.2}", cost),
+                None => "?".dimmed().to_string(),
+            };
+            table.add_row(vec![
+                pane_id,
+                model.model.green().to_string(),
+                model.tokens.input_tokens.to_string(),
+                model.tokens.output_tokens.to_string(),
+                model.tokens.cache_read_tokens.to_string(),
+                model.tokens.cache_creation_tokens.to_string(),
+                cost,
+            ]);
+        }
+    }
+
+    println!("{table}");
+    println!(
+        "{} {} tokens, {} estimated",
+        "Total:".bold(),
+        report.tokens.total(),
+        format!("${:.2}", report.cost_usd).bold()
+    );
+    if report
+        .panes
+        .iter()
+        .flat_map(|p| &p.models)
+        .any(|m| m.cost_usd.is_none())
+    {
+        println!(
+            "{} {}",
+            "!".yellow(),
+            "Models without a price are excluded; add them to ~/.config/axel/pricing.toml".dimmed()
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,131 @@
+//! Workspace registry commands for axel.
+//!
+//! Provides `axel workspaces ls` and the lookup behind `axel open <name>`,
+//! both backed by the registry of workspaces axel has launched or
+//! initialized (see `axel_core::registry`).
+
+use std::path::PathBuf;
+
+use anyhow::{Result, bail};
+use axel_core::{
+    registry::{RegisteredWorkspace, Registry, registry_path},
+    tmux::has_session,
+};
+use chrono::Local;
+use colored::Colorize;
+
+use crate::{
+    display_path,
+    picker::{FuzzyPicker, fuzzy_score},
+};
+
+/// Known workspaces, most recently used first
+fn load_registry() -> Result<Registry> {
+    match registry_path() {
+        Some(path) => Registry::load(&path),
+        None => Ok(Registry::default()),
+    }
+}
+
+/// List the workspaces in the registry
+pub fn workspace_list(json_output: bool) -> Result<()> {
+    let registry = load_registry()?;
+
+    if json_output {
+        println!("{}", serde_json::to_string_pretty(&registry.workspaces)?);
+        return Ok(());
+    }
+
+    if registry.workspaces.is_empty() {
+        println!(
+            "{}",
+            "No known workspaces; launch one with axel to add it".dimmed()
+        );
+        return Ok(());
+    }
+
+    use comfy_table::{Table, presets::NOTHING};
+
+    let mut table = Table::new();
+    table.load_preset(NOTHING);
+
+    for workspace in &registry.workspaces {
+        let state = if !workspace.manifest.exists() {
+            "missing".red().to_string()
+        } else if has_session(&workspace.dir_name()) {
+            "running".green().to_string()
+        } else {
+            "-".dimmed().to_string()
+        };
+        table.add_row(vec![
+            workspace.name.blue().to_string(),
+            display_path(&workspace.manifest).dimmed().to_string(),
+            workspace
+                .last_used
+                .with_timezone(&Local)
+                .format("%Y-%m-%d %H:%M")
+                .to_string()
+                .dimmed()
+                .to_string(),
+            state,
+        ]);
+    }
+
+    println!("{table}");
+
+    Ok(())
+}
+
+/// Manifest of the known workspace matching `query`.
+///
+/// A workspace's name or directory name matching exactly wins; otherwise
+/// `query` is matched fuzzily, and the user picks when several workspaces
+/// match (or when there is no query).
+pub fn find_workspace(query: Option<&str>) -> Result<Option<PathBuf>> {
+    let workspaces: Vec<RegisteredWorkspace> = load_registry()?
+        .workspaces
+        .into_iter()
+        .filter(|w| w.manifest.exists())
+        .collect();
+    if workspaces.is_empty() {
+        bail!("No known workspaces; launch one with axel to add it");
+    }
+
+    let candidates: Vec<&RegisteredWorkspace> = match query {
+        Some(query) => {
+            if let Some(workspace) = workspaces
+                .iter()
+                .find(|w| w.name == query || w.dir_name() == query)
+            {
+                return Ok(Some(workspace.manifest.clone()));
+            }
+            let mut scored: Vec<(i64, &RegisteredWorkspace)> = workspaces
+                .iter()
+                .filter_map(|w| {
+                    let score = fuzzy_score(query, &w.name).max(fuzzy_score(query, &w.dir_name()));
+                    score.map(|score| (score, w))
+                })
+                .collect();
+            // Stable, so equal scores keep the most recently used first
+            scored.sort_by_key(|&(score, _)| std::cmp::Reverse(score));
+            scored.into_iter().map(|(_, w)| w).collect()
+        }
+        None => workspaces.iter().collect(),
+    };
+
+    match candidates.as_slice() {
+        [] => bail!(
+            "No known workspace matches '{}' (see axel workspaces ls)",
+            query.unwrap_or_default()
+        ),
+        [workspace] => Ok(Some(workspace.manifest.clone())),
+        _ => {
+            let labels: Vec<String> = candidates.iter().map(|w| w.name.clone()).collect();
+            let picked = FuzzyPicker::new("Workspace:", &labels, |i| {
+                vec![display_path(&candidates[i].manifest)]
+            })
+            .interact()?;
+            Ok(picked.map(|i| candidates[i].manifest.clone()))
+        }
+    }
+}
@@ -0,0 +1,371 @@
+//! Worktree commands for axel.
+//!
+//! `axel -w <branch>` creates worktrees implicitly; these commands list
+//! them alongside the axel session running in each one, create them ahead
+//! of a launch, and remove the ones nobody is using anymore. A session
+//! belongs to a worktree when its manifest (`AXEL_MANIFEST`) lives inside it.
+//!
+//! `axel pr <number>` (or `-w pr/<number>`) checks out a GitHub pull
+//! request the same way and seeds the AI panes with its description.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, bail};
+use axel_core::{
+    Error,
+    config::{WorktreeCopy, load_config, manifest_in},
+    git::{self, Worktree, WorktreeInfo},
+    github,
+    tmux::{SessionInfo, list_sessions},
+};
+use colored::Colorize;
+use serde::Serialize;
+
+use crate::display_path;
+
+/// A worktree with its axel session and working tree state
+#[derive(Debug, Serialize)]
+struct WorktreeStatus {
+    path: PathBuf,
+    branch: Option<String>,
+    head: String,
+    main: bool,
+    /// Name of the axel session launched from this worktree
+    session: Option<String>,
+    /// Uncommitted changes or untracked files; None when the directory is gone
+    dirty: Option<bool>,
+}
+
+/// Resolve the repository of the current directory, failing when git is
+/// missing or the directory isn't in a repository.
+fn current_repo() -> Result<PathBuf> {
+    git::ensure_available()?;
+    let cwd = std::env::current_dir()?;
+    if !git::is_git_repo(&cwd) {
+        bail!(Error::NotGitRepo);
+    }
+    git::repo_root(&cwd)
+}
+
+/// Collect the worktrees of `repo` with their sessions and dirty state.
+fn worktree_statuses(repo: &Path) -> Result<Vec<WorktreeStatus>> {
+    let worktrees = git::worktrees(repo)?;
+    // No tmux server (or no tmux at all) simply means no sessions
+    let sessions = list_sessions(true).unwrap_or_default();
+
+    Ok(worktrees
+        .iter()
+        .map(|worktree| WorktreeStatus {
+            path: worktree.path.clone(),
+            branch: worktree.branch.clone(),
+            head: worktree.head.clone(),
+            main: worktree.main,
+            session: session_for(worktree, &worktrees, &sessions),
+            dirty: (!worktree.prunable && worktree.path.is_dir())
+                .then(|| git::is_dirty(&worktree.path).ok())
+                .flatten(),
+        })
+        .collect())
+}
+
+/// Find the axel session launched from `worktree`.
+///
+/// A session's directory can be nested inside several worktrees (linked
+/// worktrees may live inside the main one), so it belongs to the deepest.
+fn session_for(
+    worktree: &Worktree,
+    worktrees: &[Worktree],
+    sessions: &[SessionInfo],
+) -> Option<String> {
+    let canonical = |path: &Path| path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    let own = canonical(&worktree.path);
+
+    sessions
+        .iter()
+        .find(|session| {
+            let Some(dir) = session
+                .working_dir
+                .as_deref()
+                .map(|d| canonical(Path::new(d)))
+            else {
+                return false;
+            };
+            dir.starts_with(&own)
+                && !worktrees.iter().any(|other| {
+                    let other = canonical(&other.path);
+                    other != own && other.starts_with(&own) && dir.starts_with(&other)
+                })
+        })
+        .map(|session| session.name.clone())
+}
+
+/// Label for a worktree in messages: its branch, or its directory when detached
+fn worktree_label(status: &WorktreeStatus) -> String {
+    status
+        .branch
+        .clone()
+        .unwrap_or_else(|| display_path(&status.path))
+}
+
+// =============================================================================
+// Worktree Listing
+// =============================================================================
+
+/// List the repository's worktrees with their branch, session and status.
+pub fn worktree_list(json_output: bool) -> Result<()> {
+    let repo = current_repo()?;
+    let statuses = worktree_statuses(&repo)?;
+
+    if json_output {
+        println!("{}", serde_json::to_string_pretty(&statuses)?);
+        return Ok(());
+    }
+
+    use comfy_table::{Table, presets::NOTHING};
+
+    let mut table = Table::new();
+    table.load_preset(NOTHING);
+
+    for status in &statuses {
+        let branch = match &status.branch {
+            Some(branch) => branch.blue().to_string(),
+            None => format!("({})", &status.head[..status.head.len().min(7)])
+                .yellow()
+                .to_string(),
+        };
+        let session = match &status.session {
+            Some(name) => name.green().to_string(),
+            None => "-".dimmed().to_string(),
+        };
+        let state = match status.dirty {
+            Some(true) => "dirty".yellow().to_string(),
+            Some(false) => "clean".dimmed().to_string(),
+            None => "missing".red().to_string(),
+        };
+        let mut path = display_path(&status.path).dimmed().to_string();
+        if status.main {
+            path = format!("{} {}", path, "(main)".dimmed());
+        }
+
+        table.add_row(vec![branch, path, session, state]);
+    }
+
+    println!("{table}");
+
+    Ok(())
+}
+
+// =============================================================================
+// Worktree Creation and Removal
+// =============================================================================
+
+/// Create (or reuse) the worktree for `branch` and report what was done.
+///
+/// Used by `axel worktree add` and by `-w/--worktree` before launching. A
+/// `pr/<number>` branch checks out that GitHub pull request instead, and
+/// its description is returned as the prompt to seed the AI panes with.
+pub fn open_worktree(branch: &str) -> Result<(WorktreeInfo, Option<String>)> {
+    if let Some(number) = github::pr_branch_number(branch) {
+        let (info, prompt) = open_pull_request(number)?;
+        return Ok((info, Some(prompt)));
+    }
+
+    let repo = current_repo()?;
+    let info = git::ensure_worktree(&repo, branch, &worktree_copy(&repo))
+        .context("Failed to create worktree")?;
+    report_worktree(&repo, &info);
+    Ok((info, None))
+}
+
+/// Check out GitHub pull request `number` in a worktree.
+///
+/// Returns the worktree and a prompt describing the pull request.
+pub fn open_pull_request(number: u64) -> Result<(WorktreeInfo, String)> {
+    let repo = current_repo()?;
+    let pr = github::fetch_pull_request(&repo, number)?;
+    eprintln!(
+        "{} {} #{} {}",
+        "✔".green(),
+        "Found pull request".dimmed(),
+        pr.number,
+        pr.title.blue()
+    );
+
+    let info = pr
+        .checkout(&repo, &worktree_copy(&repo))
+        .context("Failed to create worktree")?;
+    report_worktree(&repo, &info);
+    Ok((info, pr.to_prompt()))
+}
+
+/// `worktree.copy` entries of the repository's root manifest
+fn worktree_copy(repo: &Path) -> Vec<WorktreeCopy> {
+    load_config(&manifest_in(repo))
+        .map(|config| config.worktree.copy)
+        .unwrap_or_default()
+}
+
+/// Print what [`git::ensure_worktree`] did
+fn report_worktree(repo: &Path, info: &WorktreeInfo) {
+    if info.created {
+        if info.branch_created {
+            eprintln!(
+                "{} {} {} (from {})",
+                "✔".green(),
+                "Created branch".dimmed(),
+                info.branch.blue(),
+                git::default_branch(repo).unwrap_or_else(|_| "HEAD".to_string())
+            );
+        }
+        eprintln!(
+            "{} {} {}",
+            "✔".green(),
+            "Created worktree at".dimmed(),
+            display_path(&info.path)
+        );
+        if !info.copied.is_empty() {
+            eprintln!(
+                "{} {} {}",
+                "✔".green(),
+                "Copied".dimmed(),
+                info.copied.join(", ")
+            );
+        }
+    } else {
+        eprintln!(
+            "{} {} {}",
+            "✔".green(),
+            "Using existing worktree at".dimmed(),
+            display_path(&info.path)
+        );
+    }
+}
+
+/// Create the worktree for `branch` without launching a workspace.
+pub fn worktree_add(branch: &str) -> Result<()> {
+    open_worktree(branch)?;
+    eprintln!(
+        "{}",
+        format!("Launch it with `axel -w {}`", branch).dimmed()
+    );
+    Ok(())
+}
+
+/// Remove the worktree for `branch`.
+///
+/// Refuses while an axel session runs in it, and when it has uncommitted
+/// changes unless `force` is set. The branch itself is kept.
+pub fn worktree_rm(branch: &str, force: bool) -> Result<()> {
+    let repo = current_repo()?;
+    let status = worktree_statuses(&repo)?
+        .into_iter()
+        .find(|s| !s.main && s.branch.as_deref() == Some(branch))
+        .ok_or_else(|| Error::WorktreeNotFound(branch.to_string()))?;
+
+    if let Some(session) = status.session {
+        bail!(Error::WorktreeInUse {
+            branch: branch.to_string(),
+            session,
+        });
+    }
+    if status.dirty == Some(true) && !force {
+        bail!(Error::WorktreeDirty(branch.to_string()));
+    }
+
+    if !git::remove_worktree_at(&repo, &status.path, force)? {
+        bail!(
+            "Failed to remove worktree at {}",
+            display_path(&status.path)
+        );
+    }
+    println!(
+        "{} {} {}",
+        "✔".green(),
+        "Removed worktree for".dimmed(),
+        branch.blue()
+    );
+    Ok(())
+}
+
+/// Remove linked worktrees that no axel session is running in.
+///
+/// Worktrees with uncommitted changes are kept unless `force` is set.
+/// Stale references to deleted directories are pruned as well. With
+/// `dry_run`, only prints what would be removed.
+pub fn worktree_prune(dry_run: bool, force: bool, skip_confirm: bool) -> Result<()> {
+    let repo = current_repo()?;
+    let statuses = worktree_statuses(&repo)?;
+
+    let mut candidates = Vec::new();
+    for status in statuses.iter().filter(|s| !s.main && s.session.is_none()) {
+        match status.dirty {
+            Some(true) if !force => eprintln!(
+                "{} {} {}",
+                "!".yellow(),
+                "Keeping worktree with uncommitted changes:".dimmed(),
+                worktree_label(status)
+            ),
+            _ => candidates.push(status),
+        }
+    }
+
+    if candidates.is_empty() {
+        println!("{}", "No unused worktrees".dimmed());
+        if !dry_run {
+            git::prune_worktrees(&repo)?;
+        }
+        return Ok(());
+    }
+
+    for status in &candidates {
+        println!(
+            "  {} {}",
+            worktree_label(status).blue(),
+            display_path(&status.path).dimmed()
+        );
+    }
+    if dry_run {
+        return Ok(());
+    }
+
+    if !skip_confirm {
+        use dialoguer::{Confirm, theme::ColorfulTheme};
+        let confirmed = Confirm::with_theme(&ColorfulTheme::default())
+            .with_prompt(format!(
+                "Remove {} worktree{}?",
+                candidates.len(),
+                if candidates.len() == 1 { "" } else { "s" }
+            ))
+            .default(false)
+            .interact()?;
+
+        if !confirmed {
+            println!("{}", "Cancelled".dimmed());
+            return Ok(());
+        }
+    }
+
+    for status in candidates {
+        // Stale entries have no directory left; `git worktree prune` drops them
+        if status.dirty.is_none() {
+            continue;
+        }
+        match git::remove_worktree_at(&repo, &status.path, force) {
+            Ok(true) => println!(
+                "{} {} {}",
+                "✔".green(),
+                "Removed worktree for".dimmed(),
+                worktree_label(status).blue()
+            ),
+            Ok(false) => eprintln!(
+                "{} Failed to remove worktree at {}",
+                "✘".red(),
+                display_path(&status.path)
+            ),
+            Err(e) => eprintln!("{} Failed to remove worktree: {}", "✘".red(), e),
+        }
+    }
+    git::prune_worktrees(&repo)?;
+
+    Ok(())
+}
@@ -10,6 +10,9 @@
 //! - **cli**: Command-line argument definitions (clap)
 //! - **skill**: Skill management commands (list, new, import, fork, link, rm)
 //! - **session**: Session management commands (list, new, join, kill, launch)
+//! - **dash**: Operator dashboard (sessions, panes, live events, token usage)
+//! - **transcript**: Recording and viewing AI pane transcripts
+//! - **picker**: Fuzzy picker for interactive selection
 //!
 //! # Workflow
 //!
@@ -23,24 +26,45 @@
 
 mod cli;
 mod commands;
+mod picker;
 
-use std::path::{Path, PathBuf};
+use std::{
+    path::{Path, PathBuf},
+    process::ExitCode,
+    time::Duration,
+};
 
 use anyhow::Result;
 use axel_core::{
-    config::{generate_config, workspaces_dir},
-    git,
-    tmux::{attach_session, current_session, has_session},
+    Error,
+    config::{find_manifest, generate_config, load_config, read_prompt_file, workspaces_dir},
+    github, platform,
+    registry::register_workspace,
+    tmux::{self, current_session, has_session},
 };
 use clap::{CommandFactory, Parser};
-use cli::{Cli, Commands, LayoutCommands, SessionCommands, SkillCommands};
+use clap_complete::CompleteEnv;
+use cli::{
+    CheckpointCommands, Cli, Commands, ConfigCommands, LayoutCommands, PromptCommands,
+    SessionCommands, SkillCommands, WorkspaceCommands, WorktreeCommands,
+};
 use colored::Colorize;
 use commands::{
     session::{
-        do_kill_all_sessions, do_kill_workspace, do_list_sessions, launch_from_manifest,
-        launch_grid_by_name, launch_pane_by_name,
+        LaunchOptions, attach, do_kill_all_sessions, do_kill_workspace, do_list_sessions,
+        launch_from_manifest, launch_grid_by_name, launch_pane_by_name, record_pipe,
+        session_attach, session_broadcast, session_env, session_focus, session_play,
+        session_record, session_reload, session_restore, session_send, session_snapshot,
+        session_watch,
+    },
+    skill::{
+        fork_skill, import_skill, link_skill, list_skills, new_skill, rm_skill, skill_bench,
+        skill_deps, skill_install, skill_lint, skill_pack, skill_show, skill_status, skill_update,
+        sync_skills, where_skills,
+    },
+    worktree::{
+        open_pull_request, open_worktree, worktree_add, worktree_list, worktree_prune, worktree_rm,
     },
-    skill::{fork_skill, import_skill, link_skill, list_skills, new_skill, rm_skill},
 };
 
 // =============================================================================
@@ -49,7 +73,19 @@ use commands::{
 
 /// Entry point for the axel CLI.
 ///
-/// Parses command-line arguments and dispatches to the appropriate handler:
+/// Runs [`run`] and reports its error, if any, exiting with status 1.
+fn main() -> ExitCode {
+    tmux::exit_on_interrupt();
+    match run() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            report_error(&e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Parse command-line arguments and dispatch to the appropriate handler:
 ///
 /// - **Subcommands** (`init`, `bootstrap`, `skill`): Handled first
 /// - **Flags** (`-k`): Kill workspace
@@ -59,72 +95,78 @@ use commands::{
 /// The manifest path is resolved by walking up the directory tree from the
 /// current directory until `AXEL.md` is found, or uses the path specified
 /// with `-m/--manifest-path`.
-fn main() -> Result<()> {
-    let cli = Cli::parse();
+fn run() -> Result<()> {
+    // Answer the completion scripts of `axel completions` and exit
+    CompleteEnv::with_factory(|| {
+        let manifest_path =
+            resolve_manifest_path(commands::completions::completing_manifest_path().as_deref());
+        commands::completions::command(&manifest_path, &manifest_base_dir(&manifest_path))
+    })
+    .var(commands::completions::COMPLETE_VAR)
+    .complete();
+
+    let mut cli = Cli::parse();
+    if let Some(ref path) = cli.prompt_file {
+        cli.prompt = Some(read_prompt_file(path)?);
+    }
     let workspaces_dir = workspaces_dir();
 
-    // Handle git worktree if specified
-    let _worktree_info = if let Some(ref branch) = cli.worktree {
-        let cwd = std::env::current_dir()?;
-        if !git::is_git_repo(&cwd) {
-            eprintln!("{} Not a git repository", "✘".red());
-            std::process::exit(1);
-        }
-
-        match git::ensure_worktree(&cwd, branch) {
-            Ok(info) => {
-                if info.created {
-                    if info.branch_created {
-                        eprintln!(
-                            "{} {} {} (from {})",
-                            "✔".green(),
-                            "Created branch".dimmed(),
-                            info.branch.blue(),
-                            git::default_branch(&cwd).unwrap_or_else(|_| "HEAD".to_string())
-                        );
-                    }
-                    eprintln!(
-                        "{} {} {}",
-                        "✔".green(),
-                        "Created worktree at".dimmed(),
-                        display_path(&info.path)
-                    );
-                } else {
-                    eprintln!(
-                        "{} {} {}",
-                        "✔".green(),
-                        "Using existing worktree at".dimmed(),
-                        display_path(&info.path)
-                    );
-                }
-                // Change to worktree directory
-                std::env::set_current_dir(&info.path)?;
-                Some(info)
-            }
-            Err(e) => {
-                eprintln!("{} Failed to create worktree: {}", "✘".red(), e);
-                std::process::exit(1);
-            }
-        }
+    // Handle git worktree if specified; `pr/<number>` also yields a prompt
+    let (worktree_prompt, new_worktree) = if let Some(ref branch) = cli.worktree {
+        let (info, prompt) = open_worktree(branch)?;
+        // Change to worktree directory
+        std::env::set_current_dir(&info.path)?;
+        (prompt, info.created)
     } else {
-        None
+        (None, false)
     };
 
     let manifest_path = resolve_manifest_path(cli.manifest_path.as_deref());
     let base_dir = manifest_base_dir(&manifest_path);
 
+    // With `tmux.isolation: socket`, the workspace's sessions live on axel's
+    // own tmux server
+    if let Ok(config) = load_config(&manifest_path) {
+        config.tmux.use_server();
+    }
+
     // Handle subcommands first
     if let Some(command) = cli.command {
         return match command {
             Commands::Init { workspace } => init_workspace(workspace),
             Commands::Bootstrap => bootstrap_skills(),
             Commands::Skill { action } => match action {
-                SkillCommands::List => list_skills(&manifest_path, &base_dir),
+                SkillCommands::List { json } => list_skills(&manifest_path, &base_dir, json),
                 SkillCommands::New { name } => new_skill(name.as_deref(), &base_dir),
                 SkillCommands::Import { path } => import_skill(&path),
-                SkillCommands::Fork { name } => fork_skill(&name, &manifest_path, &base_dir),
-                SkillCommands::Link { name } => link_skill(&name, &manifest_path, &base_dir),
-                SkillCommands::Rm { name } => rm_skill(&name, &manifest_path, &base_dir),
+                SkillCommands::Fork { name } => fork_skill(&name, &base_dir),
+                SkillCommands::Link { name } => link_skill(&name, &base_dir),
+                SkillCommands::Rm { name } => rm_skill(&name, &base_dir),
+                SkillCommands::Deps { name, tree } => {
+                    skill_deps(name.as_deref(), tree, &manifest_path)
+                }
+                SkillCommands::Sync { url, name } => sync_skills(url.as_deref(), name.as_deref()),
+                SkillCommands::Status { json } => skill_status(&manifest_path, json),
+                SkillCommands::Update => skill_update(&manifest_path),
+                SkillCommands::Pack { names, output } => {
+                    skill_pack(&names, &output, &manifest_path, &base_dir)
+                }
+                SkillCommands::Install {
+                    bundle,
+                    local,
+                    force,
+                } => skill_install(&bundle, local, force, &base_dir),
+                SkillCommands::Show {
+                    name,
+                    content,
+                    json,
+                } => skill_show(&name, content, json, &manifest_path),
+                SkillCommands::Lint {
+                    names,
+                    strict,
+                    json,
+                } => skill_lint(&names, strict, json, &manifest_path),
+                SkillCommands::Bench { count, driver } => skill_bench(count, &driver),
             },
             Commands::Session { action } => match action {
                 SessionCommands::List { all, json } => do_list_sessions(!all, json),
@@ -135,52 +177,23 @@ fn main() -> Result<()> {
                     pane_id,
                     port,
                     prompt,
+                    prompt_file,
                     worktree,
+                    only,
                     tmux,
                 } => {
+                    let prompt = match prompt_file {
+                        Some(path) => Some(read_prompt_file(&path)?),
+                        None => prompt,
+                    };
                     // Handle git worktree if specified at subcommand level
+                    let (mut worktree_prompt, mut new_worktree) = (None, false);
                     if let Some(ref branch) = worktree {
-                        let cwd = std::env::current_dir()?;
-                        if !git::is_git_repo(&cwd) {
-                            eprintln!("{} Not a git repository", "✘".red());
-                            std::process::exit(1);
-                        }
-
-                        match git::ensure_worktree(&cwd, branch) {
-                            Ok(info) => {
-                                if info.created {
-                                    if info.branch_created {
-                                        eprintln!(
-                                            "{} {} {} (from {})",
-                                            "✔".green(),
-                                            "Created branch".dimmed(),
-                                            info.branch.blue(),
-                                            git::default_branch(&cwd)
-                                                .unwrap_or_else(|_| "HEAD".to_string())
-                                        );
-                                    }
-                                    eprintln!(
-                                        "{} {} {}",
-                                        "✔".green(),
-                                        "Created worktree at".dimmed(),
-                                        display_path(&info.path)
-                                    );
-                                } else {
-                                    eprintln!(
-                                        "{} {} {}",
-                                        "✔".green(),
-                                        "Using existing worktree at".dimmed(),
-                                        display_path(&info.path)
-                                    );
-                                }
-                                // Change to worktree directory
-                                std::env::set_current_dir(&info.path)?;
-                            }
-                            Err(e) => {
-                                eprintln!("{} Failed to create worktree: {}", "✘".red(), e);
-                                std::process::exit(1);
-                            }
-                        }
+                        let (info, prompt) = open_worktree(branch)?;
+                        // Change to worktree directory
+                        std::env::set_current_dir(&info.path)?;
+                        worktree_prompt = prompt;
+                        new_worktree = info.created;
                     }
 
                     // Re-resolve manifest path after potential worktree change
@@ -191,11 +204,13 @@ fn main() -> Result<()> {
                         launch_pane_by_name(
                             &manifest_path,
                             &name,
-                            prompt.as_deref(),
+                            prompt.as_deref().or(worktree_prompt.as_deref()),
                             pane_id.as_deref(),
                             port,
                             tmux,
                             session_name.as_deref(),
+                            &cli.skill_vars,
+                            new_worktree,
                         )
                     } else if let Some(grid_name) = grid {
                         // Launch a specific grid layout
@@ -205,10 +220,31 @@ fn main() -> Result<()> {
                             session_name.as_deref(),
                             pane_id.as_deref(),
                             port,
+                            &LaunchOptions {
+                                only: &only,
+                                attach_in: cli.attach_in,
+                                no_clear: cli.no_clear,
+                                profile_timing: cli.profile_timing,
+                                skill_vars: &cli.skill_vars,
+                                prompt: worktree_prompt.as_deref(),
+                                worktree_setup: new_worktree,
+                            },
                         )
                     } else {
                         // Launch the default grid (full workspace)
-                        launch_from_manifest(&manifest_path, cli.profile.as_deref())
+                        launch_from_manifest(
+                            &manifest_path,
+                            cli.profile.as_deref(),
+                            &LaunchOptions {
+                                only: &only,
+                                attach_in: cli.attach_in,
+                                no_clear: cli.no_clear,
+                                profile_timing: cli.profile_timing,
+                                skill_vars: &cli.skill_vars,
+                                prompt: worktree_prompt.as_deref(),
+                                worktree_setup: new_worktree,
+                            },
+                        )
                     }
                 }
                 SessionCommands::Join { name } => {
@@ -218,8 +254,52 @@ fn main() -> Result<()> {
                         do_list_sessions(false, false)?;
                         std::process::exit(1);
                     }
-                    attach_session(&name)
+                    attach(&name, false, cli.attach_in)
+                }
+                SessionCommands::Env { name, vars } => session_env(&name, &vars),
+                SessionCommands::Broadcast {
+                    text,
+                    session,
+                    panes,
+                } => session_broadcast(&text, session.as_deref(), &panes),
+                SessionCommands::Focus { session, prev } => session_focus(session.as_deref(), prev),
+                SessionCommands::Watch { session, interval } => {
+                    session_watch(session.as_deref(), interval)
                 }
+                SessionCommands::Reload { session, grid } => {
+                    session_reload(session.as_deref(), grid.as_deref(), cli.profile_timing)
+                }
+                SessionCommands::Snapshot {
+                    session,
+                    file,
+                    scrollback,
+                } => session_snapshot(session.as_deref(), file.as_deref(), scrollback, &base_dir),
+                SessionCommands::Restore { file, session_name } => session_restore(
+                    file.as_deref(),
+                    session_name.as_deref(),
+                    &base_dir,
+                    cli.attach_in,
+                    cli.profile_timing,
+                ),
+                SessionCommands::Record {
+                    name,
+                    panes,
+                    stop,
+                    pipe,
+                    transcript,
+                } => match pipe {
+                    Some(cast) => record_pipe(
+                        name.as_deref().unwrap_or_default(),
+                        &cast,
+                        transcript.as_deref(),
+                    ),
+                    None => session_record(name.as_deref(), &panes, stop, &base_dir),
+                },
+                SessionCommands::Play {
+                    file,
+                    speed,
+                    idle_limit,
+                } => session_play(&file, speed, idle_limit),
                 SessionCommands::Kill {
                     name,
                     all,
@@ -248,13 +328,200 @@ fn main() -> Result<()> {
                     }
                 }
             },
-            Commands::Server { port, session, log } => {
+            Commands::Worktree { action } => match action {
+                WorktreeCommands::List { json } => worktree_list(json),
+                WorktreeCommands::Add { branch } => worktree_add(&branch),
+                WorktreeCommands::Rm { branch, force } => worktree_rm(&branch, force),
+                WorktreeCommands::Prune {
+                    dry_run,
+                    force,
+                    confirm,
+                } => worktree_prune(dry_run, force, confirm),
+            },
+            Commands::Workspace { action } => match action {
+                WorkspaceCommands::List { json } => commands::workspace::workspace_list(json),
+            },
+            Commands::Open { name } => {
+                let Some(manifest_path) = commands::workspace::find_workspace(name.as_deref())?
+                else {
+                    return Ok(());
+                };
+                if let Some(dir) = manifest_path.parent() {
+                    std::env::set_current_dir(dir)?;
+                }
+                launch_from_manifest(
+                    &manifest_path,
+                    cli.profile.as_deref(),
+                    &LaunchOptions {
+                        only: &cli.only,
+                        attach_in: cli.attach_in,
+                        no_clear: cli.no_clear,
+                        profile_timing: cli.profile_timing,
+                        skill_vars: &cli.skill_vars,
+                        prompt: None,
+                        worktree_setup: false,
+                    },
+                )
+            }
+            Commands::Attach { name, all } => session_attach(name.as_deref(), all, cli.attach_in),
+            Commands::Up => commands::fleet::up(),
+            Commands::Down { keep_skills } => commands::fleet::down(&workspaces_dir, keep_skills),
+            Commands::Broadcast {
+                text,
+                session,
+                panes,
+            } => session_broadcast(&text, session.as_deref(), &panes),
+            Commands::Send {
+                target,
+                prompt,
+                wait,
+                timeout,
+            } => session_send(&target, &prompt, wait, Duration::from_secs(timeout)),
+            Commands::Pr { pr } => {
+                let number = github::parse_pr_number(&pr)
+                    .ok_or_else(|| anyhow::anyhow!("Invalid pull request '{}'", pr))?;
+                let (info, prompt) = open_pull_request(number)?;
+                std::env::set_current_dir(&info.path)?;
+
+                let manifest_path = resolve_manifest_path(cli.manifest_path.as_deref());
+                launch_from_manifest(
+                    &manifest_path,
+                    cli.profile.as_deref(),
+                    &LaunchOptions {
+                        only: &cli.only,
+                        attach_in: cli.attach_in,
+                        no_clear: cli.no_clear,
+                        profile_timing: cli.profile_timing,
+                        skill_vars: &cli.skill_vars,
+                        prompt: Some(&prompt),
+                        worktree_setup: info.created,
+                    },
+                )
+            }
+            Commands::WhereSkills { driver } => where_skills(&driver, &base_dir),
+            Commands::UpgradeConfig { dry_run } => {
+                commands::config::upgrade_config(&manifest_path, dry_run)
+            }
+            Commands::Config { action } => match action {
+                ConfigCommands::Validate { json } => {
+                    commands::config::validate_config(&manifest_path, json)
+                }
+            },
+            Commands::Doctor { port } => commands::doctor::doctor(&manifest_path, port),
+            Commands::Status { json } => commands::status::status(&manifest_path, json),
+            Commands::Completions { shell } => commands::completions::completions(shell),
+            Commands::Usage { log, json } => {
+                let log = log.unwrap_or_else(|| base_dir.join(".axel/events.jsonl"));
+                commands::usage::show_usage(&log, json)
+            }
+            Commands::Audit {
+                pane,
+                since,
+                stat,
+                log,
+                json,
+            } => {
+                let log = log.unwrap_or_else(|| base_dir.join(".axel/events.jsonl"));
+                commands::audit::show_audit(&log, pane, since, &base_dir, stat, json)
+            }
+            Commands::Checkpoints { action } => match action {
+                CheckpointCommands::List { json } => commands::checkpoint::checkpoint_list(json),
+                CheckpointCommands::Diff { checkpoint, stat } => {
+                    commands::checkpoint::checkpoint_diff(checkpoint.as_deref(), stat)
+                }
+                CheckpointCommands::Restore { checkpoint } => {
+                    commands::checkpoint::checkpoint_restore(&checkpoint)
+                }
+            },
+            Commands::Transcript {
+                pane,
+                previous,
+                follow,
+                record,
+                session,
+            } => match record {
+                Some(path) => commands::transcript::record(&path, session.as_deref()),
+                None => commands::transcript::show_transcript(
+                    &base_dir,
+                    pane.as_deref(),
+                    previous,
+                    follow,
+                ),
+            },
+            Commands::Dash { port } => commands::dash::dash(port),
+            Commands::Server {
+                port,
+                session,
+                log,
+                db,
+                metrics,
+                rotate_mb,
+                rotate_hours,
+                keep_archives,
+                keep_days,
+            } => {
                 // Run the server in async context
                 let rt = tokio::runtime::Runtime::new()?;
                 rt.block_on(async {
-                    commands::server::run(commands::server::ServerArgs { port, session, log }).await
+                    commands::server::run(
+                        commands::server::ServerArgs {
+                            port,
+                            session,
+                            log,
+                            db,
+                            metrics,
+                            rotate_mb,
+                            rotate_hours,
+                            keep_archives,
+                            keep_days,
+                        },
+                        &manifest_path,
+                    )
+                    .await
                 })
             }
+            Commands::Run {
+                pane,
+                prompt,
+                prompt_file,
+            } => {
+                let prompt = match prompt_file {
+                    Some(path) => Some(read_prompt_file(&path)?),
+                    None => prompt,
+                };
+                commands::session::run_pane(
+                    &manifest_path,
+                    &pane,
+                    prompt.as_deref(),
+                    &cli.skill_vars,
+                )
+            }
+            Commands::Ci {
+                grid,
+                timeout,
+                max_turns,
+                junit,
+                json,
+            } => commands::ci::ci(
+                &manifest_path,
+                &grid,
+                &commands::ci::CiLimits {
+                    timeout: Duration::from_secs(timeout),
+                    max_turns,
+                },
+                junit.as_deref(),
+                json.as_deref(),
+                &cli.skill_vars,
+            ),
+            Commands::Prompt { action } => match action {
+                PromptCommands::List { json } => {
+                    commands::prompt::list_prompts(&manifest_path, json)
+                }
+                PromptCommands::Show { name } => {
+                    commands::prompt::show_prompt(&name, &manifest_path)
+                }
+                PromptCommands::New { name } => commands::prompt::new_prompt(&name),
+            },
             Commands::Layout { action } => match action {
                 LayoutCommands::List { json } => {
                     commands::layout::list_panes(cli.manifest_path.as_deref(), json)
@@ -272,7 +539,7 @@ fn main() -> Result<()> {
             eprintln!("{} --pane-id requires --prompt", "✘".red());
             std::process::exit(1);
         }
-        axel_core::tmux::send_keys(pane_id, prompt)?;
+        axel_core::tmux::send_text(pane_id, prompt)?;
         return Ok(());
     }
 
@@ -302,11 +569,13 @@ fn main() -> Result<()> {
             launch_pane_by_name(
                 &manifest_path,
                 name,
-                cli.prompt.as_deref(),
+                cli.prompt.as_deref().or(worktree_prompt.as_deref()),
                 cli.pane_id.as_deref(),
                 cli.server_port,
                 cli.tmux,
                 cli.session_name.as_deref(),
+                &cli.skill_vars,
+                new_worktree,
             )?;
         } else {
             eprintln!(
@@ -317,7 +586,19 @@ fn main() -> Result<()> {
             std::process::exit(1);
         }
     } else if cli.manifest_path.is_some() || manifest_path.exists() {
-        launch_from_manifest(&manifest_path, cli.profile.as_deref())?;
+        launch_from_manifest(
+            &manifest_path,
+            cli.profile.as_deref(),
+            &LaunchOptions {
+                only: &cli.only,
+                attach_in: cli.attach_in,
+                no_clear: cli.no_clear,
+                profile_timing: cli.profile_timing,
+                skill_vars: &cli.skill_vars,
+                prompt: worktree_prompt.as_deref(),
+                worktree_setup: new_worktree,
+            },
+        )?;
     } else {
         Cli::command().print_help()?;
     }
@@ -325,6 +606,34 @@ fn main() -> Result<()> {
     Ok(())
 }
 
+/// Print an error returned by a command.
+///
+/// Typed [`Error`]s get the same `✘` line as other messages, plus a hint
+/// when there is an obvious next step. Anything else is printed with its
+/// causes.
+fn report_error(error: &anyhow::Error) {
+    let Some(e) = error.downcast_ref::<Error>() else {
+        eprintln!("Error: {:?}", error);
+        return;
+    };
+    eprintln!("{} {}", "✘".red(), e);
+    let hint = match e {
+        Error::SessionNotFound(_) => "Run `axel session list` to see running sessions",
+        Error::SkillNotFound(_) | Error::GlobalSkillNotFound(_) => {
+            "Run `axel skill list` to see available skills"
+        }
+        Error::SessionConflict { .. } => {
+            "To fix this, update the 'workspace' field in your AXEL.md to use a unique name."
+        }
+        Error::AmbiguousPane { .. } => "Pick one with `axel send <session>:<pane>`",
+        Error::WorktreeNotFound(_) => "Run `axel worktree ls` to see worktrees",
+        Error::WorktreeInUse { .. } => "Kill the session first with `axel session kill <name>`",
+        Error::WorktreeDirty(_) => "Use --force to remove it anyway",
+        _ => return,
+    };
+    eprintln!("{}", hint.dimmed());
+}
+
 // =============================================================================
 // Path Resolution
 // =============================================================================
@@ -347,12 +656,11 @@ fn resolve_manifest_path(cli_path: Option<&str>) -> PathBuf {
         return make_absolute(&path);
     }
 
-    // Walk up directory tree looking for AXEL.md
+    // Walk up directory tree looking for AXEL.md (or axel.yaml)
     let mut current = std::env::current_dir().unwrap_or_default();
     loop {
-        let md_candidate = current.join("AXEL.md");
-        if md_candidate.exists() {
-            return md_candidate;
+        if let Some(manifest) = find_manifest(&current) {
+            return manifest;
         }
 
         match current.parent() {
@@ -426,6 +734,7 @@ fn init_workspace(workspace_name: Option<String>) -> Result<()> {
     let config_content = generate_config(&name, &current_dir.to_string_lossy());
     std::fs::write(&config_path, config_content)?;
     println!("{} {} AXEL.md", "✔".green(), "Created".dimmed());
+    register_workspace(&name, &config_path).ok();
 
     println!();
     println!("Launch with: {}", "axel".blue());
@@ -446,8 +755,6 @@ fn init_workspace(workspace_name: Option<String>) -> Result<()> {
 ///
 /// For more controlled imports, prefer `axel skill import`.
 fn bootstrap_skills() -> Result<()> {
-    use std::os::unix::process::CommandExt;
-
     use axel_core::all_skill_patterns;
     use dialoguer::{Confirm, Input, Select, theme::ColorfulTheme};
     use ignore::WalkBuilder;
@@ -654,7 +961,7 @@ Please consolidate and organize them into clean skills:
     // Change to the global skills directory and launch AI
     std::env::set_current_dir(&global_skills_dir)?;
 
-    let err = std::process::Command::new(ai_command).arg(&prompt).exec();
+    let err = platform::exec(std::process::Command::new(ai_command).arg(&prompt));
 
     Err(err.into())
 }
@@ -0,0 +1,215 @@
+//! Fuzzy picker for interactive selection.
+//!
+//! Filters a list as the user types, matching the query's characters in
+//! order anywhere in an item (`fa2` finds `repo-feat-auth-codex-2`), and
+//! shows a preview of the highlighted item below the list.
+//!
+//! Keys: type to filter, ↑/↓ (or Tab) to move, Enter to pick, Esc to cancel.
+
+use std::collections::HashMap;
+
+use anyhow::{Result, bail};
+use colored::Colorize;
+use console::{Key, Term};
+
+/// Items shown at once
+const VISIBLE_ITEMS: usize = 10;
+
+/// An interactive fuzzy picker over `items`.
+pub struct FuzzyPicker<'a, F> {
+    prompt: &'a str,
+    items: &'a [String],
+    /// Lines shown below the list for the highlighted item
+    preview: F,
+}
+
+impl<'a, F: FnMut(usize) -> Vec<String>> FuzzyPicker<'a, F> {
+    pub fn new(prompt: &'a str, items: &'a [String], preview: F) -> Self {
+        Self {
+            prompt,
+            items,
+            preview,
+        }
+    }
+
+    /// Show the picker and return the index of the picked item, or None if
+    /// the user cancelled.
+    pub fn interact(mut self) -> Result<Option<usize>> {
+        let term = Term::stderr();
+        if !term.is_term() {
+            bail!("Interactive picker needs a terminal");
+        }
+
+        let mut query = String::new();
+        let mut selected = 0;
+        let mut previews: HashMap<usize, Vec<String>> = HashMap::new();
+        let mut drawn = 0;
+
+        term.hide_cursor()?;
+        let _cursor = ShowCursorOnDrop(&term);
+        let picked = loop {
+            let matches = self.matches(&query);
+            selected = selected.min(matches.len().saturating_sub(1));
+
+            let mut lines = vec![format!("{} {}", self.prompt.bold(), query)];
+            let start = selected.saturating_sub(VISIBLE_ITEMS - 1);
+            for (row, &index) in matches.iter().enumerate().skip(start).take(VISIBLE_ITEMS) {
+                if row == selected {
+                    lines.push(format!("{} {}", "❯".cyan(), self.items[index].bold()));
+                } else {
+                    lines.push(format!("  {}", self.items[index]));
+                }
+            }
+            match matches.get(selected) {
+                Some(&index) => {
+                    let preview = previews
+                        .entry(index)
+                        .or_insert_with(|| (self.preview)(index));
+                    lines.push(String::new());
+                    lines.extend(preview.iter().map(|line| format!("  {}", line.dimmed())));
+                }
+                None => lines.push(format!("  {}", "No matches".dimmed())),
+            }
+
+            term.clear_last_lines(drawn)?;
+            for line in &lines {
+                term.write_line(line)?;
+            }
+            drawn = lines.len();
+
+            match term.read_key()? {
+                Key::Enter => break matches.get(selected).copied(),
+                Key::Escape | Key::Char('\u{3}') => break None,
+                Key::ArrowUp | Key::BackTab => selected = selected.saturating_sub(1),
+                Key::ArrowDown | Key::Tab if selected + 1 < matches.len() => selected += 1,
+                Key::Backspace => {
+                    query.pop();
+                    selected = 0;
+                }
+                Key::Char(c) if !c.is_control() => {
+                    query.push(c);
+                    selected = 0;
+                }
+                _ => {}
+            }
+        };
+
+        term.clear_last_lines(drawn)?;
+        Ok(picked)
+    }
+
+    /// Indices of the items matching `query`, best match first
+    fn matches(&self, query: &str) -> Vec<usize> {
+        let mut scored: Vec<(i64, usize)> = self
+            .items
+            .iter()
+            .enumerate()
+            .filter_map(|(i, item)| fuzzy_score(query, item).map(|score| (score, i)))
+            .collect();
+        // Stable, so equal scores keep the list order
+        scored.sort_by_key(|&(score, _)| std::cmp::Reverse(score));
+        scored.into_iter().map(|(_, i)| i).collect()
+    }
+}
+
+/// Shows the terminal's cursor again when the picker returns, errors
+/// included
+struct ShowCursorOnDrop<'a>(&'a Term);
+
+impl Drop for ShowCursorOnDrop<'_> {
+    fn drop(&mut self) {
+        let _ = self.0.show_cursor();
+    }
+}
+
+/// Score how well `query` matches `candidate`, or None if it doesn't.
+///
+/// Every query character must appear in order (case-insensitively).
+/// Consecutive matches and matches at the start of a word (after `-`, `_`,
+/// `/`, `.` or a space) score higher; skipped characters cost a little.
+pub(crate) fn fuzzy_score(query: &str, candidate: &str) -> Option<i64> {
+    let candidate: Vec<char> = candidate.chars().flat_map(char::to_lowercase).collect();
+    let mut score = 0;
+    let mut position = 0;
+    let mut previous: Option<usize> = None;
+
+    for q in query.chars().flat_map(char::to_lowercase) {
+        let found = position + candidate[position..].iter().position(|&c| c == q)?;
+        score += 1;
+        if previous == Some(found.wrapping_sub(1)) {
+            score += 5;
+        }
+        if found == 0 || matches!(candidate[found - 1], '-' | '_' | '/' | '.' | ' ') {
+            score += 3;
+        }
+        score -= (found - position) as i64;
+        previous = Some(found);
+        position = found + 1;
+    }
+
+    Some(score)
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fuzzy_score_matches() {
+        let cases = [
+            // (query, candidate, matches)
+            ("", "anything", true),
+            ("fa2", "repo-feat-auth-codex-2", true),
+            ("FEAT", "repo-feat-auth", true),
+            ("feat", "REPO-FEAT-AUTH", true),
+            ("taef", "repo-feat-auth", false),
+            ("featx", "repo-feat-auth", false),
+            ("a", "", false),
+        ];
+        for (query, candidate, matches) in cases {
+            assert_eq!(
+                fuzzy_score(query, candidate).is_some(),
+                matches,
+                "{:?} in {:?}",
+                query,
+                candidate
+            );
+        }
+    }
+
+    #[test]
+    fn test_fuzzy_score_ordering() {
+        let cases = [
+            // (query, better, worse)
+            ("api", "api-server", "a-p-i"),
+            ("api", "web-api", "wrapping"),
+            ("cod", "codex", "claude-opus-dev"),
+            ("auth", "auth", "feat-auth"),
+        ];
+        for (query, better, worse) in cases {
+            assert!(
+                fuzzy_score(query, better) > fuzzy_score(query, worse),
+                "{:?}: {:?} should rank above {:?}",
+                query,
+                better,
+                worse
+            );
+        }
+    }
+
+    #[test]
+    fn test_matches_best_first() {
+        let items: Vec<String> = ["wrapping", "web-api", "api-server", "docs"]
+            .map(String::from)
+            .to_vec();
+        let picker = FuzzyPicker::new("Pick:", &items, |_| Vec::new());
+        assert_eq!(picker.matches("api"), vec![2, 1, 0]);
+        // Every item matches an empty query, in list order
+        assert_eq!(picker.matches(""), vec![0, 1, 2, 3]);
+        assert!(picker.matches("zzz").is_empty());
+    }
+}
@@ -0,0 +1,281 @@
+//! Audit of the files agents change
+//!
+//! When a Claude pane reports `PostToolUse` for a tool that writes files
+//! (`Edit`, `MultiEdit`, `Write`, `NotebookEdit`), the event server captures
+//! the file's diff against `HEAD` with git and logs it as a `file_diff`
+//! event next to the hook. Untracked files are diffed as wholly added;
+//! ignored files and files outside a git repository aren't captured.
+//!
+//! Each diff holds everything changed in the file since the last commit,
+//! so the last one captured of each file is what an agent's edits add up
+//! to. `axel audit` lists them (see [`audit`]) to review before committing.
+
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use anyhow::{Context, Result, bail};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::server::TimestampedEvent;
+
+/// Event type of the diffs the event server captures
+pub const FILE_DIFF_EVENT: &str = "file_diff";
+
+/// Claude Code tools that write the file named in their input
+const WRITING_TOOLS: &[&str] = &["Edit", "MultiEdit", "Write", "NotebookEdit"];
+
+/// The diff of a file after a tool call wrote it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileDiff {
+    pub tool_name: String,
+    pub file_path: PathBuf,
+    /// `git diff` of the file against `HEAD`, empty when it has no changes
+    pub diff: String,
+}
+
+/// The tool and absolute path of the file written by the tool call of a
+/// `PostToolUse` payload, if the tool writes files
+pub fn written_file(payload: &Value) -> Option<(String, PathBuf)> {
+    let tool = payload.get("tool_name")?.as_str()?;
+    if !WRITING_TOOLS.contains(&tool) {
+        return None;
+    }
+    let input = payload.get("tool_input")?;
+    let path = ["file_path", "notebook_path"]
+        .iter()
+        .find_map(|field| input.get(*field)?.as_str())?;
+    let path = Path::new(path);
+    let path = match payload.get("cwd").and_then(Value::as_str) {
+        Some(cwd) if path.is_relative() => Path::new(cwd).join(path),
+        _ => path.to_path_buf(),
+    };
+    Some((tool.to_string(), path))
+}
+
+/// Capture the diff of the file written by the tool call of a
+/// `PostToolUse` payload, or `None` for other tools and files git can't
+/// diff
+pub fn capture_diff(payload: &Value) -> Option<FileDiff> {
+    let (tool_name, file_path) = written_file(payload)?;
+    let diff = file_diff(&file_path).ok()?;
+    Some(FileDiff {
+        tool_name,
+        file_path,
+        diff,
+    })
+}
+
+/// `git diff` of the file at `path` against `HEAD`, or the whole file as
+/// added when git doesn't track it
+pub fn file_diff(path: &Path) -> Result<String> {
+    let (dir, name) = match (path.parent(), path.file_name()) {
+        (Some(dir), Some(name)) => (dir, name),
+        _ => bail!("Not a file: {}", path.display()),
+    };
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .args(args)
+            .arg("--")
+            .arg(name)
+            .current_dir(dir)
+            .output()
+            .context("Failed to execute git")
+    };
+
+    let tracked = git(&["ls-files", "--error-unmatch"])?;
+    if tracked.status.success() {
+        let output = git(&["diff", "--no-color", "--no-ext-diff", "HEAD"])?;
+        if !output.status.success() {
+            bail!(
+                "Failed to diff {}: {}",
+                path.display(),
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+        return Ok(String::from_utf8_lossy(&output.stdout).into_owned());
+    }
+    if String::from_utf8_lossy(&tracked.stderr).contains("not a git repository") {
+        bail!("Not in a git repository: {}", path.display());
+    }
+    if git(&["check-ignore", "-q"])?.status.success() {
+        bail!("Ignored by git: {}", path.display());
+    }
+
+    // Untracked: `--no-index` exits with 1 when the files differ
+    let output = Command::new("git")
+        .args(["diff", "--no-color", "--no-ext-diff", "--no-index", "--"])
+        .arg("/dev/null")
+        .arg(name)
+        .current_dir(dir)
+        .output()
+        .context("Failed to execute git")?;
+    if output.status.code() != Some(0) && output.status.code() != Some(1) {
+        bail!(
+            "Failed to diff {}: {}",
+            path.display(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// A file changed by agents, with the last diff captured of it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AuditEntry {
+    pub file_path: PathBuf,
+    /// Panes that changed the file, in the order they first did
+    pub panes: Vec<String>,
+    /// Tool calls that wrote the file
+    pub changes: usize,
+    pub first_change: DateTime<Utc>,
+    pub last_change: DateTime<Utc>,
+    /// Lines added and removed by `diff`
+    pub added: usize,
+    pub removed: usize,
+    pub diff: String,
+}
+
+/// The files changed in the `file_diff` events among `events`, in the
+/// order they were first changed
+pub fn audit(events: &[TimestampedEvent]) -> Vec<AuditEntry> {
+    let mut entries: Vec<AuditEntry> = Vec::new();
+    let mut index: HashMap<PathBuf, usize> = HashMap::new();
+
+    for event in events.iter().filter(|e| e.event_type == FILE_DIFF_EVENT) {
+        let Ok(diff) = serde_json::from_value::<FileDiff>(event.event.clone()) else {
+            continue;
+        };
+        let i = *index.entry(diff.file_path.clone()).or_insert_with(|| {
+            entries.push(AuditEntry {
+                file_path: diff.file_path.clone(),
+                panes: Vec::new(),
+                changes: 0,
+                first_change: event.timestamp,
+                last_change: event.timestamp,
+                added: 0,
+                removed: 0,
+                diff: String::new(),
+            });
+            entries.len() - 1
+        });
+
+        let entry = &mut entries[i];
+        if !entry.panes.contains(&event.pane_id) {
+            entry.panes.push(event.pane_id.clone());
+        }
+        entry.changes += 1;
+        if event.timestamp >= entry.last_change {
+            entry.last_change = event.timestamp;
+            (entry.added, entry.removed) = line_counts(&diff.diff);
+            entry.diff = diff.diff;
+        }
+    }
+    entries
+}
+
+/// Lines added and removed by a unified diff
+fn line_counts(diff: &str) -> (usize, usize) {
+    let mut counts = (0, 0);
+    for line in diff.lines() {
+        if line.starts_with("+++") || line.starts_with("---") {
+            continue;
+        }
+        if line.starts_with('+') {
+            counts.0 += 1;
+        } else if line.starts_with('-') {
+            counts.1 += 1;
+        }
+    }
+    counts
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn git(dir: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .args(["-c", "user.name=axel", "-c", "user.email=axel@example.com"])
+            .args(["-c", "commit.gpgsign=false"])
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .unwrap()
+            .status;
+        assert!(status.success(), "git {:?} failed", args);
+    }
+
+    #[test]
+    fn test_capture_diff() {
+        let dir = std::env::temp_dir().join("axel-test-audit-diff");
+        std::fs::remove_dir_all(&dir).ok();
+        std::fs::create_dir_all(&dir).unwrap();
+        git(&dir, &["init", "-q"]);
+        std::fs::write(dir.join("lib.rs"), "fn a() {}\n").unwrap();
+        git(&dir, &["add", "lib.rs"]);
+        git(&dir, &["commit", "-qm", "init"]);
+
+        let post_tool_use = |tool: &str, file: &str| {
+            json!({
+                "hook_event_name": "PostToolUse",
+                "cwd": dir,
+                "tool_name": tool,
+                "tool_input": {"file_path": file},
+            })
+        };
+
+        // Tracked files are diffed against HEAD
+        std::fs::write(dir.join("lib.rs"), "fn b() {}\n").unwrap();
+        let diff = capture_diff(&post_tool_use("Edit", "lib.rs")).unwrap();
+        assert_eq!(diff.file_path, dir.join("lib.rs"));
+        assert!(diff.diff.contains("-fn a() {}\n+fn b() {}\n"));
+        assert_eq!(line_counts(&diff.diff), (1, 1));
+
+        // Untracked files are wholly added
+        std::fs::write(dir.join("new.rs"), "one\ntwo\n").unwrap();
+        let path = dir.join("new.rs");
+        let diff = capture_diff(&post_tool_use("Write", path.to_str().unwrap())).unwrap();
+        assert_eq!(line_counts(&diff.diff), (2, 0));
+
+        assert_eq!(capture_diff(&post_tool_use("Read", "lib.rs")), None);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_audit_keeps_last_diff() {
+        let diff = |pane: &str, file: &str, diff: &str| {
+            TimestampedEvent::new(
+                FILE_DIFF_EVENT,
+                pane,
+                json!({"tool_name": "Edit", "file_path": file, "diff": diff}),
+            )
+        };
+        let events = vec![
+            diff("coder", "/app/a.rs", "+one\n"),
+            diff("coder", "/app/b.rs", "-old\n"),
+            TimestampedEvent::new("PostToolUse", "coder", json!({})),
+            diff("reviewer", "/app/a.rs", "+one\n+two\n-three\n"),
+        ];
+
+        let entries = audit(&events);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].file_path, PathBuf::from("/app/a.rs"));
+        assert_eq!(entries[0].panes, vec!["coder", "reviewer"]);
+        assert_eq!(entries[0].changes, 2);
+        assert_eq!((entries[0].added, entries[0].removed), (2, 1));
+        assert_eq!(entries[0].diff, "+one\n+two\n-three\n");
+        assert_eq!((entries[1].added, entries[1].removed), (0, 1));
+    }
+}
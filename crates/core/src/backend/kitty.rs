@@ -0,0 +1,416 @@
+//! kitty backend.
+//!
+//! Drives kitty through its remote control protocol (`kitty @`). A session is
+//! a kitty OS window whose windows all carry the `axel_session` user
+//! variable; the first pane is launched as a new OS window and the rest of
+//! the grid is built with `launch --location vsplit/hsplit` in the `splits`
+//! layout, using the same column-then-row algorithm and percentages as tmux.
+//! Each further `window:` in the grid becomes a tab of that OS window.
+//!
+//! Remote control has to be enabled (`allow_remote_control yes` in
+//! kitty.conf); outside kitty, `listen_on` must be set too so `kitty @` can
+//! find the instance through `KITTY_LISTEN_ON`.
+//!
+//! Commands run as the window's own process (with `startup_command` chained
+//! in front). Wrapper scripts, notes and pane colors are tmux-only.
+
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    io::Write,
+    process::{Command, Stdio},
+};
+
+use anyhow::{Context, Result, bail};
+use serde::Deserialize;
+
+use super::{Backend, Split, recorded_session, split_grid};
+use crate::{
+    config::{ResolvedPane, WorkspaceConfig, expand_path, group_by_window},
+    timing::Timings,
+    tmux::{OtelConfig, SessionInfo, build_pane_command, direct_command, install_workspace_skills},
+};
+
+/// User variable tagging every kitty window of a session with its name
+const SESSION_VAR: &str = "axel_session";
+
+/// kitty layout that supports splitting a window in either direction
+const SPLITS_LAYOUT: &str = "splits";
+
+/// kitty backend
+pub struct KittyBackend;
+
+/// An OS window as reported by `kitty @ ls`
+#[derive(Debug, Deserialize)]
+struct KittyOsWindow {
+    tabs: Vec<KittyTab>,
+}
+
+/// A tab of an OS window
+#[derive(Debug, Deserialize)]
+struct KittyTab {
+    id: u64,
+    windows: Vec<KittyWindow>,
+}
+
+/// A kitty window (a pane, in tmux terms)
+#[derive(Debug, Deserialize)]
+struct KittyWindow {
+    id: u64,
+    #[serde(default)]
+    user_vars: HashMap<String, String>,
+}
+
+/// Run `kitty @` and return its trimmed stdout
+fn kitty_cli(args: &[&str]) -> Result<String> {
+    kitty_cli_with_input(args, None)
+}
+
+/// Run `kitty @`, writing `input` to its stdin, and return its trimmed stdout
+fn kitty_cli_with_input(args: &[&str], input: Option<&str>) -> Result<String> {
+    let mut child = Command::new("kitty")
+        .arg("@")
+        .args(args)
+        .stdin(if input.is_some() {
+            Stdio::piped()
+        } else {
+            Stdio::null()
+        })
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .context("Failed to execute kitty command")?;
+    if let (Some(input), Some(mut stdin)) = (input, child.stdin.take()) {
+        stdin.write_all(input.as_bytes())?;
+    }
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        bail!(
+            "kitty command failed: {:?}: {}",
+            args,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Match expression for the windows of a session
+fn session_match(name: &str) -> String {
+    format!("var:{}={}", SESSION_VAR, name)
+}
+
+/// Window IDs belonging to a session
+fn session_windows(name: &str) -> Vec<String> {
+    list_windows()
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|w| w.user_vars.get(SESSION_VAR).map(String::as_str) == Some(name))
+        .map(|w| w.id.to_string())
+        .collect()
+}
+
+/// Every tab of the running kitty instance
+fn list_tabs() -> Result<Vec<KittyTab>> {
+    let json = kitty_cli(&["ls"])?;
+    let os_windows: Vec<KittyOsWindow> = serde_json::from_str(&json)?;
+    Ok(os_windows
+        .into_iter()
+        .flat_map(|os_window| os_window.tabs)
+        .collect())
+}
+
+/// Every window of the running kitty instance
+fn list_windows() -> Result<Vec<KittyWindow>> {
+    Ok(list_tabs()?
+        .into_iter()
+        .flat_map(|tab| tab.windows)
+        .collect())
+}
+
+/// Build the `--title`, `--var`, `--cwd` and trailing command arguments of
+/// `kitty @ launch` for a pane
+fn launch_args(session_name: &str, pane: &ResolvedPane, command: Option<&str>) -> Vec<String> {
+    let mut args = vec![
+        "--title".to_string(),
+        pane.name.clone(),
+        "--var".to_string(),
+        format!("{}={}", SESSION_VAR, session_name),
+    ];
+    if let Some(path) = pane.path() {
+        args.push("--cwd".to_string());
+        args.push(expand_path(path));
+    }
+    if let Some(command) = command {
+        args.extend(["sh", "-c"].map(String::from));
+        args.push(command.to_string());
+    }
+    args
+}
+
+/// Split a tab into its grid, starting from its first window `first_id`.
+///
+/// `panes` are sorted by (col, row); the first one already runs in `first_id`.
+fn build_grid(
+    session_name: &str,
+    first_id: &str,
+    panes: &[&ResolvedPane],
+    command_for: &dyn Fn(&ResolvedPane) -> Option<String>,
+    timings: &mut Timings,
+) -> Result<()> {
+    kitty_cli(&[
+        "goto-layout",
+        "--match",
+        &format!("window_id:{}", first_id),
+        SPLITS_LAYOUT,
+    ])?;
+
+    split_grid(first_id, panes, |target, split, pane| {
+        let window_id = KittyBackend.split_pane(
+            session_name,
+            target,
+            split,
+            pane,
+            command_for(pane).as_deref(),
+        )?;
+        timings.lap(&format!("send {}", pane.name));
+        Ok(window_id)
+    })
+}
+
+impl Backend for KittyBackend {
+    fn name(&self) -> &'static str {
+        "kitty"
+    }
+
+    fn is_available(&self) -> bool {
+        Command::new("kitty")
+            .arg("--version")
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .map(|s| s.success())
+            .unwrap_or(false)
+    }
+
+    fn has_session(&self, name: &str) -> bool {
+        !session_windows(name).is_empty()
+    }
+
+    fn list_sessions(&self) -> Result<Vec<SessionInfo>> {
+        // Tabs and windows of each session
+        let mut sessions: BTreeMap<String, (HashSet<u64>, u32)> = BTreeMap::new();
+        for tab in list_tabs()? {
+            for mut window in tab.windows {
+                if let Some(name) = window.user_vars.remove(SESSION_VAR) {
+                    let (tabs, windows) = sessions.entry(name).or_default();
+                    tabs.insert(tab.id);
+                    *windows += 1;
+                }
+            }
+        }
+        Ok(sessions
+            .into_iter()
+            .map(|(name, (tabs, windows))| recorded_session(name, tabs.len() as u32, windows))
+            .collect())
+    }
+
+    fn create_workspace(
+        &self,
+        session_name: &str,
+        config: &WorkspaceConfig,
+        grid: Option<&str>,
+        otel_config: Option<OtelConfig>,
+        timings: &mut Timings,
+    ) -> Result<()> {
+        if !self.is_available() {
+            bail!("kitty is not installed (https://sw.kovidgoyal.net/kitty)");
+        }
+
+        let mut panes = config.resolve_panes(grid);
+        if panes.is_empty() {
+            bail!("No panes defined");
+        }
+        let workspace_dir = config.workspace_dir();
+        let index = config.load_index()?;
+        timings.lap("index load");
+
+        if let Some(ref workspace_dir) = workspace_dir {
+            install_workspace_skills(config, &panes, workspace_dir, timings);
+        }
+
+        panes.sort_by(|a, b| a.col.cmp(&b.col).then(a.row.cmp(&b.row)));
+        let windows = group_by_window(&panes);
+
+        let command_for = |pane: &ResolvedPane| {
+            let command = build_pane_command(
+                pane,
+                workspace_dir.as_deref(),
+                index.as_ref(),
+                otel_config.as_ref(),
+            );
+            direct_command(pane, command)
+        };
+
+        // The first window's first pane opens a new OS window
+        let (first_name, first_panes) = &windows[0];
+        let first = first_panes[0];
+        let mut args: Vec<String> = vec![
+            "launch".into(),
+            "--type".into(),
+            "os-window".into(),
+            "--os-window-title".into(),
+            session_name.into(),
+            "--tab-title".into(),
+            first_name.unwrap_or(&config.workspace).into(),
+        ];
+        args.extend(launch_args(
+            session_name,
+            first,
+            command_for(first).as_deref(),
+        ));
+        let first_id = kitty_cli(&args.iter().map(String::as_str).collect::<Vec<_>>())?;
+        timings.lap("session create");
+        timings.lap(&format!("send {}", first.name));
+        build_grid(session_name, &first_id, first_panes, &command_for, timings)?;
+
+        // Further windows become tabs of the same OS window
+        for (name, window_panes) in &windows[1..] {
+            let pane = window_panes[0];
+            let mut args: Vec<String> = vec![
+                "launch".into(),
+                "--type".into(),
+                "tab".into(),
+                "--match".into(),
+                format!("window_id:{}", first_id),
+                "--tab-title".into(),
+                name.unwrap_or(&config.workspace).into(),
+            ];
+            args.extend(launch_args(
+                session_name,
+                pane,
+                command_for(pane).as_deref(),
+            ));
+            let tab_id = kitty_cli(&args.iter().map(String::as_str).collect::<Vec<_>>())?;
+            timings.lap(&format!("send {}", pane.name));
+            build_grid(session_name, &tab_id, window_panes, &command_for, timings)?;
+        }
+        kitty_cli(&["focus-window", "--match", &format!("id:{}", first_id)])?;
+
+        Ok(())
+    }
+
+    fn split_pane(
+        &self,
+        session_name: &str,
+        target: &str,
+        split: Split,
+        pane: &ResolvedPane,
+        command: Option<&str>,
+    ) -> Result<String> {
+        let (location, bias) = match split {
+            Split::Right(width) => ("vsplit", width),
+            Split::Down(height) => ("hsplit", Some(height)),
+        };
+        let mut args: Vec<String> = vec![
+            "launch".into(),
+            "--type".into(),
+            "window".into(),
+            "--next-to".into(),
+            format!("id:{}", target),
+            "--location".into(),
+            location.into(),
+        ];
+        if let Some(bias) = bias {
+            args.push("--bias".into());
+            args.push(bias.to_string());
+        }
+        args.extend(launch_args(session_name, pane, command));
+        kitty_cli(&args.iter().map(String::as_str).collect::<Vec<_>>())
+    }
+
+    fn send_text(&self, target: &str, text: &str) -> Result<()> {
+        // Through stdin, so kitty doesn't interpret escapes in the text
+        kitty_cli_with_input(
+            &["send-text", "--match", &format!("id:{}", target), "--stdin"],
+            Some(&format!("{}\r", text)),
+        )
+        .map(|_| ())
+    }
+
+    fn kill_session(&self, name: &str) -> Result<()> {
+        kitty_cli(&["close-window", "--match", &session_match(name)]).map(|_| ())
+    }
+
+    fn attach_command(&self, name: &str) -> Vec<String> {
+        // The session already has an OS window; bring it forward
+        [
+            "kitty",
+            "@",
+            "focus-window",
+            "--match",
+            &session_match(name),
+        ]
+        .into_iter()
+        .map(String::from)
+        .collect()
+    }
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{CustomPaneConfig, PaneConfig};
+
+    #[test]
+    fn test_launch_args() {
+        let pane = ResolvedPane {
+            name: "logs".to_string(),
+            col: 1,
+            row: 0,
+            width: None,
+            height: None,
+            colspan: 1,
+            rowspan: 1,
+            window: None,
+            config: PaneConfig::Custom(CustomPaneConfig {
+                name: "logs".to_string(),
+                path: Some("/code/app".to_string()),
+                ..Default::default()
+            }),
+        };
+        assert_eq!(
+            launch_args("app", &pane, Some("tail -f log")),
+            vec![
+                "--title",
+                "logs",
+                "--var",
+                "axel_session=app",
+                "--cwd",
+                "/code/app",
+                "sh",
+                "-c",
+                "tail -f log"
+            ]
+        );
+        assert_eq!(launch_args("app", &pane, None).len(), 6);
+    }
+
+    #[test]
+    fn test_list_windows_json() {
+        let json = r#"[{"id": 1, "tabs": [{"id": 1, "windows": [
+            {"id": 3, "title": "claude", "user_vars": {"axel_session": "app"}},
+            {"id": 4, "title": "zsh"}
+        ]}]}]"#;
+        let os_windows: Vec<KittyOsWindow> = serde_json::from_str(json).unwrap();
+        let windows: Vec<_> = os_windows
+            .into_iter()
+            .flat_map(|w| w.tabs)
+            .flat_map(|t| t.windows)
+            .map(|w| (w.id, w.user_vars.get(SESSION_VAR).cloned()))
+            .collect();
+        assert_eq!(windows, [(3, Some("app".to_string())), (4, None)]);
+    }
+}
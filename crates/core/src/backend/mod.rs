@@ -0,0 +1,292 @@
+//! Terminal multiplexer backends.
+//!
+//! A [`Backend`] creates, splits, types into, lists and tears down workspace
+//! sessions for one terminal multiplexer. tmux is the default; Zellij,
+//! WezTerm, kitty and Windows Terminal are selected per grid with
+//! `type: zellij` / `type: wezterm` / `type: kitty` /
+//! `type: windows_terminal`, or for the whole manifest with
+//! `backend: zellij` / `backend: wezterm` / `backend: kitty` /
+//! `backend: windows_terminal`.
+//!
+//! # Submodules
+//!
+//! - `tmux`: adapts [`crate::tmux`], which also handles control mode,
+//!   orchestrators and session reloads
+//! - `zellij`: renders the grid as a KDL layout and drives the `zellij` CLI
+//! - `wezterm`: builds the grid with `wezterm cli spawn`/`split-pane`
+//! - `kitty`: builds the grid with `kitty @ launch` in the splits layout
+//! - `windows_terminal`: opens each pane as a tab with `wt new-tab`
+//! - `sessions`: records the workspace of each session
+
+mod kitty;
+mod sessions;
+mod tmux;
+mod wezterm;
+mod windows_terminal;
+mod zellij;
+
+use std::fmt;
+
+use anyhow::Result;
+pub use kitty::KittyBackend;
+use serde::Deserialize;
+pub use sessions::{
+    BackendSessions, forget_session, record_session, recorded_session, sessions_path,
+};
+pub use tmux::TmuxBackend;
+pub use wezterm::WezTermBackend;
+pub use windows_terminal::WindowsTerminalBackend;
+pub use zellij::{ZellijBackend, zellij_layout};
+
+use crate::{
+    config::{ResolvedPane, WorkspaceConfig},
+    timing::Timings,
+    tmux::{OtelConfig, SessionInfo, column_widths, row_height},
+};
+
+/// Operations axel needs from a terminal multiplexer
+pub trait Backend {
+    /// Backend name for display/config
+    fn name(&self) -> &'static str;
+
+    /// Whether the multiplexer's CLI is installed
+    fn is_available(&self) -> bool;
+
+    /// Check if a session exists
+    fn has_session(&self, name: &str) -> bool;
+
+    /// All running sessions
+    fn list_sessions(&self) -> Result<Vec<SessionInfo>>;
+
+    /// Create a detached session laid out from a grid (defaults to "default"),
+    /// installing skills for the panes it contains.
+    ///
+    /// Each phase is charged to `timings`.
+    fn create_workspace(
+        &self,
+        session_name: &str,
+        config: &WorkspaceConfig,
+        grid: Option<&str>,
+        otel_config: Option<OtelConfig>,
+        timings: &mut Timings,
+    ) -> Result<()>;
+
+    /// Split pane `target` of session `session_name`, opening `pane` in the
+    /// new pane with `command` as its process, and return the new pane's ID
+    /// (a target for [`send_text`](Backend::send_text) and further splits).
+    fn split_pane(
+        &self,
+        session_name: &str,
+        target: &str,
+        split: Split,
+        pane: &ResolvedPane,
+        command: Option<&str>,
+    ) -> Result<String>;
+
+    /// Type `text` followed by Enter into `target` (a pane for tmux and
+    /// WezTerm, a window ID for kitty, the focused pane of a session for
+    /// Zellij)
+    fn send_text(&self, target: &str, text: &str) -> Result<()>;
+
+    /// Kill a session
+    fn kill_session(&self, name: &str) -> Result<()>;
+
+    /// Command that attaches the current terminal to a session
+    fn attach_command(&self, name: &str) -> Vec<String>;
+}
+
+/// Which backend runs a workspace
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BackendKind {
+    /// tmux (default)
+    #[default]
+    Tmux,
+    /// Zellij
+    Zellij,
+    /// WezTerm's built-in multiplexer
+    WezTerm,
+    /// kitty, through remote control
+    Kitty,
+    /// Windows Terminal tabs, opened with `wt`
+    #[serde(rename = "windows_terminal")]
+    WindowsTerminal,
+}
+
+impl BackendKind {
+    /// Every backend, tmux first
+    pub const ALL: [BackendKind; 5] = [
+        BackendKind::Tmux,
+        BackendKind::Zellij,
+        BackendKind::WezTerm,
+        BackendKind::Kitty,
+        BackendKind::WindowsTerminal,
+    ];
+
+    /// Name used in manifests
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BackendKind::Tmux => "tmux",
+            BackendKind::Zellij => "zellij",
+            BackendKind::WezTerm => "wezterm",
+            BackendKind::Kitty => "kitty",
+            BackendKind::WindowsTerminal => "windows_terminal",
+        }
+    }
+}
+
+impl fmt::Display for BackendKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Get the backend implementation for a kind
+pub fn get_backend(kind: BackendKind) -> Box<dyn Backend> {
+    match kind {
+        BackendKind::Tmux => Box::new(TmuxBackend),
+        BackendKind::Zellij => Box::new(ZellijBackend),
+        BackendKind::WezTerm => Box::new(WezTermBackend),
+        BackendKind::Kitty => Box::new(KittyBackend),
+        BackendKind::WindowsTerminal => Box::new(WindowsTerminalBackend),
+    }
+}
+
+/// The installed backends
+pub fn available_backends() -> Vec<Box<dyn Backend>> {
+    BackendKind::ALL
+        .into_iter()
+        .map(get_backend)
+        .filter(|backend| backend.is_available())
+        .collect()
+}
+
+/// The installed backend running session `name`, if any
+pub fn find_session_backend(name: &str) -> Option<Box<dyn Backend>> {
+    available_backends()
+        .into_iter()
+        .find(|backend| backend.has_session(name))
+}
+
+/// Running sessions of every installed backend
+pub fn list_all_sessions() -> Vec<SessionInfo> {
+    available_backends()
+        .iter()
+        .flat_map(|backend| backend.list_sessions().unwrap_or_default())
+        .collect()
+}
+
+/// Where a new pane is split off its target, with its size as a percentage
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Split {
+    /// New column to the right, with the grid's width if it sets one
+    Right(Option<u32>),
+    /// New row below
+    Down(u32),
+}
+
+/// Split a tab into its grid, starting from its first pane `first_id`.
+///
+/// `panes` are sorted by (col, row); the first one already runs in `first_id`.
+/// Each column is split off the top pane of the column to its left, then each
+/// column's rows off its last pane. `split(target, split, pane)` creates one
+/// pane and returns its ID.
+pub(crate) fn split_grid<'a>(
+    first_id: &str,
+    panes: &[&'a ResolvedPane],
+    mut split: impl FnMut(&str, Split, &'a ResolvedPane) -> Result<String>,
+) -> Result<()> {
+    let col_widths = column_widths(panes, None);
+    let mut columns: Vec<Vec<&ResolvedPane>> = Vec::new();
+    for &pane in panes {
+        match columns.last_mut() {
+            Some(column) if column[0].col == pane.col => column.push(pane),
+            _ => columns.push(vec![pane]),
+        }
+    }
+
+    let mut col_top_ids: Vec<String> = vec![first_id.to_string()];
+    for column in columns.iter().skip(1) {
+        let pane = column[0];
+        let left = col_top_ids.last().cloned().unwrap_or_default();
+        col_top_ids.push(split(
+            &left,
+            Split::Right(col_widths.get(&pane.col).copied()),
+            pane,
+        )?);
+    }
+
+    for (column, top_id) in columns.iter().zip(&col_top_ids) {
+        let mut last_id = top_id.clone();
+        for (row_idx, &pane) in column.iter().enumerate().skip(1) {
+            let height = row_height(pane, row_idx, column.len(), None);
+            last_id = split(&last_id, Split::Down(height), pane)?;
+        }
+    }
+
+    Ok(())
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{CustomPaneConfig, PaneConfig};
+
+    fn pane(name: &str, col: u32, row: u32, width: Option<u32>) -> ResolvedPane {
+        ResolvedPane {
+            name: name.to_string(),
+            col,
+            row,
+            width,
+            height: None,
+            colspan: 1,
+            rowspan: 1,
+            window: None,
+            config: PaneConfig::Custom(CustomPaneConfig {
+                name: name.to_string(),
+                ..Default::default()
+            }),
+        }
+    }
+
+    #[test]
+    fn test_split_grid() {
+        let panes = [
+            pane("claude", 0, 0, Some(60)),
+            pane("codex", 0, 1, None),
+            pane("shell", 1, 0, Some(40)),
+            pane("logs", 1, 1, None),
+            pane("tests", 1, 2, None),
+        ];
+        let panes: Vec<&ResolvedPane> = panes.iter().collect();
+
+        let mut steps = Vec::new();
+        split_grid("0", &panes, |target, split, pane| {
+            steps.push((target.to_string(), split, pane.name.clone()));
+            Ok(steps.len().to_string())
+        })
+        .unwrap();
+
+        let step = |target: &str, split, name: &str| (target.to_string(), split, name.to_string());
+        assert_eq!(
+            steps,
+            [
+                step("0", Split::Right(Some(40)), "shell"),
+                step("0", Split::Down(50), "codex"),
+                step("1", Split::Down(66), "logs"),
+                step("3", Split::Down(50), "tests"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_get_backend() {
+        for kind in BackendKind::ALL {
+            assert_eq!(get_backend(kind).name(), kind.as_str());
+        }
+    }
+}
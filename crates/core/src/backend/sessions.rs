@@ -0,0 +1,134 @@
+//! Workspaces of running sessions.
+//!
+//! tmux sessions also carry their manifest path in the session environment;
+//! Zellij, WezTerm, kitty and Windows Terminal sessions have nowhere to keep
+//! it. Each session's workspace directory is recorded at launch in
+//! `~/.local/share/axel/sessions.json`, so `axel ls` shows it and `axel kill`
+//! cleans up the skills of the right workspace, whatever directory it runs
+//! from.
+
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::tmux::SessionInfo;
+
+/// Session file, relative to the home directory
+pub const SESSIONS_FILE: &str = ".local/share/axel/sessions.json";
+
+/// Session file (`~/.local/share/axel/sessions.json`)
+pub fn sessions_path() -> Option<PathBuf> {
+    dirs::home_dir().map(|home| home.join(SESSIONS_FILE))
+}
+
+/// Workspace directory of each backend session, by session name
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct BackendSessions {
+    #[serde(default)]
+    pub sessions: BTreeMap<String, PathBuf>,
+}
+
+impl BackendSessions {
+    /// Read the sessions at `path`; a missing file is empty
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = match std::fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => return Err(e).with_context(|| format!("Failed to read {}", path.display())),
+        };
+        serde_json::from_str(&content)
+            .with_context(|| format!("Invalid session file {}", path.display()))
+    }
+
+    /// Write the sessions to `path`, creating its directory
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+/// Remember that session `name` runs the workspace in `workspace_dir`
+pub fn record_session(name: &str, workspace_dir: &Path) -> Result<()> {
+    let Some(path) = sessions_path() else {
+        return Ok(());
+    };
+    let mut sessions = BackendSessions::load(&path)?;
+    sessions
+        .sessions
+        .insert(name.to_string(), workspace_dir.to_path_buf());
+    sessions.save(&path)
+}
+
+/// Forget session `name`, returning the workspace directory it ran, if known
+pub fn forget_session(name: &str) -> Result<Option<PathBuf>> {
+    let Some(path) = sessions_path() else {
+        return Ok(None);
+    };
+    let mut sessions = BackendSessions::load(&path)?;
+    let workspace_dir = sessions.sessions.remove(name);
+    if workspace_dir.is_some() {
+        sessions.save(&path)?;
+    }
+    Ok(workspace_dir)
+}
+
+/// Session `name` of a backend without a session environment, with the
+/// workspace recorded for it; `windows` and `panes` are 0 when unknown
+pub fn recorded_session(name: String, windows: u32, panes: u32) -> SessionInfo {
+    let working_dir = sessions_path()
+        .and_then(|path| BackendSessions::load(&path).ok())
+        .and_then(|mut sessions| sessions.sessions.remove(&name));
+    let git = working_dir
+        .as_deref()
+        .and_then(|dir| crate::git::status(dir).ok());
+    SessionInfo {
+        name,
+        windows,
+        panes,
+        created: 0,
+        attached: false,
+        working_dir: working_dir.map(|dir| dir.to_string_lossy().into_owned()),
+        port: None,
+        axel_pane_id: None,
+        git,
+    }
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backend_sessions_roundtrip() {
+        let dir = std::env::temp_dir().join("axel-test-backend-sessions");
+        std::fs::remove_dir_all(&dir).ok();
+        let path = dir.join("sessions.json");
+
+        assert_eq!(
+            BackendSessions::load(&path).unwrap(),
+            BackendSessions::default()
+        );
+
+        let sessions = BackendSessions {
+            sessions: BTreeMap::from([("api".to_string(), PathBuf::from("/code/api"))]),
+        };
+        sessions.save(&path).unwrap();
+        assert_eq!(BackendSessions::load(&path).unwrap(), sessions);
+
+        std::fs::write(&path, "not json").unwrap();
+        assert!(BackendSessions::load(&path).is_err());
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
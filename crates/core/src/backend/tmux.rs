@@ -0,0 +1,99 @@
+//! tmux backend.
+//!
+//! Adapter over [`crate::tmux`], which holds the tmux layout algorithm,
+//! session styling, control mode and session reloads. Sessions carry their
+//! manifest path in the session environment, so they're listed with their
+//! workspace, port and axel pane ID.
+
+use std::process::{Command, Stdio};
+
+use anyhow::Result;
+
+use super::{Backend, Split};
+use crate::{
+    config::{ResolvedPane, WorkspaceConfig, expand_path},
+    timing::Timings,
+    tmux::{self, OtelConfig, SessionInfo, SplitWindow},
+};
+
+/// tmux backend
+pub struct TmuxBackend;
+
+impl Backend for TmuxBackend {
+    fn name(&self) -> &'static str {
+        "tmux"
+    }
+
+    fn is_available(&self) -> bool {
+        Command::new("tmux")
+            .arg("-V")
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .map(|s| s.success())
+            .unwrap_or(false)
+    }
+
+    fn has_session(&self, name: &str) -> bool {
+        tmux::has_session(name)
+    }
+
+    fn list_sessions(&self) -> Result<Vec<SessionInfo>> {
+        tmux::list_sessions(false)
+    }
+
+    fn create_workspace(
+        &self,
+        session_name: &str,
+        config: &WorkspaceConfig,
+        grid: Option<&str>,
+        otel_config: Option<OtelConfig>,
+        timings: &mut Timings,
+    ) -> Result<()> {
+        tmux::create_workspace(session_name, config, grid, otel_config, timings)
+    }
+
+    fn split_pane(
+        &self,
+        _session_name: &str,
+        target: &str,
+        split: Split,
+        pane: &ResolvedPane,
+        command: Option<&str>,
+    ) -> Result<String> {
+        let path = pane
+            .path()
+            .map(expand_path)
+            .unwrap_or_else(|| ".".to_string());
+        let mut window = SplitWindow::new().target(target).start_directory(&path);
+        window = match split {
+            Split::Right(width) => {
+                window = window.horizontal();
+                match width {
+                    Some(width) => window.percentage(width),
+                    None => window,
+                }
+            }
+            Split::Down(height) => window.vertical().percentage(height),
+        };
+        if let Some(command) = command {
+            window = window.command(command);
+        }
+        window.run()
+    }
+
+    fn send_text(&self, target: &str, text: &str) -> Result<()> {
+        tmux::send_text(target, text)
+    }
+
+    fn kill_session(&self, name: &str) -> Result<()> {
+        tmux::detach_session(name)?;
+        tmux::kill_session(name)
+    }
+
+    fn attach_command(&self, name: &str) -> Vec<String> {
+        let mut command = tmux::tmux_program();
+        command.extend(["attach-session", "-t", name].map(String::from));
+        command
+    }
+}
@@ -0,0 +1,300 @@
+//! WezTerm backend.
+//!
+//! Drives WezTerm's built-in multiplexer through `wezterm cli`. A session is
+//! a WezTerm workspace: the first pane is spawned in a new window tagged with
+//! the workspace name, and the rest of the grid is built with `split-pane`
+//! using the same column-then-row algorithm and percentages as tmux. Each
+//! further `window:` in the grid becomes a tab of that window.
+//!
+//! Commands run as the pane's own process (with `startup_command` chained in
+//! front). Wrapper scripts, notes and pane colors are tmux-only.
+
+use std::{
+    collections::{BTreeMap, HashSet},
+    process::{Command, Stdio},
+};
+
+use anyhow::{Context, Result, bail};
+use serde::Deserialize;
+
+use super::{Backend, Split, recorded_session, split_grid};
+use crate::{
+    config::{ResolvedPane, WorkspaceConfig, expand_path, group_by_window},
+    timing::Timings,
+    tmux::{OtelConfig, SessionInfo, build_pane_command, direct_command, install_workspace_skills},
+};
+
+/// WezTerm backend
+pub struct WezTermBackend;
+
+/// A pane as reported by `wezterm cli list --format json`
+#[derive(Debug, Deserialize)]
+struct WezTermPane {
+    pane_id: u64,
+    window_id: u64,
+    tab_id: u64,
+    workspace: String,
+}
+
+/// Run `wezterm cli` and return its trimmed stdout
+fn wezterm_cli(args: &[&str]) -> Result<String> {
+    let output = Command::new("wezterm")
+        .arg("cli")
+        .args(args)
+        .output()
+        .context("Failed to execute wezterm command")?;
+    if !output.status.success() {
+        bail!(
+            "wezterm command failed: {:?}: {}",
+            args,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// All panes in the running WezTerm mux
+fn list_panes() -> Result<Vec<WezTermPane>> {
+    let json = wezterm_cli(&["list", "--format", "json"])?;
+    Ok(serde_json::from_str(&json)?)
+}
+
+/// Pane IDs belonging to a workspace
+fn workspace_panes(name: &str) -> Vec<String> {
+    list_panes()
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|p| p.workspace == name)
+        .map(|p| p.pane_id.to_string())
+        .collect()
+}
+
+/// Build the `--cwd` and trailing `-- sh -c <command>` arguments for a pane
+fn pane_args(pane: &ResolvedPane, command: Option<&str>) -> Vec<String> {
+    let mut args = Vec::new();
+    if let Some(path) = pane.path() {
+        args.push("--cwd".to_string());
+        args.push(expand_path(path));
+    }
+    if let Some(command) = command {
+        args.extend(["--", "sh", "-c"].map(String::from));
+        args.push(command.to_string());
+    }
+    args
+}
+
+/// Split a tab into its grid, starting from its first pane `first_id`.
+///
+/// `panes` are sorted by (col, row); the first one already runs in `first_id`.
+fn build_grid(
+    session_name: &str,
+    first_id: &str,
+    panes: &[&ResolvedPane],
+    command_for: &dyn Fn(&ResolvedPane) -> Option<String>,
+    timings: &mut Timings,
+) -> Result<()> {
+    split_grid(first_id, panes, |target, split, pane| {
+        let pane_id = WezTermBackend.split_pane(
+            session_name,
+            target,
+            split,
+            pane,
+            command_for(pane).as_deref(),
+        )?;
+        timings.lap(&format!("send {}", pane.name));
+        Ok(pane_id)
+    })
+}
+
+impl Backend for WezTermBackend {
+    fn name(&self) -> &'static str {
+        "wezterm"
+    }
+
+    fn is_available(&self) -> bool {
+        Command::new("wezterm")
+            .arg("--version")
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .map(|s| s.success())
+            .unwrap_or(false)
+    }
+
+    fn has_session(&self, name: &str) -> bool {
+        !workspace_panes(name).is_empty()
+    }
+
+    fn list_sessions(&self) -> Result<Vec<SessionInfo>> {
+        // Tabs and panes of each workspace
+        let mut workspaces: BTreeMap<String, (HashSet<u64>, u32)> = BTreeMap::new();
+        for pane in list_panes()? {
+            let (tabs, panes) = workspaces.entry(pane.workspace).or_default();
+            tabs.insert(pane.tab_id);
+            *panes += 1;
+        }
+        Ok(workspaces
+            .into_iter()
+            .map(|(name, (tabs, panes))| recorded_session(name, tabs.len() as u32, panes))
+            .collect())
+    }
+
+    fn create_workspace(
+        &self,
+        session_name: &str,
+        config: &WorkspaceConfig,
+        grid: Option<&str>,
+        otel_config: Option<OtelConfig>,
+        timings: &mut Timings,
+    ) -> Result<()> {
+        if !self.is_available() {
+            bail!("wezterm is not installed (https://wezterm.org)");
+        }
+
+        let mut panes = config.resolve_panes(grid);
+        if panes.is_empty() {
+            bail!("No panes defined");
+        }
+        let workspace_dir = config.workspace_dir();
+        let index = config.load_index()?;
+        timings.lap("index load");
+
+        if let Some(ref workspace_dir) = workspace_dir {
+            install_workspace_skills(config, &panes, workspace_dir, timings);
+        }
+
+        panes.sort_by(|a, b| a.col.cmp(&b.col).then(a.row.cmp(&b.row)));
+        let windows = group_by_window(&panes);
+
+        let command_for = |pane: &ResolvedPane| {
+            let command = build_pane_command(
+                pane,
+                workspace_dir.as_deref(),
+                index.as_ref(),
+                otel_config.as_ref(),
+            );
+            direct_command(pane, command)
+        };
+
+        // The first window's first pane opens a new window in the workspace
+        let (_, first_panes) = &windows[0];
+        let first = first_panes[0];
+        let mut args: Vec<String> = ["spawn", "--new-window", "--workspace", session_name]
+            .map(String::from)
+            .to_vec();
+        args.extend(pane_args(first, command_for(first).as_deref()));
+        let first_id = wezterm_cli(&args.iter().map(String::as_str).collect::<Vec<_>>())?;
+        timings.lap("session create");
+        timings.lap(&format!("send {}", first.name));
+        build_grid(session_name, &first_id, first_panes, &command_for, timings)?;
+
+        // Further windows become tabs in the same WezTerm window
+        let window_id = list_panes()?
+            .into_iter()
+            .find(|p| p.pane_id.to_string() == first_id)
+            .map(|p| p.window_id.to_string())
+            .context("Spawned WezTerm pane not found")?;
+        for (name, window_panes) in &windows[1..] {
+            let pane = window_panes[0];
+            let mut args: Vec<String> =
+                vec!["spawn".into(), "--window-id".into(), window_id.clone()];
+            args.extend(pane_args(pane, command_for(pane).as_deref()));
+            let tab_id = wezterm_cli(&args.iter().map(String::as_str).collect::<Vec<_>>())?;
+            let title = name.unwrap_or(&config.workspace);
+            wezterm_cli(&["set-tab-title", "--pane-id", &tab_id, title])?;
+            timings.lap(&format!("send {}", pane.name));
+            build_grid(session_name, &tab_id, window_panes, &command_for, timings)?;
+        }
+        wezterm_cli(&["activate-pane", "--pane-id", &first_id])?;
+
+        Ok(())
+    }
+
+    fn split_pane(
+        &self,
+        _session_name: &str,
+        target: &str,
+        split: Split,
+        pane: &ResolvedPane,
+        command: Option<&str>,
+    ) -> Result<String> {
+        let mut args: Vec<String> = vec!["split-pane".into(), "--pane-id".into(), target.into()];
+        let percent = match split {
+            Split::Right(width) => {
+                args.push("--right".into());
+                width
+            }
+            Split::Down(height) => {
+                args.push("--bottom".into());
+                Some(height)
+            }
+        };
+        if let Some(percent) = percent {
+            args.push("--percent".into());
+            args.push(percent.to_string());
+        }
+        args.extend(pane_args(pane, command));
+        wezterm_cli(&args.iter().map(String::as_str).collect::<Vec<_>>())
+    }
+
+    fn send_text(&self, target: &str, text: &str) -> Result<()> {
+        wezterm_cli(&[
+            "send-text",
+            "--pane-id",
+            target,
+            "--no-paste",
+            &format!("{}\r", text),
+        ])
+        .map(|_| ())
+    }
+
+    fn kill_session(&self, name: &str) -> Result<()> {
+        for pane_id in workspace_panes(name) {
+            wezterm_cli(&["kill-pane", "--pane-id", &pane_id])?;
+        }
+        Ok(())
+    }
+
+    fn attach_command(&self, name: &str) -> Vec<String> {
+        // The workspace already has a window; bring its first pane forward
+        let pane_id = workspace_panes(name).into_iter().next().unwrap_or_default();
+        ["wezterm", "cli", "activate-pane", "--pane-id", &pane_id]
+            .into_iter()
+            .map(String::from)
+            .collect()
+    }
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{CustomPaneConfig, PaneConfig};
+
+    #[test]
+    fn test_pane_args() {
+        let pane = ResolvedPane {
+            name: "logs".to_string(),
+            col: 1,
+            row: 0,
+            width: None,
+            height: None,
+            colspan: 1,
+            rowspan: 1,
+            window: None,
+            config: PaneConfig::Custom(CustomPaneConfig {
+                name: "logs".to_string(),
+                path: Some("/code/app".to_string()),
+                ..Default::default()
+            }),
+        };
+        assert_eq!(
+            pane_args(&pane, Some("tail -f log")),
+            vec!["--cwd", "/code/app", "--", "sh", "-c", "tail -f log"]
+        );
+        assert_eq!(pane_args(&pane, None), vec!["--cwd", "/code/app"]);
+    }
+}
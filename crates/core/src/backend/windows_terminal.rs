@@ -0,0 +1,245 @@
+//! Windows Terminal backend.
+//!
+//! Opens the grid with `wt.exe` in a Windows Terminal window named after the
+//! session (`wt -w <session>`). `wt` can't size splits from the grid's
+//! percentages, so every pane becomes a tab titled with its name, in grid
+//! order (windows, then columns, then rows).
+//!
+//! Splitting a pane later (`split_pane`) splits the focused pane of the
+//! window, since `wt` has no pane IDs to target.
+//!
+//! `wt` can't list, type into or close tabs either: axel can't tell whether
+//! a session is running, so `axel ls`, `axel send` and `axel kill` don't
+//! reach these sessions; closing the window ends it. Launching a running
+//! session again adds its tabs to the same window.
+//!
+//! Commands run with `sh -c`, which comes with Git for Windows. Panes
+//! without a command open the default profile's shell.
+
+use std::process::Command;
+
+use anyhow::{Context, Result, bail};
+
+use super::{Backend, Split};
+use crate::{
+    config::{ResolvedPane, WorkspaceConfig, expand_path, group_by_window},
+    timing::Timings,
+    tmux::{OtelConfig, SessionInfo, build_pane_command, direct_command, install_workspace_skills},
+};
+
+/// Windows Terminal backend
+pub struct WindowsTerminalBackend;
+
+/// Whether `wt` is on the `PATH`
+fn wt_installed() -> bool {
+    std::env::var_os("PATH").is_some_and(|path| {
+        std::env::split_paths(&path).any(|dir| {
+            let wt = dir.join("wt");
+            wt.is_file() || wt.with_extension("exe").is_file()
+        })
+    })
+}
+
+/// Build the `--title`, `--startingDirectory` and trailing command arguments
+/// of a `wt` subcommand for a pane.
+///
+/// `;` separates `wt` subcommands, so the ones inside commands are escaped.
+fn pane_args(pane: &ResolvedPane, command: Option<&str>) -> Vec<String> {
+    let mut args = vec!["--title".to_string(), pane.name.clone()];
+    args.push("--suppressApplicationTitle".to_string());
+    if let Some(path) = pane.path() {
+        args.push("--startingDirectory".to_string());
+        args.push(expand_path(path));
+    }
+    if let Some(command) = command {
+        args.extend(["sh", "-c"].map(String::from));
+        args.push(command.replace(';', "\\;"));
+    }
+    args
+}
+
+/// Run `wt` with `args`
+fn run_wt(args: &[String]) -> Result<()> {
+    let status = Command::new("wt")
+        .args(args)
+        .status()
+        .context("Failed to execute wt")?;
+    if !status.success() {
+        bail!("wt failed ({})", status);
+    }
+    Ok(())
+}
+
+/// Build the `wt` arguments that open one tab per pane in the window
+/// `session`.
+fn wt_args(session: &str, tabs: &[(&ResolvedPane, Option<String>)]) -> Vec<String> {
+    let mut args = vec!["-w".to_string(), session.to_string()];
+    for (i, (pane, command)) in tabs.iter().enumerate() {
+        if i > 0 {
+            args.push(";".to_string());
+        }
+        args.push("new-tab".to_string());
+        args.extend(pane_args(pane, command.as_deref()));
+    }
+    args
+}
+
+impl Backend for WindowsTerminalBackend {
+    fn name(&self) -> &'static str {
+        "windows_terminal"
+    }
+
+    fn is_available(&self) -> bool {
+        wt_installed()
+    }
+
+    fn has_session(&self, _name: &str) -> bool {
+        false
+    }
+
+    fn list_sessions(&self) -> Result<Vec<SessionInfo>> {
+        Ok(Vec::new())
+    }
+
+    fn create_workspace(
+        &self,
+        session_name: &str,
+        config: &WorkspaceConfig,
+        grid: Option<&str>,
+        otel_config: Option<OtelConfig>,
+        timings: &mut Timings,
+    ) -> Result<()> {
+        if !self.is_available() {
+            bail!("Windows Terminal (wt) is not installed (https://aka.ms/terminal)");
+        }
+
+        let mut panes = config.resolve_panes(grid);
+        if panes.is_empty() {
+            bail!("No panes defined");
+        }
+        let workspace_dir = config.workspace_dir();
+        let index = config.load_index()?;
+        timings.lap("index load");
+
+        if let Some(ref workspace_dir) = workspace_dir {
+            install_workspace_skills(config, &panes, workspace_dir, timings);
+        }
+
+        panes.sort_by(|a, b| a.col.cmp(&b.col).then(a.row.cmp(&b.row)));
+        let tabs: Vec<(&ResolvedPane, Option<String>)> = group_by_window(&panes)
+            .into_iter()
+            .flat_map(|(_, window_panes)| window_panes)
+            .map(|pane| {
+                let command = build_pane_command(
+                    pane,
+                    workspace_dir.as_deref(),
+                    index.as_ref(),
+                    otel_config.as_ref(),
+                );
+                (pane, direct_command(pane, command))
+            })
+            .collect();
+
+        run_wt(&wt_args(session_name, &tabs)).context("wt failed to open the workspace")?;
+        timings.lap("session create");
+
+        Ok(())
+    }
+
+    fn split_pane(
+        &self,
+        session_name: &str,
+        _target: &str,
+        split: Split,
+        pane: &ResolvedPane,
+        command: Option<&str>,
+    ) -> Result<String> {
+        let direction = match split {
+            Split::Right(_) => "-V",
+            Split::Down(_) => "-H",
+        };
+        let mut args = ["-w", session_name, "split-pane", direction]
+            .map(String::from)
+            .to_vec();
+        args.extend(pane_args(pane, command));
+        run_wt(&args)?;
+        Ok(session_name.to_string())
+    }
+
+    fn send_text(&self, _target: &str, _text: &str) -> Result<()> {
+        bail!("Windows Terminal can't type into tabs from the command line")
+    }
+
+    fn kill_session(&self, _name: &str) -> Result<()> {
+        bail!("Windows Terminal can't close tabs from the command line; close its window")
+    }
+
+    fn attach_command(&self, name: &str) -> Vec<String> {
+        ["wt", "-w", name, "focus-tab", "--target", "0"]
+            .into_iter()
+            .map(String::from)
+            .collect()
+    }
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{CustomPaneConfig, PaneConfig};
+
+    fn pane(name: &str, path: Option<&str>) -> ResolvedPane {
+        ResolvedPane {
+            name: name.to_string(),
+            col: 0,
+            row: 0,
+            width: None,
+            height: None,
+            colspan: 1,
+            rowspan: 1,
+            window: None,
+            config: PaneConfig::Custom(CustomPaneConfig {
+                name: name.to_string(),
+                path: path.map(String::from),
+                ..Default::default()
+            }),
+        }
+    }
+
+    #[test]
+    fn test_wt_args() {
+        let logs = pane("logs", Some("/code/app"));
+        let shell = pane("shell", None);
+        let args = wt_args(
+            "app",
+            &[
+                (&logs, Some("cd web; tail -f log".to_string())),
+                (&shell, None),
+            ],
+        );
+        assert_eq!(
+            args,
+            vec![
+                "-w",
+                "app",
+                "new-tab",
+                "--title",
+                "logs",
+                "--suppressApplicationTitle",
+                "--startingDirectory",
+                "/code/app",
+                "sh",
+                "-c",
+                "cd web\\; tail -f log",
+                ";",
+                "new-tab",
+                "--title",
+                "shell",
+                "--suppressApplicationTitle",
+            ]
+        );
+    }
+}
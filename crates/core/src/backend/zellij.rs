@@ -0,0 +1,385 @@
+//! Zellij backend.
+//!
+//! Zellij has no equivalent of tmux's split-by-percentage commands, so the
+//! whole grid is rendered up front as a KDL layout and the session is
+//! started from it in the background:
+//!
+//! - Columns become children of a vertically split pane, sized by `width`
+//! - Rows within a column become a horizontally split pane, sized by `height`
+//! - Each `window:` becomes its own tab, named after the window
+//! - Each pane runs its command (with `startup_command` chained in front)
+//!   through `sh -c`; panes without a command get a plain shell
+//!
+//! Wrapper scripts, notes and pane colors are tmux-only and are skipped.
+//! Later splits (`action new-pane`) open next to the session's focused pane
+//! and are sized by Zellij.
+
+use std::{
+    collections::BTreeMap,
+    process::{Command, Stdio},
+};
+
+use anyhow::{Context, Result, bail};
+
+use super::{Backend, Split, recorded_session};
+use crate::{
+    config::{ResolvedPane, WorkspaceConfig, expand_path},
+    timing::Timings,
+    tmux::{OtelConfig, SessionInfo, build_pane_command, direct_command, install_workspace_skills},
+};
+
+/// Zellij backend
+pub struct ZellijBackend;
+
+/// Run a zellij command, returning an error if it fails
+fn zellij_run(args: &[&str]) -> Result<()> {
+    let status = Command::new("zellij")
+        .args(args)
+        .status()
+        .context("Failed to execute zellij command")?;
+    if !status.success() {
+        bail!("zellij command failed: {:?}", args);
+    }
+    Ok(())
+}
+
+impl Backend for ZellijBackend {
+    fn name(&self) -> &'static str {
+        "zellij"
+    }
+
+    fn is_available(&self) -> bool {
+        Command::new("zellij")
+            .arg("--version")
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .map(|s| s.success())
+            .unwrap_or(false)
+    }
+
+    fn has_session(&self, name: &str) -> bool {
+        session_names()
+            .map(|sessions| sessions.iter().any(|s| s == name))
+            .unwrap_or(false)
+    }
+
+    fn list_sessions(&self) -> Result<Vec<SessionInfo>> {
+        Ok(session_names()?
+            .into_iter()
+            .map(|name| recorded_session(name, 0, 0))
+            .collect())
+    }
+
+    fn create_workspace(
+        &self,
+        session_name: &str,
+        config: &WorkspaceConfig,
+        grid: Option<&str>,
+        otel_config: Option<OtelConfig>,
+        timings: &mut Timings,
+    ) -> Result<()> {
+        if !self.is_available() {
+            bail!("zellij is not installed (https://zellij.dev)");
+        }
+
+        let panes = config.resolve_panes(grid);
+        if panes.is_empty() {
+            bail!("No panes defined");
+        }
+        let workspace_dir = config.workspace_dir();
+        let index = config.load_index()?;
+        timings.lap("index load");
+
+        if let Some(ref workspace_dir) = workspace_dir {
+            install_workspace_skills(config, &panes, workspace_dir, timings);
+        }
+
+        let commands: Vec<Option<String>> = panes
+            .iter()
+            .map(|pane| {
+                let command = build_pane_command(
+                    pane,
+                    workspace_dir.as_deref(),
+                    index.as_ref(),
+                    otel_config.as_ref(),
+                );
+                direct_command(pane, command)
+            })
+            .collect();
+        let layout = zellij_layout(&config.workspace, &panes, &commands);
+
+        let layout_path = std::env::temp_dir().join(format!("axel-{}.kdl", session_name));
+        std::fs::write(&layout_path, layout)?;
+        zellij_run(&[
+            "attach",
+            "--create-background",
+            session_name,
+            "options",
+            "--default-layout",
+            &layout_path.to_string_lossy(),
+        ])?;
+        timings.lap("session create");
+        Ok(())
+    }
+
+    fn split_pane(
+        &self,
+        session_name: &str,
+        _target: &str,
+        split: Split,
+        pane: &ResolvedPane,
+        command: Option<&str>,
+    ) -> Result<String> {
+        let direction = match split {
+            Split::Right(_) => "right",
+            Split::Down(_) => "down",
+        };
+        let mut args: Vec<String> = [
+            "--session",
+            session_name,
+            "action",
+            "new-pane",
+            "--direction",
+            direction,
+            "--name",
+            &pane.name,
+        ]
+        .map(String::from)
+        .to_vec();
+        if let Some(path) = pane.path() {
+            args.push("--cwd".to_string());
+            args.push(expand_path(path));
+        }
+        if let Some(command) = command {
+            args.extend(["--", "sh", "-c", command].map(String::from));
+        }
+        zellij_run(&args.iter().map(String::as_str).collect::<Vec<_>>())?;
+        // Zellij panes are addressed through their session
+        Ok(session_name.to_string())
+    }
+
+    fn send_text(&self, target: &str, text: &str) -> Result<()> {
+        zellij_run(&["--session", target, "action", "write-chars", text])?;
+        zellij_run(&["--session", target, "action", "write", "13"])
+    }
+
+    fn kill_session(&self, name: &str) -> Result<()> {
+        zellij_run(&["delete-session", "--force", name])
+    }
+
+    fn attach_command(&self, name: &str) -> Vec<String> {
+        ["zellij", "attach", name]
+            .into_iter()
+            .map(String::from)
+            .collect()
+    }
+}
+
+/// Names of the running Zellij sessions
+fn session_names() -> Result<Vec<String>> {
+    let output = Command::new("zellij")
+        .args(["list-sessions", "--short", "--no-formatting"])
+        .stderr(Stdio::null())
+        .output()
+        .context("Failed to execute zellij command")?;
+    if !output.status.success() {
+        // No sessions exist
+        return Ok(Vec::new());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .map(String::from)
+        .collect())
+}
+
+/// Panes of one tab with the command each runs
+type TabPanes<'a> = Vec<(&'a ResolvedPane, Option<&'a str>)>;
+
+/// Render a grid as a Zellij KDL layout, one tab per window.
+///
+/// The first window's tab is named `tab_name`; cells with a `window:` get a
+/// tab of that name. `commands[i]` is what `panes[i]` runs (`None` for a
+/// plain shell).
+pub fn zellij_layout(
+    tab_name: &str,
+    panes: &[ResolvedPane],
+    commands: &[Option<String>],
+) -> String {
+    // Unnamed cells form the first tab, named windows follow in order
+    let mut windows: Vec<(Option<&str>, TabPanes)> = Vec::new();
+    for (pane, command) in panes.iter().zip(commands) {
+        let name = pane.window.as_deref();
+        let entry = (pane, command.as_deref());
+        match windows.iter_mut().find(|(w, _)| *w == name) {
+            Some((_, window_panes)) => window_panes.push(entry),
+            None => windows.push((name, vec![entry])),
+        }
+    }
+    windows.sort_by_key(|(name, _)| name.is_some());
+
+    let mut out = String::new();
+    out.push_str("layout {\n");
+    out.push_str("    default_tab_template {\n");
+    out.push_str("        pane size=1 borderless=true {\n");
+    out.push_str("            plugin location=\"zellij:tab-bar\"\n");
+    out.push_str("        }\n");
+    out.push_str("        children\n");
+    out.push_str("        pane size=2 borderless=true {\n");
+    out.push_str("            plugin location=\"zellij:status-bar\"\n");
+    out.push_str("        }\n");
+    out.push_str("    }\n");
+    for (name, window_panes) in windows {
+        render_tab(&mut out, name.unwrap_or(tab_name), window_panes);
+    }
+    out.push_str("}\n");
+    out
+}
+
+/// Render one tab: columns split vertically, rows within them horizontally
+fn render_tab(out: &mut String, name: &str, panes: TabPanes) {
+    let mut columns: BTreeMap<u32, TabPanes> = BTreeMap::new();
+    for (pane, command) in panes {
+        columns.entry(pane.col).or_default().push((pane, command));
+    }
+    for rows in columns.values_mut() {
+        rows.sort_by_key(|(pane, _)| pane.row);
+    }
+
+    out.push_str(&format!("    tab name={} {{\n", kdl_string(name)));
+    out.push_str("        pane split_direction=\"vertical\" {\n");
+
+    for rows in columns.values() {
+        let width = rows.iter().find_map(|(pane, _)| pane.width);
+        if let [(pane, command)] = rows.as_slice() {
+            render_pane(out, 12, pane, *command, width);
+            continue;
+        }
+        out.push_str(&" ".repeat(12));
+        out.push_str("pane");
+        if let Some(width) = width {
+            out.push_str(&format!(" size=\"{}%\"", width));
+        }
+        out.push_str(" split_direction=\"horizontal\" {\n");
+        for (pane, command) in rows {
+            render_pane(out, 16, pane, *command, pane.height);
+        }
+        out.push_str(&" ".repeat(12));
+        out.push_str("}\n");
+    }
+
+    out.push_str("        }\n");
+    out.push_str("    }\n");
+}
+
+/// Render one leaf pane at `indent` spaces
+fn render_pane(
+    out: &mut String,
+    indent: usize,
+    pane: &ResolvedPane,
+    command: Option<&str>,
+    size: Option<u32>,
+) {
+    let pad = " ".repeat(indent);
+    out.push_str(&pad);
+    out.push_str(&format!("pane name={}", kdl_string(&pane.name)));
+    if let Some(path) = pane.path() {
+        out.push_str(&format!(" cwd={}", kdl_string(&expand_path(path))));
+    }
+    if let Some(size) = size {
+        out.push_str(&format!(" size=\"{}%\"", size));
+    }
+    match command {
+        Some(command) => {
+            out.push_str(" command=\"sh\" {\n");
+            out.push_str(&format!("{}    args \"-c\" {}\n", pad, kdl_string(command)));
+            out.push_str(&pad);
+            out.push_str("}\n");
+        }
+        None => out.push('\n'),
+    }
+}
+
+/// Quote a string for KDL
+fn kdl_string(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{CustomPaneConfig, PaneConfig};
+
+    fn pane(
+        name: &str,
+        col: u32,
+        row: u32,
+        width: Option<u32>,
+        height: Option<u32>,
+    ) -> ResolvedPane {
+        ResolvedPane {
+            name: name.to_string(),
+            col,
+            row,
+            width,
+            height,
+            colspan: 1,
+            rowspan: 1,
+            window: None,
+            config: PaneConfig::Custom(CustomPaneConfig {
+                name: name.to_string(),
+                path: Some("/code/app".to_string()),
+                ..Default::default()
+            }),
+        }
+    }
+
+    #[test]
+    fn test_zellij_layout_from_grid() {
+        let panes = vec![
+            pane("claude", 0, 0, Some(60), None),
+            pane("logs", 1, 1, None, Some(30)),
+            pane("shell", 1, 0, Some(40), Some(70)),
+        ];
+        let commands = vec![
+            Some("claude \"hi\"".to_string()),
+            Some("tail -f log".to_string()),
+            None,
+        ];
+
+        assert_eq!(
+            zellij_layout("app", &panes, &commands),
+            r#"layout {
+    default_tab_template {
+        pane size=1 borderless=true {
+            plugin location="zellij:tab-bar"
+        }
+        children
+        pane size=2 borderless=true {
+            plugin location="zellij:status-bar"
+        }
+    }
+    tab name="app" {
+        pane split_direction="vertical" {
+            pane name="claude" cwd="/code/app" size="60%" command="sh" {
+                args "-c" "claude \"hi\""
+            }
+            pane size="40%" split_direction="horizontal" {
+                pane name="shell" cwd="/code/app" size="70%"
+                pane name="logs" cwd="/code/app" size="30%" command="sh" {
+                    args "-c" "tail -f log"
+                }
+            }
+        }
+    }
+}
+"#
+        );
+    }
+}
@@ -0,0 +1,295 @@
+//! Spending limits for AI panes
+//!
+//! An AI pane's `budget:` caps the tokens, estimated cost and turns the
+//! agent may use:
+//!
+//! ```yaml
+//! layouts:
+//!   panes:
+//!     - type: claude
+//!       budget:
+//!         max_tokens: 2000000
+//!         max_cost_usd: 5
+//!         max_turns: 20
+//! ```
+//!
+//! Launching the workspace points each pane with a budget at the session's
+//! event server under the pane's name, like orchestrator role panes (see
+//! [`wire_orchestrator`](crate::config::WorkspaceConfig::wire_orchestrator)),
+//! and also sends it the pane's OTEL telemetry. The server adds up each
+//! pane's token usage and cost, and counts its finished turns. The first
+//! time a pane goes over its budget, the server presses Escape in the pane
+//! to interrupt the agent, logs a `budget_exceeded` event and raises a
+//! `BudgetExceeded` notification.
+//!
+//! `axel ci` runs have no server: the budget applies to the pane's one-shot
+//! command instead (see [`crate::ci`]).
+
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    config::{PaneConfig, WorkspaceConfig},
+    server::{Pricing, TimestampedEvent, UsageTracker},
+};
+
+/// Hook events sent while an agent is idle or winding down, which don't
+/// count as working past the turn budget
+const IDLE_HOOKS: &[&str] = &["Stop", "SubagentStop", "SessionEnd"];
+
+/// `budget:` of an AI pane
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct BudgetConfig {
+    /// Tokens of every kind, cache reads included
+    #[serde(default)]
+    pub max_tokens: Option<u64>,
+    /// Estimated cost in USD, at the server's prices
+    #[serde(default)]
+    pub max_cost_usd: Option<f64>,
+    /// Finished turns; the agent is interrupted when it works on another
+    #[serde(default)]
+    pub max_turns: Option<u32>,
+}
+
+impl BudgetConfig {
+    /// Whether the budget sets any limit
+    pub fn is_empty(&self) -> bool {
+        self.max_tokens.is_none() && self.max_cost_usd.is_none() && self.max_turns.is_none()
+    }
+
+    /// Why `tokens` and `cost` spent so far, working on turn `turn`, are
+    /// over the budget, if they are
+    pub fn exceeded(&self, tokens: u64, cost: f64, turn: Option<u32>) -> Option<String> {
+        match self {
+            BudgetConfig {
+                max_tokens: Some(max),
+                ..
+            } if tokens > *max => Some(format!(
+                "used {} tokens of its {} token budget",
+                tokens, max
+            )),
+            BudgetConfig {
+                max_cost_usd: Some(max),
+                ..
+            } if cost > *max => Some(format!("spent ${:.2} of its ${:.2} budget", cost, max)),
+            BudgetConfig {
+                max_turns: Some(max),
+                ..
+            } if turn.is_some_and(|turn| turn > *max) => {
+                Some(format!("is past its budget of {} turns", max))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Budgets of the panes a server watches, and their turns so far
+#[derive(Debug, Default)]
+pub struct BudgetTracker {
+    /// Budgets by pane id
+    budgets: HashMap<String, BudgetConfig>,
+    /// Finished turns by pane id
+    turns: HashMap<String, u32>,
+    /// Panes already over budget, which are only reported once
+    exceeded: HashSet<String>,
+}
+
+impl BudgetTracker {
+    pub fn new(budgets: HashMap<String, BudgetConfig>) -> Self {
+        Self {
+            budgets,
+            ..Default::default()
+        }
+    }
+
+    /// Fold `event` into the pane's turns and check the pane's budget
+    /// against `usage`.
+    ///
+    /// Returns why the pane is over budget, the first time it is.
+    pub fn record(
+        &mut self,
+        event: &TimestampedEvent,
+        usage: &UsageTracker,
+        pricing: &Pricing,
+    ) -> Option<String> {
+        let pane_id = &event.pane_id;
+        let budget = self.budgets.get(pane_id)?;
+        if self.exceeded.contains(pane_id) {
+            return None;
+        }
+
+        let hook = event.hook_name();
+        if hook == Some("Stop") {
+            *self.turns.entry(pane_id.clone()).or_default() += 1;
+        }
+        let turns = self.turns.get(pane_id).copied().unwrap_or_default();
+        let working = hook.is_some_and(|name| !IDLE_HOOKS.contains(&name));
+
+        let report = usage.report(pricing);
+        let pane = report.panes.iter().find(|p| &p.pane_id == pane_id);
+        let tokens = pane.map(|p| p.tokens.total()).unwrap_or_default();
+        let cost = pane.map(|p| p.cost_usd).unwrap_or_default();
+
+        let reason = budget.exceeded(tokens, cost, working.then_some(turns + 1))?;
+        self.exceeded.insert(pane_id.clone());
+        Some(reason)
+    }
+}
+
+impl WorkspaceConfig {
+    /// Budgets of the AI panes that set one, by pane name
+    pub fn budgets(&self) -> HashMap<String, BudgetConfig> {
+        self.layouts
+            .panes
+            .iter()
+            .filter_map(|pane| match pane {
+                PaneConfig::Claude(c)
+                | PaneConfig::Codex(c)
+                | PaneConfig::Opencode(c)
+                | PaneConfig::Antigravity(c)
+                | PaneConfig::External(c) => c
+                    .budget
+                    .clone()
+                    .filter(|b| !b.is_empty())
+                    .map(|b| (pane.pane_type().to_string(), b)),
+                PaneConfig::Custom(_) => None,
+            })
+            .collect()
+    }
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::server::OtelEventType;
+
+    fn token_usage(pane: &str, count: u64) -> TimestampedEvent {
+        TimestampedEvent::new(
+            OtelEventType::Metrics.to_string(),
+            pane,
+            json!({"resourceMetrics": [{"scopeMetrics": [{"metrics": [{
+                "name": "claude_code.token.usage",
+                "sum": {"aggregationTemporality": 1, "dataPoints": [{
+                    "asInt": count.to_string(),
+                    "attributes": [
+                        {"key": "type", "value": {"stringValue": "output"}},
+                        {"key": "model", "value": {"stringValue": "claude-sonnet-4-5"}},
+                    ],
+                }]},
+            }]}]}]}),
+        )
+    }
+
+    fn hook(pane: &str, name: &str) -> TimestampedEvent {
+        TimestampedEvent::new("unknown_hook", pane, json!({"hook_event_name": name}))
+    }
+
+    #[test]
+    fn test_budget_tracker() {
+        let mut tracker = BudgetTracker::new(HashMap::from([
+            (
+                "claude".to_string(),
+                BudgetConfig {
+                    max_tokens: Some(1000),
+                    ..Default::default()
+                },
+            ),
+            (
+                "codex".to_string(),
+                BudgetConfig {
+                    max_turns: Some(2),
+                    ..Default::default()
+                },
+            ),
+        ]));
+        let pricing = Pricing::builtin();
+        let mut usage = UsageTracker::new();
+        let mut record = |event: TimestampedEvent| {
+            usage.record(&event);
+            tracker.record(&event, &usage, &pricing)
+        };
+
+        assert_eq!(record(token_usage("claude", 600)), None);
+        assert_eq!(
+            record(token_usage("claude", 600)),
+            Some("used 1200 tokens of its 1000 token budget".to_string())
+        );
+        // Reported once, and panes without a budget are never over it
+        assert_eq!(record(token_usage("claude", 600)), None);
+        assert_eq!(record(token_usage("shell", 5000)), None);
+
+        assert_eq!(record(hook("codex", "Stop")), None);
+        assert_eq!(record(hook("codex", "PreToolUse")), None);
+        assert_eq!(record(hook("codex", "Stop")), None);
+        assert_eq!(
+            record(hook("codex", "PreToolUse")),
+            Some("is past its budget of 2 turns".to_string())
+        );
+    }
+
+    #[test]
+    fn test_workspace_budgets() {
+        let config: WorkspaceConfig = serde_yaml::from_str(
+            r#"
+workspace: test
+layouts:
+  panes:
+    - type: claude
+      budget:
+        max_cost_usd: 2.5
+    - type: codex
+      budget: {}
+    - type: custom
+      name: shell
+"#,
+        )
+        .unwrap();
+        assert_eq!(
+            config.budgets(),
+            HashMap::from([(
+                "claude".to_string(),
+                BudgetConfig {
+                    max_cost_usd: Some(2.5),
+                    ..Default::default()
+                }
+            )])
+        );
+    }
+
+    #[test]
+    fn test_wire_budget_panes() {
+        let dir = std::env::temp_dir().join("axel-test-wire-budgets");
+        std::fs::remove_dir_all(&dir).ok();
+
+        let mut config: WorkspaceConfig = serde_yaml::from_str(
+            "workspace: test\nlayouts:\n  panes:\n    - type: claude\n      budget:\n        max_turns: 3\n    - type: codex\n",
+        )
+        .unwrap();
+        assert_eq!(config.event_server_port(), Some(4318));
+        config.wire_orchestrator(&dir).unwrap();
+
+        let PaneConfig::Claude(claude) = &config.layouts.panes[0] else {
+            panic!("expected a claude pane");
+        };
+        assert_eq!(claude.args[0], "--settings");
+        let settings = std::fs::read_to_string(dir.join(".axel/hooks/claude.json")).unwrap();
+        assert!(settings.contains("http://localhost:4318/events/claude"));
+        assert!(settings.contains("\"CLAUDE_CODE_ENABLE_TELEMETRY\": \"1\""));
+        assert!(settings.contains("/v1/metrics/claude"));
+
+        // Panes without a budget or role are left alone
+        let PaneConfig::Codex(codex) = &config.layouts.panes[1] else {
+            panic!("expected a codex pane");
+        };
+        assert!(codex.args.is_empty());
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
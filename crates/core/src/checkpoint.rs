@@ -0,0 +1,435 @@
+//! Checkpoint commits of agents' turns
+//!
+//! With `checkpoints: true` in the manifest, the event server snapshots the
+//! working tree each time an AI pane finishes a turn:
+//!
+//! ```yaml
+//! checkpoints: true
+//! ```
+//!
+//! A checkpoint is a commit of every file git doesn't ignore, tracked or
+//! not, made without touching the index, `HEAD` or the branch. Each branch
+//! (or worktree) gets a chain of them on a scratch ref,
+//! `refs/axel/checkpoints/<branch>`, each commit the child of the one
+//! before, so a checkpoint's diff is what one turn changed. Turns that
+//! change nothing make no checkpoint.
+//!
+//! `axel checkpoints ls` lists them, newest first, `diff` shows a turn's
+//! changes and `restore` puts the working tree back as it was after a turn,
+//! checkpointing the current state first so a restore can be undone.
+
+use std::{
+    ffi::OsStr,
+    path::{Path, PathBuf},
+    process::Command,
+    sync::{
+        Mutex,
+        atomic::{AtomicU64, Ordering},
+    },
+};
+
+use anyhow::{Context, Result, bail};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::{
+    config::{PaneConfig, WorkspaceConfig},
+    git,
+};
+
+/// Event type logged when the event server makes a checkpoint
+pub const CHECKPOINT_EVENT: &str = "checkpoint";
+
+/// Namespace of the checkpoint refs
+const CHECKPOINT_REFS: &str = "refs/axel/checkpoints";
+
+/// Subject prefix of checkpoint commits
+const CHECKPOINT_SUBJECT: &str = "axel checkpoint: ";
+
+/// Checkpoints commit under this identity, unsigned, whatever git's config
+/// says
+const CHECKPOINT_IDENTITY: [&str; 6] = [
+    "-c",
+    "user.name=axel",
+    "-c",
+    "user.email=axel@localhost",
+    "-c",
+    "commit.gpgsign=false",
+];
+
+/// Serializes this process's checkpoints, so turns ending together don't
+/// race to move the checkpoint ref
+static CHECKPOINT_LOCK: Mutex<()> = Mutex::new(());
+
+/// Numbers the scratch indexes of this process
+static SCRATCH_INDEXES: AtomicU64 = AtomicU64::new(0);
+
+/// A checkpoint commit
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Checkpoint {
+    pub commit: String,
+    /// The checkpoint before, or the commit checked out for the first one
+    pub parent: Option<String>,
+    pub time: DateTime<Utc>,
+    /// What the checkpoint was made for, such as the pane whose turn ended
+    pub label: String,
+}
+
+/// Scratch ref holding the checkpoints of the branch checked out in `dir`
+pub fn checkpoint_ref(dir: &Path) -> Result<String> {
+    let branch = git::current_branch(dir)?;
+    let branch = if branch == "HEAD" {
+        "detached"
+    } else {
+        &branch
+    };
+    Ok(format!("{}/{}", CHECKPOINT_REFS, branch))
+}
+
+/// Checkpoint the working tree of the repository at `dir`, labeled with
+/// `label`.
+///
+/// Returns `None` when nothing changed since the last checkpoint (or the
+/// commit checked out, for the first one).
+pub fn create_checkpoint(dir: &Path, label: &str) -> Result<Option<Checkpoint>> {
+    let _lock = CHECKPOINT_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let root = git::repo_root(dir)?;
+    let reference = checkpoint_ref(&root)?;
+    let tree = snapshot_tree(&root)?;
+
+    let previous = rev_parse(&root, &reference);
+    let parent = previous.clone().or_else(|| rev_parse(&root, "HEAD"));
+    if let Some(parent) = &parent
+        && rev_parse(&root, &format!("{}^{{tree}}", parent)).as_deref() == Some(tree.as_str())
+    {
+        return Ok(None);
+    }
+
+    let message = format!("{}{}", CHECKPOINT_SUBJECT, label);
+    let mut args = vec!["commit-tree", tree.as_str(), "-m", message.as_str()];
+    if let Some(parent) = &parent {
+        args.extend(["-p", parent.as_str()]);
+    }
+    let commit = git_output(&root, &args)?;
+    git_output(
+        &root,
+        &[
+            "update-ref",
+            "-m",
+            &message,
+            &reference,
+            &commit,
+            previous.as_deref().unwrap_or(""),
+        ],
+    )?;
+
+    Ok(Some(Checkpoint {
+        commit,
+        parent,
+        time: Utc::now(),
+        label: label.to_string(),
+    }))
+}
+
+/// Checkpoints of the branch checked out in `dir`, newest first
+pub fn list_checkpoints(dir: &Path) -> Result<Vec<Checkpoint>> {
+    let root = git::repo_root(dir)?;
+    let reference = checkpoint_ref(&root)?;
+    if rev_parse(&root, &reference).is_none() {
+        return Ok(Vec::new());
+    }
+    let log = git_output(
+        &root,
+        &[
+            "log",
+            "--first-parent",
+            "--format=%H%x1f%P%x1f%ct%x1f%s",
+            &reference,
+        ],
+    )?;
+    Ok(parse_checkpoints(&log))
+}
+
+/// The checkpoint of the branch checked out in `dir` whose commit starts
+/// with `rev`, or the newest one without `rev`
+pub fn find_checkpoint(dir: &Path, rev: Option<&str>) -> Result<Checkpoint> {
+    let checkpoints = list_checkpoints(dir)?;
+    let Some(rev) = rev else {
+        return checkpoints.into_iter().next().context("No checkpoints yet");
+    };
+    let mut matches = checkpoints
+        .into_iter()
+        .filter(|c| c.commit.starts_with(rev));
+    match (matches.next(), matches.next()) {
+        (Some(checkpoint), None) if !rev.is_empty() => Ok(checkpoint),
+        (Some(_), _) => bail!("Checkpoint '{}' is ambiguous", rev),
+        (None, _) => bail!("No checkpoint '{}'", rev),
+    }
+}
+
+/// Parse `git log --format=%H%x1f%P%x1f%ct%x1f%s` output, up to the first
+/// commit that isn't a checkpoint
+fn parse_checkpoints(log: &str) -> Vec<Checkpoint> {
+    log.lines()
+        .map_while(|line| {
+            let mut fields = line.split('\x1f');
+            let (commit, parents, time, subject) = (
+                fields.next()?,
+                fields.next()?,
+                fields.next()?,
+                fields.next()?,
+            );
+            let label = subject.strip_prefix(CHECKPOINT_SUBJECT)?;
+            Some(Checkpoint {
+                commit: commit.to_string(),
+                parent: parents.split_whitespace().next().map(str::to_string),
+                time: DateTime::from_timestamp(time.parse().ok()?, 0)?,
+                label: label.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Put the working tree of the repository at `dir` back as it was at
+/// `checkpoint`.
+///
+/// The current state is checkpointed first, so the restore can be undone;
+/// files added since `checkpoint` are deleted and files git ignores are
+/// left alone. The index and `HEAD` don't move. Returns the checkpoint of
+/// the state before the restore, if anything changed since the last one.
+pub fn restore_checkpoint(dir: &Path, checkpoint: &Checkpoint) -> Result<Option<Checkpoint>> {
+    let root = git::repo_root(dir)?;
+    let saved = create_checkpoint(&root, "before restore")?;
+    let current = match &saved {
+        Some(saved) => saved.commit.clone(),
+        None => rev_parse(&root, &checkpoint_ref(&root)?).context("No checkpoints to restore")?,
+    };
+
+    let added = git_output(
+        &root,
+        &[
+            "diff",
+            "--name-only",
+            "--no-renames",
+            "--diff-filter=A",
+            "-z",
+            &checkpoint.commit,
+            &current,
+        ],
+    )?;
+    git_output(
+        &root,
+        &[
+            "restore",
+            &format!("--source={}", checkpoint.commit),
+            "--worktree",
+            "--",
+            ":/",
+        ],
+    )?;
+    for path in added.split('\0').filter(|p| !p.is_empty()) {
+        let path = root.join(path);
+        if let Err(e) = std::fs::remove_file(&path)
+            && e.kind() != std::io::ErrorKind::NotFound
+        {
+            return Err(e).with_context(|| format!("Failed to remove {}", path.display()));
+        }
+    }
+
+    // The next turn's checkpoint is diffed against the restored state
+    create_checkpoint(&root, &format!("restored {}", short(&checkpoint.commit)))?;
+    Ok(saved)
+}
+
+/// Abbreviated commit hash
+pub fn short(commit: &str) -> &str {
+    &commit[..commit.len().min(8)]
+}
+
+/// Tree of the working tree at `root`, written through a copy of the index
+/// so the real one is left as it is.
+///
+/// Each call gets its own scratch index: the event server and the CLI
+/// checkpoint from separate processes.
+fn snapshot_tree(root: &Path) -> Result<String> {
+    let index = git_path(root, "index")?;
+    let scratch = git_path(
+        root,
+        &format!(
+            "axel-checkpoint-index-{}-{}",
+            std::process::id(),
+            SCRATCH_INDEXES.fetch_add(1, Ordering::Relaxed)
+        ),
+    )?;
+    match std::fs::copy(&index, &scratch) {
+        Ok(_) => {}
+        // A repository without commits may have no index yet
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            std::fs::remove_file(&scratch).ok();
+        }
+        Err(e) => return Err(e).context("Failed to copy the git index"),
+    }
+
+    let tree = (|| {
+        git_command(root, &["add", "-A"])
+            .env("GIT_INDEX_FILE", &scratch)
+            .output()
+            .context("Failed to execute git")
+            .and_then(|output| check_output(&["add", "-A"], output))?;
+        git_command(root, &["write-tree"])
+            .env("GIT_INDEX_FILE", &scratch)
+            .output()
+            .context("Failed to execute git")
+            .and_then(|output| check_output(&["write-tree"], output))
+    })();
+    std::fs::remove_file(&scratch).ok();
+    tree
+}
+
+/// Path of `name` in the git directory of the repository at `root`
+fn git_path(root: &Path, name: &str) -> Result<PathBuf> {
+    let path = PathBuf::from(git_output(root, &["rev-parse", "--git-path", name])?);
+    Ok(if path.is_relative() {
+        root.join(path)
+    } else {
+        path
+    })
+}
+
+/// The commit (or object) `rev` names, if it exists
+fn rev_parse(root: &Path, rev: &str) -> Option<String> {
+    git_output(root, &["rev-parse", "--verify", "--quiet", rev]).ok()
+}
+
+fn git_command<S: AsRef<OsStr>>(root: &Path, args: &[S]) -> Command {
+    let mut command = Command::new("git");
+    command
+        .args(CHECKPOINT_IDENTITY)
+        .args(args)
+        .current_dir(root);
+    command
+}
+
+/// Run git in `root` and return its trimmed output
+fn git_output(root: &Path, args: &[&str]) -> Result<String> {
+    let output = git_command(root, args)
+        .output()
+        .context("Failed to execute git")?;
+    check_output(args, output)
+}
+
+fn check_output(args: &[&str], output: std::process::Output) -> Result<String> {
+    if !output.status.success() {
+        bail!(
+            "git {} failed: {}",
+            args.first().unwrap_or(&""),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+impl WorkspaceConfig {
+    /// AI panes whose turns are checkpointed, by name: all Claude and Codex
+    /// panes with `checkpoints: true`
+    pub fn checkpointed_panes(&self) -> Vec<String> {
+        if !self.checkpoints {
+            return Vec::new();
+        }
+        self.layouts
+            .panes
+            .iter()
+            .filter(|pane| matches!(pane, PaneConfig::Claude(_) | PaneConfig::Codex(_)))
+            .map(|pane| pane.pane_type().to_string())
+            .collect()
+    }
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn git(dir: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .args(CHECKPOINT_IDENTITY)
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .unwrap()
+            .status;
+        assert!(status.success(), "git {:?} failed", args);
+    }
+
+    #[test]
+    fn test_checkpoints() {
+        let dir = std::env::temp_dir().join("axel-test-checkpoints");
+        std::fs::remove_dir_all(&dir).ok();
+        std::fs::create_dir_all(&dir).unwrap();
+        git(&dir, &["init", "-q", "-b", "main"]);
+        std::fs::write(dir.join("lib.rs"), "v0\n").unwrap();
+        git(&dir, &["add", "lib.rs"]);
+        git(&dir, &["commit", "-qm", "init"]);
+        assert_eq!(checkpoint_ref(&dir).unwrap(), "refs/axel/checkpoints/main");
+
+        // Nothing changed, no checkpoint
+        assert_eq!(create_checkpoint(&dir, "claude").unwrap(), None);
+
+        // A good turn, then a bad one adding a file
+        std::fs::write(dir.join("lib.rs"), "v1\n").unwrap();
+        let good = create_checkpoint(&dir, "claude").unwrap().unwrap();
+        std::fs::write(dir.join("lib.rs"), "broken\n").unwrap();
+        std::fs::write(dir.join("junk.rs"), "junk\n").unwrap();
+        let bad = create_checkpoint(&dir, "codex").unwrap().unwrap();
+        assert_eq!(bad.parent.as_deref(), Some(good.commit.as_str()));
+
+        let checkpoints = list_checkpoints(&dir).unwrap();
+        assert_eq!(
+            checkpoints
+                .iter()
+                .map(|c| c.label.as_str())
+                .collect::<Vec<_>>(),
+            vec!["codex", "claude"]
+        );
+
+        // The index and HEAD are left alone
+        let status = Command::new("git")
+            .args(["status", "--porcelain"])
+            .current_dir(&dir)
+            .output()
+            .unwrap();
+        assert_eq!(
+            String::from_utf8_lossy(&status.stdout),
+            " M lib.rs\n?? junk.rs\n"
+        );
+        // and no scratch index is left behind
+        assert!(std::fs::read_dir(dir.join(".git")).unwrap().all(|e| {
+            !e.unwrap()
+                .file_name()
+                .to_string_lossy()
+                .starts_with("axel-")
+        }));
+
+        // Restoring the good turn drops the bad one's changes
+        let saved = restore_checkpoint(&dir, &good).unwrap();
+        assert_eq!(saved, None);
+        assert_eq!(std::fs::read_to_string(dir.join("lib.rs")).unwrap(), "v1\n");
+        assert!(!dir.join("junk.rs").exists());
+        assert_eq!(
+            list_checkpoints(&dir).unwrap()[0].label,
+            format!("restored {}", short(&good.commit))
+        );
+        assert_eq!(
+            find_checkpoint(&dir, Some(short(&good.commit)))
+                .unwrap()
+                .commit,
+            good.commit
+        );
+        assert!(find_checkpoint(&dir, Some("zzz")).is_err());
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
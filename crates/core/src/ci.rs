@@ -0,0 +1,633 @@
+//! Headless agent runs for `axel ci`
+//!
+//! Each pane of a grid runs once, one after the other and without tmux: AI
+//! panes run their prompt non-interactively (see
+//! [`one_shot_command`](crate::tmux::one_shot_command)) and custom panes run
+//! their `command`. A pane's output is captured, a pane that runs past its
+//! timeout is killed along with its children, and the results are reported
+//! as JSON or JUnit XML for CI systems.
+//!
+//! An AI pane's `budget:` (see [`crate::budget`]) applies to its run: limits
+//! the agent's CLI takes become flags of its command, and for the others the
+//! agent reports its usage as JSON events on its output, which is checked as
+//! it runs. A pane going over budget is killed and fails.
+
+use std::{
+    collections::HashMap,
+    io::Read,
+    path::Path,
+    process::{Command, Stdio},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use anyhow::{Result, bail};
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::{budget::BudgetConfig, config::PaneConfig};
+
+/// Time given to a timed-out pane to exit after SIGTERM before SIGKILL
+const KILL_GRACE: Duration = Duration::from_secs(2);
+
+/// How a pane's run ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CaseStatus {
+    Passed,
+    Failed,
+    TimedOut,
+    /// The pane has nothing to run (no prompt, no command)
+    Skipped,
+}
+
+/// Result of one pane's run
+#[derive(Debug, Clone, Serialize)]
+pub struct CaseResult {
+    /// Pane name in the grid
+    pub name: String,
+    pub status: CaseStatus,
+    /// Command that was run
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exit_code: Option<i32>,
+    pub duration_secs: f64,
+    /// Combined stdout and stderr
+    pub output: String,
+    /// Why the pane failed or was skipped
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+impl CaseResult {
+    /// A pane that was not run
+    pub fn skipped(name: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            status: CaseStatus::Skipped,
+            command: None,
+            exit_code: None,
+            duration_secs: 0.0,
+            output: String::new(),
+            message: Some(message.into()),
+        }
+    }
+}
+
+/// Results of a grid's run
+#[derive(Debug, Clone, Serialize)]
+pub struct CiReport {
+    /// Grid that was run
+    pub grid: String,
+    pub cases: Vec<CaseResult>,
+}
+
+impl CiReport {
+    /// Number of panes with `status`
+    pub fn count(&self, status: CaseStatus) -> usize {
+        self.cases.iter().filter(|c| c.status == status).count()
+    }
+
+    /// Whether no pane failed or timed out
+    pub fn passed(&self) -> bool {
+        self.count(CaseStatus::Failed) + self.count(CaseStatus::TimedOut) == 0
+    }
+
+    /// The report as a JUnit XML test suite, one test case per pane
+    pub fn to_junit(&self) -> String {
+        let time: f64 = self.cases.iter().map(|c| c.duration_secs).sum();
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        xml.push_str(&format!(
+            "<testsuite name=\"axel ci: {}\" tests=\"{}\" failures=\"{}\" errors=\"0\" skipped=\"{}\" time=\"{:.3}\">\n",
+            xml_escape(&self.grid),
+            self.cases.len(),
+            self.count(CaseStatus::Failed) + self.count(CaseStatus::TimedOut),
+            self.count(CaseStatus::Skipped),
+            time
+        ));
+        for case in &self.cases {
+            xml.push_str(&format!(
+                "  <testcase name=\"{}\" classname=\"axel.{}\" time=\"{:.3}\">\n",
+                xml_escape(&case.name),
+                xml_escape(&self.grid),
+                case.duration_secs
+            ));
+            let message = xml_escape(case.message.as_deref().unwrap_or_default());
+            match case.status {
+                CaseStatus::Passed => {}
+                CaseStatus::Failed | CaseStatus::TimedOut => {
+                    xml.push_str(&format!("    <failure message=\"{}\"/>\n", message));
+                }
+                CaseStatus::Skipped => {
+                    xml.push_str(&format!("    <skipped message=\"{}\"/>\n", message));
+                }
+            }
+            if !case.output.is_empty() {
+                xml.push_str(&format!(
+                    "    <system-out>{}</system-out>\n",
+                    xml_escape(&case.output)
+                ));
+            }
+            xml.push_str("  </testcase>\n");
+        }
+        xml.push_str("</testsuite>\n");
+        xml
+    }
+}
+
+/// Apply the budget of AI pane `pane` to its one-shot run: its `budget:`,
+/// with `max_turns` for panes that don't limit their turns.
+///
+/// Claude takes turn and cost limits as flags (`--max-turns`,
+/// `--max-budget-usd`). Other limits are left to [`run_case`], with the agent
+/// made to report its usage as JSON events. Returns the budget `run_case`
+/// checks, `None` when there's nothing to check; fails for agents that can't
+/// report their usage.
+pub fn apply_budget(pane: &mut PaneConfig, max_turns: Option<u32>) -> Result<Option<BudgetConfig>> {
+    let (mut budget, own_turns) = match &*pane {
+        PaneConfig::Claude(c)
+        | PaneConfig::Codex(c)
+        | PaneConfig::Opencode(c)
+        | PaneConfig::Antigravity(c)
+        | PaneConfig::External(c) => (c.budget.clone().unwrap_or_default(), c.max_turns),
+        PaneConfig::Custom(_) => return Ok(None),
+    };
+    if budget.max_turns.is_none() && !(matches!(pane, PaneConfig::Claude(_)) && own_turns.is_some())
+    {
+        budget.max_turns = max_turns;
+    }
+    if budget.is_empty() {
+        return Ok(None);
+    }
+
+    match pane {
+        PaneConfig::Claude(c) => {
+            if let Some(turns) = budget.max_turns {
+                c.max_turns = Some(c.max_turns.map_or(turns, |own| own.min(turns)));
+            }
+            if let Some(cost) = budget.max_cost_usd {
+                c.args
+                    .extend(["--max-budget-usd".to_string(), cost.to_string()]);
+            }
+            if budget.max_tokens.is_none() {
+                return Ok(None);
+            }
+            c.output_format = Some("stream-json".to_string());
+            c.args.push("--verbose".to_string());
+            Ok(Some(BudgetConfig {
+                max_tokens: budget.max_tokens,
+                ..Default::default()
+            }))
+        }
+        PaneConfig::Codex(c) => {
+            c.args.push("--json".to_string());
+            Ok(Some(budget))
+        }
+        PaneConfig::Opencode(c) => {
+            c.args.extend(["--format".to_string(), "json".to_string()]);
+            Ok(Some(budget))
+        }
+        _ => bail!(
+            "{} panes don't report their usage, so their budget can't be enforced",
+            pane.pane_type()
+        ),
+    }
+}
+
+/// Usage an agent reports in the JSON events of its output
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CaseUsage {
+    /// Tokens of every kind, cache reads included
+    pub tokens: u64,
+    pub cost_usd: f64,
+    /// Turns started
+    pub turns: u32,
+}
+
+impl CaseUsage {
+    /// Usage reported in `output` by Claude's `stream-json` events,
+    /// `codex exec --json` or `opencode run --format json`; other lines are
+    /// ignored
+    pub fn of_output(output: &str) -> Self {
+        let mut usage = Self::default();
+        // Claude repeats a message's usage with each of its content blocks,
+        // and totals them in its result
+        let mut messages: HashMap<String, u64> = HashMap::new();
+        let mut result = None;
+        for line in output.lines() {
+            let Ok(event) = serde_json::from_str::<Value>(line) else {
+                continue;
+            };
+            match event["type"].as_str() {
+                Some("assistant") => {
+                    if let Some(id) = event["message"]["id"].as_str() {
+                        messages.insert(
+                            id.to_string(),
+                            sum(&event["message"]["usage"], CLAUDE_TOKENS),
+                        );
+                    }
+                }
+                Some("result") => {
+                    result = Some(sum(&event["usage"], CLAUDE_TOKENS));
+                    usage.cost_usd = event["total_cost_usd"].as_f64().unwrap_or_default();
+                }
+                Some("turn.started") | Some("step_start") => usage.turns += 1,
+                Some("turn.completed") => usage.tokens += sum(&event["usage"], CODEX_TOKENS),
+                Some("step_finish") => {
+                    let part = &event["part"];
+                    usage.tokens += sum(&part["tokens"], OPENCODE_TOKENS)
+                        + sum(&part["tokens"]["cache"], &["read", "write"]);
+                    usage.cost_usd += part["cost"].as_f64().unwrap_or_default();
+                }
+                _ => {}
+            }
+        }
+        usage.tokens += result.unwrap_or_else(|| messages.values().sum());
+        usage
+    }
+
+    /// Why the usage is over `budget`, if it is
+    pub fn exceeded(&self, budget: &BudgetConfig) -> Option<String> {
+        budget.exceeded(self.tokens, self.cost_usd, Some(self.turns))
+    }
+}
+
+/// Token counts of a Claude message's `usage`
+const CLAUDE_TOKENS: &[&str] = &[
+    "input_tokens",
+    "output_tokens",
+    "cache_read_input_tokens",
+    "cache_creation_input_tokens",
+];
+
+/// Token counts of a Codex turn's `usage` (its input includes cached tokens)
+const CODEX_TOKENS: &[&str] = &["input_tokens", "output_tokens"];
+
+/// Token counts of an OpenCode step's `tokens`, besides its `cache`
+const OPENCODE_TOKENS: &[&str] = &["input", "output", "reasoning"];
+
+/// Sum of the `keys` of `object` that are numbers
+fn sum(object: &Value, keys: &[&str]) -> u64 {
+    keys.iter().filter_map(|key| object[key].as_u64()).sum()
+}
+
+/// Run `command` with `sh -c` in `dir`, capturing its output and killing it
+/// (and everything it started) after `timeout`, or as soon as the usage its
+/// output reports goes over `budget`
+pub fn run_case(
+    name: &str,
+    command: &str,
+    dir: &Path,
+    timeout: Duration,
+    budget: Option<&BudgetConfig>,
+) -> CaseResult {
+    let start = Instant::now();
+    let result = |status, exit_code, output: String, message: Option<String>| CaseResult {
+        name: name.to_string(),
+        status,
+        command: Some(command.to_string()),
+        exit_code,
+        duration_secs: start.elapsed().as_secs_f64(),
+        output,
+        message,
+    };
+
+    let mut process = Command::new("sh");
+    process
+        .arg("-c")
+        .arg(command)
+        .current_dir(dir)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    // Own process group, so a timeout kills the agent and not just `sh`
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        process.process_group(0);
+    }
+    let mut child = match process.spawn() {
+        Ok(child) => child,
+        Err(e) => {
+            return result(
+                CaseStatus::Failed,
+                None,
+                String::new(),
+                Some(format!("failed to start: {}", e)),
+            );
+        }
+    };
+
+    // Both streams go to one buffer, in the order they arrive
+    let output = Arc::new(Mutex::new(Vec::new()));
+    let readers: Vec<_> = [
+        child
+            .stdout
+            .take()
+            .map(|s| Box::new(s) as Box<dyn Read + Send>),
+        child
+            .stderr
+            .take()
+            .map(|s| Box::new(s) as Box<dyn Read + Send>),
+    ]
+    .into_iter()
+    .flatten()
+    .map(|mut stream| {
+        let output = Arc::clone(&output);
+        std::thread::spawn(move || {
+            let mut buf = [0u8; 8192];
+            while let Ok(n) = stream.read(&mut buf) {
+                if n == 0 {
+                    break;
+                }
+                output.lock().unwrap().extend_from_slice(&buf[..n]);
+            }
+        })
+    })
+    .collect();
+
+    let deadline = start + timeout;
+    let over_budget = |output: &[u8]| {
+        let usage = CaseUsage::of_output(&String::from_utf8_lossy(output));
+        budget
+            .and_then(|budget| usage.exceeded(budget))
+            .map(|reason| format!("{} {}", name, reason))
+    };
+    // Why the pane was stopped, and when
+    let mut stopped: Option<(CaseStatus, String, Instant)> = None;
+    let mut checked = 0;
+    let status = loop {
+        if stopped.is_none() {
+            if Instant::now() >= deadline {
+                let message = format!("timed out after {}s", timeout.as_secs());
+                stopped = Some((CaseStatus::TimedOut, message, Instant::now()));
+            } else if budget.is_some() {
+                let output = output.lock().unwrap();
+                if output.len() != checked {
+                    checked = output.len();
+                    stopped = over_budget(&output)
+                        .map(|message| (CaseStatus::Failed, message, Instant::now()));
+                }
+            }
+            if stopped.is_some() {
+                kill_group(child.id(), "TERM");
+            }
+        }
+        match child.try_wait() {
+            Ok(Some(status)) => break Some(status),
+            Ok(None)
+                if stopped
+                    .as_ref()
+                    .is_some_and(|(_, _, at)| at.elapsed() >= KILL_GRACE) =>
+            {
+                kill_group(child.id(), "KILL");
+                break child.wait().ok();
+            }
+            Ok(None) => std::thread::sleep(Duration::from_millis(50)),
+            Err(_) => break None,
+        }
+    };
+    // Children that outlived `sh` would hold the pipes open
+    kill_group(child.id(), "KILL");
+    for reader in readers {
+        reader.join().ok();
+    }
+    let output = output.lock().unwrap();
+    // Usage reported just before the pane exited
+    if stopped.is_none() {
+        stopped = over_budget(&output).map(|message| (CaseStatus::Failed, message, Instant::now()));
+    }
+    let output = String::from_utf8_lossy(&output).into_owned();
+
+    let exit_code = status.and_then(|s| s.code());
+    if let Some((status, message, _)) = stopped {
+        result(status, exit_code, output, Some(message))
+    } else if status.is_some_and(|s| s.success()) {
+        result(CaseStatus::Passed, exit_code, output, None)
+    } else {
+        let message = match exit_code {
+            Some(code) => format!("exited with {}", code),
+            None => "killed by a signal".to_string(),
+        };
+        result(CaseStatus::Failed, exit_code, output, Some(message))
+    }
+}
+
+/// Send `signal` to the process group led by `pid`
+///
+/// Windows has no signals: the process tree is ended with `taskkill` and
+/// `signal` only decides whether it's forced.
+fn kill_group(pid: u32, signal: &str) {
+    let mut kill = if cfg!(windows) {
+        let mut kill = Command::new("taskkill");
+        kill.args(["/T", "/PID", &pid.to_string()]);
+        if signal == "KILL" {
+            kill.arg("/F");
+        }
+        kill
+    } else {
+        let mut kill = Command::new("kill");
+        kill.arg(format!("-{}", signal))
+            .arg("--")
+            .arg(format!("-{}", pid));
+        kill
+    };
+    kill.stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .ok();
+}
+
+fn xml_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            // Control characters (ANSI colors) aren't allowed in XML 1.0
+            c if c.is_control() && !matches!(c, '\n' | '\r' | '\t') => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run_case() {
+        let dir = std::env::temp_dir();
+        let passed = run_case(
+            "ok",
+            "echo out; echo err >&2",
+            &dir,
+            Duration::from_secs(5),
+            None,
+        );
+        assert_eq!(passed.status, CaseStatus::Passed);
+        assert!(passed.output.contains("out\n") && passed.output.contains("err\n"));
+
+        let failed = run_case("bad", "exit 3", &dir, Duration::from_secs(5), None);
+        assert_eq!(failed.status, CaseStatus::Failed);
+        assert_eq!(failed.exit_code, Some(3));
+        assert_eq!(failed.message.as_deref(), Some("exited with 3"));
+
+        let slow = run_case("slow", "sleep 30", &dir, Duration::from_millis(200), None);
+        assert_eq!(slow.status, CaseStatus::TimedOut);
+        assert!(slow.duration_secs < 10.0);
+    }
+
+    #[test]
+    fn test_run_case_over_budget() {
+        let dir = std::env::temp_dir();
+        let budget = BudgetConfig {
+            max_tokens: Some(1000),
+            ..Default::default()
+        };
+        let turn = r#"{"type":"turn.completed","usage":{"input_tokens":900,"output_tokens":200}}"#;
+        let command = format!("echo '{}'; sleep 30", turn);
+        let case = run_case(
+            "codex",
+            &command,
+            &dir,
+            Duration::from_secs(20),
+            Some(&budget),
+        );
+        assert_eq!(case.status, CaseStatus::Failed);
+        assert_eq!(
+            case.message.as_deref(),
+            Some("codex used 1100 tokens of its 1000 token budget")
+        );
+        assert!(case.duration_secs < 10.0);
+
+        // Usage reported on exit counts too
+        let command = format!("echo '{}'", turn);
+        let case = run_case(
+            "codex",
+            &command,
+            &dir,
+            Duration::from_secs(5),
+            Some(&budget),
+        );
+        assert_eq!(case.status, CaseStatus::Failed);
+
+        let case = run_case("codex", &command, &dir, Duration::from_secs(5), None);
+        assert_eq!(case.status, CaseStatus::Passed);
+    }
+
+    #[test]
+    fn test_case_usage() {
+        let claude = [
+            r#"{"type":"assistant","message":{"id":"m1","usage":{"input_tokens":10,"output_tokens":5}}}"#,
+            r#"{"type":"assistant","message":{"id":"m1","usage":{"input_tokens":10,"output_tokens":5}}}"#,
+            r#"{"type":"assistant","message":{"id":"m2","usage":{"input_tokens":3,"cache_read_input_tokens":100}}}"#,
+        ]
+        .join("\n");
+        assert_eq!(CaseUsage::of_output(&claude).tokens, 118);
+        let result = r#"{"type":"result","total_cost_usd":0.5,"usage":{"input_tokens":20,"output_tokens":30}}"#;
+        let usage = CaseUsage::of_output(&format!("{}\n{}", claude, result));
+        assert_eq!((usage.tokens, usage.cost_usd), (50, 0.5));
+
+        let codex = r#"{"type":"turn.started"}
+{"type":"turn.completed","usage":{"input_tokens":100,"cached_input_tokens":80,"output_tokens":20}}
+{"type":"turn.started"}"#;
+        let usage = CaseUsage::of_output(codex);
+        assert_eq!((usage.tokens, usage.turns), (120, 2));
+
+        let opencode = r#"some text
+{"type":"step_start","part":{}}
+{"type":"step_finish","part":{"cost":0.25,"tokens":{"input":10,"output":20,"reasoning":5,"cache":{"read":100,"write":1}}}}"#;
+        let usage = CaseUsage::of_output(opencode);
+        assert_eq!(
+            usage,
+            CaseUsage {
+                tokens: 136,
+                cost_usd: 0.25,
+                turns: 1
+            }
+        );
+        let budget = BudgetConfig {
+            max_cost_usd: Some(0.2),
+            ..Default::default()
+        };
+        assert_eq!(
+            usage.exceeded(&budget).as_deref(),
+            Some("spent $0.25 of its $0.20 budget")
+        );
+    }
+
+    #[test]
+    fn test_apply_budget() {
+        let pane = |yaml: &str| -> PaneConfig { serde_yaml::from_str(yaml).unwrap() };
+
+        // Claude's turns and cost are flags; its tokens are tracked
+        let mut claude = pane("type: claude\nbudget:\n  max_turns: 5\n  max_cost_usd: 2\n");
+        assert_eq!(apply_budget(&mut claude, Some(10)).unwrap(), None);
+        let PaneConfig::Claude(c) = &claude else {
+            panic!("expected a claude pane");
+        };
+        assert_eq!(c.max_turns, Some(5));
+        assert_eq!(c.args, ["--max-budget-usd", "2"]);
+
+        let mut claude = pane("type: claude\nbudget:\n  max_tokens: 1000\n");
+        let budget = apply_budget(&mut claude, None).unwrap().unwrap();
+        assert_eq!(budget.max_tokens, Some(1000));
+        let PaneConfig::Claude(c) = &claude else {
+            panic!("expected a claude pane");
+        };
+        assert_eq!(c.output_format.as_deref(), Some("stream-json"));
+
+        // Other agents report their usage, with `--max-turns` as a budget
+        let mut codex = pane("type: codex\n");
+        let budget = apply_budget(&mut codex, Some(3)).unwrap().unwrap();
+        assert_eq!(budget.max_turns, Some(3));
+        let PaneConfig::Codex(c) = &codex else {
+            panic!("expected a codex pane");
+        };
+        assert_eq!(c.args, ["--json"]);
+        assert_eq!(
+            apply_budget(&mut pane("type: opencode\n"), None).unwrap(),
+            None
+        );
+
+        assert!(apply_budget(&mut pane("type: antigravity\n"), Some(3)).is_err());
+    }
+
+    #[test]
+    fn test_junit_report() {
+        let report = CiReport {
+            grid: "default".to_string(),
+            cases: vec![
+                CaseResult {
+                    name: "claude".to_string(),
+                    status: CaseStatus::Failed,
+                    command: Some("claude -p 'hi'".to_string()),
+                    exit_code: Some(1),
+                    duration_secs: 1.5,
+                    output: "<error> & \u{1b}[31mred\u{1b}[0m".to_string(),
+                    message: Some("exited with 1".to_string()),
+                },
+                CaseResult::skipped("shell", "nothing to run"),
+            ],
+        };
+        assert!(!report.passed());
+
+        let xml = report.to_junit();
+        assert!(xml.contains(
+            r#"<testsuite name="axel ci: default" tests="2" failures="1" errors="0" skipped="1" time="1.500">"#
+        ));
+        assert!(xml.contains(r#"<failure message="exited with 1"/>"#));
+        assert!(xml.contains("<system-out>&lt;error&gt; &amp; [31mred[0m</system-out>"));
+        assert!(xml.contains(r#"<skipped message="nothing to run"/>"#));
+    }
+}
@@ -2,6 +2,8 @@
 //!
 //! Provides a builder pattern for constructing Claude Code CLI commands.
 
+use crate::shell::{quote, single_quote};
+
 /// Claude Code command builder
 #[derive(Debug, Default, Clone)]
 pub struct ClaudeCommand {
@@ -13,8 +15,24 @@ pub struct ClaudeCommand {
     pub model: Option<String>,
     /// Resume a previous conversation by ID
     pub resume: Option<String>,
+    /// Continue the most recent conversation in the directory
+    pub continue_last: bool,
+    /// ID to give a new conversation
+    pub session_id: Option<String>,
     /// Initial prompt to send
     pub prompt: Option<String>,
+    /// MCP server config file
+    pub mcp_config: Option<String>,
+    /// Permission mode (e.g., "plan", "acceptEdits")
+    pub permission_mode: Option<String>,
+    /// Output format in print mode ("text", "json", "stream-json")
+    pub output_format: Option<String>,
+    /// Maximum number of agentic turns in print mode
+    pub max_turns: Option<u32>,
+    /// Additional directories the agent may access
+    pub add_dirs: Vec<String>,
+    /// Run the prompt non-interactively and print the reply (`-p`)
+    pub print: bool,
     /// Additional CLI arguments
     pub extra_args: Vec<String>,
 }
@@ -44,46 +62,131 @@ impl ClaudeCommand {
     }
 
     /// Resume a previous conversation
-    #[allow(dead_code)]
     pub fn resume(mut self, id: impl Into<String>) -> Self {
         self.resume = Some(id.into());
         self
     }
 
+    /// Continue the most recent conversation
+    pub fn continue_last(mut self) -> Self {
+        self.continue_last = true;
+        self
+    }
+
+    /// Start the conversation with a fixed ID, so it can be resumed later
+    pub fn session_id(mut self, id: impl Into<String>) -> Self {
+        self.session_id = Some(id.into());
+        self
+    }
+
     /// Set the initial prompt
     pub fn prompt(mut self, prompt: impl Into<String>) -> Self {
         self.prompt = Some(prompt.into());
         self
     }
 
+    /// Load MCP servers from a config file
+    pub fn mcp_config(mut self, path: impl Into<String>) -> Self {
+        self.mcp_config = Some(path.into());
+        self
+    }
+
+    /// Set the permission mode
+    pub fn permission_mode(mut self, mode: impl Into<String>) -> Self {
+        self.permission_mode = Some(mode.into());
+        self
+    }
+
+    /// Set the output format (print mode)
+    pub fn output_format(mut self, format: impl Into<String>) -> Self {
+        self.output_format = Some(format.into());
+        self
+    }
+
+    /// Limit the number of agentic turns (print mode)
+    pub fn max_turns(mut self, turns: u32) -> Self {
+        self.max_turns = Some(turns);
+        self
+    }
+
+    /// Give the agent access to another directory
+    pub fn add_dir(mut self, dir: impl Into<String>) -> Self {
+        self.add_dirs.push(dir.into());
+        self
+    }
+
     /// Add an extra argument
     pub fn extra_arg(mut self, arg: impl Into<String>) -> Self {
         self.extra_args.push(arg.into());
         self
     }
 
+    /// Run in print mode
+    pub fn print(mut self) -> Self {
+        self.print = true;
+        self
+    }
+
     /// Build the command string to execute
     pub fn build(&self) -> String {
         let mut parts = vec!["claude".to_string()];
 
+        if self.print {
+            parts.push("-p".to_string());
+        }
+
         if !self.allowed_tools.is_empty() {
             parts.push("--allowedTools".to_string());
-            parts.push(self.allowed_tools.join(","));
+            parts.push(quote(&self.allowed_tools.join(",")).into_owned());
         }
 
         if !self.disallowed_tools.is_empty() {
             parts.push("--disallowedTools".to_string());
-            parts.push(self.disallowed_tools.join(","));
+            parts.push(quote(&self.disallowed_tools.join(",")).into_owned());
         }
 
         if let Some(model) = &self.model {
             parts.push("--model".to_string());
-            parts.push(model.clone());
+            parts.push(quote(model).into_owned());
+        }
+
+        if let Some(mode) = &self.permission_mode {
+            parts.push("--permission-mode".to_string());
+            parts.push(quote(mode).into_owned());
+        }
+
+        if let Some(format) = &self.output_format {
+            parts.push("--output-format".to_string());
+            parts.push(quote(format).into_owned());
+        }
+
+        if let Some(turns) = self.max_turns {
+            parts.push("--max-turns".to_string());
+            parts.push(turns.to_string());
+        }
+
+        for dir in &self.add_dirs {
+            parts.push("--add-dir".to_string());
+            parts.push(quote(dir).into_owned());
         }
 
         if let Some(resume) = &self.resume {
             parts.push("--resume".to_string());
-            parts.push(resume.clone());
+            parts.push(quote(resume).into_owned());
+        }
+
+        if self.continue_last {
+            parts.push("--continue".to_string());
+        }
+
+        if let Some(id) = &self.session_id {
+            parts.push("--session-id".to_string());
+            parts.push(quote(id).into_owned());
+        }
+
+        if let Some(path) = &self.mcp_config {
+            parts.push("--mcp-config".to_string());
+            parts.push(quote(path).into_owned());
         }
 
         for arg in &self.extra_args {
@@ -93,8 +196,7 @@ impl ClaudeCommand {
         // Prompt goes last if present (as a positional argument)
         // Use single quotes for shell safety (handles newlines, $, `, etc.)
         if let Some(prompt) = &self.prompt {
-            let escaped = prompt.replace('\'', "'\\''");
-            parts.push(format!("'{}'", escaped));
+            parts.push(single_quote(prompt));
         }
 
         parts.join(" ")
@@ -134,4 +236,63 @@ mod tests {
             .build();
         assert_eq!(cmd, "claude --allowedTools Read --model sonnet 'Hello'");
     }
+
+    #[test]
+    fn test_permission_and_print_flags() {
+        let cmd = ClaudeCommand::new()
+            .permission_mode("plan")
+            .output_format("json")
+            .max_turns(5)
+            .add_dir("../shared libs")
+            .add_dir("/tmp")
+            .extra_arg("-p")
+            .prompt("Summarize")
+            .build();
+        assert_eq!(
+            cmd,
+            "claude --permission-mode plan --output-format json --max-turns 5 \
+             --add-dir '../shared libs' --add-dir /tmp -p 'Summarize'"
+        );
+    }
+
+    #[test]
+    fn test_resume_and_session_id() {
+        let cmd = ClaudeCommand::new().model("opus").resume("abc").build();
+        assert_eq!(cmd, "claude --model opus --resume abc");
+
+        let cmd = ClaudeCommand::new().continue_last().build();
+        assert_eq!(cmd, "claude --continue");
+
+        let cmd = ClaudeCommand::new()
+            .session_id("0f8e3c1a-5b7d-4e2f-9a6b-1c2d3e4f5a6b")
+            .prompt("Hi")
+            .build();
+        assert_eq!(
+            cmd,
+            "claude --session-id 0f8e3c1a-5b7d-4e2f-9a6b-1c2d3e4f5a6b 'Hi'"
+        );
+    }
+
+    #[test]
+    fn test_with_mcp_config() {
+        let cmd = ClaudeCommand::new()
+            .mcp_config("/code/my app/.axel/mcp/claude.json")
+            .build();
+        assert_eq!(
+            cmd,
+            "claude --mcp-config '/code/my app/.axel/mcp/claude.json'"
+        );
+    }
+
+    #[test]
+    fn test_values_are_quoted() {
+        let cmd = ClaudeCommand::new()
+            .allowed_tools(vec!["Read".to_string(), "Bash(git:*)".to_string()])
+            .prompt("Don't run `rm -rf $HOME`")
+            .build();
+        assert_eq!(
+            cmd,
+            "claude --allowedTools 'Read,Bash(git:*)' 'Don'\\''t run `rm -rf $HOME`'"
+        );
+    }
 }
@@ -0,0 +1,304 @@
+//! Skill bundles
+//!
+//! `axel skill pack` writes skills to a gzipped tarball so they can be
+//! shared without a git repository; `axel skill install` unpacks one into a
+//! skills directory. Every skill is stored as `skills/<name>/SKILL.md` with
+//! the supporting files of its directory (flat `<name>.md` skills become
+//! directory skills).
+//!
+//! Installing validates the whole bundle before writing anything: entries
+//! must be plain files under `skills/<name>/`, names must be letters,
+//! digits, `-`, `_` or `.`, every skill needs a `SKILL.md`, and skills that
+//! already exist in the target directory are refused unless overwriting is
+//! asked for.
+
+use std::{
+    collections::BTreeMap,
+    io::Read,
+    path::{Component, Path, PathBuf},
+};
+
+use anyhow::{Context, Result, bail};
+use flate2::{Compression, read::GzDecoder, write::GzEncoder};
+
+use super::lock::collect_files;
+
+/// Directory holding the skills inside a bundle
+const BUNDLE_SKILLS_DIR: &str = "skills";
+
+/// A skill read from a bundle
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BundledSkill {
+    pub name: String,
+    /// Files of the skill, relative to its directory
+    pub files: BTreeMap<PathBuf, BundledFile>,
+}
+
+/// A file of a bundled skill
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BundledFile {
+    /// Unix permission bits
+    pub mode: u32,
+    pub content: Vec<u8>,
+}
+
+/// Write the skill files at `paths` to a gzipped tarball at `output`.
+///
+/// Returns the names of the packed skills.
+pub fn pack_skills(paths: &[PathBuf], output: &Path) -> Result<Vec<String>> {
+    let file = std::fs::File::create(output)
+        .with_context(|| format!("Failed to create {}", output.display()))?;
+    let mut tar = tar::Builder::new(GzEncoder::new(file, Compression::default()));
+    tar.follow_symlinks(true);
+
+    let mut names = Vec::new();
+    for path in paths {
+        let name = bundle_skill_name(path);
+        if names.contains(&name) {
+            bail!("Skill '{}' is listed twice", name);
+        }
+        let skill_dir = Path::new(BUNDLE_SKILLS_DIR).join(&name);
+        match path.parent() {
+            Some(dir) if path.file_name().is_some_and(|n| n == "SKILL.md") => {
+                let mut files = Vec::new();
+                collect_files(dir, &mut files)?;
+                files.sort();
+                for file in files {
+                    let relative = file.strip_prefix(dir).unwrap_or(&file);
+                    tar.append_path_with_name(&file, skill_dir.join(relative))?;
+                }
+            }
+            _ => tar.append_path_with_name(path, skill_dir.join("SKILL.md"))?,
+        }
+        names.push(name);
+    }
+
+    tar.into_inner()?.finish()?;
+    Ok(names)
+}
+
+/// Read and validate the skills in the bundle at `path`
+pub fn read_bundle(path: &Path) -> Result<Vec<BundledSkill>> {
+    let file =
+        std::fs::File::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+    let mut archive = tar::Archive::new(GzDecoder::new(file));
+
+    let mut skills: BTreeMap<String, BTreeMap<PathBuf, BundledFile>> = BTreeMap::new();
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        let entry_path = entry.path()?.to_path_buf();
+        let kind = entry.header().entry_type();
+        if kind.is_dir() {
+            continue;
+        }
+        if !kind.is_file() {
+            bail!(
+                "Bundle entry {} is not a regular file",
+                entry_path.display()
+            );
+        }
+
+        let components: Vec<&str> = entry_path
+            .components()
+            .map(|c| match c {
+                Component::Normal(part) => part.to_str(),
+                _ => None,
+            })
+            .collect::<Option<_>>()
+            .with_context(|| format!("Unsafe path {} in bundle", entry_path.display()))?;
+        let [BUNDLE_SKILLS_DIR, name, rest @ ..] = components.as_slice() else {
+            bail!(
+                "Unexpected entry {} in bundle (expected skills/<name>/...)",
+                entry_path.display()
+            );
+        };
+        if rest.is_empty() {
+            bail!(
+                "Unexpected entry {} in bundle (expected skills/<name>/...)",
+                entry_path.display()
+            );
+        }
+        if !is_valid_skill_name(name) {
+            bail!("Invalid skill name '{}' in bundle", name);
+        }
+
+        let mode = entry.header().mode()? & 0o777;
+        let mut content = Vec::new();
+        entry.read_to_end(&mut content)?;
+        skills
+            .entry(name.to_string())
+            .or_default()
+            .insert(rest.iter().collect(), BundledFile { mode, content });
+    }
+
+    if skills.is_empty() {
+        bail!("Bundle {} contains no skills", path.display());
+    }
+    for (name, files) in &skills {
+        if !files.contains_key(Path::new("SKILL.md")) {
+            bail!("Skill '{}' in bundle has no SKILL.md", name);
+        }
+    }
+    Ok(skills
+        .into_iter()
+        .map(|(name, files)| BundledSkill { name, files })
+        .collect())
+}
+
+/// Install `skills` into `skills_dir` as `<name>/SKILL.md` directories.
+///
+/// Fails without writing anything if a skill already exists there, unless
+/// `overwrite` is set, in which case the existing skill is replaced.
+pub fn install_bundle(skills: &[BundledSkill], skills_dir: &Path, overwrite: bool) -> Result<()> {
+    let existing: Vec<&str> = skills
+        .iter()
+        .filter(|s| {
+            skills_dir.join(&s.name).exists() || skills_dir.join(format!("{}.md", s.name)).exists()
+        })
+        .map(|s| s.name.as_str())
+        .collect();
+    if !existing.is_empty() && !overwrite {
+        bail!(
+            "Already installed in {}: {} (use --force to replace)",
+            skills_dir.display(),
+            existing.join(", ")
+        );
+    }
+
+    for skill in skills {
+        let dir = skills_dir.join(&skill.name);
+        if existing.contains(&skill.name.as_str()) {
+            std::fs::remove_dir_all(&dir).ok();
+            std::fs::remove_file(skills_dir.join(format!("{}.md", skill.name))).ok();
+        }
+        for (relative, file) in &skill.files {
+            let target = dir.join(relative);
+            if let Some(parent) = target.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(&target, &file.content)
+                .with_context(|| format!("Failed to write {}", target.display()))?;
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::PermissionsExt;
+                std::fs::set_permissions(&target, std::fs::Permissions::from_mode(file.mode))?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Name a skill file is packed under
+fn bundle_skill_name(path: &Path) -> String {
+    let name = if path.file_name().is_some_and(|n| n == "SKILL.md") {
+        path.parent().and_then(|p| p.file_name())
+    } else {
+        path.file_stem()
+    };
+    name.map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+/// Whether `name` is usable as a skill directory name
+fn is_valid_skill_name(name: &str) -> bool {
+    !name.is_empty()
+        && !name.starts_with('.')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pack_and_install_bundle() {
+        let dir = std::env::temp_dir().join("axel-test-skill-bundle");
+        std::fs::remove_dir_all(&dir).ok();
+        std::fs::create_dir_all(dir.join("src/review/scripts")).unwrap();
+        std::fs::write(dir.join("src/review/SKILL.md"), "# Review\n").unwrap();
+        std::fs::write(dir.join("src/review/scripts/run.sh"), "echo 1\n").unwrap();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let script = dir.join("src/review/scripts/run.sh");
+            std::fs::set_permissions(script, std::fs::Permissions::from_mode(0o755)).unwrap();
+        }
+        std::fs::write(dir.join("src/docs.md"), "# Docs\n").unwrap();
+
+        let bundle = dir.join("bundle.tar.gz");
+        let names = pack_skills(
+            &[dir.join("src/review/SKILL.md"), dir.join("src/docs.md")],
+            &bundle,
+        )
+        .unwrap();
+        assert_eq!(names, vec!["review", "docs"]);
+
+        let skills = read_bundle(&bundle).unwrap();
+        assert_eq!(skills.len(), 2);
+        assert_eq!(skills[0].name, "docs");
+        assert_eq!(
+            skills[1].files.keys().collect::<Vec<_>>(),
+            vec![Path::new("SKILL.md"), Path::new("scripts/run.sh")]
+        );
+
+        let target = dir.join("installed");
+        install_bundle(&skills, &target, false).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(target.join("docs/SKILL.md")).unwrap(),
+            "# Docs\n"
+        );
+        assert!(target.join("review/scripts/run.sh").exists());
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = |p: &Path| std::fs::metadata(p).unwrap().permissions().mode() & 0o777;
+            assert_eq!(mode(&target.join("review/scripts/run.sh")), 0o755);
+        }
+
+        // Existing skills are refused unless overwriting
+        let err = install_bundle(&skills, &target, false).unwrap_err();
+        assert!(err.to_string().contains("docs, review"), "{}", err);
+        install_bundle(&skills, &target, true).unwrap();
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_read_bundle_rejects_unsafe_entries() {
+        let dir = std::env::temp_dir().join("axel-test-skill-bundle-unsafe");
+        std::fs::remove_dir_all(&dir).ok();
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let write_bundle = |name: &str, entry: &str| {
+            let path = dir.join(name);
+            let file = std::fs::File::create(&path).unwrap();
+            let mut tar = tar::Builder::new(GzEncoder::new(file, Compression::default()));
+            let content = b"# Skill\n";
+            let mut header = tar::Header::new_gnu();
+            header.set_size(content.len() as u64);
+            header.set_mode(0o644);
+            // `set_path` refuses `..`, so write the raw name
+            header.as_old_mut().name[..entry.len()].copy_from_slice(entry.as_bytes());
+            header.set_cksum();
+            tar.append(&header, &content[..]).unwrap();
+            tar.into_inner().unwrap().finish().unwrap();
+            path
+        };
+
+        let traversal = write_bundle("a.tar.gz", "skills/../../etc/SKILL.md");
+        assert!(read_bundle(&traversal).is_err());
+        let outside = write_bundle("b.tar.gz", "README.md");
+        assert!(read_bundle(&outside).is_err());
+        let no_skill_md = write_bundle("c.tar.gz", "skills/review/notes.md");
+        let err = read_bundle(&no_skill_md).unwrap_err();
+        assert!(err.to_string().contains("has no SKILL.md"), "{}", err);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -0,0 +1,270 @@
+//! Conditional sections with `when:`
+//!
+//! Panes, grids and skill directories can carry a `when:` condition, so one
+//! manifest serves several machines. Entries whose condition doesn't hold
+//! are dropped before the manifest is parsed, which lets two panes share a
+//! name as long as only one of them applies:
+//!
+//! ```yaml
+//! skills:
+//!   - path: ~/Library/axel/skills
+//!     when: { os: macos }
+//! layouts:
+//!   panes:
+//!     - name: watch
+//!       command: fswatch -o src | xargs -n1 cargo check
+//!       when: { os: macos }
+//!     - name: watch
+//!       command: inotifywait -mr src -e modify | xargs -n1 cargo check
+//!       when: { os: linux }
+//!   grids:
+//!     gpu:
+//!       when:
+//!         hostname: [rig-01, rig-02]   # short or full host name
+//!         env: CUDA_HOME               # set and non-empty; or { VAR: value }
+//!       ...
+//! ```
+//!
+//! `os` is `linux`, `macos` or `windows`, or a list of them. Every key of a
+//! condition must hold. Conditions are applied to each manifest before it is
+//! merged with its `extends:` bases, and before panes extend one another.
+
+use std::cell::OnceCell;
+
+use anyhow::{Result, bail};
+use serde_yaml::Value;
+
+/// Key holding an entry's condition
+const WHEN_KEY: &str = "when";
+
+/// What conditions are checked against
+struct Host {
+    os: &'static str,
+    /// Looked up the first time a condition names a host
+    hostname: OnceCell<Option<String>>,
+}
+
+impl Host {
+    fn current() -> Self {
+        Self {
+            os: std::env::consts::OS,
+            hostname: OnceCell::new(),
+        }
+    }
+
+    fn hostname(&self) -> Option<&str> {
+        self.hostname
+            .get_or_init(|| {
+                let output = std::process::Command::new("hostname").output().ok()?;
+                let name = String::from_utf8_lossy(&output.stdout).trim().to_string();
+                (output.status.success() && !name.is_empty()).then_some(name)
+            })
+            .as_deref()
+    }
+}
+
+/// Drop the panes, grids and skill directories of `value` whose `when:`
+/// doesn't hold on this machine, and strip `when:` from the others.
+///
+/// Returns whether any entry had a condition.
+pub(super) fn apply_conditions(value: &mut Value) -> Result<bool> {
+    filter(value, &Host::current())
+}
+
+fn filter(value: &mut Value, host: &Host) -> Result<bool> {
+    let mut found = false;
+
+    if let Some(Value::Sequence(skills)) = value.get_mut("skills") {
+        found |= retain_entries(skills, host)?;
+    }
+
+    let Some(layouts) = value.get_mut("layouts") else {
+        return Ok(found);
+    };
+    if let Some(Value::Sequence(panes)) = layouts.get_mut("panes") {
+        found |= retain_entries(panes, host)?;
+    }
+    if let Some(Value::Mapping(grids)) = layouts.get_mut("grids") {
+        let mut keep = Vec::with_capacity(grids.len());
+        for (_, grid) in grids.iter_mut() {
+            keep.push(take_condition(grid, host)?);
+        }
+        found |= keep.iter().any(Option::is_some);
+        let mut keep = keep.into_iter();
+        grids.retain(|_, _| keep.next().flatten() != Some(false));
+    }
+
+    Ok(found)
+}
+
+/// Keep the entries of `entries` whose condition holds
+fn retain_entries(entries: &mut Vec<Value>, host: &Host) -> Result<bool> {
+    let mut found = false;
+    let mut kept = Vec::with_capacity(entries.len());
+    for mut entry in entries.drain(..) {
+        match take_condition(&mut entry, host)? {
+            Some(holds) => {
+                found = true;
+                if holds {
+                    kept.push(entry);
+                }
+            }
+            None => kept.push(entry),
+        }
+    }
+    *entries = kept;
+    Ok(found)
+}
+
+/// Remove `entry`'s `when:` and check it; `None` when it has none
+fn take_condition(entry: &mut Value, host: &Host) -> Result<Option<bool>> {
+    let Some(when) = entry
+        .as_mapping_mut()
+        .and_then(|entry| entry.remove(WHEN_KEY))
+    else {
+        return Ok(None);
+    };
+    holds(&when, host).map(Some)
+}
+
+/// Whether every key of the condition `when` holds on `host`
+fn holds(when: &Value, host: &Host) -> Result<bool> {
+    let Value::Mapping(when) = when else {
+        bail!("`when` must be a mapping of os, hostname and env conditions");
+    };
+    for (key, value) in when {
+        let holds = match key.as_str() {
+            Some("os") => names(value, "os")?.iter().any(|os| os_name(os) == host.os),
+            Some("hostname") => {
+                let wanted = names(value, "hostname")?;
+                host.hostname().is_some_and(|hostname| {
+                    let short = hostname.split('.').next().unwrap_or(hostname);
+                    wanted.iter().any(|name| {
+                        name.eq_ignore_ascii_case(hostname) || name.eq_ignore_ascii_case(short)
+                    })
+                })
+            }
+            Some("env") => env_holds(value)?,
+            _ => bail!(
+                "Unknown `when` condition '{}' (expected os, hostname or env)",
+                key.as_str().unwrap_or_default()
+            ),
+        };
+        if !holds {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+/// A name or a list of names
+fn names<'a>(value: &'a Value, key: &str) -> Result<Vec<&'a str>> {
+    match value {
+        Value::String(name) => Ok(vec![name]),
+        Value::Sequence(names) => names
+            .iter()
+            .map(|name| match name {
+                Value::String(name) => Ok(name.as_str()),
+                _ => bail!("`when.{key}` entries must be strings"),
+            })
+            .collect(),
+        _ => bail!("`when.{key}` must be a name or a list of names"),
+    }
+}
+
+/// `std::env::consts::OS` spelling of `os`
+fn os_name(os: &str) -> &str {
+    match os {
+        "darwin" | "macOS" | "mac" => "macos",
+        other => other,
+    }
+}
+
+/// `env: VAR` and `env: [A, B]` need the variables set and non-empty;
+/// `env: { VAR: value }` needs them set to these values
+fn env_holds(value: &Value) -> Result<bool> {
+    let var = |name: &str| std::env::var(name).ok();
+    match value {
+        Value::Mapping(vars) => vars.iter().try_fold(true, |holds, (name, expected)| {
+            let (Value::String(name), Value::String(expected)) = (name, expected) else {
+                bail!("`when.env` values must be strings");
+            };
+            Ok(holds && var(name).as_deref() == Some(expected.as_str()))
+        }),
+        _ => Ok(names(value, "env")?
+            .iter()
+            .all(|name| var(name).is_some_and(|value| !value.is_empty()))),
+    }
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn host(os: &'static str, hostname: &str) -> Host {
+        Host {
+            os,
+            hostname: OnceCell::from(Some(hostname.to_string())),
+        }
+    }
+
+    #[test]
+    fn test_when_conditions() {
+        let yaml = r#"
+skills:
+  - path: ./skills
+  - path: ~/Library/axel/skills
+    when: { os: macos }
+layouts:
+  panes:
+    - name: watch
+      command: fswatch
+      when: { os: [darwin] }
+    - name: watch
+      command: inotifywait
+      when: { os: linux }
+    - type: claude
+  grids:
+    default:
+      watch: { col: 0, row: 0 }
+    gpu:
+      when: { hostname: rig-01, env: PATH }
+      watch: { col: 0, row: 0 }
+    ci:
+      when: { env: { PATH: not-the-path } }
+      watch: { col: 0, row: 0 }
+"#;
+
+        let mut value: Value = serde_yaml::from_str(yaml).unwrap();
+        assert!(filter(&mut value, &host("linux", "rig-01.lab.example")).unwrap());
+        assert_eq!(value["skills"].as_sequence().unwrap().len(), 1);
+        let panes = value["layouts"]["panes"].as_sequence().unwrap();
+        assert_eq!(panes.len(), 2);
+        assert_eq!(panes[0]["command"].as_str(), Some("inotifywait"));
+        assert!(panes[0].get("when").is_none());
+        let grids = value["layouts"]["grids"].as_mapping().unwrap();
+        let names: Vec<&str> = grids.keys().filter_map(Value::as_str).collect();
+        assert_eq!(names, ["default", "gpu"]);
+        assert!(grids["gpu"].get("when").is_none());
+
+        let mut value: Value = serde_yaml::from_str(yaml).unwrap();
+        filter(&mut value, &host("macos", "laptop")).unwrap();
+        assert_eq!(value["skills"].as_sequence().unwrap().len(), 2);
+        let panes = value["layouts"]["panes"].as_sequence().unwrap();
+        assert_eq!(panes[0]["command"].as_str(), Some("fswatch"));
+        assert_eq!(value["layouts"]["grids"].as_mapping().unwrap().len(), 1);
+
+        // Manifests without conditions are left alone
+        let mut value: Value = serde_yaml::from_str("layouts:\n  panes: []\n").unwrap();
+        assert!(!filter(&mut value, &host("linux", "rig-01")).unwrap());
+
+        let mut value: Value =
+            serde_yaml::from_str("skills:\n  - path: x\n    when: { arch: arm }\n").unwrap();
+        let err = filter(&mut value, &host("linux", "rig-01")).unwrap_err();
+        assert!(err.to_string().contains("Unknown `when` condition 'arch'"));
+    }
+}
@@ -0,0 +1,134 @@
+//! Context files for AI panes
+//!
+//! `context_files:` on an AI pane lists files the agent should start with.
+//! Claude Code and OpenCode resolve `@path` references themselves, so the
+//! files are referenced in the prompt. Other drivers have no file syntax for
+//! the initial prompt, so the file contents are appended to it instead.
+
+use std::path::{Path, PathBuf};
+
+use colored::Colorize;
+
+use super::{AiPaneConfig, expand_path};
+
+impl AiPaneConfig {
+    /// Resolve `context_files` against the workspace directory
+    pub fn context_paths(&self, workspace_dir: Option<&Path>) -> Vec<PathBuf> {
+        self.context_files
+            .iter()
+            .map(|file| {
+                if file.starts_with('/') || file.starts_with('~') {
+                    PathBuf::from(expand_path(file))
+                } else if let Some(base) = workspace_dir {
+                    base.join(file)
+                } else {
+                    PathBuf::from(file)
+                }
+            })
+            .collect()
+    }
+
+    /// Combine the initial prompt with this pane's context files.
+    ///
+    /// Returns `prompt` unchanged when no context files are configured.
+    pub fn prompt_with_context(
+        &self,
+        prompt: Option<&str>,
+        workspace_dir: Option<&Path>,
+    ) -> Option<String> {
+        if self.context_files.is_empty() {
+            return prompt.map(str::to_string);
+        }
+        let paths = self.context_paths(workspace_dir);
+
+        let context = match self.pane_type.as_str() {
+            "claude" | "opencode" => paths
+                .iter()
+                .map(|path| format!("@{}", path.display()))
+                .collect::<Vec<_>>()
+                .join(" "),
+            _ => self
+                .context_files
+                .iter()
+                .zip(&paths)
+                .filter_map(|(name, path)| match std::fs::read_to_string(path) {
+                    Ok(content) => Some(format!(
+                        "<file path=\"{}\">\n{}\n</file>",
+                        name,
+                        content.trim_end()
+                    )),
+                    Err(e) => {
+                        eprintln!(
+                            "{} Context file {} not readable: {}",
+                            "!".yellow(),
+                            path.display(),
+                            e
+                        );
+                        None
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join("\n\n"),
+        };
+
+        match prompt {
+            Some(prompt) if !context.is_empty() => Some(format!("{}\n\n{}", context, prompt)),
+            Some(prompt) => Some(prompt.to_string()),
+            None if !context.is_empty() => Some(context),
+            None => None,
+        }
+    }
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pane(yaml: &str) -> AiPaneConfig {
+        serde_yaml::from_str(yaml).unwrap()
+    }
+
+    #[test]
+    fn test_claude_references_context_files() {
+        let config = pane("type: claude\ncontext_files: [docs/api.md, /etc/notes.md]\n");
+        let dir = Path::new("/code/app");
+
+        assert_eq!(
+            config.prompt_with_context(Some("Review the API"), Some(dir)),
+            Some("@/code/app/docs/api.md @/etc/notes.md\n\nReview the API".to_string())
+        );
+        assert_eq!(
+            config.prompt_with_context(None, Some(dir)),
+            Some("@/code/app/docs/api.md @/etc/notes.md".to_string())
+        );
+    }
+
+    #[test]
+    fn test_codex_inlines_context_files() {
+        let dir = std::env::temp_dir().join("axel-test-context-files");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("plan.md"), "1. Ship it\n").unwrap();
+
+        let config = pane("type: codex\ncontext_files: [plan.md, missing.md]\n");
+        assert_eq!(
+            config.prompt_with_context(Some("Go"), Some(&dir)),
+            Some("<file path=\"plan.md\">\n1. Ship it\n</file>\n\nGo".to_string())
+        );
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_prompt_unchanged_without_context_files() {
+        let config = pane("type: claude\n");
+        assert_eq!(
+            config.prompt_with_context(Some("Hi"), None),
+            Some("Hi".to_string())
+        );
+        assert_eq!(config.prompt_with_context(None, None), None);
+    }
+}
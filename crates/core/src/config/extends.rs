@@ -0,0 +1,509 @@
+//! Manifest layering with `extends:`
+//!
+//! A manifest can build on one or more base manifests:
+//!
+//! ```yaml
+//! extends: ~/.config/axel/base.AXEL.md   # or a list, later entries win
+//! ```
+//!
+//! Bases are merged at the YAML level before the manifest is parsed, with
+//! the extending manifest winning:
+//!
+//! - `layouts.panes` merge by pane name (or type); a local pane replaces the
+//!   base pane with the same name, new panes are appended
+//! - `layouts.grids` merge by grid name; a local grid replaces the base grid
+//! - `skills` lists local directories first, then base directories
+//! - any other key is taken from the local manifest when present
+//!
+//! Relative paths in a base manifest are rebased onto the base's directory,
+//! so they keep pointing at the same place. Bases can extend other bases;
+//! a chain that revisits a manifest is an error.
+//!
+//! Panes can extend other panes of the (merged) manifest by name, so
+//! variants don't repeat their skills, tools and args:
+//!
+//! ```yaml
+//! layouts:
+//!   panes:
+//!     - type: claude
+//!       skills: [reviewer]
+//!       allowed_tools: [Read, Grep]
+//!     - name: claude-opus
+//!       extends: claude
+//!       model: opus
+//! ```
+//!
+//! The extending pane takes every key of the pane it extends, `type`
+//! included, except `name`, which it must set itself; its own keys replace
+//! the inherited ones whole (lists are not concatenated). Panes can extend
+//! panes that extend others; a chain that comes back to a pane is an error.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, bail};
+use serde_yaml::{Mapping, Value};
+
+use super::{conditions::apply_conditions, expand_path, manifest::manifest_yaml};
+
+/// Key holding the manifests to extend
+const EXTENDS_KEY: &str = "extends";
+
+/// Parse the frontmatter of `path`, merged over everything it `extends:`.
+///
+/// Entries whose `when:` doesn't hold are dropped from each manifest first.
+/// Returns `None` when there is nothing to merge or drop, so the caller can
+/// deserialize the original text and keep YAML error locations.
+pub(super) fn extended_frontmatter(path: &Path, yaml: &str) -> Result<Option<Value>> {
+    let mut value: Value = serde_yaml::from_str(yaml)?;
+    let conditional = apply_conditions(&mut value)?;
+    if value.get(EXTENDS_KEY).is_some() {
+        let mut chain = vec![canonical(path)];
+        value = resolve(path, value, &mut chain)?;
+    } else if !conditional && !has_pane_templates(&value) {
+        return Ok(None);
+    }
+    resolve_pane_templates(&mut value)?;
+    Ok(Some(value))
+}
+
+/// Merge `value` (the frontmatter of `path`) over its bases, recursively
+fn resolve(path: &Path, mut value: Value, chain: &mut Vec<PathBuf>) -> Result<Value> {
+    let bases = match value.as_mapping_mut().and_then(|m| m.remove(EXTENDS_KEY)) {
+        None | Some(Value::Null) => return Ok(value),
+        Some(Value::String(base)) => vec![base],
+        Some(Value::Sequence(bases)) => bases
+            .into_iter()
+            .map(|b| match b {
+                Value::String(s) => Ok(s),
+                _ => bail!("`extends` entries must be file paths"),
+            })
+            .collect::<Result<_>>()?,
+        Some(_) => bail!("`extends` must be a file path or a list of file paths"),
+    };
+
+    let dir = path.parent().unwrap_or(Path::new("."));
+    let mut merged = Value::Mapping(Mapping::new());
+    for base in bases {
+        let base_path = resolve_path(&base, dir);
+        let key = canonical(&base_path);
+        if chain.contains(&key) {
+            chain.push(key);
+            let names: Vec<_> = chain.iter().map(|p| p.display().to_string()).collect();
+            bail!("Manifest extends cycle: {}", names.join(" → "));
+        }
+        chain.push(key);
+
+        let content = std::fs::read_to_string(&base_path)
+            .with_context(|| format!("Failed to read base manifest {}", base_path.display()))?;
+        let yaml = manifest_yaml(&base_path, &content)
+            .with_context(|| format!("Invalid base manifest {}", base_path.display()))?;
+        let mut base_value: Value = serde_yaml::from_str(yaml)
+            .with_context(|| format!("Invalid base manifest {}", base_path.display()))?;
+        apply_conditions(&mut base_value)
+            .with_context(|| format!("Invalid base manifest {}", base_path.display()))?;
+        let mut base_value = resolve(&base_path, base_value, chain)?;
+        rebase_paths(
+            &mut base_value,
+            base_path.parent().unwrap_or(Path::new(".")),
+        );
+        merged = merge(merged, base_value);
+
+        chain.pop();
+    }
+
+    Ok(merge(merged, value))
+}
+
+/// Merge `local` over `base` using the rules in the module docs
+fn merge(base: Value, local: Value) -> Value {
+    let (mut base, local) = match (base, local) {
+        (Value::Mapping(base), Value::Mapping(local)) => (base, local),
+        (_, local) => return local,
+    };
+
+    for (key, local_value) in local {
+        let merged = match (key.as_str(), base.remove(&key)) {
+            (Some("layouts"), Some(Value::Mapping(mut base_layouts))) => {
+                let Value::Mapping(local_layouts) = local_value else {
+                    base.insert(key, local_value);
+                    continue;
+                };
+                for (layout_key, layout_value) in local_layouts {
+                    let merged = match (layout_key.as_str(), base_layouts.remove(&layout_key)) {
+                        (Some("panes"), Some(Value::Sequence(base_panes))) => {
+                            merge_panes(base_panes, layout_value)
+                        }
+                        (Some("grids"), Some(Value::Mapping(mut base_grids))) => {
+                            if let Value::Mapping(local_grids) = layout_value {
+                                base_grids.extend(local_grids);
+                                Value::Mapping(base_grids)
+                            } else {
+                                layout_value
+                            }
+                        }
+                        _ => layout_value,
+                    };
+                    base_layouts.insert(layout_key, merged);
+                }
+                Value::Mapping(base_layouts)
+            }
+            (Some("skills"), Some(Value::Sequence(base_skills))) => match local_value {
+                Value::Sequence(mut skills) => {
+                    for skill in base_skills {
+                        if !skills.iter().any(|s| s.get("path") == skill.get("path")) {
+                            skills.push(skill);
+                        }
+                    }
+                    Value::Sequence(skills)
+                }
+                other => other,
+            },
+            _ => local_value,
+        };
+        base.insert(key, merged);
+    }
+
+    Value::Mapping(base)
+}
+
+/// Merge pane lists by pane identity, keeping base order
+fn merge_panes(mut base: Vec<Value>, local: Value) -> Value {
+    let Value::Sequence(local) = local else {
+        return local;
+    };
+    for pane in local {
+        let id = pane_id(&pane);
+        match base.iter_mut().find(|p| id.is_some() && pane_id(p) == id) {
+            Some(existing) => *existing = pane,
+            None => base.push(pane),
+        }
+    }
+    Value::Sequence(base)
+}
+
+/// The name a pane is referenced by in grids: `name`, falling back to `type`
+fn pane_id(pane: &Value) -> Option<&str> {
+    pane.get("name")
+        .or_else(|| pane.get("type"))
+        .and_then(Value::as_str)
+}
+
+// =============================================================================
+// Pane Templates
+// =============================================================================
+
+/// The manifest's panes, if it has any
+fn panes_mut(value: &mut Value) -> Option<&mut Vec<Value>> {
+    match value.get_mut("layouts")?.get_mut("panes")? {
+        Value::Sequence(panes) => Some(panes),
+        _ => None,
+    }
+}
+
+fn has_pane_templates(value: &Value) -> bool {
+    value
+        .get("layouts")
+        .and_then(|layouts| layouts.get("panes"))
+        .and_then(Value::as_sequence)
+        .is_some_and(|panes| panes.iter().any(|p| p.get(EXTENDS_KEY).is_some()))
+}
+
+/// Replace every pane that `extends:` another with the merged pane
+fn resolve_pane_templates(value: &mut Value) -> Result<()> {
+    let Some(panes) = panes_mut(value) else {
+        return Ok(());
+    };
+    let resolved = (0..panes.len())
+        .map(|index| resolve_pane(panes, index, &mut Vec::new()))
+        .collect::<Result<Vec<_>>>()?;
+    *panes = resolved;
+    Ok(())
+}
+
+/// Merge pane `index` over the chain of panes it extends
+fn resolve_pane(panes: &[Value], index: usize, chain: &mut Vec<String>) -> Result<Value> {
+    let pane = &panes[index];
+    let Some(template) = pane.get(EXTENDS_KEY) else {
+        return Ok(pane.clone());
+    };
+    let Some(template) = template.as_str() else {
+        bail!("A pane's `extends` must be the name of another pane");
+    };
+    let Some(name) = pane.get("name").and_then(Value::as_str) else {
+        bail!("Pane extending '{}' needs its own name", template);
+    };
+
+    chain.push(name.to_string());
+    if chain.iter().any(|n| n == template) {
+        chain.push(template.to_string());
+        bail!("Pane extends cycle: {}", chain.join(" → "));
+    }
+    let Some(template_index) = panes.iter().position(|p| pane_id(p) == Some(template)) else {
+        bail!("Pane '{}' extends unknown pane '{}'", name, template);
+    };
+    let Value::Mapping(mut merged) = resolve_pane(panes, template_index, chain)? else {
+        bail!(
+            "Pane '{}' extends '{}', which is not a pane",
+            name,
+            template
+        );
+    };
+    chain.pop();
+
+    merged.remove("name");
+    if let Value::Mapping(own) = pane {
+        for (key, value) in own {
+            if key.as_str() != Some(EXTENDS_KEY) {
+                merged.insert(key.clone(), value.clone());
+            }
+        }
+    }
+    Ok(Value::Mapping(merged))
+}
+
+// =============================================================================
+// Paths
+// =============================================================================
+
+/// Make relative skill, pane, grid, prompt and context file paths absolute
+/// against `dir`
+fn rebase_paths(value: &mut Value, dir: &Path) {
+    let rebase = |v: &mut Value| {
+        if let Value::String(path) = v
+            && is_relative(path)
+        {
+            *path = dir.join(&*path).to_string_lossy().into_owned();
+        }
+    };
+
+    if let Some(Value::Sequence(skills)) = value.get_mut("skills") {
+        for skill in skills {
+            if let Some(path) = skill.get_mut("path") {
+                rebase(path);
+            }
+        }
+    }
+
+    if let Some(panes) = panes_mut(value) {
+        for pane in panes {
+            if let Some(path) = pane.get_mut("path") {
+                rebase(path);
+            }
+            if let Some(file) = pane.get_mut("prompt_file") {
+                rebase(file);
+            }
+            if let Some(Value::Sequence(files)) = pane.get_mut("context_files") {
+                files.iter_mut().for_each(rebase);
+            }
+        }
+    }
+
+    if let Some(Value::Mapping(grids)) = value
+        .get_mut("layouts")
+        .and_then(|layouts| layouts.get_mut("grids"))
+    {
+        for grid in grids.values_mut() {
+            if let Some(path) = grid.get_mut("path") {
+                rebase(path);
+            }
+            if let Value::Mapping(cells) = grid {
+                for cell in cells.values_mut() {
+                    if let Some(path) = cell.get_mut("path") {
+                        rebase(path);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Relative paths are rebased; absolute, `~` and `${VAR}` paths are not
+fn is_relative(path: &str) -> bool {
+    !(path.starts_with('/') || path.starts_with('~') || path.starts_with('$'))
+}
+
+/// Resolve an `extends` entry: `~` expanded, relative to the manifest's directory
+fn resolve_path(base: &str, dir: &Path) -> PathBuf {
+    if base.starts_with('/') || base.starts_with('~') {
+        PathBuf::from(expand_path(base))
+    } else {
+        dir.join(base)
+    }
+}
+
+fn canonical(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use crate::config::{PaneConfig, load_config};
+
+    #[test]
+    fn test_extends_merges_base_manifest() {
+        let dir = std::env::temp_dir().join("axel-test-extends");
+        std::fs::remove_dir_all(&dir).ok();
+        std::fs::create_dir_all(dir.join("base/skills")).unwrap();
+        std::fs::create_dir_all(dir.join("app")).unwrap();
+        std::fs::write(
+            dir.join("base/base.AXEL.md"),
+            r#"---
+workspace: base
+skills:
+  - path: ./skills
+layouts:
+  panes:
+    - type: claude
+      model: sonnet
+    - type: shell
+      name: logs
+      command: tail -f log
+  grids:
+    default:
+      claude:
+        col: 0
+      logs:
+        col: 1
+    review:
+      claude:
+        col: 0
+---
+"#,
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join("app/AXEL.md"),
+            r#"---
+workspace: app
+extends: ../base/base.AXEL.md
+skills:
+  - path: ./skills
+layouts:
+  panes:
+    - type: claude
+      model: opus
+    - type: codex
+  grids:
+    default:
+      claude:
+        col: 0
+      codex:
+        col: 1
+---
+"#,
+        )
+        .unwrap();
+
+        let config = load_config(&dir.join("app/AXEL.md")).unwrap();
+        assert_eq!(config.workspace, "app");
+
+        let skill_paths: Vec<_> = config.skills.iter().map(|s| s.path.as_str()).collect();
+        let base_skills = dir.join("app/../base/./skills");
+        assert_eq!(
+            skill_paths,
+            vec!["./skills", &*base_skills.to_string_lossy()]
+        );
+
+        let panes: Vec<_> = config.layouts.panes.iter().map(|p| p.pane_type()).collect();
+        assert_eq!(panes, vec!["claude", "logs", "codex"]);
+        let PaneConfig::Claude(claude) = &config.layouts.panes[0] else {
+            panic!("expected claude pane");
+        };
+        assert_eq!(claude.model.as_deref(), Some("opus"));
+
+        let cells: Vec<_> = config.layouts.grids["default"].cells.keys().collect();
+        assert_eq!(cells, vec!["claude", "codex"]);
+        assert!(config.layouts.grids.contains_key("review"));
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_extends_cycle_is_an_error() {
+        let dir = std::env::temp_dir().join("axel-test-extends-cycle");
+        std::fs::remove_dir_all(&dir).ok();
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join("a.md"),
+            "---\nworkspace: a\nextends: b.md\nlayouts: {}\n---\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join("b.md"),
+            "---\nworkspace: b\nextends: [a.md]\nlayouts: {}\n---\n",
+        )
+        .unwrap();
+
+        let err = format!("{:#}", load_config(&dir.join("a.md")).unwrap_err());
+        assert!(err.contains("Manifest extends cycle"), "{}", err);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_pane_extends_pane() {
+        let dir = std::env::temp_dir().join("axel-test-pane-extends");
+        std::fs::remove_dir_all(&dir).ok();
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("AXEL.md");
+        std::fs::write(
+            &path,
+            r#"---
+workspace: test
+layouts:
+  panes:
+    - type: claude
+      model: sonnet
+      skills: [reviewer]
+      allowed_tools: [Read]
+    - name: claude-opus
+      extends: claude
+      model: opus
+    - name: claude-opus-strict
+      extends: claude-opus
+      allowed_tools: [Grep]
+  grids:
+    default:
+      claude-opus-strict:
+        col: 0
+---
+"#,
+        )
+        .unwrap();
+
+        let config = load_config(&path).unwrap();
+        let panes: Vec<_> = config.layouts.panes.iter().map(|p| p.pane_type()).collect();
+        assert_eq!(panes, vec!["claude", "claude-opus", "claude-opus-strict"]);
+        let PaneConfig::Claude(strict) = &config.layouts.panes[2] else {
+            panic!("expected claude pane");
+        };
+        assert_eq!(strict.model.as_deref(), Some("opus"));
+        assert_eq!(strict.skills, vec!["reviewer"]);
+        assert_eq!(strict.allowed_tools, vec!["Grep"]);
+
+        let resolved = config.resolve_panes(None);
+        assert_eq!(resolved[0].name, "claude-opus-strict");
+
+        std::fs::write(
+            &path,
+            "---\nworkspace: test\nlayouts:\n  panes:\n    - name: a\n      extends: b\n    - name: b\n      type: claude\n      extends: a\n---\n",
+        )
+        .unwrap();
+        let err = format!("{:#}", load_config(&path).unwrap_err());
+        assert!(err.contains("Pane extends cycle: a → b → a"), "{}", err);
+
+        std::fs::write(
+            &path,
+            "---\nworkspace: test\nlayouts:\n  panes:\n    - name: a\n      extends: ghost\n---\n",
+        )
+        .unwrap();
+        let err = format!("{:#}", load_config(&path).unwrap_err());
+        assert!(err.contains("extends unknown pane 'ghost'"), "{}", err);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -0,0 +1,276 @@
+//! Skill include resolution
+//!
+//! A composite skill can list other skills in its `includes:` frontmatter.
+//! This module builds the include tree for display (`axel skill deps`),
+//! detecting missing skills and cycles along the way.
+
+use std::{collections::HashSet, path::PathBuf};
+
+use colored::Colorize;
+
+use super::{Skill, WorkspaceConfig};
+
+/// How a node in the include tree was resolved
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SkillNodeStatus {
+    /// Skill was found; its includes are expanded as children
+    Found,
+    /// No skill with this name exists in the skill directories
+    Missing,
+    /// Skill is already an ancestor of this node; not expanded again
+    Cycle,
+}
+
+/// A skill and the skills it includes, recursively
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SkillNode {
+    /// Skill name as referenced in `includes:`
+    pub name: String,
+    /// Resolution status
+    pub status: SkillNodeStatus,
+    /// Included skills, in declaration order
+    pub children: Vec<SkillNode>,
+}
+
+impl SkillNode {
+    /// Build an include tree rooted at `name`.
+    ///
+    /// `includes_of` returns a skill's includes, or `None` if it doesn't exist.
+    pub fn build(name: &str, includes_of: &dyn Fn(&str) -> Option<Vec<String>>) -> Self {
+        let mut ancestors = Vec::new();
+        Self::build_inner(name, includes_of, &mut ancestors)
+    }
+
+    fn build_inner(
+        name: &str,
+        includes_of: &dyn Fn(&str) -> Option<Vec<String>>,
+        ancestors: &mut Vec<String>,
+    ) -> Self {
+        if ancestors.iter().any(|a| a == name) {
+            return Self::leaf(name, SkillNodeStatus::Cycle);
+        }
+        let Some(includes) = includes_of(name) else {
+            return Self::leaf(name, SkillNodeStatus::Missing);
+        };
+
+        ancestors.push(name.to_string());
+        let children = includes
+            .iter()
+            .map(|child| Self::build_inner(child, includes_of, ancestors))
+            .collect();
+        ancestors.pop();
+
+        SkillNode {
+            name: name.to_string(),
+            status: SkillNodeStatus::Found,
+            children,
+        }
+    }
+
+    fn leaf(name: &str, status: SkillNodeStatus) -> Self {
+        SkillNode {
+            name: name.to_string(),
+            status,
+            children: Vec::new(),
+        }
+    }
+
+    /// Include chains that loop back on themselves, e.g. `["a", "b", "a"]`
+    pub fn cycles(&self) -> Vec<Vec<String>> {
+        let mut cycles = Vec::new();
+        let mut path = Vec::new();
+        self.collect_cycles(&mut path, &mut cycles);
+        cycles
+    }
+
+    fn collect_cycles(&self, path: &mut Vec<String>, cycles: &mut Vec<Vec<String>>) {
+        path.push(self.name.clone());
+        if self.status == SkillNodeStatus::Cycle {
+            let start = path.iter().position(|n| n == &self.name).unwrap_or(0);
+            cycles.push(path[start..].to_vec());
+        }
+        for child in &self.children {
+            child.collect_cycles(path, cycles);
+        }
+        path.pop();
+    }
+
+    /// Render the tree with box-drawing connectors, one skill per line
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        out.push_str(&self.label());
+        out.push('\n');
+        self.render_children("", &mut out);
+        out
+    }
+
+    fn render_children(&self, prefix: &str, out: &mut String) {
+        for (i, child) in self.children.iter().enumerate() {
+            let last = i == self.children.len() - 1;
+            out.push_str(prefix);
+            out.push_str(if last { "└── " } else { "├── " });
+            out.push_str(&child.label());
+            out.push('\n');
+            let child_prefix = format!("{}{}", prefix, if last { "    " } else { "│   " });
+            child.render_children(&child_prefix, out);
+        }
+    }
+
+    fn label(&self) -> String {
+        match self.status {
+            SkillNodeStatus::Found => self.name.clone(),
+            SkillNodeStatus::Missing => format!("{} (not found)", self.name),
+            SkillNodeStatus::Cycle => format!("{} (cycle)", self.name),
+        }
+    }
+}
+
+impl WorkspaceConfig {
+    /// Build the include tree for a skill from the configured skill directories
+    pub fn skill_tree(&self, name: &str) -> SkillNode {
+        SkillNode::build(name, &|n| {
+            self.find_skill(n)
+                .and_then(|path| Skill::from_file(&path).ok())
+                .map(|skill| skill.includes)
+        })
+    }
+
+    /// Expand skill names with everything they include, transitively.
+    ///
+    /// Returns skill paths in depth-first order with each skill appearing once,
+    /// so cycles terminate. Missing includes are reported and skipped.
+    pub(super) fn flatten_includes(&self, skill_names: &[String]) -> Vec<PathBuf> {
+        let mut seen = HashSet::new();
+        let mut paths = Vec::new();
+        for name in skill_names {
+            self.flatten_into(name, None, &mut seen, &mut paths);
+        }
+        paths
+    }
+
+    fn flatten_into(
+        &self,
+        name: &str,
+        parent: Option<&str>,
+        seen: &mut HashSet<String>,
+        paths: &mut Vec<PathBuf>,
+    ) {
+        if !seen.insert(name.to_string()) {
+            return;
+        }
+        let Some(path) = self.find_skill(name) else {
+            if let Some(parent) = parent {
+                eprintln!(
+                    "{} Skill '{}' included by '{}' not found",
+                    "!".yellow(),
+                    name,
+                    parent
+                );
+            }
+            return;
+        };
+        let includes = Skill::from_file(&path)
+            .map(|skill| skill.includes)
+            .unwrap_or_default();
+        paths.push(path);
+        for include in &includes {
+            self.flatten_into(include, Some(name), seen, paths);
+        }
+    }
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    fn lookup(graph: &[(&str, &[&str])]) -> impl Fn(&str) -> Option<Vec<String>> {
+        let graph: HashMap<String, Vec<String>> = graph
+            .iter()
+            .map(|(name, deps)| {
+                (
+                    name.to_string(),
+                    deps.iter().map(|d| d.to_string()).collect(),
+                )
+            })
+            .collect();
+        move |name| graph.get(name).cloned()
+    }
+
+    #[test]
+    fn test_render_include_tree() {
+        let includes = lookup(&[
+            ("fullstack", &["frontend", "backend"]),
+            ("frontend", &["css", "typescript"]),
+            ("backend", &["typescript", "sql"]),
+            ("css", &[]),
+            ("typescript", &[]),
+        ]);
+        let tree = SkillNode::build("fullstack", &includes);
+        assert!(tree.cycles().is_empty());
+        assert_eq!(
+            tree.render(),
+            "fullstack\n\
+             ├── frontend\n\
+             │   ├── css\n\
+             │   └── typescript\n\
+             └── backend\n    \
+                 ├── typescript\n    \
+                 └── sql (not found)\n"
+        );
+    }
+
+    #[test]
+    fn test_detect_include_cycle() {
+        let includes = lookup(&[("a", &["b", "c"]), ("b", &["c"]), ("c", &["a"])]);
+        let tree = SkillNode::build("a", &includes);
+        assert_eq!(
+            tree.cycles(),
+            vec![vec!["a", "b", "c", "a"], vec!["a", "c", "a"]]
+        );
+        assert!(tree.render().contains("└── a (cycle)"));
+
+        let self_ref = lookup(&[("loop", &["loop"])]);
+        assert_eq!(
+            SkillNode::build("loop", &self_ref).cycles(),
+            vec![vec!["loop", "loop"]]
+        );
+    }
+
+    #[test]
+    fn test_resolve_skills_flattens_includes() {
+        let dir = std::env::temp_dir().join("axel-test-skill-includes");
+        let skills = dir.join("skills");
+        std::fs::create_dir_all(&skills).unwrap();
+        std::fs::write(
+            skills.join("composite.md"),
+            "---\nincludes: [part, other]\n---\nComposite",
+        )
+        .unwrap();
+        std::fs::write(
+            skills.join("part.md"),
+            "---\nincludes: [composite]\n---\nPart",
+        )
+        .unwrap();
+        std::fs::write(skills.join("other.md"), "Other").unwrap();
+        std::fs::write(skills.join("unrelated.md"), "Unrelated").unwrap();
+
+        let yaml = "workspace: test\nlayouts: {}\nskills:\n  - path: ./skills\n";
+        let mut config: WorkspaceConfig = serde_yaml::from_str(yaml).unwrap();
+        config.manifest_path = Some(dir.join("AXEL.md"));
+
+        let names: Vec<_> = config
+            .resolve_skills(&["composite".to_string()])
+            .iter()
+            .map(|p| p.file_stem().unwrap().to_string_lossy().to_string())
+            .collect();
+        assert_eq!(names, vec!["composite", "part", "other"]);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
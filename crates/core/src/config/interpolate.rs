@@ -0,0 +1,216 @@
+//! Environment variable interpolation
+//!
+//! Paths, prompts, commands and args in a manifest may reference the
+//! environment so machine-specific values stay out of AXEL.md:
+//!
+//! - `${VAR}` expands to `$VAR`; an unset variable is an error
+//! - `${VAR:-default}` falls back to `default` when `$VAR` is unset or empty
+//! - `$${` is a literal `${` (no expansion)
+//!
+//! A `$` not followed by `{` is left alone, so shell snippets like `$HOME`
+//! in commands still reach the shell untouched.
+
+use anyhow::{Result, bail};
+
+use super::{PaneConfig, WorkspaceConfig};
+
+/// Expand `${...}` placeholders in `input` using `lookup`
+pub fn interpolate(input: &str, lookup: &dyn Fn(&str) -> Option<String>) -> Result<String> {
+    let mut out = String::with_capacity(input.len());
+    let mut rest = input;
+
+    while let Some(pos) = rest.find('$') {
+        out.push_str(&rest[..pos]);
+        let after = &rest[pos + 1..];
+
+        if let Some(escaped) = after.strip_prefix("${") {
+            out.push_str("${");
+            rest = escaped;
+        } else if let Some(body) = after.strip_prefix('{') {
+            let Some(end) = body.find('}') else {
+                bail!("Unterminated '${{' in '{}'", input);
+            };
+            let expr = &body[..end];
+            let (name, default) = match expr.split_once(":-") {
+                Some((name, default)) => (name, Some(default)),
+                None => (expr, None),
+            };
+            if name.is_empty()
+                || name.starts_with(|c: char| c.is_ascii_digit())
+                || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+            {
+                bail!("Invalid variable name '{}' in '{}'", name, input);
+            }
+
+            match (lookup(name), default) {
+                (Some(value), Some(default)) if value.is_empty() => out.push_str(default),
+                (Some(value), _) => out.push_str(&value),
+                (None, Some(default)) => out.push_str(default),
+                (None, None) => bail!(
+                    "Environment variable '{}' is not set (use ${{{}:-default}} for a fallback)",
+                    name,
+                    name
+                ),
+            }
+            rest = &body[end + 1..];
+        } else {
+            out.push('$');
+            rest = after;
+        }
+    }
+
+    out.push_str(rest);
+    Ok(out)
+}
+
+impl WorkspaceConfig {
+    /// Expand environment placeholders in paths, prompts, commands and args
+    pub(super) fn interpolate_env(&mut self) -> Result<()> {
+        let lookup = |name: &str| std::env::var(name).ok();
+        let expand = |value: &mut String| -> Result<()> {
+            *value = interpolate(value, &lookup)?;
+            Ok(())
+        };
+
+        for skill_path in &mut self.skills {
+            expand(&mut skill_path.path)?;
+        }
+        if let Some(parent) = &mut self.parent {
+            expand(parent)?;
+        }
+        if let Some(preprocessor) = &mut self.index_preprocessor {
+            expand(preprocessor)?;
+        }
+
+        for pane in &mut self.layouts.panes {
+            let name = pane.pane_type().to_string();
+            let result = match pane {
+                PaneConfig::Claude(c)
+                | PaneConfig::Codex(c)
+                | PaneConfig::Opencode(c)
+                | PaneConfig::Antigravity(c)
+                | PaneConfig::External(c) => c
+                    .path
+                    .iter_mut()
+                    .chain(c.startup_command.iter_mut())
+                    .chain(c.prompt.iter_mut())
+                    .chain(c.prompt_file.iter_mut())
+                    .chain(c.session_id.iter_mut())
+                    .chain(c.context_files.iter_mut())
+                    .chain(c.add_dirs.iter_mut())
+                    .chain(c.args.iter_mut())
+                    .chain(c.mcp.values_mut().flat_map(|server| {
+                        std::iter::once(&mut server.command)
+                            .chain(server.args.iter_mut())
+                            .chain(server.env.values_mut())
+                    }))
+                    .try_for_each(expand),
+                PaneConfig::Custom(c) => c
+                    .path
+                    .iter_mut()
+                    .chain(c.startup_command.iter_mut())
+                    .chain(c.command.iter_mut())
+                    .try_for_each(expand),
+            };
+            result.map_err(|e| e.context(format!("In pane '{}'", name)))?;
+        }
+
+        for (name, grid) in &mut self.layouts.grids {
+            grid.path
+                .iter_mut()
+                .chain(
+                    grid.cells
+                        .values_mut()
+                        .flat_map(|cell| cell.path.iter_mut()),
+                )
+                .try_for_each(expand)
+                .map_err(|e| e.context(format!("In grid '{}'", name)))?;
+        }
+
+        Ok(())
+    }
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn env(name: &str) -> Option<String> {
+        match name {
+            "HOME_DIR" => Some("/home/me".to_string()),
+            "EMPTY" => Some(String::new()),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_interpolate_placeholders() {
+        assert_eq!(
+            interpolate("${HOME_DIR}/code", &env).unwrap(),
+            "/home/me/code"
+        );
+        assert_eq!(
+            interpolate("${MODEL:-opus} and ${HOME_DIR:-x}", &env).unwrap(),
+            "opus and /home/me"
+        );
+        assert_eq!(
+            interpolate("[${EMPTY:-fallback}]", &env).unwrap(),
+            "[fallback]"
+        );
+        assert_eq!(interpolate("[${EMPTY}]", &env).unwrap(), "[]");
+        assert_eq!(interpolate("${MISSING:-}", &env).unwrap(), "");
+        assert_eq!(
+            interpolate("echo $$HOME_DIR $${HOME_DIR} $HOME", &env).unwrap(),
+            "echo $$HOME_DIR ${HOME_DIR} $HOME"
+        );
+
+        let err = interpolate("${MISSING}/x", &env).unwrap_err().to_string();
+        assert!(err.contains("'MISSING' is not set"), "{}", err);
+        assert!(interpolate("${HOME_DIR", &env).is_err());
+        assert!(interpolate("${1BAD}", &env).is_err());
+    }
+
+    #[test]
+    fn test_interpolate_manifest_fields() {
+        let dir = std::env::temp_dir().join("axel-test-interpolate");
+        std::fs::create_dir_all(&dir).unwrap();
+        let manifest = dir.join("AXEL.md");
+        std::fs::write(
+            &manifest,
+            r#"---
+workspace: ${AXEL_TEST_UNUSED}
+skills:
+  - path: ${AXEL_TEST_SKILLS:-./skills}
+layouts:
+  panes:
+    - type: claude
+      prompt: "Work on ${AXEL_TEST_TICKET:-nothing}"
+      args: ["--model", "${AXEL_TEST_MODEL:-opus}"]
+    - type: shell
+      command: "echo $${HOME}"
+---
+"#,
+        )
+        .unwrap();
+
+        let config = crate::config::load_config(&manifest).unwrap();
+        // Only paths, prompts, commands and args are expanded
+        assert_eq!(config.workspace, "${AXEL_TEST_UNUSED}");
+        assert_eq!(config.skills[0].path, "./skills");
+        let PaneConfig::Claude(claude) = &config.layouts.panes[0] else {
+            panic!("expected claude pane");
+        };
+        assert_eq!(claude.prompt.as_deref(), Some("Work on nothing"));
+        assert_eq!(claude.args, vec!["--model", "opus"]);
+        let PaneConfig::Custom(shell) = &config.layouts.panes[1] else {
+            panic!("expected custom pane");
+        };
+        assert_eq!(shell.command.as_deref(), Some("echo ${HOME}"));
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
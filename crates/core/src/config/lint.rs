@@ -0,0 +1,405 @@
+//! Skill linting
+//!
+//! `axel skill lint` checks the skills in a workspace's skill directories
+//! before they reach an agent:
+//!
+//! - frontmatter that doesn't parse (axel would silently ignore it), or is
+//!   missing `name`/`description`
+//! - `tools:` entries the drivers using the skill don't know, or drivers
+//!   that ignore `tools:` altogether (per-driver overrides are checked
+//!   against their own driver)
+//! - prompts large enough to crowd the model's context window
+//! - skills with the same name in several directories (only the first is
+//!   installed)
+//! - `includes:` of skills that don't exist
+//!
+//! Prompt size is estimated at four bytes per token.
+
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+use serde::Serialize;
+
+use super::{Severity, Skill, SkillFrontmatter, WorkspaceConfig};
+use crate::drivers::{self, SkillDriver};
+
+/// Share of the context window above which a prompt is reported
+const CONTEXT_WARNING_PERCENT: usize = 10;
+
+/// Share of the context window above which a prompt is an error
+const CONTEXT_ERROR_PERCENT: usize = 50;
+
+/// A problem found in a skill
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SkillLintIssue {
+    /// Skill name
+    pub skill: String,
+    /// Skill file
+    pub path: PathBuf,
+    pub severity: Severity,
+    pub message: String,
+}
+
+/// A skill file found in a skill directory
+struct SkillEntry {
+    name: String,
+    path: PathBuf,
+}
+
+impl WorkspaceConfig {
+    /// Lint the skills in the workspace's skill directories, or only those
+    /// named in `names`
+    pub fn lint_skills(&self, names: &[String]) -> Vec<SkillLintIssue> {
+        let mut by_name: BTreeMap<String, Vec<PathBuf>> = BTreeMap::new();
+        for dir in self.skills_dirs() {
+            for entry in skill_entries(&dir) {
+                by_name.entry(entry.name).or_default().push(entry.path);
+            }
+        }
+
+        let mut issues = Vec::new();
+        for (name, paths) in &by_name {
+            if !names.is_empty() && !names.contains(name) {
+                continue;
+            }
+            let issue = |severity, message| SkillLintIssue {
+                skill: name.clone(),
+                path: paths[0].clone(),
+                severity,
+                message,
+            };
+
+            for shadowed in &paths[1..] {
+                issues.push(issue(
+                    Severity::Warning,
+                    format!("Also defined in {}, which is ignored", shadowed.display()),
+                ));
+            }
+
+            let drivers: Vec<Box<dyn SkillDriver>> = self
+                .skill_drivers(name)
+                .iter()
+                .filter_map(|d| drivers::get_driver(d))
+                .collect();
+            for (severity, message) in lint_skill_file(&paths[0], &drivers) {
+                issues.push(issue(severity, message));
+            }
+
+            if let Ok(skill) = Skill::from_file(&paths[0]) {
+                for include in skill.includes {
+                    if !by_name.contains_key(&include) {
+                        issues.push(issue(
+                            Severity::Error,
+                            format!("Includes unknown skill '{}'", include),
+                        ));
+                    }
+                }
+            }
+        }
+
+        for name in names {
+            if !by_name.contains_key(name) {
+                issues.push(SkillLintIssue {
+                    skill: name.clone(),
+                    path: PathBuf::new(),
+                    severity: Severity::Error,
+                    message: "Not found in any skill directory".to_string(),
+                });
+            }
+        }
+        issues
+    }
+}
+
+/// Lint one skill file for the drivers that install it
+pub fn lint_skill_file(path: &Path, drivers: &[Box<dyn SkillDriver>]) -> Vec<(Severity, String)> {
+    let mut issues = Vec::new();
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) => return vec![(Severity::Error, format!("Cannot read: {}", e))],
+    };
+
+    let (frontmatter, body) = match content.strip_prefix("---").and_then(|rest| {
+        rest.find("\n---")
+            .map(|end| (&rest[..end], &rest[end + 4..]))
+    }) {
+        Some((yaml, body)) => match serde_yaml::from_str::<SkillFrontmatter>(yaml) {
+            Ok(frontmatter) => (Some(frontmatter), body),
+            Err(e) => {
+                issues.push((Severity::Error, format!("Invalid frontmatter: {}", e)));
+                (None, body)
+            }
+        },
+        None => {
+            issues.push((
+                Severity::Warning,
+                "No frontmatter; name and description are guessed from the content".to_string(),
+            ));
+            (None, content.as_str())
+        }
+    };
+
+    if body.trim().is_empty() {
+        issues.push((Severity::Error, "Prompt is empty".to_string()));
+    }
+
+    let mut model = None;
+    if let Some(frontmatter) = &frontmatter {
+        let file_name = skill_file_name(path);
+        match &frontmatter.name {
+            None => issues.push((Severity::Warning, "Missing `name`".to_string())),
+            Some(name) if *name != file_name => issues.push((
+                Severity::Warning,
+                format!(
+                    "`name: {}` differs from the installed name '{}'",
+                    name, file_name
+                ),
+            )),
+            Some(_) => {}
+        }
+        if frontmatter
+            .description
+            .as_deref()
+            .is_none_or(|d| d.trim().is_empty())
+        {
+            issues.push((Severity::Warning, "Missing `description`".to_string()));
+        }
+        for driver in drivers {
+            let overrides = frontmatter.drivers.get(driver.name());
+            let tools = overrides
+                .and_then(|o| o.tools.as_ref())
+                .or(frontmatter.tools.as_ref());
+            if let Some(tools) = tools {
+                lint_tools(tools, driver.as_ref(), &mut issues);
+            }
+        }
+        for name in frontmatter.drivers.keys() {
+            if crate::drivers::get_driver(name).is_none() {
+                issues.push((
+                    Severity::Warning,
+                    format!("Overrides for unknown driver '{}'", name),
+                ));
+            }
+        }
+        model = frontmatter.model.as_deref();
+    }
+
+    let tokens = content.len() / 4;
+    let window = context_window(model);
+    let percent = tokens * 100 / window;
+    if percent >= CONTEXT_WARNING_PERCENT {
+        let severity = if percent >= CONTEXT_ERROR_PERCENT {
+            Severity::Error
+        } else {
+            Severity::Warning
+        };
+        issues.push((
+            severity,
+            format!(
+                "Prompt is ~{} tokens, {}% of a {}-token context window",
+                tokens, percent, window
+            ),
+        ));
+    }
+
+    issues
+}
+
+/// Check a comma-separated `tools:` list against a driver's tools
+fn lint_tools(tools: &str, driver: &dyn SkillDriver, issues: &mut Vec<(Severity, String)>) {
+    let Some(known) = driver.skill_tools() else {
+        issues.push((
+            Severity::Warning,
+            format!("`tools` is ignored by {}", driver.name()),
+        ));
+        return;
+    };
+    for tool in tools.split(',').map(str::trim).filter(|t| !t.is_empty()) {
+        // `Bash(git:*)` restricts a tool; MCP tools are named per server
+        let base = tool.split('(').next().unwrap_or(tool).trim();
+        if !base.starts_with("mcp__") && !known.contains(&base) {
+            issues.push((
+                Severity::Error,
+                format!("Unknown tool '{}' for {}", tool, driver.name()),
+            ));
+        }
+    }
+}
+
+/// Context window of `model`, in tokens
+fn context_window(model: Option<&str>) -> usize {
+    let model = model.unwrap_or_default().to_lowercase();
+    if model.contains("gemini") {
+        1_000_000
+    } else if model.contains("gpt") || model.contains("codex") {
+        400_000
+    } else {
+        200_000
+    }
+}
+
+/// Skill files directly in `dir`, as `<name>/SKILL.md` or `<name>.md`
+fn skill_entries(dir: &Path) -> Vec<SkillEntry> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut skills: Vec<SkillEntry> = entries
+        .flatten()
+        .map(|e| e.path())
+        .filter_map(|path| {
+            let file = if path.is_dir() {
+                path.join("SKILL.md")
+            } else if path.extension().is_some_and(|ext| ext == "md")
+                && path.file_name().is_some_and(|n| n != "index.md")
+            {
+                path
+            } else {
+                return None;
+            };
+            file.is_file().then(|| SkillEntry {
+                name: skill_file_name(&file),
+                path: file,
+            })
+        })
+        .collect();
+    skills.sort_by(|a, b| a.name.cmp(&b.name));
+    skills
+}
+
+/// Name a skill file installs as
+fn skill_file_name(path: &Path) -> String {
+    let name = if path.file_name().is_some_and(|n| n == "SKILL.md") {
+        path.parent().and_then(|p| p.file_name())
+    } else {
+        path.file_stem()
+    };
+    name.map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::drivers::{ClaudeDriver, CodexDriver};
+
+    #[test]
+    fn test_lint_skill_file() {
+        let dir = std::env::temp_dir().join("axel-test-skill-lint");
+        std::fs::remove_dir_all(&dir).ok();
+        std::fs::create_dir_all(dir.join("review")).unwrap();
+
+        let good = dir.join("review/SKILL.md");
+        std::fs::write(
+            &good,
+            "---\nname: review\ndescription: Review diffs\ntools: Read, Bash(git:*), mcp__github__pr\n---\n# Review\n",
+        )
+        .unwrap();
+        let claude: Vec<Box<dyn SkillDriver>> = vec![Box::new(ClaudeDriver)];
+        assert!(lint_skill_file(&good, &claude).is_empty());
+
+        // Codex ignores `tools:`
+        let codex: Vec<Box<dyn SkillDriver>> = vec![Box::new(CodexDriver)];
+        assert_eq!(
+            lint_skill_file(&good, &codex),
+            vec![(Severity::Warning, "`tools` is ignored by codex".to_string())]
+        );
+
+        let bad = dir.join("bad.md");
+        std::fs::write(&bad, "---\nname: other\ntools: Reed\n---\n\n").unwrap();
+        let messages: Vec<String> = lint_skill_file(&bad, &claude)
+            .into_iter()
+            .map(|(_, m)| m)
+            .collect();
+        assert_eq!(
+            messages,
+            vec![
+                "Prompt is empty",
+                "`name: other` differs from the installed name 'bad'",
+                "Missing `description`",
+                "Unknown tool 'Reed' for claude",
+            ]
+        );
+
+        // Overrides are checked against their driver
+        std::fs::write(
+            &bad,
+            "---\nname: bad\ndescription: Bad\ntools: Read\ndrivers:\n  claude:\n    tools: Raed\n  cursor:\n    model: x\n---\nBody\n",
+        )
+        .unwrap();
+        let messages: Vec<String> = lint_skill_file(&bad, &claude)
+            .into_iter()
+            .map(|(_, m)| m)
+            .collect();
+        assert_eq!(
+            messages,
+            vec![
+                "Unknown tool 'Raed' for claude",
+                "Overrides for unknown driver 'cursor'",
+            ]
+        );
+
+        // A list is not a valid `tools:` value
+        std::fs::write(&bad, "---\ntools: [Read]\n---\nBody\n").unwrap();
+        let issues = lint_skill_file(&bad, &claude);
+        assert_eq!(issues[0].0, Severity::Error);
+        assert!(issues[0].1.starts_with("Invalid frontmatter"));
+
+        std::fs::write(&bad, "x".repeat(400_000)).unwrap();
+        let issues = lint_skill_file(&bad, &claude);
+        assert_eq!(
+            issues.last().unwrap(),
+            &(
+                Severity::Error,
+                "Prompt is ~100000 tokens, 50% of a 200000-token context window".to_string()
+            )
+        );
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_lint_skills_finds_duplicates_and_includes() {
+        let dir = std::env::temp_dir().join("axel-test-skill-lint-workspace");
+        std::fs::remove_dir_all(&dir).ok();
+        std::fs::create_dir_all(dir.join("skills")).unwrap();
+        std::fs::create_dir_all(dir.join("shared/docs")).unwrap();
+        let skill = |name: &str, extra: &str| {
+            format!(
+                "---\nname: {}\ndescription: {} skill\n{}---\nBody\n",
+                name, name, extra
+            )
+        };
+        std::fs::write(
+            dir.join("skills/docs.md"),
+            skill("docs", "includes: [style]\n"),
+        )
+        .unwrap();
+        std::fs::write(dir.join("shared/docs/SKILL.md"), skill("docs", "")).unwrap();
+
+        let manifest = dir.join("AXEL.md");
+        std::fs::write(
+            &manifest,
+            "---\nworkspace: test\nskills:\n  - path: ./skills\n  - path: ./shared\nlayouts:\n  panes: []\n---\n",
+        )
+        .unwrap();
+        let config = crate::config::load_config(&manifest).unwrap();
+
+        let issues = config.lint_skills(&[]);
+        let messages: Vec<&str> = issues.iter().map(|i| i.message.as_str()).collect();
+        assert_eq!(issues.len(), 2, "{:?}", messages);
+        assert_eq!(issues[0].path, dir.join("skills/docs.md"));
+        assert!(messages[0].starts_with("Also defined in"));
+        assert_eq!(messages[1], "Includes unknown skill 'style'");
+
+        let missing = config.lint_skills(&["nope".to_string()]);
+        assert_eq!(missing[0].message, "Not found in any skill directory");
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -0,0 +1,281 @@
+//! Skill lockfile
+//!
+//! `skills.lock`, next to AXEL.md, records a content hash of every skill the
+//! manifest's panes use. It is written the first time a workspace installs
+//! its skills and refreshed by `axel skill update`. Launching afterwards
+//! compares the skills on disk against it, so a teammate whose global or
+//! synced skills differ from the ones the workspace was locked with is told
+//! before the agents start.
+//!
+//! A skill's hash covers every file of its directory (`<name>/SKILL.md` and
+//! any supporting files), or the file itself for flat `<name>.md` skills.
+
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use super::{PaneConfig, WorkspaceConfig};
+
+/// Lockfile name, written next to the manifest
+pub const SKILLS_LOCK_FILE: &str = "skills.lock";
+
+/// Header written at the top of the lockfile
+const LOCK_HEADER: &str = "# Generated by axel. Refresh with `axel skill update`.\n\n";
+
+/// Contents of `skills.lock`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SkillsLock {
+    /// Locked skills, by name
+    #[serde(default)]
+    pub skills: BTreeMap<String, LockedSkill>,
+}
+
+/// One skill in the lockfile
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LockedSkill {
+    /// Where the skill was found (`~` for the home directory)
+    pub source: String,
+    /// `sha256:` hash of the skill's content
+    pub hash: String,
+}
+
+/// How a skill differs from the lockfile
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase", tag = "status")]
+pub enum SkillDrift {
+    /// Content changed since it was locked
+    Modified {
+        locked: LockedSkill,
+        current: LockedSkill,
+    },
+    /// Used by the manifest but not locked
+    Added { current: LockedSkill },
+    /// Locked but no longer used or found
+    Removed { locked: LockedSkill },
+}
+
+impl SkillsLock {
+    /// Path of the lockfile in `workspace_dir`
+    pub fn path(workspace_dir: &Path) -> PathBuf {
+        workspace_dir.join(SKILLS_LOCK_FILE)
+    }
+
+    /// Read the lockfile in `workspace_dir`, if there is one
+    pub fn load(workspace_dir: &Path) -> Result<Option<Self>> {
+        let path = Self::path(workspace_dir);
+        let content = match std::fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e).with_context(|| format!("Failed to read {}", path.display())),
+        };
+        let lock =
+            toml::from_str(&content).with_context(|| format!("Invalid {}", path.display()))?;
+        Ok(Some(lock))
+    }
+
+    /// Write the lockfile to `workspace_dir`
+    pub fn save(&self, workspace_dir: &Path) -> Result<()> {
+        let content = format!("{}{}", LOCK_HEADER, toml::to_string(self)?);
+        std::fs::write(Self::path(workspace_dir), content)?;
+        Ok(())
+    }
+
+    /// Lock the skill files at `paths`
+    pub fn from_skills(paths: &[PathBuf]) -> Result<Self> {
+        let mut skills = BTreeMap::new();
+        for path in paths {
+            skills.insert(
+                skill_name(path),
+                LockedSkill {
+                    source: display_source(path),
+                    hash: skill_hash(path)?,
+                },
+            );
+        }
+        Ok(Self { skills })
+    }
+
+    /// Differences between this lockfile and `current`, by skill name
+    pub fn drift(&self, current: &SkillsLock) -> Vec<(String, SkillDrift)> {
+        let mut drift = Vec::new();
+        for (name, locked) in &self.skills {
+            match current.skills.get(name) {
+                Some(now) if now.hash != locked.hash => drift.push((
+                    name.clone(),
+                    SkillDrift::Modified {
+                        locked: locked.clone(),
+                        current: now.clone(),
+                    },
+                )),
+                Some(_) => {}
+                None => drift.push((
+                    name.clone(),
+                    SkillDrift::Removed {
+                        locked: locked.clone(),
+                    },
+                )),
+            }
+        }
+        for (name, now) in &current.skills {
+            if !self.skills.contains_key(name) {
+                drift.push((
+                    name.clone(),
+                    SkillDrift::Added {
+                        current: now.clone(),
+                    },
+                ));
+            }
+        }
+        drift.sort_by(|a, b| a.0.cmp(&b.0));
+        drift
+    }
+}
+
+impl WorkspaceConfig {
+    /// Lock the skills used by any pane of the manifest, as they are on disk
+    pub fn current_skills_lock(&self) -> Result<SkillsLock> {
+        let mut names: Vec<String> = Vec::new();
+        for pane in &self.layouts.panes {
+            let skills = match pane {
+                PaneConfig::Claude(c)
+                | PaneConfig::Codex(c)
+                | PaneConfig::Opencode(c)
+                | PaneConfig::Antigravity(c)
+                | PaneConfig::External(c) => &c.skills,
+                PaneConfig::Custom(_) => continue,
+            };
+            for name in skills {
+                if !names.contains(name) {
+                    names.push(name.clone());
+                }
+            }
+        }
+        SkillsLock::from_skills(&self.resolve_skills(&names))
+    }
+}
+
+/// Skill name for a skill file: the directory name for `<name>/SKILL.md`,
+/// otherwise the file stem
+pub(super) fn skill_name(path: &Path) -> String {
+    let dir_name = if path.file_name().is_some_and(|n| n == "SKILL.md") {
+        path.parent().and_then(|p| p.file_name())
+    } else {
+        path.file_stem()
+    };
+    dir_name
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+/// `sha256:` hash of a skill's content.
+///
+/// For `<name>/SKILL.md`, every file in the skill directory is hashed along
+/// with its relative path, in sorted order.
+pub fn skill_hash(path: &Path) -> Result<String> {
+    let mut hasher = Sha256::new();
+    match path.parent() {
+        Some(dir) if path.file_name().is_some_and(|n| n == "SKILL.md") => {
+            let mut files = Vec::new();
+            collect_files(dir, &mut files)?;
+            files.sort();
+            for file in files {
+                let relative = file.strip_prefix(dir).unwrap_or(&file);
+                hasher.update(relative.to_string_lossy().as_bytes());
+                hasher.update([0]);
+                hasher.update(std::fs::read(&file)?);
+                hasher.update([0]);
+            }
+        }
+        _ => hasher.update(std::fs::read(path)?),
+    }
+    let digest: String = hasher
+        .finalize()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect();
+    Ok(format!("sha256:{}", digest))
+}
+
+/// Files under `dir`, recursively, skipping hidden entries
+pub(super) fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path
+            .file_name()
+            .is_some_and(|n| n.to_string_lossy().starts_with('.'))
+        {
+            continue;
+        }
+        if path.is_dir() {
+            collect_files(&path, files)?;
+        } else {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Skill location with the home directory shown as `~`
+fn display_source(path: &Path) -> String {
+    let dir = if path.file_name().is_some_and(|n| n == "SKILL.md") {
+        path.parent().unwrap_or(path)
+    } else {
+        path
+    };
+    match dirs::home_dir().and_then(|home| dir.strip_prefix(home).ok().map(Path::to_path_buf)) {
+        Some(relative) => format!("~/{}", relative.display()),
+        None => dir.display().to_string(),
+    }
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lock_round_trip_and_drift() {
+        let dir = std::env::temp_dir().join("axel-test-skills-lock");
+        std::fs::remove_dir_all(&dir).ok();
+        std::fs::create_dir_all(dir.join("skills/review/scripts")).unwrap();
+        std::fs::write(dir.join("skills/review/SKILL.md"), "# Review\n").unwrap();
+        std::fs::write(dir.join("skills/review/scripts/run.sh"), "echo 1\n").unwrap();
+        std::fs::write(dir.join("skills/docs.md"), "# Docs\n").unwrap();
+
+        let paths = vec![
+            dir.join("skills/review/SKILL.md"),
+            dir.join("skills/docs.md"),
+        ];
+        let locked = SkillsLock::from_skills(&paths).unwrap();
+        assert_eq!(
+            locked.skills.keys().collect::<Vec<_>>(),
+            vec!["docs", "review"]
+        );
+        assert!(locked.skills["review"].hash.starts_with("sha256:"));
+
+        locked.save(&dir).unwrap();
+        assert_eq!(SkillsLock::load(&dir).unwrap(), Some(locked.clone()));
+        assert!(locked.drift(&locked).is_empty());
+
+        // Supporting files count towards a skill's hash
+        std::fs::write(dir.join("skills/review/scripts/run.sh"), "echo 2\n").unwrap();
+        let current = SkillsLock::from_skills(&paths[..1]).unwrap();
+        let drift = locked.drift(&current);
+        assert_eq!(drift.len(), 2);
+        assert_eq!(drift[0].0, "docs");
+        assert!(matches!(drift[0].1, SkillDrift::Removed { .. }));
+        assert_eq!(drift[1].0, "review");
+        assert!(matches!(drift[1].1, SkillDrift::Modified { .. }));
+
+        assert_eq!(SkillsLock::load(&dir.join("missing")).unwrap(), None);
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
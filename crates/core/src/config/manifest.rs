@@ -0,0 +1,108 @@
+//! Manifest files
+//!
+//! A workspace is configured either by `AXEL.md`, YAML frontmatter followed
+//! by the project documentation (the index), or by a plain `axel.yaml` for
+//! teams that keep configuration and docs apart. A YAML manifest has no
+//! index unless it points at one:
+//!
+//! ```yaml
+//! workspace: api
+//! index: docs/AGENTS.md   # optional, relative to the manifest
+//! layouts:
+//!   panes: [...]
+//! ```
+//!
+//! `AXEL.md` wins when a directory has both.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+
+use super::extract_frontmatter;
+
+/// Markdown manifest: frontmatter plus the index
+pub const MANIFEST_FILE: &str = "AXEL.md";
+
+/// Plain YAML manifest
+pub const YAML_MANIFEST_FILE: &str = "axel.yaml";
+
+/// Whether the manifest at `path` is plain YAML (`.yaml` or `.yml`) rather
+/// than markdown with frontmatter
+pub fn is_yaml_manifest(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext == "yaml" || ext == "yml")
+}
+
+/// The manifest in `dir`: `AXEL.md`, or `axel.yaml` without one
+pub fn find_manifest(dir: &Path) -> Option<PathBuf> {
+    [MANIFEST_FILE, YAML_MANIFEST_FILE]
+        .iter()
+        .map(|name| dir.join(name))
+        .find(|path| path.exists())
+}
+
+/// The manifest in `dir`, or the path `AXEL.md` would have there
+pub fn manifest_in(dir: &Path) -> PathBuf {
+    find_manifest(dir).unwrap_or_else(|| dir.join(MANIFEST_FILE))
+}
+
+/// The YAML configuration in `content`, read from the manifest at `path`
+pub(super) fn manifest_yaml<'a>(path: &Path, content: &'a str) -> Result<&'a str> {
+    if is_yaml_manifest(path) {
+        Ok(content)
+    } else {
+        extract_frontmatter(content)
+    }
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::load_config;
+
+    #[test]
+    fn test_yaml_manifest() {
+        let dir = std::env::temp_dir().join("axel-test-yaml-manifest");
+        std::fs::remove_dir_all(&dir).ok();
+        std::fs::create_dir_all(dir.join("docs")).unwrap();
+        assert_eq!(find_manifest(&dir), None);
+        assert_eq!(manifest_in(&dir), dir.join(MANIFEST_FILE));
+
+        let path = dir.join(YAML_MANIFEST_FILE);
+        std::fs::write(
+            &path,
+            "workspace: api\nlayouts:\n  panes:\n    - type: claude\n",
+        )
+        .unwrap();
+        assert_eq!(find_manifest(&dir), Some(path.clone()));
+
+        let config = load_config(&path).unwrap();
+        assert_eq!(config.workspace, "api");
+        assert_eq!(config.index_path(), None);
+        assert!(config.load_index().unwrap().is_none());
+
+        // The index lives in its own file
+        std::fs::write(
+            &path,
+            "workspace: api\nindex: docs/AGENTS.md\nlayouts:\n  panes: []\n",
+        )
+        .unwrap();
+        std::fs::write(dir.join("docs/AGENTS.md"), "# API\n\nServes the API.\n").unwrap();
+        let config = load_config(&path).unwrap();
+        assert_eq!(config.index_path(), Some(dir.join("docs/AGENTS.md")));
+        assert_eq!(
+            config.load_index().unwrap().unwrap().content,
+            "# API\n\nServes the API."
+        );
+
+        // AXEL.md wins over axel.yaml
+        std::fs::write(dir.join(MANIFEST_FILE), "---\nworkspace: api\n---\n").unwrap();
+        assert_eq!(find_manifest(&dir), Some(dir.join(MANIFEST_FILE)));
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
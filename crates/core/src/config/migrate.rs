@@ -0,0 +1,413 @@
+//! Migration of legacy manifests to the current schema
+//!
+//! Early manifests (`barrel.yaml`, and AXEL.md files written before the
+//! rename) described pane definitions under `shells:`, grid layouts under
+//! `profiles:` and skill directories under `agents:`. This module rewrites
+//! them to `layouts.panes`, `layouts.grids` and `skills`, recording every
+//! transformation so the CLI can report what changed.
+
+use anyhow::{Context, Result};
+use serde_yaml::{Mapping, Value};
+
+use super::split_frontmatter;
+
+/// Result of migrating a manifest
+#[derive(Debug)]
+pub struct Migration {
+    /// Migrated manifest, always as AXEL.md (YAML frontmatter + markdown body)
+    pub content: String,
+    /// Human-readable description of each transformation, in order
+    pub changes: Vec<String>,
+    /// Things the migration could not carry over (e.g. YAML comments)
+    pub warnings: Vec<String>,
+}
+
+impl Migration {
+    /// Whether the manifest was already in the current format
+    pub fn is_up_to_date(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+/// Migrate a manifest to the current schema.
+///
+/// Accepts either an AXEL.md file with YAML frontmatter or a plain YAML
+/// manifest (`barrel.yaml`). The markdown body of an AXEL.md is preserved
+/// as-is; a plain YAML manifest gets a minimal body with the workspace name.
+pub fn migrate_manifest(content: &str) -> Result<Migration> {
+    migrate(content, false)
+}
+
+/// Migrate a plain YAML manifest (`axel.yaml`) to the current schema,
+/// keeping it plain YAML.
+pub fn migrate_yaml_manifest(content: &str) -> Result<Migration> {
+    migrate(content, true)
+}
+
+fn migrate(content: &str, keep_yaml: bool) -> Result<Migration> {
+    let (yaml, body) = match split_frontmatter(content) {
+        Ok((yaml, body)) if !keep_yaml => (yaml, Some(body)),
+        _ => (content, None),
+    };
+    let is_markdown = body.is_some();
+
+    let value: Value = serde_yaml::from_str(yaml).context("Failed to parse manifest YAML")?;
+    let Value::Mapping(root) = value else {
+        anyhow::bail!("Manifest must be a YAML mapping");
+    };
+
+    let mut changes = Vec::new();
+    let migrated = migrate_root(root, &mut changes)?;
+
+    // A manifest already in the current format is returned untouched
+    if (is_markdown || keep_yaml) && changes.is_empty() {
+        return Ok(Migration {
+            content: content.to_string(),
+            changes,
+            warnings: Vec::new(),
+        });
+    }
+
+    let mut warnings = Vec::new();
+    if yaml.lines().any(|l| l.trim_start().starts_with('#')) {
+        warnings.push("Comments in the manifest YAML are not preserved".to_string());
+    }
+
+    let yaml_out = serde_yaml::to_string(&Value::Mapping(migrated.clone()))?;
+    let content = match body {
+        Some(body) => format!("---\n{}---{}", yaml_out, body),
+        None if keep_yaml => yaml_out,
+        None => {
+            changes.push("Converted plain YAML manifest to AXEL.md frontmatter".to_string());
+            let name = migrated
+                .get("workspace")
+                .and_then(Value::as_str)
+                .unwrap_or("workspace");
+            format!("---\n{}---\n\n# {}\n", yaml_out, name)
+        }
+    };
+
+    Ok(Migration {
+        content,
+        changes,
+        warnings,
+    })
+}
+
+// =============================================================================
+// Transformations
+// =============================================================================
+
+/// Rewrite top-level keys, keeping their original order where possible
+fn migrate_root(root: Mapping, changes: &mut Vec<String>) -> Result<Mapping> {
+    if root.contains_key("agents") && root.contains_key("skills") {
+        anyhow::bail!("Manifest defines both `agents` and `skills`; merge them by hand");
+    }
+    if root.contains_key("name") && root.contains_key("workspace") {
+        anyhow::bail!("Manifest defines both `name` and `workspace`; keep only `workspace`");
+    }
+
+    let mut out = Mapping::new();
+    for (key, value) in root {
+        match key.as_str() {
+            Some("name") => {
+                changes.push("Renamed `name` to `workspace`".to_string());
+                out.insert("workspace".into(), value);
+            }
+            Some("agents") => {
+                changes.push("Renamed `agents` to `skills`".to_string());
+                out.insert("skills".into(), migrate_skill_paths(value, changes));
+            }
+            Some("skills") => {
+                out.insert(key, migrate_skill_paths(value, changes));
+            }
+            Some("shells") => {
+                changes.push("Moved `shells` to `layouts.panes`".to_string());
+                let layouts = layouts_entry(&mut out)?;
+                insert_unique(layouts, "panes", migrate_panes(value, changes)?)?;
+            }
+            Some("profiles") => {
+                changes.push("Moved `profiles` to `layouts.grids`".to_string());
+                let layouts = layouts_entry(&mut out)?;
+                insert_unique(layouts, "grids", value)?;
+            }
+            Some("layouts") => {
+                let Value::Mapping(existing) = value else {
+                    anyhow::bail!("`layouts` must be a mapping");
+                };
+                let migrated = migrate_layouts(existing, changes)?;
+                let layouts = layouts_entry(&mut out)?;
+                for (k, v) in migrated {
+                    let name = k.as_str().unwrap_or_default().to_string();
+                    insert_unique(layouts, &name, v)?;
+                }
+            }
+            _ => {
+                out.insert(key, value);
+            }
+        }
+    }
+    Ok(out)
+}
+
+/// Rename legacy keys nested under an existing `layouts` section
+fn migrate_layouts(layouts: Mapping, changes: &mut Vec<String>) -> Result<Mapping> {
+    let mut out = Mapping::new();
+    for (key, value) in layouts {
+        match key.as_str() {
+            Some("shells") => {
+                changes.push("Renamed `layouts.shells` to `layouts.panes`".to_string());
+                insert_unique(&mut out, "panes", migrate_panes(value, changes)?)?;
+            }
+            Some("panes") => {
+                insert_unique(&mut out, "panes", migrate_panes(value, changes)?)?;
+            }
+            Some("profiles") => {
+                changes.push("Renamed `layouts.profiles` to `layouts.grids`".to_string());
+                insert_unique(&mut out, "grids", value)?;
+            }
+            _ => {
+                out.insert(key, value);
+            }
+        }
+    }
+    Ok(out)
+}
+
+/// Normalize pane definitions to a list, renaming per-pane `agents` to `skills`.
+///
+/// Legacy manifests could key shells by name (`shells: { claude: {...} }`);
+/// those become list entries with an explicit `name` (and `type` if missing).
+fn migrate_panes(value: Value, changes: &mut Vec<String>) -> Result<Value> {
+    let panes = match value {
+        Value::Sequence(seq) => seq,
+        Value::Mapping(map) => {
+            changes.push("Converted keyed shell definitions to a pane list".to_string());
+            map.into_iter()
+                .map(|(name, config)| {
+                    let mut pane = Mapping::new();
+                    let config = match config {
+                        Value::Mapping(m) => m,
+                        Value::Null => Mapping::new(),
+                        _ => anyhow::bail!("Shell {:?} must be a mapping", name),
+                    };
+                    if !config.contains_key("type") {
+                        pane.insert("type".into(), name.clone());
+                    }
+                    pane.insert("name".into(), name);
+                    pane.extend(config);
+                    Ok(Value::Mapping(pane))
+                })
+                .collect::<Result<Vec<_>>>()?
+        }
+        Value::Null => Vec::new(),
+        _ => anyhow::bail!("Pane definitions must be a list"),
+    };
+
+    let panes = panes
+        .into_iter()
+        .map(|pane| match pane {
+            Value::Mapping(map) if map.contains_key("agents") => {
+                let label = map
+                    .get("name")
+                    .or_else(|| map.get("type"))
+                    .and_then(Value::as_str)
+                    .unwrap_or("?")
+                    .to_string();
+                if map.contains_key("skills") {
+                    anyhow::bail!("Pane '{}' defines both `agents` and `skills`", label);
+                }
+                changes.push(format!("Renamed `agents` to `skills` in pane '{}'", label));
+                let renamed = map
+                    .into_iter()
+                    .map(|(k, v)| match k.as_str() {
+                        Some("agents") => (Value::from("skills"), v),
+                        _ => (k, v),
+                    })
+                    .collect();
+                Ok(Value::Mapping(renamed))
+            }
+            other => Ok(other),
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(Value::Sequence(panes))
+}
+
+/// Convert bare string skill paths to `{ path: ... }` entries
+fn migrate_skill_paths(value: Value, changes: &mut Vec<String>) -> Value {
+    let Value::Sequence(entries) = value else {
+        return value;
+    };
+    let entries = entries
+        .into_iter()
+        .map(|entry| match entry {
+            Value::String(path) => {
+                changes.push(format!("Converted skill path '{}' to `- path:` form", path));
+                let mut map = Mapping::new();
+                map.insert("path".into(), Value::String(path));
+                Value::Mapping(map)
+            }
+            other => other,
+        })
+        .collect();
+    Value::Sequence(entries)
+}
+
+// =============================================================================
+// Helpers
+// =============================================================================
+
+/// Get (or create in place) the `layouts` mapping of the output manifest
+fn layouts_entry(out: &mut Mapping) -> Result<&mut Mapping> {
+    match out
+        .entry("layouts".into())
+        .or_insert_with(|| Value::Mapping(Mapping::new()))
+    {
+        Value::Mapping(map) => Ok(map),
+        _ => anyhow::bail!("`layouts` must be a mapping"),
+    }
+}
+
+/// Insert a key, failing if both a legacy and a current key produced it
+fn insert_unique(map: &mut Mapping, key: &str, value: Value) -> Result<()> {
+    if map.contains_key(key) {
+        anyhow::bail!(
+            "Manifest defines `layouts.{}` in both legacy and current form; merge them by hand",
+            key
+        );
+    }
+    map.insert(key.into(), value);
+    Ok(())
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{GridType, PaneConfig, WorkspaceConfig};
+
+    fn parse(content: &str) -> WorkspaceConfig {
+        let (yaml, _) = split_frontmatter(content).unwrap();
+        serde_yaml::from_str(yaml).unwrap()
+    }
+
+    #[test]
+    fn test_migrate_legacy_manifest() {
+        let legacy = r#"---
+name: demo
+agents:
+  - ./agents
+  - path: ~/.config/axel/skills
+shells:
+  - type: claude
+    agents: ["*"]
+  - type: custom
+    name: logs
+    command: tail -f log.txt
+profiles:
+  default:
+    type: tmux
+    claude:
+      col: 0
+      row: 0
+    logs:
+      col: 1
+      row: 0
+---
+
+# Demo workspace
+"#;
+        let migration = migrate_manifest(legacy).unwrap();
+        assert!(!migration.is_up_to_date());
+        assert!(migration.content.ends_with("---\n\n# Demo workspace\n"));
+        assert_eq!(
+            migration.changes,
+            vec![
+                "Renamed `name` to `workspace`",
+                "Renamed `agents` to `skills`",
+                "Converted skill path './agents' to `- path:` form",
+                "Moved `shells` to `layouts.panes`",
+                "Renamed `agents` to `skills` in pane 'claude'",
+                "Moved `profiles` to `layouts.grids`",
+            ]
+        );
+
+        let config = parse(&migration.content);
+        assert_eq!(config.workspace, "demo");
+        assert_eq!(config.skills.len(), 2);
+        assert_eq!(config.skills[0].path, "./agents");
+        assert_eq!(config.layouts.panes.len(), 2);
+        let PaneConfig::Claude(claude) = &config.layouts.panes[0] else {
+            panic!("expected claude pane");
+        };
+        assert_eq!(claude.skills, vec!["*"]);
+        let grid = &config.layouts.grids["default"];
+        assert_eq!(grid.grid_type, GridType::Tmux);
+        assert_eq!(grid.cells["logs"].col, 1);
+
+        // Migrating again is a no-op
+        let again = migrate_manifest(&migration.content).unwrap();
+        assert!(again.is_up_to_date());
+        assert_eq!(again.content, migration.content);
+    }
+
+    #[test]
+    fn test_migrate_plain_yaml_keyed_shells() {
+        let legacy = r#"
+name: demo
+layouts:
+  shells:
+    claude:
+      color: purple
+    server:
+      type: custom
+      command: npm run dev
+  profiles:
+    default:
+      claude: { col: 0, row: 0 }
+      server: { col: 1, row: 0 }
+"#;
+        let migration = migrate_manifest(legacy).unwrap();
+        assert!(
+            migration
+                .changes
+                .contains(&"Renamed `layouts.shells` to `layouts.panes`".into())
+        );
+        assert!(
+            migration
+                .changes
+                .contains(&"Renamed `layouts.profiles` to `layouts.grids`".into())
+        );
+        assert_eq!(
+            migration.changes.last().unwrap(),
+            "Converted plain YAML manifest to AXEL.md frontmatter"
+        );
+
+        let config = parse(&migration.content);
+        let names: Vec<_> = config.layouts.panes.iter().map(|p| p.pane_type()).collect();
+        assert_eq!(names, vec!["claude", "server"]);
+        assert_eq!(config.layouts.panes[0].color(), Some("purple"));
+        assert!(config.layouts.grids.contains_key("default"));
+    }
+
+    #[test]
+    fn test_migrate_yaml_manifest() {
+        let migration = migrate_yaml_manifest("name: demo\nlayouts:\n  panes: []\n").unwrap();
+        assert_eq!(migration.changes, vec!["Renamed `name` to `workspace`"]);
+        assert!(migration.content.starts_with("workspace: demo\n"));
+
+        // Current manifests are left alone
+        let current = "workspace: demo\nlayouts:\n  panes: []\n";
+        assert!(migrate_yaml_manifest(current).unwrap().is_up_to_date());
+    }
+
+    #[test]
+    fn test_migrate_conflicting_keys() {
+        let content = "---\nworkspace: demo\nshells: []\nlayouts:\n  panes: []\n---\n";
+        assert!(migrate_manifest(content).is_err());
+    }
+}
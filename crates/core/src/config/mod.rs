@@ -0,0 +1,2230 @@
+//! Configuration types and parsing for axel workspaces
+//!
+//! This module provides the core configuration types for axel workspaces,
+//! including workspace configuration, shell definitions, terminal profiles,
+//! and skill management.
+
+mod bundle;
+mod conditions;
+mod context;
+mod extends;
+mod includes;
+mod interpolate;
+mod lint;
+mod lock;
+mod manifest;
+mod migrate;
+mod overrides;
+mod parent;
+mod prompts;
+mod remotes;
+mod skill_cache;
+mod tags;
+mod template;
+mod validate;
+mod worktree;
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result};
+pub use bundle::{BundledFile, BundledSkill, install_bundle, pack_skills, read_bundle};
+use colored::Colorize;
+pub use includes::{SkillNode, SkillNodeStatus};
+use indexmap::IndexMap;
+pub use interpolate::interpolate;
+pub use lint::{SkillLintIssue, lint_skill_file};
+pub use lock::{LockedSkill, SKILLS_LOCK_FILE, SkillDrift, SkillsLock, skill_hash};
+pub use manifest::{
+    MANIFEST_FILE, YAML_MANIFEST_FILE, find_manifest, is_yaml_manifest, manifest_in,
+};
+pub use migrate::{Migration, migrate_manifest, migrate_yaml_manifest};
+pub use overrides::{DRIVER_SKILLS_DIR, DriverOverride, apply_driver_override};
+pub use prompts::{
+    NamedPrompt, PROMPTS_DIR, PromptSource, global_prompts, prompt_ref, prompts_dir,
+    read_prompt_file,
+};
+pub use remotes::{
+    REMOTES_DIR, SkillRemote, SyncOutcome, list_remotes, remote_for_path, remotes_dir, sync_remote,
+};
+use serde::{Deserialize, Serialize};
+pub use skill_cache::{
+    CachedSkill, SKILL_CACHE_FILE, SkillCache, skill_cache_path, with_skill_cache,
+};
+pub use tags::SKILL_TAG_PREFIX;
+pub use template::{RENDERED_SKILLS_DIR, RenderedSkills, render_template};
+pub use validate::{Severity, ValidationIssue, validate_manifest};
+pub use worktree::{WorktreeConfig, WorktreeCopy};
+
+use crate::{
+    backend::BackendKind, budget::BudgetConfig, drivers::SkillDriver, hooks::ClaudeHooksConfig,
+    orchestrator::OrchestratorConfig, policy::PolicyRule, server::NotificationsConfig,
+    stuck::StuckConfig, tmux::TmuxConfig,
+};
+
+// =============================================================================
+// Workspace Configuration
+// =============================================================================
+
+/// Main workspace configuration loaded from AXEL.md (YAML frontmatter) or
+/// a plain `axel.yaml`
+#[derive(Debug, Deserialize)]
+pub struct WorkspaceConfig {
+    /// Workspace name (used as tmux session name)
+    #[serde(alias = "name")]
+    pub workspace: String,
+    /// Layout configurations (panes + grids)
+    pub layouts: LayoutsConfig,
+    /// Agent directories configuration
+    #[serde(default)]
+    pub skills: Vec<SkillPathConfig>,
+    /// Markdown file used as the index instead of the manifest's body,
+    /// relative to the manifest; the only index of a YAML manifest
+    #[serde(default)]
+    pub index: Option<String>,
+    /// Shell command the index is piped through before it is used as a prompt
+    #[serde(default)]
+    pub index_preprocessor: Option<String>,
+    /// Start panes through a wrapper script that shows notes (default true);
+    /// panes can override this with their own `wrapper`
+    #[serde(default)]
+    pub wrapper: Option<bool>,
+    /// Multiplexer for `tmux` grids (default tmux); `zellij`, `wezterm`,
+    /// `kitty` or `windows_terminal` runs them there instead
+    #[serde(default)]
+    pub backend: Option<BackendKind>,
+    /// Parent manifest whose skill directories are inherited (`auto` for the
+    /// nearest ancestor AXEL.md)
+    #[serde(default)]
+    pub parent: Option<String>,
+    /// Desktop notifications raised by the event server
+    #[serde(default)]
+    pub notifications: Option<NotificationsConfig>,
+    /// Optional Claude Code hook events reported to the event server
+    #[serde(default)]
+    pub hooks: ClaudeHooksConfig,
+    /// Rules approving, denying or escalating Claude panes' tool calls
+    #[serde(default)]
+    pub policies: Vec<PolicyRule>,
+    /// Commit a checkpoint of the working tree after each AI pane's turn
+    #[serde(default)]
+    pub checkpoints: bool,
+    /// Values for `{{var}}` placeholders in skill files
+    #[serde(default)]
+    pub skills_vars: BTreeMap<String, String>,
+    /// Named prompts panes reference as `prompt: "@name"`
+    #[serde(default)]
+    pub prompts: BTreeMap<String, String>,
+    /// Files brought into worktrees created with `-w`
+    #[serde(default)]
+    pub worktree: WorktreeConfig,
+    /// Roles and the pipeline routing agent replies between their panes
+    #[serde(default)]
+    pub orchestrator: Option<OrchestratorConfig>,
+    /// Prefix, colors, status line and extra key bindings of tmux sessions
+    #[serde(default)]
+    pub tmux: TmuxConfig,
+    /// Path to the manifest file (set during loading, not from YAML)
+    #[serde(skip)]
+    pub manifest_path: Option<PathBuf>,
+    /// Skill directories inherited from the parent chain (set during loading)
+    #[serde(skip)]
+    pub inherited_skills_dirs: Vec<PathBuf>,
+}
+
+/// Layout configuration containing pane definitions and grid layouts
+#[derive(Debug, Deserialize, Default)]
+pub struct LayoutsConfig {
+    /// Pane definitions (AI shells, regular shells, custom commands)
+    #[serde(default)]
+    pub panes: Vec<PaneConfig>,
+    /// Grid layouts (named configurations of pane arrangements)
+    #[serde(default)]
+    pub grids: HashMap<String, Grid>,
+}
+
+/// Configuration for an skill search path
+#[derive(Debug, Deserialize, Clone)]
+pub struct SkillPathConfig {
+    /// Path to skills directory (relative to manifest or absolute)
+    pub path: String,
+}
+
+impl WorkspaceConfig {
+    /// Get all resolved skill directories that exist
+    ///
+    /// The manifest's own directories come first, followed by any inherited
+    /// from a `parent:` manifest, then synced remotes (`axel skill sync`).
+    pub fn skills_dirs(&self) -> Vec<PathBuf> {
+        let manifest_dir = self
+            .manifest_path
+            .as_ref()
+            .and_then(|p| p.parent())
+            .map(|p| p.to_path_buf());
+
+        self.skills
+            .iter()
+            .filter_map(|skill_config| {
+                let path = &skill_config.path;
+                let resolved = if path.starts_with('/') || path.starts_with('~') {
+                    PathBuf::from(expand_path(path))
+                } else if let Some(ref base) = manifest_dir {
+                    base.join(path)
+                } else {
+                    PathBuf::from(path)
+                };
+
+                if resolved.exists() {
+                    Some(resolved)
+                } else {
+                    None
+                }
+            })
+            .chain(self.inherited_skills_dirs.iter().cloned())
+            .chain(remotes::remote_skills_dirs())
+            .collect()
+    }
+
+    /// Find an skill file by name across all skill directories
+    ///
+    /// Supports both flat files (name.md) and directory structure (name/SKILL.md).
+    /// Returns the first match (priority order defined by skills config).
+    /// Warns if skill is found in multiple directories.
+    pub fn find_skill(&self, name: &str) -> Option<PathBuf> {
+        let mut candidates = self.skill_candidates(name).into_iter();
+        let first_match = candidates.next()?;
+        for ignored in candidates {
+            eprintln!(
+                "{} Duplicate skill '{}', ignoring {}",
+                "!".yellow(),
+                name,
+                ignored.display()
+            );
+        }
+        Some(first_match)
+    }
+
+    /// Every file defining skill `name`, in priority order; the first one
+    /// is the one installed
+    pub fn skill_candidates(&self, name: &str) -> Vec<PathBuf> {
+        // index.md is workspace context, not a skill
+        if name == "index" {
+            return Vec::new();
+        }
+        with_skill_cache(|cache| {
+            self.skills_dirs()
+                .iter()
+                .filter_map(|dir| {
+                    // skills/<name>/SKILL.md wins over skills/<name>.md
+                    let skills = cache.skills(dir);
+                    let mut matches = skills.iter().filter(|s| s.name == name);
+                    let first = matches.next()?;
+                    let skill = matches
+                        .find(|s| s.path.ends_with("SKILL.md"))
+                        .unwrap_or(first);
+                    Some(skill.path.clone())
+                })
+                .collect()
+        })
+    }
+
+    /// Drivers of the AI panes that install skill `name`
+    pub fn skill_drivers(&self, name: &str) -> Vec<&'static str> {
+        let mut drivers: Vec<&'static str> = Vec::new();
+        for pane in &self.layouts.panes {
+            let (driver, skills) = match pane {
+                PaneConfig::Claude(c) => ("claude", &c.skills),
+                PaneConfig::Codex(c) => ("codex", &c.skills),
+                PaneConfig::Opencode(c) => ("opencode", &c.skills),
+                PaneConfig::Antigravity(c) => ("antigravity", &c.skills),
+                PaneConfig::External(c) => match crate::drivers::external_driver(&c.pane_type) {
+                    Some(driver) => (driver.name(), &c.skills),
+                    None => continue,
+                },
+                PaneConfig::Custom(_) => continue,
+            };
+            if !drivers.contains(&driver) && self.selects_skill(skills, name) {
+                drivers.push(driver);
+            }
+        }
+        drivers
+    }
+
+    /// Find all skill files across all skill directories
+    ///
+    /// Uses priority order from config - first directory wins for conflicting names.
+    /// Returns skills in priority order (preserves insertion order via IndexMap internally).
+    pub fn find_all_skills(&self) -> Vec<PathBuf> {
+        let mut skills_by_name: IndexMap<String, PathBuf> = IndexMap::new();
+
+        with_skill_cache(|cache| {
+            for dir in self.skills_dirs() {
+                for skill in cache.skills(&dir) {
+                    if skills_by_name.contains_key(&skill.name) {
+                        eprintln!(
+                            "{} Duplicate skill '{}', ignoring {}",
+                            "!".yellow(),
+                            skill.name,
+                            dir.display()
+                        );
+                    } else {
+                        skills_by_name.insert(skill.name.clone(), skill.path.clone());
+                    }
+                }
+            }
+        });
+
+        skills_by_name.into_values().collect()
+    }
+
+    /// Resolve skill paths based on config (supports "*" for all)
+    ///
+    /// Skills listed in another skill's `includes:` are pulled in transitively.
+    pub fn resolve_skills(&self, skill_names: &[String]) -> Vec<PathBuf> {
+        if skill_names.iter().any(|n| n == "*") {
+            self.find_all_skills()
+        } else {
+            self.flatten_includes(&self.expand_skill_tags(skill_names))
+        }
+    }
+
+    /// Load and parse skills from paths
+    ///
+    /// Returns skills in priority order (IndexMap preserves insertion order).
+    #[allow(dead_code)]
+    pub fn load_skills(&self, skill_names: &[String]) -> IndexMap<String, Skill> {
+        let paths = self.resolve_skills(skill_names);
+        let mut skills = IndexMap::new();
+
+        for path in paths {
+            if let Ok(skill) = Skill::from_file(&path) {
+                skills.entry(skill.name.clone()).or_insert(skill);
+            }
+        }
+
+        skills
+    }
+
+    /// Get workspace directory (parent of manifest)
+    pub fn workspace_dir(&self) -> Option<PathBuf> {
+        self.manifest_path
+            .as_ref()
+            .and_then(|p| p.parent())
+            .map(|p| p.to_path_buf())
+    }
+
+    /// File the index is read from: `index:` if set, otherwise the body of
+    /// a markdown manifest. YAML manifests have no index without `index:`.
+    pub fn index_path(&self) -> Option<PathBuf> {
+        let manifest = self.manifest_path.as_ref()?;
+        match &self.index {
+            Some(index) => {
+                let dir = manifest.parent().unwrap_or(Path::new("."));
+                Some(dir.join(expand_path(index)))
+            }
+            None if is_yaml_manifest(manifest) => None,
+            None => Some(manifest.clone()),
+        }
+    }
+
+    /// Load the workspace context from AXEL.md
+    ///
+    /// Reads the content after the YAML frontmatter from the manifest file.
+    /// This content is used as initial context for AI assistants. When
+    /// `index_preprocessor` is set, the content is piped through it first;
+    /// a failing preprocessor is an error rather than a silently raw index.
+    pub fn load_index(&self) -> Result<Option<WorkspaceIndex>> {
+        let Some(index) = self
+            .index_path()
+            .and_then(|path| WorkspaceIndex::from_manifest(&path, &self.workspace).ok())
+        else {
+            return Ok(None);
+        };
+
+        match &self.index_preprocessor {
+            Some(command) => {
+                let dir = self.workspace_dir().unwrap_or_default();
+                index.preprocess(command, &dir).map(Some)
+            }
+            None => Ok(Some(index)),
+        }
+    }
+
+    /// Get the grid type for a given grid name (defaults to "default")
+    pub fn grid_type(&self, grid_name: Option<&str>) -> GridType {
+        let grid_name = grid_name.unwrap_or("default");
+        self.layouts
+            .grids
+            .get(grid_name)
+            .map(|g| g.grid_type)
+            .unwrap_or_default()
+    }
+
+    /// Get the named tmux layout for a grid, if one is set (defaults to "default")
+    pub fn grid_layout(&self, grid_name: Option<&str>) -> Option<TmuxLayout> {
+        let grid_name = grid_name.unwrap_or("default");
+        self.layouts.grids.get(grid_name).and_then(|g| g.layout)
+    }
+
+    /// Get the cell a grid (defaults to "default") starts focused on, and
+    /// whether it starts zoomed
+    pub fn grid_focus(&self, grid_name: Option<&str>) -> (Option<&str>, bool) {
+        let grid_name = grid_name.unwrap_or("default");
+        self.layouts
+            .grids
+            .get(grid_name)
+            .map_or((None, false), |g| (g.focus.as_deref(), g.zoomed))
+    }
+
+    /// Get which panes a `shell` grid (defaults to "default") runs
+    pub fn grid_shell_run(&self, grid_name: Option<&str>) -> ShellRun {
+        let grid_name = grid_name.unwrap_or("default");
+        self.layouts
+            .grids
+            .get(grid_name)
+            .map_or(ShellRun::default(), |g| g.run)
+    }
+
+    /// Get the backend that runs a grid (defaults to "default")
+    ///
+    /// `type: zellij`, `type: wezterm`, `type: kitty` and `type: windows_terminal` grids always use that backend;
+    /// plain `tmux` grids follow the manifest-level `backend`. Returns `None` for `shell` and `tmux_cc`
+    /// grids, which are launched without a backend or always in tmux.
+    pub fn backend_kind(&self, grid_name: Option<&str>) -> Option<BackendKind> {
+        match self.grid_type(grid_name) {
+            GridType::Zellij => Some(BackendKind::Zellij),
+            GridType::WezTerm => Some(BackendKind::WezTerm),
+            GridType::Kitty => Some(BackendKind::Kitty),
+            GridType::WindowsTerminal => Some(BackendKind::WindowsTerminal),
+            GridType::Tmux => Some(self.backend.unwrap_or_default()),
+            GridType::TmuxCC | GridType::Shell => None,
+        }
+    }
+
+    /// Resolve panes using the specified grid (defaults to "default")
+    pub fn resolve_panes(&self, grid_name: Option<&str>) -> Vec<ResolvedPane> {
+        let grid_name = grid_name.unwrap_or("default");
+        let Some(grid) = self.layouts.grids.get(grid_name) else {
+            return vec![];
+        };
+
+        // Build lookup map of pane templates by type
+        let templates: HashMap<&str, &PaneConfig> = self
+            .layouts
+            .panes
+            .iter()
+            .map(|p| (p.pane_type(), p))
+            .collect();
+
+        // Default path from manifest directory
+        let default_path = self
+            .workspace_dir()
+            .map(|p| p.to_string_lossy().to_string());
+
+        grid.cells
+            .iter()
+            .filter_map(|(cell_name, grid_cell)| {
+                let pane_type = grid_cell.pane_type.as_deref().unwrap_or(cell_name.as_str());
+
+                let template = templates.get(pane_type)?;
+
+                let mut config = (*template).clone();
+
+                if let Some(path) = grid_cell.path.as_ref().or(grid.path.as_ref()) {
+                    config.set_path(self.manifest_relative(path));
+                } else if config.path().is_none()
+                    && let Some(ref default) = default_path
+                {
+                    config.set_path(default.clone());
+                }
+
+                if let Some(ref color) = grid_cell.color {
+                    config.set_color(color.clone());
+                }
+
+                Some(ResolvedPane {
+                    name: cell_name.clone(),
+                    col: grid_cell.col,
+                    row: grid_cell.row,
+                    width: grid_cell.width,
+                    height: grid_cell.height,
+                    colspan: grid_cell.colspan.unwrap_or(1).max(1),
+                    rowspan: grid_cell.rowspan.unwrap_or(1).max(1),
+                    window: grid_cell.window.clone(),
+                    config,
+                })
+            })
+            .collect()
+    }
+
+    /// Resolve a grid or cell `path` against the manifest's directory
+    fn manifest_relative(&self, path: &str) -> String {
+        let expanded = expand_path(path);
+        match self.workspace_dir() {
+            Some(dir) if Path::new(&expanded).is_relative() => {
+                dir.join(expanded).to_string_lossy().into_owned()
+            }
+            _ => expanded,
+        }
+    }
+
+    /// Restrict a grid (defaults to "default") to the named cells
+    ///
+    /// Used by `--only` to launch a subset of a grid without defining a
+    /// separate one. See [`Grid::retain_cells`] for how positions are compacted.
+    pub fn retain_grid_cells(&mut self, grid_name: Option<&str>, names: &[String]) -> Result<()> {
+        let grid_name = grid_name.unwrap_or("default");
+        let Some(grid) = self.layouts.grids.get_mut(grid_name) else {
+            anyhow::bail!("Grid '{}' not found", grid_name);
+        };
+        grid.retain_cells(names)
+    }
+
+    /// Whether a pane is started through a wrapper script.
+    ///
+    /// The pane's own `wrapper` wins over the workspace-level one; both
+    /// default to true.
+    pub fn uses_wrapper(&self, pane: &ResolvedPane) -> bool {
+        pane.config.wrapper().or(self.wrapper).unwrap_or(true)
+    }
+
+    /// Keep scrollback in every pane (`--no-clear`), overriding per-pane `clear`
+    pub fn disable_clear(&mut self) {
+        for pane in &mut self.layouts.panes {
+            pane.set_clear(false);
+        }
+    }
+
+    /// Put `text` at the start of every AI pane's initial prompt, e.g. the
+    /// pull request a workspace was launched for
+    pub fn seed_prompt(&mut self, text: &str) {
+        for pane in &mut self.layouts.panes {
+            if let PaneConfig::Claude(c)
+            | PaneConfig::Codex(c)
+            | PaneConfig::Opencode(c)
+            | PaneConfig::Antigravity(c)
+            | PaneConfig::External(c) = pane
+            {
+                c.prompt = Some(match c.prompt.take() {
+                    Some(prompt) => format!("{}\n\n{}", text, prompt),
+                    None => text.to_string(),
+                });
+            }
+        }
+    }
+
+    /// Get the profile type for a given profile name (legacy alias for grid_type)
+    #[deprecated(note = "Use grid_type instead")]
+    pub fn profile_type(&self, profile_name: Option<&str>) -> GridType {
+        self.grid_type(profile_name)
+    }
+}
+
+// =============================================================================
+// Skill Types
+// =============================================================================
+
+/// Parsed skill ready for AI tool configuration
+#[derive(Debug, Clone, Serialize)]
+pub struct Skill {
+    /// Agent name (derived from filename or frontmatter)
+    #[serde(skip)]
+    pub name: String,
+    /// Description of when to use this skill
+    pub description: String,
+    /// The system prompt content
+    pub prompt: String,
+    /// Optional list of allowed tools
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tools: Option<Vec<String>>,
+    /// Optional model to use
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    /// Other skills this one builds on (installed alongside it)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub includes: Vec<String>,
+    /// Tags panes can select the skill by (`tag:<name>`)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Frontmatter overrides by driver name
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub drivers: BTreeMap<String, DriverOverride>,
+}
+
+/// YAML frontmatter for skill files
+#[derive(Debug, Deserialize, Default)]
+struct SkillFrontmatter {
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    tools: Option<String>,
+    #[serde(default)]
+    model: Option<String>,
+    #[serde(default)]
+    includes: Vec<String>,
+    #[serde(default)]
+    tags: Vec<String>,
+    #[serde(default)]
+    drivers: BTreeMap<String, DriverOverride>,
+}
+
+impl Skill {
+    /// Parse an skill from a markdown file with optional YAML frontmatter
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)?;
+
+        // Derive name from path
+        let name = if path.file_name().map(|n| n == "SKILL.md").unwrap_or(false) {
+            path.parent()
+                .and_then(|p| p.file_name())
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_else(|| "skill".to_string())
+        } else {
+            path.file_stem()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_else(|| "skill".to_string())
+        };
+
+        // Parse YAML frontmatter
+        let (frontmatter, prompt) = if let Some(after_start) = content.strip_prefix("---") {
+            if let Some(end_idx) = after_start.find("\n---") {
+                let fm_content = &after_start[..end_idx];
+                let rest = &after_start[end_idx + 4..];
+                let fm: SkillFrontmatter = serde_yaml::from_str(fm_content).unwrap_or_default();
+                (fm, rest.trim().to_string())
+            } else {
+                (SkillFrontmatter::default(), content)
+            }
+        } else {
+            (SkillFrontmatter::default(), content)
+        };
+
+        let name = frontmatter.name.unwrap_or(name);
+
+        let description = frontmatter.description.unwrap_or_else(|| {
+            prompt
+                .lines()
+                .find(|l| !l.trim().is_empty() && !l.starts_with('#'))
+                .or_else(|| prompt.lines().next())
+                .map(|l| l.trim_start_matches('#').trim().to_string())
+                .unwrap_or_else(|| format!("{} skill", name))
+        });
+
+        let tools = frontmatter.tools.map(|t| {
+            t.split(',')
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+                .collect()
+        });
+
+        Ok(Skill {
+            name,
+            description,
+            prompt,
+            tools,
+            model: frontmatter.model,
+            includes: frontmatter.includes,
+            tags: frontmatter.tags,
+            drivers: frontmatter.drivers,
+        })
+    }
+}
+
+// =============================================================================
+// Workspace Index
+// =============================================================================
+
+/// Workspace index - project context from AXEL.md
+///
+/// This is NOT a skill - it's a project description used as initial context.
+/// The content is extracted from AXEL.md after the YAML frontmatter.
+#[derive(Debug, Clone)]
+pub struct WorkspaceIndex {
+    /// Project name (from workspace config)
+    pub name: String,
+    /// Project description from frontmatter
+    pub description: Option<String>,
+    /// Full markdown content (after frontmatter)
+    pub content: String,
+}
+
+impl WorkspaceIndex {
+    /// Parse a workspace index from the AXEL.md manifest file
+    ///
+    /// Extracts the content after the YAML frontmatter, which contains
+    /// project documentation used as context for AI assistants.
+    pub fn from_manifest(path: &Path, workspace_name: &str) -> Result<Self> {
+        let raw_content = std::fs::read_to_string(path)?;
+
+        // Extract content after YAML frontmatter
+        let content = if let Some(after_start) = raw_content.strip_prefix("---") {
+            if let Some(end_idx) = after_start.find("\n---") {
+                after_start[end_idx + 4..].trim().to_string()
+            } else {
+                String::new()
+            }
+        } else {
+            raw_content.trim().to_string()
+        };
+
+        // Return None-equivalent if no content after frontmatter
+        if content.is_empty() {
+            anyhow::bail!("No content after frontmatter in {}", path.display());
+        }
+
+        Ok(WorkspaceIndex {
+            name: workspace_name.to_string(),
+            description: None,
+            content,
+        })
+    }
+
+    /// Pipe the index content through a shell command, replacing it with stdout.
+    ///
+    /// The command runs via `sh -c` in `dir` with the content on stdin.
+    pub fn preprocess(mut self, command: &str, dir: &Path) -> Result<Self> {
+        use std::{
+            io::Write,
+            process::{Command, Stdio},
+        };
+
+        let mut child = Command::new("sh")
+            .args(["-c", command])
+            .current_dir(dir)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .with_context(|| format!("Failed to run index_preprocessor `{}`", command))?;
+
+        // Feed stdin from a thread so large indexes can't deadlock on a full pipe
+        let mut stdin = child.stdin.take().expect("stdin is piped");
+        let content = std::mem::take(&mut self.content);
+        let writer = std::thread::spawn(move || stdin.write_all(content.as_bytes()));
+        let output = child.wait_with_output()?;
+        writer.join().ok();
+
+        if !output.status.success() {
+            anyhow::bail!(
+                "index_preprocessor `{}` failed ({}): {}",
+                command,
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+
+        self.content = String::from_utf8(output.stdout)
+            .context("index_preprocessor output is not valid UTF-8")?
+            .trim()
+            .to_string();
+        Ok(self)
+    }
+
+    /// Build the initial prompt to send to Claude/Codex
+    pub fn to_initial_prompt(&self) -> String {
+        format!(
+            "Context: You're working on a project called {}. Here's the project documentation:\n\n{}\n\n---\nAwaiting your instructions.",
+            self.name, self.content
+        )
+    }
+}
+
+// =============================================================================
+// Grid Configuration
+// =============================================================================
+
+/// Grid type (how the layout is rendered)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum GridType {
+    /// Standard tmux session with panes
+    #[default]
+    Tmux,
+    /// iTerm2 tmux control mode (-CC)
+    TmuxCC,
+    /// Direct shell execution in the terminal, no multiplexer (see [`ShellRun`])
+    Shell,
+    /// Zellij session laid out from the grid
+    Zellij,
+    /// WezTerm workspace built from the grid
+    WezTerm,
+    /// kitty OS window built from the grid
+    Kitty,
+    /// Windows Terminal window with a tab per pane
+    WindowsTerminal,
+}
+
+impl<'de> serde::Deserialize<'de> for GridType {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        match s.as_str() {
+            "tmux" => Ok(GridType::Tmux),
+            "tmux_cc" => Ok(GridType::TmuxCC),
+            "shell" => Ok(GridType::Shell),
+            "zellij" => Ok(GridType::Zellij),
+            "wezterm" => Ok(GridType::WezTerm),
+            "kitty" => Ok(GridType::Kitty),
+            "windows_terminal" => Ok(GridType::WindowsTerminal),
+            _ => Err(serde::de::Error::custom(format!(
+                "unknown grid type: {} (expected tmux, tmux_cc, shell, zellij, wezterm, kitty, or windows_terminal)",
+                s
+            ))),
+        }
+    }
+}
+
+/// Named tmux layout, applied with `select-layout` after the cells are created
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TmuxLayout {
+    /// Panes spread out evenly from left to right
+    EvenHorizontal,
+    /// Panes spread out evenly from top to bottom
+    EvenVertical,
+    /// One large pane on top, the rest spread out left to right below
+    MainHorizontal,
+    /// One large pane on the left, the rest spread out top to bottom
+    MainVertical,
+    /// Panes spread out as evenly as possible in rows and columns
+    Tiled,
+}
+
+impl TmuxLayout {
+    /// Layout name as understood by `tmux select-layout`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TmuxLayout::EvenHorizontal => "even-horizontal",
+            TmuxLayout::EvenVertical => "even-vertical",
+            TmuxLayout::MainHorizontal => "main-horizontal",
+            TmuxLayout::MainVertical => "main-vertical",
+            TmuxLayout::Tiled => "tiled",
+        }
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for TmuxLayout {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        match s.as_str() {
+            "even-horizontal" => Ok(TmuxLayout::EvenHorizontal),
+            "even-vertical" => Ok(TmuxLayout::EvenVertical),
+            "main-horizontal" => Ok(TmuxLayout::MainHorizontal),
+            "main-vertical" => Ok(TmuxLayout::MainVertical),
+            "tiled" => Ok(TmuxLayout::Tiled),
+            _ => Err(serde::de::Error::custom(format!(
+                "unknown layout: {} (expected even-horizontal, even-vertical, main-horizontal, main-vertical, or tiled)",
+                s
+            ))),
+        }
+    }
+}
+
+/// Which panes a `shell` grid runs, set with the grid's `run:` key
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ShellRun {
+    /// Exec the first pane in place of axel
+    #[default]
+    First,
+    /// Run every pane as a background job, prefixing its output with the pane name
+    Jobs,
+    /// Ask which pane to run, then exec it
+    Pick,
+}
+
+impl<'de> serde::Deserialize<'de> for ShellRun {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        match s.as_str() {
+            "first" => Ok(ShellRun::First),
+            "jobs" => Ok(ShellRun::Jobs),
+            "pick" => Ok(ShellRun::Pick),
+            _ => Err(serde::de::Error::custom(format!(
+                "unknown run mode: {} (expected first, jobs, or pick)",
+                s
+            ))),
+        }
+    }
+}
+
+/// A grid layout with type and cell definitions
+#[derive(Debug, Clone)]
+pub struct Grid {
+    /// Grid type (tmux, tmux_cc, shell, zellij, wezterm, kitty, windows_terminal)
+    pub grid_type: GridType,
+    /// Named tmux layout; when set, cell width/height are ignored
+    pub layout: Option<TmuxLayout>,
+    /// Working directory for every pane of the grid, relative to the manifest
+    pub path: Option<String>,
+    /// Cell selected when the session starts (default: the top-left one)
+    pub focus: Option<String>,
+    /// Start with the focused pane zoomed to fill its window
+    pub zoomed: bool,
+    /// Panes run by a `shell` grid
+    pub run: ShellRun,
+    /// Cell definitions (pane placements)
+    pub cells: IndexMap<String, GridCell>,
+}
+
+impl<'de> serde::Deserialize<'de> for Grid {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let mut map: IndexMap<String, serde_yaml::Value> = IndexMap::deserialize(deserializer)?;
+
+        let grid_type = if let Some(type_value) = map.shift_remove("type") {
+            serde_yaml::from_value(type_value).map_err(serde::de::Error::custom)?
+        } else {
+            GridType::default()
+        };
+
+        let layout = map
+            .shift_remove("layout")
+            .map(serde_yaml::from_value)
+            .transpose()
+            .map_err(serde::de::Error::custom)?;
+
+        let path = map
+            .shift_remove("path")
+            .map(serde_yaml::from_value)
+            .transpose()
+            .map_err(serde::de::Error::custom)?;
+
+        let focus = map
+            .shift_remove("focus")
+            .map(serde_yaml::from_value)
+            .transpose()
+            .map_err(serde::de::Error::custom)?;
+
+        let zoomed = map
+            .shift_remove("zoomed")
+            .map(serde_yaml::from_value)
+            .transpose()
+            .map_err(serde::de::Error::custom)?
+            .unwrap_or(false);
+
+        let run = map
+            .shift_remove("run")
+            .map(serde_yaml::from_value)
+            .transpose()
+            .map_err(serde::de::Error::custom)?
+            .unwrap_or_default();
+
+        let cells: IndexMap<String, GridCell> = map
+            .into_iter()
+            .filter_map(|(k, v)| serde_yaml::from_value(v).ok().map(|cell| (k, cell)))
+            .collect();
+
+        Ok(Grid {
+            grid_type,
+            layout,
+            path,
+            focus,
+            zoomed,
+            run,
+            cells,
+        })
+    }
+}
+
+impl Grid {
+    /// Keep only the named cells, re-normalizing positions into a compact grid
+    ///
+    /// Remaining columns are renumbered left to right starting at 0, and rows
+    /// within each column likewise, so the layout has no empty gaps.
+    /// Fails if a name does not match any cell in the grid.
+    pub fn retain_cells(&mut self, names: &[String]) -> Result<()> {
+        if let Some(missing) = names.iter().find(|n| !self.cells.contains_key(n.as_str())) {
+            anyhow::bail!(
+                "Pane '{}' not found in grid. Available panes: {}",
+                missing,
+                self.cells.keys().cloned().collect::<Vec<_>>().join(", ")
+            );
+        }
+
+        self.cells.retain(|name, _| names.contains(name));
+
+        let mut windows: Vec<Option<String>> =
+            self.cells.values().map(|c| c.window.clone()).collect();
+        windows.sort();
+        windows.dedup();
+        for window in windows {
+            let in_window = |c: &GridCell| c.window == window;
+
+            let cols =
+                compact_positions(self.cells.values().filter(|c| in_window(c)).map(|c| c.col));
+            for cell in self.cells.values_mut().filter(|c| in_window(c)) {
+                cell.col = cols[&cell.col];
+            }
+
+            for col in 0..cols.len() as u32 {
+                let rows = compact_positions(
+                    self.cells
+                        .values()
+                        .filter(|c| in_window(c) && c.col == col)
+                        .map(|c| c.row),
+                );
+                for cell in self
+                    .cells
+                    .values_mut()
+                    .filter(|c| in_window(c) && c.col == col)
+                {
+                    cell.row = rows[&cell.row];
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Map sparse positions to consecutive indices (e.g. 0, 2, 5 -> 0, 1, 2)
+fn compact_positions(positions: impl Iterator<Item = u32>) -> HashMap<u32, u32> {
+    let mut sorted: Vec<u32> = positions.collect();
+    sorted.sort_unstable();
+    sorted.dedup();
+    sorted
+        .into_iter()
+        .enumerate()
+        .map(|(idx, pos)| (pos, idx as u32))
+        .collect()
+}
+
+/// Cell entry in a grid (references a pane definition)
+#[derive(Debug, Deserialize, Default, Clone)]
+pub struct GridCell {
+    /// Reference to a pane type defined in layouts.panes
+    pub pane_type: Option<String>,
+    /// Column position
+    #[serde(default)]
+    pub col: u32,
+    /// Row position
+    #[serde(default)]
+    pub row: u32,
+    /// Width percentage
+    #[serde(default)]
+    pub width: Option<u32>,
+    /// Height percentage
+    #[serde(default)]
+    pub height: Option<u32>,
+    /// Number of columns the cell spans (default 1, tmux grids)
+    #[serde(default)]
+    pub colspan: Option<u32>,
+    /// Number of rows the cell spans (default 1, tmux grids)
+    #[serde(default)]
+    pub rowspan: Option<u32>,
+    /// Override color from pane definition
+    #[serde(default)]
+    pub color: Option<String>,
+    /// Window the cell is placed in (cells without one share the first window)
+    #[serde(default)]
+    pub window: Option<String>,
+    /// Override the pane's working directory (and the grid's), relative to
+    /// the manifest
+    #[serde(default)]
+    pub path: Option<String>,
+}
+
+// =============================================================================
+// Pane Configuration
+// =============================================================================
+
+/// Raw pane config for deserialization
+#[derive(Debug, Default, Deserialize)]
+struct PaneConfigRaw {
+    #[serde(rename = "type")]
+    pane_type: String,
+    /// Unique name for the pane (used to reference in grids)
+    /// For AI types (claude, codex, etc.), defaults to the type name
+    /// For custom types, this must be provided to create unique identifiers
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    path: Option<String>,
+    #[serde(default)]
+    color: Option<String>,
+    #[serde(default)]
+    notes: Vec<Note>,
+    #[serde(default)]
+    model: Option<String>,
+    #[serde(default)]
+    skills: Vec<String>,
+    #[serde(default)]
+    allowed_tools: Vec<String>,
+    #[serde(default)]
+    disallowed_tools: Vec<String>,
+    #[serde(default)]
+    prompt: Option<String>,
+    #[serde(default)]
+    prompt_file: Option<String>,
+    #[serde(default)]
+    context_files: Vec<String>,
+    #[serde(default)]
+    args: Vec<String>,
+    #[serde(default)]
+    mcp: BTreeMap<String, McpServer>,
+    #[serde(default)]
+    resume: Option<ResumeConfig>,
+    #[serde(default)]
+    session_id: Option<String>,
+    #[serde(default)]
+    permission_mode: Option<String>,
+    #[serde(default)]
+    output_format: Option<String>,
+    #[serde(default)]
+    max_turns: Option<u32>,
+    #[serde(default)]
+    add_dirs: Vec<String>,
+    #[serde(default)]
+    budget: Option<BudgetConfig>,
+    #[serde(default)]
+    stuck: Option<StuckConfig>,
+    #[serde(default)]
+    command: Option<String>,
+    #[serde(default)]
+    startup_command: Option<String>,
+    #[serde(default)]
+    clear: Option<bool>,
+    #[serde(default)]
+    wrapper: Option<bool>,
+}
+
+impl PaneConfigRaw {
+    /// Build the config shared by all AI pane types
+    fn into_ai(self) -> AiPaneConfig {
+        AiPaneConfig {
+            name: self.name.or(Some(self.pane_type.clone())),
+            pane_type: self.pane_type,
+            path: self.path,
+            color: self.color,
+            notes: self.notes,
+            startup_command: self.startup_command,
+            clear: self.clear,
+            wrapper: self.wrapper,
+            model: self.model,
+            skills: self.skills,
+            allowed_tools: self.allowed_tools,
+            disallowed_tools: self.disallowed_tools,
+            prompt: self.prompt,
+            prompt_file: self.prompt_file,
+            context_files: self.context_files,
+            args: self.args,
+            mcp: self.mcp,
+            resume: self.resume,
+            session_id: self.session_id,
+            permission_mode: self.permission_mode,
+            output_format: self.output_format,
+            max_turns: self.max_turns,
+            add_dirs: self.add_dirs,
+            budget: self.budget,
+            stuck: self.stuck,
+        }
+    }
+}
+
+/// Pane configuration - known AI types or custom shell types
+#[derive(Debug, Clone)]
+pub enum PaneConfig {
+    /// Claude Code shell
+    Claude(AiPaneConfig),
+    /// Codex shell
+    Codex(AiPaneConfig),
+    /// OpenCode shell
+    Opencode(AiPaneConfig),
+    /// Google Antigravity shell
+    Antigravity(AiPaneConfig),
+    /// Tool described by an external driver (`~/.config/axel/drivers/`),
+    /// whose type is the driver's name
+    External(AiPaneConfig),
+    /// Custom shell with arbitrary command
+    Custom(CustomPaneConfig),
+}
+
+impl<'de> serde::Deserialize<'de> for PaneConfig {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let raw = PaneConfigRaw::deserialize(deserializer)?;
+        PaneConfig::from_raw(raw, |name| crate::drivers::external_driver(name).is_some())
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+impl PaneConfig {
+    /// Pane a manifest entry giving only `type: <pane_type>` describes, if
+    /// that is valid on its own
+    pub fn of_type(pane_type: &str) -> Option<Self> {
+        let raw = PaneConfigRaw {
+            pane_type: pane_type.to_string(),
+            ..Default::default()
+        };
+        Self::from_raw(raw, |name| crate::drivers::external_driver(name).is_some()).ok()
+    }
+
+    /// Pane of a raw manifest entry; types `is_driver` accepts are external
+    /// drivers' panes
+    fn from_raw(raw: PaneConfigRaw, is_driver: impl Fn(&str) -> bool) -> Result<Self, String> {
+        match raw.pane_type.as_str() {
+            "claude" => Ok(PaneConfig::Claude(raw.into_ai())),
+            "codex" => Ok(PaneConfig::Codex(raw.into_ai())),
+            "opencode" => Ok(PaneConfig::Opencode(raw.into_ai())),
+            "antigravity" => Ok(PaneConfig::Antigravity(raw.into_ai())),
+            // "custom" type requires a name field
+            "custom" => {
+                let name = raw.name.ok_or("custom pane type requires a 'name' field")?;
+                Ok(PaneConfig::Custom(CustomPaneConfig {
+                    pane_type: raw.pane_type,
+                    name,
+                    path: raw.path,
+                    color: raw.color,
+                    command: raw.command,
+                    notes: raw.notes,
+                    startup_command: raw.startup_command,
+                    clear: raw.clear,
+                    wrapper: raw.wrapper,
+                }))
+            }
+            _ if is_driver(&raw.pane_type) => Ok(PaneConfig::External(raw.into_ai())),
+            // Legacy: "shell" and other unknown types become custom panes
+            // The type becomes the name for backwards compatibility
+            _ => Ok(PaneConfig::Custom(CustomPaneConfig {
+                pane_type: "custom".to_string(),
+                name: raw.name.unwrap_or(raw.pane_type),
+                path: raw.path,
+                color: raw.color,
+                command: raw.command,
+                notes: raw.notes,
+                startup_command: raw.startup_command,
+                clear: raw.clear,
+                wrapper: raw.wrapper,
+            })),
+        }
+    }
+}
+
+impl PaneConfig {
+    /// Get the unique pane identifier (name) for referencing in grids
+    /// For AI panes, this defaults to the type (claude, codex, etc.) unless overridden
+    /// For custom panes, this is the required name field
+    pub fn pane_type(&self) -> &str {
+        match self {
+            PaneConfig::Claude(c)
+            | PaneConfig::Codex(c)
+            | PaneConfig::Opencode(c)
+            | PaneConfig::Antigravity(c)
+            | PaneConfig::External(c) => c.name.as_deref().unwrap_or(&c.pane_type),
+            PaneConfig::Custom(c) => &c.name,
+        }
+    }
+
+    /// Get the actual type (claude, codex, custom, etc.)
+    pub fn actual_type(&self) -> &str {
+        match self {
+            PaneConfig::Claude(c)
+            | PaneConfig::Codex(c)
+            | PaneConfig::Opencode(c)
+            | PaneConfig::Antigravity(c)
+            | PaneConfig::External(c) => &c.pane_type,
+            PaneConfig::Custom(c) => &c.pane_type,
+        }
+    }
+
+    /// Whether this is an AI pane (vs custom command)
+    pub fn is_ai(&self) -> bool {
+        !matches!(self, PaneConfig::Custom(_))
+    }
+
+    /// Get the color if set
+    pub fn color(&self) -> Option<&str> {
+        match self {
+            PaneConfig::Claude(c)
+            | PaneConfig::Codex(c)
+            | PaneConfig::Opencode(c)
+            | PaneConfig::Antigravity(c)
+            | PaneConfig::External(c) => c.color.as_deref(),
+            PaneConfig::Custom(c) => c.color.as_deref(),
+        }
+    }
+
+    /// Set the color
+    pub fn set_color(&mut self, color: String) {
+        match self {
+            PaneConfig::Claude(c)
+            | PaneConfig::Codex(c)
+            | PaneConfig::Opencode(c)
+            | PaneConfig::Antigravity(c)
+            | PaneConfig::External(c) => {
+                c.color = Some(color);
+            }
+            PaneConfig::Custom(c) => c.color = Some(color),
+        }
+    }
+
+    /// Get the path if set
+    pub fn path(&self) -> Option<&str> {
+        match self {
+            PaneConfig::Claude(c)
+            | PaneConfig::Codex(c)
+            | PaneConfig::Opencode(c)
+            | PaneConfig::Antigravity(c)
+            | PaneConfig::External(c) => c.path.as_deref(),
+            PaneConfig::Custom(c) => c.path.as_deref(),
+        }
+    }
+
+    /// Set the path
+    pub fn set_path(&mut self, path: String) {
+        match self {
+            PaneConfig::Claude(c)
+            | PaneConfig::Codex(c)
+            | PaneConfig::Opencode(c)
+            | PaneConfig::Antigravity(c)
+            | PaneConfig::External(c) => {
+                c.path = Some(path);
+            }
+            PaneConfig::Custom(c) => c.path = Some(path),
+        }
+    }
+
+    /// Get notes
+    pub fn notes(&self) -> &[Note] {
+        match self {
+            PaneConfig::Claude(c)
+            | PaneConfig::Codex(c)
+            | PaneConfig::Opencode(c)
+            | PaneConfig::Antigravity(c)
+            | PaneConfig::External(c) => &c.notes,
+            PaneConfig::Custom(c) => &c.notes,
+        }
+    }
+
+    /// Get the startup command if set
+    pub fn startup_command(&self) -> Option<&str> {
+        match self {
+            PaneConfig::Claude(c)
+            | PaneConfig::Codex(c)
+            | PaneConfig::Opencode(c)
+            | PaneConfig::Antigravity(c)
+            | PaneConfig::External(c) => c.startup_command.as_deref(),
+            PaneConfig::Custom(c) => c.startup_command.as_deref(),
+        }
+    }
+
+    /// Set the command run before the pane starts
+    pub fn set_startup_command(&mut self, startup: String) {
+        match self {
+            PaneConfig::Claude(c)
+            | PaneConfig::Codex(c)
+            | PaneConfig::Opencode(c)
+            | PaneConfig::Antigravity(c)
+            | PaneConfig::External(c) => c.startup_command = Some(startup),
+            PaneConfig::Custom(c) => c.startup_command = Some(startup),
+        }
+    }
+
+    /// Whether the pane is cleared before its header is printed (default true)
+    pub fn clear(&self) -> bool {
+        match self {
+            PaneConfig::Claude(c)
+            | PaneConfig::Codex(c)
+            | PaneConfig::Opencode(c)
+            | PaneConfig::Antigravity(c)
+            | PaneConfig::External(c) => c.clear,
+            PaneConfig::Custom(c) => c.clear,
+        }
+        .unwrap_or(true)
+    }
+
+    /// Whether the pane is started through a wrapper script, if set on the pane
+    pub fn wrapper(&self) -> Option<bool> {
+        match self {
+            PaneConfig::Claude(c)
+            | PaneConfig::Codex(c)
+            | PaneConfig::Opencode(c)
+            | PaneConfig::Antigravity(c)
+            | PaneConfig::External(c) => c.wrapper,
+            PaneConfig::Custom(c) => c.wrapper,
+        }
+    }
+
+    /// Set whether the pane is cleared
+    pub fn set_clear(&mut self, clear: bool) {
+        match self {
+            PaneConfig::Claude(c)
+            | PaneConfig::Codex(c)
+            | PaneConfig::Opencode(c)
+            | PaneConfig::Antigravity(c)
+            | PaneConfig::External(c) => c.clear = Some(clear),
+            PaneConfig::Custom(c) => c.clear = Some(clear),
+        }
+    }
+}
+
+/// Configuration for AI panes (claude, codex, opencode, antigravity)
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct AiPaneConfig {
+    /// The pane type identifier (claude, codex, etc.)
+    #[serde(default, rename = "type")]
+    pub pane_type: String,
+    /// Unique name for referencing in grids (defaults to pane_type)
+    #[serde(default)]
+    pub name: Option<String>,
+    /// Working directory path
+    #[serde(default)]
+    pub path: Option<String>,
+    /// Pane background color
+    #[serde(default)]
+    pub color: Option<String>,
+    /// Notes to display in pane header
+    #[serde(default)]
+    pub notes: Vec<Note>,
+    /// Shell command run in the pane before the AI command starts
+    #[serde(default)]
+    pub startup_command: Option<String>,
+    /// Clear the pane before printing its header (default true)
+    #[serde(default)]
+    pub clear: Option<bool>,
+    /// Start the pane through a wrapper script (default: workspace `wrapper`)
+    #[serde(default)]
+    pub wrapper: Option<bool>,
+    /// Model to use (e.g., "sonnet", "opus")
+    #[serde(default)]
+    pub model: Option<String>,
+    /// Agents to load - use "*" for all, or list specific names
+    #[serde(default)]
+    pub skills: Vec<String>,
+    /// Allowed tools
+    #[serde(default)]
+    pub allowed_tools: Vec<String>,
+    /// Disallowed tools
+    #[serde(default)]
+    pub disallowed_tools: Vec<String>,
+    /// Initial prompt to send
+    #[serde(default)]
+    pub prompt: Option<String>,
+    /// File the initial prompt is read from (relative to the workspace)
+    #[serde(default)]
+    pub prompt_file: Option<String>,
+    /// Files to start the agent with (relative to the workspace)
+    #[serde(default)]
+    pub context_files: Vec<String>,
+    /// Additional CLI arguments
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// MCP servers the agent can use, by name (claude and opencode)
+    #[serde(default)]
+    pub mcp: BTreeMap<String, McpServer>,
+    /// Pick up an earlier conversation instead of starting fresh (claude)
+    #[serde(default)]
+    pub resume: Option<ResumeConfig>,
+    /// ID for a new conversation, so it can be resumed by ID (claude)
+    #[serde(default)]
+    pub session_id: Option<String>,
+    /// Permission mode, e.g. "plan" or "acceptEdits" (claude, opencode)
+    #[serde(default)]
+    pub permission_mode: Option<String>,
+    /// Output format in print mode: "text", "json" or "stream-json" (claude)
+    #[serde(default)]
+    pub output_format: Option<String>,
+    /// Maximum number of agentic turns in print mode (claude)
+    #[serde(default)]
+    pub max_turns: Option<u32>,
+    /// Additional directories the agent may access (claude)
+    #[serde(default)]
+    pub add_dirs: Vec<String>,
+    /// Tokens, cost and turns the agent may use before the event server
+    /// interrupts it
+    #[serde(default)]
+    pub budget: Option<BudgetConfig>,
+    /// Minutes without activity mid-task before the event server reports
+    /// the pane as stuck
+    #[serde(default)]
+    pub stuck: Option<StuckConfig>,
+}
+
+/// Conversation an AI pane resumes
+///
+/// `resume: true` continues the most recent conversation in the pane's
+/// directory; `resume: <session-id>` resumes that conversation. The pane's
+/// initial prompt is not sent again when resuming.
+#[derive(Debug, Deserialize, Clone, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum ResumeConfig {
+    Last(bool),
+    Session(String),
+}
+
+/// An MCP server started by the agent over stdio
+#[derive(Debug, Deserialize, Serialize, Clone, Default, PartialEq, Eq)]
+pub struct McpServer {
+    /// Executable to run
+    pub command: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// Environment variables set for the server
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
+}
+
+/// Configuration for custom pane types
+#[derive(Debug, Clone)]
+pub struct CustomPaneConfig {
+    /// The type (e.g., "custom", "shell", or a custom type name)
+    pub pane_type: String,
+    /// Unique name for referencing in grids (required for custom panes)
+    pub name: String,
+    /// Working directory path
+    pub path: Option<String>,
+    /// Pane background color
+    pub color: Option<String>,
+    /// Command to execute
+    pub command: Option<String>,
+    /// Notes to display in pane header
+    pub notes: Vec<Note>,
+    /// Shell command run in the pane before its command starts
+    pub startup_command: Option<String>,
+    /// Clear the pane before printing its header (default true)
+    pub clear: Option<bool>,
+    /// Start the pane through a wrapper script (default: workspace `wrapper`)
+    pub wrapper: Option<bool>,
+}
+
+impl Default for CustomPaneConfig {
+    fn default() -> Self {
+        Self {
+            pane_type: "custom".to_string(),
+            name: "shell".to_string(),
+            path: None,
+            color: None,
+            command: None,
+            notes: Vec::new(),
+            startup_command: None,
+            clear: None,
+            wrapper: None,
+        }
+    }
+}
+
+/// A line (or lines) shown in the pane header before the shell starts.
+///
+/// Plain strings are displayed as-is. `{ file: ... }` and `{ cmd: ... }` are
+/// evaluated once at launch, relative to the pane's working directory, so the
+/// header can show dynamic info like recent commits or open TODOs.
+#[derive(Debug, Deserialize, Clone, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum Note {
+    /// Static note text
+    Text(String),
+    /// Contents of a file
+    File { file: String },
+    /// Standard output of a shell command
+    Cmd { cmd: String },
+}
+
+impl Note {
+    /// Evaluate the note into display lines.
+    ///
+    /// Relative file paths and commands are resolved against `dir`. Failures
+    /// produce a single explanatory line rather than aborting the launch.
+    /// Blank lines are dropped so the header stays compact.
+    pub fn lines(&self, dir: &Path) -> Vec<String> {
+        let text = match self {
+            Note::Text(text) => return vec![text.trim().to_string()],
+            Note::File { file } => match std::fs::read_to_string(dir.join(expand_path(file))) {
+                Ok(content) => content,
+                Err(e) => return vec![format!("({}: {})", file, e)],
+            },
+            Note::Cmd { cmd } => match std::process::Command::new("sh")
+                .args(["-c", cmd])
+                .current_dir(dir)
+                .output()
+            {
+                Ok(output) if output.status.success() => {
+                    String::from_utf8_lossy(&output.stdout).into_owned()
+                }
+                Ok(output) => return vec![format!("({}: {})", cmd, output.status)],
+                Err(e) => return vec![format!("({}: {})", cmd, e)],
+            },
+        };
+
+        text.lines()
+            .map(str::trim_end)
+            .filter(|line| !line.trim().is_empty())
+            .map(String::from)
+            .collect()
+    }
+}
+
+/// Resolved pane with config and layout merged
+#[derive(Debug, Clone)]
+pub struct ResolvedPane {
+    /// Pane name
+    pub name: String,
+    /// Column position
+    pub col: u32,
+    /// Row position
+    pub row: u32,
+    /// Width percentage
+    pub width: Option<u32>,
+    /// Height percentage
+    pub height: Option<u32>,
+    /// Number of columns the pane spans (at least 1)
+    pub colspan: u32,
+    /// Number of rows the pane spans (at least 1)
+    pub rowspan: u32,
+    /// Window name (`None` for the first window)
+    pub window: Option<String>,
+    /// Pane configuration
+    pub config: PaneConfig,
+}
+
+impl ResolvedPane {
+    /// Get the path if set
+    pub fn path(&self) -> Option<&str> {
+        self.config.path()
+    }
+
+    /// Get the color if set
+    pub fn color(&self) -> Option<&str> {
+        self.config.color()
+    }
+
+    /// Get notes
+    pub fn notes(&self) -> &[Note] {
+        self.config.notes()
+    }
+
+    /// Get the startup command if set
+    pub fn startup_command(&self) -> Option<&str> {
+        self.config.startup_command()
+    }
+
+    /// Whether the pane is cleared before its header is printed
+    pub fn clear(&self) -> bool {
+        self.config.clear()
+    }
+}
+
+/// Group panes by window, in the order the windows are created.
+///
+/// Panes without a `window` make up the first window; named windows follow
+/// in the order they first appear. Each window's panes keep their order.
+pub fn group_by_window(panes: &[ResolvedPane]) -> Vec<(Option<&str>, Vec<&ResolvedPane>)> {
+    let mut windows: Vec<(Option<&str>, Vec<&ResolvedPane>)> = Vec::new();
+    for pane in panes {
+        let name = pane.window.as_deref();
+        match windows.iter_mut().find(|(w, _)| *w == name) {
+            Some((_, window_panes)) => window_panes.push(pane),
+            None => windows.push((name, vec![pane])),
+        }
+    }
+    windows.sort_by_key(|(name, _)| name.is_some());
+    windows
+}
+
+// =============================================================================
+// Helper Functions
+// =============================================================================
+
+/// Get the workspaces directory
+pub fn workspaces_dir() -> PathBuf {
+    PathBuf::from("/Users/ludovic/Coding/barrel/workspaces")
+}
+
+/// Extract YAML frontmatter from a markdown file.
+/// Frontmatter is delimited by `---` at the start of the file.
+fn extract_frontmatter(content: &str) -> Result<&str> {
+    split_frontmatter(content).map(|(yaml, _)| yaml)
+}
+
+/// Split a markdown file into its YAML frontmatter and the body that follows
+/// the closing `---` (starting with the newline after it).
+fn split_frontmatter(content: &str) -> Result<(&str, &str)> {
+    let trimmed = content.trim_start();
+    if !trimmed.starts_with("---") {
+        anyhow::bail!("No frontmatter found: file must start with ---");
+    }
+    let after_opening = &trimmed[3..];
+    let after_opening = after_opening.strip_prefix('\n').unwrap_or(after_opening);
+    match after_opening.find("\n---") {
+        Some(end) => Ok((&after_opening[..end], &after_opening[end + 4..])),
+        None => anyhow::bail!("No closing --- found for frontmatter"),
+    }
+}
+
+/// Load workspace configuration from a file.
+/// Parses YAML from markdown frontmatter, or a whole `.yaml` manifest
+/// (merged over any `extends:` bases, without entries whose `when:` doesn't
+/// hold),
+/// expands `${VAR}` placeholders, reads `prompt_file`s, resolves `@name`
+/// prompts and follows any `parent:` chain.
+pub fn load_config(path: &Path) -> Result<WorkspaceConfig> {
+    let mut config = parse_manifest(path)?;
+    config.inherit_parent_skills()?;
+    Ok(config)
+}
+
+/// Parse a single manifest without following its `parent:` chain
+fn parse_manifest(path: &Path) -> Result<WorkspaceConfig> {
+    let content = std::fs::read_to_string(path)?;
+    let yaml = manifest::manifest_yaml(path, &content)?;
+    let mut config: WorkspaceConfig = match extends::extended_frontmatter(path, yaml)? {
+        Some(value) => serde_yaml::from_value(value)?,
+        None => serde_yaml::from_str(yaml)?,
+    };
+    config.manifest_path = Some(path.to_path_buf());
+    config
+        .interpolate_env()
+        .with_context(|| format!("Failed to expand variables in {}", path.display()))?;
+    config
+        .resolve_prompts()
+        .with_context(|| format!("Failed to resolve prompts in {}", path.display()))?;
+    Ok(config)
+}
+
+/// Generate a new workspace configuration as a markdown file with YAML frontmatter
+pub fn generate_config(workspace: &str, _workspace_path: &str) -> String {
+    format!(
+        r#"---
+workspace: {workspace}
+
+# =============================================================================
+# Skill directories
+# =============================================================================
+# Search paths for skill files (first match wins for duplicate names)
+# Supports: ./relative, ~/home, /absolute paths
+
+skills:
+  - path: ./skills
+  - path: ~/.config/axel/skills
+
+# Optional: start panes without the wrapper script that prints notes. Commands
+# then run directly as the pane's process. Panes can set `wrapper` themselves.
+# wrapper: false
+
+# Optional: inherit skill directories from another manifest (e.g. a monorepo
+# root). Its directories rank below the ones above. `auto` uses the nearest
+# AXEL.md in a parent directory.
+# parent: ../..
+
+# Optional: pipe the markdown below through a command before it is sent as the
+# initial prompt (stdin -> stdout), e.g. to expand variables
+# index_preprocessor: envsubst
+
+# Optional: prepare worktrees created with `axel -w`
+# worktree:
+#   copy:                        # Untracked files brought from the main checkout
+#     - .env
+#     - path: node_modules       # Symlinked instead of copied
+#       symlink: true
+#   setup:                       # Run in the first pane before anything starts
+#     - pnpm install
+
+# =============================================================================
+# Layouts
+# =============================================================================
+
+layouts:
+  # ---------------------------------------------------------------------------
+  # Pane definitions
+  # ---------------------------------------------------------------------------
+  # Define panes that can be used in grid layouts
+  #
+  # Built-in types: claude, codex, opencode, antigravity, shell
+  # Custom types use the 'command' field
+
+  panes:
+    # Claude Code - AI coding assistant
+    - type: claude
+      color: gray
+      skills:
+        - "*"                    # Load all skills, or list specific: ["skill1", "skill2"]
+      # model: sonnet            # Model: sonnet, opus, haiku
+      # prompt: "Your task..."   # Initial prompt ("@name" for a named prompt)
+      # prompt_file: TASK.md     # Or read the initial prompt from a file
+      # context_files: []        # Files referenced as @path in the prompt
+      # allowed_tools: []        # Restrict to specific tools
+      # disallowed_tools: []     # Block specific tools
+      # args: []                 # Additional CLI arguments
+      # mcp: {{}}                  # MCP servers by name (command, args, env)
+      # resume: true             # Continue the last conversation (or a session ID)
+      # permission_mode: plan    # default, acceptEdits, plan, bypassPermissions
+      # add_dirs: []             # Extra directories the agent may access
+
+    # Codex - OpenAI coding assistant
+    - type: codex
+      color: green
+      skills:
+        - "*"
+      # model: o3-mini           # Model to use
+      # prompt: "Your task..."   # Initial prompt
+      # args: []                 # Additional CLI arguments
+
+    # OpenCode - Open-source coding assistant
+    # - type: opencode
+    #   color: blue
+    #   skills: ["*"]
+
+    # Antigravity - Google coding assistant
+    # - type: antigravity
+    #   color: orange
+    #   skills: ["*"]
+    #   # model: gemini-3-pro    # Model to use
+
+    # Regular shell with notes displayed on startup
+    - type: shell
+      notes:
+        - "$ axel -k {workspace}"
+        # Notes can also be captured at launch:
+        # - cmd: git log --oneline -5
+        # - file: TODO.md
+
+    # Custom command example
+    # - type: logs
+    #   command: "tail -f /var/log/app.log"
+    #   color: red
+
+  # ---------------------------------------------------------------------------
+  # Grid layouts
+  # ---------------------------------------------------------------------------
+  # Layout configurations for tmux sessions
+  #
+  # Grid types:
+  #   tmux    - Standard tmux session (default)
+  #   tmux_cc - iTerm2 tmux integration mode
+  #   shell   - No tmux, run panes directly (see `run:` first, pick, jobs)
+  #   zellij  - Zellij session (or set `backend: zellij` for all tmux grids)
+  #   wezterm - WezTerm workspace (or `backend: wezterm`)
+  #   kitty   - kitty OS window, via remote control (or `backend: kitty`)
+  #   windows_terminal - Windows Terminal tab per pane (or `backend: windows_terminal`)
+  #
+  # Cell positioning:
+  #   col: 0, 1, 2...  - Column position (left to right)
+  #   row: 0, 1, 2...  - Row position within column (top to bottom)
+  #   width: 50        - Column width percentage
+  #   height: 30       - Row height percentage
+  #   window: logs     - Place the cell in a separate window (tab)
+  #
+  # Colors: purple, yellow, red, green, blue, gray, orange
+
+  grids:
+    # Default grid - two columns
+    default:
+      type: tmux
+      claude:
+        col: 0
+        row: 0
+      shell:
+        col: 1
+        row: 0
+        color: yellow
+
+    # Solo mode - single AI pane
+    # solo:
+    #   type: shell
+    #   claude:
+    #     col: 0
+    #     row: 0
+
+    # Three column layout
+    # wide:
+    #   type: tmux
+    #   claude:
+    #     col: 0
+    #     row: 0
+    #     width: 40
+    #   codex:
+    #     col: 1
+    #     row: 0
+    #     width: 40
+    #   shell:
+    #     col: 2
+    #     row: 0
+    #     width: 20
+---
+
+# {workspace}
+
+<!-- Project context for AI assistants. This content is used as initial context when launching panes. -->
+
+## Overview
+
+<!-- Brief description of what this project does -->
+
+## Getting Started
+
+<!-- How to set up and run the project -->
+
+## Architecture
+
+<!-- High-level architecture overview -->
+
+## Key Files
+
+<!-- Important files and directories -->
+"#,
+        workspace = workspace,
+    )
+}
+
+/// Convert color name to tmux color code
+pub fn to_tmux_color(color: &str) -> &'static str {
+    match color {
+        "purple" => "#251F2B",
+        "yellow" => "#2B2011",
+        "red" => "#231517",
+        "green" => "#122322",
+        "blue" => "#1E202E",
+        "gray" | "grey" => "#1a1a1a",
+        "orange" => "#2B2011",
+        _ => "default",
+    }
+}
+
+/// Convert color name to RGB for terminal escape sequences
+pub fn to_fg_rgb(color: &str) -> &'static str {
+    match color {
+        "purple" => "198;147;241",
+        "yellow" => "255;182;21",
+        "red" => "251;109;136",
+        "green" => "0;217;146",
+        "blue" => "133;162;255",
+        "gray" | "grey" => "150;150;150",
+        "orange" => "255;182;21",
+        _ => "255;255;255",
+    }
+}
+
+/// Expand ~ to home directory in paths
+pub fn expand_path(path: &str) -> String {
+    path.strip_prefix("~/")
+        .and_then(|stripped| dirs::home_dir().map(|home| home.join(stripped)))
+        .map(|p| p.to_string_lossy().into_owned())
+        .unwrap_or_else(|| path.to_string())
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_skill_parsing_without_frontmatter() {
+        let content = "# Test Agent\n\nYou are a helpful skill.";
+        let temp_dir = std::env::temp_dir();
+        let skill_path = temp_dir.join("test-skill.md");
+        std::fs::write(&skill_path, content).unwrap();
+
+        let skill = Skill::from_file(&skill_path).unwrap();
+        assert_eq!(skill.name, "test-skill");
+        assert_eq!(skill.prompt, content);
+        assert!(skill.description.contains("Test Agent") || skill.description.contains("helpful"));
+
+        std::fs::remove_file(&skill_path).ok();
+    }
+
+    #[test]
+    fn test_skill_parsing_with_frontmatter() {
+        let content = r#"---
+name: custom-name
+description: A custom description
+tools: Read, Write, Bash
+model: opus
+---
+
+# My Agent
+
+You are a specialized skill."#;
+
+        let temp_dir = std::env::temp_dir();
+        let skill_path = temp_dir.join("frontmatter-skill.md");
+        std::fs::write(&skill_path, content).unwrap();
+
+        let skill = Skill::from_file(&skill_path).unwrap();
+        assert_eq!(skill.name, "custom-name");
+        assert_eq!(skill.description, "A custom description");
+        assert_eq!(
+            skill.tools,
+            Some(vec![
+                "Read".to_string(),
+                "Write".to_string(),
+                "Bash".to_string()
+            ])
+        );
+        assert_eq!(skill.model, Some("opus".to_string()));
+        assert!(skill.prompt.contains("My Agent"));
+        assert!(skill.prompt.contains("specialized skill"));
+
+        std::fs::remove_file(&skill_path).ok();
+    }
+
+    #[test]
+    fn test_skill_dir_structure() {
+        let temp_dir = std::env::temp_dir().join("axel-test-skills");
+        let skill_dir = temp_dir.join("my-skill");
+        std::fs::create_dir_all(&skill_dir).ok();
+
+        let skill_file = skill_dir.join("SKILL.md");
+        std::fs::write(&skill_file, "# My Agent\n\nHello").unwrap();
+
+        let skill = Skill::from_file(&skill_file).unwrap();
+        assert_eq!(skill.name, "my-skill");
+
+        std::fs::remove_dir_all(&temp_dir).ok();
+    }
+
+    #[test]
+    fn test_grid_retain_cells_compacts_positions() {
+        let mut grid: Grid = serde_yaml::from_str(
+            r#"
+type: tmux
+claude: { col: 0, row: 0 }
+codex: { col: 1, row: 0 }
+backend: { col: 2, row: 0 }
+frontend: { col: 2, row: 1 }
+logs: { col: 2, row: 2 }
+"#,
+        )
+        .unwrap();
+
+        grid.retain_cells(&["claude".to_string(), "logs".to_string()])
+            .unwrap();
+
+        assert_eq!(grid.cells.len(), 2);
+        assert_eq!((grid.cells["claude"].col, grid.cells["claude"].row), (0, 0));
+        assert_eq!((grid.cells["logs"].col, grid.cells["logs"].row), (1, 0));
+    }
+
+    #[test]
+    fn test_grid_retain_cells_unknown_name() {
+        let mut grid: Grid = serde_yaml::from_str("claude: { col: 0, row: 0 }").unwrap();
+        let err = grid.retain_cells(&["codex".to_string()]).unwrap_err();
+        assert!(err.to_string().contains("codex"));
+        assert_eq!(grid.cells.len(), 1);
+    }
+
+    #[test]
+    fn test_retain_grid_cells_resolves_subset() {
+        let yaml = r#"
+workspace: test
+layouts:
+  panes:
+    - type: claude
+    - type: custom
+      name: shell
+  grids:
+    default:
+      claude: { col: 1, row: 1 }
+      shell: { col: 0, row: 0 }
+"#;
+        let mut config: WorkspaceConfig = serde_yaml::from_str(yaml).unwrap();
+        config
+            .retain_grid_cells(None, &["claude".to_string()])
+            .unwrap();
+
+        let panes = config.resolve_panes(None);
+        assert_eq!(panes.len(), 1);
+        assert_eq!(panes[0].name, "claude");
+        assert_eq!((panes[0].col, panes[0].row), (0, 0));
+        assert!(panes[0].config.is_ai());
+    }
+
+    #[test]
+    fn test_resolve_panes_grid_paths() {
+        let yaml = r#"
+workspace: test
+layouts:
+  panes:
+    - type: claude
+      path: ./api
+    - type: codex
+    - type: custom
+      name: shell
+  grids:
+    default:
+      claude: { col: 0 }
+      codex: { col: 1 }
+    monorepo:
+      path: ./frontend
+      claude: { col: 0, path: ./backend }
+      codex: { col: 1 }
+      shell: { col: 2, path: /tmp }
+"#;
+        let mut config: WorkspaceConfig = serde_yaml::from_str(yaml).unwrap();
+        config.manifest_path = Some(PathBuf::from("/work/AXEL.md"));
+
+        let paths = |grid| {
+            config
+                .resolve_panes(Some(grid))
+                .iter()
+                .map(|p| p.path().unwrap().to_string())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(paths("default"), vec!["./api", "/work"]);
+        assert_eq!(
+            paths("monorepo"),
+            vec!["/work/./backend", "/work/./frontend", "/tmp"]
+        );
+        assert!(!config.layouts.grids["monorepo"].cells.contains_key("path"));
+    }
+
+    #[test]
+    fn test_external_pane_keeps_type() {
+        let pane = |yaml: &str| {
+            let raw: PaneConfigRaw = serde_yaml::from_str(yaml).unwrap();
+            PaneConfig::from_raw(raw, |name| name == "aider").unwrap()
+        };
+
+        // A named pane of an external driver keeps the driver as its type
+        let helper = pane("type: aider\nname: helper\nskills: [review]\nprompt: Hi");
+        let PaneConfig::External(c) = &helper else {
+            panic!("expected an external pane, got {:?}", helper);
+        };
+        assert_eq!(
+            (helper.pane_type(), helper.actual_type()),
+            ("helper", "aider")
+        );
+        assert_eq!(c.skills, vec!["review"]);
+        assert_eq!(c.prompt.as_deref(), Some("Hi"));
+        assert!(helper.is_ai());
+
+        // Types no driver defines are still custom panes named after them
+        let server = pane("type: server\ncommand: pnpm dev");
+        assert!(matches!(server, PaneConfig::Custom(_)));
+        assert_eq!(
+            (server.pane_type(), server.actual_type()),
+            ("server", "custom")
+        );
+    }
+
+    #[test]
+    fn test_seed_prompt() {
+        let yaml = r#"
+workspace: test
+layouts:
+  panes:
+    - type: claude
+      prompt: Run the tests
+    - type: codex
+    - type: shell
+"#;
+        let mut config: WorkspaceConfig = serde_yaml::from_str(yaml).unwrap();
+        config.seed_prompt("PR #7");
+        let prompts: Vec<_> = config
+            .layouts
+            .panes
+            .iter()
+            .map(|pane| match pane {
+                PaneConfig::Custom(_) => None,
+                PaneConfig::Claude(c) | PaneConfig::Codex(c) => c.prompt.as_deref(),
+                _ => unreachable!(),
+            })
+            .collect();
+        assert_eq!(
+            prompts,
+            [Some("PR #7\n\nRun the tests"), Some("PR #7"), None]
+        );
+    }
+
+    #[test]
+    fn test_group_by_window() {
+        let yaml = r#"
+workspace: test
+layouts:
+  panes:
+    - type: claude
+    - type: custom
+      name: server
+    - type: custom
+      name: logs
+  grids:
+    default:
+      server: { col: 0, window: servers }
+      claude: { col: 0 }
+      logs: { col: 1, window: servers }
+"#;
+        let mut config: WorkspaceConfig = serde_yaml::from_str(yaml).unwrap();
+        let panes = config.resolve_panes(None);
+        let windows: Vec<(Option<&str>, Vec<&str>)> = group_by_window(&panes)
+            .into_iter()
+            .map(|(name, panes)| (name, panes.iter().map(|p| p.name.as_str()).collect()))
+            .collect();
+        assert_eq!(
+            windows,
+            vec![
+                (None, vec!["claude"]),
+                (Some("servers"), vec!["server", "logs"]),
+            ]
+        );
+
+        // Positions are compacted within each window
+        config
+            .retain_grid_cells(None, &["claude".to_string(), "logs".to_string()])
+            .unwrap();
+        let cells = &config.layouts.grids["default"].cells;
+        assert_eq!((cells["claude"].col, cells["logs"].col), (0, 0));
+    }
+
+    fn index_with(content: &str) -> WorkspaceIndex {
+        WorkspaceIndex {
+            name: "test".to_string(),
+            description: None,
+            content: content.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_index_preprocessor_rewrites_content() {
+        let index = index_with("Deploy to {{ENV}} from {{ENV}}-branch")
+            .preprocess("sed 's/{{ENV}}/prod/g'", &std::env::temp_dir())
+            .unwrap();
+        assert_eq!(index.content, "Deploy to prod from prod-branch");
+        assert!(index.to_initial_prompt().contains("Deploy to prod"));
+    }
+
+    #[test]
+    fn test_index_preprocessor_failure_is_reported() {
+        let err = index_with("anything")
+            .preprocess(
+                "cat >/dev/null; echo 'bad template' >&2; exit 2",
+                &std::env::temp_dir(),
+            )
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("index_preprocessor"));
+        assert!(err.contains("bad template"));
+    }
+
+    #[test]
+    fn test_load_index_applies_preprocessor() {
+        let dir = std::env::temp_dir().join("axel-test-index-preprocessor");
+        std::fs::create_dir_all(&dir).unwrap();
+        let manifest = dir.join("AXEL.md");
+        std::fs::write(
+            &manifest,
+            "---\nworkspace: test\nindex_preprocessor: tr a-z A-Z\nlayouts: {}\n---\n\nhello index\n",
+        )
+        .unwrap();
+
+        let config = load_config(&manifest).unwrap();
+        let index = config.load_index().unwrap().unwrap();
+        assert_eq!(index.content, "HELLO INDEX");
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
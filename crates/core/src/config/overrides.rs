@@ -0,0 +1,163 @@
+//! Per-driver skill overrides
+//!
+//! A skill can adjust itself for one tool without being duplicated. Fields
+//! under `drivers.<name>` in its frontmatter replace the top-level ones for
+//! that driver, and `body` replaces the prompt:
+//!
+//! ```yaml
+//! ---
+//! name: review
+//! description: Review the current diff
+//! tools: Read, Grep, Bash(git:*)
+//! drivers:
+//!   codex:
+//!     description: Review the current diff with `git diff`
+//!   opencode:
+//!     body: |
+//!       Review the current diff. Run `git diff` yourself first.
+//! ---
+//! ```
+//!
+//! When a driver has overrides, the transformed skill is written to
+//! `.axel/drivers/<driver>/` in the workspace and that copy is installed;
+//! other drivers get the skill as written.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_yaml::{Mapping, Value};
+
+/// Directory transformed skills are written to, relative to the workspace
+pub const DRIVER_SKILLS_DIR: &str = ".axel/drivers";
+
+/// Frontmatter key holding the per-driver overrides
+const DRIVERS_KEY: &str = "drivers";
+
+/// Frontmatter fields a skill overrides for one driver
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DriverOverride {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Comma-separated, like the top-level `tools:`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tools: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    /// Replaces the prompt after the frontmatter
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+}
+
+/// Apply the `drivers.<driver>` overrides of a skill file's `content`.
+///
+/// Returns None when the skill has no overrides for `driver`. Otherwise the
+/// result has the overridden fields in place and no `drivers:` block.
+pub fn apply_driver_override(content: &str, driver: &str) -> Result<Option<String>> {
+    let Some((yaml, body)) = content.strip_prefix("---").and_then(|rest| {
+        rest.find("\n---")
+            .map(|end| (&rest[..end], &rest[end + 4..]))
+    }) else {
+        return Ok(None);
+    };
+    // Cheap check before parsing every skill's frontmatter
+    if !yaml.contains(DRIVERS_KEY) {
+        return Ok(None);
+    }
+
+    let mut frontmatter: Mapping =
+        serde_yaml::from_str(yaml).context("Invalid skill frontmatter")?;
+    let Some(overrides) = frontmatter
+        .get(DRIVERS_KEY)
+        .and_then(|d| d.get(driver))
+        .cloned()
+    else {
+        return Ok(None);
+    };
+    let overrides: DriverOverride = serde_yaml::from_value(overrides)
+        .with_context(|| format!("Invalid `drivers.{}` in skill frontmatter", driver))?;
+
+    frontmatter.remove(DRIVERS_KEY);
+    for (key, value) in [
+        ("description", overrides.description),
+        ("tools", overrides.tools),
+        ("model", overrides.model),
+    ] {
+        if let Some(value) = value {
+            frontmatter.insert(Value::from(key), Value::from(value));
+        }
+    }
+
+    let yaml = if frontmatter.is_empty() {
+        String::new()
+    } else {
+        serde_yaml::to_string(&frontmatter)?
+    };
+    let body = match overrides.body {
+        Some(body) => format!("\n{}\n", body.trim_end()),
+        None => body.to_string(),
+    };
+    Ok(Some(format!("---\n{}---{}", yaml, body)))
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SKILL: &str = "---\nname: review\ndescription: Review diffs\ntools: Read, Grep\ndrivers:\n  codex:\n    tools: Read\n  opencode:\n    description: Review with git diff\n    body: |\n      Run git diff first.\n---\n# Review\n\nLook at the diff.\n";
+
+    #[test]
+    fn test_apply_driver_override() {
+        assert_eq!(apply_driver_override(SKILL, "claude").unwrap(), None);
+        assert_eq!(
+            apply_driver_override("# No frontmatter\n", "codex").unwrap(),
+            None
+        );
+
+        assert_eq!(
+            apply_driver_override(SKILL, "codex").unwrap().unwrap(),
+            "---\nname: review\ndescription: Review diffs\ntools: Read\n---\n# Review\n\nLook at the diff.\n"
+        );
+        assert_eq!(
+            apply_driver_override(SKILL, "opencode").unwrap().unwrap(),
+            "---\nname: review\ndescription: Review with git diff\ntools: Read, Grep\n---\nRun git diff first.\n"
+        );
+
+        let typo = "---\ndrivers:\n  codex:\n    tool: Read\n---\nBody\n";
+        assert!(apply_driver_override(typo, "codex").is_err());
+    }
+
+    #[test]
+    fn test_render_skills_applies_driver_overrides() {
+        let dir = std::env::temp_dir().join("axel-test-skill-overrides");
+        std::fs::remove_dir_all(&dir).ok();
+        std::fs::create_dir_all(dir.join("skills/review")).unwrap();
+        let source = dir.join("skills/review/SKILL.md");
+        std::fs::write(&source, SKILL).unwrap();
+
+        let manifest = dir.join("AXEL.md");
+        std::fs::write(
+            &manifest,
+            "---\nworkspace: test\nlayouts:\n  panes: []\n---\n",
+        )
+        .unwrap();
+        let config = crate::config::load_config(&manifest).unwrap();
+
+        let paths = vec![source.clone()];
+        let claude = config.render_skills(&paths, &dir, "claude").unwrap();
+        assert_eq!(claude.paths, paths);
+
+        let opencode = config.render_skills(&paths, &dir, "opencode").unwrap();
+        let target = dir.join(".axel/drivers/opencode/review/SKILL.md");
+        assert_eq!(opencode.paths, vec![target.clone()]);
+        let skill = crate::config::Skill::from_file(&target).unwrap();
+        assert_eq!(skill.name, "review");
+        assert_eq!(skill.description, "Review with git diff");
+        assert_eq!(skill.prompt, "Run git diff first.");
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -0,0 +1,184 @@
+//! Parent manifest inheritance
+//!
+//! A sub-project manifest can set `parent:` to inherit the skill directories
+//! of another manifest, typically the monorepo root. `parent: auto` picks the
+//! nearest AXEL.md in an ancestor directory.
+//!
+//! Inherited directories rank below the child's own, so a child skill
+//! overrides a parent skill of the same name. Parents can have parents of
+//! their own; a chain that revisits a manifest is an error.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, bail};
+
+use super::{
+    WorkspaceConfig, expand_path,
+    manifest::{MANIFEST_FILE, find_manifest, manifest_in},
+    parse_manifest,
+};
+
+/// `parent:` value that selects the nearest ancestor manifest
+const AUTO_PARENT: &str = "auto";
+
+impl WorkspaceConfig {
+    /// Load the parent manifest chain and record its skill directories.
+    pub(super) fn inherit_parent_skills(&mut self) -> Result<()> {
+        let Some(manifest) = self.manifest_path.clone() else {
+            return Ok(());
+        };
+
+        let mut chain = vec![canonical(&manifest)];
+        let mut own_dirs: Vec<PathBuf> = self.skills_dirs().iter().map(|d| canonical(d)).collect();
+        let mut inherited = Vec::new();
+        let mut next = self.parent_manifest()?;
+
+        while let Some(path) = next {
+            let key = canonical(&path);
+            if chain.contains(&key) {
+                chain.push(key);
+                let names: Vec<_> = chain.iter().map(|p| p.display().to_string()).collect();
+                bail!("Parent manifest cycle: {}", names.join(" → "));
+            }
+            chain.push(key);
+
+            let parent = parse_manifest(&path)
+                .with_context(|| format!("Failed to load parent manifest {}", path.display()))?;
+            for dir in parent.skills_dirs() {
+                let key = canonical(&dir);
+                if !own_dirs.contains(&key) {
+                    own_dirs.push(key);
+                    inherited.push(dir);
+                }
+            }
+            next = parent.parent_manifest()?;
+        }
+
+        self.inherited_skills_dirs = inherited;
+        Ok(())
+    }
+
+    /// Resolve this manifest's `parent:` field to a manifest path
+    fn parent_manifest(&self) -> Result<Option<PathBuf>> {
+        let (Some(parent), Some(manifest)) = (&self.parent, &self.manifest_path) else {
+            return Ok(None);
+        };
+        let manifest_dir = manifest.parent().unwrap_or(Path::new("."));
+
+        if parent == AUTO_PARENT {
+            let found = manifest_dir.ancestors().skip(1).find_map(find_manifest);
+            return match found {
+                Some(path) => Ok(Some(path)),
+                None => bail!(
+                    "parent: auto found no {} above {}",
+                    MANIFEST_FILE,
+                    manifest_dir.display()
+                ),
+            };
+        }
+
+        let path = if parent.starts_with('/') || parent.starts_with('~') {
+            PathBuf::from(expand_path(parent))
+        } else {
+            manifest_dir.join(parent)
+        };
+        let path = if path.is_dir() {
+            manifest_in(&path)
+        } else {
+            path
+        };
+        if !path.exists() {
+            bail!("Parent manifest {} not found", path.display());
+        }
+        Ok(Some(path))
+    }
+}
+
+fn canonical(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::load_config;
+
+    fn write_manifest(dir: &Path, frontmatter: &str) -> PathBuf {
+        std::fs::create_dir_all(dir).unwrap();
+        let path = dir.join(MANIFEST_FILE);
+        std::fs::write(&path, format!("---\n{}layouts: {{}}\n---\n", frontmatter)).unwrap();
+        path
+    }
+
+    fn write_skill(dir: &Path, name: &str, body: &str) {
+        std::fs::create_dir_all(dir).unwrap();
+        std::fs::write(dir.join(format!("{}.md", name)), body).unwrap();
+    }
+
+    #[test]
+    fn test_inherit_parent_skills() {
+        let root = std::env::temp_dir().join("axel-test-parent-skills");
+        std::fs::remove_dir_all(&root).ok();
+        let app = root.join("apps").join("web");
+
+        write_manifest(&root, "workspace: mono\nskills:\n  - path: ./shared\n");
+        write_skill(&root.join("shared"), "review", "Root review");
+        write_skill(&root.join("shared"), "deploy", "Root deploy");
+
+        let manifest = write_manifest(
+            &app,
+            "workspace: web\nparent: ../..\nskills:\n  - path: ./skills\n",
+        );
+        write_skill(&app.join("skills"), "review", "Web review");
+
+        let config = load_config(&manifest).unwrap();
+        let dirs: Vec<_> = config.skills_dirs().iter().map(|d| canonical(d)).collect();
+        assert_eq!(
+            dirs,
+            vec![
+                canonical(&app.join("skills")),
+                canonical(&root.join("shared"))
+            ]
+        );
+        // Child skill wins over the inherited one of the same name
+        let found = |name| config.find_skill(name).map(|p| canonical(&p));
+        assert_eq!(
+            found("review"),
+            Some(canonical(&app.join("skills/review.md")))
+        );
+        assert_eq!(
+            found("deploy"),
+            Some(canonical(&root.join("shared/deploy.md")))
+        );
+        assert_eq!(config.resolve_skills(&["*".to_string()]).len(), 2);
+
+        // `auto` finds the same root manifest
+        let auto = write_manifest(&app, "workspace: web\nparent: auto\n");
+        let config = load_config(&auto).unwrap();
+        let dirs: Vec<_> = config.skills_dirs().iter().map(|d| canonical(d)).collect();
+        assert_eq!(dirs, vec![canonical(&root.join("shared"))]);
+
+        std::fs::remove_dir_all(&root).ok();
+    }
+
+    #[test]
+    fn test_parent_cycle_is_an_error() {
+        let root = std::env::temp_dir().join("axel-test-parent-cycle");
+        std::fs::remove_dir_all(&root).ok();
+
+        let a = write_manifest(&root.join("a"), "workspace: a\nparent: ../b\n");
+        write_manifest(&root.join("b"), "workspace: b\nparent: ../a/AXEL.md\n");
+
+        let err = load_config(&a).unwrap_err().to_string();
+        assert!(err.contains("Parent manifest cycle"), "{}", err);
+
+        let missing = write_manifest(&root.join("c"), "workspace: c\nparent: ../nope\n");
+        assert!(load_config(&missing).is_err());
+
+        std::fs::remove_dir_all(&root).ok();
+    }
+}
@@ -0,0 +1,339 @@
+//! Named prompts and prompt files
+//!
+//! Long prompts can live outside the pane definitions and be referenced by
+//! name with `prompt: @triage-bug`. Prompts come from the manifest's
+//! `prompts:` section and from `~/.config/axel/prompts/<name>.md`; the
+//! manifest wins when both define a name.
+//!
+//! ```yaml
+//! prompts:
+//!   triage-bug: |
+//!     Reproduce the bug in the linked issue, find the cause and propose a fix.
+//! layouts:
+//!   panes:
+//!     - type: claude
+//!       prompt: "@triage-bug"
+//! ```
+//!
+//! Only a prompt that is exactly `@` followed by a name (letters, digits,
+//! `-` and `_`) is a reference, so `@docs/task.md` file references are left
+//! alone. A reference to a prompt that doesn't exist is an error.
+//!
+//! A pane can also read its prompt from a file with `prompt_file:`
+//! (relative to the workspace), which avoids escaping long task
+//! descriptions in YAML. A pane sets either `prompt` or `prompt_file`.
+
+use std::{
+    io::Read,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result, bail};
+use serde::Serialize;
+
+use super::{PaneConfig, WorkspaceConfig};
+
+/// Directory holding global prompts, relative to the home directory
+pub const PROMPTS_DIR: &str = ".config/axel/prompts";
+
+/// Where a named prompt is defined
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PromptSource {
+    Manifest,
+    Global,
+}
+
+/// A reusable prompt
+#[derive(Debug, Clone, Serialize)]
+pub struct NamedPrompt {
+    pub name: String,
+    pub source: PromptSource,
+    /// File the prompt is read from, for global prompts
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<PathBuf>,
+    pub text: String,
+}
+
+/// Directory holding global prompts (`~/.config/axel/prompts`)
+pub fn prompts_dir() -> Option<PathBuf> {
+    dirs::home_dir().map(|home| home.join(PROMPTS_DIR))
+}
+
+/// The name referenced by `prompt`, if it is a `@name` reference
+pub fn prompt_ref(prompt: &str) -> Option<&str> {
+    let name = prompt.trim().strip_prefix('@')?;
+    let valid = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    valid.then_some(name)
+}
+
+/// Read a prompt from `path`, or from stdin when `path` is `-`
+pub fn read_prompt_file(path: &str) -> Result<String> {
+    let text = if path == "-" {
+        let mut text = String::new();
+        std::io::stdin()
+            .read_to_string(&mut text)
+            .context("Failed to read the prompt from stdin")?;
+        text
+    } else {
+        std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read prompt file {}", path))?
+    };
+    Ok(text.trim_end().to_string())
+}
+
+/// Prompts in `dir` (`<name>.md` files), sorted by name
+pub fn global_prompts(dir: &Path) -> Vec<NamedPrompt> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut prompts: Vec<NamedPrompt> = entries
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            if path.extension()? != "md" {
+                return None;
+            }
+            let name = path.file_stem()?.to_string_lossy().into_owned();
+            let text = std::fs::read_to_string(&path).ok()?;
+            Some(NamedPrompt {
+                name,
+                source: PromptSource::Global,
+                path: Some(path),
+                text: text.trim_end().to_string(),
+            })
+        })
+        .collect();
+    prompts.sort_by(|a, b| a.name.cmp(&b.name));
+    prompts
+}
+
+impl WorkspaceConfig {
+    /// Every named prompt: the manifest's, then global ones it doesn't shadow
+    pub fn prompts(&self) -> Vec<NamedPrompt> {
+        self.prompts_in(prompts_dir().as_deref())
+    }
+
+    fn prompts_in(&self, global_dir: Option<&Path>) -> Vec<NamedPrompt> {
+        let mut prompts: Vec<NamedPrompt> = self
+            .prompts
+            .iter()
+            .map(|(name, text)| NamedPrompt {
+                name: name.clone(),
+                source: PromptSource::Manifest,
+                path: None,
+                text: text.trim_end().to_string(),
+            })
+            .collect();
+        for prompt in global_dir.map(global_prompts).unwrap_or_default() {
+            if !self.prompts.contains_key(&prompt.name) {
+                prompts.push(prompt);
+            }
+        }
+        prompts
+    }
+
+    /// Read pane `prompt_file`s and replace `@name` prompts with the named
+    /// prompt's text
+    pub(super) fn resolve_prompts(&mut self) -> Result<()> {
+        self.read_prompt_files()?;
+        self.resolve_prompt_refs_in(prompts_dir().as_deref())
+    }
+
+    /// Set the prompt of panes with a `prompt_file` to the file's content
+    fn read_prompt_files(&mut self) -> Result<()> {
+        let workspace_dir = self.workspace_dir();
+        for pane in &mut self.layouts.panes {
+            let name = pane.pane_type().to_string();
+            let (PaneConfig::Claude(c)
+            | PaneConfig::Codex(c)
+            | PaneConfig::Opencode(c)
+            | PaneConfig::Antigravity(c)
+            | PaneConfig::External(c)) = pane
+            else {
+                continue;
+            };
+            let Some(file) = &c.prompt_file else {
+                continue;
+            };
+            if c.prompt.is_some() {
+                bail!("Pane '{}' sets both prompt and prompt_file", name);
+            }
+            let path = match &workspace_dir {
+                Some(dir) if !file.starts_with('/') && !file.starts_with('~') => dir.join(file),
+                _ => PathBuf::from(super::expand_path(file)),
+            };
+            let text = std::fs::read_to_string(&path).with_context(|| {
+                format!(
+                    "Failed to read prompt file {} of pane '{}'",
+                    path.display(),
+                    name
+                )
+            })?;
+            c.prompt = Some(text.trim_end().to_string());
+        }
+        Ok(())
+    }
+
+    fn resolve_prompt_refs_in(&mut self, global_dir: Option<&Path>) -> Result<()> {
+        let uses_refs = self.layouts.panes.iter().any(|pane| {
+            pane_prompt(pane)
+                .and_then(|prompt| prompt_ref(prompt))
+                .is_some()
+        });
+        if !uses_refs {
+            return Ok(());
+        }
+
+        let prompts = self.prompts_in(global_dir);
+        for pane in &mut self.layouts.panes {
+            let name = pane.pane_type().to_string();
+            let (PaneConfig::Claude(c)
+            | PaneConfig::Codex(c)
+            | PaneConfig::Opencode(c)
+            | PaneConfig::Antigravity(c)
+            | PaneConfig::External(c)) = pane
+            else {
+                continue;
+            };
+            let Some(reference) = c.prompt.as_deref().and_then(prompt_ref) else {
+                continue;
+            };
+            let Some(prompt) = prompts.iter().find(|p| p.name == reference) else {
+                bail!(
+                    "Pane '{}' uses unknown prompt '@{}' (see axel prompt ls)",
+                    name,
+                    reference
+                );
+            };
+            c.prompt = Some(prompt.text.clone());
+        }
+        Ok(())
+    }
+}
+
+fn pane_prompt(pane: &PaneConfig) -> Option<&str> {
+    match pane {
+        PaneConfig::Claude(c)
+        | PaneConfig::Codex(c)
+        | PaneConfig::Opencode(c)
+        | PaneConfig::Antigravity(c)
+        | PaneConfig::External(c) => c.prompt.as_deref(),
+        PaneConfig::Custom(_) => None,
+    }
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prompt_ref() {
+        assert_eq!(prompt_ref("@triage-bug"), Some("triage-bug"));
+        assert_eq!(prompt_ref(" @release_notes\n"), Some("release_notes"));
+        assert_eq!(prompt_ref("@docs/task.md"), None);
+        assert_eq!(prompt_ref("@"), None);
+        assert_eq!(prompt_ref("Fix @triage-bug"), None);
+    }
+
+    #[test]
+    fn test_resolve_prompt_refs() {
+        let dir = std::env::temp_dir().join("axel-test-prompts");
+        std::fs::remove_dir_all(&dir).ok();
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("review.md"), "Review the diff\n").unwrap();
+        std::fs::write(dir.join("triage-bug.md"), "Global triage\n").unwrap();
+
+        let yaml = r#"
+workspace: test
+prompts:
+  triage-bug: |
+    Reproduce the bug first.
+layouts:
+  panes:
+    - type: claude
+      prompt: "@triage-bug"
+    - type: codex
+      prompt: "@review"
+    - type: opencode
+      prompt: "@docs/task.md"
+"#;
+        let mut config: WorkspaceConfig = serde_yaml::from_str(yaml).unwrap();
+        let names: Vec<_> = config
+            .prompts_in(Some(&dir))
+            .into_iter()
+            .map(|p| (p.name, p.source))
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                ("triage-bug".to_string(), PromptSource::Manifest),
+                ("review".to_string(), PromptSource::Global),
+            ]
+        );
+
+        config.resolve_prompt_refs_in(Some(&dir)).unwrap();
+        let prompts: Vec<_> = config.layouts.panes.iter().map(pane_prompt).collect();
+        assert_eq!(
+            prompts,
+            vec![
+                Some("Reproduce the bug first."),
+                Some("Review the diff"),
+                Some("@docs/task.md"),
+            ]
+        );
+
+        let mut config: WorkspaceConfig = serde_yaml::from_str(
+            "workspace: test\nlayouts:\n  panes:\n    - type: claude\n      prompt: \"@ghost\"\n",
+        )
+        .unwrap();
+        let err = config.resolve_prompt_refs_in(Some(&dir)).unwrap_err();
+        assert!(err.to_string().contains("unknown prompt '@ghost'"));
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_prompt_file() {
+        let dir = std::env::temp_dir().join("axel-test-prompt-file");
+        std::fs::remove_dir_all(&dir).ok();
+        std::fs::create_dir_all(dir.join("tasks")).unwrap();
+        std::fs::write(
+            dir.join("tasks/migrate.md"),
+            "Migrate the API:\n- keep `v1: routes` working\n",
+        )
+        .unwrap();
+        let path = dir.join("AXEL.md");
+        std::fs::write(
+            &path,
+            "---\nworkspace: test\nlayouts:\n  panes:\n    - type: claude\n      prompt_file: tasks/migrate.md\n---\n",
+        )
+        .unwrap();
+
+        let config = crate::config::load_config(&path).unwrap();
+        assert_eq!(
+            pane_prompt(&config.layouts.panes[0]),
+            Some("Migrate the API:\n- keep `v1: routes` working")
+        );
+        assert_eq!(
+            read_prompt_file(&dir.join("tasks/migrate.md").to_string_lossy()).unwrap(),
+            "Migrate the API:\n- keep `v1: routes` working"
+        );
+
+        std::fs::write(
+            &path,
+            "---\nworkspace: test\nlayouts:\n  panes:\n    - type: claude\n      prompt: Hi\n      prompt_file: tasks/migrate.md\n---\n",
+        )
+        .unwrap();
+        let err = format!("{:#}", crate::config::load_config(&path).unwrap_err());
+        assert!(err.contains("sets both prompt and prompt_file"), "{}", err);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -0,0 +1,278 @@
+//! Remote skill repositories
+//!
+//! `axel skill sync <git-url>` clones a skills repository into
+//! `~/.config/axel/skills/.remotes/<name>`, or pulls it when it is already
+//! there. Every synced remote is a skill directory of every workspace,
+//! ranked below the manifest's own and inherited directories, so a team can
+//! share a canonical skill set and still override single skills locally.
+//!
+//! Skills are read from the repository's `skills/` directory when it has
+//! one, otherwise from its root. The remote a skill came from is reported
+//! by [`remote_for_path`].
+
+use std::{
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use anyhow::{Context, Result, bail};
+use serde::Serialize;
+
+/// Directory holding synced remotes, relative to the home directory
+pub const REMOTES_DIR: &str = ".config/axel/skills/.remotes";
+
+/// Subdirectory of a remote that holds its skills, when present
+const REMOTE_SKILLS_SUBDIR: &str = "skills";
+
+/// A synced skills repository
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SkillRemote {
+    /// Directory name under the remotes directory
+    pub name: String,
+    /// `origin` URL of the clone
+    pub url: Option<String>,
+    /// Path of the clone
+    pub path: PathBuf,
+    /// Checked-out commit
+    pub commit: Option<String>,
+}
+
+impl SkillRemote {
+    /// Read the remote cloned at `path`
+    fn at(path: &Path) -> Self {
+        Self {
+            name: path
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            url: git_output(path, &["remote", "get-url", "origin"]),
+            path: path.to_path_buf(),
+            commit: git_output(path, &["rev-parse", "HEAD"]),
+        }
+    }
+
+    /// Directory the remote's skills are read from
+    pub fn skills_dir(&self) -> PathBuf {
+        skills_dir_of(&self.path)
+    }
+}
+
+/// Result of [`sync_remote`]
+#[derive(Debug, Clone)]
+pub struct SyncOutcome {
+    pub remote: SkillRemote,
+    /// The remote was cloned rather than pulled
+    pub cloned: bool,
+    /// Commit checked out before a pull
+    pub previous_commit: Option<String>,
+}
+
+impl SyncOutcome {
+    /// Whether the sync changed the checked-out commit
+    pub fn changed(&self) -> bool {
+        self.cloned || self.previous_commit != self.remote.commit
+    }
+}
+
+/// Directory holding synced remotes (`~/.config/axel/skills/.remotes`)
+pub fn remotes_dir() -> Option<PathBuf> {
+    dirs::home_dir().map(|home| home.join(REMOTES_DIR))
+}
+
+/// Clone `url` into the remotes directory, or pull it if already cloned.
+///
+/// The clone is named `name`, or after the repository in the URL.
+pub fn sync_remote(url: &str, name: Option<&str>) -> Result<SyncOutcome> {
+    let dir = remotes_dir().context("Could not find home directory")?;
+    sync_remote_in(&dir, url, name)
+}
+
+/// [`sync_remote`] into an explicit remotes directory
+fn sync_remote_in(dir: &Path, url: &str, name: Option<&str>) -> Result<SyncOutcome> {
+    let name = match name {
+        Some(name) => name.to_string(),
+        None => remote_name(url)?,
+    };
+    let path = dir.join(&name);
+
+    if path.exists() {
+        let previous = SkillRemote::at(&path);
+        if let Some(existing) = &previous.url
+            && existing != url
+        {
+            bail!(
+                "Remote '{}' already tracks {}; pick another name with --name",
+                name,
+                existing
+            );
+        }
+        run_git(&path, &["pull", "--ff-only", "--quiet"])
+            .with_context(|| format!("Failed to pull remote '{}'", name))?;
+        return Ok(SyncOutcome {
+            remote: SkillRemote::at(&path),
+            cloned: false,
+            previous_commit: previous.commit,
+        });
+    }
+
+    std::fs::create_dir_all(dir)?;
+    let target = path.to_string_lossy().to_string();
+    run_git(dir, &["clone", "--quiet", "--depth", "1", url, &target])
+        .with_context(|| format!("Failed to clone {}", url))?;
+    Ok(SyncOutcome {
+        remote: SkillRemote::at(&path),
+        cloned: true,
+        previous_commit: None,
+    })
+}
+
+/// Every synced remote, by name
+pub fn list_remotes() -> Vec<SkillRemote> {
+    remote_paths().iter().map(|p| SkillRemote::at(p)).collect()
+}
+
+/// Skill directories of every synced remote
+pub(super) fn remote_skills_dirs() -> Vec<PathBuf> {
+    remote_paths().iter().map(|p| skills_dir_of(p)).collect()
+}
+
+/// Clones in the remotes directory, by name
+fn remote_paths() -> Vec<PathBuf> {
+    remotes_dir()
+        .map(|dir| remote_paths_in(&dir))
+        .unwrap_or_default()
+}
+
+/// Clones in `dir`, by name
+fn remote_paths_in(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut paths: Vec<PathBuf> = entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.join(".git").exists())
+        .collect();
+    paths.sort();
+    paths
+}
+
+/// The remote a skill file or directory belongs to, if any
+pub fn remote_for_path(path: &Path) -> Option<SkillRemote> {
+    let dir = remotes_dir()?;
+    let name = path.strip_prefix(&dir).ok()?.components().next()?;
+    Some(SkillRemote::at(&dir.join(name)))
+}
+
+/// Directory name for a remote: the repository name in `url`
+fn remote_name(url: &str) -> Result<String> {
+    let name = url
+        .trim_end_matches('/')
+        .rsplit(['/', ':'])
+        .next()
+        .unwrap_or_default()
+        .trim_end_matches(".git");
+    if name.is_empty() || name.starts_with('.') {
+        bail!("Cannot derive a remote name from {}; use --name", url);
+    }
+    Ok(name.to_string())
+}
+
+/// Skills directory of a clone: `skills/` if present, else the root
+fn skills_dir_of(path: &Path) -> PathBuf {
+    let subdir = path.join(REMOTE_SKILLS_SUBDIR);
+    if subdir.is_dir() {
+        subdir
+    } else {
+        path.to_path_buf()
+    }
+}
+
+/// Run git in `dir`, failing on a non-zero exit
+fn run_git(dir: &Path, args: &[&str]) -> Result<()> {
+    let status = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .status()
+        .context("Failed to execute git")?;
+    if !status.success() {
+        bail!("git {} failed", args.join(" "));
+    }
+    Ok(())
+}
+
+/// Trimmed stdout of a git command in `dir`, if it succeeds
+fn git_output(dir: &Path, args: &[&str]) -> Option<String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .ok()
+        .filter(|o| o.status.success())?;
+    Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn git(dir: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .args(["-c", "user.name=axel", "-c", "user.email=axel@test"])
+            .args(args)
+            .current_dir(dir)
+            .status()
+            .unwrap();
+        assert!(status.success());
+    }
+
+    #[test]
+    fn test_remote_name_from_url() {
+        assert_eq!(
+            remote_name("https://github.com/acme/team-skills.git").unwrap(),
+            "team-skills"
+        );
+        assert_eq!(remote_name("git@github.com:acme/skills").unwrap(), "skills");
+        assert_eq!(remote_name("/srv/git/shared/").unwrap(), "shared");
+        assert!(remote_name("https://example.com/.git").is_err());
+    }
+
+    #[test]
+    fn test_sync_clones_then_pulls() {
+        let root = std::env::temp_dir().join("axel-test-skill-remotes");
+        std::fs::remove_dir_all(&root).ok();
+        let upstream = root.join("upstream");
+        std::fs::create_dir_all(upstream.join("skills/review")).unwrap();
+        std::fs::write(upstream.join("skills/review/SKILL.md"), "# Review\n").unwrap();
+        git(&upstream, &["init", "--quiet"]);
+        git(&upstream, &["add", "."]);
+        git(&upstream, &["commit", "--quiet", "-m", "init"]);
+
+        let remotes = root.join("remotes");
+        let url = format!("file://{}", upstream.display());
+        let first = sync_remote_in(&remotes, &url, None).unwrap();
+        assert!(first.cloned && first.changed());
+        assert_eq!(first.remote.name, "upstream");
+        assert_eq!(first.remote.skills_dir(), remotes.join("upstream/skills"));
+        assert!(remotes.join("upstream/skills/review/SKILL.md").exists());
+
+        // Nothing new upstream
+        let second = sync_remote_in(&remotes, &url, None).unwrap();
+        assert!(!second.cloned && !second.changed());
+
+        std::fs::write(upstream.join("skills/review/SKILL.md"), "# Review v2\n").unwrap();
+        git(&upstream, &["commit", "--quiet", "-am", "update"]);
+        let third = sync_remote_in(&remotes, &url, None).unwrap();
+        assert!(third.changed());
+
+        // A different URL under the same name is refused
+        assert!(sync_remote_in(&remotes, "file:///elsewhere/upstream", None).is_err());
+        assert_eq!(remote_paths_in(&remotes), vec![remotes.join("upstream")]);
+
+        std::fs::remove_dir_all(&root).ok();
+    }
+}
@@ -0,0 +1,314 @@
+//! Skill discovery cache
+//!
+//! Listing skills reads every skill directory and skill file, which is slow
+//! when global skills live on a network mount. What was found is kept in
+//! `~/.cache/axel/skills.json`: for each skill directory, its skills with
+//! their name, description and content hash.
+//!
+//! Entries are checked against modification times instead of being read
+//! again. A directory whose mtime changed (a skill was added, removed or
+//! renamed) is listed again; a skill whose file or own directory changed is
+//! read again. The rest comes from the cache.
+
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+    time::UNIX_EPOCH,
+};
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use super::lock::{skill_hash, skill_name};
+
+/// Cache file, relative to the home directory
+pub const SKILL_CACHE_FILE: &str = ".cache/axel/skills.json";
+
+/// Longest description kept, in characters
+const DESCRIPTION_LEN: usize = 60;
+
+/// Cache file (`~/.cache/axel/skills.json`)
+pub fn skill_cache_path() -> Option<PathBuf> {
+    dirs::home_dir().map(|home| home.join(SKILL_CACHE_FILE))
+}
+
+/// A skill found in a skill directory
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CachedSkill {
+    /// Skill name (directory name or file stem)
+    pub name: String,
+    /// The skill file (`<name>/SKILL.md` or `<name>.md`)
+    pub path: PathBuf,
+    /// First line of text after the frontmatter, shortened
+    pub description: String,
+    /// `sha256:` hash of the skill's content (see [`skill_hash`])
+    pub hash: String,
+    /// Modification times it was read at (see [`skill_mtime`])
+    mtime: (u64, u64),
+}
+
+/// A listed skill directory
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct CachedDir {
+    mtime: u64,
+    skills: Vec<CachedSkill>,
+}
+
+/// Skills of every directory listed so far
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct SkillCache {
+    #[serde(default)]
+    dirs: BTreeMap<PathBuf, CachedDir>,
+    /// Whether anything changed since the cache was loaded
+    #[serde(skip)]
+    changed: bool,
+}
+
+impl SkillCache {
+    /// Load the cache from `path`; a missing or unreadable cache is empty
+    pub fn load(path: &Path) -> Self {
+        std::fs::read_to_string(path)
+            .ok()
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default()
+    }
+
+    /// Write the cache to `path` if anything changed.
+    ///
+    /// The file is replaced in one step, so processes listing skills at the
+    /// same time never read half of it.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if !self.changed {
+            return Ok(());
+        }
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let temp = path.with_extension(format!("json.{}", std::process::id()));
+        std::fs::write(&temp, serde_json::to_string(self)?)?;
+        std::fs::rename(&temp, path)?;
+        Ok(())
+    }
+
+    /// The skills of `dir`, sorted by name, reading only what changed since
+    /// they were cached
+    pub fn skills(&mut self, dir: &Path) -> &[CachedSkill] {
+        let Some(mtime) = mtime(dir) else {
+            if self.dirs.remove(dir).is_some() {
+                self.changed = true;
+            }
+            return &[];
+        };
+
+        let cached = self.dirs.remove(dir);
+        let (listed, mut previous) = match cached {
+            Some(cached) if cached.mtime == mtime => (true, cached.skills),
+            Some(cached) => (false, cached.skills),
+            None => (false, Vec::new()),
+        };
+        let changed = &mut self.changed;
+
+        let skills = if listed {
+            previous
+                .drain(..)
+                .filter_map(|skill| {
+                    let current = skill_mtime(&skill.path);
+                    if current == Some(skill.mtime) {
+                        return Some(skill);
+                    }
+                    *changed = true;
+                    read_skill(&skill.path)
+                })
+                .collect()
+        } else {
+            *changed = true;
+            let mut skills: Vec<CachedSkill> = list_skill_files(dir)
+                .into_iter()
+                .filter_map(|path| match previous.iter().position(|s| s.path == path) {
+                    Some(i) if skill_mtime(&path) == Some(previous[i].mtime) => {
+                        Some(previous.swap_remove(i))
+                    }
+                    _ => read_skill(&path),
+                })
+                .collect();
+            skills.sort_by(|a, b| a.name.cmp(&b.name));
+            skills
+        };
+
+        &self
+            .dirs
+            .entry(dir.to_path_buf())
+            .or_insert(CachedDir { mtime, skills })
+            .skills
+    }
+}
+
+/// Run `f` with the user's skill cache, saving it afterwards.
+///
+/// Without a home directory the cache lives only for the call.
+pub fn with_skill_cache<T>(f: impl FnOnce(&mut SkillCache) -> T) -> T {
+    let path = skill_cache_path();
+    let mut cache = path.as_deref().map(SkillCache::load).unwrap_or_default();
+    let result = f(&mut cache);
+    if let Some(path) = path {
+        cache.save(&path).ok();
+    }
+    result
+}
+
+/// Skill files directly in `dir`: `<name>/SKILL.md` and `<name>.md`, except
+/// `index.md`, which is workspace context rather than a skill
+fn list_skill_files(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.path();
+            if path.is_dir() {
+                let skill_file = path.join("SKILL.md");
+                skill_file.exists().then_some(skill_file)
+            } else if path.extension().is_some_and(|ext| ext == "md")
+                && path.file_name().is_some_and(|n| n != "index.md")
+                && path.is_file()
+            {
+                Some(path)
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+/// Read the skill at `path`
+fn read_skill(path: &Path) -> Option<CachedSkill> {
+    let name = skill_name(path);
+    if name.is_empty() {
+        return None;
+    }
+    let content = std::fs::read_to_string(path).ok()?;
+    Some(CachedSkill {
+        name,
+        path: path.to_path_buf(),
+        description: description(&content),
+        hash: skill_hash(path).ok()?,
+        mtime: skill_mtime(path)?,
+    })
+}
+
+/// The first line of text of a skill after its frontmatter, or its first
+/// heading, shortened to [`DESCRIPTION_LEN`] characters
+fn description(content: &str) -> String {
+    let body = if content.starts_with("---") {
+        content
+            .find("\n---")
+            .map(|i| &content[i + 4..])
+            .unwrap_or(content)
+    } else {
+        content
+    };
+
+    body.lines()
+        .find(|l| !l.trim().is_empty() && !l.starts_with('#'))
+        .or_else(|| {
+            body.lines()
+                .find(|l| l.starts_with('#'))
+                .map(|l| l.trim_start_matches('#'))
+        })
+        .map(|line| {
+            let line = line.trim();
+            if line.chars().count() > DESCRIPTION_LEN {
+                let short: String = line.chars().take(DESCRIPTION_LEN - 3).collect();
+                format!("{}...", short)
+            } else {
+                line.to_string()
+            }
+        })
+        .unwrap_or_else(|| "No description".to_string())
+}
+
+/// Modification time of the skill file, and of its own directory for
+/// `<name>/SKILL.md` skills
+fn skill_mtime(path: &Path) -> Option<(u64, u64)> {
+    let dir_mtime = match path.parent() {
+        Some(dir) if path.file_name().is_some_and(|n| n == "SKILL.md") => mtime(dir)?,
+        _ => 0,
+    };
+    Some((mtime(path)?, dir_mtime))
+}
+
+/// Modification time in nanoseconds since the epoch
+fn mtime(path: &Path) -> Option<u64> {
+    let modified = std::fs::metadata(path).ok()?.modified().ok()?;
+    Some(modified.duration_since(UNIX_EPOCH).ok()?.as_nanos() as u64)
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, SystemTime};
+
+    use super::*;
+
+    /// Move the mtime of `path` forward, as a later edit would
+    fn touch(path: &Path, secs: u64) {
+        let time = SystemTime::now() + Duration::from_secs(secs);
+        std::fs::File::options()
+            .append(true)
+            .open(path)
+            .unwrap()
+            .set_modified(time)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_skill_cache() {
+        let dir = std::env::temp_dir().join("axel-test-skill-cache");
+        std::fs::remove_dir_all(&dir).ok();
+        std::fs::create_dir_all(dir.join("review")).unwrap();
+        std::fs::write(
+            dir.join("review/SKILL.md"),
+            "---\nname: review\n---\n# Review\nReview the diff\n",
+        )
+        .unwrap();
+        std::fs::write(dir.join("deploy.md"), "# Deploy to staging\n").unwrap();
+        std::fs::write(dir.join("index.md"), "Workspace context\n").unwrap();
+
+        let mut cache = SkillCache::default();
+        let skills = cache.skills(&dir).to_vec();
+        let names: Vec<_> = skills.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, ["deploy", "review"]);
+        assert_eq!(skills[0].description, "Deploy to staging");
+        assert_eq!(skills[1].description, "Review the diff");
+        assert_eq!(
+            skills[1].hash,
+            skill_hash(&dir.join("review/SKILL.md")).unwrap()
+        );
+
+        // Round trip through the cache file
+        let path = std::env::temp_dir().join("axel-test-skill-cache.json");
+        cache.save(&path).unwrap();
+        let mut cache = SkillCache::load(&path);
+        assert_eq!(cache.skills(&dir), skills.as_slice());
+        assert!(!cache.changed);
+
+        // An edited skill is read again
+        std::fs::write(dir.join("deploy.md"), "Ship it\n").unwrap();
+        touch(&dir.join("deploy.md"), 60);
+        assert_eq!(cache.skills(&dir)[0].description, "Ship it");
+        assert!(cache.changed);
+
+        // A new skill shows up once the directory changes
+        std::fs::write(dir.join("audit.md"), "Audit deps\n").unwrap();
+        let names: Vec<_> = cache.skills(&dir).iter().map(|s| s.name.clone()).collect();
+        assert_eq!(names, ["audit", "deploy", "review"]);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert!(cache.skills(&dir).is_empty());
+    }
+}
@@ -0,0 +1,137 @@
+//! Skill tags
+//!
+//! Skills can declare `tags:` in their frontmatter, and a pane can select
+//! them by tag as well as by name:
+//!
+//! ```yaml
+//! - type: claude
+//!   skills: [code-reviewer, tag:rust, tag:testing]
+//! ```
+//!
+//! A `tag:<name>` entry expands to every skill in the skill directories
+//! carrying that tag, so a large shared library can be sliced per
+//! workspace without listing each skill.
+
+use colored::Colorize;
+
+use super::{Skill, WorkspaceConfig, lock::skill_name};
+
+/// Prefix of a pane `skills:` entry that selects skills by tag
+pub const SKILL_TAG_PREFIX: &str = "tag:";
+
+impl WorkspaceConfig {
+    /// Names of the skills tagged `tag`, in skill directory order
+    pub fn skills_tagged(&self, tag: &str) -> Vec<String> {
+        self.find_all_skills()
+            .iter()
+            .filter_map(|path| Skill::from_file(path).ok().map(|skill| (path, skill)))
+            .filter(|(_, skill)| skill.tags.iter().any(|t| t == tag))
+            .map(|(path, _)| skill_name(path))
+            .collect()
+    }
+
+    /// Replace `tag:<name>` entries with the names of the skills carrying
+    /// the tag, keeping the first occurrence of each skill
+    pub(super) fn expand_skill_tags(&self, entries: &[String]) -> Vec<String> {
+        if !entries.iter().any(|e| e.starts_with(SKILL_TAG_PREFIX)) {
+            return entries.to_vec();
+        }
+
+        let mut names: Vec<String> = Vec::new();
+        for entry in entries {
+            let expanded = match entry.strip_prefix(SKILL_TAG_PREFIX) {
+                Some(tag) => {
+                    let tagged = self.skills_tagged(tag);
+                    if tagged.is_empty() {
+                        eprintln!("{} No skill is tagged '{}'", "!".yellow(), tag);
+                    }
+                    tagged
+                }
+                None => vec![entry.clone()],
+            };
+            for name in expanded {
+                if !names.contains(&name) {
+                    names.push(name);
+                }
+            }
+        }
+        names
+    }
+
+    /// Whether a pane's `skills:` entries select skill `name`, by name,
+    /// tag or `*`
+    pub fn selects_skill(&self, entries: &[String], name: &str) -> bool {
+        entries.iter().any(|e| e == name || e == "*")
+            || entries.iter().any(|e| e.starts_with(SKILL_TAG_PREFIX))
+                && self
+                    .find_skill(name)
+                    .and_then(|path| Skill::from_file(&path).ok())
+                    .is_some_and(|skill| {
+                        entries
+                            .iter()
+                            .filter_map(|e| e.strip_prefix(SKILL_TAG_PREFIX))
+                            .any(|tag| skill.tags.iter().any(|t| t == tag))
+                    })
+    }
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_skills_expands_tags() {
+        let dir = std::env::temp_dir().join("axel-test-skill-tags");
+        std::fs::remove_dir_all(&dir).ok();
+        let skills = dir.join("skills");
+        std::fs::create_dir_all(skills.join("clippy")).unwrap();
+        std::fs::write(
+            skills.join("clippy/SKILL.md"),
+            "---\ntags: [rust, lint]\n---\nClippy",
+        )
+        .unwrap();
+        std::fs::write(
+            skills.join("cargo-test.md"),
+            "---\ntags: [rust, testing]\n---\nTest",
+        )
+        .unwrap();
+        std::fs::write(skills.join("jest.md"), "---\ntags: [testing]\n---\nJest").unwrap();
+        std::fs::write(skills.join("docs.md"), "Docs").unwrap();
+
+        let manifest = dir.join("AXEL.md");
+        std::fs::write(
+            &manifest,
+            "---\nworkspace: test\nskills:\n  - path: ./skills\nlayouts:\n  panes: []\n---\n",
+        )
+        .unwrap();
+        let config = crate::config::load_config(&manifest).unwrap();
+
+        let mut rust = config.skills_tagged("rust");
+        rust.sort();
+        assert_eq!(rust, vec!["cargo-test", "clippy"]);
+
+        let entries = vec![
+            "docs".to_string(),
+            "tag:rust".to_string(),
+            "tag:testing".to_string(),
+        ];
+        let mut names: Vec<String> = config
+            .resolve_skills(&entries)
+            .iter()
+            .map(|p| skill_name(p))
+            .collect();
+        assert_eq!(names.remove(0), "docs");
+        names.sort();
+        assert_eq!(names, vec!["cargo-test", "clippy", "jest"]);
+
+        assert!(config.selects_skill(&entries, "jest"));
+        assert!(!config.selects_skill(&["tag:lint".to_string()], "jest"));
+        assert!(config.selects_skill(&["*".to_string()], "jest"));
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
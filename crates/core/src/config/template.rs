@@ -0,0 +1,224 @@
+//! Skill templating
+//!
+//! Skill files may contain `{{var}}` placeholders (`{{ var }}` works too),
+//! so one skill can serve several workspaces. Names are letters, digits and
+//! underscores; anything else between braces is left alone.
+//!
+//! ```yaml
+//! skills_vars:
+//!   repo: txtx/barrel
+//!   release_branch: main
+//! ```
+//!
+//! Values come from the manifest's `skills_vars:` and from `--skill-var
+//! KEY=VALUE` on the command line, which wins. Skills that use placeholders
+//! are rendered into `.axel/skills/` in the workspace when they are
+//! installed, and the drivers link the rendered copy; skills without
+//! placeholders are linked from where they live. Placeholders with no value
+//! are left as they are and reported. Skills with overrides for the driver
+//! being installed are transformed first (see `overrides`).
+
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result};
+
+use super::{DRIVER_SKILLS_DIR, WorkspaceConfig, apply_driver_override};
+
+/// Directory rendered skills are written to, relative to the workspace
+pub const RENDERED_SKILLS_DIR: &str = ".axel/skills";
+
+/// Skill paths ready to install
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RenderedSkills {
+    /// Paths to link: the rendered copy for templated skills, the original
+    /// file otherwise
+    pub paths: Vec<PathBuf>,
+    /// Placeholders without a value, by skill file
+    pub undefined: Vec<(PathBuf, Vec<String>)>,
+}
+
+/// Substitute `{{var}}` placeholders in `content` with `vars`.
+///
+/// Returns the rendered content and the names of placeholders that have no
+/// value; those are left untouched.
+pub fn render_template(content: &str, vars: &BTreeMap<String, String>) -> (String, Vec<String>) {
+    let mut out = String::with_capacity(content.len());
+    let mut undefined: Vec<String> = Vec::new();
+    let mut rest = content;
+
+    while let Some(start) = rest.find("{{") {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("}}") else {
+            out.push_str(&rest[start..]);
+            rest = "";
+            break;
+        };
+
+        let name = after[..end].trim();
+        let is_var =
+            !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+        match vars.get(name) {
+            Some(value) if is_var => out.push_str(value),
+            _ => {
+                if is_var && !undefined.iter().any(|n| n == name) {
+                    undefined.push(name.to_string());
+                }
+                out.push_str(&rest[start..start + 2 + end + 2]);
+            }
+        }
+        rest = &after[end + 2..];
+    }
+
+    out.push_str(rest);
+    (out, undefined)
+}
+
+impl WorkspaceConfig {
+    /// Add `KEY=VALUE` assignments from the command line to `skills_vars`,
+    /// replacing manifest values
+    pub fn set_skills_vars(&mut self, assignments: &[String]) -> Result<()> {
+        for assignment in assignments {
+            let Some((key, value)) = assignment.split_once('=') else {
+                anyhow::bail!("Expected KEY=VALUE for --skill-var, got '{}'", assignment);
+            };
+            if key.is_empty() {
+                anyhow::bail!("Missing variable name in --skill-var '{}'", assignment);
+            }
+            self.skills_vars.insert(key.to_string(), value.to_string());
+        }
+        Ok(())
+    }
+
+    /// Prepare the skills at `paths` for `driver`: apply its frontmatter
+    /// overrides and render placeholders, writing changed skills into the
+    /// workspace's `.axel/` directory
+    pub fn render_skills(
+        &self,
+        paths: &[PathBuf],
+        workspace_dir: &Path,
+        driver: &str,
+    ) -> Result<RenderedSkills> {
+        let mut rendered = RenderedSkills::default();
+        for path in paths {
+            let content = std::fs::read_to_string(path)
+                .with_context(|| format!("Failed to read {}", path.display()))?;
+            let overridden = apply_driver_override(&content, driver)
+                .with_context(|| format!("Failed to apply overrides of {}", path.display()))?;
+            if overridden.is_none() && !content.contains("{{") {
+                rendered.paths.push(path.clone());
+                continue;
+            }
+
+            let (output, undefined) =
+                render_template(overridden.as_deref().unwrap_or(&content), &self.skills_vars);
+            if output == content {
+                rendered.paths.push(path.clone());
+            } else {
+                let dir = match overridden {
+                    Some(_) => workspace_dir.join(DRIVER_SKILLS_DIR).join(driver),
+                    None => workspace_dir.join(RENDERED_SKILLS_DIR),
+                };
+                let target = rendered_path(path, &dir);
+                if let Some(parent) = target.parent() {
+                    std::fs::create_dir_all(parent)?;
+                }
+                std::fs::write(&target, output)
+                    .with_context(|| format!("Failed to write {}", target.display()))?;
+                rendered.paths.push(target);
+            }
+            if !undefined.is_empty() {
+                rendered.undefined.push((path.clone(), undefined));
+            }
+        }
+        Ok(rendered)
+    }
+}
+
+/// Where a skill is written under `dir`: `<name>/SKILL.md` stays a
+/// directory skill, flat `<name>.md` files stay flat
+fn rendered_path(path: &Path, dir: &Path) -> PathBuf {
+    match path.parent().and_then(|p| p.file_name()) {
+        Some(name) if path.file_name().is_some_and(|n| n == "SKILL.md") => {
+            dir.join(name).join("SKILL.md")
+        }
+        _ => dir.join(path.file_name().unwrap_or_default()),
+    }
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vars() -> BTreeMap<String, String> {
+        BTreeMap::from([
+            ("repo".to_string(), "txtx/barrel".to_string()),
+            ("branch".to_string(), "main".to_string()),
+        ])
+    }
+
+    #[test]
+    fn test_render_template() {
+        let (out, undefined) = render_template(
+            "Release {{repo}} from {{ branch }}; tag {{version}} and {{version}}.",
+            &vars(),
+        );
+        assert_eq!(
+            out,
+            "Release txtx/barrel from main; tag {{version}} and {{version}}."
+        );
+        assert_eq!(undefined, vec!["version"]);
+
+        // Things that are not placeholders are left alone
+        let (out, undefined) = render_template("${{ github.ref }} {{}} {{a b}} {{repo", &vars());
+        assert_eq!(out, "${{ github.ref }} {{}} {{a b}} {{repo");
+        assert!(undefined.is_empty());
+    }
+
+    #[test]
+    fn test_render_skills_into_workspace() {
+        let dir = std::env::temp_dir().join("axel-test-skill-template");
+        std::fs::remove_dir_all(&dir).ok();
+        std::fs::create_dir_all(dir.join("skills/release-manager")).unwrap();
+        std::fs::write(
+            dir.join("skills/release-manager/SKILL.md"),
+            "# Release {{repo}}\nBranch: {{branch}}\n",
+        )
+        .unwrap();
+        std::fs::write(dir.join("skills/plain.md"), "# Plain\n").unwrap();
+
+        let manifest = dir.join("AXEL.md");
+        std::fs::write(
+            &manifest,
+            "---\nworkspace: test\nskills_vars:\n  repo: acme/app\n  branch: develop\nlayouts:\n  panes: []\n---\n",
+        )
+        .unwrap();
+        let mut config = crate::config::load_config(&manifest).unwrap();
+        config
+            .set_skills_vars(&["branch=release".to_string()])
+            .unwrap();
+        assert!(config.set_skills_vars(&["branch".to_string()]).is_err());
+
+        let paths = vec![
+            dir.join("skills/release-manager/SKILL.md"),
+            dir.join("skills/plain.md"),
+        ];
+        let rendered = config.render_skills(&paths, &dir, "claude").unwrap();
+        let target = dir.join(".axel/skills/release-manager/SKILL.md");
+        assert_eq!(rendered.paths, vec![target.clone(), paths[1].clone()]);
+        assert!(rendered.undefined.is_empty());
+        assert_eq!(
+            std::fs::read_to_string(&target).unwrap(),
+            "# Release acme/app\nBranch: release\n"
+        );
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
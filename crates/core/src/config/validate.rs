@@ -0,0 +1,738 @@
+//! Manifest validation
+//!
+//! `axel config validate` checks a parsed manifest for problems that would
+//! otherwise only surface (or be silently ignored) at launch time: grid cells
+//! referencing undefined panes, overlapping cells, spans that can't be laid
+//! out, sizes over 100%, missing
+//! skills, and paths that don't exist. All problems are reported at once.
+
+use std::{
+    collections::HashMap,
+    fmt,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Result;
+use serde::Serialize;
+
+use super::{
+    AiPaneConfig, Grid, GridType, PaneConfig, SKILL_TAG_PREFIX, ShellRun, WorkspaceConfig,
+    expand_path, group_by_window, load_config,
+};
+use crate::{
+    backend::BackendKind,
+    hooks::{OPTIONAL_HOOK_EVENTS, REQUIRED_HOOK_EVENTS},
+    policy::PolicySet,
+    tmux::layout_tree,
+};
+
+/// Claude Code permission modes
+const PERMISSION_MODES: &[&str] = &["default", "acceptEdits", "plan", "bypassPermissions"];
+
+/// Claude Code print-mode output formats
+const OUTPUT_FORMATS: &[&str] = &["text", "json", "stream-json"];
+
+/// How serious a validation problem is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// The manifest won't launch as written
+    Error,
+    /// Likely a mistake, but launching still works
+    Warning,
+}
+
+/// A single problem found in a manifest
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ValidationIssue {
+    /// How serious the problem is
+    pub severity: Severity,
+    /// Human-readable description
+    pub message: String,
+}
+
+impl ValidationIssue {
+    fn error(message: String) -> Self {
+        ValidationIssue {
+            severity: Severity::Error,
+            message,
+        }
+    }
+
+    fn warning(message: String) -> Self {
+        ValidationIssue {
+            severity: Severity::Warning,
+            message,
+        }
+    }
+}
+
+impl fmt::Display for ValidationIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+/// Load the manifest at `path` and validate it.
+///
+/// Fails only if the manifest can't be parsed at all.
+pub fn validate_manifest(path: &Path) -> Result<Vec<ValidationIssue>> {
+    Ok(load_config(path)?.validate())
+}
+
+impl WorkspaceConfig {
+    /// Check the manifest for problems, returning all of them
+    pub fn validate(&self) -> Vec<ValidationIssue> {
+        let mut issues = Vec::new();
+        self.validate_grids(&mut issues);
+        self.validate_panes(&mut issues);
+        self.validate_orchestrator(&mut issues);
+        self.validate_skill_paths(&mut issues);
+        self.validate_hooks(&mut issues);
+        self.validate_policies(&mut issues);
+        issues
+    }
+
+    fn validate_grids(&self, issues: &mut Vec<ValidationIssue>) {
+        let pane_types: Vec<&str> = self.layouts.panes.iter().map(|p| p.pane_type()).collect();
+
+        let mut grid_names: Vec<&String> = self.layouts.grids.keys().collect();
+        grid_names.sort();
+
+        for grid_name in grid_names {
+            let grid = &self.layouts.grids[grid_name];
+            // Positions and sizes are per window; `None` is the first window
+            let mut positions: HashMap<(Option<&str>, u32, u32), &str> = HashMap::new();
+            let mut col_widths: HashMap<(Option<&str>, u32), u32> = HashMap::new();
+            let mut col_heights: HashMap<(Option<&str>, u32), u32> = HashMap::new();
+
+            for (cell_name, cell) in &grid.cells {
+                let pane_type = cell.pane_type.as_deref().unwrap_or(cell_name.as_str());
+                if !pane_types.contains(&pane_type) {
+                    issues.push(ValidationIssue::error(format!(
+                        "Grid '{}': cell '{}' references undefined pane type '{}'",
+                        grid_name, cell_name, pane_type
+                    )));
+                }
+
+                let window = cell.window.as_deref();
+                if let Some(other) = positions.insert((window, cell.col, cell.row), cell_name) {
+                    issues.push(ValidationIssue::error(format!(
+                        "Grid '{}': cells '{}' and '{}' both occupy col {}, row {}{}",
+                        grid_name,
+                        other,
+                        cell_name,
+                        cell.col,
+                        cell.row,
+                        in_window(window)
+                    )));
+                }
+
+                // Cells in a column share its width; the first one sets it
+                if let Some(width) = cell.width {
+                    col_widths.entry((window, cell.col)).or_insert(width);
+                }
+                if let Some(height) = cell.height {
+                    *col_heights.entry((window, cell.col)).or_default() += height;
+                }
+            }
+
+            let mut window_widths: HashMap<Option<&str>, u32> = HashMap::new();
+            for ((window, _), width) in col_widths {
+                *window_widths.entry(window).or_default() += width;
+            }
+            let mut windows: Vec<_> = window_widths.into_iter().collect();
+            windows.sort_unstable();
+            for (window, total_width) in windows.into_iter().filter(|(_, w)| *w > 100) {
+                issues.push(ValidationIssue::error(format!(
+                    "Grid '{}': column widths{} sum to {}% (over 100%)",
+                    grid_name,
+                    in_window(window),
+                    total_width
+                )));
+            }
+
+            self.validate_spans(grid_name, grid, issues);
+            self.validate_focus(grid_name, grid, issues);
+            if grid.run != ShellRun::First && grid.grid_type != GridType::Shell {
+                issues.push(ValidationIssue::warning(format!(
+                    "Grid '{}': run only applies to shell grids",
+                    grid_name
+                )));
+            }
+
+            let mut cols: Vec<_> = col_heights.into_iter().collect();
+            cols.sort_unstable();
+            for ((window, col), total_height) in cols.into_iter().filter(|(_, h)| *h > 100) {
+                issues.push(ValidationIssue::error(format!(
+                    "Grid '{}': row heights in col {}{} sum to {}% (over 100%)",
+                    grid_name,
+                    col,
+                    in_window(window),
+                    total_height
+                )));
+            }
+        }
+    }
+
+    /// Check that cells spanning several columns or rows can be laid out
+    fn validate_spans(&self, grid_name: &str, grid: &Grid, issues: &mut Vec<ValidationIssue>) {
+        let spans = |span: Option<u32>| span.is_some_and(|n| n > 1);
+        if !grid
+            .cells
+            .values()
+            .any(|cell| spans(cell.colspan) || spans(cell.rowspan))
+        {
+            return;
+        }
+
+        let tmux = matches!(grid.grid_type, GridType::Tmux | GridType::TmuxCC)
+            && self.backend.unwrap_or_default() == BackendKind::Tmux;
+        if !tmux {
+            issues.push(ValidationIssue::warning(format!(
+                "Grid '{}': colspan and rowspan only apply to tmux grids",
+                grid_name
+            )));
+            return;
+        }
+
+        let panes = self.resolve_panes(Some(grid_name));
+        for (window, window_panes) in group_by_window(&panes) {
+            if let Err(e) = layout_tree(&window_panes) {
+                issues.push(ValidationIssue::error(format!(
+                    "Grid '{}'{}: {}",
+                    grid_name,
+                    in_window(window),
+                    e
+                )));
+            }
+        }
+    }
+
+    /// Check that `focus:` names a cell of the grid
+    fn validate_focus(&self, grid_name: &str, grid: &Grid, issues: &mut Vec<ValidationIssue>) {
+        if let Some(ref focus) = grid.focus
+            && !grid.cells.contains_key(focus)
+        {
+            issues.push(ValidationIssue::error(format!(
+                "Grid '{}': focus '{}' is not a cell of the grid",
+                grid_name, focus
+            )));
+        }
+
+        let tmux = matches!(grid.grid_type, GridType::Tmux | GridType::TmuxCC)
+            && self.backend.unwrap_or_default() == BackendKind::Tmux;
+        if (grid.focus.is_some() || grid.zoomed) && !tmux {
+            issues.push(ValidationIssue::warning(format!(
+                "Grid '{}': focus and zoomed only apply to tmux grids",
+                grid_name
+            )));
+        }
+    }
+
+    fn validate_panes(&self, issues: &mut Vec<ValidationIssue>) {
+        let workspace_dir = self.workspace_dir();
+
+        for pane in &self.layouts.panes {
+            let name = pane.pane_type();
+
+            if let Some(path) = pane.path() {
+                let resolved = resolve_path(path, workspace_dir.as_deref());
+                if !resolved.is_dir() {
+                    issues.push(ValidationIssue::error(format!(
+                        "Pane '{}': path {} does not exist",
+                        name,
+                        resolved.display()
+                    )));
+                }
+            }
+
+            let ai = match pane {
+                PaneConfig::Claude(c)
+                | PaneConfig::Codex(c)
+                | PaneConfig::Opencode(c)
+                | PaneConfig::Antigravity(c)
+                | PaneConfig::External(c) => c,
+                PaneConfig::Custom(_) => continue,
+            };
+
+            for skill in &ai.skills {
+                if let Some(tag) = skill.strip_prefix(SKILL_TAG_PREFIX) {
+                    if self.skills_tagged(tag).is_empty() {
+                        issues.push(ValidationIssue::warning(format!(
+                            "Pane '{}': no skill is tagged '{}'",
+                            name, tag
+                        )));
+                    }
+                } else if skill != "*" && self.find_skill(skill).is_none() {
+                    issues.push(ValidationIssue::error(format!(
+                        "Pane '{}': skill '{}' not found in any skill directory",
+                        name, skill
+                    )));
+                }
+            }
+
+            self.validate_claude_options(name, pane, ai, issues);
+
+            if !ai.mcp.is_empty()
+                && matches!(pane, PaneConfig::Codex(_) | PaneConfig::Antigravity(_))
+            {
+                issues.push(ValidationIssue::warning(format!(
+                    "Pane '{}': mcp is only supported by claude and opencode panes",
+                    name
+                )));
+            }
+
+            for (file, path) in ai
+                .context_files
+                .iter()
+                .zip(ai.context_paths(workspace_dir.as_deref()))
+            {
+                if !path.is_file() {
+                    issues.push(ValidationIssue::error(format!(
+                        "Pane '{}': context file '{}' does not exist",
+                        name, file
+                    )));
+                }
+            }
+        }
+    }
+
+    fn validate_claude_options(
+        &self,
+        name: &str,
+        pane: &PaneConfig,
+        ai: &AiPaneConfig,
+        issues: &mut Vec<ValidationIssue>,
+    ) {
+        let options: Vec<&str> = [
+            ("resume", ai.resume.is_some()),
+            ("session_id", ai.session_id.is_some()),
+            ("permission_mode", ai.permission_mode.is_some()),
+            ("output_format", ai.output_format.is_some()),
+            ("max_turns", ai.max_turns.is_some()),
+            ("add_dirs", !ai.add_dirs.is_empty()),
+        ]
+        .into_iter()
+        .filter_map(|(option, set)| set.then_some(option))
+        .collect();
+        if options.is_empty() {
+            return;
+        }
+        if !matches!(pane, PaneConfig::Claude(_)) {
+            issues.push(ValidationIssue::warning(format!(
+                "Pane '{}': {} only apply to claude panes",
+                name,
+                options.join(", ")
+            )));
+            return;
+        }
+
+        if let Some(id) = ai.session_id.as_deref().filter(|id| !is_uuid(id)) {
+            issues.push(ValidationIssue::error(format!(
+                "Pane '{}': session_id '{}' is not a UUID",
+                name, id
+            )));
+        }
+        if let Some(mode) = ai
+            .permission_mode
+            .as_deref()
+            .filter(|m| !PERMISSION_MODES.contains(m))
+        {
+            issues.push(ValidationIssue::warning(format!(
+                "Pane '{}': unknown permission_mode '{}' (expected one of {})",
+                name,
+                mode,
+                PERMISSION_MODES.join(", ")
+            )));
+        }
+        if let Some(format) = ai
+            .output_format
+            .as_deref()
+            .filter(|f| !OUTPUT_FORMATS.contains(f))
+        {
+            issues.push(ValidationIssue::error(format!(
+                "Pane '{}': unknown output_format '{}' (expected one of {})",
+                name,
+                format,
+                OUTPUT_FORMATS.join(", ")
+            )));
+        }
+        let print_mode = ai.args.iter().any(|a| a == "-p" || a == "--print");
+        if !print_mode && (ai.output_format.is_some() || ai.max_turns.is_some()) {
+            issues.push(ValidationIssue::warning(format!(
+                "Pane '{}': output_format and max_turns only apply with `-p` in args",
+                name
+            )));
+        }
+    }
+
+    fn validate_orchestrator(&self, issues: &mut Vec<ValidationIssue>) {
+        let Some(orchestrator) = &self.orchestrator else {
+            return;
+        };
+
+        for (role, name) in &orchestrator.roles {
+            match self.layouts.panes.iter().find(|p| p.pane_type() == name) {
+                None => issues.push(ValidationIssue::error(format!(
+                    "Orchestrator role '{}': pane '{}' is not defined",
+                    role, name
+                ))),
+                Some(PaneConfig::Claude(_) | PaneConfig::Codex(_)) => {}
+                Some(pane) if pane.is_ai() => issues.push(ValidationIssue::warning(format!(
+                    "Orchestrator role '{}': {} panes can't report their replies \
+                     (only claude and codex can)",
+                    role,
+                    pane.actual_type()
+                ))),
+                Some(_) => issues.push(ValidationIssue::error(format!(
+                    "Orchestrator role '{}': pane '{}' is not an AI pane",
+                    role, name
+                ))),
+            }
+        }
+
+        for step in &orchestrator.pipeline {
+            for role in [&step.from, &step.to] {
+                if !orchestrator.roles.contains_key(role) {
+                    issues.push(ValidationIssue::error(format!(
+                        "Orchestrator pipeline: step {} -> {} uses undefined role '{}'",
+                        step.from, step.to, role
+                    )));
+                }
+            }
+        }
+    }
+
+    fn validate_skill_paths(&self, issues: &mut Vec<ValidationIssue>) {
+        let workspace_dir = self.workspace_dir();
+        for skill_path in &self.skills {
+            let resolved = resolve_path(&skill_path.path, workspace_dir.as_deref());
+            if !resolved.is_dir() {
+                issues.push(ValidationIssue::warning(format!(
+                    "Skill directory {} does not exist",
+                    resolved.display()
+                )));
+            }
+        }
+    }
+
+    fn validate_policies(&self, issues: &mut Vec<ValidationIssue>) {
+        if let Err(e) = PolicySet::compile(&self.policies) {
+            issues.push(ValidationIssue::error(format!("{:#}", e)));
+        }
+        if !self.policies.is_empty() && self.policed_panes().is_empty() {
+            issues.push(ValidationIssue::warning(
+                "policies only apply to Claude panes, and there are none".to_string(),
+            ));
+        }
+    }
+
+    fn validate_hooks(&self, issues: &mut Vec<ValidationIssue>) {
+        for event in &self.hooks.events {
+            if REQUIRED_HOOK_EVENTS.contains(&event.as_str()) {
+                issues.push(ValidationIssue::warning(format!(
+                    "hooks: {} is always reported and needn't be listed",
+                    event
+                )));
+            } else if !OPTIONAL_HOOK_EVENTS.contains(&event.as_str()) {
+                issues.push(ValidationIssue::error(format!(
+                    "hooks: unknown event '{}' (expected one of {})",
+                    event,
+                    OPTIONAL_HOOK_EVENTS.join(", ")
+                )));
+            }
+        }
+    }
+}
+
+/// Suffix naming a cell's window in messages (empty for the first window)
+fn in_window(window: Option<&str>) -> String {
+    window
+        .map(|w| format!(" of window '{}'", w))
+        .unwrap_or_default()
+}
+
+/// Resolve a manifest path: absolute and `~` paths as-is, others against `base`
+fn resolve_path(path: &str, base: Option<&Path>) -> PathBuf {
+    if path.starts_with('/') || path.starts_with('~') {
+        PathBuf::from(expand_path(path))
+    } else if let Some(base) = base {
+        base.join(path)
+    } else {
+        PathBuf::from(path)
+    }
+}
+
+/// Whether `id` is a UUID in its hyphenated form, as Claude Code requires
+fn is_uuid(id: &str) -> bool {
+    let groups: Vec<&str> = id.split('-').collect();
+    groups.iter().map(|g| g.len()).eq([8, 4, 4, 4, 12])
+        && groups
+            .iter()
+            .all(|g| g.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_reports_all_problems() {
+        let dir = std::env::temp_dir().join("axel-test-validate");
+        std::fs::remove_dir_all(&dir).ok();
+        std::fs::create_dir_all(dir.join("skills")).unwrap();
+        std::fs::write(dir.join("skills/review.md"), "Review").unwrap();
+        std::fs::write(
+            dir.join("AXEL.md"),
+            r#"---
+workspace: test
+skills:
+  - path: ./skills
+  - path: ./missing-skills
+layouts:
+  panes:
+    - type: claude
+      skills: [review, ghost]
+      context_files: [NOTES.md]
+      session_id: not-a-uuid
+      permission_mode: yolo
+    - type: shell
+      path: ./nowhere
+  grids:
+    default:
+      claude:
+        col: 0
+        width: 70
+      shell:
+        col: 1
+        width: 40
+      logs:
+        col: 1
+      other:
+        pane_type: codex
+        col: 2
+        height: 60
+      more:
+        pane_type: shell
+        col: 2
+        row: 1
+        height: 50
+---
+"#,
+        )
+        .unwrap();
+
+        let issues = validate_manifest(&dir.join("AXEL.md")).unwrap();
+        let messages: Vec<_> = issues.iter().map(|i| i.message.as_str()).collect();
+        let missing_skills = format!(
+            "Skill directory {} does not exist",
+            dir.join("./missing-skills").display()
+        );
+        let missing_path = format!(
+            "Pane 'shell': path {} does not exist",
+            dir.join("./nowhere").display()
+        );
+        assert_eq!(
+            messages,
+            vec![
+                "Grid 'default': cell 'logs' references undefined pane type 'logs'",
+                "Grid 'default': cells 'shell' and 'logs' both occupy col 1, row 0",
+                "Grid 'default': cell 'other' references undefined pane type 'codex'",
+                "Grid 'default': column widths sum to 110% (over 100%)",
+                "Grid 'default': row heights in col 2 sum to 110% (over 100%)",
+                "Pane 'claude': skill 'ghost' not found in any skill directory",
+                "Pane 'claude': session_id 'not-a-uuid' is not a UUID",
+                "Pane 'claude': unknown permission_mode 'yolo' (expected one of default, acceptEdits, plan, bypassPermissions)",
+                "Pane 'claude': context file 'NOTES.md' does not exist",
+                missing_path.as_str(),
+                missing_skills.as_str(),
+            ]
+        );
+        assert_eq!(issues.last().unwrap().severity, Severity::Warning);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_validate_orchestrator() {
+        let config: WorkspaceConfig = serde_yaml::from_str(
+            r#"
+workspace: test
+orchestrator:
+  roles:
+    planner: claude
+    implementer: opencode
+    reviewer: server
+    tester: ghost
+  pipeline:
+    - from: planner
+      to: implementer
+    - from: implementer
+      to: deployer
+layouts:
+  panes:
+    - type: claude
+    - type: opencode
+    - type: custom
+      name: server
+      command: pnpm dev
+"#,
+        )
+        .unwrap();
+
+        let messages: Vec<_> = config.validate().into_iter().map(|i| i.message).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "Orchestrator role 'implementer': opencode panes can't report their replies (only claude and codex can)",
+                "Orchestrator role 'reviewer': pane 'server' is not an AI pane",
+                "Orchestrator role 'tester': pane 'ghost' is not defined",
+                "Orchestrator pipeline: step implementer -> deployer uses undefined role 'deployer'",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_spans() {
+        let config: WorkspaceConfig = serde_yaml::from_str(
+            r#"
+workspace: test
+layouts:
+  panes:
+    - type: claude
+    - type: shell
+    - type: custom
+      name: logs
+    - type: custom
+      name: tests
+  grids:
+    default:
+      claude: { col: 0, row: 0, rowspan: 2 }
+      shell: { col: 1, row: 0 }
+      tests: { col: 1, row: 1 }
+      logs: { col: 0, row: 2, colspan: 2 }
+    tangled:
+      claude: { col: 0, row: 0, colspan: 2 }
+      shell: { col: 1, row: 0, rowspan: 2 }
+    zellij:
+      type: zellij
+      claude: { col: 0, row: 0, colspan: 2 }
+"#,
+        )
+        .unwrap();
+
+        let messages: Vec<_> = config.validate().into_iter().map(|i| i.message).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "Grid 'tangled': Cells claude, shell overlap or can't be divided into columns and rows",
+                "Grid 'zellij': colspan and rowspan only apply to tmux grids",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_focus() {
+        let config: WorkspaceConfig = serde_yaml::from_str(
+            r#"
+workspace: test
+layouts:
+  panes:
+    - type: claude
+    - type: shell
+  grids:
+    default:
+      focus: shell
+      zoomed: true
+      claude: { col: 0, row: 0 }
+      shell: { col: 1, row: 0 }
+    typo:
+      focus: codex
+      claude: { col: 0, row: 0 }
+    zellij:
+      type: zellij
+      zoomed: true
+      claude: { col: 0, row: 0 }
+"#,
+        )
+        .unwrap();
+
+        let grid = &config.layouts.grids["default"];
+        assert_eq!(grid.focus.as_deref(), Some("shell"));
+        assert!(grid.zoomed);
+        assert!(!grid.cells.contains_key("focus"));
+
+        let messages: Vec<_> = config.validate().into_iter().map(|i| i.message).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "Grid 'typo': focus 'codex' is not a cell of the grid",
+                "Grid 'zellij': focus and zoomed only apply to tmux grids",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_shell_run() {
+        let config: WorkspaceConfig = serde_yaml::from_str(
+            r#"
+workspace: test
+layouts:
+  panes:
+    - type: claude
+    - type: shell
+  grids:
+    default:
+      type: shell
+      run: jobs
+      claude: { col: 0, row: 0 }
+      shell: { col: 1, row: 0 }
+    tmux:
+      run: pick
+      claude: { col: 0, row: 0 }
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(config.grid_shell_run(None), ShellRun::Jobs);
+        assert_eq!(config.grid_shell_run(Some("tmux")), ShellRun::Pick);
+        assert_eq!(config.grid_shell_run(Some("missing")), ShellRun::First);
+        assert!(!config.layouts.grids["default"].cells.contains_key("run"));
+
+        let messages: Vec<_> = config.validate().into_iter().map(|i| i.message).collect();
+        assert_eq!(
+            messages,
+            vec!["Grid 'tmux': run only applies to shell grids"]
+        );
+    }
+
+    #[test]
+    fn test_validate_hooks() {
+        let config: WorkspaceConfig = serde_yaml::from_str(
+            r#"
+workspace: test
+hooks:
+  events: [PreCompact, Stop, Compact]
+layouts:
+  panes:
+    - type: claude
+"#,
+        )
+        .unwrap();
+
+        let messages: Vec<_> = config.validate().into_iter().map(|i| i.message).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "hooks: Stop is always reported and needn't be listed",
+                "hooks: unknown event 'Compact' (expected one of Notification, SubagentStop, \
+                 PreCompact, SessionStart, SessionEnd)",
+            ]
+        );
+    }
+}
@@ -0,0 +1,286 @@
+//! Preparing new worktrees
+//!
+//! A fresh worktree only has tracked files, so anything ignored that the
+//! project needs to run (`.env`, local config, installed dependencies) is
+//! missing. The `worktree.copy` list names what to bring over from the main
+//! checkout when `axel -w` creates a worktree, and `worktree.setup` lists
+//! commands to run in it before the workspace starts:
+//!
+//! ```yaml
+//! worktree:
+//!   copy:
+//!     - .env
+//!     - config/local.toml
+//!     - path: node_modules     # shared with the main checkout
+//!       symlink: true
+//!     - path: vendor
+//!       exclude: [target]      # names skipped inside copied directories
+//!   setup:
+//!     - pnpm install
+//!     - direnv allow
+//! ```
+//!
+//! Copy paths are relative to the repository root. Entries missing from the
+//! main checkout, or already present in the worktree, are skipped.
+//!
+//! Setup commands run in the first pane, so their output is visible there,
+//! and the pane's own command starts once they finish. The other panes wait
+//! for the same moment, so no agent or server starts in a half-prepared tree.
+
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+use super::WorkspaceConfig;
+use crate::{platform, shell};
+
+/// `worktree:` section of the manifest
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct WorktreeConfig {
+    /// Untracked files and directories copied into new worktrees
+    #[serde(default)]
+    pub copy: Vec<WorktreeCopy>,
+    /// Shell commands run once in a newly created worktree
+    #[serde(default)]
+    pub setup: Vec<String>,
+}
+
+impl WorktreeConfig {
+    /// The setup commands as one command line that stops at the first failure
+    pub fn setup_command(&self) -> Option<String> {
+        (!self.setup.is_empty()).then(|| self.setup.join(" && "))
+    }
+}
+
+impl WorkspaceConfig {
+    /// Run `worktree.setup` in the first pane of `grid` before its command.
+    ///
+    /// The first pane creates `marker` once the setup is done, whether it
+    /// succeeded or not; every other pane waits for it before starting.
+    pub fn schedule_worktree_setup(&mut self, grid: Option<&str>, marker: &Path) {
+        let Some(setup) = self.worktree.setup_command() else {
+            return;
+        };
+        let names: Vec<String> = self
+            .resolve_panes(grid)
+            .iter()
+            .map(|pane| pane.config.pane_type().to_string())
+            .collect();
+        let Some(first) = names.first() else {
+            return;
+        };
+
+        let marker = shell::quote(&marker.to_string_lossy()).into_owned();
+        let run = format!("sh -c {}; touch {}", shell::single_quote(&setup), marker);
+        let wait = format!(
+            "sh -c {}",
+            shell::single_quote(&format!(
+                "[ -e {m} ] || echo 'Waiting for worktree setup...'; \
+                 until [ -e {m} ]; do sleep 0.5; done",
+                m = marker
+            ))
+        );
+
+        for pane in &mut self.layouts.panes {
+            let step = if pane.pane_type() == first {
+                &run
+            } else if names.iter().any(|name| name == pane.pane_type()) {
+                &wait
+            } else {
+                continue;
+            };
+            let startup = match pane.startup_command() {
+                Some(startup) => format!("{}; {}", step, startup),
+                None => step.clone(),
+            };
+            pane.set_startup_command(startup);
+        }
+    }
+}
+
+/// One `worktree.copy` entry, written as a bare path or a mapping
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(from = "WorktreeCopyRaw")]
+pub struct WorktreeCopy {
+    /// Path relative to the repository root
+    pub path: String,
+    /// Symlink to the main checkout's copy instead of copying
+    pub symlink: bool,
+    /// File or directory names skipped inside a copied directory
+    pub exclude: Vec<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum WorktreeCopyRaw {
+    Path(String),
+    Entry {
+        path: String,
+        #[serde(default)]
+        symlink: bool,
+        #[serde(default)]
+        exclude: Vec<String>,
+    },
+}
+
+impl From<WorktreeCopyRaw> for WorktreeCopy {
+    fn from(raw: WorktreeCopyRaw) -> Self {
+        match raw {
+            WorktreeCopyRaw::Path(path) => WorktreeCopy {
+                path,
+                symlink: false,
+                exclude: Vec::new(),
+            },
+            WorktreeCopyRaw::Entry {
+                path,
+                symlink,
+                exclude,
+            } => WorktreeCopy {
+                path,
+                symlink,
+                exclude,
+            },
+        }
+    }
+}
+
+impl WorktreeCopy {
+    /// Bring this entry from the checkout at `source` into the worktree at
+    /// `target`.
+    ///
+    /// Returns false when there was nothing to do: the entry doesn't exist
+    /// in `source` or already exists in `target`.
+    pub fn apply(&self, source: &Path, target: &Path) -> Result<bool> {
+        let from = source.join(&self.path);
+        let to = target.join(&self.path);
+        if from.symlink_metadata().is_err() || to.symlink_metadata().is_ok() {
+            return Ok(false);
+        }
+
+        if let Some(parent) = to.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        if self.symlink {
+            platform::symlink(&from, &to)
+        } else {
+            copy_recursive(&from, &to, &self.exclude)
+        }
+        .with_context(|| format!("Failed to copy {} into the worktree", self.path))?;
+        Ok(true)
+    }
+}
+
+/// Copy a file or directory, skipping entries named in `exclude`
+fn copy_recursive(from: &Path, to: &Path, exclude: &[String]) -> std::io::Result<()> {
+    let metadata = from.symlink_metadata()?;
+    if metadata.is_symlink() {
+        return platform::symlink(&std::fs::read_link(from)?, to);
+    }
+    if !metadata.is_dir() {
+        return std::fs::copy(from, to).map(|_| ());
+    }
+
+    std::fs::create_dir_all(to)?;
+    for entry in std::fs::read_dir(from)? {
+        let entry = entry?;
+        let name = entry.file_name();
+        if exclude.iter().any(|e| *e == *name.to_string_lossy()) {
+            continue;
+        }
+        copy_recursive(&entry.path(), &to.join(&name), exclude)?;
+    }
+    Ok(())
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_schedule_worktree_setup() {
+        let yaml = r#"
+workspace: test
+worktree:
+  setup: [pnpm install, direnv allow]
+layouts:
+  panes:
+    - type: claude
+    - type: custom
+      name: server
+      command: pnpm dev
+      startup_command: nvm use
+    - type: custom
+      name: unused
+  grids:
+    default:
+      claude: { col: 0, row: 0 }
+      server: { col: 1, row: 0 }
+"#;
+        let mut config: WorkspaceConfig = serde_yaml::from_str(yaml).unwrap();
+        config.schedule_worktree_setup(None, Path::new("/tmp/axel-setup-1"));
+        let startup: Vec<_> = config
+            .layouts
+            .panes
+            .iter()
+            .map(|pane| pane.startup_command())
+            .collect();
+
+        assert_eq!(
+            startup[0],
+            Some("sh -c 'pnpm install && direnv allow'; touch /tmp/axel-setup-1")
+        );
+        let server = startup[1].unwrap();
+        assert!(server.starts_with("sh -c '[ -e /tmp/axel-setup-1 ] ||"));
+        assert!(server.ends_with("done'; nvm use"));
+        assert_eq!(startup[2], None);
+    }
+
+    #[test]
+    fn test_worktree_copy_entries() {
+        let config: WorktreeConfig = serde_yaml::from_str(
+            "copy:\n  - .env\n  - path: node_modules\n    symlink: true\n  - path: vendor\n    exclude: [target]\n",
+        )
+        .unwrap();
+        assert_eq!(config.copy[0].path, ".env");
+        assert!(!config.copy[0].symlink);
+        assert!(config.copy[1].symlink);
+        assert_eq!(config.copy[2].exclude, ["target"]);
+
+        let dir = std::env::temp_dir().join("axel-test-worktree-copy");
+        std::fs::remove_dir_all(&dir).ok();
+        let (main, worktree) = (dir.join("main"), dir.join("worktree"));
+        std::fs::create_dir_all(main.join("node_modules/pkg")).unwrap();
+        std::fs::create_dir_all(main.join("vendor/lib/target")).unwrap();
+        std::fs::create_dir_all(&worktree).unwrap();
+        std::fs::write(main.join(".env"), "KEY=1\n").unwrap();
+        std::fs::write(main.join("vendor/lib/mod.rs"), "").unwrap();
+        std::fs::write(main.join("vendor/lib/target/out"), "").unwrap();
+
+        for entry in &config.copy {
+            assert!(entry.apply(&main, &worktree).unwrap(), "{}", entry.path);
+        }
+        assert_eq!(
+            std::fs::read_to_string(worktree.join(".env")).unwrap(),
+            "KEY=1\n"
+        );
+        assert_eq!(
+            std::fs::read_link(worktree.join("node_modules")).unwrap(),
+            main.join("node_modules")
+        );
+        assert!(worktree.join("vendor/lib/mod.rs").is_file());
+        assert!(!worktree.join("vendor/lib/target").exists());
+
+        // Present in the worktree already, or missing from the main checkout
+        assert!(!config.copy[0].apply(&main, &worktree).unwrap());
+        std::fs::remove_file(main.join(".env")).unwrap();
+        std::fs::remove_file(worktree.join(".env")).unwrap();
+        assert!(!config.copy[0].apply(&main, &worktree).unwrap());
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -0,0 +1,348 @@
+//! Environment checks for `axel doctor`
+//!
+//! Each check looks at one thing axel depends on (tmux and the features the
+//! workspace turns on, the agent CLIs, the config directories, the event
+//! server's port, the manifest) and, when something is wrong, says how to
+//! fix it.
+
+use std::{
+    net::TcpListener,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use crate::{
+    PaneConfig,
+    config::{Severity, load_config},
+    server::ServerClient,
+};
+
+/// Oldest tmux with every feature the workspace uses
+const TMUX_RECOMMENDED: (u32, u32) = (3, 3);
+
+/// Tmux options axel turns on, with the version that added them
+const TMUX_FEATURES: &[(&str, (u32, u32))] = &[
+    // Lets agents' escape sequences (images, notifications) through
+    ("allow-passthrough", (3, 3)),
+    // Sends modified keys like Shift+Enter to the agents
+    ("extended-keys", (3, 2)),
+];
+
+/// Agent CLIs checked, with how to install them
+const AGENTS: &[(&str, &str)] = &[
+    ("claude", "npm install -g @anthropic-ai/claude-code"),
+    ("codex", "npm install -g @openai/codex"),
+    ("opencode", "npm install -g opencode-ai"),
+];
+
+/// Outcome of a check
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckStatus {
+    Ok,
+    Warning,
+    Error,
+}
+
+/// Result of one check
+#[derive(Debug, Clone)]
+pub struct Check {
+    pub status: CheckStatus,
+    /// What was found
+    pub message: String,
+    /// How to fix it, for failed checks
+    pub fix: Option<String>,
+}
+
+impl Check {
+    fn ok(message: impl Into<String>) -> Self {
+        Self {
+            status: CheckStatus::Ok,
+            message: message.into(),
+            fix: None,
+        }
+    }
+
+    fn warning(message: impl Into<String>, fix: impl Into<String>) -> Self {
+        Self {
+            status: CheckStatus::Warning,
+            message: message.into(),
+            fix: Some(fix.into()),
+        }
+    }
+
+    fn error(message: impl Into<String>, fix: impl Into<String>) -> Self {
+        Self {
+            status: CheckStatus::Error,
+            message: message.into(),
+            fix: Some(fix.into()),
+        }
+    }
+}
+
+/// Run every check for the workspace at `manifest_path`, with the event
+/// server on `port`
+pub fn run_checks(manifest_path: &Path, port: u16) -> Vec<Check> {
+    let mut checks = vec![check_tmux()];
+    let drivers = manifest_drivers(manifest_path);
+    checks.extend(
+        AGENTS
+            .iter()
+            .map(|(name, install)| check_agent(name, install, drivers.iter().any(|d| d == name))),
+    );
+    checks.extend(
+        config_dirs(manifest_path)
+            .iter()
+            .map(|dir| check_writable(dir)),
+    );
+    checks.push(check_port(port));
+    checks.push(check_manifest(manifest_path));
+    checks
+}
+
+// =============================================================================
+// Tmux
+// =============================================================================
+
+/// Parse the output of `tmux -V` (`tmux 3.4`, `tmux 3.3a`, `tmux next-3.6`)
+/// into its major and minor version
+pub fn parse_tmux_version(output: &str) -> Option<(u32, u32)> {
+    let version = output.trim().strip_prefix("tmux ")?;
+    let version = version.rsplit('-').next()?;
+    let (major, minor) = version.split_once('.')?;
+    let minor: String = minor.chars().take_while(char::is_ascii_digit).collect();
+    Some((major.parse().ok()?, minor.parse().ok()?))
+}
+
+/// Tmux options `version` doesn't have
+pub fn missing_tmux_features(version: (u32, u32)) -> Vec<&'static str> {
+    TMUX_FEATURES
+        .iter()
+        .filter(|(_, since)| version < *since)
+        .map(|(name, _)| *name)
+        .collect()
+}
+
+fn check_tmux() -> Check {
+    let output = match Command::new("tmux").arg("-V").output() {
+        Ok(output) if output.status.success() => output,
+        _ => {
+            return Check::error(
+                "tmux not found",
+                "Install tmux 3.3 or later (brew install tmux, apt install tmux)",
+            );
+        }
+    };
+    let output = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    let Some(version) = parse_tmux_version(&output) else {
+        // Development builds (`tmux master`) have every feature
+        return Check::ok(output);
+    };
+
+    let missing = missing_tmux_features(version);
+    if missing.is_empty() {
+        Check::ok(output)
+    } else {
+        Check::warning(
+            format!("{} lacks {}", output, missing.join(" and ")),
+            format!(
+                "Upgrade tmux to {}.{} or later",
+                TMUX_RECOMMENDED.0, TMUX_RECOMMENDED.1
+            ),
+        )
+    }
+}
+
+// =============================================================================
+// Agents
+// =============================================================================
+
+/// Drivers of the AI panes in the manifest, if it loads
+fn manifest_drivers(manifest_path: &Path) -> Vec<String> {
+    let Ok(config) = load_config(manifest_path) else {
+        return Vec::new();
+    };
+    config
+        .layouts
+        .panes
+        .iter()
+        .filter(|pane| !matches!(pane, PaneConfig::Custom(_)))
+        .map(|pane| pane.actual_type().to_string())
+        .collect()
+}
+
+/// Check that `name` runs; missing is an error only when the manifest
+/// `used` it
+fn check_agent(name: &str, install: &str, used: bool) -> Check {
+    match Command::new(name).arg("--version").output() {
+        Ok(output) if output.status.success() => {
+            let version = String::from_utf8_lossy(&output.stdout);
+            let version = version.lines().next().unwrap_or_default().trim();
+            Check::ok(format!("{} {}", name, version).trim_end().to_string())
+        }
+        Ok(_) => Check::warning(
+            format!("{} --version failed", name),
+            format!("Reinstall it: {}", install),
+        ),
+        Err(_) if used => Check::error(
+            format!("{} not found (used by the manifest)", name),
+            install.to_string(),
+        ),
+        Err(_) => Check::warning(format!("{} not found", name), install.to_string()),
+    }
+}
+
+// =============================================================================
+// Config Directories
+// =============================================================================
+
+/// Directories axel writes to: the global config and the workspace's `.axel`
+fn config_dirs(manifest_path: &Path) -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+    if let Some(home) = dirs::home_dir() {
+        dirs.push(home.join(".config/axel"));
+    }
+    let workspace = manifest_path
+        .parent()
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    dirs.push(workspace.join(".axel"));
+    dirs
+}
+
+/// Check that files can be created in `dir` (or where it would be created)
+fn check_writable(dir: &Path) -> Check {
+    let existing = dir.ancestors().find(|d| d.exists()).unwrap_or(dir);
+    let probe = existing.join(format!(".axel-doctor-{}", std::process::id()));
+    match std::fs::write(&probe, b"") {
+        Ok(()) => {
+            std::fs::remove_file(&probe).ok();
+            Check::ok(format!("{} is writable", dir.display()))
+        }
+        Err(e) => Check::error(
+            format!("{} is not writable ({})", dir.display(), e),
+            format!("Fix the permissions of {}", existing.display()),
+        ),
+    }
+}
+
+// =============================================================================
+// Event Server
+// =============================================================================
+
+fn check_port(port: u16) -> Check {
+    if TcpListener::bind(("127.0.0.1", port)).is_ok() {
+        return Check::ok(format!("Port {} is free for the event server", port));
+    }
+    if ServerClient::new(port).usage().is_ok() {
+        Check::ok(format!("Port {} is used by a running axel server", port))
+    } else {
+        Check::error(
+            format!("Port {} is used by another program", port),
+            format!(
+                "Stop it (lsof -i :{}) or pass --port to axel server and axel session new",
+                port
+            ),
+        )
+    }
+}
+
+// =============================================================================
+// Manifest
+// =============================================================================
+
+fn check_manifest(manifest_path: &Path) -> Check {
+    if !manifest_path.exists() {
+        return Check::warning(
+            format!("No manifest at {}", manifest_path.display()),
+            "Run axel init to create one, or pass -m",
+        );
+    }
+    let config = match load_config(manifest_path) {
+        Ok(config) => config,
+        Err(e) => {
+            return Check::error(
+                format!("{} does not load: {:#}", manifest_path.display(), e),
+                "Fix the frontmatter, or run axel upgrade-config for a legacy manifest",
+            );
+        }
+    };
+
+    let issues = config.validate();
+    let errors = issues
+        .iter()
+        .filter(|issue| issue.severity == Severity::Error)
+        .count();
+    let warnings = issues.len() - errors;
+    let summary = format!(
+        "{} has {} error{} and {} warning{}",
+        manifest_path.display(),
+        errors,
+        if errors == 1 { "" } else { "s" },
+        warnings,
+        if warnings == 1 { "" } else { "s" }
+    );
+    let fix = "Run axel config validate for the details";
+    match (errors, warnings) {
+        (0, 0) => Check::ok(format!("{} is valid", manifest_path.display())),
+        (0, _) => Check::warning(summary, fix),
+        _ => Check::error(summary, fix),
+    }
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_tmux_version() {
+        assert_eq!(parse_tmux_version("tmux 3.4\n"), Some((3, 4)));
+        assert_eq!(parse_tmux_version("tmux 3.3a"), Some((3, 3)));
+        assert_eq!(parse_tmux_version("tmux next-3.6"), Some((3, 6)));
+        assert_eq!(parse_tmux_version("tmux master"), None);
+        assert_eq!(parse_tmux_version("screen 4.9"), None);
+    }
+
+    #[test]
+    fn test_missing_tmux_features() {
+        assert!(missing_tmux_features((3, 4)).is_empty());
+        assert_eq!(missing_tmux_features((3, 2)), vec!["allow-passthrough"]);
+        assert_eq!(
+            missing_tmux_features((2, 9)),
+            vec!["allow-passthrough", "extended-keys"]
+        );
+    }
+
+    #[test]
+    fn test_check_manifest() {
+        let dir = std::env::temp_dir().join("axel-test-doctor");
+        std::fs::remove_dir_all(&dir).ok();
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("AXEL.md");
+
+        assert_eq!(check_manifest(&path).status, CheckStatus::Warning);
+
+        std::fs::write(
+            &path,
+            "---\nworkspace: test\nlayouts:\n  panes:\n    - type: claude\n---\n",
+        )
+        .unwrap();
+        assert_eq!(check_manifest(&path).status, CheckStatus::Ok);
+
+        std::fs::write(
+            &path,
+            "---\nworkspace: test\nlayouts:\n  panes:\n    - type: claude\n  grids:\n    default:\n      claude:\n        col: 0\n      ghost:\n        col: 1\n---\n",
+        )
+        .unwrap();
+        let check = check_manifest(&path);
+        assert_eq!(check.status, CheckStatus::Error);
+        assert!(check.fix.unwrap().contains("axel config validate"));
+
+        assert_eq!(check_writable(&dir.join(".axel")).status, CheckStatus::Ok);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
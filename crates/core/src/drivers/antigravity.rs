@@ -15,9 +15,10 @@
 
 use std::path::{Path, PathBuf};
 
-use anyhow::Result;
+use anyhow::{Result, bail};
 
 use super::SkillDriver;
+use crate::error::Error;
 
 /// Path to the merged rules file for Antigravity
 const ANTIGRAVITY_RULES_FILE: &str = ".antigravity/rules.md";
@@ -38,6 +39,11 @@ impl SkillDriver for AntigravityDriver {
         &[".antigravity/rules.md", ".agent/workflows/*.md"]
     }
 
+    /// Skills are merged into one rules file, as `## <name>` sections
+    fn installed_skill_path(&self, workspace_dir: &Path, _name: &str) -> PathBuf {
+        workspace_dir.join(ANTIGRAVITY_RULES_FILE)
+    }
+
     fn install_skills(&self, workspace_dir: &Path, skill_paths: &[PathBuf]) -> Result<usize> {
         if skill_paths.is_empty() {
             return Ok(0);
@@ -53,10 +59,16 @@ impl SkillDriver for AntigravityDriver {
         merged_content.push_str("# Axel Skills\n\n");
         merged_content.push_str("<!-- Auto-generated by axel. Do not edit. -->\n\n");
 
-        let mut count = 0;
+        let mut failures = Vec::new();
         for source_path in skill_paths {
             let name = derive_skill_name(source_path);
-            let content = std::fs::read_to_string(source_path)?;
+            let content = match std::fs::read_to_string(source_path) {
+                Ok(content) => content,
+                Err(e) => {
+                    failures.push((name, e.to_string()));
+                    continue;
+                }
+            };
 
             // Strip YAML frontmatter if present
             let content = strip_frontmatter(&content);
@@ -64,12 +76,23 @@ impl SkillDriver for AntigravityDriver {
             merged_content.push_str(&format!("## {}\n\n", name));
             merged_content.push_str(content.trim());
             merged_content.push_str("\n\n---\n\n");
-            count += 1;
+        }
+        if !failures.is_empty() {
+            bail!(Error::SkillInstall {
+                driver: self.name().to_string(),
+                failures,
+            });
         }
 
-        std::fs::write(&rules_path, merged_content)?;
+        // Written aside and renamed, so a failed write keeps the old rules
+        let temp_path = rules_path.with_extension("md.axel-new");
+        std::fs::write(&temp_path, merged_content)
+            .and_then(|()| std::fs::rename(&temp_path, &rules_path))
+            .inspect_err(|_| {
+                std::fs::remove_file(&temp_path).ok();
+            })?;
 
-        Ok(count)
+        Ok(skill_paths.len())
     }
 
     fn cleanup(&self, workspace_dir: &Path) -> bool {
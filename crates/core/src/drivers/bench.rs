@@ -0,0 +1,113 @@
+//! Skill install benchmark
+//!
+//! Generates a set of throwaway skills and times how long it takes to resolve
+//! and install them for a driver. Backs the hidden `axel skill bench` command
+//! and guards against install-time regressions for large skill sets.
+
+use std::{
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use anyhow::Result;
+
+use super::SkillDriver;
+use crate::config::WorkspaceConfig;
+
+/// Timing results for one benchmark run
+#[derive(Debug, Clone)]
+pub struct BenchReport {
+    /// Number of skills generated
+    pub skills: usize,
+    /// Number of skills the driver reported as installed
+    pub installed: usize,
+    /// Time spent in `resolve_skills`
+    pub resolve: Duration,
+    /// Time spent in the driver's `install_skills`
+    pub install: Duration,
+}
+
+impl BenchReport {
+    /// Total time for resolve and install
+    pub fn total(&self) -> Duration {
+        self.resolve + self.install
+    }
+
+    /// Skills installed per second
+    pub fn throughput(&self) -> f64 {
+        let secs = self.total().as_secs_f64();
+        if secs > 0.0 {
+            self.installed as f64 / secs
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Generate `count` skills under `dir` and time resolving and installing
+/// them with `driver`.
+///
+/// `dir` is used as a scratch workspace and removed when the run finishes.
+pub fn bench_install(driver: &dyn SkillDriver, count: usize, dir: &Path) -> Result<BenchReport> {
+    let skills_dir = dir.join("skills");
+    std::fs::create_dir_all(&skills_dir)?;
+    for i in 0..count {
+        let name = format!("bench-skill-{:05}", i);
+        std::fs::write(
+            skills_dir.join(format!("{}.md", name)),
+            format!(
+                "---\nname: {}\ndescription: Generated benchmark skill {}\n---\n\nBenchmark skill body.\n",
+                name, i
+            ),
+        )?;
+    }
+
+    let result = run(driver, count, dir);
+    driver.cleanup(dir);
+    std::fs::remove_dir_all(dir).ok();
+    result
+}
+
+fn run(driver: &dyn SkillDriver, count: usize, dir: &Path) -> Result<BenchReport> {
+    let mut config: WorkspaceConfig =
+        serde_yaml::from_str("workspace: bench\nlayouts: {}\nskills:\n  - path: ./skills\n")?;
+    config.manifest_path = Some(dir.join("AXEL.md"));
+
+    let start = Instant::now();
+    let skill_paths = config.resolve_skills(&["*".to_string()]);
+    let resolve = start.elapsed();
+
+    let start = Instant::now();
+    let installed = driver.install_skills(dir, &skill_paths)?;
+    let install = start.elapsed();
+
+    Ok(BenchReport {
+        skills: count,
+        installed,
+        resolve,
+        install,
+    })
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::drivers::ClaudeDriver;
+
+    #[test]
+    fn test_bench_install_small() {
+        let dir = std::env::temp_dir().join("axel-test-skill-bench");
+        std::fs::remove_dir_all(&dir).ok();
+
+        let report = bench_install(&ClaudeDriver, 10, &dir).unwrap();
+        assert_eq!(report.skills, 10);
+        assert_eq!(report.installed, 10);
+        assert!(report.total() >= report.install);
+        assert!(report.throughput() >= 0.0);
+        assert!(!dir.exists());
+    }
+}
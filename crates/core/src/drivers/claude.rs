@@ -2,29 +2,38 @@
 //!
 //! Installs skills to `.claude/skills/` directory as symlinks. Each skill
 //! becomes available as `/skill-name` in Claude Code. Creates CLAUDE.md
-//! symlink pointing to AXEL.md for project context.
+//! symlink pointing to AXEL.md for project context. MCP servers are passed
+//! with `--mcp-config` (see [`super::mcp`]).
 
-use std::path::{Path, PathBuf};
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
 
 use anyhow::Result;
 
-use super::SkillDriver;
+use super::{
+    SkillDriver,
+    install::{SkillLink, install_links},
+};
 use crate::{
-    config::WorkspaceConfig,
-    hooks::{otel_metrics_endpoint, otel_traces_endpoint},
+    config::{McpServer, WorkspaceConfig},
+    hooks::{otel_metrics_endpoint, otel_traces_endpoint, remove_hooks_settings, settings_path},
+    platform,
 };
 
-/// Helper to create index file symlink (e.g., CLAUDE.md, AGENTS.md) pointing to AXEL.md
+/// Helper to create index file symlink (e.g., CLAUDE.md, AGENTS.md) pointing to
+/// the workspace index (AXEL.md, or the `index:` of the manifest)
 pub(super) fn install_index_symlink(
     config: &WorkspaceConfig,
     workspace_dir: &Path,
     filename: &str,
 ) -> Result<bool> {
-    let Some(manifest_path) = &config.manifest_path else {
+    let Some(index_path) = config.index_path() else {
         return Ok(false);
     };
 
-    if !manifest_path.exists() {
+    if !index_path.exists() {
         return Ok(false);
     }
 
@@ -36,21 +45,37 @@ pub(super) fn install_index_symlink(
     }
 
     // Canonicalize the source path
-    let canonical_source = manifest_path
+    let canonical_source = index_path
         .canonicalize()
-        .unwrap_or_else(|_| manifest_path.clone());
-
-    // Create symlink
-    #[cfg(unix)]
-    {
-        std::os::unix::fs::symlink(&canonical_source, &link_path)?;
-        Ok(true)
-    }
+        .unwrap_or_else(|_| index_path.clone());
 
-    #[cfg(not(unix))]
-    Ok(false)
+    platform::symlink(&canonical_source, &link_path)?;
+    Ok(true)
 }
 
+/// Claude Code's built-in tools
+const CLAUDE_TOOLS: &[&str] = &[
+    "Bash",
+    "BashOutput",
+    "Edit",
+    "ExitPlanMode",
+    "Glob",
+    "Grep",
+    "KillShell",
+    "LS",
+    "MultiEdit",
+    "NotebookEdit",
+    "NotebookRead",
+    "Read",
+    "Skill",
+    "SlashCommand",
+    "Task",
+    "TodoWrite",
+    "WebFetch",
+    "WebSearch",
+    "Write",
+];
+
 /// Claude Code skill driver
 pub struct ClaudeDriver;
 
@@ -67,49 +92,37 @@ impl SkillDriver for ClaudeDriver {
         &["CLAUDE.md", ".claude/skills/*/SKILL.md"]
     }
 
-    fn install_skills(&self, workspace_dir: &Path, skill_paths: &[PathBuf]) -> Result<usize> {
-        if skill_paths.is_empty() {
-            return Ok(0);
-        }
-
-        let skills_dir = self.skills_dir(workspace_dir);
-        std::fs::create_dir_all(&skills_dir)?;
-
-        let mut count = 0;
-        for source_path in skill_paths {
-            let name = derive_skill_name(source_path);
-
-            // Claude Code expects: .claude/skills/<skill-name>/SKILL.md
-            let skill_dir = skills_dir.join(&name);
-            let link_path = skill_dir.join("SKILL.md");
-
-            // Remove existing skill directory if present
-            if skill_dir.exists() {
-                std::fs::remove_dir_all(&skill_dir).ok();
-            }
-
-            // Create skill directory
-            std::fs::create_dir_all(&skill_dir)?;
-
-            // Canonicalize the source path to get a clean absolute path
-            let canonical_source = source_path
-                .canonicalize()
-                .unwrap_or_else(|_| source_path.clone());
+    fn mcp_config(&self, servers: &BTreeMap<String, McpServer>) -> Option<serde_json::Value> {
+        Some(super::mcp::claude_mcp_config(servers))
+    }
 
-            // Create symlink to SKILL.md
-            #[cfg(unix)]
-            {
-                std::os::unix::fs::symlink(&canonical_source, &link_path)?;
-                count += 1;
-            }
-        }
+    fn skill_tools(&self) -> Option<&'static [&'static str]> {
+        Some(CLAUDE_TOOLS)
+    }
 
-        Ok(count)
+    fn install_skills(&self, workspace_dir: &Path, skill_paths: &[PathBuf]) -> Result<usize> {
+        let links: Vec<SkillLink> = skill_paths
+            .iter()
+            .map(|source| {
+                let name = derive_skill_name(source);
+                SkillLink {
+                    link: self.installed_skill_path(workspace_dir, &name),
+                    source: source.clone(),
+                    name,
+                }
+            })
+            .collect();
+        install_links(self.name(), &links)
     }
 
     fn cleanup(&self, workspace_dir: &Path) -> bool {
         let mut cleaned = false;
 
+        // Remove generated MCP configs
+        if super::cleanup_mcp_configs(workspace_dir) {
+            cleaned = true;
+        }
+
         // Remove skill directories from .claude/skills/
         // Each skill is a directory containing SKILL.md symlink
         let skills_dir = self.skills_dir(workspace_dir);
@@ -133,6 +146,11 @@ impl SkillDriver for ClaudeDriver {
             }
         }
 
+        // Remove axel's hooks from .claude/settings.json, keeping the project's
+        if remove_hooks_settings(&settings_path(workspace_dir)) {
+            cleaned = true;
+        }
+
         // Remove CLAUDE.md symlink
         let claude_md = workspace_dir.join("CLAUDE.md");
         if claude_md
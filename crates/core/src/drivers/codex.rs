@@ -5,24 +5,42 @@
 //! making them discoverable via the `/skills` slash command.
 //!
 //! Codex also uses AGENTS.md for project context (similar to Claude's CLAUDE.md).
+//! Projects often keep their own AGENTS.md, so the workspace index goes in a
+//! marked section of it (see [`super::managed`]) and the rest is left alone.
 //!
-//! ## OpenTelemetry Support
+//! ## Profiles
 //!
-//! Codex supports OTEL telemetry export via `-c`/`--config` CLI flags.
-//! Unlike Claude which uses environment variables, Codex requires config
-//! file settings or CLI overrides for OTEL export.
+//! Codex panes are launched with `codex --profile axel-<workspace>-<pane>`.
+//! Codex only reads profiles from its own config (`$CODEX_HOME/config.toml`,
+//! `~/.codex/config.toml` by default), so that is where the profile is
+//! written. It points Codex at the merged skills file
+//! (`project_doc_fallback_filenames`) and, when the pane reports to an event
+//! server, sets up OTEL export and bell notifications. The rest of the file
+//! is left as is, and cleaning a workspace only removes its own profiles.
 //! See: https://developers.openai.com/codex/config-advanced/
 
 use std::path::{Path, PathBuf};
 
-use anyhow::Result;
+use anyhow::{Context, Result};
+use toml_edit::{Array, DocumentMut, InlineTable, Item, Table, value};
 
-use super::{SkillDriver, claude::install_index_symlink};
+use super::{
+    SkillDriver,
+    install::{SkillLink, install_links},
+    managed::{remove_managed_section, write_managed_section},
+};
 use crate::{
     config::WorkspaceConfig,
     hooks::{otel_logs_endpoint, otel_metrics_endpoint, otel_traces_endpoint},
 };
 
+/// Codex config, relative to the workspace, where earlier versions wrote
+/// axel's profiles; they are removed on cleanup
+pub const CODEX_CONFIG: &str = ".codex/config.toml";
+
+/// Prefix of the profiles Codex panes are launched with
+pub const CODEX_PROFILE: &str = "axel";
+
 /// Codex skill driver
 pub struct CodexDriver;
 
@@ -40,43 +58,18 @@ impl SkillDriver for CodexDriver {
     }
 
     fn install_skills(&self, workspace_dir: &Path, skill_paths: &[PathBuf]) -> Result<usize> {
-        if skill_paths.is_empty() {
-            return Ok(0);
-        }
-
-        let skills_dir = self.skills_dir(workspace_dir);
-        std::fs::create_dir_all(&skills_dir)?;
-
-        let mut count = 0;
-        for source_path in skill_paths {
-            let name = derive_skill_name(source_path);
-
-            // Codex expects: .codex/skills/<skill-name>/SKILL.md
-            let skill_dir = skills_dir.join(&name);
-            let link_path = skill_dir.join("SKILL.md");
-
-            // Remove existing skill directory if present
-            if skill_dir.exists() {
-                std::fs::remove_dir_all(&skill_dir).ok();
-            }
-
-            // Create skill directory
-            std::fs::create_dir_all(&skill_dir)?;
-
-            // Canonicalize the source path to get a clean absolute path
-            let canonical_source = source_path
-                .canonicalize()
-                .unwrap_or_else(|_| source_path.clone());
-
-            // Create symlink to SKILL.md
-            #[cfg(unix)]
-            {
-                std::os::unix::fs::symlink(&canonical_source, &link_path)?;
-                count += 1;
-            }
-        }
-
-        Ok(count)
+        let links: Vec<SkillLink> = skill_paths
+            .iter()
+            .map(|source| {
+                let name = derive_skill_name(source);
+                SkillLink {
+                    link: self.installed_skill_path(workspace_dir, &name),
+                    source: source.clone(),
+                    name,
+                }
+            })
+            .collect();
+        install_links(self.name(), &links)
     }
 
     fn cleanup(&self, workspace_dir: &Path) -> bool {
@@ -104,16 +97,22 @@ impl SkillDriver for CodexDriver {
             }
         }
 
-        // Remove AGENTS.md symlink
-        let agents_md = workspace_dir.join("AGENTS.md");
-        if agents_md
-            .symlink_metadata()
-            .map(|m| m.file_type().is_symlink())
-            .unwrap_or(false)
-            && std::fs::remove_file(&agents_md).is_ok()
+        let prefix = format!("{}-", codex_profile_prefix(workspace_dir));
+        if let Some(path) = codex_config_path()
+            && remove_codex_profiles(&path, |name| name.starts_with(&prefix))
         {
             cleaned = true;
         }
+        let legacy = format!("{}-", CODEX_PROFILE);
+        if remove_codex_profiles(&workspace_dir.join(CODEX_CONFIG), |name| {
+            name == CODEX_PROFILE || name.starts_with(&legacy)
+        }) {
+            cleaned = true;
+        }
+
+        if cleanup_agents_md(workspace_dir) {
+            cleaned = true;
+        }
 
         cleaned
     }
@@ -123,47 +122,9 @@ impl SkillDriver for CodexDriver {
     }
 
     fn otel_cli_args(&self, port: u16, pane_id: &str) -> Vec<String> {
-        // Codex uses -c/--config flags for configuration overrides.
-        // Unlike Claude which uses env vars, Codex requires config file or CLI flags.
-        // See: https://developers.openai.com/codex/config-advanced/
-        //
-        // The values need to be shell-quoted because they contain special characters.
-        let logs_endpoint = otel_logs_endpoint(port, pane_id);
-        let metrics_endpoint = otel_metrics_endpoint(port, pane_id);
-        let traces_endpoint = otel_traces_endpoint(port, pane_id);
-
-        vec![
-            // Enable analytics (required for metrics export)
-            "-c".to_string(),
-            "'analytics_enabled=true'".to_string(),
-            // Enable bell notifications for approvals (allows tmux to detect them)
-            "-c".to_string(),
-            "'tui_notifications=\"always\"'".to_string(),
-            "-c".to_string(),
-            "'tui_notification_method=\"bel\"'".to_string(),
-            // Disable paste burst detection so tmux send-keys works correctly
-            // (otherwise Enter is treated as newline when sent shortly after text)
-            "-c".to_string(),
-            "'disable_paste_burst=true'".to_string(),
-            // Configure log exporter (OTLP HTTP with JSON protocol)
-            "-c".to_string(),
-            format!(
-                r#"'otel.exporter={{otlp-http={{endpoint="{}",protocol="json"}}}}'"#,
-                logs_endpoint
-            ),
-            // Configure trace exporter
-            "-c".to_string(),
-            format!(
-                r#"'otel.trace_exporter={{otlp-http={{endpoint="{}",protocol="json"}}}}'"#,
-                traces_endpoint
-            ),
-            // Configure metrics exporter (override default Statsig)
-            "-c".to_string(),
-            format!(
-                r#"'otel.metrics_exporter={{otlp-http={{endpoint="{}",protocol="json"}}}}'"#,
-                metrics_endpoint
-            ),
-        ]
+        // The profile's settings as `-c` overrides, for commands launched
+        // without one
+        config_overrides(&codex_profile(Some((port, pane_id))))
     }
 
     fn tmux_bell_hook_command(&self, port: u16, pane_id: &str) -> Option<String> {
@@ -193,8 +154,209 @@ fi'"#,
     }
 
     fn install_index(&self, config: &WorkspaceConfig, workspace_dir: &Path) -> Result<bool> {
-        install_index_symlink(config, workspace_dir, "AGENTS.md")
+        install_agents_md(config, workspace_dir)
+    }
+}
+
+/// Write the workspace index into axel's section of AGENTS.md (shared by
+/// Codex and OpenCode), keeping what the project wrote there
+pub(super) fn install_agents_md(config: &WorkspaceConfig, workspace_dir: &Path) -> Result<bool> {
+    let agents_md = workspace_dir.join("AGENTS.md");
+    // An index kept in AGENTS.md itself is already where Codex reads it
+    if let Some(index_path) = config.index_path()
+        && index_path.canonicalize().ok() == agents_md.canonicalize().ok()
+        && agents_md.exists()
+    {
+        return Ok(false);
+    }
+    let Some(index) = config.load_index()? else {
+        return Ok(false);
+    };
+    write_managed_section(&agents_md, &index.content)?;
+    Ok(true)
+}
+
+/// Remove axel's section from AGENTS.md, or the symlink older installs made.
+///
+/// Returns true if anything was removed.
+pub(super) fn cleanup_agents_md(workspace_dir: &Path) -> bool {
+    let agents_md = workspace_dir.join("AGENTS.md");
+    if agents_md.is_symlink() {
+        std::fs::remove_file(&agents_md).is_ok()
+    } else {
+        remove_managed_section(&agents_md)
+    }
+}
+
+/// Codex's own config (`$CODEX_HOME/config.toml`, `~/.codex/config.toml`
+/// by default), the only place it reads profiles from
+pub fn codex_config_path() -> Option<PathBuf> {
+    std::env::var_os("CODEX_HOME")
+        .filter(|home| !home.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| dirs::home_dir().map(|home| home.join(".codex")))
+        .map(|home| home.join("config.toml"))
+}
+
+/// Name of the profile for the Codex pane `pane_name` of the workspace in
+/// `workspace_dir`
+pub fn codex_profile_name(workspace_dir: &Path, pane_name: &str) -> String {
+    format!(
+        "{}-{}",
+        codex_profile_prefix(workspace_dir),
+        profile_slug(pane_name)
+    )
+}
+
+/// `axel-<workspace>`, shared by the profiles of a workspace's panes
+fn codex_profile_prefix(workspace_dir: &Path) -> String {
+    let dir = workspace_dir
+        .canonicalize()
+        .unwrap_or_else(|_| workspace_dir.to_path_buf());
+    let name = dir
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "workspace".to_string());
+    format!("{}-{}", CODEX_PROFILE, profile_slug(&name))
+}
+
+/// `name` with everything but letters and digits replaced by `_`, so the
+/// `-` separating a profile name's parts stays unambiguous
+fn profile_slug(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+/// Settings of a Codex pane's profile.
+///
+/// With `otel` (the event server's port and the pane ID), the profile also
+/// exports telemetry to the server and rings the bell for approvals.
+pub fn codex_profile(otel: Option<(u16, &str)>) -> Table {
+    let mut profile = Table::new();
+    // Discover the merged skills file
+    profile["project_doc_fallback_filenames"] = value(Array::from_iter([".codex/AGENTS.md"]));
+
+    if let Some((port, pane_id)) = otel {
+        // Required for metrics export
+        profile["analytics_enabled"] = value(true);
+        // Bell notifications for approvals let tmux detect them
+        profile["tui_notifications"] = value("always");
+        profile["tui_notification_method"] = value("bel");
+        // Otherwise Enter sent by tmux shortly after text is taken as a newline
+        profile["disable_paste_burst"] = value(true);
+
+        let mut otel = Table::new();
+        otel["exporter"] = otlp_exporter(otel_logs_endpoint(port, pane_id));
+        otel["trace_exporter"] = otlp_exporter(otel_traces_endpoint(port, pane_id));
+        otel["metrics_exporter"] = otlp_exporter(otel_metrics_endpoint(port, pane_id));
+        profile["otel"] = Item::Table(otel);
+    }
+    profile
+}
+
+/// OTLP HTTP exporter with the JSON protocol
+fn otlp_exporter(endpoint: String) -> Item {
+    let mut otlp = InlineTable::new();
+    otlp.insert("endpoint", endpoint.into());
+    otlp.insert("protocol", "json".into());
+    let mut exporter = InlineTable::new();
+    exporter.insert("otlp-http", otlp.into());
+    value(exporter)
+}
+
+/// Write `profile` to the Codex config at `path` as profile `name`.
+///
+/// Other settings and profiles in the file are kept, comments included.
+pub fn write_codex_profile(path: &Path, name: &str, profile: Table) -> Result<()> {
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(e).with_context(|| format!("Failed to read {}", path.display())),
+    };
+    let mut doc: DocumentMut = content
+        .parse()
+        .with_context(|| format!("Invalid {}", path.display()))?;
+
+    let profiles = doc
+        .entry("profiles")
+        .or_insert_with(|| {
+            let mut profiles = Table::new();
+            profiles.set_implicit(true);
+            Item::Table(profiles)
+        })
+        .as_table_mut()
+        .with_context(|| format!("`profiles` in {} is not a table", path.display()))?;
+    profiles.insert(name, Item::Table(profile));
+
+    let updated = doc.to_string();
+    if updated != content {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, updated)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+    }
+    Ok(())
+}
+
+/// Remove the profiles whose name is `ours` from the Codex config at `path`,
+/// and the file if nothing else is left in it.
+///
+/// Returns true if any were removed.
+fn remove_codex_profiles(path: &Path, ours: impl Fn(&str) -> bool) -> bool {
+    let Some(mut doc) = std::fs::read_to_string(path)
+        .ok()
+        .and_then(|content| content.parse::<DocumentMut>().ok())
+    else {
+        return false;
+    };
+    let Some(profiles) = doc.get_mut("profiles").and_then(Item::as_table_mut) else {
+        return false;
+    };
+    let removed: Vec<String> = profiles
+        .iter()
+        .map(|(name, _)| name.to_string())
+        .filter(|name| ours(name))
+        .collect();
+    if removed.is_empty() {
+        return false;
+    }
+    for name in &removed {
+        profiles.remove(name);
+    }
+    if profiles.is_empty() {
+        doc.remove("profiles");
+    }
+
+    let content = doc.to_string();
+    if content.trim().is_empty() {
+        std::fs::remove_file(path).is_ok()
+    } else {
+        std::fs::write(path, content).is_ok()
+    }
+}
+
+/// `-c key=value` arguments applying `profile`'s settings on the command
+/// line. Sections are flattened to dotted keys (`otel.exporter=...`).
+pub fn config_overrides(profile: &Table) -> Vec<String> {
+    let mut overrides = Vec::new();
+    for (key, item) in profile.iter() {
+        let settings: Vec<(String, &Item)> = match item.as_table() {
+            Some(section) => section
+                .iter()
+                .map(|(name, item)| (format!("{}.{}", key, name), item))
+                .collect(),
+            None => vec![(key.to_string(), item)],
+        };
+        for (key, item) in settings {
+            if let Some(value) = item.as_value() {
+                overrides.push("-c".to_string());
+                overrides.push(format!("{}={}", key, value.clone().decorated("", "")));
+            }
+        }
     }
+    overrides
 }
 
 /// Derive skill name from file path.
@@ -214,3 +376,90 @@ fn derive_skill_name(path: &Path) -> String {
             .unwrap_or_else(|| "skill".to_string())
     }
 }
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_codex_profile() {
+        let dir = std::env::temp_dir().join("axel-test-codex-profile");
+        std::fs::remove_dir_all(&dir).ok();
+        let workspace = dir.join("my app");
+        std::fs::create_dir_all(&workspace).unwrap();
+        let path = dir.join("codex-home/config.toml");
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, "# User settings\nmodel = \"o3\"\n").unwrap();
+
+        let name = codex_profile_name(&workspace, "codex");
+        assert_eq!(name, "axel-my_app-codex");
+        write_codex_profile(&path, &name, codex_profile(None)).unwrap();
+        write_codex_profile(
+            &path,
+            &codex_profile_name(&workspace, "reviewer"),
+            codex_profile(Some((4318, "reviewer"))),
+        )
+        .unwrap();
+        write_codex_profile(&path, "axel-other-codex", codex_profile(None)).unwrap();
+        let written = std::fs::read_to_string(&path).unwrap();
+        assert!(written.starts_with("# User settings\nmodel = \"o3\"\n"));
+        assert!(written.contains(
+            "[profiles.axel-my_app-codex]\nproject_doc_fallback_filenames = [\".codex/AGENTS.md\"]"
+        ));
+        assert!(written.contains("[profiles.axel-my_app-reviewer.otel]"));
+
+        let doc: DocumentMut = written.parse().unwrap();
+        let profile = doc["profiles"]["axel-my_app-reviewer"].as_table().unwrap();
+        assert_eq!(profile["tui_notification_method"].as_str(), Some("bel"));
+        assert_eq!(
+            profile["otel"]["exporter"]["otlp-http"]["endpoint"].as_str(),
+            Some("http://localhost:4318/v1/logs/reviewer")
+        );
+
+        // Writing the same profile again leaves the file alone
+        write_codex_profile(&path, &name, codex_profile(None)).unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), written);
+
+        // Cleaning the workspace keeps other workspaces' profiles
+        let prefix = format!("{}-", codex_profile_prefix(&workspace));
+        assert!(remove_codex_profiles(&path, |name| name.starts_with(&prefix)));
+        let cleaned = std::fs::read_to_string(&path).unwrap();
+        assert!(cleaned.starts_with("# User settings\nmodel = \"o3\"\n"));
+        assert!(!cleaned.contains("my_app"));
+        assert!(cleaned.contains("[profiles.axel-other-codex]"));
+
+        // Profiles earlier versions wrote to the workspace are removed too
+        let legacy = workspace.join(CODEX_CONFIG);
+        std::fs::create_dir_all(legacy.parent().unwrap()).unwrap();
+        std::fs::write(&legacy, "[profiles.axel]\nanalytics_enabled = true\n").unwrap();
+        assert!(CodexDriver.cleanup(&workspace));
+        assert!(!legacy.exists());
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_config_overrides() {
+        let overrides = config_overrides(&codex_profile(Some((4318, "%1"))));
+        let settings: Vec<&str> = overrides
+            .chunks(2)
+            .map(|pair| {
+                assert_eq!(pair[0], "-c");
+                pair[1].as_str()
+            })
+            .collect();
+        assert_eq!(
+            settings[0],
+            r#"project_doc_fallback_filenames=[".codex/AGENTS.md"]"#
+        );
+        assert!(settings.contains(&r#"tui_notifications="always""#));
+        assert!(settings.contains(&"disable_paste_burst=true"));
+        assert!(settings.contains(
+            &r#"otel.metrics_exporter={ otlp-http = { endpoint = "http://localhost:4318/v1/metrics/%1", protocol = "json" } }"#
+        ));
+    }
+}
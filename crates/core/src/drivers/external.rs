@@ -0,0 +1,385 @@
+//! External skill drivers defined in TOML.
+//!
+//! Users can teach axel about new AI tools without recompiling by dropping a
+//! definition into `~/.config/axel/drivers/<name>.toml`:
+//!
+//! ```toml
+//! name = "aider"
+//! skills_dir = ".aider/skills"
+//! index_filename = "CONVENTIONS.md"
+//! command = "aider --message {prompt}"
+//!
+//! [otel_env]
+//! OTEL_EXPORTER_OTLP_ENDPOINT = "http://localhost:{port}"
+//! OTEL_RESOURCE_ATTRIBUTES = "axel.pane_id={pane_id}"
+//! ```
+//!
+//! Skills are symlinked into `skills_dir` as `<name>.md`, the index file is
+//! symlinked to AXEL.md, and a pane whose type names the driver runs its
+//! command template. Definitions are loaded once per process.
+
+use std::{
+    collections::BTreeMap,
+    path::{Component, Path, PathBuf},
+    sync::OnceLock,
+};
+
+use anyhow::{Context, Result, bail};
+use colored::Colorize;
+use serde::Deserialize;
+
+use super::{
+    SkillDriver,
+    claude::install_index_symlink,
+    install::{SkillLink, install_links},
+};
+use crate::config::WorkspaceConfig;
+
+/// Directory holding external driver definitions, relative to the home directory
+const DRIVERS_DIR: &str = ".config/axel/drivers";
+
+/// Driver definition as written in TOML
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ExternalDriverDef {
+    name: String,
+    skills_dir: String,
+    #[serde(default)]
+    index_filename: Option<String>,
+    #[serde(default)]
+    command: Option<String>,
+    #[serde(default)]
+    otel_env: BTreeMap<String, String>,
+}
+
+/// A skill driver loaded from a TOML definition
+///
+/// Strings are leaked on load so the driver can satisfy the `'static`
+/// signatures of [`SkillDriver`]; definitions are only loaded once.
+#[derive(Debug, Clone)]
+pub struct ExternalDriver {
+    name: &'static str,
+    skills_dir: &'static str,
+    index_filename: Option<&'static str>,
+    command: Option<&'static str>,
+    patterns: &'static [&'static str],
+    otel_env: Vec<(&'static str, &'static str)>,
+}
+
+impl ExternalDriver {
+    /// Parse a driver definition from TOML
+    pub fn parse(content: &str) -> Result<Self> {
+        let def: ExternalDriverDef = toml::from_str(content)?;
+        if def.name.is_empty()
+            || !def
+                .name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            bail!(
+                "invalid driver name '{}' (use letters, digits, '-' or '_')",
+                def.name
+            );
+        }
+        let skills_dir = def.skills_dir.trim_end_matches('/');
+        if skills_dir.is_empty() || !inside_workspace(skills_dir) {
+            bail!("skills_dir must be a path inside the workspace");
+        }
+        if let Some(index) = &def.index_filename
+            && !inside_workspace(index)
+        {
+            bail!("index_filename must be a path inside the workspace");
+        }
+
+        let mut patterns = vec![leak(format!("{}/*.md", skills_dir))];
+        if let Some(index) = &def.index_filename {
+            patterns.insert(0, leak(index.clone()));
+        }
+
+        Ok(ExternalDriver {
+            name: leak(def.name),
+            skills_dir: leak(skills_dir.to_string()),
+            index_filename: def.index_filename.map(leak),
+            command: def.command.map(leak),
+            patterns: Box::leak(patterns.into_boxed_slice()),
+            otel_env: def
+                .otel_env
+                .into_iter()
+                .map(|(k, v)| (leak(k), leak(v)))
+                .collect(),
+        })
+    }
+
+    /// Render the command template, substituting `{prompt}` with the
+    /// single-quoted prompt (or nothing when there is none)
+    pub fn command(&self, prompt: Option<&str>) -> Option<String> {
+        let template = self.command?;
+        let prompt = prompt.map(crate::shell::single_quote).unwrap_or_default();
+        Some(template.replace("{prompt}", &prompt).trim().to_string())
+    }
+}
+
+/// Whether `path` is relative and stays inside the directory it is joined
+/// to: cleanup deletes the links found there
+fn inside_workspace(path: &str) -> bool {
+    Path::new(path)
+        .components()
+        .all(|c| matches!(c, Component::Normal(_) | Component::CurDir))
+}
+
+fn leak(s: String) -> &'static str {
+    Box::leak(s.into_boxed_str())
+}
+
+/// Load every `*.toml` driver definition in `dir`, sorted by file name.
+///
+/// Invalid definitions are reported and skipped.
+pub fn load_external_drivers(dir: &Path) -> Vec<ExternalDriver> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut paths: Vec<PathBuf> = entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.extension().map(|e| e == "toml").unwrap_or(false))
+        .collect();
+    paths.sort();
+
+    let mut drivers: Vec<ExternalDriver> = Vec::new();
+    for path in paths {
+        let parsed = std::fs::read_to_string(&path)
+            .map_err(anyhow::Error::from)
+            .and_then(|content| ExternalDriver::parse(&content))
+            .with_context(|| format!("Invalid driver definition {}", path.display()));
+        match parsed {
+            Ok(driver) if drivers.iter().any(|d| d.name == driver.name) => {
+                eprintln!(
+                    "{} Driver '{}' is defined more than once; ignoring {}",
+                    "!".yellow(),
+                    driver.name,
+                    path.display()
+                );
+            }
+            Ok(driver) => drivers.push(driver),
+            Err(e) => eprintln!("{} {:#}", "!".yellow(), e),
+        }
+    }
+    drivers
+}
+
+/// External drivers from `~/.config/axel/drivers`, loaded on first use
+pub fn external_drivers() -> &'static [ExternalDriver] {
+    static DRIVERS: OnceLock<Vec<ExternalDriver>> = OnceLock::new();
+    DRIVERS.get_or_init(|| {
+        dirs::home_dir()
+            .map(|home| load_external_drivers(&home.join(DRIVERS_DIR)))
+            .unwrap_or_default()
+    })
+}
+
+impl SkillDriver for ExternalDriver {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn skills_dir(&self, workspace_dir: &Path) -> PathBuf {
+        workspace_dir.join(self.skills_dir)
+    }
+
+    fn skill_patterns(&self) -> &'static [&'static str] {
+        self.patterns
+    }
+
+    fn installed_skill_path(&self, workspace_dir: &Path, name: &str) -> PathBuf {
+        self.skills_dir(workspace_dir).join(format!("{}.md", name))
+    }
+
+    fn install_skills(&self, workspace_dir: &Path, skill_paths: &[PathBuf]) -> Result<usize> {
+        let links: Vec<SkillLink> = skill_paths
+            .iter()
+            .map(|source| {
+                let name = derive_skill_name(source);
+                SkillLink {
+                    link: self.installed_skill_path(workspace_dir, &name),
+                    source: source.clone(),
+                    name,
+                }
+            })
+            .collect();
+        install_links(self.name(), &links)
+    }
+
+    fn cleanup(&self, workspace_dir: &Path) -> bool {
+        let is_symlink = |path: &Path| {
+            path.symlink_metadata()
+                .map(|m| m.file_type().is_symlink())
+                .unwrap_or(false)
+        };
+        let mut cleaned = false;
+
+        // Remove skill symlinks, preserving manually created files
+        if let Ok(entries) = std::fs::read_dir(self.skills_dir(workspace_dir)) {
+            for entry in entries.flatten() {
+                let path = entry.path();
+                if is_symlink(&path) && std::fs::remove_file(&path).is_ok() {
+                    cleaned = true;
+                }
+            }
+        }
+
+        if let Some(index) = self.index_filename {
+            let index_path = workspace_dir.join(index);
+            if is_symlink(&index_path) && std::fs::remove_file(&index_path).is_ok() {
+                cleaned = true;
+            }
+        }
+
+        cleaned
+    }
+
+    fn otel_env_vars(&self, port: u16, pane_id: &str) -> Vec<(String, String)> {
+        self.otel_env
+            .iter()
+            .map(|(key, value)| {
+                let value = value
+                    .replace("{port}", &port.to_string())
+                    .replace("{pane_id}", pane_id);
+                (key.to_string(), value)
+            })
+            .collect()
+    }
+
+    fn supports_otel(&self) -> bool {
+        !self.otel_env.is_empty()
+    }
+
+    fn install_index(&self, config: &WorkspaceConfig, workspace_dir: &Path) -> Result<bool> {
+        match self.index_filename {
+            Some(filename) => install_index_symlink(config, workspace_dir, filename),
+            None => Ok(false),
+        }
+    }
+
+    fn index_filename(&self) -> Option<&'static str> {
+        self.index_filename
+    }
+}
+
+/// Derive skill name from file path.
+///
+/// Handles two naming conventions:
+/// - `<name>/SKILL.md` -> uses the directory name
+/// - `<name>.md` -> uses the file stem
+fn derive_skill_name(path: &Path) -> String {
+    if path.file_name().map(|n| n == "SKILL.md").unwrap_or(false) {
+        path.parent()
+            .and_then(|p| p.file_name())
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| "skill".to_string())
+    } else {
+        path.file_stem()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| "skill".to_string())
+    }
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const AIDER: &str = r#"
+name = "aider"
+skills_dir = ".aider/skills/"
+index_filename = "CONVENTIONS.md"
+command = "aider --message {prompt}"
+
+[otel_env]
+OTEL_EXPORTER_OTLP_ENDPOINT = "http://localhost:{port}"
+OTEL_RESOURCE_ATTRIBUTES = "axel.pane_id={pane_id}"
+"#;
+
+    #[test]
+    fn test_parse_external_driver() {
+        let driver = ExternalDriver::parse(AIDER).unwrap();
+        assert_eq!(driver.name(), "aider");
+        assert_eq!(
+            driver.skills_dir(Path::new("/code/app")),
+            PathBuf::from("/code/app/.aider/skills")
+        );
+        assert_eq!(
+            driver.skill_patterns(),
+            &["CONVENTIONS.md", ".aider/skills/*.md"]
+        );
+        assert_eq!(driver.index_filename(), Some("CONVENTIONS.md"));
+        assert_eq!(
+            driver.command(Some("it's time")).as_deref(),
+            Some("aider --message 'it'\\''s time'")
+        );
+        assert_eq!(driver.command(None).as_deref(), Some("aider --message"));
+
+        assert!(driver.supports_otel());
+        assert_eq!(
+            driver.otel_env_vars(4318, "p1"),
+            vec![
+                (
+                    "OTEL_EXPORTER_OTLP_ENDPOINT".to_string(),
+                    "http://localhost:4318".to_string()
+                ),
+                (
+                    "OTEL_RESOURCE_ATTRIBUTES".to_string(),
+                    "axel.pane_id=p1".to_string()
+                ),
+            ]
+        );
+
+        assert!(ExternalDriver::parse("name = \"a b\"\nskills_dir = \"s\"").is_err());
+        assert!(ExternalDriver::parse("name = \"x\"\nskills_dir = \"/abs\"").is_err());
+        assert!(ExternalDriver::parse("name = \"x\"\nskills_dir = \"../outside\"").is_err());
+        assert!(ExternalDriver::parse("name = \"x\"\nskills_dir = \"s/../../up\"").is_err());
+        assert!(
+            ExternalDriver::parse("name = \"x\"\nskills_dir = \"s\"\nindex_filename = \"../X.md\"")
+                .is_err()
+        );
+        assert!(ExternalDriver::parse("name = \"x\"\nskills_dir = \"./.x/skills\"").is_ok());
+        assert!(ExternalDriver::parse("name = \"x\"\nskills_dir = \"s\"\nbogus = 1").is_err());
+    }
+
+    #[test]
+    fn test_load_external_drivers_skips_invalid() {
+        let dir = std::env::temp_dir().join("axel-test-external-drivers");
+        std::fs::remove_dir_all(&dir).ok();
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("aider.toml"), AIDER).unwrap();
+        std::fs::write(dir.join("broken.toml"), "name = ").unwrap();
+        std::fs::write(dir.join("dup.toml"), AIDER).unwrap();
+        std::fs::write(dir.join("notes.txt"), "ignored").unwrap();
+
+        let drivers = load_external_drivers(&dir);
+        let names: Vec<_> = drivers.iter().map(|d| d.name()).collect();
+        assert_eq!(names, vec!["aider"]);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_external_driver_install_and_cleanup() {
+        let dir = std::env::temp_dir().join("axel-test-external-install");
+        std::fs::remove_dir_all(&dir).ok();
+        std::fs::create_dir_all(&dir).unwrap();
+        let skill = dir.join("review.md");
+        std::fs::write(&skill, "Review").unwrap();
+
+        let driver = ExternalDriver::parse(AIDER).unwrap();
+        assert_eq!(driver.install_skills(&dir, &[skill]).unwrap(), 1);
+        assert!(dir.join(".aider/skills/review.md").is_symlink());
+
+        assert!(driver.cleanup(&dir));
+        assert!(!dir.join(".aider/skills/review.md").exists());
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
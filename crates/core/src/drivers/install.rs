@@ -0,0 +1,176 @@
+//! All-or-nothing skill installs
+//!
+//! Drivers link each skill's file to where their tool looks for it. A link
+//! that can't be made (a permission error, a file in the way) used to leave
+//! the workspace with half of its skills, so [`install_links`] works in two
+//! steps:
+//!
+//! 1. Every link is created next to its final path (`.SKILL.md.axel-new`),
+//!    along with any directory it needs. If one fails, the rest are still
+//!    tried so the error lists every failed skill, then everything created
+//!    is removed and the installed skills are left as they were.
+//! 2. Once all links exist, each is renamed over its final path.
+//!
+//! Links that already point at their skill are left alone, so installing
+//! again only touches what changed.
+
+use std::{
+    io,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Result;
+
+use crate::{error::Error, platform};
+
+/// A skill file to link into a driver's skills directory
+pub(super) struct SkillLink {
+    /// Skill name, for errors
+    pub name: String,
+    /// The skill's file
+    pub source: PathBuf,
+    /// Where the driver's tool looks for it
+    pub link: PathBuf,
+}
+
+/// Create every link of `links`, or none of them.
+///
+/// Returns the number of skills installed, counting those already up to
+/// date. On failure the error is an [`Error::SkillInstall`] naming each
+/// skill that couldn't be linked.
+pub(super) fn install_links(driver: &str, links: &[SkillLink]) -> Result<usize> {
+    let mut staged: Vec<(PathBuf, &Path)> = Vec::new();
+    let mut created_dirs: Vec<PathBuf> = Vec::new();
+    let mut failures: Vec<(String, String)> = Vec::new();
+
+    for skill in links {
+        let source = skill
+            .source
+            .canonicalize()
+            .unwrap_or_else(|_| skill.source.clone());
+        if std::fs::read_link(&skill.link).is_ok_and(|target| target == source) {
+            continue;
+        }
+
+        let temp = staging_path(&skill.link);
+        let result = create_dirs(&skill.link, &mut created_dirs).and_then(|()| {
+            if temp.symlink_metadata().is_ok() {
+                std::fs::remove_file(&temp)?;
+            }
+            platform::symlink(&source, &temp)
+        });
+        match result {
+            Ok(()) => staged.push((temp, &skill.link)),
+            Err(e) => failures.push((skill.name.clone(), e.to_string())),
+        }
+    }
+
+    if failures.is_empty() {
+        for (temp, link) in &staged {
+            if let Err(e) = std::fs::rename(temp, link) {
+                let name = links
+                    .iter()
+                    .find(|skill| skill.link == *link)
+                    .map(|skill| skill.name.clone())
+                    .unwrap_or_default();
+                failures.push((name, e.to_string()));
+                std::fs::remove_file(temp).ok();
+            }
+        }
+    } else {
+        for (temp, _) in &staged {
+            std::fs::remove_file(temp).ok();
+        }
+        for dir in created_dirs.iter().rev() {
+            std::fs::remove_dir(dir).ok();
+        }
+    }
+
+    if !failures.is_empty() {
+        anyhow::bail!(Error::SkillInstall {
+            driver: driver.to_string(),
+            failures,
+        });
+    }
+    Ok(links.len())
+}
+
+/// Where the link for `link` is created before it's moved into place
+fn staging_path(link: &Path) -> PathBuf {
+    let name = link
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    link.with_file_name(format!(".{}.axel-new", name))
+}
+
+/// Create the missing parent directories of `path`, recording each one
+fn create_dirs(path: &Path, created: &mut Vec<PathBuf>) -> io::Result<()> {
+    let mut missing: Vec<&Path> = path
+        .ancestors()
+        .skip(1)
+        .take_while(|dir| !dir.as_os_str().is_empty() && !dir.exists())
+        .collect();
+    missing.reverse();
+    for dir in missing {
+        std::fs::create_dir(dir)?;
+        created.push(dir.to_path_buf());
+    }
+    Ok(())
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_install_links() {
+        let dir = std::env::temp_dir().join("axel-test-install-links");
+        std::fs::remove_dir_all(&dir).ok();
+        std::fs::create_dir_all(&dir).unwrap();
+        let review = dir.join("review.md");
+        let deploy = dir.join("deploy.md");
+        std::fs::write(&review, "Review").unwrap();
+        std::fs::write(&deploy, "Deploy").unwrap();
+        let skills = dir.join("skills");
+        let link = |name: &str, source: &Path| SkillLink {
+            name: name.to_string(),
+            source: source.to_path_buf(),
+            link: skills.join(name).join("SKILL.md"),
+        };
+
+        // A file where a skill's directory should be makes that skill fail;
+        // the other one isn't installed either
+        std::fs::create_dir_all(&skills).unwrap();
+        std::fs::write(skills.join("deploy"), "in the way").unwrap();
+        let links = [link("review", &review), link("deploy", &deploy)];
+        let err = install_links("claude", &links).unwrap_err();
+        match err.downcast_ref() {
+            Some(Error::SkillInstall { driver, failures }) => {
+                assert_eq!(driver, "claude");
+                let names: Vec<_> = failures.iter().map(|(name, _)| name.as_str()).collect();
+                assert_eq!(names, ["deploy"]);
+            }
+            other => panic!("unexpected error: {:?}", other),
+        }
+        assert!(!skills.join("review").exists());
+
+        std::fs::remove_file(skills.join("deploy")).unwrap();
+        assert_eq!(install_links("claude", &links).unwrap(), 2);
+        assert!(skills.join("review/SKILL.md").is_symlink());
+        assert!(!skills.join("review/.SKILL.md.axel-new").exists());
+
+        // Installing again leaves up-to-date links alone
+        assert_eq!(install_links("claude", &links).unwrap(), 2);
+        assert_eq!(
+            std::fs::read_to_string(skills.join("deploy/SKILL.md")).unwrap(),
+            "Deploy"
+        );
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -0,0 +1,162 @@
+//! Sections of user files owned by axel
+//!
+//! Some files a tool reads are also the user's own, such as Codex's
+//! `AGENTS.md`. axel writes its part between two markers:
+//!
+//! ```markdown
+//! Notes the user wrote, left as they are.
+//!
+//! <!-- axel:start -->
+//! Workspace index, replaced on every install.
+//! <!-- axel:end -->
+//! ```
+//!
+//! Installing again replaces only what's between the markers, and cleanup
+//! removes them along with what they hold, deleting the file only when
+//! nothing else is left in it.
+
+use std::path::Path;
+
+use anyhow::{Context, Result};
+
+/// Start of the section axel owns
+pub const MANAGED_START: &str = "<!-- axel:start -->";
+
+/// End of the section axel owns
+pub const MANAGED_END: &str = "<!-- axel:end -->";
+
+/// Write `content` as axel's section of the file at `path`, keeping the
+/// rest of the file.
+///
+/// A symlink at `path` is an older install's link and is replaced by a
+/// file. Returns true if the file changed.
+pub(super) fn write_managed_section(path: &Path, content: &str) -> Result<bool> {
+    let is_symlink = path.is_symlink();
+    let existing = if is_symlink {
+        String::new()
+    } else {
+        read_existing(path)?
+    };
+
+    let section = format!("{}\n{}\n{}\n", MANAGED_START, content.trim(), MANAGED_END);
+    let (before, after) = split_managed(&existing);
+    let updated = match (before.trim_end(), after.trim_start()) {
+        ("", "") => section,
+        (before, "") => format!("{}\n\n{}", before, section),
+        ("", after) => format!("{}\n{}", section, after),
+        (before, after) => format!("{}\n\n{}\n{}", before, section, after),
+    };
+    if !is_symlink && updated == existing {
+        return Ok(false);
+    }
+
+    if is_symlink {
+        std::fs::remove_file(path)?;
+    }
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, updated).with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(true)
+}
+
+/// Remove axel's section from the file at `path`, and the file if nothing
+/// else is left in it.
+///
+/// Returns true if there was a section to remove.
+pub(super) fn remove_managed_section(path: &Path) -> bool {
+    let Ok(existing) = std::fs::read_to_string(path) else {
+        return false;
+    };
+    if !existing.contains(MANAGED_START) {
+        return false;
+    }
+
+    let (before, after) = split_managed(&existing);
+    let rest = match (before.trim_end(), after.trim_start()) {
+        ("", after) => after.to_string(),
+        (before, "") => format!("{}\n", before),
+        (before, after) => format!("{}\n\n{}", before, after),
+    };
+    if rest.trim().is_empty() {
+        std::fs::remove_file(path).is_ok()
+    } else {
+        std::fs::write(path, rest).is_ok()
+    }
+}
+
+/// Content of `path`, empty if it doesn't exist
+fn read_existing(path: &Path) -> Result<String> {
+    match std::fs::read_to_string(path) {
+        Ok(content) => Ok(content),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(String::new()),
+        Err(e) => Err(e).with_context(|| format!("Failed to read {}", path.display())),
+    }
+}
+
+/// The text before and after axel's section. Without a section, everything
+/// is before it; without an end marker, the section runs to the end.
+fn split_managed(content: &str) -> (&str, &str) {
+    let Some(start) = content.find(MANAGED_START) else {
+        return (content, "");
+    };
+    let after = content[start..]
+        .find(MANAGED_END)
+        .map(|end| &content[start + end + MANAGED_END.len()..])
+        .unwrap_or("");
+    (&content[..start], after)
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_managed_section() {
+        let dir = std::env::temp_dir().join("axel-test-managed-section");
+        std::fs::remove_dir_all(&dir).ok();
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("AGENTS.md");
+
+        // The user's notes are kept, axel's section goes after them
+        std::fs::write(&path, "# Notes\n\nRun tests with make.\n").unwrap();
+        assert!(write_managed_section(&path, "Index v1\n").unwrap());
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "# Notes\n\nRun tests with make.\n\n<!-- axel:start -->\nIndex v1\n<!-- axel:end -->\n"
+        );
+
+        // Installing again only replaces the section
+        std::fs::write(
+            &path,
+            std::fs::read_to_string(&path).unwrap() + "\nMore notes.\n",
+        )
+        .unwrap();
+        assert!(write_managed_section(&path, "Index v2").unwrap());
+        assert!(!write_managed_section(&path, "Index v2").unwrap());
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "# Notes\n\nRun tests with make.\n\n<!-- axel:start -->\nIndex v2\n<!-- axel:end -->\n\nMore notes.\n"
+        );
+
+        // Cleanup leaves the user's notes
+        assert!(remove_managed_section(&path));
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "# Notes\n\nRun tests with make.\n\nMore notes.\n"
+        );
+        assert!(!remove_managed_section(&path));
+
+        // A file holding only axel's section is removed
+        std::fs::remove_file(&path).unwrap();
+        assert!(write_managed_section(&path, "Index").unwrap());
+        assert!(remove_managed_section(&path));
+        assert!(!path.exists());
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
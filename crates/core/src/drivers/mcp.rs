@@ -0,0 +1,184 @@
+//! MCP server configuration for AI panes.
+//!
+//! An AI pane can list the MCP servers its agent should start:
+//!
+//! ```yaml
+//! - type: claude
+//!   mcp:
+//!     github:
+//!       command: npx
+//!       args: ["-y", "@modelcontextprotocol/server-github"]
+//!       env:
+//!         GITHUB_TOKEN: ${GITHUB_TOKEN}
+//! ```
+//!
+//! The servers are written, in the tool's own format, to
+//! `.axel/mcp/<pane>.json` when skills are installed, and the pane command
+//! points the tool at that file (`--mcp-config` for Claude Code,
+//! `OPENCODE_CONFIG` for OpenCode). A project's own `.mcp.json` or
+//! `opencode.json` is left untouched. The files are removed with the
+//! installed skills.
+
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result};
+use serde_json::{Value, json};
+
+use super::SkillDriver;
+use crate::config::McpServer;
+
+/// Directory MCP configs are written to, relative to the workspace
+pub const MCP_CONFIG_DIR: &str = ".axel/mcp";
+
+/// Path of the MCP config generated for pane `pane_name`
+pub fn mcp_config_path(workspace_dir: &Path, pane_name: &str) -> PathBuf {
+    workspace_dir
+        .join(MCP_CONFIG_DIR)
+        .join(format!("{}.json", pane_name))
+}
+
+/// Write the MCP config of pane `pane_name` for `driver`.
+///
+/// Returns false when the pane has no servers or the driver can't be given
+/// MCP servers; a config left over from an earlier launch is removed then.
+pub fn install_mcp_config(
+    driver: &dyn SkillDriver,
+    workspace_dir: &Path,
+    pane_name: &str,
+    servers: &BTreeMap<String, McpServer>,
+) -> Result<bool> {
+    let path = mcp_config_path(workspace_dir, pane_name);
+    let config = (!servers.is_empty())
+        .then(|| driver.mcp_config(servers))
+        .flatten();
+    let Some(config) = config else {
+        std::fs::remove_file(&path).ok();
+        return Ok(false);
+    };
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(&path, serde_json::to_string_pretty(&config)? + "\n")
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(true)
+}
+
+/// Remove the generated MCP configs
+///
+/// Returns true if any were removed.
+pub fn cleanup_mcp_configs(workspace_dir: &Path) -> bool {
+    let dir = workspace_dir.join(MCP_CONFIG_DIR);
+    dir.is_dir() && std::fs::remove_dir_all(&dir).is_ok()
+}
+
+/// `mcpServers` config read by Claude Code's `--mcp-config`
+pub(super) fn claude_mcp_config(servers: &BTreeMap<String, McpServer>) -> Value {
+    let servers: serde_json::Map<String, Value> = servers
+        .iter()
+        .map(|(name, server)| {
+            let mut entry = json!({
+                "type": "stdio",
+                "command": server.command,
+                "args": server.args,
+            });
+            if !server.env.is_empty() {
+                entry["env"] = json!(server.env);
+            }
+            (name.clone(), entry)
+        })
+        .collect();
+    json!({ "mcpServers": servers })
+}
+
+/// `mcp` section of an OpenCode config file
+pub(super) fn opencode_mcp_config(servers: &BTreeMap<String, McpServer>) -> Value {
+    let servers: serde_json::Map<String, Value> = servers
+        .iter()
+        .map(|(name, server)| {
+            let command: Vec<&str> = std::iter::once(server.command.as_str())
+                .chain(server.args.iter().map(String::as_str))
+                .collect();
+            let mut entry = json!({
+                "type": "local",
+                "command": command,
+                "enabled": true,
+            });
+            if !server.env.is_empty() {
+                entry["environment"] = json!(server.env);
+            }
+            (name.clone(), entry)
+        })
+        .collect();
+    json!({
+        "$schema": "https://opencode.ai/config.json",
+        "mcp": servers,
+    })
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::drivers::{ClaudeDriver, CodexDriver, OpenCodeDriver};
+
+    fn servers() -> BTreeMap<String, McpServer> {
+        BTreeMap::from([(
+            "github".to_string(),
+            McpServer {
+                command: "npx".to_string(),
+                args: vec!["-y".to_string(), "server-github".to_string()],
+                env: BTreeMap::from([("GITHUB_TOKEN".to_string(), "abc".to_string())]),
+            },
+        )])
+    }
+
+    #[test]
+    fn test_install_mcp_config() {
+        let dir = std::env::temp_dir().join("axel-test-mcp-config");
+        std::fs::remove_dir_all(&dir).ok();
+        std::fs::create_dir_all(&dir).unwrap();
+        let read = |pane: &str| -> Value {
+            let content = std::fs::read_to_string(mcp_config_path(&dir, pane)).unwrap();
+            serde_json::from_str(&content).unwrap()
+        };
+
+        assert!(install_mcp_config(&ClaudeDriver, &dir, "claude", &servers()).unwrap());
+        assert_eq!(
+            read("claude"),
+            json!({"mcpServers": {"github": {
+                "type": "stdio",
+                "command": "npx",
+                "args": ["-y", "server-github"],
+                "env": {"GITHUB_TOKEN": "abc"},
+            }}})
+        );
+
+        assert!(install_mcp_config(&OpenCodeDriver, &dir, "opencode", &servers()).unwrap());
+        assert_eq!(
+            read("opencode")["mcp"]["github"],
+            json!({
+                "type": "local",
+                "command": ["npx", "-y", "server-github"],
+                "enabled": true,
+                "environment": {"GITHUB_TOKEN": "abc"},
+            })
+        );
+
+        // Codex has no MCP support here; removing the servers removes the file
+        assert!(!install_mcp_config(&CodexDriver, &dir, "codex", &servers()).unwrap());
+        assert!(!install_mcp_config(&ClaudeDriver, &dir, "claude", &BTreeMap::new()).unwrap());
+        assert!(!mcp_config_path(&dir, "claude").exists());
+
+        assert!(cleanup_mcp_configs(&dir));
+        assert!(!dir.join(MCP_CONFIG_DIR).exists());
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
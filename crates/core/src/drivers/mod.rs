@@ -2,22 +2,41 @@
 //!
 //! This module provides the `SkillDriver` trait and implementations for various
 //! AI coding tools (Claude Code, Codex, OpenCode, Antigravity). Drivers handle
-//! installing skill files in tool-specific formats.
+//! installing skill files in tool-specific formats. Additional drivers can be
+//! defined in TOML without recompiling (see [`ExternalDriver`]).
 
 mod antigravity;
+mod bench;
 mod claude;
 mod codex;
+mod external;
+mod install;
+mod managed;
+mod mcp;
 mod opencode;
 
-use std::path::{Path, PathBuf};
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
 
 pub use antigravity::AntigravityDriver;
 use anyhow::Result;
+pub use bench::{BenchReport, bench_install};
 pub use claude::ClaudeDriver;
-pub use codex::CodexDriver;
-pub use opencode::OpenCodeDriver;
-
-use crate::config::WorkspaceConfig;
+pub use codex::{
+    CODEX_CONFIG, CODEX_PROFILE, CodexDriver, codex_config_path, codex_profile, codex_profile_name,
+    config_overrides, write_codex_profile,
+};
+pub use external::{ExternalDriver, external_drivers, load_external_drivers};
+pub use managed::{MANAGED_END, MANAGED_START};
+pub use mcp::{MCP_CONFIG_DIR, cleanup_mcp_configs, install_mcp_config, mcp_config_path};
+pub use opencode::{
+    OPENCODE_CONFIG_DIR, OpenCodeDriver, install_opencode_config, opencode_config,
+    opencode_config_path,
+};
+
+use crate::config::{McpServer, WorkspaceConfig};
 
 /// Trait for skill installation drivers
 ///
@@ -34,9 +53,19 @@ pub trait SkillDriver {
     /// Returns patterns like "CLAUDE.md", ".claude/skills/*.md", etc.
     fn skill_patterns(&self) -> &'static [&'static str];
 
+    /// Where skill `name` ends up once installed
+    ///
+    /// Defaults to `<skills_dir>/<name>/SKILL.md`.
+    fn installed_skill_path(&self, workspace_dir: &Path, name: &str) -> PathBuf {
+        self.skills_dir(workspace_dir).join(name).join("SKILL.md")
+    }
+
     /// Install skills to the target directory
     ///
-    /// Returns the number of skills installed.
+    /// Either every skill is installed or none of the call's changes are
+    /// kept; the error is then an [`Error::SkillInstall`](crate::Error)
+    /// listing each skill that failed. Installing the same skills again is
+    /// safe. Returns the number of skills installed.
     fn install_skills(&self, workspace_dir: &Path, skill_paths: &[PathBuf]) -> Result<usize>;
 
     /// Clean up installed skills from the workspace
@@ -44,6 +73,20 @@ pub trait SkillDriver {
     /// Returns true if any cleanup was performed.
     fn cleanup(&self, workspace_dir: &Path) -> bool;
 
+    /// Tools a skill may list in its `tools:` frontmatter.
+    ///
+    /// Returns None if the tool ignores `tools:` (the default).
+    fn skill_tools(&self) -> Option<&'static [&'static str]> {
+        None
+    }
+
+    /// MCP config file giving the tool `servers`, in the tool's format.
+    ///
+    /// Returns None if the tool can't be given MCP servers (the default).
+    fn mcp_config(&self, _servers: &BTreeMap<String, McpServer>) -> Option<serde_json::Value> {
+        None
+    }
+
     /// Get environment variables for OpenTelemetry configuration.
     ///
     /// Returns a list of (key, value) pairs to set when launching the shell.
@@ -76,14 +119,15 @@ pub trait SkillDriver {
         None
     }
 
-    /// Install index file (e.g., CLAUDE.md, AGENTS.md) as symlink to AXEL.md.
+    /// Install the workspace index where the tool reads project context.
     ///
     /// Each tool expects project context in a specific file:
-    /// - Claude Code: CLAUDE.md
-    /// - Codex: AGENTS.md
-    /// - OpenCode: OPENCODE.md
+    /// - Claude Code: CLAUDE.md, a symlink to AXEL.md
+    /// - Codex and OpenCode: AGENTS.md, in a section between
+    ///   [`MANAGED_START`] and [`MANAGED_END`] so the rest of the file stays
+    ///   the user's
     ///
-    /// Returns true if the symlink was created.
+    /// Returns true if the index was installed.
     fn install_index(&self, _config: &WorkspaceConfig, _workspace_dir: &Path) -> Result<bool> {
         Ok(false)
     }
@@ -95,24 +139,51 @@ pub trait SkillDriver {
 }
 
 /// Get a driver by name
+///
+/// Built-in drivers take precedence over external drivers of the same name.
 pub fn get_driver(name: &str) -> Option<Box<dyn SkillDriver>> {
     match name {
         "claude" => Some(Box::new(ClaudeDriver)),
         "codex" => Some(Box::new(CodexDriver)),
         "opencode" => Some(Box::new(OpenCodeDriver)),
         "antigravity" => Some(Box::new(AntigravityDriver)),
-        _ => None,
+        _ => external_driver(name).map(|d| Box::new(d.clone()) as Box<dyn SkillDriver>),
+    }
+}
+
+/// Get an external driver by name, unless a built-in driver shadows it
+pub fn external_driver(name: &str) -> Option<&'static ExternalDriver> {
+    if BUILTIN_DRIVERS.contains(&name) {
+        return None;
     }
+    external_drivers().iter().find(|d| d.name() == name)
 }
 
-/// Get all available drivers
+/// Directory where a driver installs skills for the given workspace
+///
+/// Returns `None` for an unknown driver name.
+pub fn driver_skills_dir(driver_name: &str, workspace_dir: &Path) -> Option<PathBuf> {
+    get_driver(driver_name).map(|driver| driver.skills_dir(workspace_dir))
+}
+
+/// Names of the drivers compiled into axel
+const BUILTIN_DRIVERS: &[&str] = &["claude", "codex", "opencode", "antigravity"];
+
+/// Get all available drivers, built-in first, then external
 pub fn all_drivers() -> Vec<Box<dyn SkillDriver>> {
-    vec![
+    let mut drivers: Vec<Box<dyn SkillDriver>> = vec![
         Box::new(ClaudeDriver),
         Box::new(CodexDriver),
         Box::new(OpenCodeDriver),
         Box::new(AntigravityDriver),
-    ]
+    ];
+    drivers.extend(
+        external_drivers()
+            .iter()
+            .filter(|d| !BUILTIN_DRIVERS.contains(&d.name()))
+            .map(|d| Box::new(d.clone()) as Box<dyn SkillDriver>),
+    );
+    drivers
 }
 
 /// Get all skill file patterns from all drivers
@@ -125,3 +196,31 @@ pub fn all_skill_patterns() -> Vec<&'static str> {
     }
     patterns
 }
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_driver_skills_dir() {
+        let workspace = Path::new("/code/app");
+        for (driver, expected) in [
+            ("claude", "/code/app/.claude/skills"),
+            ("codex", "/code/app/.codex/skills"),
+            ("opencode", "/code/app/.opencode/skill"),
+            ("antigravity", "/code/app/.antigravity"),
+        ] {
+            assert_eq!(
+                driver_skills_dir(driver, workspace),
+                Some(PathBuf::from(expected)),
+                "{}",
+                driver
+            );
+        }
+        assert_eq!(driver_skills_dir("cursor", workspace), None);
+    }
+}
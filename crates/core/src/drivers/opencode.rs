@@ -7,13 +7,46 @@
 //! 1. Creates `.opencode/skill/` if it doesn't exist
 //! 2. Symlinks each skill file as `<name>.md`
 //! 3. On cleanup, removes only symlinks (preserving any manually created files)
+//!
+//! A pane's MCP servers and permissions are written to a generated config
+//! file, `.axel/opencode/<pane>.json`, which the pane command points
+//! OpenCode at (`OPENCODE_CONFIG`). Permissions come from the pane's
+//! `permission_mode`, `allowed_tools` and `disallowed_tools`, written the
+//! Claude Code way:
+//!
+//! ```yaml
+//! - type: opencode
+//!   permission_mode: acceptEdits      # edits allowed without asking
+//!   allowed_tools: ["Bash(git status:*)", WebFetch]
+//!   disallowed_tools: ["Bash(rm:*)"]
+//! ```
+//!
+//! ## OpenTelemetry Support
+//!
+//! OpenCode exports traces through the AI SDK once its experimental
+//! `openTelemetry` setting is on. The setting and the OTLP exporters are
+//! given through environment variables, like Claude Code.
+
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
 
-use std::path::{Path, PathBuf};
+use anyhow::{Context, Result};
+use serde_json::{Map, Value, json};
 
-use anyhow::Result;
+use super::{
+    SkillDriver,
+    codex::{cleanup_agents_md, install_agents_md},
+    install::{SkillLink, install_links},
+};
+use crate::{
+    config::{AiPaneConfig, McpServer, WorkspaceConfig},
+    hooks::{otel_logs_endpoint, otel_metrics_endpoint, otel_traces_endpoint},
+};
 
-use super::{SkillDriver, claude::install_index_symlink};
-use crate::config::WorkspaceConfig;
+/// Directory OpenCode configs are written to, relative to the workspace
+pub const OPENCODE_CONFIG_DIR: &str = ".axel/opencode";
 
 /// OpenCode skill driver
 pub struct OpenCodeDriver;
@@ -31,43 +64,41 @@ impl SkillDriver for OpenCodeDriver {
         &[".opencode/skill/*.md", ".opencode/SKILL.md"]
     }
 
-    fn install_skills(&self, workspace_dir: &Path, skill_paths: &[PathBuf]) -> Result<usize> {
-        if skill_paths.is_empty() {
-            return Ok(0);
-        }
-
-        let skills_dir = self.skills_dir(workspace_dir);
-        std::fs::create_dir_all(&skills_dir)?;
-
-        let mut count = 0;
-        for source_path in skill_paths {
-            let name = derive_skill_name(source_path);
-            let link_path = skills_dir.join(format!("{}.md", name));
-
-            // Remove existing symlink/file if present
-            if link_path.exists() || link_path.is_symlink() {
-                std::fs::remove_file(&link_path).ok();
-            }
-
-            // Canonicalize the source path to get a clean absolute path
-            let canonical_source = source_path
-                .canonicalize()
-                .unwrap_or_else(|_| source_path.clone());
+    fn mcp_config(&self, servers: &BTreeMap<String, McpServer>) -> Option<serde_json::Value> {
+        Some(super::mcp::opencode_mcp_config(servers))
+    }
 
-            // Create symlink
-            #[cfg(unix)]
-            {
-                std::os::unix::fs::symlink(&canonical_source, &link_path)?;
-                count += 1;
-            }
-        }
+    fn installed_skill_path(&self, workspace_dir: &Path, name: &str) -> PathBuf {
+        self.skills_dir(workspace_dir).join(format!("{}.md", name))
+    }
 
-        Ok(count)
+    fn install_skills(&self, workspace_dir: &Path, skill_paths: &[PathBuf]) -> Result<usize> {
+        let links: Vec<SkillLink> = skill_paths
+            .iter()
+            .map(|source| {
+                let name = derive_skill_name(source);
+                SkillLink {
+                    link: self.installed_skill_path(workspace_dir, &name),
+                    source: source.clone(),
+                    name,
+                }
+            })
+            .collect();
+        install_links(self.name(), &links)
     }
 
     fn cleanup(&self, workspace_dir: &Path) -> bool {
         let mut cleaned = false;
 
+        // Remove generated MCP and OpenCode configs
+        if super::cleanup_mcp_configs(workspace_dir) {
+            cleaned = true;
+        }
+        let config_dir = workspace_dir.join(OPENCODE_CONFIG_DIR);
+        if config_dir.is_dir() && std::fs::remove_dir_all(&config_dir).is_ok() {
+            cleaned = true;
+        }
+
         // Remove skill symlinks from .opencode/skill/
         let skills_dir = self.skills_dir(workspace_dir);
         if skills_dir.exists()
@@ -86,27 +117,178 @@ impl SkillDriver for OpenCodeDriver {
             }
         }
 
-        // Remove AGENTS.md symlink
-        let agents_md = workspace_dir.join("AGENTS.md");
-        if agents_md
-            .symlink_metadata()
-            .map(|m| m.file_type().is_symlink())
-            .unwrap_or(false)
-            && std::fs::remove_file(&agents_md).is_ok()
-        {
+        if cleanup_agents_md(workspace_dir) {
             cleaned = true;
         }
 
         cleaned
     }
 
+    fn supports_otel(&self) -> bool {
+        true
+    }
+
+    fn otel_env_vars(&self, port: u16, pane_id: &str) -> Vec<(String, String)> {
+        vec![
+            // Turn on the AI SDK's telemetry (merged into the rest of the config)
+            (
+                "OPENCODE_CONFIG_CONTENT".to_string(),
+                json!({"experimental": {"openTelemetry": true}}).to_string(),
+            ),
+            ("OTEL_TRACES_EXPORTER".to_string(), "otlp".to_string()),
+            ("OTEL_METRICS_EXPORTER".to_string(), "otlp".to_string()),
+            ("OTEL_LOGS_EXPORTER".to_string(), "otlp".to_string()),
+            (
+                "OTEL_EXPORTER_OTLP_PROTOCOL".to_string(),
+                "http/json".to_string(),
+            ),
+            (
+                "OTEL_EXPORTER_OTLP_TRACES_ENDPOINT".to_string(),
+                otel_traces_endpoint(port, pane_id),
+            ),
+            (
+                "OTEL_EXPORTER_OTLP_METRICS_ENDPOINT".to_string(),
+                otel_metrics_endpoint(port, pane_id),
+            ),
+            (
+                "OTEL_EXPORTER_OTLP_LOGS_ENDPOINT".to_string(),
+                otel_logs_endpoint(port, pane_id),
+            ),
+            // Same export intervals as Claude Code
+            (
+                "OTEL_METRIC_EXPORT_INTERVAL".to_string(),
+                "10000".to_string(),
+            ),
+            ("OTEL_BSP_SCHEDULE_DELAY".to_string(), "10000".to_string()),
+        ]
+    }
+
     fn index_filename(&self) -> Option<&'static str> {
         Some("AGENTS.md")
     }
 
     fn install_index(&self, config: &WorkspaceConfig, workspace_dir: &Path) -> Result<bool> {
-        install_index_symlink(config, workspace_dir, "AGENTS.md")
+        install_agents_md(config, workspace_dir)
+    }
+}
+
+/// Path of the OpenCode config generated for pane `pane_name`
+pub fn opencode_config_path(workspace_dir: &Path, pane_name: &str) -> PathBuf {
+    workspace_dir
+        .join(OPENCODE_CONFIG_DIR)
+        .join(format!("{}.json", pane_name))
+}
+
+/// OpenCode config for a pane's MCP servers and permissions.
+///
+/// Returns None when the pane sets neither.
+pub fn opencode_config(config: &AiPaneConfig) -> Option<Value> {
+    let permission = opencode_permissions(config);
+    if config.mcp.is_empty() && permission.is_empty() {
+        return None;
     }
+
+    let mut opencode = json!({"$schema": "https://opencode.ai/config.json"});
+    if !config.mcp.is_empty() {
+        opencode["mcp"] = super::mcp::opencode_mcp_config(&config.mcp)["mcp"].take();
+    }
+    if !permission.is_empty() {
+        opencode["permission"] = Value::Object(permission);
+    }
+    Some(opencode)
+}
+
+/// Write the OpenCode config of pane `pane_name`.
+///
+/// Returns false when the pane needs none; a config left over from an
+/// earlier launch is removed then.
+pub fn install_opencode_config(
+    workspace_dir: &Path,
+    pane_name: &str,
+    config: &AiPaneConfig,
+) -> Result<bool> {
+    let path = opencode_config_path(workspace_dir, pane_name);
+    let Some(opencode) = opencode_config(config) else {
+        std::fs::remove_file(&path).ok();
+        return Ok(false);
+    };
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(&path, serde_json::to_string_pretty(&opencode)? + "\n")
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(true)
+}
+
+/// OpenCode `permission` settings for a pane.
+///
+/// `permission_mode` sets the defaults (`bypassPermissions` allows
+/// everything, `acceptEdits` allows edits, `plan` denies them). Then
+/// `allowed_tools` allow and `disallowed_tools` deny, so a tool in both is
+/// denied: `Edit`, `Write` and `MultiEdit` map to `edit`, `WebFetch` to
+/// `webfetch`, `Bash` to `bash` and `Bash(<command>:*)` to a `bash` pattern.
+/// Other tools have no OpenCode permission and are skipped.
+fn opencode_permissions(config: &AiPaneConfig) -> Map<String, Value> {
+    let mut edit = None;
+    let mut bash = None;
+    let mut webfetch = None;
+    match config.permission_mode.as_deref() {
+        Some("bypassPermissions") => {
+            edit = Some("allow");
+            bash = Some("allow");
+            webfetch = Some("allow");
+        }
+        Some("acceptEdits") => edit = Some("allow"),
+        Some("plan") => edit = Some("deny"),
+        _ => {}
+    }
+
+    let mut bash_patterns = Map::new();
+    let tools = config
+        .allowed_tools
+        .iter()
+        .map(|tool| (tool, "allow"))
+        .chain(config.disallowed_tools.iter().map(|tool| (tool, "deny")));
+    for (tool, level) in tools {
+        let (name, pattern) = match tool.split_once('(') {
+            Some((name, rest)) => (name, rest.strip_suffix(')')),
+            None => (tool.as_str(), None),
+        };
+        match (name, pattern) {
+            ("Bash", Some(pattern)) => {
+                let pattern = match pattern.strip_suffix(":*") {
+                    Some(prefix) => format!("{} *", prefix),
+                    None => pattern.to_string(),
+                };
+                bash_patterns.insert(pattern, level.into());
+            }
+            ("Bash", None) => bash = Some(level),
+            ("Edit" | "Write" | "MultiEdit", _) => edit = Some(level),
+            ("WebFetch", _) => webfetch = Some(level),
+            _ => {}
+        }
+    }
+
+    let mut permission = Map::new();
+    if let Some(level) = edit {
+        permission.insert("edit".to_string(), level.into());
+    }
+    if bash_patterns.is_empty() {
+        if let Some(level) = bash {
+            permission.insert("bash".to_string(), level.into());
+        }
+    } else {
+        // Commands matching no pattern fall back to `*`
+        if let Some(level) = bash {
+            bash_patterns.insert("*".to_string(), level.into());
+        }
+        permission.insert("bash".to_string(), Value::Object(bash_patterns));
+    }
+    if let Some(level) = webfetch {
+        permission.insert("webfetch".to_string(), level.into());
+    }
+    permission
 }
 
 /// Derive skill name from file path.
@@ -126,3 +308,61 @@ fn derive_skill_name(path: &Path) -> String {
             .unwrap_or_else(|| "skill".to_string())
     }
 }
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pane(yaml: &str) -> AiPaneConfig {
+        serde_yaml::from_str(yaml).unwrap()
+    }
+
+    #[test]
+    fn test_opencode_config() {
+        assert_eq!(opencode_config(&pane("type: opencode\n")), None);
+
+        let config = pane(
+            r#"
+type: opencode
+permission_mode: acceptEdits
+allowed_tools: [Bash, "Bash(git status:*)", WebFetch, Read]
+disallowed_tools: ["Bash(rm:*)", WebFetch]
+mcp:
+  github:
+    command: npx
+    args: [server-github]
+"#,
+        );
+        let opencode = opencode_config(&config).unwrap();
+        assert_eq!(
+            opencode["permission"],
+            json!({
+                "edit": "allow",
+                "bash": {"*": "allow", "git status *": "allow", "rm *": "deny"},
+                "webfetch": "deny",
+            })
+        );
+        assert_eq!(
+            opencode["mcp"]["github"]["command"],
+            json!(["npx", "server-github"])
+        );
+
+        let dir = std::env::temp_dir().join("axel-test-opencode-config");
+        std::fs::remove_dir_all(&dir).ok();
+        assert!(install_opencode_config(&dir, "opencode", &config).unwrap());
+        let path = opencode_config_path(&dir, "opencode");
+        let written: Value =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(written, opencode);
+
+        // A pane without servers or permissions loses its old config
+        assert!(!install_opencode_config(&dir, "opencode", &pane("type: opencode\n")).unwrap());
+        assert!(!path.exists());
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
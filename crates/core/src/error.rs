@@ -0,0 +1,111 @@
+//! Typed errors
+//!
+//! Failures a caller is likely to handle rather than just report. Library
+//! code returns them inside [`anyhow::Error`] instead of printing and exiting
+//! the process, so an application embedding axel can recover:
+//!
+//! ```ignore
+//! match launch(&opts) {
+//!     Err(e) if matches!(e.downcast_ref(), Some(Error::SessionNotFound(_))) => ...,
+//!     other => other?,
+//! }
+//! ```
+//!
+//! The CLI prints them and exits with status 1.
+
+use std::path::PathBuf;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("Manifest not found: {}", .0.display())]
+    ManifestNotFound(PathBuf),
+
+    #[error("Manifest has {0} error{s}", s = plural(*.0))]
+    InvalidManifest(usize),
+
+    #[error("Grid '{name}' not found. Available grids: {}", .available.join(", "))]
+    GridNotFound {
+        name: String,
+        available: Vec<String>,
+    },
+
+    #[error("Session '{0}' not found")]
+    SessionNotFound(String),
+
+    #[error("Not inside a tmux session. Specify one with --session <name>")]
+    NotInSession,
+
+    /// A session with the workspace's name was launched from another manifest
+    #[error(
+        "A session named '{name}' already exists for a different workspace ({})",
+        .existing.display()
+    )]
+    SessionConflict { name: String, existing: PathBuf },
+
+    #[error("No matching AI panes in session '{0}'")]
+    NoMatchingPanes(String),
+
+    #[error("Session '{session}' has several AI panes: {}", .panes.join(", "))]
+    AmbiguousPane { session: String, panes: Vec<String> },
+
+    #[error("Skill '{0}' not found")]
+    SkillNotFound(String),
+
+    #[error("Global skill '{0}' not found")]
+    GlobalSkillNotFound(String),
+
+    #[error("Skill '{0}' already exists in workspace")]
+    SkillExists(String),
+
+    #[error("Path not found: {}", .0.display())]
+    PathNotFound(PathBuf),
+
+    #[error("Cannot import symlinks")]
+    SymlinkImport,
+
+    #[error("No .md files found in directory")]
+    NoSkillFiles,
+
+    #[error(
+        "Skill lint found {errors} error{} and {warnings} warning{}",
+        plural(*.errors),
+        plural(*.warnings)
+    )]
+    LintFailed { errors: usize, warnings: usize },
+
+    /// Some skills couldn't be installed for a driver; none of the call's
+    /// changes were kept
+    #[error(
+        "Failed to install {} skill{} for {driver}: {}",
+        .failures.len(),
+        plural(.failures.len()),
+        .failures.iter().map(|(skill, e)| format!("{} ({})", skill, e)).collect::<Vec<_>>().join(", ")
+    )]
+    SkillInstall {
+        driver: String,
+        /// Skill names and what went wrong with each
+        failures: Vec<(String, String)>,
+    },
+
+    #[error("{0} skill{s} changed since skills.lock", s = plural(*.0))]
+    SkillDrift(usize),
+
+    #[error("Found {0} include cycle{s}", s = plural(*.0))]
+    IncludeCycle(usize),
+
+    #[error("Not a git repository")]
+    NotGitRepo,
+
+    #[error("No worktree found for branch '{0}'")]
+    WorktreeNotFound(String),
+
+    #[error("Worktree for '{branch}' is in use by session '{session}'")]
+    WorktreeInUse { branch: String, session: String },
+
+    #[error("Worktree for '{0}' has uncommitted changes")]
+    WorktreeDirty(String),
+}
+
+fn plural(count: usize) -> &'static str {
+    if count == 1 { "" } else { "s" }
+}
@@ -0,0 +1,135 @@
+//! Workspaces launched together by `axel up`
+//!
+//! `~/.config/axel/fleet.yaml` lists workspaces (usually different
+//! repositories) that are started and stopped as a group:
+//!
+//! ```yaml
+//! workspaces:
+//!   - ~/src/api
+//!   - ~/src/web
+//!   - path: ~/src/infra/AXEL.md
+//!     grid: ops
+//! ```
+//!
+//! An entry is a workspace directory or its manifest, optionally with the
+//! grid to launch instead of the default one. `axel up` starts each
+//! workspace in its own tmux session, in the background, and `axel down`
+//! kills them.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+use crate::config::{expand_path, manifest_in};
+
+/// Fleet file, relative to the home directory
+pub const FLEET_FILE: &str = ".config/axel/fleet.yaml";
+
+/// Fleet file (`~/.config/axel/fleet.yaml`)
+pub fn fleet_path() -> Option<PathBuf> {
+    dirs::home_dir().map(|home| home.join(FLEET_FILE))
+}
+
+/// Contents of the fleet file
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct FleetConfig {
+    #[serde(default)]
+    pub workspaces: Vec<FleetWorkspace>,
+}
+
+/// One workspace of the fleet, written as a bare path or a mapping
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(from = "FleetWorkspaceRaw")]
+pub struct FleetWorkspace {
+    /// Workspace directory or manifest; `~/` is expanded
+    pub path: String,
+    /// Grid to launch instead of the default one
+    pub grid: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum FleetWorkspaceRaw {
+    Path(String),
+    Entry {
+        path: String,
+        #[serde(default)]
+        grid: Option<String>,
+    },
+}
+
+impl From<FleetWorkspaceRaw> for FleetWorkspace {
+    fn from(raw: FleetWorkspaceRaw) -> Self {
+        match raw {
+            FleetWorkspaceRaw::Path(path) => FleetWorkspace { path, grid: None },
+            FleetWorkspaceRaw::Entry { path, grid } => FleetWorkspace { path, grid },
+        }
+    }
+}
+
+impl FleetWorkspace {
+    /// The workspace's manifest: `path` itself when it names a file,
+    /// otherwise `AXEL.md` (or `axel.yaml`) inside it
+    pub fn manifest_path(&self) -> PathBuf {
+        let path = PathBuf::from(expand_path(&self.path));
+        if path.is_file() || path.extension().is_some_and(|ext| ext == "md") {
+            path
+        } else {
+            manifest_in(&path)
+        }
+    }
+
+    /// Name of the workspace's session: its directory's name, as when the
+    /// workspace is launched with `axel`
+    pub fn session_name(&self) -> String {
+        self.manifest_path()
+            .parent()
+            .and_then(Path::file_name)
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default()
+    }
+}
+
+/// Read the fleet file at `path`
+pub fn load_fleet(path: &Path) -> Result<FleetConfig> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    serde_yaml::from_str(&content).with_context(|| format!("Invalid fleet file {}", path.display()))
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_fleet() {
+        let dir = std::env::temp_dir().join("axel-test-fleet");
+        std::fs::remove_dir_all(&dir).ok();
+        std::fs::create_dir_all(dir.join("api")).unwrap();
+        let path = dir.join("fleet.yaml");
+        std::fs::write(
+            &path,
+            format!(
+                "workspaces:\n  - {0}/api\n  - path: {0}/web/AXEL.md\n    grid: review\n",
+                dir.display()
+            ),
+        )
+        .unwrap();
+
+        let fleet = load_fleet(&path).unwrap();
+        assert_eq!(fleet.workspaces.len(), 2);
+        assert_eq!(fleet.workspaces[0].grid, None);
+        assert_eq!(fleet.workspaces[0].manifest_path(), dir.join("api/AXEL.md"));
+        assert_eq!(fleet.workspaces[0].session_name(), "api");
+        assert_eq!(fleet.workspaces[1].grid.as_deref(), Some("review"));
+        assert_eq!(fleet.workspaces[1].manifest_path(), dir.join("web/AXEL.md"));
+        assert_eq!(fleet.workspaces[1].session_name(), "web");
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -8,6 +8,8 @@
 //! ```bash
 //! axel -w feat/auth    # Create worktree + launch workspace
 //! axel -w feat/auth -k # Kill workspace + optionally prune worktree
+//! axel worktree ls     # Show worktrees, their sessions and dirty state
+//! axel worktree prune  # Remove worktrees whose session is gone
 //! ```
 //!
 //! Worktrees are created as siblings to the main repository:
@@ -16,13 +18,23 @@
 //! ~/code/myproject-feat-auth/    # worktree for feat/auth
 //! ~/code/myproject-fix-bug/      # worktree for fix/bug
 //! ```
+//!
+//! New worktrees get a symlink to the main `AXEL.md` and the untracked files
+//! listed under `worktree.copy` (see [`crate::config::WorktreeCopy`]).
 
 use std::{
+    ffi::OsStr,
     path::{Path, PathBuf},
     process::Command,
 };
 
 use anyhow::{Context, Result, bail};
+use serde::Serialize;
+
+use crate::{
+    config::{WorktreeCopy, find_manifest},
+    platform,
+};
 
 /// Result of ensuring a worktree exists.
 #[derive(Debug)]
@@ -35,6 +47,40 @@ pub struct WorktreeInfo {
     pub created: bool,
     /// Whether the branch was newly created
     pub branch_created: bool,
+    /// `worktree.copy` entries brought over from the main checkout
+    pub copied: Vec<String>,
+}
+
+/// Check that the `git` executable is available on `PATH`.
+///
+/// Plain launches never need git; call this before worktree operations so a
+/// missing git produces a clear message instead of an opaque spawn failure.
+pub fn ensure_available() -> Result<()> {
+    ensure_available_in(std::env::var_os("PATH").as_deref())
+}
+
+/// Check for a `git` executable in the given `PATH`-style directory list.
+fn ensure_available_in(path: Option<&OsStr>) -> Result<()> {
+    let found =
+        path.is_some_and(|p| std::env::split_paths(p).any(|dir| is_executable(&dir.join("git"))));
+    if !found {
+        bail!("git not found; worktree features require git");
+    }
+    Ok(())
+}
+
+/// Whether a path is an executable file.
+fn is_executable(path: &Path) -> bool {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        path.metadata()
+            .is_ok_and(|m| m.is_file() && m.permissions().mode() & 0o111 != 0)
+    }
+    #[cfg(not(unix))]
+    {
+        path.with_extension("exe").is_file()
+    }
 }
 
 /// Check if we're inside a git repository.
@@ -149,8 +195,23 @@ pub fn default_branch(path: &Path) -> Result<String> {
     current_branch(path)
 }
 
-/// List all worktrees for a repository.
-pub fn list_worktrees(path: &Path) -> Result<Vec<(PathBuf, String)>> {
+/// A worktree as reported by `git worktree list`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Worktree {
+    /// Path to the worktree directory
+    pub path: PathBuf,
+    /// Checked-out branch, or None for a detached HEAD
+    pub branch: Option<String>,
+    /// Commit checked out
+    pub head: String,
+    /// Whether this is the main working tree rather than a linked one
+    pub main: bool,
+    /// Whether git considers the worktree stale (its directory is gone)
+    pub prunable: bool,
+}
+
+/// List all worktrees for a repository, main working tree first.
+pub fn worktrees(path: &Path) -> Result<Vec<Worktree>> {
     let output = Command::new("git")
         .args(["worktree", "list", "--porcelain"])
         .current_dir(path)
@@ -161,21 +222,109 @@ pub fn list_worktrees(path: &Path) -> Result<Vec<(PathBuf, String)>> {
         bail!("Failed to list worktrees");
     }
 
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    let mut worktrees = Vec::new();
-    let mut current_path: Option<PathBuf> = None;
-
-    for line in stdout.lines() {
-        if let Some(path_str) = line.strip_prefix("worktree ") {
-            current_path = Some(PathBuf::from(path_str));
-        } else if let Some(branch) = line.strip_prefix("branch refs/heads/")
-            && let Some(path) = current_path.take()
-        {
-            worktrees.push((path, branch.to_string()));
+    Ok(parse_worktrees(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Parse `git worktree list --porcelain` output.
+///
+/// Entries are separated by blank lines; the first one is the main tree.
+fn parse_worktrees(porcelain: &str) -> Vec<Worktree> {
+    let mut worktrees: Vec<Worktree> = Vec::new();
+
+    for line in porcelain.lines() {
+        if let Some(path) = line.strip_prefix("worktree ") {
+            worktrees.push(Worktree {
+                path: PathBuf::from(path),
+                branch: None,
+                head: String::new(),
+                main: worktrees.is_empty(),
+                prunable: false,
+            });
+        } else if let Some(worktree) = worktrees.last_mut() {
+            if let Some(head) = line.strip_prefix("HEAD ") {
+                worktree.head = head.to_string();
+            } else if let Some(branch) = line.strip_prefix("branch ") {
+                let branch = branch.strip_prefix("refs/heads/").unwrap_or(branch);
+                worktree.branch = Some(branch.to_string());
+            } else if line == "prunable" || line.starts_with("prunable ") {
+                worktree.prunable = true;
+            }
+        }
+    }
+
+    worktrees
+}
+
+/// List the worktrees that have a branch checked out, as (path, branch).
+pub fn list_worktrees(path: &Path) -> Result<Vec<(PathBuf, String)>> {
+    Ok(worktrees(path)?
+        .into_iter()
+        .filter_map(|w| Some((w.path, w.branch?)))
+        .collect())
+}
+
+/// Branch and working tree state of a checkout.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct RepoStatus {
+    /// Checked-out branch, or `(detached)`
+    pub branch: String,
+    /// Commits not yet pushed to the upstream branch
+    pub ahead: u32,
+    /// Upstream commits not yet pulled
+    pub behind: u32,
+    /// Changed and untracked files
+    pub dirty: usize,
+}
+
+/// Get the branch, upstream distance and changed-file count of a checkout.
+pub fn status(path: &Path) -> Result<RepoStatus> {
+    let output = Command::new("git")
+        .args(["status", "--porcelain=v2", "--branch"])
+        .current_dir(path)
+        .output()
+        .context("Failed to execute git")?;
+
+    if !output.status.success() {
+        bail!("Failed to get status of {}", path.display());
+    }
+
+    Ok(parse_status(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Parse `git status --porcelain=v2 --branch` output.
+fn parse_status(porcelain: &str) -> RepoStatus {
+    let mut status = RepoStatus::default();
+    for line in porcelain.lines() {
+        if let Some(head) = line.strip_prefix("# branch.head ") {
+            status.branch = head.to_string();
+        } else if let Some(ab) = line.strip_prefix("# branch.ab ") {
+            for count in ab.split_whitespace() {
+                if let Some(ahead) = count.strip_prefix('+') {
+                    status.ahead = ahead.parse().unwrap_or(0);
+                } else if let Some(behind) = count.strip_prefix('-') {
+                    status.behind = behind.parse().unwrap_or(0);
+                }
+            }
+        } else if !line.starts_with('#') && !line.is_empty() {
+            status.dirty += 1;
         }
     }
+    status
+}
+
+/// Check whether a working tree has uncommitted changes or untracked files.
+pub fn is_dirty(path: &Path) -> Result<bool> {
+    let output = Command::new("git")
+        .args(["status", "--porcelain"])
+        .current_dir(path)
+        .output()
+        .context("Failed to execute git")?;
+
+    if !output.status.success() {
+        bail!("Failed to get status of {}", path.display());
+    }
 
-    Ok(worktrees)
+    Ok(!output.stdout.is_empty())
 }
 
 /// Find existing worktree for a branch.
@@ -195,8 +344,9 @@ fn branch_to_dirname(branch: &str) -> String {
 /// Ensure a worktree exists for a branch, creating if necessary.
 ///
 /// If the branch doesn't exist, it will be created from the default branch.
-/// The worktree is created as a sibling directory to the repository.
-pub fn ensure_worktree(path: &Path, branch: &str) -> Result<WorktreeInfo> {
+/// The worktree is created as a sibling directory to the repository, and the
+/// `copy` entries (the manifest's `worktree.copy`) are brought into it.
+pub fn ensure_worktree(path: &Path, branch: &str, copy: &[WorktreeCopy]) -> Result<WorktreeInfo> {
     let repo_root = repo_root(path)?;
     let repo_name = repo_name(path)?;
 
@@ -209,6 +359,7 @@ pub fn ensure_worktree(path: &Path, branch: &str) -> Result<WorktreeInfo> {
                 branch: branch.to_string(),
                 created: false,
                 branch_created: false,
+                copied: Vec::new(),
             });
         } else {
             // Worktree reference exists but directory is gone - prune stale references
@@ -289,13 +440,18 @@ pub fn ensure_worktree(path: &Path, branch: &str) -> Result<WorktreeInfo> {
         }
     }
 
-    // Symlink AXEL.md if it exists in main repo but not in worktree
-    let main_manifest = repo_root.join("AXEL.md");
-    let worktree_manifest = worktree_path.join("AXEL.md");
-    if main_manifest.exists() && !worktree_manifest.exists() {
-        #[cfg(unix)]
-        {
-            std::os::unix::fs::symlink(&main_manifest, &worktree_manifest).ok();
+    // Symlink the manifest if it exists in main repo but not in worktree
+    if let Some(main_manifest) = find_manifest(&repo_root)
+        && let Some(name) = main_manifest.file_name()
+        && find_manifest(&worktree_path).is_none()
+    {
+        platform::symlink(&main_manifest, &worktree_path.join(name)).ok();
+    }
+
+    let mut copied = Vec::new();
+    for entry in copy {
+        if entry.apply(&repo_root, &worktree_path)? {
+            copied.push(entry.path.clone());
         }
     }
 
@@ -304,6 +460,7 @@ pub fn ensure_worktree(path: &Path, branch: &str) -> Result<WorktreeInfo> {
         branch: branch.to_string(),
         created: true,
         branch_created,
+        copied,
     })
 }
 
@@ -311,11 +468,16 @@ pub fn ensure_worktree(path: &Path, branch: &str) -> Result<WorktreeInfo> {
 ///
 /// If `force` is true, removes even if there are uncommitted changes.
 pub fn remove_worktree(path: &Path, branch: &str, force: bool) -> Result<bool> {
-    let worktree_path = match find_worktree(path, branch)? {
-        Some(p) => p,
-        None => return Ok(false),
-    };
+    match find_worktree(path, branch)? {
+        Some(worktree_path) => remove_worktree_at(path, &worktree_path, force),
+        None => Ok(false),
+    }
+}
 
+/// Remove the worktree at `worktree_path`.
+///
+/// If `force` is true, removes even if there are uncommitted changes.
+pub fn remove_worktree_at(path: &Path, worktree_path: &Path, force: bool) -> Result<bool> {
     let mut args = vec!["worktree", "remove"];
     if force {
         args.push("--force");
@@ -351,4 +513,77 @@ mod tests {
         assert_eq!(branch_to_dirname("fix/bug-123"), "fix-bug-123");
         assert_eq!(branch_to_dirname("main"), "main");
     }
+
+    #[test]
+    fn test_parse_worktrees() {
+        let porcelain = "worktree /code/app\nHEAD 1111\nbranch refs/heads/main\n\n\
+                         worktree /code/app-feat-auth\nHEAD 2222\nbranch refs/heads/feat/auth\n\n\
+                         worktree /code/app-detached\nHEAD 3333\ndetached\n\n\
+                         worktree /code/app-gone\nHEAD 4444\nbranch refs/heads/gone\n\
+                         prunable gitdir file points to non-existent location\n";
+        let worktrees = parse_worktrees(porcelain);
+
+        assert_eq!(worktrees.len(), 4);
+        assert!(worktrees[0].main);
+        assert_eq!(worktrees[0].branch.as_deref(), Some("main"));
+        assert_eq!(worktrees[1].path, PathBuf::from("/code/app-feat-auth"));
+        assert_eq!(worktrees[1].branch.as_deref(), Some("feat/auth"));
+        assert_eq!(worktrees[1].head, "2222");
+        assert!(!worktrees[1].main && !worktrees[1].prunable);
+        assert_eq!(worktrees[2].branch, None);
+        assert!(worktrees[3].prunable);
+    }
+
+    #[test]
+    fn test_parse_status() {
+        let porcelain = "# branch.oid 1111\n# branch.head feat/auth\n\
+                         # branch.upstream origin/feat/auth\n# branch.ab +2 -1\n\
+                         1 .M N... 100644 100644 100644 aaa bbb src/lib.rs\n\
+                         ? notes.txt\n";
+        assert_eq!(
+            parse_status(porcelain),
+            RepoStatus {
+                branch: "feat/auth".to_string(),
+                ahead: 2,
+                behind: 1,
+                dirty: 2,
+            }
+        );
+
+        // No upstream, clean
+        let status = parse_status("# branch.oid 1111\n# branch.head main\n");
+        assert_eq!((status.ahead, status.behind, status.dirty), (0, 0, 0));
+    }
+
+    #[test]
+    fn test_ensure_available_without_git_on_path() {
+        let dir = std::env::temp_dir().join("axel-test-no-git");
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let err = ensure_available_in(Some(dir.as_os_str())).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "git not found; worktree features require git"
+        );
+        assert!(ensure_available_in(None).is_err());
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_ensure_available_with_git_stub() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = std::env::temp_dir().join("axel-test-git-stub");
+        std::fs::create_dir_all(&dir).unwrap();
+        let stub = dir.join("git");
+        std::fs::write(&stub, "#!/bin/sh\n").unwrap();
+        std::fs::set_permissions(&stub, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        let path = std::env::join_paths([Path::new("/nonexistent"), &dir]).unwrap();
+        assert!(ensure_available_in(Some(&path)).is_ok());
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
 }
@@ -0,0 +1,238 @@
+//! GitHub pull request checkout for axel workspaces.
+//!
+//! Launching from a pull request reads it with the GitHub CLI (`gh`),
+//! fetches its head into a local branch and opens that branch in a
+//! worktree, so the workspace starts on the PR's code:
+//!
+//! ```bash
+//! axel pr 1234         # Same as `axel -w pr/1234`
+//! ```
+//!
+//! A PR from the same repository checks out its own branch, tracking the
+//! remote one. A PR from a fork is fetched from `pull/<n>/head` into a
+//! local `pr/<n>` branch.
+
+use std::{path::Path, process::Command};
+
+use anyhow::{Context, Result, bail};
+use serde::Deserialize;
+
+use crate::{
+    config::WorktreeCopy,
+    git::{self, WorktreeInfo},
+};
+
+/// Remote pull requests are fetched from
+const REMOTE: &str = "origin";
+
+/// Changed files listed in the prompt before the rest are summarized
+const MAX_PROMPT_FILES: usize = 50;
+
+/// Fields requested from `gh pr view --json`
+const PR_FIELDS: &str =
+    "number,title,body,url,headRefName,baseRefName,isCrossRepository,additions,deletions,files";
+
+/// A pull request as reported by `gh pr view`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PullRequest {
+    pub number: u64,
+    pub title: String,
+    #[serde(default)]
+    pub body: String,
+    pub url: String,
+    /// Branch the PR was opened from
+    pub head_ref_name: String,
+    /// Branch the PR merges into
+    pub base_ref_name: String,
+    /// Whether the head branch lives in a fork
+    #[serde(default)]
+    pub is_cross_repository: bool,
+    #[serde(default)]
+    pub additions: u64,
+    #[serde(default)]
+    pub deletions: u64,
+    #[serde(default)]
+    pub files: Vec<PullRequestFile>,
+}
+
+/// A file changed by a pull request
+#[derive(Debug, Clone, Deserialize)]
+pub struct PullRequestFile {
+    pub path: String,
+    #[serde(default)]
+    pub additions: u64,
+    #[serde(default)]
+    pub deletions: u64,
+}
+
+/// Parse a pull request reference: `1234`, `#1234`, `pr/1234` or a PR URL.
+pub fn parse_pr_number(reference: &str) -> Option<u64> {
+    let reference = reference.trim().trim_end_matches('/');
+    let number = if let Some((_, number)) = reference.rsplit_once("/pull/") {
+        number
+    } else {
+        reference
+            .strip_prefix("pr/")
+            .or_else(|| reference.strip_prefix('#'))
+            .unwrap_or(reference)
+    };
+    number.parse().ok()
+}
+
+/// Parse a `-w` branch of the form `pr/<number>`.
+pub fn pr_branch_number(branch: &str) -> Option<u64> {
+    branch.strip_prefix("pr/")?.parse().ok()
+}
+
+/// Read pull request `number` of the repository at `path` with `gh`.
+pub fn fetch_pull_request(path: &Path, number: u64) -> Result<PullRequest> {
+    let output = Command::new("gh")
+        .args(["pr", "view", &number.to_string(), "--json", PR_FIELDS])
+        .current_dir(path)
+        .output()
+        .map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => {
+                anyhow::anyhow!(
+                    "gh not found; launching from a pull request requires the GitHub CLI"
+                )
+            }
+            _ => anyhow::Error::new(e).context("Failed to execute gh"),
+        })?;
+
+    if !output.status.success() {
+        bail!(
+            "Failed to read pull request #{}: {}",
+            number,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    serde_json::from_slice(&output.stdout).context("Failed to parse `gh pr view` output")
+}
+
+impl PullRequest {
+    /// Local branch the pull request is checked out on
+    pub fn branch(&self) -> String {
+        if self.is_cross_repository {
+            format!("pr/{}", self.number)
+        } else {
+            self.head_ref_name.clone()
+        }
+    }
+
+    /// Fetch the PR head and open its branch in a worktree, bringing the
+    /// `copy` entries into it like [`git::ensure_worktree`].
+    ///
+    /// An existing local branch is left as is, so local commits on it are
+    /// never overwritten.
+    pub fn checkout(&self, path: &Path, copy: &[WorktreeCopy]) -> Result<WorktreeInfo> {
+        let branch = self.branch();
+        if !git::branch_exists_local(path, &branch) {
+            let refspec = if self.is_cross_repository {
+                format!("pull/{}/head:{}", self.number, branch)
+            } else {
+                branch.clone()
+            };
+            let status = Command::new("git")
+                .args(["fetch", REMOTE, &refspec])
+                .current_dir(path)
+                .status()
+                .context("Failed to execute git")?;
+
+            if !status.success() {
+                bail!("Failed to fetch pull request #{}", self.number);
+            }
+        }
+
+        git::ensure_worktree(path, &branch, copy)
+    }
+
+    /// Initial prompt describing the pull request for the AI panes
+    pub fn to_prompt(&self) -> String {
+        let mut prompt = format!(
+            "You are working on pull request #{}: {}\n{}\n\nIt merges `{}` into `{}`.",
+            self.number, self.title, self.url, self.head_ref_name, self.base_ref_name
+        );
+
+        let body = self.body.trim();
+        if !body.is_empty() {
+            prompt.push_str("\n\n## Description\n\n");
+            prompt.push_str(body);
+        }
+
+        if !self.files.is_empty() {
+            prompt.push_str(&format!(
+                "\n\n## Changes\n\n{} file{} changed, +{} -{}\n",
+                self.files.len(),
+                if self.files.len() == 1 { "" } else { "s" },
+                self.additions,
+                self.deletions
+            ));
+            for file in self.files.iter().take(MAX_PROMPT_FILES) {
+                prompt.push_str(&format!(
+                    "\n- {} (+{} -{})",
+                    file.path, file.additions, file.deletions
+                ));
+            }
+            if self.files.len() > MAX_PROMPT_FILES {
+                prompt.push_str(&format!(
+                    "\n- ... and {} more",
+                    self.files.len() - MAX_PROMPT_FILES
+                ));
+            }
+        }
+
+        prompt
+    }
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_pr_number() {
+        assert_eq!(parse_pr_number("1234"), Some(1234));
+        assert_eq!(parse_pr_number("#1234"), Some(1234));
+        assert_eq!(parse_pr_number("pr/1234"), Some(1234));
+        assert_eq!(
+            parse_pr_number("https://github.com/txtx/axel/pull/1234/"),
+            Some(1234)
+        );
+        assert_eq!(parse_pr_number("feat/auth"), None);
+
+        assert_eq!(pr_branch_number("pr/42"), Some(42));
+        assert_eq!(pr_branch_number("pr/fix-login"), None);
+        assert_eq!(pr_branch_number("42"), None);
+    }
+
+    #[test]
+    fn test_pull_request_prompt_and_branch() {
+        let json = r#"{
+            "number": 7, "title": "Add login", "body": "Adds the login page.\n",
+            "url": "https://github.com/acme/app/pull/7",
+            "headRefName": "feat/login", "baseRefName": "main",
+            "isCrossRepository": false, "additions": 12, "deletions": 3,
+            "files": [{"path": "src/login.rs", "additions": 12, "deletions": 3}]
+        }"#;
+        let mut pr: PullRequest = serde_json::from_str(json).unwrap();
+
+        assert_eq!(pr.branch(), "feat/login");
+        assert_eq!(
+            pr.to_prompt(),
+            "You are working on pull request #7: Add login\n\
+             https://github.com/acme/app/pull/7\n\n\
+             It merges `feat/login` into `main`.\n\n\
+             ## Description\n\nAdds the login page.\n\n\
+             ## Changes\n\n1 file changed, +12 -3\n\n- src/login.rs (+12 -3)"
+        );
+
+        pr.is_cross_repository = true;
+        assert_eq!(pr.branch(), "pr/7");
+    }
+}
@@ -6,6 +6,8 @@
 mod settings;
 
 pub use settings::{
-    ClaudeSettings, Hook, HookMatcher, HooksConfig, generate_hooks_settings, otel_logs_endpoint,
-    otel_metrics_endpoint, otel_traces_endpoint, settings_path, write_settings,
+    ClaudeHooksConfig, ClaudeSettings, HOOK_MARKER, Hook, HookMatcher, HooksConfig,
+    OPTIONAL_HOOK_EVENTS, REQUIRED_HOOK_EVENTS, events_endpoint, generate_hooks_settings,
+    otel_logs_endpoint, otel_metrics_endpoint, otel_traces_endpoint, remove_hooks_settings,
+    settings_path, write_settings,
 };
@@ -1,15 +1,40 @@
 //! Claude settings.json generator for hook configuration.
+//!
+//! The workspace's `.claude/settings.json` is also the project's own, so
+//! axel merges into it rather than replacing it:
+//!
+//! - axel's hooks end with [`HOOK_MARKER`]. Writing the settings again
+//!   replaces them and leaves every other hook alone.
+//! - Other settings (`env`) are merged key by key. The value each key had
+//!   before is kept in `.settings.json.axel` next to the file.
+//!
+//! [`remove_hooks_settings`] undoes both: it removes axel's hooks and puts
+//! back the previous values, deleting the file if axel created it.
 
-use std::path::Path;
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// Command of axel's hooks, followed by the events endpoint
+const HOOK_COMMAND_PREFIX: &str = "curl -s -X POST -H 'Content-Type: application/json' -d @- ";
+
+/// Shell comment ending the command of axel's hooks, which tells them apart
+/// from the project's
+pub const HOOK_MARKER: &str = "# axel-hook";
 
 /// Claude Code settings.json structure
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ClaudeSettings {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub hooks: Option<HooksConfig>,
+    /// Environment variables set for the session
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub env: BTreeMap<String, String>,
 }
 
 /// Hooks configuration
@@ -30,6 +55,49 @@ pub struct HooksConfig {
     pub subagent_stop: Option<Vec<HookMatcher>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub permission_request: Option<Vec<HookMatcher>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notification: Option<Vec<HookMatcher>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pre_compact: Option<Vec<HookMatcher>>,
+}
+
+/// Hook events every Claude pane reports: permissions, budgets and the
+/// orchestrator's routing depend on them
+pub const REQUIRED_HOOK_EVENTS: &[&str] =
+    &["PreToolUse", "PostToolUse", "Stop", "PermissionRequest"];
+
+/// Hook events reported unless the manifest's `hooks.events` leaves them out
+pub const OPTIONAL_HOOK_EVENTS: &[&str] = &[
+    "Notification",
+    "SubagentStop",
+    "PreCompact",
+    "SessionStart",
+    "SessionEnd",
+];
+
+/// Which optional hook events Claude panes report (`hooks:` in the manifest)
+///
+/// ```yaml
+/// hooks:
+///   events: [SessionStart, SessionEnd]   # default: all of OPTIONAL_HOOK_EVENTS
+/// ```
+#[derive(Debug, Clone, Deserialize)]
+pub struct ClaudeHooksConfig {
+    /// Optional events to register, out of [`OPTIONAL_HOOK_EVENTS`]
+    #[serde(default = "default_hook_events")]
+    pub events: Vec<String>,
+}
+
+impl Default for ClaudeHooksConfig {
+    fn default() -> Self {
+        Self {
+            events: default_hook_events(),
+        }
+    }
+}
+
+fn default_hook_events() -> Vec<String> {
+    OPTIONAL_HOOK_EVENTS.iter().map(|e| e.to_string()).collect()
 }
 
 /// Hook matcher configuration
@@ -49,40 +117,56 @@ pub struct Hook {
     pub timeout: Option<u32>,
 }
 
-/// Generate Claude settings with hooks that POST events to the axel server
-pub fn generate_hooks_settings(port: u16, pane_id: &str) -> ClaudeSettings {
-    let endpoint = format!("http://localhost:{}/events/{}", port, pane_id);
+/// Generate Claude settings with hooks that POST events to the axel server.
+///
+/// The [`REQUIRED_HOOK_EVENTS`] are always registered, along with the
+/// optional events `config` selects.
+pub fn generate_hooks_settings(
+    port: u16,
+    pane_id: &str,
+    config: &ClaudeHooksConfig,
+) -> ClaudeSettings {
+    let endpoint = events_endpoint(port, pane_id);
 
     // Create a curl command that reads from stdin and POSTs to the endpoint
-    let curl_command = format!(
-        "curl -s -X POST -H 'Content-Type: application/json' -d @- {}",
-        endpoint
-    );
-
-    let create_hook = |_event_type: &str| -> Vec<HookMatcher> {
-        vec![HookMatcher {
-            matcher: "*".to_string(),
-            hooks: vec![Hook {
-                hook_type: "command".to_string(),
-                command: curl_command.clone(),
-                timeout: Some(5),
-            }],
-        }]
+    let curl_command = format!("{}{} {}", HOOK_COMMAND_PREFIX, endpoint, HOOK_MARKER);
+
+    let create_hook = |event_type: &str| -> Option<Vec<HookMatcher>> {
+        let enabled = REQUIRED_HOOK_EVENTS.contains(&event_type)
+            || config.events.iter().any(|e| e == event_type);
+        enabled.then(|| {
+            vec![HookMatcher {
+                matcher: "*".to_string(),
+                hooks: vec![Hook {
+                    hook_type: "command".to_string(),
+                    command: curl_command.clone(),
+                    timeout: Some(5),
+                }],
+            }]
+        })
     };
 
     ClaudeSettings {
         hooks: Some(HooksConfig {
-            pre_tool_use: Some(create_hook("PreToolUse")),
-            post_tool_use: Some(create_hook("PostToolUse")),
-            session_start: Some(create_hook("SessionStart")),
-            session_end: Some(create_hook("SessionEnd")),
-            stop: Some(create_hook("Stop")),
-            subagent_stop: Some(create_hook("SubagentStop")),
-            permission_request: Some(create_hook("PermissionRequest")),
+            pre_tool_use: create_hook("PreToolUse"),
+            post_tool_use: create_hook("PostToolUse"),
+            session_start: create_hook("SessionStart"),
+            session_end: create_hook("SessionEnd"),
+            stop: create_hook("Stop"),
+            subagent_stop: create_hook("SubagentStop"),
+            permission_request: create_hook("PermissionRequest"),
+            notification: create_hook("Notification"),
+            pre_compact: create_hook("PreCompact"),
         }),
+        env: BTreeMap::new(),
     }
 }
 
+/// Get the hook events endpoint URL for a pane
+pub fn events_endpoint(port: u16, pane_id: &str) -> String {
+    format!("http://localhost:{}/events/{}", port, pane_id)
+}
+
 /// Get the OTEL exporter metrics endpoint URL with pane_id
 /// Returns the full URL for OTEL_EXPORTER_OTLP_METRICS_ENDPOINT
 pub fn otel_metrics_endpoint(port: u16, pane_id: &str) -> String {
@@ -102,6 +186,10 @@ pub fn otel_logs_endpoint(port: u16, pane_id: &str) -> String {
 }
 
 /// Write the Claude settings to a file
+///
+/// An existing file keeps its settings and hooks: axel's hooks replace the
+/// ones it wrote before, and its other settings are merged in, recording
+/// the values they replace so [`remove_hooks_settings`] can restore them.
 pub fn write_settings(settings: &ClaudeSettings, path: &Path) -> Result<()> {
     // Ensure parent directory exists
     if let Some(parent) = path.parent() {
@@ -109,29 +197,374 @@ pub fn write_settings(settings: &ClaudeSettings, path: &Path) -> Result<()> {
     }
 
     // Check if there's an existing settings file
-    let final_settings = if path.exists() {
-        // Read existing settings
+    let existed = path.exists();
+    let mut existing = if existed {
         let content = std::fs::read_to_string(path)?;
-        let mut existing: serde_json::Value = serde_json::from_str(&content)?;
+        serde_json::from_str(&content)
+            .with_context(|| format!("Invalid settings in {}", path.display()))?
+    } else {
+        Value::Object(Map::new())
+    };
+    let Some(existing_map) = existing.as_object_mut() else {
+        anyhow::bail!("{} is not a JSON object", path.display());
+    };
 
-        // Merge hooks into existing settings
-        if let Some(hooks) = &settings.hooks {
-            existing["hooks"] = serde_json::to_value(hooks)?;
-        }
+    // The file's state before axel's first write, kept until cleanup. A
+    // file the project already had is never removed, even if it was empty.
+    let previous_path = previous_values_path(path);
+    let mut previous = load_previous_values(&previous_path);
+    if previous.is_empty() && !existed {
+        previous.insert(String::new(), None);
+    }
 
-        existing
-    } else {
-        serde_json::to_value(settings)?
+    let Value::Object(mut overlay) = serde_json::to_value(settings)? else {
+        unreachable!("settings serialize to an object");
     };
+    let hooks = overlay.remove("hooks");
+    merge_values(existing_map, overlay, "", &mut previous);
+
+    if let Some(Value::Object(hooks)) = hooks {
+        remove_axel_hooks(&mut existing);
+        let existing_hooks = existing
+            .as_object_mut()
+            .expect("checked above")
+            .entry("hooks")
+            .or_insert_with(|| Value::Object(Map::new()));
+        if let Some(existing_hooks) = existing_hooks.as_object_mut() {
+            for (event, matchers) in hooks {
+                let Value::Array(matchers) = matchers else {
+                    continue;
+                };
+                match existing_hooks.get_mut(&event).and_then(Value::as_array_mut) {
+                    Some(existing) => existing.extend(matchers),
+                    None => {
+                        existing_hooks.insert(event, Value::Array(matchers));
+                    }
+                }
+            }
+        }
+    }
 
     // Write the settings
-    let json = serde_json::to_string_pretty(&final_settings)?;
+    let json = serde_json::to_string_pretty(&existing)?;
     std::fs::write(path, json)?;
+    if !previous.is_empty() {
+        std::fs::write(&previous_path, serde_json::to_string_pretty(&previous)?)?;
+    }
 
     Ok(())
 }
 
+/// Remove axel's hooks from the settings file at `path` and restore the
+/// settings it replaced. The file is removed if axel created it and nothing
+/// else was added since.
+///
+/// Returns true if anything was removed or restored.
+pub fn remove_hooks_settings(path: &Path) -> bool {
+    let previous_path = previous_values_path(path);
+    let previous = load_previous_values(&previous_path);
+    let Some(mut settings) = std::fs::read_to_string(path)
+        .ok()
+        .and_then(|content| serde_json::from_str::<Value>(&content).ok())
+    else {
+        std::fs::remove_file(&previous_path).ok();
+        return false;
+    };
+
+    let mut changed = remove_axel_hooks(&mut settings);
+    let created = previous.contains_key("");
+    for (pointer, value) in previous {
+        if !pointer.is_empty() && restore_value(&mut settings, &pointer, value) {
+            changed = true;
+        }
+    }
+    std::fs::remove_file(&previous_path).ok();
+    if !changed {
+        return false;
+    }
+
+    if created && settings.as_object().is_some_and(|s| s.is_empty()) {
+        std::fs::remove_file(path).is_ok()
+    } else {
+        serde_json::to_string_pretty(&settings).is_ok_and(|json| std::fs::write(path, json).is_ok())
+    }
+}
+
+/// Where the values replaced in the settings file at `path` are kept:
+/// JSON pointers to the value they had, `null` for keys axel added. The
+/// empty pointer records that axel created the file.
+fn previous_values_path(path: &Path) -> PathBuf {
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    path.with_file_name(format!(".{}.axel", name))
+}
+
+/// Values recorded by earlier writes, empty if there are none
+fn load_previous_values(path: &Path) -> BTreeMap<String, Option<Value>> {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+/// Merge `overlay` into `base`, object by object. Each value replaced or
+/// added is recorded in `previous` under its JSON pointer, unless an
+/// earlier write already recorded it.
+fn merge_values(
+    base: &mut Map<String, Value>,
+    overlay: Map<String, Value>,
+    pointer: &str,
+    previous: &mut BTreeMap<String, Option<Value>>,
+) {
+    for (key, value) in overlay {
+        let key_pointer = format!("{}/{}", pointer, key.replace('~', "~0").replace('/', "~1"));
+        match (base.get_mut(&key), value) {
+            (Some(Value::Object(base)), Value::Object(value)) => {
+                merge_values(base, value, &key_pointer, previous);
+            }
+            (current, value) => {
+                if current.as_deref() == Some(&value) {
+                    continue;
+                }
+                previous
+                    .entry(key_pointer)
+                    .or_insert_with(|| current.cloned());
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+/// Put back the value `pointer` had before axel's first write, removing it
+/// if it didn't exist. Returns true if the settings changed.
+fn restore_value(settings: &mut Value, pointer: &str, value: Option<Value>) -> bool {
+    let Some((parent, key)) = pointer.rsplit_once('/') else {
+        return false;
+    };
+    let key = key.replace("~1", "/").replace("~0", "~");
+    let Some(parent) = settings.pointer_mut(parent).and_then(Value::as_object_mut) else {
+        return false;
+    };
+    match value {
+        Some(value) => parent.insert(key, value.clone()).as_ref() != Some(&value),
+        None => parent.remove(&key).is_some(),
+    }
+}
+
+/// Whether `hook` is one of axel's, posting events to the axel server.
+/// Hooks written before [`HOOK_MARKER`] are recognized by their command.
+fn is_axel_hook(hook: &Value) -> bool {
+    hook.get("command")
+        .and_then(Value::as_str)
+        .is_some_and(|command| {
+            command.ends_with(HOOK_MARKER)
+                || command
+                    .strip_prefix(HOOK_COMMAND_PREFIX)
+                    .is_some_and(|endpoint| {
+                        endpoint.starts_with("http://localhost:") && endpoint.contains("/events/")
+                    })
+        })
+}
+
+/// Remove axel's hooks from `settings`, along with the matchers, events and
+/// `hooks` object they leave empty.
+///
+/// Returns true if any were removed.
+fn remove_axel_hooks(settings: &mut Value) -> bool {
+    let Some(events) = settings.get_mut("hooks").and_then(Value::as_object_mut) else {
+        return false;
+    };
+    let mut removed = false;
+    for matchers in events.values_mut().filter_map(Value::as_array_mut) {
+        matchers.retain_mut(|matcher| {
+            let Some(hooks) = matcher.get_mut("hooks").and_then(Value::as_array_mut) else {
+                return true;
+            };
+            let before = hooks.len();
+            hooks.retain(|hook| !is_axel_hook(hook));
+            if hooks.len() == before {
+                return true;
+            }
+            removed = true;
+            !hooks.is_empty()
+        });
+    }
+    events.retain(|_, matchers| !matchers.as_array().is_some_and(|m| m.is_empty()));
+    if events.is_empty()
+        && let Some(settings) = settings.as_object_mut()
+    {
+        settings.remove("hooks");
+    }
+    removed
+}
+
 /// Get the path to the Claude settings file in a workspace
 pub fn settings_path(workspace_dir: &Path) -> std::path::PathBuf {
     workspace_dir.join(".claude").join("settings.json")
 }
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_write_settings_keeps_user_hooks() {
+        let dir = std::env::temp_dir().join("axel-test-hooks-settings");
+        std::fs::remove_dir_all(&dir).ok();
+        let path = settings_path(&dir);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        let user_hook = json!({
+            "matcher": "Bash",
+            "hooks": [{"type": "command", "command": "./lint.sh"}]
+        });
+        let user_settings = json!({
+            "permissions": {"allow": ["Bash(make:*)"]},
+            "env": {"RUST_LOG": "info"},
+            "hooks": {"PreToolUse": [user_hook]}
+        });
+        std::fs::write(&path, user_settings.to_string()).unwrap();
+
+        // Writing twice (a new port) leaves a single set of axel's hooks
+        let mut settings = generate_hooks_settings(4000, "pane", &ClaudeHooksConfig::default());
+        settings
+            .env
+            .insert("RUST_LOG".to_string(), "debug".to_string());
+        settings
+            .env
+            .insert("AXEL_PANE".to_string(), "pane".to_string());
+        write_settings(&settings, &path).unwrap();
+        let mut settings = generate_hooks_settings(4001, "pane", &ClaudeHooksConfig::default());
+        settings
+            .env
+            .insert("RUST_LOG".to_string(), "trace".to_string());
+        write_settings(&settings, &path).unwrap();
+
+        let written: Value =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(written["permissions"], user_settings["permissions"]);
+        assert_eq!(
+            written["env"],
+            json!({"RUST_LOG": "trace", "AXEL_PANE": "pane"})
+        );
+        let pre_tool_use = written["hooks"]["PreToolUse"].as_array().unwrap();
+        assert_eq!(pre_tool_use.len(), 2);
+        assert_eq!(pre_tool_use[0], user_hook);
+        assert_eq!(
+            pre_tool_use[1]["hooks"][0]["command"],
+            format!(
+                "{}{} {}",
+                HOOK_COMMAND_PREFIX,
+                events_endpoint(4001, "pane"),
+                HOOK_MARKER
+            )
+        );
+        assert_eq!(written["hooks"]["Stop"].as_array().unwrap().len(), 1);
+        assert_eq!(written["hooks"]["PreCompact"].as_array().unwrap().len(), 1);
+
+        // Cleanup restores the file as it was before axel's first write
+        assert!(remove_hooks_settings(&path));
+        let restored: Value =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(restored, user_settings);
+        assert!(!previous_values_path(&path).exists());
+        assert!(!remove_hooks_settings(&path));
+
+        // Optional events can be left out, the required ones can't
+        let config = ClaudeHooksConfig {
+            events: vec!["Notification".to_string()],
+        };
+        let hooks =
+            serde_json::to_value(generate_hooks_settings(4000, "pane", &config).hooks).unwrap();
+        let mut events: Vec<&str> = hooks
+            .as_object()
+            .unwrap()
+            .keys()
+            .map(|k| k.as_str())
+            .collect();
+        events.sort();
+        assert_eq!(
+            events,
+            [
+                "Notification",
+                "PermissionRequest",
+                "PostToolUse",
+                "PreToolUse",
+                "Stop"
+            ]
+        );
+
+        // A file axel created is removed
+        std::fs::remove_file(&path).unwrap();
+        write_settings(
+            &generate_hooks_settings(4000, "pane", &ClaudeHooksConfig::default()),
+            &path,
+        )
+        .unwrap();
+        assert!(remove_hooks_settings(&path));
+        assert!(!path.exists());
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_write_settings_is_idempotent() {
+        let dir = std::env::temp_dir().join("axel-test-hooks-settings-idempotent");
+        std::fs::remove_dir_all(&dir).ok();
+        let path = settings_path(&dir);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, r#"{"env": {"RUST_LOG": "info"}}"#).unwrap();
+
+        let mut settings = generate_hooks_settings(4000, "pane", &ClaudeHooksConfig::default());
+        settings
+            .env
+            .insert("RUST_LOG".to_string(), "debug".to_string());
+        write_settings(&settings, &path).unwrap();
+        let written = std::fs::read_to_string(&path).unwrap();
+        let previous = std::fs::read_to_string(previous_values_path(&path)).unwrap();
+
+        // The same settings again change neither file
+        write_settings(&settings, &path).unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), written);
+        assert_eq!(
+            std::fs::read_to_string(previous_values_path(&path)).unwrap(),
+            previous
+        );
+
+        // and cleanup still restores the value from before the first write
+        assert!(remove_hooks_settings(&path));
+        let restored: Value =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(restored, json!({"env": {"RUST_LOG": "info"}}));
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_remove_hooks_settings_keeps_empty_user_file() {
+        let dir = std::env::temp_dir().join("axel-test-hooks-settings-empty");
+        std::fs::remove_dir_all(&dir).ok();
+        let path = settings_path(&dir);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, "{}").unwrap();
+
+        write_settings(
+            &generate_hooks_settings(4000, "pane", &ClaudeHooksConfig::default()),
+            &path,
+        )
+        .unwrap();
+        assert!(remove_hooks_settings(&path));
+        let restored: Value =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(restored, json!({}));
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
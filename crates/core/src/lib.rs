@@ -2,27 +2,70 @@
 //!
 //! This crate provides the core functionality for axel including:
 //! - Configuration parsing and types
+//! - Typed errors for failures callers handle
 //! - Tmux session management
+//! - Multiplexer backends (tmux, Zellij)
 //! - Skill driver implementations
 //! - Claude command building
 //! - Git worktree management
+//! - GitHub pull request checkout
 //! - Claude hooks configuration
+//! - Orchestration of agent replies between role panes
+//! - Token, cost and turn budgets for AI panes
+//! - Detection of AI panes stuck mid-task
+//! - Policies approving or denying agents' tool calls
+//! - Audit of the files agents change, from git diffs
+//! - Checkpoint commits of agents' turns, to roll back a bad one
+//! - Shell quoting for built commands
+//! - Unix/Windows differences (exec, symlinks, the user's shell)
+//! - Terminal launchers for attaching in a new window
+//! - Transcripts of AI pane output and asciicast recordings
+//! - Environment checks for `axel doctor`
+//! - Headless grid runs and reports for `axel ci`
+//! - Fleets of workspaces started together by `axel up`
+//! - Registry of known workspaces for `axel open`
+//! - Launch phase timing
 
+pub mod audit;
+pub mod backend;
+pub mod budget;
+pub mod checkpoint;
+pub mod ci;
 pub mod claude;
 pub mod config;
+pub mod doctor;
 pub mod drivers;
+pub mod error;
+pub mod fleet;
 pub mod git;
+pub mod github;
 pub mod hooks;
+pub mod orchestrator;
+pub mod platform;
+pub mod policy;
+pub mod recording;
+pub mod registry;
 pub mod server;
+pub mod shell;
+pub mod stuck;
+pub mod terminal;
+pub mod timing;
 pub mod tmux;
+pub mod transcript;
 
 // Re-export commonly used types at crate root
 pub use config::{
-    AiPaneConfig, CustomPaneConfig, Grid, GridCell, GridType, LayoutsConfig, PaneConfig,
-    ResolvedPane, Skill, SkillPathConfig, WorkspaceConfig, WorkspaceIndex,
+    AiPaneConfig, CustomPaneConfig, Grid, GridCell, GridType, LayoutsConfig, Note, PaneConfig,
+    ResolvedPane, ShellRun, Skill, SkillNode, SkillNodeStatus, SkillPathConfig, TmuxLayout,
+    WorkspaceConfig, WorkspaceIndex,
 };
-pub use drivers::{ClaudeDriver, CodexDriver, OpenCodeDriver, SkillDriver, all_skill_patterns};
+pub use drivers::{
+    ClaudeDriver, CodexDriver, OpenCodeDriver, SkillDriver, all_skill_patterns, driver_skills_dir,
+};
+pub use error::Error;
 pub use hooks::{
     generate_hooks_settings, otel_logs_endpoint, otel_metrics_endpoint, otel_traces_endpoint,
-    settings_path, write_settings,
+    remove_hooks_settings, settings_path, write_settings,
 };
+pub use terminal::Terminal;
+pub use timing::Timings;
@@ -0,0 +1,375 @@
+//! Routing agent replies between panes
+//!
+//! The manifest's `orchestrator:` section gives AI panes a role and chains
+//! the roles into a pipeline. When an agent finishes its turn, the event
+//! server takes its final reply and pastes it as the prompt of the pane
+//! playing the next role:
+//!
+//! ```yaml
+//! orchestrator:
+//!   roles:
+//!     planner: claude            # role: pane name
+//!     implementer: codex
+//!     reviewer: reviewer
+//!   pipeline:
+//!     - from: planner
+//!       to: implementer
+//!       prompt: "Implement this plan:\n\n{{output}}"
+//!     - from: implementer
+//!       to: reviewer
+//!       prompt: "Review the changes {{from}} just made:\n\n{{output}}"
+//!     - from: reviewer
+//!       to: implementer
+//! ```
+//!
+//! A step's `prompt` replaces `{{output}}` with the reply and `{{from}}`
+//! with the sending role; without one the reply is sent as is. A role may
+//! feed several steps, and steps may loop back to an earlier role.
+//!
+//! Launching the workspace points each role pane's end-of-turn hook at the
+//! event server under the pane's name (a hooks settings file for Claude, a
+//! `notify` program for Codex) and runs `axel server` for the session in a
+//! background `orchestrator` window. Panes with a `budget:` or `stuck:` are
+//! wired to the same server (see [`crate::budget`] and [`crate::stuck`]).
+
+use std::path::Path;
+
+use anyhow::Result;
+use indexmap::IndexMap;
+use serde::Deserialize;
+
+use crate::{
+    config::{PaneConfig, WorkspaceConfig},
+    drivers::get_driver,
+    hooks::{events_endpoint, generate_hooks_settings, write_settings},
+    server::TimestampedEvent,
+    shell,
+};
+
+/// Directory, relative to the workspace, holding the role panes' hook settings
+const HOOKS_DIR: &str = ".axel/hooks";
+
+/// `orchestrator:` section of the manifest
+#[derive(Debug, Clone, Deserialize)]
+pub struct OrchestratorConfig {
+    /// Port of the event server that routes replies
+    #[serde(default = "default_port")]
+    pub port: u16,
+    /// Pane name playing each role
+    pub roles: IndexMap<String, String>,
+    /// Where each role's replies go next
+    #[serde(default)]
+    pub pipeline: Vec<PipelineStep>,
+}
+
+fn default_port() -> u16 {
+    4318
+}
+
+/// One edge of the pipeline
+#[derive(Debug, Clone, Deserialize)]
+pub struct PipelineStep {
+    /// Role whose replies are routed
+    pub from: String,
+    /// Role receiving them
+    pub to: String,
+    /// Prompt template with `{{output}}` and `{{from}}` placeholders
+    #[serde(default)]
+    pub prompt: Option<String>,
+}
+
+impl PipelineStep {
+    /// The prompt sent to the `to` role for `output`
+    pub fn render(&self, output: &str) -> String {
+        match &self.prompt {
+            Some(template) => template
+                .replace("{{from}}", &self.from)
+                .replace("{{output}}", output),
+            None => output.to_string(),
+        }
+    }
+}
+
+/// A prompt to paste into a role's pane
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Delivery {
+    /// Role that produced the reply
+    pub from: String,
+    /// Role receiving the prompt
+    pub to: String,
+    /// Name of the receiving pane
+    pub pane: String,
+    pub prompt: String,
+}
+
+impl OrchestratorConfig {
+    /// The role played by the pane named `pane`
+    pub fn role_of(&self, pane: &str) -> Option<&str> {
+        self.roles
+            .iter()
+            .find(|(_, name)| *name == pane)
+            .map(|(role, _)| role.as_str())
+    }
+
+    /// Prompts to send for `event`.
+    ///
+    /// Only a `Stop` event from a role pane with a non-empty reply is
+    /// routed; it yields one delivery per pipeline step leaving that role.
+    pub fn route(&self, event: &TimestampedEvent) -> Vec<Delivery> {
+        if event.hook_name() != Some("Stop") {
+            return Vec::new();
+        }
+        let Some(from) = self.role_of(&event.pane_id) else {
+            return Vec::new();
+        };
+        let Some(output) = event.stop_reply().filter(|reply| !reply.trim().is_empty()) else {
+            return Vec::new();
+        };
+
+        self.pipeline
+            .iter()
+            .filter(|step| step.from == from)
+            .filter_map(|step| {
+                Some(Delivery {
+                    from: step.from.clone(),
+                    to: step.to.clone(),
+                    pane: self.roles.get(&step.to)?.clone(),
+                    prompt: step.render(&output),
+                })
+            })
+            .collect()
+    }
+}
+
+impl WorkspaceConfig {
+    /// Port of the event server the session runs in the background, when
+    /// it needs one: for the orchestrator, watched panes, policies or
+    /// checkpoints
+    pub fn event_server_port(&self) -> Option<u16> {
+        match &self.orchestrator {
+            Some(orchestrator) => Some(orchestrator.port),
+            None if !self.watched_panes().is_empty()
+                || !self.policed_panes().is_empty()
+                || !self.checkpointed_panes().is_empty() =>
+            {
+                Some(default_port())
+            }
+            None => None,
+        }
+    }
+
+    /// Names of the panes whose usage and activity the event server
+    /// watches: panes with a budget or a stuck threshold
+    pub fn watched_panes(&self) -> Vec<String> {
+        let mut names: Vec<String> = self
+            .budgets()
+            .into_keys()
+            .chain(self.stuck_thresholds().into_keys())
+            .collect();
+        names.sort();
+        names.dedup();
+        names
+    }
+
+    /// Report the end of each role pane's turns to the session's event
+    /// server, and the usage and activity of each watched pane.
+    ///
+    /// Events are posted to `/events/<pane name>`, so the server can tell
+    /// which role replied. Claude panes load a hooks settings file written
+    /// under `workspace_dir`; Codex panes get a `notify` program. Other
+    /// drivers have no end-of-turn hook and are left alone. Watched panes
+    /// also export OTEL telemetry under their name (through the settings
+    /// file's `env` for Claude, `-c` overrides for Codex). With `policies:`,
+    /// every Claude pane is wired so the server can decide its tool calls;
+    /// with `checkpoints: true`, every Claude and Codex pane so it can
+    /// checkpoint their turns.
+    pub fn wire_orchestrator(&mut self, workspace_dir: &Path) -> Result<()> {
+        let Some(port) = self.event_server_port() else {
+            return Ok(());
+        };
+        let watched = self.watched_panes();
+        let policed = self.policed_panes();
+        let checkpointed = self.checkpointed_panes();
+
+        for pane in &mut self.layouts.panes {
+            let name = pane.pane_type().to_string();
+            let is_role = self
+                .orchestrator
+                .as_ref()
+                .is_some_and(|o| o.role_of(&name).is_some());
+            let is_watched = watched.contains(&name);
+            if !is_role && !is_watched && !policed.contains(&name) && !checkpointed.contains(&name)
+            {
+                continue;
+            }
+            match pane {
+                PaneConfig::Claude(c) => {
+                    let path = workspace_dir.join(HOOKS_DIR).join(format!("{}.json", name));
+                    let mut settings = generate_hooks_settings(port, &name, &self.hooks);
+                    if is_watched && let Some(driver) = get_driver("claude") {
+                        settings.env = driver.otel_env_vars(port, &name).into_iter().collect();
+                    }
+                    write_settings(&settings, &path)?;
+                    c.args.push("--settings".to_string());
+                    c.args
+                        .push(shell::quote(&path.to_string_lossy()).into_owned());
+                }
+                PaneConfig::Codex(c) => {
+                    c.args.push("-c".to_string());
+                    c.args
+                        .push(shell::quote(&codex_notify(port, &name)).into_owned());
+                    if is_watched && let Some(driver) = get_driver("codex") {
+                        c.args.extend(
+                            driver
+                                .otel_cli_args(port, &name)
+                                .iter()
+                                .map(|arg| shell::quote(arg).into_owned()),
+                        );
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Codex `notify` setting posting turn completions to the server.
+///
+/// Codex appends the event JSON as the program's last argument, so the
+/// curl command ends with `-d` to send it as the body.
+fn codex_notify(port: u16, pane: &str) -> String {
+    format!(
+        r#"notify=["curl","-s","-X","POST","-H","Content-Type: application/json","{}","-d"]"#,
+        events_endpoint(port, pane)
+    )
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MANIFEST: &str = r#"
+workspace: test
+orchestrator:
+  roles:
+    planner: claude
+    implementer: codex
+    reviewer: reviewer
+  pipeline:
+    - from: planner
+      to: implementer
+      prompt: "Implement this plan from the {{from}}:\n\n{{output}}"
+    - from: implementer
+      to: reviewer
+layouts:
+  panes:
+    - type: claude
+    - type: codex
+    - type: claude
+      name: reviewer
+    - type: custom
+      name: server
+      command: pnpm dev
+"#;
+
+    fn stop(pane: &str, payload: serde_json::Value) -> TimestampedEvent {
+        TimestampedEvent::new("unknown_hook", pane, payload)
+    }
+
+    #[test]
+    fn test_route() {
+        let config: WorkspaceConfig = serde_yaml::from_str(MANIFEST).unwrap();
+        let orchestrator = config.orchestrator.unwrap();
+        assert_eq!(orchestrator.port, 4318);
+        assert_eq!(orchestrator.role_of("reviewer"), Some("reviewer"));
+
+        let planned = stop(
+            "claude",
+            serde_json::json!({"hook_event_name": "Stop", "last_assistant_message": "1. Do it"}),
+        );
+        assert_eq!(
+            orchestrator.route(&planned),
+            vec![Delivery {
+                from: "planner".to_string(),
+                to: "implementer".to_string(),
+                pane: "codex".to_string(),
+                prompt: "Implement this plan from the planner:\n\n1. Do it".to_string(),
+            }]
+        );
+
+        // Codex reports the end of a turn through `notify`
+        let implemented = stop(
+            "codex",
+            serde_json::json!({"type": "agent-turn-complete", "last-assistant-message": "Done"}),
+        );
+        let deliveries = orchestrator.route(&implemented);
+        assert_eq!(deliveries.len(), 1);
+        assert_eq!(
+            (deliveries[0].pane.as_str(), deliveries[0].prompt.as_str()),
+            ("reviewer", "Done")
+        );
+
+        // Not a Stop, the last role, an empty reply, or not a role at all
+        let tool = stop(
+            "claude",
+            serde_json::json!({"hook_event_name": "PreToolUse", "last_assistant_message": "x"}),
+        );
+        let reviewed = stop(
+            "reviewer",
+            serde_json::json!({"hook_event_name": "Stop", "last_assistant_message": "LGTM"}),
+        );
+        let empty = stop(
+            "claude",
+            serde_json::json!({"hook_event_name": "Stop", "last_assistant_message": " "}),
+        );
+        let server = stop(
+            "server",
+            serde_json::json!({"hook_event_name": "Stop", "last_assistant_message": "x"}),
+        );
+        for event in [tool, reviewed, empty, server] {
+            assert!(orchestrator.route(&event).is_empty(), "{:?}", event.event);
+        }
+    }
+
+    #[test]
+    fn test_wire_orchestrator() {
+        let dir = std::env::temp_dir().join("axel-test-wire-orchestrator");
+        std::fs::remove_dir_all(&dir).ok();
+
+        let mut config: WorkspaceConfig = serde_yaml::from_str(MANIFEST).unwrap();
+        config.wire_orchestrator(&dir).unwrap();
+        let args: Vec<&[String]> = config
+            .layouts
+            .panes
+            .iter()
+            .map(|pane| match pane {
+                PaneConfig::Claude(c) | PaneConfig::Codex(c) => c.args.as_slice(),
+                PaneConfig::Custom(_) => &[],
+                _ => unreachable!(),
+            })
+            .collect();
+
+        let settings = dir.join(".axel/hooks/reviewer.json");
+        assert_eq!(
+            args[2],
+            [
+                "--settings".to_string(),
+                settings.to_string_lossy().to_string()
+            ]
+        );
+        let written = std::fs::read_to_string(&settings).unwrap();
+        assert!(written.contains("http://localhost:4318/events/reviewer"));
+
+        assert_eq!(args[1][0], "-c");
+        assert!(args[1][1].starts_with("'notify=[\"curl\""));
+        assert!(args[1][1].ends_with("/events/codex\",\"-d\"]'"));
+        assert!(args[3].is_empty());
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
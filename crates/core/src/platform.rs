@@ -0,0 +1,84 @@
+//! Differences between Unix and Windows
+//!
+//! axel is built around tmux, but shell-mode grids, the Windows Terminal
+//! backend and skill management also work on Windows. The few places where
+//! the platforms differ go through these helpers:
+//!
+//! - Handing the terminal over to a pane's command: Unix replaces axel's
+//!   process with it; Windows has no `exec`, so axel runs the command and
+//!   exits with its status.
+//! - Links: skill files, index files and worktree manifests are symlinked.
+//!   Windows only allows symlinks in Developer Mode or as administrator, so
+//!   files and directories are copied when the link can't be made.
+//! - The interactive shell: `$SHELL` on Unix, `%COMSPEC%` on Windows.
+//!
+//! Pane commands are POSIX shell lines on every platform and run with
+//! `sh -c`; on Windows, `sh` comes from Git for Windows.
+
+use std::{io, path::Path, process::Command};
+
+/// Replace the current process with `command`, returning only on failure.
+///
+/// Without `exec` (Windows), `command` runs to completion and axel exits
+/// with its exit code.
+pub fn exec(command: &mut Command) -> io::Error {
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        command.exec()
+    }
+    #[cfg(not(unix))]
+    {
+        match command.status() {
+            Ok(status) => std::process::exit(status.code().unwrap_or(1)),
+            Err(e) => e,
+        }
+    }
+}
+
+/// The user's interactive shell
+pub fn user_shell() -> String {
+    #[cfg(unix)]
+    {
+        std::env::var("SHELL").unwrap_or_else(|_| "sh".to_string())
+    }
+    #[cfg(not(unix))]
+    {
+        std::env::var("COMSPEC").unwrap_or_else(|_| "cmd.exe".to_string())
+    }
+}
+
+/// Create a symlink at `link` pointing to `original`.
+///
+/// On Windows, a file or directory that can't be linked is copied instead.
+pub fn symlink(original: &Path, link: &Path) -> io::Result<()> {
+    #[cfg(unix)]
+    {
+        std::os::unix::fs::symlink(original, link)
+    }
+    #[cfg(not(unix))]
+    {
+        if original.is_dir() {
+            std::os::windows::fs::symlink_dir(original, link).or_else(|_| copy_dir(original, link))
+        } else {
+            std::os::windows::fs::symlink_file(original, link)
+                .or_else(|_| std::fs::copy(original, link).map(|_| ()))
+        }
+    }
+}
+
+/// Copy the directory `from` to `to`, recursively
+#[cfg(not(unix))]
+fn copy_dir(from: &Path, to: &Path) -> io::Result<()> {
+    std::fs::create_dir_all(to)?;
+    for entry in std::fs::read_dir(from)? {
+        let entry = entry?;
+        let target = to.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_dir(&entry.path(), &target)?;
+        } else {
+            std::fs::copy(entry.path(), &target)?;
+        }
+    }
+    Ok(())
+}
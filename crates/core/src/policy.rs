@@ -0,0 +1,409 @@
+//! Tool call policies for unattended agents
+//!
+//! The manifest's `policies:` decides Claude Code tool calls before they
+//! run, so an agent left alone can't wander off or wait forever on a
+//! permission prompt:
+//!
+//! ```yaml
+//! policies:
+//!   - tool: Bash
+//!     command: '^rm -rf|git push --force'
+//!     action: deny
+//!     reason: Destructive command
+//!   - tool: [Edit, Write]
+//!     path: ["src/**", "tests/**"]
+//!     action: allow
+//!   - tool: Bash
+//!     command: '^git push'
+//!     action: ask            # escalate to a person
+//! ```
+//!
+//! A rule matches a call when every condition it sets holds: `tool` names
+//! the tool (globs like `mcp__github__*` work), `path` globs the file the
+//! tool works on relative to the agent's directory (`*` stops at `/`, and
+//! files outside the directory never match) and `command` is a
+//! regex searched in a Bash command. The first matching rule decides:
+//! `allow` runs the call without a prompt, `deny` refuses it with the
+//! reason and `ask` (or `escalate`) always asks, even for tools the agent
+//! was allowed. Calls no rule matches go through Claude Code's usual
+//! permissions.
+//!
+//! Claude panes report `PreToolUse` to the session's event server when
+//! there are policies, and the server answers each hook with the decision
+//! (see [`PolicySet::decide`]), logging it as a `policy_decision` event.
+
+use std::path::{Component, Path, PathBuf};
+
+use anyhow::{Context, Result};
+use globset::{Glob, GlobBuilder, GlobMatcher};
+use regex::Regex;
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::{Value, json};
+
+use crate::config::{PaneConfig, WorkspaceConfig};
+
+/// Fields of a tool's input holding the file or directory it works on
+const PATH_FIELDS: &[&str] = &["file_path", "notebook_path", "path"];
+
+/// What a policy does with the calls it matches
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PolicyAction {
+    /// Run the call without asking
+    Allow,
+    /// Refuse the call
+    Deny,
+    /// Ask a person, whatever the agent's permissions
+    #[serde(alias = "escalate")]
+    Ask,
+}
+
+impl PolicyAction {
+    /// Claude Code's `permissionDecision` for the action
+    fn decision(self) -> &'static str {
+        match self {
+            PolicyAction::Allow => "allow",
+            PolicyAction::Deny => "deny",
+            PolicyAction::Ask => "ask",
+        }
+    }
+}
+
+/// One rule under `policies:`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PolicyRule {
+    /// Tool names or globs; any tool when empty
+    #[serde(default, deserialize_with = "one_or_many")]
+    pub tool: Vec<String>,
+    /// Globs of the file the tool works on
+    #[serde(default, deserialize_with = "one_or_many")]
+    pub path: Vec<String>,
+    /// Regex searched in the tool's `command`
+    #[serde(default)]
+    pub command: Option<String>,
+    pub action: PolicyAction,
+    /// Why, shown to the agent (and to the person asked)
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
+/// A string or a list of strings
+fn one_or_many<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMany {
+        One(String),
+        Many(Vec<String>),
+    }
+    Ok(match OneOrMany::deserialize(deserializer)? {
+        OneOrMany::One(value) => vec![value],
+        OneOrMany::Many(values) => values,
+    })
+}
+
+/// A rule with its globs and regex compiled
+#[derive(Debug, Clone)]
+struct CompiledRule {
+    rule: PolicyRule,
+    tools: Vec<GlobMatcher>,
+    paths: Vec<GlobMatcher>,
+    command: Option<Regex>,
+}
+
+impl CompiledRule {
+    fn compile(rule: &PolicyRule) -> Result<Self> {
+        let globs = |patterns: &[String], paths: bool| -> Result<Vec<GlobMatcher>> {
+            patterns
+                .iter()
+                .map(|p| {
+                    let glob = if paths {
+                        GlobBuilder::new(p).literal_separator(true).build()
+                    } else {
+                        Glob::new(p)
+                    };
+                    glob.map(|g| g.compile_matcher())
+                        .with_context(|| format!("invalid glob '{}'", p))
+                })
+                .collect()
+        };
+        Ok(Self {
+            tools: globs(&rule.tool, false)?,
+            paths: globs(&rule.path, true)?,
+            command: rule
+                .command
+                .as_deref()
+                .map(|c| Regex::new(c).with_context(|| format!("invalid regex '{}'", c)))
+                .transpose()?,
+            rule: rule.clone(),
+        })
+    }
+
+    /// Whether the call of `tool` with `input`, made from `cwd`, matches
+    fn matches(&self, tool: &str, input: &Value, cwd: Option<&Path>) -> bool {
+        if !self.tools.is_empty() && !self.tools.iter().any(|g| g.is_match(tool)) {
+            return false;
+        }
+        if !self.paths.is_empty() {
+            let Some(path) = PATH_FIELDS
+                .iter()
+                .find_map(|field| input.get(*field)?.as_str())
+            else {
+                return false;
+            };
+            let Some(relative) = relative_path(Path::new(path), cwd) else {
+                return false;
+            };
+            if !self.paths.iter().any(|g| g.is_match(&relative)) {
+                return false;
+            }
+        }
+        if let Some(command) = &self.command {
+            let Some(text) = input.get("command").and_then(Value::as_str) else {
+                return false;
+            };
+            if !command.is_match(text) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// `path` relative to `cwd`, with `.` and `..` resolved, or `None` when it
+/// is outside `cwd` (or absolute, without a `cwd`)
+fn relative_path(path: &Path, cwd: Option<&Path>) -> Option<PathBuf> {
+    match cwd {
+        Some(cwd) => normalize(&cwd.join(path))?
+            .strip_prefix(normalize(cwd)?)
+            .ok()
+            .map(Path::to_path_buf),
+        None if path.is_relative() => normalize(path),
+        None => None,
+    }
+}
+
+/// Resolve `.` and `..` in `path` without touching the filesystem; `None`
+/// when a relative path climbs above where it starts
+fn normalize(path: &Path) -> Option<PathBuf> {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => match normalized.components().next_back() {
+                Some(Component::Normal(_)) => {
+                    normalized.pop();
+                }
+                Some(Component::RootDir | Component::Prefix(_)) => {}
+                _ => return None,
+            },
+            component => normalized.push(component),
+        }
+    }
+    Some(normalized)
+}
+
+/// The manifest's `policies:`, ready to decide tool calls
+#[derive(Debug, Clone, Default)]
+pub struct PolicySet {
+    rules: Vec<CompiledRule>,
+}
+
+impl PolicySet {
+    /// Compile `rules`, failing on the first invalid glob or regex
+    pub fn compile(rules: &[PolicyRule]) -> Result<Self> {
+        let rules = rules
+            .iter()
+            .enumerate()
+            .map(|(i, rule)| {
+                CompiledRule::compile(rule).with_context(|| format!("policy {}", i + 1))
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self { rules })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// The first rule matching the tool call of a `PreToolUse` payload
+    pub fn matching(&self, payload: &Value) -> Option<&PolicyRule> {
+        let tool = payload.get("tool_name")?.as_str()?;
+        let input = payload.get("tool_input").unwrap_or(&Value::Null);
+        let cwd = payload.get("cwd").and_then(Value::as_str).map(Path::new);
+        self.rules
+            .iter()
+            .find(|rule| rule.matches(tool, input, cwd))
+            .map(|rule| &rule.rule)
+    }
+
+    /// The hook output deciding the tool call of a `PreToolUse` payload,
+    /// or `None` to leave it to Claude Code's permissions
+    pub fn decide(&self, payload: &Value) -> Option<Value> {
+        let rule = self.matching(payload)?;
+        let reason = rule
+            .reason
+            .clone()
+            .unwrap_or_else(|| format!("axel policy: {}", rule.action.decision()));
+        Some(json!({
+            "hookSpecificOutput": {
+                "hookEventName": "PreToolUse",
+                "permissionDecision": rule.action.decision(),
+                "permissionDecisionReason": reason,
+            }
+        }))
+    }
+}
+
+impl WorkspaceConfig {
+    /// Claude panes whose tool calls go through the policies, by name: all
+    /// of them when there are policies
+    pub fn policed_panes(&self) -> Vec<String> {
+        if self.policies.is_empty() {
+            return Vec::new();
+        }
+        self.layouts
+            .panes
+            .iter()
+            .filter(|pane| matches!(pane, PaneConfig::Claude(_)))
+            .map(|pane| pane.pane_type().to_string())
+            .collect()
+    }
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pre_tool_use(tool: &str, input: Value) -> Value {
+        json!({
+            "hook_event_name": "PreToolUse",
+            "cwd": "/code/app",
+            "tool_name": tool,
+            "tool_input": input,
+        })
+    }
+
+    #[test]
+    fn test_policy_decide() {
+        let rules: Vec<PolicyRule> = serde_yaml::from_str(
+            r#"
+- tool: Bash
+  command: '^rm -rf|git push --force'
+  action: deny
+  reason: Destructive command
+- tool: [Edit, Write]
+  path: ["src/**", "*.md"]
+  action: allow
+- tool: mcp__github__*
+  action: escalate
+"#,
+        )
+        .unwrap();
+        let policies = PolicySet::compile(&rules).unwrap();
+        let decision = |payload: Value| {
+            policies
+                .decide(&payload)
+                .map(|d| d["hookSpecificOutput"]["permissionDecision"].clone())
+        };
+
+        let denied = policies
+            .decide(&pre_tool_use("Bash", json!({"command": "rm -rf /"})))
+            .unwrap();
+        assert_eq!(
+            denied,
+            json!({"hookSpecificOutput": {
+                "hookEventName": "PreToolUse",
+                "permissionDecision": "deny",
+                "permissionDecisionReason": "Destructive command",
+            }})
+        );
+        assert_eq!(
+            decision(pre_tool_use("Bash", json!({"command": "cargo test"}))),
+            None
+        );
+
+        // Paths are matched relative to the agent's directory
+        assert_eq!(
+            decision(pre_tool_use(
+                "Edit",
+                json!({"file_path": "/code/app/src/main.rs"})
+            )),
+            Some(json!("allow"))
+        );
+        assert_eq!(
+            decision(pre_tool_use(
+                "Write",
+                json!({"file_path": "/code/app/README.md"})
+            )),
+            Some(json!("allow"))
+        );
+        assert_eq!(
+            decision(pre_tool_use(
+                "Edit",
+                json!({"file_path": "docs/../src/lib.rs"})
+            )),
+            Some(json!("allow"))
+        );
+        assert_eq!(
+            decision(pre_tool_use("Edit", json!({"file_path": "/etc/hosts"}))),
+            None
+        );
+
+        // `*` stops at `/`, and files outside the agent's directory never
+        // match, however the globs read
+        assert_eq!(
+            decision(pre_tool_use(
+                "Write",
+                json!({"file_path": "/code/app/docs/guide.md"})
+            )),
+            None
+        );
+        assert_eq!(
+            decision(pre_tool_use(
+                "Write",
+                json!({"file_path": "/home/u/.ssh/notes.md"})
+            )),
+            None
+        );
+        for escape in [
+            "/code/app/src/../../../etc/cron.d/x",
+            "src/../../../etc/cron.d/x",
+            "../other/src/main.rs",
+        ] {
+            assert_eq!(
+                decision(pre_tool_use("Edit", json!({"file_path": escape}))),
+                None,
+                "{}",
+                escape
+            );
+        }
+        let no_cwd = json!({
+            "tool_name": "Edit",
+            "tool_input": {"file_path": "/src/main.rs"},
+        });
+        assert_eq!(decision(no_cwd), None);
+
+        assert_eq!(
+            decision(pre_tool_use("mcp__github__create_pr", json!({}))),
+            Some(json!("ask"))
+        );
+
+        let invalid = [PolicyRule {
+            tool: Vec::new(),
+            path: Vec::new(),
+            command: Some("(".to_string()),
+            action: PolicyAction::Deny,
+            reason: None,
+        }];
+        let err = PolicySet::compile(&invalid).unwrap_err();
+        assert_eq!(err.to_string(), "policy 1");
+        assert!(format!("{:#}", err).contains("invalid regex '('"));
+    }
+}
@@ -0,0 +1,282 @@
+//! Asciicast recordings of panes
+//!
+//! `axel session record` pipes the output of a session's panes (tmux
+//! `pipe-pane`) into files in the [asciicast v2] format, one per pane, under
+//! `.axel/recordings/` in the workspace. Each file is a JSON header line
+//! followed by `[seconds, "o", output]` events, so a recording can be
+//! replayed with its original timing by `axel session play` or shared and
+//! played with asciinema.
+//!
+//! [asciicast v2]: https://docs.asciinema.org/manual/asciicast/v2/
+
+use std::{
+    fs::{File, OpenOptions},
+    io::{Read, Write},
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use anyhow::{Context, Result, bail};
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+
+use crate::transcript::TranscriptWriter;
+
+/// Directory, relative to the workspace, holding the recordings
+pub const RECORDINGS_DIR: &str = ".axel/recordings";
+
+/// Extension of recording files
+const EXTENSION: &str = "cast";
+
+/// First line of an asciicast file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AsciicastHeader {
+    pub version: u8,
+    /// Terminal columns
+    pub width: u16,
+    /// Terminal rows
+    pub height: u16,
+    /// Unix time the recording started
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+}
+
+impl AsciicastHeader {
+    /// Header for a recording of a `width` x `height` pane starting now
+    pub fn new(width: u16, height: u16, title: Option<String>) -> Self {
+        Self {
+            version: 2,
+            width,
+            height,
+            timestamp: Some(Local::now().timestamp()),
+            title,
+        }
+    }
+
+    /// Start a recording at `path` holding just this header
+    pub fn create_file(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut line = serde_json::to_string(self)?;
+        line.push('\n');
+        std::fs::write(path, line).with_context(|| format!("Failed to create {}", path.display()))
+    }
+}
+
+/// Path for a new recording of `pane` in `session`, named after the time
+/// it starts
+pub fn recording_path(workspace_dir: &Path, session: &str, pane: &str) -> PathBuf {
+    workspace_dir.join(RECORDINGS_DIR).join(format!(
+        "{}-{}-{}.{}",
+        session,
+        pane,
+        Local::now().format("%Y%m%d-%H%M%S"),
+        EXTENSION
+    ))
+}
+
+/// Writes pane output as asciicast events timed from its creation
+pub struct AsciicastWriter<W: Write> {
+    out: W,
+    start: Instant,
+    /// Trailing bytes of an incomplete UTF-8 character, held for the next
+    /// write
+    pending: Vec<u8>,
+}
+
+impl AsciicastWriter<File> {
+    /// Continue the recording at `path`, created with
+    /// [`AsciicastHeader::create_file`], timing events from now
+    pub fn append(path: &Path) -> Result<Self> {
+        let file = OpenOptions::new()
+            .append(true)
+            .open(path)
+            .with_context(|| format!("Failed to open {}", path.display()))?;
+        Ok(Self::new(file))
+    }
+}
+
+impl<W: Write> AsciicastWriter<W> {
+    /// Write events to `out`, which already has the header
+    pub fn new(out: W) -> Self {
+        Self {
+            out,
+            start: Instant::now(),
+            pending: Vec::new(),
+        }
+    }
+
+    /// Record `bytes` of output as happening now
+    pub fn write(&mut self, bytes: &[u8]) -> Result<()> {
+        self.write_at(self.start.elapsed(), bytes)
+    }
+
+    /// Record `bytes` of output at `elapsed` since the recording started
+    fn write_at(&mut self, elapsed: Duration, bytes: &[u8]) -> Result<()> {
+        self.pending.extend_from_slice(bytes);
+        // Keep a character split across reads for the next event
+        let complete = match std::str::from_utf8(&self.pending) {
+            Ok(_) => self.pending.len(),
+            Err(e) if e.error_len().is_none() => e.valid_up_to(),
+            Err(_) => self.pending.len(),
+        };
+        if complete == 0 {
+            return Ok(());
+        }
+        let data = String::from_utf8_lossy(&self.pending[..complete]).into_owned();
+        self.pending.drain(..complete);
+
+        let time = (elapsed.as_secs_f64() * 1e6).round() / 1e6;
+        serde_json::to_writer(&mut self.out, &(time, "o", data))?;
+        writeln!(self.out)?;
+        self.out.flush()?;
+        Ok(())
+    }
+}
+
+/// Copy pane output from `input` into a transcript and/or an asciicast
+/// recording until the input ends (tmux closes it when the pipe is replaced
+/// or the pane goes away).
+pub fn record_pane<T: Write, C: Write>(
+    mut input: impl Read,
+    mut transcript: Option<TranscriptWriter<T>>,
+    mut cast: Option<AsciicastWriter<C>>,
+) -> Result<()> {
+    let mut buf = [0; 8192];
+    loop {
+        let n = match input.read(&mut buf) {
+            Ok(0) => break,
+            Ok(n) => n,
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e.into()),
+        };
+        if let Some(transcript) = &mut transcript {
+            transcript.write(&buf[..n])?;
+        }
+        if let Some(cast) = &mut cast {
+            cast.write(&buf[..n])?;
+        }
+    }
+    if let Some(transcript) = transcript {
+        transcript.finish()?;
+    }
+    Ok(())
+}
+
+/// A recording read back for playback
+#[derive(Debug, Clone)]
+pub struct Asciicast {
+    pub header: AsciicastHeader,
+    /// Output events: seconds since the start and the output
+    pub events: Vec<(f64, String)>,
+}
+
+impl Asciicast {
+    /// Read the recording at `path`
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        Self::parse(&content).with_context(|| format!("Invalid recording {}", path.display()))
+    }
+
+    /// Parse an asciicast v2 recording, keeping its output events
+    pub fn parse(content: &str) -> Result<Self> {
+        let mut lines = content.lines().filter(|line| !line.trim().is_empty());
+        let Some(first) = lines.next() else {
+            bail!("Recording is empty");
+        };
+        let header: AsciicastHeader = serde_json::from_str(first)?;
+        if header.version != 2 {
+            bail!("Unsupported asciicast version {}", header.version);
+        }
+
+        let mut events = Vec::new();
+        for line in lines {
+            let (time, kind, data): (f64, String, String) = serde_json::from_str(line)?;
+            if kind == "o" {
+                events.push((time, data));
+            }
+        }
+        Ok(Self { header, events })
+    }
+
+    /// Seconds from the start to the last output
+    pub fn duration(&self) -> f64 {
+        self.events.last().map_or(0.0, |(time, _)| *time)
+    }
+
+    /// Write the output to `out` with its original timing, sped up by
+    /// `speed` and with pauses capped at `idle_limit` seconds
+    pub fn play(&self, out: &mut impl Write, speed: f64, idle_limit: Option<f64>) -> Result<()> {
+        let mut previous = 0.0;
+        for (time, data) in &self.events {
+            let mut pause = (time - previous).max(0.0);
+            if let Some(limit) = idle_limit {
+                pause = pause.min(limit);
+            }
+            previous = *time;
+            if pause > 0.0 && speed.is_finite() {
+                std::thread::sleep(Duration::from_secs_f64(pause / speed));
+            }
+            out.write_all(data.as_bytes())?;
+            out.flush()?;
+        }
+        Ok(())
+    }
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_asciicast_round_trip() {
+        let dir = std::env::temp_dir().join("axel-test-asciicast");
+        std::fs::remove_dir_all(&dir).ok();
+        let path = recording_path(&dir, "demo", "claude");
+        let header = AsciicastHeader::new(120, 40, Some("demo claude".to_string()));
+        header.create_file(&path).unwrap();
+
+        let mut writer = AsciicastWriter::append(&path).unwrap();
+        writer
+            .write_at(Duration::from_millis(250), b"\x1b[1mhi\x1b[0m\r\n")
+            .unwrap();
+        // "é" split across two reads arrives as one event
+        writer
+            .write_at(Duration::from_millis(500), b"caf\xc3")
+            .unwrap();
+        writer
+            .write_at(Duration::from_millis(1500), b"\xa9")
+            .unwrap();
+
+        drop(writer);
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        let lines: Vec<&str> = content.lines().collect();
+        assert!(lines[0].starts_with(r#"{"version":2,"width":120,"height":40,"timestamp":"#));
+        assert_eq!(lines[1], r#"[0.25,"o","\u001b[1mhi\u001b[0m\r\n"]"#);
+        assert_eq!(lines[2], r#"[0.5,"o","caf"]"#);
+        assert_eq!(lines[3], r#"[1.5,"o","é"]"#);
+
+        let cast = Asciicast::parse(&format!("{}\n[1.6, \"i\", \"q\"]\n", content)).unwrap();
+        assert_eq!(cast.header, header);
+        assert_eq!(cast.events.len(), 3);
+        assert_eq!(cast.duration(), 1.5);
+
+        let mut played = Vec::new();
+        cast.play(&mut played, f64::INFINITY, None).unwrap();
+        assert_eq!(played, "\x1b[1mhi\x1b[0m\r\ncafé".as_bytes());
+
+        assert!(Asciicast::parse("").is_err());
+        assert!(Asciicast::parse(r#"{"version":1,"width":1,"height":1}"#).is_err());
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -0,0 +1,141 @@
+//! Registry of known workspaces
+//!
+//! Every workspace launched or initialized with axel is remembered in
+//! `~/.local/share/axel/registry.json`, so `axel open <name>` can launch it
+//! from any directory. Workspaces whose manifest is gone are dropped the
+//! next time the registry is written.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Registry file, relative to the home directory
+pub const REGISTRY_FILE: &str = ".local/share/axel/registry.json";
+
+/// Registry file (`~/.local/share/axel/registry.json`)
+pub fn registry_path() -> Option<PathBuf> {
+    dirs::home_dir().map(|home| home.join(REGISTRY_FILE))
+}
+
+/// A workspace axel has launched or initialized
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RegisteredWorkspace {
+    /// The manifest's `workspace:` name
+    pub name: String,
+    pub manifest: PathBuf,
+    /// Last launch (or init)
+    pub last_used: DateTime<Utc>,
+}
+
+impl RegisteredWorkspace {
+    /// Name of the directory holding the manifest
+    pub fn dir_name(&self) -> String {
+        self.manifest
+            .parent()
+            .and_then(Path::file_name)
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default()
+    }
+}
+
+/// Known workspaces, most recently used first
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Registry {
+    #[serde(default)]
+    pub workspaces: Vec<RegisteredWorkspace>,
+}
+
+impl Registry {
+    /// Read the registry at `path`; a missing registry is empty
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = match std::fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => return Err(e).with_context(|| format!("Failed to read {}", path.display())),
+        };
+        serde_json::from_str(&content)
+            .with_context(|| format!("Invalid workspace registry {}", path.display()))
+    }
+
+    /// Write the registry to `path`, creating its directory
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Mark the workspace at `manifest` as used now, adding it if it is new,
+    /// and drop workspaces whose manifest no longer exists
+    pub fn record(&mut self, name: &str, manifest: &Path) {
+        let manifest = manifest
+            .canonicalize()
+            .unwrap_or_else(|_| manifest.to_path_buf());
+        self.workspaces
+            .retain(|w| w.manifest != manifest && w.manifest.exists());
+        self.workspaces.insert(
+            0,
+            RegisteredWorkspace {
+                name: name.to_string(),
+                manifest,
+                last_used: Utc::now(),
+            },
+        );
+    }
+}
+
+/// Remember the workspace at `manifest` in the user's registry
+pub fn register_workspace(name: &str, manifest: &Path) -> Result<()> {
+    let Some(path) = registry_path() else {
+        return Ok(());
+    };
+    let mut registry = Registry::load(&path)?;
+    registry.record(name, manifest);
+    registry.save(&path)
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registry_record() {
+        let dir = std::env::temp_dir().join("axel-test-registry");
+        std::fs::remove_dir_all(&dir).ok();
+        for name in ["api", "web"] {
+            std::fs::create_dir_all(dir.join(name)).unwrap();
+            std::fs::write(dir.join(name).join("AXEL.md"), "").unwrap();
+        }
+        let path = dir.join("registry.json");
+
+        let mut registry = Registry::load(&path).unwrap();
+        assert!(registry.workspaces.is_empty());
+        registry.record("api-server", &dir.join("api/AXEL.md"));
+        registry.record("web", &dir.join("web/AXEL.md"));
+        registry.record("api-server", &dir.join("api/AXEL.md"));
+        registry.save(&path).unwrap();
+
+        let mut registry = Registry::load(&path).unwrap();
+        let names: Vec<&str> = registry
+            .workspaces
+            .iter()
+            .map(|w| w.name.as_str())
+            .collect();
+        assert_eq!(names, ["api-server", "web"]);
+        assert_eq!(registry.workspaces[0].dir_name(), "api");
+
+        // Workspaces whose manifest is gone are dropped
+        std::fs::remove_dir_all(dir.join("web")).unwrap();
+        registry.record("api-server", &dir.join("api/AXEL.md"));
+        assert_eq!(registry.workspaces.len(), 1);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
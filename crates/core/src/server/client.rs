@@ -0,0 +1,152 @@
+//! Blocking client for a running event server.
+//!
+//! Speaks just enough HTTP/1.0 over a local TCP connection to read
+//! `GET /usage` and follow the `/inbox` SSE stream, so tools like
+//! `axel dash` can watch a server without an async runtime.
+
+use std::{
+    io::{BufRead, BufReader, Read, Write},
+    net::{SocketAddr, TcpStream},
+    time::Duration,
+};
+
+use anyhow::{Context, Result, bail};
+
+use super::{events::TimestampedEvent, usage::UsageReport};
+
+/// How long to wait for the server to accept a connection
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// How long to wait for a response to a one-shot request
+const READ_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Client for the event server listening on a local port
+#[derive(Debug, Clone, Copy)]
+pub struct ServerClient {
+    port: u16,
+}
+
+impl ServerClient {
+    pub fn new(port: u16) -> Self {
+        Self { port }
+    }
+
+    pub fn port(&self) -> u16 {
+        self.port
+    }
+
+    /// Token usage and estimated cost per pane (`GET /usage`)
+    pub fn usage(&self) -> Result<UsageReport> {
+        let mut reader = self.get("/usage", Some(READ_TIMEOUT))?;
+        let mut body = String::new();
+        reader.read_to_string(&mut body)?;
+        serde_json::from_str(&body).context("Failed to parse /usage response")
+    }
+
+    /// Follow the events the server receives from now on (`GET /inbox`)
+    pub fn events(&self) -> Result<EventStream<BufReader<TcpStream>>> {
+        Ok(EventStream::new(self.get("/inbox", None)?))
+    }
+
+    /// Send a GET request and return the response body, after checking the
+    /// status and skipping the headers.
+    ///
+    /// HTTP/1.0 makes the server close the connection at the end of the
+    /// body, so neither chunked decoding nor a content length is needed.
+    fn get(&self, path: &str, timeout: Option<Duration>) -> Result<BufReader<TcpStream>> {
+        let addr = SocketAddr::from(([127, 0, 0, 1], self.port));
+        let mut stream = TcpStream::connect_timeout(&addr, CONNECT_TIMEOUT)
+            .with_context(|| format!("No event server on port {}", self.port))?;
+        stream.set_read_timeout(timeout)?;
+        write!(stream, "GET {} HTTP/1.0\r\nHost: {}\r\n\r\n", path, addr)?;
+
+        let mut reader = BufReader::new(stream);
+        let mut line = String::new();
+        reader.read_line(&mut line)?;
+        let status = line.split_whitespace().nth(1).unwrap_or_default();
+        if status != "200" {
+            bail!("GET {} failed: {}", path, line.trim());
+        }
+        loop {
+            line.clear();
+            if reader.read_line(&mut line)? == 0 || line.trim().is_empty() {
+                break;
+            }
+        }
+        Ok(reader)
+    }
+}
+
+/// Events read from an SSE stream, ending when the server closes it
+pub struct EventStream<R> {
+    reader: R,
+}
+
+impl<R: BufRead> EventStream<R> {
+    pub fn new(reader: R) -> Self {
+        Self { reader }
+    }
+}
+
+impl<R: BufRead> Iterator for EventStream<R> {
+    type Item = Result<TimestampedEvent>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut data = String::new();
+        let mut line = String::new();
+        loop {
+            line.clear();
+            match self.reader.read_line(&mut line) {
+                Ok(0) => return None,
+                Ok(_) => {}
+                Err(e) => return Some(Err(e.into())),
+            }
+            let line = line.trim_end_matches(['\r', '\n']);
+
+            // A blank line ends an event; keep-alive comments have no data
+            if line.is_empty() {
+                if data.is_empty() {
+                    continue;
+                }
+                return Some(serde_json::from_str(&data).map_err(Into::into));
+            }
+            if let Some(value) = line.strip_prefix("data:") {
+                if !data.is_empty() {
+                    data.push('\n');
+                }
+                data.push_str(value.strip_prefix(' ').unwrap_or(value));
+            }
+        }
+    }
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_stream() {
+        let body = ":\n\n\
+            data: {\"timestamp\":\"2025-01-01T00:00:00Z\",\"event_type\":\"hook\",\
+            \"pane_id\":\"p1\",\"event\":{\"type\":\"stop\"}}\r\n\r\n\
+            event: ignored\n\
+            data:{\"timestamp\":\"2025-01-01T00:00:01Z\",\n\
+            data:\"event_type\":\"otel_metrics\",\"pane_id\":\"p2\",\"event\":{}}\n\n\
+            data: not json\n\n";
+        let events: Vec<_> = EventStream::new(body.as_bytes()).collect();
+
+        assert_eq!(events.len(), 3);
+        let first = events[0].as_ref().unwrap();
+        assert_eq!(
+            (first.pane_id.as_str(), first.event_type.as_str()),
+            ("p1", "hook")
+        );
+        assert_eq!(first.event["type"], "stop");
+        assert_eq!(events[1].as_ref().unwrap().pane_id, "p2");
+        assert!(events[2].is_err());
+    }
+}
@@ -2,9 +2,20 @@
 //!
 //! Handles Claude Code hook events and OTEL telemetry data.
 
+use std::path::Path;
+
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+/// `type` of the event Codex passes to its `notify` program after a turn
+const CODEX_TURN_COMPLETE: &str = "agent-turn-complete";
+
+/// Hook name of the event the server raises when a pane goes over budget
+pub(super) const BUDGET_EXCEEDED: &str = "BudgetExceeded";
+
+/// Hook name of the event the server raises when a pane is stuck mid-task
+pub(super) const STUCK: &str = "Stuck";
+
 /// Wrapper for timestamped events logged to JSONL
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TimestampedEvent {
@@ -29,6 +40,59 @@ impl TimestampedEvent {
             event,
         }
     }
+
+    /// Name of the hook that sent this event (`Stop`, `PreToolUse`, ...).
+    ///
+    /// Claude Code names it in `hook_event_name`; payloads with a `type`
+    /// field were already typed by the server. Codex's `notify` program
+    /// reports the end of a turn, which counts as `Stop`.
+    pub fn hook_name(&self) -> Option<&str> {
+        match self.event.get("hook_event_name").and_then(|v| v.as_str()) {
+            Some(name) => Some(name),
+            None if self.event.get("type").and_then(|v| v.as_str())
+                == Some(CODEX_TURN_COMPLETE) =>
+            {
+                Some("Stop")
+            }
+            None if self.event.get("type").is_some() && self.event_type != "unknown_hook" => {
+                Some(&self.event_type)
+            }
+            None => None,
+        }
+    }
+
+    /// The agent's final reply, for a `Stop` event: the payload's
+    /// `last_assistant_message` (`last-assistant-message` from Codex), or
+    /// else the last assistant text in the transcript at `transcript_path`.
+    pub fn stop_reply(&self) -> Option<String> {
+        if let Some(message) = ["last_assistant_message", "last-assistant-message"]
+            .iter()
+            .find_map(|key| self.event.get(key)?.as_str())
+        {
+            return Some(message.to_string());
+        }
+        let transcript = self.event.get("transcript_path")?.as_str()?;
+        last_assistant_text(Path::new(transcript))
+    }
+}
+
+/// Text of the last assistant message in a Claude Code transcript (JSONL)
+fn last_assistant_text(transcript: &Path) -> Option<String> {
+    let content = std::fs::read_to_string(transcript).ok()?;
+    content.lines().rev().find_map(|line| {
+        let entry: serde_json::Value = serde_json::from_str(line).ok()?;
+        if entry.get("type")?.as_str()? != "assistant" {
+            return None;
+        }
+        let text: Vec<&str> = entry
+            .pointer("/message/content")?
+            .as_array()?
+            .iter()
+            .filter(|block| block.get("type").and_then(|t| t.as_str()) == Some("text"))
+            .filter_map(|block| block.get("text")?.as_str())
+            .collect();
+        (!text.is_empty()).then(|| text.join("\n"))
+    })
 }
 
 /// Claude Code hook event types
@@ -42,6 +106,46 @@ pub enum HookEventType {
     Stop,
     SubagentStop,
     PermissionRequest,
+    /// Claude Code shows a notification (waiting for input, idle)
+    Notification,
+    /// Claude Code is about to compact the conversation
+    PreCompact,
+    /// Sent by `axel session watch` after restarting an exited AI pane
+    PaneRespawned,
+}
+
+impl HookEventType {
+    const ALL: [HookEventType; 10] = [
+        HookEventType::PreToolUse,
+        HookEventType::PostToolUse,
+        HookEventType::SessionStart,
+        HookEventType::SessionEnd,
+        HookEventType::Stop,
+        HookEventType::SubagentStop,
+        HookEventType::PermissionRequest,
+        HookEventType::Notification,
+        HookEventType::PreCompact,
+        HookEventType::PaneRespawned,
+    ];
+
+    /// The event type Claude Code names `name` in `hook_event_name`
+    pub fn from_hook_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|t| t.to_string() == name)
+    }
+
+    /// The type of a hook payload: its `type` field, set by axel, or the
+    /// `hook_event_name` Claude Code sends
+    pub fn of_payload(payload: &serde_json::Value) -> Option<Self> {
+        serde_json::from_value::<HookEvent>(payload.clone())
+            .map(|event| event.event_type)
+            .ok()
+            .or_else(|| {
+                payload
+                    .get("hook_event_name")
+                    .and_then(|v| v.as_str())
+                    .and_then(Self::from_hook_name)
+            })
+    }
 }
 
 impl std::fmt::Display for HookEventType {
@@ -54,6 +158,9 @@ impl std::fmt::Display for HookEventType {
             HookEventType::Stop => write!(f, "Stop"),
             HookEventType::SubagentStop => write!(f, "SubagentStop"),
             HookEventType::PermissionRequest => write!(f, "PermissionRequest"),
+            HookEventType::Notification => write!(f, "Notification"),
+            HookEventType::PreCompact => write!(f, "PreCompact"),
+            HookEventType::PaneRespawned => write!(f, "PaneRespawned"),
         }
     }
 }
@@ -94,9 +201,14 @@ pub struct OutboxResponse {
     pub response_type: OutboxResponseType,
     /// The actual response text to send (e.g., "y" for allow, "n" for deny)
     pub response_text: String,
-    /// Optional tmux pane ID to target (if not provided, uses session's first pane)
+    /// Optional tmux pane ID to target
     #[serde(skip_serializing_if = "Option::is_none")]
     pub pane_id: Option<String>,
+    /// Optional name of the pane to target (e.g. `claude`), resolved to its
+    /// tmux pane ID; without it or `pane_id`, the pane `session_id` reports
+    /// from, or else the session's second pane
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pane: Option<String>,
 }
 
 /// Types of outbox responses
@@ -117,3 +229,76 @@ impl std::fmt::Display for OutboxResponseType {
         }
     }
 }
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_stop_reply() {
+        let dir = std::env::temp_dir().join("axel-test-stop-reply");
+        std::fs::create_dir_all(&dir).unwrap();
+        let transcript = dir.join("transcript.jsonl");
+        let lines = [
+            json!({"type": "user", "message": {"role": "user", "content": "hi"}}),
+            json!({"type": "assistant", "message": {"content": [
+                {"type": "text", "text": "Looking."},
+            ]}}),
+            json!({"type": "assistant", "message": {"content": [
+                {"type": "tool_use", "name": "Bash"},
+            ]}}),
+            json!({"type": "assistant", "message": {"content": [
+                {"type": "text", "text": "All tests pass."},
+                {"type": "text", "text": "Done."},
+            ]}}),
+            json!({"type": "system", "content": "Stop hook"}),
+        ];
+        let content: Vec<String> = lines.iter().map(|l| l.to_string()).collect();
+        std::fs::write(&transcript, content.join("\n")).unwrap();
+
+        let stop = TimestampedEvent::new(
+            "unknown_hook",
+            "p1",
+            json!({"hook_event_name": "Stop", "transcript_path": transcript}),
+        );
+        assert_eq!(stop.hook_name(), Some("Stop"));
+        assert_eq!(stop.stop_reply().as_deref(), Some("All tests pass.\nDone."));
+
+        let typed = TimestampedEvent::new(
+            "Stop",
+            "p1",
+            json!({"type": "stop", "last_assistant_message": "Hi"}),
+        );
+        assert_eq!(typed.hook_name(), Some("Stop"));
+        assert_eq!(typed.stop_reply().as_deref(), Some("Hi"));
+
+        let otel = TimestampedEvent::new("otel_metrics", "p1", json!({}));
+        assert_eq!(otel.hook_name(), None);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_hook_event_type_of_payload() {
+        let type_of = |payload| HookEventType::of_payload(&payload).map(|t| t.to_string());
+        assert_eq!(
+            type_of(json!({"hook_event_name": "PreCompact", "trigger": "auto"})).as_deref(),
+            Some("PreCompact")
+        );
+        assert_eq!(
+            type_of(json!({"hook_event_name": "Notification", "message": "Waiting"})).as_deref(),
+            Some("Notification")
+        );
+        assert_eq!(
+            type_of(json!({"type": "pane_respawned", "pane": "claude"})).as_deref(),
+            Some("PaneRespawned")
+        );
+        assert_eq!(type_of(json!({"hook_event_name": "Unheard"})), None);
+    }
+}
@@ -1,11 +1,72 @@
 //! JSONL file logger for event persistence.
+//!
+//! Events are also written to the SQLite store when one is configured.
+//! The log is rotated according to a [`LogRetention`] policy: the current
+//! file is compressed to a dated archive next to it
+//! (`events.jsonl` → `events-20250101-120000000.jsonl.gz`) and old archives
+//! are pruned.
 
-use std::path::PathBuf;
+use std::{
+    io::Write,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
 
 use anyhow::Result;
-use tokio::{fs::OpenOptions, io::AsyncWriteExt, sync::mpsc};
+use chrono::{DateTime, Utc};
+use flate2::{Compression, write::GzEncoder};
+use tokio::{
+    fs::{File, OpenOptions},
+    io::{AsyncBufReadExt, AsyncWriteExt, BufReader, BufWriter},
+    sync::mpsc,
+};
 
-use super::events::TimestampedEvent;
+use super::{events::TimestampedEvent, store::EventStore};
+
+/// Rotation and retention policy for the JSONL log
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogRetention {
+    /// Rotate once the log grows past this many bytes
+    pub max_size: Option<u64>,
+    /// Rotate once the log has been written to for this long
+    pub max_age: Option<Duration>,
+    /// Keep at most this many archives
+    pub max_archives: Option<usize>,
+    /// Delete archives older than this
+    pub archive_max_age: Option<Duration>,
+}
+
+impl Default for LogRetention {
+    /// Rotate at 100 MiB and keep the 10 most recent archives
+    fn default() -> Self {
+        Self {
+            max_size: Some(100 * 1024 * 1024),
+            max_age: None,
+            max_archives: Some(10),
+            archive_max_age: None,
+        }
+    }
+}
+
+impl LogRetention {
+    /// Never rotate or prune
+    pub fn none() -> Self {
+        Self {
+            max_size: None,
+            max_age: None,
+            max_archives: None,
+            archive_max_age: None,
+        }
+    }
+
+    /// Whether a log of `size` bytes started at `started` is due for rotation
+    fn is_due(&self, size: u64, started: DateTime<Utc>) -> bool {
+        self.max_size.is_some_and(|max| size >= max)
+            || self
+                .max_age
+                .is_some_and(|max| (Utc::now() - started).to_std().unwrap_or_default() >= max)
+    }
+}
 
 /// Async event logger that writes to a JSONL file
 pub struct EventLogger {
@@ -13,8 +74,13 @@ pub struct EventLogger {
 }
 
 impl EventLogger {
-    /// Create a new event logger that writes to the specified path
-    pub async fn new(path: PathBuf) -> Result<Self> {
+    /// Create a new event logger that writes to the specified path (and
+    /// `store`, if given), rotating it according to `retention`
+    pub async fn new(
+        path: PathBuf,
+        store: Option<EventStore>,
+        retention: LogRetention,
+    ) -> Result<Self> {
         // Ensure parent directory exists
         if let Some(parent) = path.parent() {
             tokio::fs::create_dir_all(parent).await?;
@@ -23,7 +89,7 @@ impl EventLogger {
         let (tx, rx) = mpsc::channel::<TimestampedEvent>(1000);
 
         // Spawn the writer task
-        tokio::spawn(writer_task(path, rx));
+        tokio::spawn(writer_task(path, store, retention, rx));
 
         Ok(Self { tx })
     }
@@ -40,42 +106,230 @@ impl EventLogger {
     }
 }
 
-/// Background task that writes events to the JSONL file
-async fn writer_task(path: PathBuf, mut rx: mpsc::Receiver<TimestampedEvent>) {
-    let file = match OpenOptions::new()
-        .create(true)
-        .append(true)
-        .open(&path)
-        .await
-    {
-        Ok(f) => f,
+/// The open log file with its size and the time of its first event
+struct LogFile {
+    writer: BufWriter<File>,
+    size: u64,
+    started: DateTime<Utc>,
+}
+
+impl LogFile {
+    async fn open(path: &Path) -> std::io::Result<Self> {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .await?;
+        let size = file.metadata().await?.len();
+        let started = first_event_time(path).await.unwrap_or_else(Utc::now);
+        Ok(Self {
+            writer: BufWriter::new(file),
+            size,
+            started,
+        })
+    }
+}
+
+/// Timestamp of the first event in an existing log
+async fn first_event_time(path: &Path) -> Option<DateTime<Utc>> {
+    let file = File::open(path).await.ok()?;
+    let first = BufReader::new(file).lines().next_line().await.ok()??;
+    serde_json::from_str::<TimestampedEvent>(&first)
+        .ok()
+        .map(|e| e.timestamp)
+}
+
+/// Background task that writes events to the JSONL file and store
+async fn writer_task(
+    path: PathBuf,
+    store: Option<EventStore>,
+    retention: LogRetention,
+    mut rx: mpsc::Receiver<TimestampedEvent>,
+) {
+    let mut log = match LogFile::open(&path).await {
+        Ok(log) => log,
         Err(e) => {
             eprintln!("Failed to open log file {:?}: {}", path, e);
             return;
         }
     };
 
-    let mut writer = tokio::io::BufWriter::new(file);
-
     while let Some(event) = rx.recv().await {
+        if let Some(store) = &store {
+            let (store, stored) = (store.clone(), event.clone());
+            match tokio::task::spawn_blocking(move || store.insert(&stored)).await {
+                Ok(Ok(())) => {}
+                Ok(Err(e)) => eprintln!("Failed to store event: {}", e),
+                Err(e) => eprintln!("Failed to store event: {}", e),
+            }
+        }
+
         match serde_json::to_string(&event) {
             Ok(json) => {
-                if let Err(e) = writer.write_all(json.as_bytes()).await {
+                if let Err(e) = log.writer.write_all(json.as_bytes()).await {
                     eprintln!("Failed to write event: {}", e);
                     continue;
                 }
-                if let Err(e) = writer.write_all(b"\n").await {
+                if let Err(e) = log.writer.write_all(b"\n").await {
                     eprintln!("Failed to write newline: {}", e);
                     continue;
                 }
                 // Flush periodically to ensure events are written
-                if let Err(e) = writer.flush().await {
+                if let Err(e) = log.writer.flush().await {
                     eprintln!("Failed to flush log file: {}", e);
                 }
+                log.size += json.len() as u64 + 1;
             }
             Err(e) => {
                 eprintln!("Failed to serialize event: {}", e);
             }
         }
+
+        if retention.is_due(log.size, log.started) {
+            let (rotate_path, policy) = (path.clone(), retention.clone());
+            match tokio::task::spawn_blocking(move || rotate_log(&rotate_path, &policy)).await {
+                Ok(Ok(_)) => {}
+                Ok(Err(e)) => eprintln!("Failed to rotate log file {:?}: {}", path, e),
+                Err(e) => eprintln!("Failed to rotate log file {:?}: {}", path, e),
+            }
+            log = match LogFile::open(&path).await {
+                Ok(log) => log,
+                Err(e) => {
+                    eprintln!("Failed to reopen log file {:?}: {}", path, e);
+                    return;
+                }
+            };
+        }
+    }
+}
+
+// =============================================================================
+// Rotation
+// =============================================================================
+
+/// Compress the log at `path` into a dated archive, truncate it, and prune
+/// archives according to `retention`. Returns the archive path.
+pub fn rotate_log(path: &Path, retention: &LogRetention) -> Result<PathBuf> {
+    let stem = log_stem(path);
+    let archive = path.with_file_name(format!(
+        "{}-{}.jsonl.gz",
+        stem,
+        Utc::now().format("%Y%m%d-%H%M%S%3f")
+    ));
+
+    let mut encoder = GzEncoder::new(std::fs::File::create(&archive)?, Compression::default());
+    std::io::copy(&mut std::fs::File::open(path)?, &mut encoder)?;
+    encoder.finish()?.flush()?;
+    std::fs::File::create(path)?;
+
+    prune_archives(path, retention)?;
+    Ok(archive)
+}
+
+/// Archives of the log at `path`, oldest first
+pub fn log_archives(path: &Path) -> Result<Vec<PathBuf>> {
+    let prefix = format!("{}-", log_stem(path));
+    let Some(dir) = path.parent() else {
+        return Ok(Vec::new());
+    };
+    let dir = if dir.as_os_str().is_empty() {
+        Path::new(".")
+    } else {
+        dir
+    };
+
+    let mut archives: Vec<PathBuf> = std::fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|p| {
+            p.file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| n.starts_with(&prefix) && n.ends_with(".jsonl.gz"))
+        })
+        .collect();
+    // Archive names embed a sortable timestamp
+    archives.sort();
+    Ok(archives)
+}
+
+/// Delete archives beyond `max_archives` or older than `archive_max_age`
+fn prune_archives(path: &Path, retention: &LogRetention) -> Result<()> {
+    let mut archives = log_archives(path)?;
+
+    if let Some(max) = retention.archive_max_age {
+        let now = SystemTime::now();
+        archives.retain(|archive| {
+            let expired = std::fs::metadata(archive)
+                .and_then(|m| m.modified())
+                .ok()
+                .and_then(|modified| now.duration_since(modified).ok())
+                .is_some_and(|age| age > max);
+            if expired {
+                std::fs::remove_file(archive).ok();
+            }
+            !expired
+        });
+    }
+
+    if let Some(max) = retention.max_archives {
+        let excess = archives.len().saturating_sub(max);
+        for archive in &archives[..excess] {
+            std::fs::remove_file(archive)?;
+        }
+    }
+    Ok(())
+}
+
+/// File name of the log without its extension (`events` for `events.jsonl`)
+fn log_stem(path: &Path) -> String {
+    path.file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| "events".to_string())
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+
+    use flate2::read::GzDecoder;
+
+    use super::*;
+
+    #[test]
+    fn test_rotate_log_archives_and_prunes() {
+        let dir = std::env::temp_dir().join("axel-test-log-rotation");
+        std::fs::remove_dir_all(&dir).ok();
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("events.jsonl");
+
+        let retention = LogRetention {
+            max_archives: Some(2),
+            ..LogRetention::none()
+        };
+        for i in 0..3 {
+            std::fs::write(&path, format!("line {}\n", i)).unwrap();
+            rotate_log(&path, &retention).unwrap();
+            // Archive names have millisecond resolution
+            std::thread::sleep(Duration::from_millis(5));
+        }
+
+        // The log is truncated and only the two newest archives remain
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "");
+        let archives = log_archives(&path).unwrap();
+        assert_eq!(archives.len(), 2);
+
+        let mut content = String::new();
+        GzDecoder::new(std::fs::File::open(&archives[1]).unwrap())
+            .read_to_string(&mut content)
+            .unwrap();
+        assert_eq!(content, "line 2\n");
+
+        assert!(LogRetention::default().is_due(200 * 1024 * 1024, Utc::now()));
+        assert!(!LogRetention::none().is_due(u64::MAX, Utc::now()));
+
+        std::fs::remove_dir_all(&dir).ok();
     }
 }
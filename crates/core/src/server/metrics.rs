@@ -0,0 +1,164 @@
+//! Prometheus metrics for the event server.
+//!
+//! When enabled, `GET /metrics` serves counters in the Prometheus text
+//! exposition format: hook events by type, OTEL payloads by signal, tokens
+//! by pane, model and kind (from the [`UsageTracker`](super::UsageTracker))
+//! and the number of agent sessions the server has seen.
+
+use std::{collections::BTreeMap, fmt::Write, sync::Mutex};
+
+use super::usage::UsageReport;
+
+/// Counters updated by the route handlers
+#[derive(Debug, Default)]
+pub struct Metrics {
+    hook_events: Mutex<BTreeMap<String, u64>>,
+    otel_payloads: Mutex<BTreeMap<String, u64>>,
+}
+
+impl Metrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Count one hook event of `event_type`
+    pub fn hook_event(&self, event_type: &str) {
+        *self
+            .hook_events
+            .lock()
+            .unwrap()
+            .entry(event_type.to_string())
+            .or_default() += 1;
+    }
+
+    /// Count one OTEL payload of `signal` (`otel_metrics`, `otel_traces`, `otel_logs`)
+    pub fn otel_payload(&self, signal: &str) {
+        *self
+            .otel_payloads
+            .lock()
+            .unwrap()
+            .entry(signal.to_string())
+            .or_default() += 1;
+    }
+
+    /// Render every metric in the Prometheus text format
+    pub fn render(&self, usage: &UsageReport, active_sessions: usize) -> String {
+        let mut out = String::new();
+
+        family(
+            &mut out,
+            "axel_hook_events_total",
+            "counter",
+            "Hook events received",
+        );
+        for (event_type, count) in self.hook_events.lock().unwrap().iter() {
+            sample(
+                &mut out,
+                "axel_hook_events_total",
+                &[("type", event_type)],
+                count,
+            );
+        }
+
+        family(
+            &mut out,
+            "axel_otel_payloads_total",
+            "counter",
+            "OTEL export requests received",
+        );
+        for (signal, count) in self.otel_payloads.lock().unwrap().iter() {
+            sample(
+                &mut out,
+                "axel_otel_payloads_total",
+                &[("signal", signal)],
+                count,
+            );
+        }
+
+        family(
+            &mut out,
+            "axel_tokens_total",
+            "counter",
+            "Tokens reported by AI panes",
+        );
+        for pane in &usage.panes {
+            for model in &pane.models {
+                let tokens = &model.tokens;
+                for (kind, count) in [
+                    ("input", tokens.input_tokens),
+                    ("output", tokens.output_tokens),
+                    ("cache_read", tokens.cache_read_tokens),
+                    ("cache_creation", tokens.cache_creation_tokens),
+                ] {
+                    let labels = [
+                        ("pane", pane.pane_id.as_str()),
+                        ("model", &model.model),
+                        ("kind", kind),
+                    ];
+                    sample(&mut out, "axel_tokens_total", &labels, count);
+                }
+            }
+        }
+
+        family(
+            &mut out,
+            "axel_active_sessions",
+            "gauge",
+            "Agent sessions mapped to a pane",
+        );
+        sample(&mut out, "axel_active_sessions", &[], active_sessions);
+
+        out
+    }
+}
+
+/// Write the `# HELP` and `# TYPE` lines of a metric family
+fn family(out: &mut String, name: &str, kind: &str, help: &str) {
+    let _ = writeln!(out, "# HELP {} {}", name, help);
+    let _ = writeln!(out, "# TYPE {} {}", name, kind);
+}
+
+/// Write one sample line
+fn sample(out: &mut String, name: &str, labels: &[(&str, &str)], value: impl std::fmt::Display) {
+    let labels: Vec<String> = labels
+        .iter()
+        .map(|(key, value)| format!("{}=\"{}\"", key, escape_label(value)))
+        .collect();
+    if labels.is_empty() {
+        let _ = writeln!(out, "{} {}", name, value);
+    } else {
+        let _ = writeln!(out, "{}{{{}}} {}", name, labels.join(","), value);
+    }
+}
+
+/// Escape a label value (backslash, double quote and newline)
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_prometheus_text() {
+        let metrics = Metrics::new();
+        metrics.hook_event("PreToolUse");
+        metrics.hook_event("PreToolUse");
+        metrics.otel_payload("otel_metrics");
+
+        let text = metrics.render(&UsageReport::default(), 3);
+        assert!(text.contains("# TYPE axel_hook_events_total counter\n"));
+        assert!(text.contains("axel_hook_events_total{type=\"PreToolUse\"} 2\n"));
+        assert!(text.contains("axel_otel_payloads_total{signal=\"otel_metrics\"} 1\n"));
+        assert!(text.contains("axel_active_sessions 3\n"));
+        assert_eq!(escape_label("a\"b\\c\n"), "a\\\"b\\\\c\\n");
+    }
+}
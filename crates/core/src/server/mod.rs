@@ -1,26 +1,64 @@
 //! Axel Event Server
 //!
-//! HTTP server that receives Claude Code hook events and OTEL telemetry data,
-//! logging everything to a JSONL file.
+//! HTTP server that receives Claude Code hook events and OTEL telemetry data
+//! (OTLP JSON or protobuf), logging everything to a JSONL file (and optionally
+//! a SQLite database that `GET /events` queries). Token counters from OTEL telemetry are aggregated
+//! into per-pane cost estimates served by `GET /usage`. Clients follow events
+//! over SSE (`/inbox`) or a WebSocket (`/ws`) that also accepts outbox
+//! responses, which can target a pane by name. The pane each Claude
+//! session reports from is saved to `.axel/state.json`, so OTEL telemetry
+//! stays attributed across restarts, and served by `GET /panes`. With [`ServerConfig::metrics`] set, `GET /metrics` exposes
+//! Prometheus counters, and `notifications:` raises desktop notifications and
+//! calls webhooks for events that need attention. Panes with a `budget:`
+//! are interrupted when their usage goes over it (see [`crate::budget`]).
+//! `PreToolUse` hooks are answered with the decision of the manifest's
+//! `policies:` (see [`crate::policy`]), the diffs of the files agents
+//! write are logged for `axel audit` (see [`crate::audit`]), and with
+//! `checkpoints: true` each turn's changes are committed to a scratch ref
+//! (see [`crate::checkpoint`]). With an
+//! [`OrchestratorConfig`], agents' final replies are pasted into the panes
+//! the pipeline routes them to. [`ServerClient`] reads a running server's
+//! usage and events from other processes.
 
+mod client;
 mod events;
 mod logger;
+mod metrics;
+mod notify;
+mod otlp;
 mod routes;
+mod state;
+mod store;
+mod usage;
 
-use std::{
-    collections::HashMap, net::SocketAddr, path::PathBuf, process::Command, sync::Arc,
-    time::Duration,
-};
+use std::{collections::HashMap, net::SocketAddr, path::PathBuf, sync::Arc, time::Duration};
 
 use anyhow::Result;
+pub use client::{EventStream, ServerClient};
 pub use events::{
     HookEvent, HookEventType, OtelEventType, OutboxResponse, OutboxResponseType, TimestampedEvent,
 };
-pub use logger::EventLogger;
+pub use logger::{EventLogger, LogRetention, log_archives, rotate_log};
+pub use metrics::Metrics;
+pub use notify::{Notification, NotificationsConfig, Notifier, WebhookConfig, WebhookKind};
+use routes::stuck_watchdog;
 pub use routes::{AppState, create_router};
+pub use state::{ServerState, spawn_state_writer, state_path};
+pub use store::{EventQuery, EventStorage, EventStore};
 use tokio::{
     net::TcpListener,
-    sync::{RwLock, broadcast, watch},
+    sync::{Mutex, RwLock, broadcast, watch},
+};
+pub use usage::{
+    ModelPrice, ModelUsage, PaneUsage, Pricing, TokenCounts, UsageReport, UsageTracker,
+};
+
+use crate::{
+    budget::{BudgetConfig, BudgetTracker},
+    orchestrator::OrchestratorConfig,
+    policy::{PolicyRule, PolicySet},
+    stuck::{StuckConfig, StuckTracker},
+    tmux::{session_pane_map, tmux_command},
 };
 
 /// Configuration for the event server
@@ -32,6 +70,30 @@ pub struct ServerConfig {
     pub session: String,
     /// Path to the JSONL log file
     pub log_path: PathBuf,
+    /// Additional indexed storage for event queries
+    pub storage: EventStorage,
+    /// Rotation and retention of the JSONL log
+    pub retention: LogRetention,
+    /// Serve Prometheus metrics on `GET /metrics`
+    pub metrics: bool,
+    /// Desktop notifications and webhooks for hook events (the manifest's
+    /// `notifications:`)
+    pub notifications: Option<NotificationsConfig>,
+    /// Display names of known panes by pane id, used in notifications
+    pub pane_names: HashMap<String, String>,
+    /// Pipeline routing agent replies between panes of `session` (the
+    /// manifest's `orchestrator:`)
+    pub orchestrator: Option<OrchestratorConfig>,
+    /// Budgets of AI panes by pane id, enforced from their OTEL usage and
+    /// hook events
+    pub budgets: HashMap<String, BudgetConfig>,
+    /// Stuck thresholds of AI panes by pane id
+    pub stuck: HashMap<String, StuckConfig>,
+    /// Rules deciding Claude panes' tool calls (the manifest's `policies:`)
+    pub policies: Vec<PolicyRule>,
+    /// Panes whose turns are checkpointed, by pane id (the manifest's
+    /// `checkpoints:`)
+    pub checkpoints: Vec<String>,
 }
 
 impl Default for ServerConfig {
@@ -40,14 +102,33 @@ impl Default for ServerConfig {
             port: 4318,
             session: String::new(),
             log_path: PathBuf::from(".axel/events.jsonl"),
+            storage: EventStorage::default(),
+            retention: LogRetention::default(),
+            metrics: false,
+            notifications: None,
+            pane_names: HashMap::new(),
+            orchestrator: None,
+            budgets: HashMap::new(),
+            stuck: HashMap::new(),
+            policies: Vec::new(),
+            checkpoints: Vec::new(),
         }
     }
 }
 
 /// Run the event server
 pub async fn run_server(config: ServerConfig) -> Result<()> {
-    // Create the event logger
-    let logger = EventLogger::new(config.log_path.clone()).await?;
+    // Open the SQLite store, if any, and create the event logger
+    let store = match &config.storage {
+        EventStorage::Jsonl => None,
+        EventStorage::Sqlite(path) => Some(EventStore::open(path)?),
+    };
+    let logger = EventLogger::new(
+        config.log_path.clone(),
+        store.clone(),
+        config.retention.clone(),
+    )
+    .await?;
 
     // Create broadcast channel for SSE subscribers (buffer 100 events)
     let (inbox_tx, _) = broadcast::channel(100);
@@ -59,13 +140,58 @@ pub async fn run_server(config: ServerConfig) -> Result<()> {
         Some(config.session.clone())
     };
 
+    // Panes recorded by the session, for outbox responses naming a pane
+    let pane_ids = tmux_session
+        .as_deref()
+        .map(session_pane_map)
+        .unwrap_or_default();
+
+    // Sessions already mapped to panes before a restart
+    let state_path = state_path(&config.log_path);
+    let saved = ServerState::load(&state_path).unwrap_or_else(|e| {
+        eprintln!("[state] {:#}; starting without saved panes", e);
+        ServerState::default()
+    });
+
+    let state_tx = spawn_state_writer(state_path, saved.clone());
+
     let state = AppState {
         event_tx: logger.sender(),
         inbox_tx,
         tmux_session,
-        session_to_pane: Arc::new(RwLock::new(HashMap::new())),
+        session_to_pane: Arc::new(RwLock::new(saved.session_to_pane)),
+        state_tx,
+        pane_ids: Arc::new(RwLock::new(pane_ids)),
+        store,
+        log_path: config.log_path.clone(),
+        usage: Arc::new(RwLock::new(UsageTracker::new())),
+        pricing: Arc::new(Pricing::load()?),
+        metrics: config.metrics.then(|| Arc::new(Metrics::new())),
+        notifier: config
+            .notifications
+            .clone()
+            .or_else(|| {
+                (!config.budgets.is_empty() || !config.stuck.is_empty())
+                    .then(NotificationsConfig::alerts_only)
+            })
+            .map(|n| Arc::new(Notifier::new(n, config.pane_names.clone()))),
+        orchestrator: config.orchestrator.clone().map(Arc::new),
+        budgets: (!config.budgets.is_empty())
+            .then(|| Arc::new(Mutex::new(BudgetTracker::new(config.budgets.clone())))),
+        stuck: (!config.stuck.is_empty())
+            .then(|| Arc::new(Mutex::new(StuckTracker::new(config.stuck.clone())))),
+        policies: Some(PolicySet::compile(&config.policies)?)
+            .filter(|p| !p.is_empty())
+            .map(Arc::new),
+        checkpoints: (!config.checkpoints.is_empty())
+            .then(|| Arc::new(config.checkpoints.iter().cloned().collect())),
     };
 
+    // Check watched panes for stalls in the background
+    if state.stuck.is_some() {
+        tokio::spawn(stuck_watchdog(state.clone()));
+    }
+
     // Build the router
     let app = create_router(state);
 
@@ -100,7 +226,7 @@ async fn session_watchdog(session: String, shutdown_tx: watch::Sender<bool>) {
         tokio::time::sleep(Duration::from_secs(5)).await;
 
         // Check if the session still exists
-        let output = Command::new("tmux")
+        let output = tmux_command()
             .args(["has-session", "-t", &session])
             .output();
 
@@ -0,0 +1,368 @@
+//! Desktop notifications and webhooks for events that need attention.
+//!
+//! Configured in the manifest:
+//!
+//! ```yaml
+//! notifications:
+//!   events: [PermissionRequest, Stop, BudgetExceeded, Stuck]  # default
+//!   sound: Glass                       # macOS sound name (optional)
+//!   webhooks:
+//!     - url: https://hooks.slack.com/services/...
+//!       kind: slack                    # slack, discord or generic
+//!       events: [Stop]                 # defaults to `events` above
+//!       template: "{pane} is done: {message}"
+//! ```
+//!
+//! Events are matched on the hook's `hook_event_name` (Claude hooks and the
+//! tmux bell hook that detects Codex approvals both send one). Notifications
+//! go through `osascript` on macOS and `notify-send` elsewhere; webhooks are
+//! POSTed with curl.
+
+use std::{collections::HashMap, process::Command};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+use super::events::{BUDGET_EXCEEDED, STUCK, TimestampedEvent};
+
+/// `notifications:` section of the manifest
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NotificationsConfig {
+    /// Show desktop notifications (default true)
+    #[serde(default = "default_true")]
+    pub desktop: bool,
+    /// Hook events that trigger a notification
+    #[serde(default = "default_events")]
+    pub events: Vec<String>,
+    /// Sound played with macOS notifications
+    #[serde(default)]
+    pub sound: Option<String>,
+    /// HTTP endpoints events are forwarded to
+    #[serde(default)]
+    pub webhooks: Vec<WebhookConfig>,
+}
+
+/// One webhook under `notifications.webhooks`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookConfig {
+    /// Endpoint to POST to
+    pub url: String,
+    /// Payload format (default generic)
+    #[serde(default)]
+    pub kind: WebhookKind,
+    /// Events to forward (defaults to `notifications.events`)
+    #[serde(default)]
+    pub events: Option<Vec<String>>,
+    /// Message template; `{title}`, `{message}`, `{pane}`, `{pane_id}` and
+    /// `{event}` are substituted
+    #[serde(default)]
+    pub template: Option<String>,
+}
+
+/// Payload format of a webhook
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WebhookKind {
+    /// `{"text": message}`
+    Slack,
+    /// `{"content": message}`
+    Discord,
+    /// `{"message": message, "event": <logged event>}`
+    #[default]
+    Generic,
+}
+
+impl Default for NotificationsConfig {
+    fn default() -> Self {
+        Self {
+            desktop: true,
+            events: default_events(),
+            sound: None,
+            webhooks: Vec::new(),
+        }
+    }
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_events() -> Vec<String> {
+    vec![
+        "PermissionRequest".to_string(),
+        "Stop".to_string(),
+        BUDGET_EXCEEDED.to_string(),
+        STUCK.to_string(),
+    ]
+}
+
+impl NotificationsConfig {
+    /// Desktop notifications for the server's own alerts only (panes over
+    /// budget or stuck), used when the manifest watches panes but has no
+    /// `notifications:`
+    pub fn alerts_only() -> Self {
+        Self {
+            events: vec![BUDGET_EXCEEDED.to_string(), STUCK.to_string()],
+            ..Default::default()
+        }
+    }
+}
+
+/// A notification ready to show
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Notification {
+    pub title: String,
+    pub body: String,
+}
+
+/// Turns incoming events into desktop notifications
+#[derive(Debug, Clone)]
+pub struct Notifier {
+    config: NotificationsConfig,
+    /// Display names of known panes, by pane id
+    pane_names: HashMap<String, String>,
+}
+
+impl Notifier {
+    pub fn new(config: NotificationsConfig, pane_names: HashMap<String, String>) -> Self {
+        Self { config, pane_names }
+    }
+
+    /// The desktop notification `event` should raise, if any
+    pub fn notification(&self, event: &TimestampedEvent) -> Option<Notification> {
+        let name = event_name(event);
+        if !self.config.events.iter().any(|e| e == name) {
+            return None;
+        }
+        Some(self.describe(name, event))
+    }
+
+    /// Title and body describing `event`
+    fn describe(&self, name: &str, event: &TimestampedEvent) -> Notification {
+        let field = |key: &str| event.event.get(key).and_then(|v| v.as_str());
+        let body = match name {
+            "PermissionRequest" => {
+                let tool = field("tool_name").unwrap_or("a tool");
+                let detail = event.event.get("tool_input").and_then(|input| {
+                    ["command", "file_path", "url"]
+                        .iter()
+                        .find_map(|key| input.get(*key).and_then(|v| v.as_str()))
+                });
+                match detail {
+                    Some(detail) => format!("Needs approval for {}: {}", tool, detail),
+                    None => format!("Needs approval for {}", tool),
+                }
+            }
+            "Stop" => "Finished its turn".to_string(),
+            _ => field("message").unwrap_or(name).to_string(),
+        };
+
+        Notification {
+            title: format!("axel · {}", self.pane_name(&event.pane_id)),
+            body,
+        }
+    }
+
+    /// Display name of a pane, or a short id for unknown panes
+    fn pane_name(&self, pane_id: &str) -> String {
+        self.pane_names
+            .get(pane_id)
+            .cloned()
+            .unwrap_or_else(|| pane_id[..8.min(pane_id.len())].to_string())
+    }
+
+    /// Webhook requests `event` should trigger, as (url, JSON body) pairs
+    pub fn webhook_requests(&self, event: &TimestampedEvent) -> Vec<(String, Value)> {
+        let name = event_name(event);
+        self.config
+            .webhooks
+            .iter()
+            .filter(|hook| {
+                let events = hook.events.as_ref().unwrap_or(&self.config.events);
+                events.iter().any(|e| e == name)
+            })
+            .map(|hook| {
+                let notification = self.describe(name, event);
+                let message = match &hook.template {
+                    Some(template) => template
+                        .replace("{title}", &notification.title)
+                        .replace("{message}", &notification.body)
+                        .replace("{pane}", &self.pane_name(&event.pane_id))
+                        .replace("{pane_id}", &event.pane_id)
+                        .replace("{event}", name),
+                    None => format!("{}: {}", notification.title, notification.body),
+                };
+                let body = match hook.kind {
+                    WebhookKind::Slack => json!({ "text": message }),
+                    WebhookKind::Discord => json!({ "content": message }),
+                    WebhookKind::Generic => json!({ "message": message, "event": event }),
+                };
+                (hook.url.clone(), body)
+            })
+            .collect()
+    }
+
+    /// Show the desktop notification and call the webhooks for `event`,
+    /// without blocking
+    pub fn notify(&self, event: &TimestampedEvent) {
+        let notification = self.notification(event).filter(|_| self.config.desktop);
+        let requests = self.webhook_requests(event);
+        if notification.is_none() && requests.is_empty() {
+            return;
+        }
+
+        let sound = self.config.sound.clone();
+        std::thread::spawn(move || {
+            if let Some(notification) = notification
+                && let Err(e) = show(&notification, sound.as_deref())
+            {
+                eprintln!("[notify] Failed to show notification: {}", e);
+            }
+            for (url, body) in requests {
+                if let Err(e) = post_json(&url, &body) {
+                    eprintln!("[notify] Failed to call webhook: {}", e);
+                }
+            }
+        });
+    }
+}
+
+/// Event name used for matching: the hook's `hook_event_name`, falling back
+/// to the logged event type
+fn event_name(event: &TimestampedEvent) -> &str {
+    event
+        .event
+        .get("hook_event_name")
+        .and_then(|v| v.as_str())
+        .unwrap_or(&event.event_type)
+}
+
+/// POST a JSON body with curl
+fn post_json(url: &str, body: &Value) -> std::io::Result<()> {
+    Command::new("curl")
+        .args([
+            "-s",
+            "-m",
+            "10",
+            "-X",
+            "POST",
+            "-H",
+            "Content-Type: application/json",
+        ])
+        .args(["-d", &body.to_string(), url])
+        .stdout(std::process::Stdio::null())
+        .status()?;
+    Ok(())
+}
+
+/// Display a notification with the platform's notifier
+fn show(notification: &Notification, sound: Option<&str>) -> std::io::Result<()> {
+    if cfg!(target_os = "macos") {
+        let mut script = format!(
+            "display notification \"{}\" with title \"{}\"",
+            applescript_escape(&notification.body),
+            applescript_escape(&notification.title)
+        );
+        if let Some(sound) = sound {
+            script.push_str(&format!(" sound name \"{}\"", applescript_escape(sound)));
+        }
+        Command::new("osascript").args(["-e", &script]).status()?;
+    } else {
+        Command::new("notify-send")
+            .args(["--app-name=axel", &notification.title, &notification.body])
+            .status()?;
+    }
+    Ok(())
+}
+
+/// Escape a string for an AppleScript string literal
+fn applescript_escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_notification_for_hook_events() {
+        let notifier = Notifier::new(
+            NotificationsConfig::default(),
+            HashMap::from([("pane-1".to_string(), "claude".to_string())]),
+        );
+        let event = |payload| TimestampedEvent::new("unknown_hook", "pane-1", payload);
+
+        assert_eq!(
+            notifier.notification(&event(json!({
+                "hook_event_name": "PermissionRequest",
+                "tool_name": "Bash",
+                "tool_input": { "command": "rm -rf target" },
+            }))),
+            Some(Notification {
+                title: "axel · claude".to_string(),
+                body: "Needs approval for Bash: rm -rf target".to_string(),
+            })
+        );
+        assert!(
+            notifier
+                .notification(&event(json!({ "hook_event_name": "PreToolUse" })))
+                .is_none()
+        );
+
+        // Unknown panes fall back to a short id
+        let stop = TimestampedEvent::new("Stop", "0123456789abcdef", json!({}));
+        assert_eq!(
+            notifier.notification(&stop).unwrap().title,
+            "axel · 01234567"
+        );
+    }
+
+    #[test]
+    fn test_webhook_requests() {
+        let config: NotificationsConfig = serde_yaml::from_str(
+            r#"
+desktop: false
+webhooks:
+  - url: https://slack.example/hook
+    kind: slack
+    events: [Stop]
+    template: "{pane} is done ({event})"
+  - url: https://example.com/generic
+"#,
+        )
+        .unwrap();
+        let notifier = Notifier::new(
+            config,
+            HashMap::from([("pane-1".to_string(), "claude".to_string())]),
+        );
+
+        let stop = TimestampedEvent::new(
+            "unknown_hook",
+            "pane-1",
+            json!({ "hook_event_name": "Stop" }),
+        );
+        let requests = notifier.webhook_requests(&stop);
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[0].0, "https://slack.example/hook");
+        assert_eq!(requests[0].1, json!({ "text": "claude is done (Stop)" }));
+        assert_eq!(requests[1].1["message"], "axel · claude: Finished its turn");
+        assert_eq!(requests[1].1["event"]["pane_id"], "pane-1");
+
+        // The generic hook inherits the default events; Slack only wants Stop
+        let permission = TimestampedEvent::new(
+            "unknown_hook",
+            "pane-1",
+            json!({ "hook_event_name": "PermissionRequest", "tool_name": "Bash" }),
+        );
+        let urls: Vec<String> = notifier
+            .webhook_requests(&permission)
+            .into_iter()
+            .map(|(url, _)| url)
+            .collect();
+        assert_eq!(urls, vec!["https://example.com/generic"]);
+    }
+}
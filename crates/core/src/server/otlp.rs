@@ -0,0 +1,107 @@
+//! OTLP request decoding.
+//!
+//! OTLP/HTTP exporters send either JSON (`application/json`) or binary
+//! protobuf (`application/x-protobuf`). Protobuf payloads are decoded and
+//! re-encoded as OTLP JSON, so everything downstream (the event log, usage
+//! tracking, session correlation) only ever sees the JSON form.
+
+use anyhow::Result;
+use axum::http::{HeaderMap, header};
+use opentelemetry_proto::tonic::collector::{
+    logs::v1::ExportLogsServiceRequest, metrics::v1::ExportMetricsServiceRequest,
+    trace::v1::ExportTraceServiceRequest,
+};
+use prost::Message;
+use serde_json::Value;
+
+use super::events::OtelEventType;
+
+/// Content type of binary OTLP payloads
+const PROTOBUF_CONTENT_TYPE: &str = "application/x-protobuf";
+
+/// Decode an OTLP request body into its JSON representation
+pub fn decode_otlp(signal: &OtelEventType, headers: &HeaderMap, body: &[u8]) -> Result<Value> {
+    let is_protobuf = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.starts_with(PROTOBUF_CONTENT_TYPE));
+    if !is_protobuf {
+        return Ok(serde_json::from_slice(body)?);
+    }
+
+    let value = match signal {
+        OtelEventType::Metrics => serde_json::to_value(ExportMetricsServiceRequest::decode(body)?)?,
+        OtelEventType::Traces => serde_json::to_value(ExportTraceServiceRequest::decode(body)?)?,
+        OtelEventType::Logs => serde_json::to_value(ExportLogsServiceRequest::decode(body)?)?,
+    };
+    Ok(value)
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use opentelemetry_proto::tonic::{
+        common::v1::{AnyValue, KeyValue, any_value},
+        metrics::v1::{
+            AggregationTemporality, Metric, NumberDataPoint, ResourceMetrics, ScopeMetrics, Sum,
+            metric, number_data_point,
+        },
+    };
+
+    use super::*;
+    use crate::server::{Pricing, TimestampedEvent, UsageTracker};
+
+    fn attribute(key: &str, value: &str) -> KeyValue {
+        KeyValue {
+            key: key.to_string(),
+            value: Some(AnyValue {
+                value: Some(any_value::Value::StringValue(value.to_string())),
+            }),
+        }
+    }
+
+    #[test]
+    fn test_protobuf_metrics_decode_to_otlp_json() {
+        let request = ExportMetricsServiceRequest {
+            resource_metrics: vec![ResourceMetrics {
+                scope_metrics: vec![ScopeMetrics {
+                    metrics: vec![Metric {
+                        name: "claude_code.token.usage".to_string(),
+                        data: Some(metric::Data::Sum(Sum {
+                            data_points: vec![NumberDataPoint {
+                                attributes: vec![
+                                    attribute("type", "input"),
+                                    attribute("model", "claude-sonnet-4-5"),
+                                    attribute("session.id", "abc"),
+                                ],
+                                value: Some(number_data_point::Value::AsDouble(42.0)),
+                                ..Default::default()
+                            }],
+                            aggregation_temporality: AggregationTemporality::Delta as i32,
+                            is_monotonic: true,
+                        })),
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+        };
+
+        let mut headers = HeaderMap::new();
+        headers.insert(header::CONTENT_TYPE, PROTOBUF_CONTENT_TYPE.parse().unwrap());
+        let payload =
+            decode_otlp(&OtelEventType::Metrics, &headers, &request.encode_to_vec()).unwrap();
+
+        let event = TimestampedEvent::new("otel_metrics", "pane", payload);
+        let report = UsageTracker::from_events([&event]).report(&Pricing::builtin());
+        assert_eq!(report.tokens.input_tokens, 42);
+
+        // JSON bodies pass through untouched
+        let json = decode_otlp(&OtelEventType::Logs, &HeaderMap::new(), br#"{"a":1}"#).unwrap();
+        assert_eq!(json, serde_json::json!({ "a": 1 }));
+    }
+}
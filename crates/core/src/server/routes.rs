@@ -1,22 +1,66 @@
 //! Axum route handlers for the event server.
 
-use std::{collections::HashMap, convert::Infallible, process::Command, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet},
+    convert::Infallible,
+    path::PathBuf,
+    sync::Arc,
+    time::Duration,
+};
 
 use axum::{
     Json, Router,
-    extract::{Path, State},
-    http::StatusCode,
+    body::Bytes,
+    extract::{
+        Path, Query, State,
+        ws::{Message, WebSocket, WebSocketUpgrade},
+    },
+    http::{HeaderMap, StatusCode, header},
     response::{
-        IntoResponse,
+        IntoResponse, Response,
         sse::{Event, KeepAlive, Sse},
     },
     routing::{get, post},
 };
-use futures_util::stream::Stream;
-use tokio::sync::{RwLock, broadcast, mpsc};
+use futures_util::{SinkExt, stream::Stream};
+use tokio::sync::{Mutex, RwLock, broadcast, mpsc, watch};
 use tokio_stream::{StreamExt, wrappers::BroadcastStream};
 
-use super::events::{HookEvent, OtelEventType, OutboxResponse, TimestampedEvent};
+use super::{
+    events::{
+        BUDGET_EXCEEDED, HookEventType, OtelEventType, OutboxResponse, STUCK, TimestampedEvent,
+    },
+    metrics::Metrics,
+    notify::Notifier,
+    otlp::decode_otlp,
+    state::ServerState,
+    store::{EventQuery, EventStore, query_log},
+    usage::{Pricing, UsageTracker},
+};
+use crate::{
+    audit::{FILE_DIFF_EVENT, capture_diff, written_file},
+    budget::BudgetTracker,
+    checkpoint::{CHECKPOINT_EVENT, create_checkpoint},
+    orchestrator::OrchestratorConfig,
+    policy::PolicySet,
+    stuck::StuckTracker,
+    tmux::{interrupt_pane, list_panes, paste_text, session_pane_map, tmux_command},
+};
+
+/// Event type logged when the orchestrator sends a reply on to another pane
+const PIPELINE_ROUTED: &str = "pipeline_routed";
+
+/// Event type logged when a pane goes over its budget
+const BUDGET_EXCEEDED_EVENT: &str = "budget_exceeded";
+
+/// Event type logged when a pane is stuck mid-task
+const STUCK_EVENT: &str = "stuck";
+
+/// Event type logged when a policy decides a tool call
+const POLICY_DECISION_EVENT: &str = "policy_decision";
+
+/// How often watched panes are checked for stalls
+const STUCK_CHECK_INTERVAL: Duration = Duration::from_secs(30);
 
 /// Shared application state
 #[derive(Clone)]
@@ -27,15 +71,51 @@ pub struct AppState {
     pub tmux_session: Option<String>,
     /// Mapping from Claude session_id to pane_id (for correlating OTEL metrics)
     pub session_to_pane: Arc<RwLock<HashMap<String, String>>>,
+    /// Saves `session_to_pane` for the next server (see [`ServerState`])
+    pub state_tx: watch::Sender<ServerState>,
+    /// Tmux pane IDs of `tmux_session`'s panes by name, for outbox
+    /// responses naming a pane
+    pub pane_ids: Arc<RwLock<HashMap<String, String>>>,
+    /// SQLite store answering `GET /events` (the JSONL log is scanned without one)
+    pub store: Option<EventStore>,
+    /// Path to the JSONL log file
+    pub log_path: PathBuf,
+    /// Token totals aggregated from OTEL events
+    pub usage: Arc<RwLock<UsageTracker>>,
+    /// Per-model prices for `GET /usage` cost estimates
+    pub pricing: Arc<Pricing>,
+    /// Prometheus counters, when `GET /metrics` is enabled
+    pub metrics: Option<Arc<Metrics>>,
+    /// Desktop notifications and webhooks for hook events
+    pub notifier: Option<Arc<Notifier>>,
+    /// Pipeline routing agent replies between panes of `tmux_session`
+    pub orchestrator: Option<Arc<OrchestratorConfig>>,
+    /// Budgets of the panes that have one, with their turns so far
+    pub budgets: Option<Arc<Mutex<BudgetTracker>>>,
+    /// Stuck thresholds of the panes that have one, with their activity
+    pub stuck: Option<Arc<Mutex<StuckTracker>>>,
+    /// Rules deciding `PreToolUse` hooks, when the manifest has some
+    pub policies: Option<Arc<PolicySet>>,
+    /// Panes whose turns are checkpointed, when the manifest has
+    /// `checkpoints: true`
+    pub checkpoints: Option<Arc<HashSet<String>>>,
 }
 
 /// Build the router with all routes
 pub fn create_router(state: AppState) -> Router {
-    Router::new()
+    let router = match state.metrics {
+        Some(_) => Router::new().route("/metrics", get(handle_metrics)),
+        None => Router::new(),
+    };
+    router
         .route("/health", get(health_check))
         .route("/inbox", get(handle_inbox_sse))
         .route("/outbox", post(handle_outbox))
+        .route("/ws", get(handle_ws))
+        .route("/events", get(handle_query_events))
         .route("/events/{pane_id}", post(handle_hook_event))
+        .route("/usage", get(handle_usage))
+        .route("/panes", get(handle_panes))
         // OTEL routes with pane_id for direct correlation
         .route("/v1/metrics/{pane_id}", post(handle_otel_metrics_with_pane))
         .route("/v1/traces/{pane_id}", post(handle_otel_traces_with_pane))
@@ -73,22 +153,161 @@ async fn handle_inbox_sse(
     Sse::new(stream).keep_alive(KeepAlive::default())
 }
 
+/// Query logged events (`GET /events?since=&pane=&type=&limit=`)
+async fn handle_query_events(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<EventQuery>,
+) -> impl IntoResponse {
+    let events = match &state.store {
+        Some(store) => {
+            let store = store.clone();
+            tokio::task::spawn_blocking(move || store.query(&query))
+                .await
+                .map_err(anyhow::Error::from)
+                .and_then(|r| r)
+        }
+        None => query_log(&state.log_path, &query).await,
+    };
+    match events {
+        Ok(events) => Json(events).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    }
+}
+
+/// Prometheus text exposition of the server's counters
+async fn handle_metrics(State(state): State<Arc<AppState>>) -> impl IntoResponse {
+    let Some(metrics) = &state.metrics else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+    let usage = state.usage.read().await.report(&state.pricing);
+    let active_sessions = state.session_to_pane.read().await.len();
+    (
+        [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+        metrics.render(&usage, active_sessions),
+    )
+        .into_response()
+}
+
+/// Token usage and estimated cost per pane and model
+async fn handle_usage(State(state): State<Arc<AppState>>) -> impl IntoResponse {
+    Json(state.usage.read().await.report(&state.pricing))
+}
+
+/// Panes the server correlates events with: the pane of each Claude
+/// session and the tmux pane ID of each named pane
+async fn handle_panes(State(state): State<Arc<AppState>>) -> impl IntoResponse {
+    Json(serde_json::json!({
+        "sessions": *state.session_to_pane.read().await,
+        "panes": *state.pane_ids.read().await,
+    }))
+}
+
+/// Bidirectional event stream (`GET /ws`)
+///
+/// Every logged event is sent to the client as a JSON text message, like
+/// `/inbox`. Text messages from the client are parsed as [`OutboxResponse`]s
+/// and delivered like `POST /outbox`; failures are reported back as
+/// `{"error": "..."}`.
+async fn handle_ws(State(state): State<Arc<AppState>>, ws: WebSocketUpgrade) -> impl IntoResponse {
+    ws.on_upgrade(move |socket| serve_ws(state, socket))
+}
+
+async fn serve_ws(state: Arc<AppState>, socket: WebSocket) {
+    let (mut sink, mut stream) = futures_util::StreamExt::split(socket);
+    let (reply_tx, mut reply_rx) = mpsc::channel::<String>(16);
+
+    // Forward broadcast events and replies to the client
+    let mut events = BroadcastStream::new(state.inbox_tx.subscribe());
+    let mut send_task = tokio::spawn(async move {
+        loop {
+            let text = tokio::select! {
+                event = events.next() => match event {
+                    Some(Ok(event)) => match serde_json::to_string(&event) {
+                        Ok(json) => json,
+                        Err(_) => continue,
+                    },
+                    Some(Err(_)) => continue, // Skip lagged messages
+                    None => break,
+                },
+                reply = reply_rx.recv() => match reply {
+                    Some(reply) => reply,
+                    None => break,
+                },
+            };
+            if sink.send(Message::Text(text.into())).await.is_err() {
+                break;
+            }
+        }
+    });
+
+    // Deliver outbox responses sent by the client
+    let mut recv_task = tokio::spawn(async move {
+        while let Some(Ok(message)) = stream.next().await {
+            let text = match message {
+                Message::Text(text) => text,
+                Message::Close(_) => break,
+                _ => continue,
+            };
+            let error = match serde_json::from_str::<OutboxResponse>(&text) {
+                Ok(response) => match deliver_outbox(&state, response).await {
+                    (status, _) if status.is_success() => continue,
+                    (_, message) => message.to_string(),
+                },
+                Err(e) => format!("Invalid outbox response: {}", e),
+            };
+            let reply = serde_json::json!({ "error": error }).to_string();
+            if reply_tx.send(reply).await.is_err() {
+                break;
+            }
+        }
+    });
+
+    // Whichever side finishes first closes the connection
+    tokio::select! {
+        _ = &mut send_task => recv_task.abort(),
+        _ = &mut recv_task => send_task.abort(),
+    }
+}
+
 /// Handle Claude Code hook events
+///
+/// A `PreToolUse` hook matching one of the policies is answered with the
+/// policy's decision, which Claude Code reads from the hook's output. A
+/// `PostToolUse` hook of a tool writing a file also gets the file's diff
+/// logged, and a `Stop` of a checkpointed pane a checkpoint commit.
 async fn handle_hook_event(
     State(state): State<Arc<AppState>>,
     Path(pane_id): Path<String>,
     Json(payload): Json<serde_json::Value>,
-) -> impl IntoResponse {
-    // Try to parse as a HookEvent to get the event type
-    let event_type = match serde_json::from_value::<HookEvent>(payload.clone()) {
-        Ok(hook_event) => hook_event.event_type.to_string(),
-        Err(_) => "unknown_hook".to_string(),
+) -> Response {
+    // Event type from the payload's `type` or Claude Code's hook_event_name
+    let hook_type = HookEventType::of_payload(&payload);
+    let decision = match (&state.policies, &hook_type) {
+        (Some(policies), Some(HookEventType::PreToolUse)) => policies.decide(&payload),
+        _ => None,
+    };
+    // A `PostToolUse` of a tool writing a file gets the file's diff logged
+    let audited =
+        matches!(hook_type, Some(HookEventType::PostToolUse)) && written_file(&payload).is_some();
+    let event_type = match hook_type {
+        Some(event_type) => event_type.to_string(),
+        None => "unknown_hook".to_string(),
     };
 
     // Extract session_id from payload and store mapping for OTEL correlation
+    // (saved when it changes, so a restarted server still has it)
     if let Some(session_id) = payload.get("session_id").and_then(|v| v.as_str()) {
         let mut mapping = state.session_to_pane.write().await;
-        mapping.insert(session_id.to_string(), pane_id.clone());
+        if mapping
+            .insert(session_id.to_string(), pane_id.clone())
+            .as_ref()
+            != Some(&pane_id)
+        {
+            // Sent under the lock, so the writer sees snapshots in order
+            state.state_tx.send_replace(ServerState {
+                session_to_pane: mapping.clone(),
+            });
+        }
     } else {
         // Log what keys ARE in the payload for debugging
         let _keys: Vec<&str> = payload
@@ -97,17 +316,307 @@ async fn handle_hook_event(
             .unwrap_or_default();
     }
 
+    if let Some(metrics) = &state.metrics {
+        metrics.hook_event(&event_type);
+    }
+
     let event = TimestampedEvent::new(event_type, pane_id, payload);
 
     // Send to file logger
     if state.event_tx.send(event.clone()).await.is_err() {
-        return (StatusCode::INTERNAL_SERVER_ERROR, "Failed to log event");
+        return (StatusCode::INTERNAL_SERVER_ERROR, "Failed to log event").into_response();
+    }
+
+    if let Some(notifier) = &state.notifier {
+        notifier.notify(&event);
+    }
+    if let Some(orchestrator) = &state.orchestrator {
+        route_reply(&state, orchestrator.clone(), event.clone());
+    }
+    watch_pane(&state, &event).await;
+    if audited {
+        log_file_diff(&state, event.clone());
+    }
+    if let Some(checkpoints) = &state.checkpoints
+        && checkpoints.contains(&event.pane_id)
+        && event.hook_name() == Some("Stop")
+    {
+        log_checkpoint(&state, event.clone());
     }
 
     // Broadcast to SSE subscribers (ignore errors if no subscribers)
-    let _ = state.inbox_tx.send(event);
+    let _ = state.inbox_tx.send(event.clone());
 
-    (StatusCode::OK, "OK")
+    if let Some(decision) = decision {
+        log_policy_decision(&state, &event, &decision).await;
+        return (StatusCode::OK, Json(decision)).into_response();
+    }
+    (StatusCode::OK, "OK").into_response()
+}
+
+/// Log and broadcast the decision a policy made on the tool call of `event`
+async fn log_policy_decision(
+    state: &AppState,
+    event: &TimestampedEvent,
+    decision: &serde_json::Value,
+) {
+    let output = &decision["hookSpecificOutput"];
+    let logged = TimestampedEvent::new(
+        POLICY_DECISION_EVENT,
+        event.pane_id.clone(),
+        serde_json::json!({
+            "tool_name": event.event.get("tool_name"),
+            "tool_input": event.event.get("tool_input"),
+            "decision": output["permissionDecision"],
+            "message": output["permissionDecisionReason"],
+        }),
+    );
+    let _ = state.event_tx.send(logged.clone()).await;
+    let _ = state.inbox_tx.send(logged);
+}
+
+/// Log the diff of the file written by the tool call of `event` as a
+/// `file_diff` event (see [`crate::audit`]).
+///
+/// Runs in the background, as capturing the diff shells out to git.
+fn log_file_diff(state: &AppState, event: TimestampedEvent) {
+    let event_tx = state.event_tx.clone();
+    let inbox_tx = state.inbox_tx.clone();
+
+    tokio::task::spawn_blocking(move || {
+        let Some(diff) = capture_diff(&event.event) else {
+            return;
+        };
+        let Ok(diff) = serde_json::to_value(diff) else {
+            return;
+        };
+        let logged = TimestampedEvent::new(FILE_DIFF_EVENT, event.pane_id, diff);
+        let _ = event_tx.blocking_send(logged.clone());
+        let _ = inbox_tx.send(logged);
+    });
+}
+
+/// Checkpoint the working tree the pane of `event` works in, after its
+/// turn, and log it as a `checkpoint` event (see [`crate::checkpoint`]).
+///
+/// The tree is the payload's `cwd`, or else the server's directory. Runs
+/// in the background, as checkpointing shells out to git.
+fn log_checkpoint(state: &AppState, event: TimestampedEvent) {
+    let event_tx = state.event_tx.clone();
+    let inbox_tx = state.inbox_tx.clone();
+
+    tokio::task::spawn_blocking(move || {
+        let dir = match event.event.get("cwd").and_then(|v| v.as_str()) {
+            Some(cwd) => PathBuf::from(cwd),
+            None => PathBuf::from("."),
+        };
+        let checkpoint = match create_checkpoint(&dir, &event.pane_id) {
+            Ok(Some(checkpoint)) => checkpoint,
+            Ok(None) => return,
+            Err(e) => {
+                eprintln!("[checkpoint] Failed for '{}': {}", event.pane_id, e);
+                return;
+            }
+        };
+        let Ok(checkpoint) = serde_json::to_value(checkpoint) else {
+            return;
+        };
+        let logged = TimestampedEvent::new(CHECKPOINT_EVENT, event.pane_id, checkpoint);
+        let _ = event_tx.blocking_send(logged.clone());
+        let _ = inbox_tx.send(logged);
+    });
+}
+
+/// Paste an agent's final reply into the panes the pipeline routes it to.
+///
+/// Runs in the background: the reply may come from a transcript file and
+/// pasting shells out to tmux. Each delivery is logged as a
+/// `pipeline_routed` event.
+fn route_reply(state: &AppState, orchestrator: Arc<OrchestratorConfig>, event: TimestampedEvent) {
+    let Some(session) = state.tmux_session.clone() else {
+        return;
+    };
+    let event_tx = state.event_tx.clone();
+    let inbox_tx = state.inbox_tx.clone();
+
+    tokio::task::spawn_blocking(move || {
+        for delivery in orchestrator.route(&event) {
+            let pane = list_panes(&session)
+                .unwrap_or_default()
+                .into_iter()
+                .find(|pane| pane.title == delivery.pane);
+            let Some(pane) = pane else {
+                eprintln!(
+                    "[orchestrator] Pane '{}' not found in session {}",
+                    delivery.pane, session
+                );
+                continue;
+            };
+            if let Err(e) = paste_text(&pane.id, &delivery.prompt) {
+                eprintln!(
+                    "[orchestrator] Failed to send to '{}': {}",
+                    delivery.pane, e
+                );
+                continue;
+            }
+
+            let routed = TimestampedEvent::new(
+                PIPELINE_ROUTED,
+                delivery.pane,
+                serde_json::json!({
+                    "from": delivery.from,
+                    "to": delivery.to,
+                    "prompt": delivery.prompt,
+                }),
+            );
+            let _ = event_tx.blocking_send(routed.clone());
+            let _ = inbox_tx.send(routed);
+        }
+    });
+}
+
+/// Track the activity of the pane `event` came from, and interrupt it if
+/// it just went over its budget.
+///
+/// Escape is pressed in the pane (see [`pane_target`]), and a
+/// `budget_exceeded` event is logged and notified.
+async fn watch_pane(state: &AppState, event: &TimestampedEvent) {
+    if let Some(stuck) = &state.stuck {
+        stuck.lock().await.record(event);
+    }
+    let Some(budgets) = &state.budgets else {
+        return;
+    };
+    let reason = {
+        let usage = state.usage.read().await;
+        budgets.lock().await.record(event, &usage, &state.pricing)
+    };
+    let Some(reason) = reason else {
+        return;
+    };
+    eprintln!("[budget] Pane '{}' {}", event.pane_id, reason);
+
+    let target = pane_target(state, &event.pane_id).await;
+    tokio::task::spawn_blocking(move || {
+        if let Err(e) = interrupt_pane(&target) {
+            eprintln!("[budget] Failed to interrupt '{}': {}", target, e);
+        }
+    });
+
+    raise_alert(
+        state,
+        BUDGET_EXCEEDED_EVENT,
+        BUDGET_EXCEEDED,
+        &event.pane_id,
+        format!("Interrupted: {}", reason),
+    )
+    .await;
+}
+
+/// Report watched panes that went quiet mid-task, every
+/// [`STUCK_CHECK_INTERVAL`].
+///
+/// Each stuck pane gets a `stuck` event, logged and notified, and its
+/// `nudge` prompt pasted in.
+pub(super) async fn stuck_watchdog(state: AppState) {
+    let Some(stuck) = state.stuck.clone() else {
+        return;
+    };
+    loop {
+        tokio::time::sleep(STUCK_CHECK_INTERVAL).await;
+        let panes = stuck.lock().await.stuck(chrono::Utc::now());
+        for pane in panes {
+            eprintln!(
+                "[stuck] Pane '{}' has been quiet for {} minutes",
+                pane.pane_id, pane.idle_minutes
+            );
+            if let Some(nudge) = pane.nudge {
+                let target = pane_target(&state, &pane.pane_id).await;
+                tokio::task::spawn_blocking(move || {
+                    if let Err(e) = paste_text(&target, &nudge) {
+                        eprintln!("[stuck] Failed to nudge '{}': {}", target, e);
+                    }
+                });
+            }
+            raise_alert(
+                &state,
+                STUCK_EVENT,
+                STUCK,
+                &pane.pane_id,
+                format!("No activity for {} minutes", pane.idle_minutes),
+            )
+            .await;
+        }
+    }
+}
+
+/// Tmux target of a watched pane: the pane of the server's session named
+/// `pane_id` (see [`resolve_pane`]), or the pane id itself
+async fn pane_target(state: &AppState, pane_id: &str) -> String {
+    let resolved = match &state.tmux_session {
+        Some(session) => resolve_pane(state, session, pane_id).await,
+        None => None,
+    };
+    resolved.unwrap_or_else(|| pane_id.to_string())
+}
+
+/// Log, notify and broadcast an event the server raises about a pane
+async fn raise_alert(
+    state: &AppState,
+    event_type: &str,
+    hook_name: &str,
+    pane_id: &str,
+    message: String,
+) {
+    let alert = TimestampedEvent::new(
+        event_type,
+        pane_id,
+        serde_json::json!({
+            "hook_event_name": hook_name,
+            "message": message,
+        }),
+    );
+    let _ = state.event_tx.send(alert.clone()).await;
+    if let Some(notifier) = &state.notifier {
+        notifier.notify(&alert);
+    }
+    let _ = state.inbox_tx.send(alert);
+}
+
+/// Tmux pane ID of the pane named `name` in `session`.
+///
+/// The ID recorded for `name` is used while that pane still exists, so
+/// layout changes and renamed titles don't matter. Otherwise the session's
+/// recorded panes (see [`session_pane_map`]) are read again, and then pane
+/// titles are searched; what is found is recorded for next time.
+async fn resolve_pane(state: &AppState, session: &str, name: &str) -> Option<String> {
+    let listed = session.to_string();
+    let panes = tokio::task::spawn_blocking(move || list_panes(&listed))
+        .await
+        .ok()?
+        .ok()?;
+    let exists = |id: &String| panes.iter().any(|pane| pane.id == *id);
+
+    let known = state.pane_ids.read().await.get(name).cloned();
+    if let Some(id) = known.filter(exists) {
+        return Some(id);
+    }
+    let mapped = session.to_string();
+    let recorded = tokio::task::spawn_blocking(move || session_pane_map(&mapped))
+        .await
+        .unwrap_or_default();
+    let id = recorded.get(name).cloned().filter(exists).or_else(|| {
+        panes
+            .iter()
+            .find(|pane| pane.title == name)
+            .map(|pane| pane.id.clone())
+    })?;
+    state
+        .pane_ids
+        .write()
+        .await
+        .insert(name.to_string(), id.clone());
+    Some(id)
 }
 
 /// Handle outbox responses from macOS app
@@ -115,6 +624,11 @@ async fn handle_outbox(
     State(state): State<Arc<AppState>>,
     Json(payload): Json<OutboxResponse>,
 ) -> impl IntoResponse {
+    deliver_outbox(&state, payload).await
+}
+
+/// Log an outbox response and inject it into the target pane
+async fn deliver_outbox(state: &AppState, payload: OutboxResponse) -> (StatusCode, &'static str) {
     let event_type = payload.response_type.to_string();
     let session_id = payload.session_id.clone();
     let response_text = payload.response_text.clone();
@@ -141,14 +655,24 @@ async fn handle_outbox(
         // Tmux mode: send keys to the appropriate pane
         let target = if let Some(ref pane_id) = payload.pane_id {
             pane_id.clone()
+        } else if let Some(ref pane) = payload.pane {
+            match resolve_pane(state, tmux_session, pane).await {
+                Some(pane_id) => pane_id,
+                None => return (StatusCode::NOT_FOUND, "Pane not found"),
+            }
         } else {
-            // Default to first pane in the session (pane 0.0)
-            // Skip pane 0 which is the server pane, target pane 1
-            format!("{}:0.1", tmux_session)
+            // The pane the Claude session reports from, if it has a name
+            let reporting = state.session_to_pane.read().await.get(&session_id).cloned();
+            let resolved = match reporting {
+                Some(pane) => resolve_pane(state, tmux_session, &pane).await,
+                None => None,
+            };
+            // Default to the session's second pane: pane 0 is the server pane
+            resolved.unwrap_or_else(|| format!("{}:0.1", tmux_session))
         };
 
         // Send the response text literally (handles special chars, spaces, newlines)
-        let text_result = Command::new("tmux")
+        let text_result = tmux_command()
             .args(["send-keys", "-t", &target, "-l", &response_text])
             .output();
 
@@ -162,7 +686,7 @@ async fn handle_outbox(
 
         // Send Enter key to submit the prompt
         // Use C-m (Ctrl+M / carriage return) which works better with TUI apps like Codex
-        let enter_result = Command::new("tmux")
+        let enter_result = tmux_command()
             .args(["send-keys", "-t", &target, "C-m"])
             .output();
 
@@ -204,51 +728,57 @@ async fn handle_outbox(
 async fn handle_otel_metrics_with_pane(
     State(state): State<Arc<AppState>>,
     Path(pane_id): Path<String>,
-    Json(payload): Json<serde_json::Value>,
+    headers: HeaderMap,
+    body: Bytes,
 ) -> impl IntoResponse {
-    handle_otel_event_with_pane(state, OtelEventType::Metrics, pane_id, payload).await
+    handle_otel_event_with_pane(state, OtelEventType::Metrics, pane_id, &headers, &body).await
 }
 
 /// Handle OTEL traces with pane_id in URL
 async fn handle_otel_traces_with_pane(
     State(state): State<Arc<AppState>>,
     Path(pane_id): Path<String>,
-    Json(payload): Json<serde_json::Value>,
+    headers: HeaderMap,
+    body: Bytes,
 ) -> impl IntoResponse {
-    handle_otel_event_with_pane(state, OtelEventType::Traces, pane_id, payload).await
+    handle_otel_event_with_pane(state, OtelEventType::Traces, pane_id, &headers, &body).await
 }
 
 /// Handle OTEL logs with pane_id in URL
 async fn handle_otel_logs_with_pane(
     State(state): State<Arc<AppState>>,
     Path(pane_id): Path<String>,
-    Json(payload): Json<serde_json::Value>,
+    headers: HeaderMap,
+    body: Bytes,
 ) -> impl IntoResponse {
-    handle_otel_event_with_pane(state, OtelEventType::Logs, pane_id, payload).await
+    handle_otel_event_with_pane(state, OtelEventType::Logs, pane_id, &headers, &body).await
 }
 
 /// Handle OTEL metrics (legacy, without pane_id)
 async fn handle_otel_metrics(
     State(state): State<Arc<AppState>>,
-    Json(payload): Json<serde_json::Value>,
+    headers: HeaderMap,
+    body: Bytes,
 ) -> impl IntoResponse {
-    handle_otel_event(state, OtelEventType::Metrics, payload).await
+    handle_otel_event(state, OtelEventType::Metrics, &headers, &body).await
 }
 
 /// Handle OTEL traces (legacy, without pane_id)
 async fn handle_otel_traces(
     State(state): State<Arc<AppState>>,
-    Json(payload): Json<serde_json::Value>,
+    headers: HeaderMap,
+    body: Bytes,
 ) -> impl IntoResponse {
-    handle_otel_event(state, OtelEventType::Traces, payload).await
+    handle_otel_event(state, OtelEventType::Traces, &headers, &body).await
 }
 
 /// Handle OTEL logs (legacy, without pane_id)
 async fn handle_otel_logs(
     State(state): State<Arc<AppState>>,
-    Json(payload): Json<serde_json::Value>,
+    headers: HeaderMap,
+    body: Bytes,
 ) -> impl IntoResponse {
-    handle_otel_event(state, OtelEventType::Logs, payload).await
+    handle_otel_event(state, OtelEventType::Logs, &headers, &body).await
 }
 
 /// OTEL handler with pane_id directly from URL
@@ -256,9 +786,18 @@ async fn handle_otel_event_with_pane(
     state: Arc<AppState>,
     event_type: OtelEventType,
     pane_id: String,
-    payload: serde_json::Value,
-) -> impl IntoResponse {
+    headers: &HeaderMap,
+    body: &[u8],
+) -> (StatusCode, &'static str) {
+    let Ok(payload) = decode_otlp(&event_type, headers, body) else {
+        return (StatusCode::BAD_REQUEST, "Invalid OTLP payload");
+    };
     let event = TimestampedEvent::new(event_type.to_string(), pane_id, payload);
+    state.usage.write().await.record(&event);
+    if let Some(metrics) = &state.metrics {
+        metrics.otel_payload(&event.event_type);
+    }
+    watch_pane(&state, &event).await;
 
     // Send to file logger
     if state.event_tx.send(event.clone()).await.is_err() {
@@ -275,8 +814,13 @@ async fn handle_otel_event_with_pane(
 async fn handle_otel_event(
     state: Arc<AppState>,
     event_type: OtelEventType,
-    payload: serde_json::Value,
-) -> impl IntoResponse {
+    headers: &HeaderMap,
+    body: &[u8],
+) -> (StatusCode, &'static str) {
+    let Ok(payload) = decode_otlp(&event_type, headers, body) else {
+        return (StatusCode::BAD_REQUEST, "Invalid OTLP payload");
+    };
+
     // Try to extract session.id from OTEL payload to find the corresponding pane_id
     let session_id_opt = extract_otel_session_id(&payload);
 
@@ -306,6 +850,11 @@ async fn handle_otel_event(
     };
 
     let event = TimestampedEvent::new(event_type.to_string(), pane_id, payload);
+    state.usage.write().await.record(&event);
+    if let Some(metrics) = &state.metrics {
+        metrics.otel_payload(&event.event_type);
+    }
+    watch_pane(&state, &event).await;
 
     // Send to file logger
     if state.event_tx.send(event.clone()).await.is_err() {
@@ -351,3 +900,179 @@ fn extract_otel_session_id(payload: &serde_json::Value) -> Option<String> {
     }
     None
 }
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use tokio::net::{TcpListener, TcpStream};
+    use tokio_tungstenite::{
+        MaybeTlsStream, WebSocketStream, connect_async, tungstenite::Message as WsMessage,
+    };
+
+    use super::*;
+    use crate::tmux::use_socket;
+
+    type Client = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+    fn test_state(tmux_session: Option<String>) -> (AppState, mpsc::Receiver<TimestampedEvent>) {
+        let (event_tx, event_rx) = mpsc::channel(16);
+        let state = AppState {
+            event_tx,
+            inbox_tx: broadcast::channel(16).0,
+            tmux_session,
+            session_to_pane: Arc::default(),
+            state_tx: watch::channel(ServerState::default()).0,
+            pane_ids: Arc::default(),
+            store: None,
+            log_path: PathBuf::from("events.jsonl"),
+            usage: Arc::default(),
+            pricing: Arc::default(),
+            metrics: None,
+            notifier: None,
+            orchestrator: None,
+            budgets: None,
+            stuck: None,
+            policies: None,
+            checkpoints: None,
+        };
+        (state, event_rx)
+    }
+
+    /// Serve `state` on a free port and connect to its `/ws`, returning once
+    /// the server side listens for events
+    async fn connect(state: AppState) -> Client {
+        let inbox_tx = state.inbox_tx.clone();
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, create_router(state)).await });
+
+        let (client, _) = connect_async(format!("ws://{addr}/ws")).await.unwrap();
+        while inbox_tx.receiver_count() == 0 {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        client
+    }
+
+    /// Next text message from the server, as JSON
+    async fn next_json(client: &mut Client) -> serde_json::Value {
+        let message = tokio::time::timeout(Duration::from_secs(5), client.next())
+            .await
+            .expect("no message from /ws")
+            .unwrap()
+            .unwrap();
+        match message {
+            WsMessage::Text(text) => serde_json::from_str(&text).unwrap(),
+            other => panic!("unexpected message {other:?}"),
+        }
+    }
+
+    fn response(pane: &str, text: &str) -> WsMessage {
+        let response = serde_json::json!({
+            "session_id": "s1",
+            "response_type": "question_response",
+            "response_text": text,
+            "pane": pane,
+        });
+        WsMessage::Text(response.to_string().into())
+    }
+
+    #[tokio::test]
+    async fn test_ws_forwards_inbox_events() {
+        let (state, _event_rx) = test_state(None);
+        let inbox_tx = state.inbox_tx.clone();
+        let mut client = connect(state).await;
+
+        let payload = serde_json::json!({ "tool_name": "Bash" });
+        inbox_tx
+            .send(TimestampedEvent::new("pre_tool_use", "claude", payload))
+            .unwrap();
+        let event = next_json(&mut client).await;
+        assert_eq!(event["event_type"], "pre_tool_use");
+        assert_eq!(event["pane_id"], "claude");
+        assert_eq!(event["event"]["tool_name"], "Bash");
+
+        client
+            .send(WsMessage::Text("not json".into()))
+            .await
+            .unwrap();
+        let reply = next_json(&mut client).await;
+        assert!(
+            reply["error"]
+                .as_str()
+                .unwrap()
+                .starts_with("Invalid outbox response")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_ws_delivers_outbox_responses() {
+        let socket = format!("axel-test-ws-{}", std::process::id());
+        let session = "axel-test-ws";
+        let tmux = |args: &[&str]| {
+            std::process::Command::new("tmux")
+                .args(["-L", &socket])
+                .args(args)
+                .output()
+        };
+        // A pane echoing what is typed into it
+        let Ok(created) = tmux(&[
+            "new-session",
+            "-d",
+            "-s",
+            session,
+            "-P",
+            "-F",
+            "#{pane_id}",
+            "cat",
+        ]) else {
+            return; // No tmux to deliver to
+        };
+        assert!(created.status.success());
+        let pane_id = String::from_utf8_lossy(&created.stdout).trim().to_string();
+        use_socket(Some(&socket));
+
+        let (state, mut event_rx) = test_state(Some(session.to_string()));
+        state
+            .pane_ids
+            .write()
+            .await
+            .insert("claude".to_string(), pane_id.clone());
+        let mut client = connect(state).await;
+
+        // A response naming a pane is logged, broadcast and typed into it
+        client.send(response("claude", "hello")).await.unwrap();
+        let logged = event_rx.recv().await.unwrap();
+        assert_eq!(logged.event_type, "question_response");
+        assert_eq!(logged.pane_id, "s1");
+        let broadcast = next_json(&mut client).await;
+        assert_eq!(broadcast["event"]["pane"], "claude");
+        assert_eq!(broadcast["event"]["response_text"], "hello");
+        let mut typed = String::new();
+        for _ in 0..50 {
+            let captured = tmux(&["capture-pane", "-p", "-t", &pane_id]).unwrap();
+            typed = String::from_utf8_lossy(&captured.stdout).into_owned();
+            if typed.contains("hello") {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+        assert!(typed.contains("hello"), "pane shows {typed:?}");
+
+        // A pane the session doesn't have is reported back, after or before
+        // the response's broadcast
+        client.send(response("ghost", "hi")).await.unwrap();
+        let mut messages = [next_json(&mut client).await, next_json(&mut client).await];
+        messages.sort_by_key(|message| message.get("error").is_none());
+        assert_eq!(
+            messages[0],
+            serde_json::json!({ "error": "Pane not found" })
+        );
+        assert_eq!(messages[1]["event"]["pane"], "ghost");
+
+        use_socket(None);
+        tmux(&["kill-server"]).ok();
+    }
+}
@@ -0,0 +1,136 @@
+//! Correlation state the event server keeps across restarts.
+//!
+//! Hook events carry Claude's `session_id` and the pane they come from, but
+//! OTEL telemetry only carries the `session_id`. The mapping between the two
+//! is learned from hooks, so it is saved to `state.json` next to the event
+//! log and read back on startup: a restarted server keeps attributing a
+//! running session's usage to its pane.
+
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use tokio::sync::watch;
+
+/// State file, next to the event log (`.axel/state.json`)
+const STATE_FILE: &str = "state.json";
+
+/// State file of the server logging to `log_path`
+pub fn state_path(log_path: &Path) -> PathBuf {
+    log_path.with_file_name(STATE_FILE)
+}
+
+/// What the server learned about the panes it hears from
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ServerState {
+    /// Pane of each Claude session, by `session_id`
+    #[serde(default)]
+    pub session_to_pane: HashMap<String, String>,
+}
+
+impl ServerState {
+    /// Read the state at `path`; a missing state file is empty
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = match std::fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => return Err(e).with_context(|| format!("Failed to read {}", path.display())),
+        };
+        serde_json::from_str(&content)
+            .with_context(|| format!("Invalid server state {}", path.display()))
+    }
+
+    /// Write the state to `path`, through a temporary file so a crash never
+    /// leaves it half-written
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let temp = path.with_extension("json.tmp");
+        std::fs::write(&temp, serde_json::to_string_pretty(self)?)?;
+        std::fs::rename(&temp, path).with_context(|| format!("Failed to write {}", path.display()))
+    }
+}
+
+/// Save each state sent on the returned channel to `path`, from one task.
+///
+/// The channel only keeps the latest state: a burst of changes is written
+/// once, and an older state never lands after a newer one.
+pub fn spawn_state_writer(path: PathBuf, initial: ServerState) -> watch::Sender<ServerState> {
+    let (state_tx, mut state_rx) = watch::channel(initial);
+    tokio::spawn(async move {
+        while state_rx.changed().await.is_ok() {
+            let state = state_rx.borrow_and_update().clone();
+            let path = path.clone();
+            match tokio::task::spawn_blocking(move || state.save(&path)).await {
+                Ok(Ok(())) => {}
+                Ok(Err(e)) => eprintln!("[state] {:#}", e),
+                Err(e) => eprintln!("[state] {}", e),
+            }
+        }
+    });
+    state_tx
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_state_roundtrip() {
+        let dir = std::env::temp_dir().join("axel-test-server-state");
+        std::fs::remove_dir_all(&dir).ok();
+        let path = state_path(&dir.join(".axel/events.jsonl"));
+        assert_eq!(path, dir.join(".axel/state.json"));
+
+        // A server that never saved starts empty
+        assert_eq!(ServerState::load(&path).unwrap(), ServerState::default());
+
+        let state = ServerState {
+            session_to_pane: HashMap::from([("abc123".to_string(), "claude".to_string())]),
+        };
+        state.save(&path).unwrap();
+        assert_eq!(ServerState::load(&path).unwrap(), state);
+
+        std::fs::write(&path, "not json").unwrap();
+        assert!(ServerState::load(&path).is_err());
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[tokio::test]
+    async fn test_state_writer_keeps_latest() {
+        let dir = std::env::temp_dir().join("axel-test-state-writer");
+        std::fs::remove_dir_all(&dir).ok();
+        let path = dir.join("state.json");
+
+        let state_tx = spawn_state_writer(path.clone(), ServerState::default());
+        let mut latest = ServerState::default();
+        for pane in ["claude", "codex", "shell"] {
+            latest
+                .session_to_pane
+                .insert("abc123".to_string(), pane.to_string());
+            state_tx.send_replace(latest.clone());
+            tokio::task::yield_now().await;
+        }
+
+        let mut saved = ServerState::default();
+        for _ in 0..100 {
+            saved = ServerState::load(&path).unwrap_or_default();
+            if saved == latest {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        assert_eq!(saved, latest);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -0,0 +1,244 @@
+//! Event storage and queries.
+//!
+//! Every event is appended to the JSONL log. With [`EventStorage::Sqlite`]
+//! events are also indexed in a SQLite database so `GET /events` can filter
+//! by pane, event type and time without scanning the whole log; without it,
+//! queries fall back to reading the JSONL file.
+
+use std::{
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use rusqlite::{Connection, params};
+use serde::Deserialize;
+
+use super::events::TimestampedEvent;
+
+/// Where the server persists events besides the JSONL log
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum EventStorage {
+    /// JSONL log only (default)
+    #[default]
+    Jsonl,
+    /// Also index events in a SQLite database at this path
+    Sqlite(PathBuf),
+}
+
+/// Filters for `GET /events`
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct EventQuery {
+    /// Only events at or after this time (RFC 3339)
+    pub since: Option<DateTime<Utc>>,
+    /// Only events from this pane
+    pub pane: Option<String>,
+    /// Only events of this type (e.g. `PreToolUse`, `otel_metrics`)
+    #[serde(rename = "type")]
+    pub event_type: Option<String>,
+    /// Return at most this many events (the most recent ones)
+    pub limit: Option<usize>,
+}
+
+impl EventQuery {
+    /// Whether an event passes every filter
+    pub fn matches(&self, event: &TimestampedEvent) -> bool {
+        self.since.is_none_or(|since| event.timestamp >= since)
+            && self.pane.as_ref().is_none_or(|p| *p == event.pane_id)
+            && self
+                .event_type
+                .as_ref()
+                .is_none_or(|t| *t == event.event_type)
+    }
+}
+
+/// SQLite event index, shared between the writer task and route handlers
+#[derive(Clone)]
+pub struct EventStore {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl EventStore {
+    /// Open (creating if needed) the database at `path`
+    pub fn open(path: &Path) -> Result<Self> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let conn = Connection::open(path)?;
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS events (
+                 id INTEGER PRIMARY KEY,
+                 timestamp TEXT NOT NULL,
+                 event_type TEXT NOT NULL,
+                 pane_id TEXT NOT NULL,
+                 event TEXT NOT NULL
+             );
+             CREATE INDEX IF NOT EXISTS events_timestamp ON events (timestamp);
+             CREATE INDEX IF NOT EXISTS events_pane ON events (pane_id, timestamp);
+             CREATE INDEX IF NOT EXISTS events_type ON events (event_type, timestamp);",
+        )?;
+        Ok(Self {
+            conn: Arc::new(Mutex::new(conn)),
+        })
+    }
+
+    /// Store one event
+    pub fn insert(&self, event: &TimestampedEvent) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT INTO events (timestamp, event_type, pane_id, event) VALUES (?1, ?2, ?3, ?4)",
+            params![
+                timestamp_key(&event.timestamp),
+                event.event_type,
+                event.pane_id,
+                event.event.to_string()
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Events matching `query`, oldest first
+    pub fn query(&self, query: &EventQuery) -> Result<Vec<TimestampedEvent>> {
+        let since = query.since.as_ref().map(timestamp_key);
+        let limit = query.limit.map_or(-1, |l| l as i64);
+
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT timestamp, event_type, pane_id, event FROM (
+                 SELECT id, timestamp, event_type, pane_id, event FROM events
+                 WHERE (?1 IS NULL OR timestamp >= ?1)
+                   AND (?2 IS NULL OR pane_id = ?2)
+                   AND (?3 IS NULL OR event_type = ?3)
+                 ORDER BY timestamp DESC, id DESC
+                 LIMIT ?4
+             ) ORDER BY timestamp, id",
+        )?;
+        let rows = stmt.query_map(params![since, query.pane, query.event_type, limit], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, String>(3)?,
+            ))
+        })?;
+
+        let mut events = Vec::new();
+        for row in rows {
+            let (timestamp, event_type, pane_id, event) = row?;
+            events.push(TimestampedEvent {
+                timestamp: DateTime::parse_from_rfc3339(&timestamp)?.with_timezone(&Utc),
+                event_type,
+                pane_id,
+                event: serde_json::from_str(&event)?,
+            });
+        }
+        Ok(events)
+    }
+}
+
+/// Timestamps are stored as fixed-width RFC 3339 strings so they sort
+fn timestamp_key(timestamp: &DateTime<Utc>) -> String {
+    timestamp.to_rfc3339_opts(chrono::SecondsFormat::Micros, true)
+}
+
+/// Query the JSONL log directly, for servers without a SQLite store
+pub async fn query_log(path: &Path, query: &EventQuery) -> Result<Vec<TimestampedEvent>> {
+    let content = match tokio::fs::read_to_string(path).await {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+    let mut events: Vec<TimestampedEvent> = content
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .filter(|event| query.matches(event))
+        .collect();
+    if let Some(limit) = query.limit {
+        events.drain(..events.len().saturating_sub(limit));
+    }
+    Ok(events)
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+
+    use super::*;
+
+    fn event(event_type: &str, pane_id: &str, at: DateTime<Utc>) -> TimestampedEvent {
+        TimestampedEvent {
+            timestamp: at,
+            event_type: event_type.to_string(),
+            pane_id: pane_id.to_string(),
+            event: serde_json::json!({ "type": event_type }),
+        }
+    }
+
+    #[test]
+    fn test_sqlite_store_filters_events() {
+        let path = std::env::temp_dir().join("axel-test-events.db");
+        std::fs::remove_file(&path).ok();
+        let store = EventStore::open(&path).unwrap();
+
+        let start = Utc::now();
+        for (i, (event_type, pane)) in [
+            ("PreToolUse", "a"),
+            ("otel_metrics", "a"),
+            ("PreToolUse", "b"),
+            ("Stop", "a"),
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            let at = start + Duration::seconds(i as i64);
+            store.insert(&event(event_type, pane, at)).unwrap();
+        }
+
+        let types = |query: EventQuery| -> Vec<String> {
+            store
+                .query(&query)
+                .unwrap()
+                .into_iter()
+                .map(|e| format!("{}/{}", e.pane_id, e.event_type))
+                .collect()
+        };
+
+        assert_eq!(types(EventQuery::default()).len(), 4);
+        assert_eq!(
+            types(EventQuery {
+                pane: Some("a".to_string()),
+                ..Default::default()
+            }),
+            vec!["a/PreToolUse", "a/otel_metrics", "a/Stop"]
+        );
+        assert_eq!(
+            types(EventQuery {
+                event_type: Some("PreToolUse".to_string()),
+                ..Default::default()
+            }),
+            vec!["a/PreToolUse", "b/PreToolUse"]
+        );
+        assert_eq!(
+            types(EventQuery {
+                since: Some(start + Duration::seconds(2)),
+                ..Default::default()
+            }),
+            vec!["b/PreToolUse", "a/Stop"]
+        );
+        // The limit keeps the most recent events, still oldest first
+        assert_eq!(
+            types(EventQuery {
+                limit: Some(2),
+                ..Default::default()
+            }),
+            vec!["b/PreToolUse", "a/Stop"]
+        );
+
+        std::fs::remove_file(&path).ok();
+    }
+}
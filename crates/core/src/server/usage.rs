@@ -0,0 +1,529 @@
+//! Token usage and cost estimates.
+//!
+//! Aggregates the token counters that AI panes export over OTEL into totals
+//! per pane and model:
+//!
+//! - Claude Code exports a `claude_code.token.usage` sum metric whose data
+//!   points carry `type` (`input`, `output`, `cacheRead`, `cacheCreation`)
+//!   and `model` attributes.
+//! - Codex logs a `codex.sse_event` record for each `response.completed`
+//!   event with `input_token_count`, `output_token_count`,
+//!   `cached_token_count` and `model` attributes.
+//!
+//! Costs are estimates computed from per-model prices in USD per million
+//! tokens. Built-in prices can be overridden or extended in
+//! `~/.config/axel/pricing.toml`:
+//!
+//! ```toml
+//! [models."claude-sonnet-4"]
+//! input = 3.0
+//! output = 15.0
+//! cache_read = 0.3
+//! cache_write = 3.75
+//! ```
+//!
+//! Models match the longest configured prefix, so `claude-sonnet-4` also
+//! prices `claude-sonnet-4-20250514`.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::Path,
+};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::events::{OtelEventType, TimestampedEvent};
+
+/// Pricing overrides, relative to the home directory
+pub const PRICING_FILE: &str = ".config/axel/pricing.toml";
+
+/// OTLP `AGGREGATION_TEMPORALITY_CUMULATIVE`
+const TEMPORALITY_CUMULATIVE: u64 = 2;
+
+/// Model label used when a data point has no `model` attribute
+const UNKNOWN_MODEL: &str = "unknown";
+
+// =============================================================================
+// Pricing
+// =============================================================================
+
+/// Price of one model in USD per million tokens
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ModelPrice {
+    pub input: f64,
+    pub output: f64,
+    pub cache_read: f64,
+    pub cache_write: f64,
+}
+
+/// Per-model prices, keyed by model name prefix
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Pricing {
+    #[serde(default)]
+    pub models: BTreeMap<String, ModelPrice>,
+}
+
+impl Pricing {
+    /// Published list prices for common Claude and OpenAI models
+    pub fn builtin() -> Self {
+        let price = |input, output, cache_read, cache_write| ModelPrice {
+            input,
+            output,
+            cache_read,
+            cache_write,
+        };
+        let models = [
+            ("claude-opus-4", price(15.0, 75.0, 1.5, 18.75)),
+            ("claude-opus-4-5", price(5.0, 25.0, 0.5, 6.25)),
+            ("claude-sonnet-4", price(3.0, 15.0, 0.3, 3.75)),
+            ("claude-3-7-sonnet", price(3.0, 15.0, 0.3, 3.75)),
+            ("claude-haiku-4-5", price(1.0, 5.0, 0.1, 1.25)),
+            ("claude-3-5-haiku", price(0.8, 4.0, 0.08, 1.0)),
+            ("gpt-5", price(1.25, 10.0, 0.125, 0.0)),
+            ("gpt-5-mini", price(0.25, 2.0, 0.025, 0.0)),
+            ("gpt-4.1", price(2.0, 8.0, 0.5, 0.0)),
+            ("o3", price(2.0, 8.0, 0.5, 0.0)),
+            ("o4-mini", price(1.1, 4.4, 0.275, 0.0)),
+        ];
+        Self {
+            models: models
+                .into_iter()
+                .map(|(model, price)| (model.to_string(), price))
+                .collect(),
+        }
+    }
+
+    /// Built-in prices merged with `~/.config/axel/pricing.toml`, if present
+    pub fn load() -> Result<Self> {
+        let mut pricing = Self::builtin();
+        if let Some(path) = dirs::home_dir().map(|home| home.join(PRICING_FILE))
+            && path.exists()
+        {
+            pricing.models.extend(Self::load_from(&path)?.models);
+        }
+        Ok(pricing)
+    }
+
+    /// Read prices from a TOML file
+    pub fn load_from(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)?;
+        toml::from_str(&content).with_context(|| format!("Invalid pricing file {:?}", path))
+    }
+
+    /// Price for `model`, matching the longest configured prefix
+    pub fn price(&self, model: &str) -> Option<&ModelPrice> {
+        self.models
+            .iter()
+            .filter(|(prefix, _)| model.starts_with(prefix.as_str()))
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map(|(_, price)| price)
+    }
+}
+
+// =============================================================================
+// Token Counts
+// =============================================================================
+
+/// Kind of token a counter measures
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum TokenKind {
+    Input,
+    Output,
+    CacheRead,
+    CacheCreation,
+}
+
+impl TokenKind {
+    /// Parse Claude Code's `type` attribute
+    fn from_claude(value: &str) -> Option<Self> {
+        match value {
+            "input" => Some(Self::Input),
+            "output" => Some(Self::Output),
+            "cacheRead" => Some(Self::CacheRead),
+            "cacheCreation" => Some(Self::CacheCreation),
+            _ => None,
+        }
+    }
+}
+
+/// Token totals for one pane and model
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct TokenCounts {
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cache_read_tokens: u64,
+    pub cache_creation_tokens: u64,
+}
+
+impl TokenCounts {
+    fn add(&mut self, kind: TokenKind, count: u64) {
+        let slot = match kind {
+            TokenKind::Input => &mut self.input_tokens,
+            TokenKind::Output => &mut self.output_tokens,
+            TokenKind::CacheRead => &mut self.cache_read_tokens,
+            TokenKind::CacheCreation => &mut self.cache_creation_tokens,
+        };
+        *slot += count;
+    }
+
+    fn merge(&mut self, other: &TokenCounts) {
+        self.input_tokens += other.input_tokens;
+        self.output_tokens += other.output_tokens;
+        self.cache_read_tokens += other.cache_read_tokens;
+        self.cache_creation_tokens += other.cache_creation_tokens;
+    }
+
+    /// Total number of tokens of every kind
+    pub fn total(&self) -> u64 {
+        self.input_tokens + self.output_tokens + self.cache_read_tokens + self.cache_creation_tokens
+    }
+
+    /// Estimated cost in USD at `price`
+    pub fn cost(&self, price: &ModelPrice) -> f64 {
+        (self.input_tokens as f64 * price.input
+            + self.output_tokens as f64 * price.output
+            + self.cache_read_tokens as f64 * price.cache_read
+            + self.cache_creation_tokens as f64 * price.cache_write)
+            / 1_000_000.0
+    }
+}
+
+// =============================================================================
+// Tracker
+// =============================================================================
+
+/// One cumulative counter series: pane, model, token kind and start time
+type SeriesKey = (String, String, TokenKind, String);
+
+/// Running token totals, fed with logged OTEL events
+#[derive(Debug, Default)]
+pub struct UsageTracker {
+    /// Sums of delta data points and log records, by (pane, model)
+    deltas: BTreeMap<(String, String), TokenCounts>,
+    /// Latest value of each cumulative series
+    cumulative: HashMap<SeriesKey, u64>,
+}
+
+impl UsageTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Build a tracker from already logged events
+    pub fn from_events<'a>(events: impl IntoIterator<Item = &'a TimestampedEvent>) -> Self {
+        let mut tracker = Self::new();
+        for event in events {
+            tracker.record(event);
+        }
+        tracker
+    }
+
+    /// Fold one event into the totals; events without token counters are ignored
+    pub fn record(&mut self, event: &TimestampedEvent) {
+        if event.event_type == OtelEventType::Metrics.to_string() {
+            self.record_metrics(&event.pane_id, &event.event);
+        } else if event.event_type == OtelEventType::Logs.to_string() {
+            self.record_logs(&event.pane_id, &event.event);
+        }
+    }
+
+    fn record_metrics(&mut self, pane_id: &str, payload: &Value) {
+        let metrics = items(payload, "resourceMetrics")
+            .flat_map(|rm| items(rm, "scopeMetrics"))
+            .flat_map(|sm| items(sm, "metrics"));
+        for metric in metrics {
+            let is_token_usage = metric
+                .get("name")
+                .and_then(Value::as_str)
+                .is_some_and(|name| name.ends_with("token.usage"));
+            let Some(sum) = metric.get("sum").filter(|_| is_token_usage) else {
+                continue;
+            };
+            let cumulative = sum
+                .get("aggregationTemporality")
+                .and_then(Value::as_u64)
+                .is_some_and(|t| t == TEMPORALITY_CUMULATIVE);
+
+            for point in items(sum, "dataPoints") {
+                let Some(kind) = attribute(point, "type")
+                    .and_then(|t| t.as_str().and_then(TokenKind::from_claude))
+                else {
+                    continue;
+                };
+                let model = attribute(point, "model")
+                    .and_then(|m| m.as_str().map(str::to_string))
+                    .unwrap_or_else(|| UNKNOWN_MODEL.to_string());
+                let count = number(point.get("asInt").or_else(|| point.get("asDouble")));
+
+                if cumulative {
+                    let start = point
+                        .get("startTimeUnixNano")
+                        .map(Value::to_string)
+                        .unwrap_or_default();
+                    self.cumulative
+                        .insert((pane_id.to_string(), model, kind, start), count);
+                } else {
+                    self.deltas
+                        .entry((pane_id.to_string(), model))
+                        .or_default()
+                        .add(kind, count);
+                }
+            }
+        }
+    }
+
+    fn record_logs(&mut self, pane_id: &str, payload: &Value) {
+        let records = items(payload, "resourceLogs")
+            .flat_map(|rl| items(rl, "scopeLogs"))
+            .flat_map(|sl| items(sl, "logRecords"));
+        for record in records {
+            let str_attr =
+                |key| attribute(record, key).and_then(|v| v.as_str().map(str::to_string));
+            if str_attr("event.name").as_deref() != Some("codex.sse_event")
+                || str_attr("event.kind").as_deref() != Some("response.completed")
+            {
+                continue;
+            }
+            let count = |key| number(attribute(record, key).as_ref());
+            let cached = count("cached_token_count");
+            let model = str_attr("model").unwrap_or_else(|| UNKNOWN_MODEL.to_string());
+
+            let counts = self.deltas.entry((pane_id.to_string(), model)).or_default();
+            // OpenAI reports cached tokens as part of the input
+            counts.add(
+                TokenKind::Input,
+                count("input_token_count").saturating_sub(cached),
+            );
+            counts.add(TokenKind::CacheRead, cached);
+            counts.add(TokenKind::Output, count("output_token_count"));
+        }
+    }
+
+    /// Totals per (pane, model)
+    fn totals(&self) -> BTreeMap<(String, String), TokenCounts> {
+        let mut totals = self.deltas.clone();
+        for ((pane_id, model, kind, _), count) in &self.cumulative {
+            totals
+                .entry((pane_id.clone(), model.clone()))
+                .or_default()
+                .add(*kind, *count);
+        }
+        totals
+    }
+
+    /// Totals per pane and model with cost estimates at `pricing`
+    pub fn report(&self, pricing: &Pricing) -> UsageReport {
+        let mut report = UsageReport::default();
+        for ((pane_id, model), tokens) in self.totals() {
+            let cost_usd = pricing.price(&model).map(|price| tokens.cost(price));
+            if report.panes.last().is_none_or(|p| p.pane_id != pane_id) {
+                report.panes.push(PaneUsage {
+                    pane_id,
+                    ..Default::default()
+                });
+            }
+            let pane = report.panes.last_mut().unwrap();
+            pane.tokens.merge(&tokens);
+            pane.cost_usd += cost_usd.unwrap_or_default();
+            pane.models.push(ModelUsage {
+                model,
+                tokens,
+                cost_usd,
+            });
+            report.tokens.merge(&tokens);
+            report.cost_usd += cost_usd.unwrap_or_default();
+        }
+        report
+    }
+}
+
+// =============================================================================
+// Report
+// =============================================================================
+
+/// Usage of a whole session, returned by `GET /usage`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UsageReport {
+    pub panes: Vec<PaneUsage>,
+    #[serde(flatten)]
+    pub tokens: TokenCounts,
+    /// Estimated cost of every priced model
+    pub cost_usd: f64,
+}
+
+/// Usage of one pane
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PaneUsage {
+    pub pane_id: String,
+    pub models: Vec<ModelUsage>,
+    #[serde(flatten)]
+    pub tokens: TokenCounts,
+    /// Estimated cost of every priced model
+    pub cost_usd: f64,
+}
+
+/// Usage of one model within a pane
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelUsage {
+    pub model: String,
+    #[serde(flatten)]
+    pub tokens: TokenCounts,
+    /// Estimated cost, or `None` when the model has no known price
+    pub cost_usd: Option<f64>,
+}
+
+// =============================================================================
+// OTLP JSON Helpers
+// =============================================================================
+
+/// Elements of the array at `key`, or nothing
+fn items<'a>(value: &'a Value, key: &str) -> impl Iterator<Item = &'a Value> {
+    value
+        .get(key)
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+}
+
+/// Unwrapped value of the OTLP attribute `key` (`stringValue`, `intValue`, ...)
+fn attribute(value: &Value, key: &str) -> Option<Value> {
+    items(value, "attributes")
+        .find(|attr| attr.get("key").and_then(Value::as_str) == Some(key))
+        .and_then(|attr| attr.get("value")?.as_object()?.values().next().cloned())
+}
+
+/// OTLP JSON encodes 64-bit integers as strings; accept either form
+fn number(value: Option<&Value>) -> u64 {
+    match value {
+        Some(Value::Number(n)) => n
+            .as_u64()
+            .unwrap_or_else(|| n.as_f64().unwrap_or(0.0) as u64),
+        Some(Value::String(s)) => s.parse().unwrap_or(0),
+        _ => 0,
+    }
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn claude_metrics(temporality: u64, points: &[(&str, &str, u64)]) -> Value {
+        let data_points: Vec<Value> = points
+            .iter()
+            .map(|(kind, model, count)| {
+                json!({
+                    "attributes": [
+                        { "key": "type", "value": { "stringValue": kind } },
+                        { "key": "model", "value": { "stringValue": model } },
+                    ],
+                    "startTimeUnixNano": "1",
+                    "asDouble": count,
+                })
+            })
+            .collect();
+        json!({ "resourceMetrics": [{ "scopeMetrics": [{ "metrics": [{
+            "name": "claude_code.token.usage",
+            "sum": { "aggregationTemporality": temporality, "dataPoints": data_points },
+        }]}]}]})
+    }
+
+    #[test]
+    fn test_usage_tracker_aggregates_claude_and_codex() {
+        let events = [
+            // Delta points add up
+            TimestampedEvent::new(
+                "otel_metrics",
+                "a",
+                claude_metrics(1, &[("input", "claude-sonnet-4-5", 1000)]),
+            ),
+            TimestampedEvent::new(
+                "otel_metrics",
+                "a",
+                claude_metrics(
+                    1,
+                    &[
+                        ("input", "claude-sonnet-4-5", 500),
+                        ("output", "claude-sonnet-4-5", 200),
+                    ],
+                ),
+            ),
+            // Cumulative points replace the previous value of their series
+            TimestampedEvent::new(
+                "otel_metrics",
+                "b",
+                claude_metrics(2, &[("cacheRead", "mystery-model", 100)]),
+            ),
+            TimestampedEvent::new(
+                "otel_metrics",
+                "b",
+                claude_metrics(2, &[("cacheRead", "mystery-model", 300)]),
+            ),
+            TimestampedEvent::new(
+                "otel_logs",
+                "b",
+                json!({ "resourceLogs": [{ "scopeLogs": [{ "logRecords": [{ "attributes": [
+                    { "key": "event.name", "value": { "stringValue": "codex.sse_event" } },
+                    { "key": "event.kind", "value": { "stringValue": "response.completed" } },
+                    { "key": "model", "value": { "stringValue": "gpt-5-codex" } },
+                    { "key": "input_token_count", "value": { "intValue": "1200" } },
+                    { "key": "cached_token_count", "value": { "intValue": "200" } },
+                    { "key": "output_token_count", "value": { "intValue": 50 } },
+                ]}]}]}]}),
+            ),
+            TimestampedEvent::new("PreToolUse", "a", json!({})),
+        ];
+
+        let report = UsageTracker::from_events(&events).report(&Pricing::builtin());
+        assert_eq!(report.panes.len(), 2);
+
+        let a = &report.panes[0];
+        assert_eq!(a.pane_id, "a");
+        assert_eq!(a.tokens.input_tokens, 1500);
+        assert_eq!(a.tokens.output_tokens, 200);
+        // 1500 * $3 + 200 * $15 per million
+        assert!((a.cost_usd - 0.0075).abs() < 1e-9);
+
+        let b = &report.panes[1];
+        let models: Vec<(&str, u64, Option<f64>)> = b
+            .models
+            .iter()
+            .map(|m| (m.model.as_str(), m.tokens.total(), m.cost_usd))
+            .collect();
+        assert_eq!(models[1], ("mystery-model", 300, None));
+        assert_eq!(models[0].0, "gpt-5-codex");
+        let codex = &b.models[0].tokens;
+        assert_eq!(
+            (
+                codex.input_tokens,
+                codex.cache_read_tokens,
+                codex.output_tokens
+            ),
+            (1000, 200, 50)
+        );
+
+        assert_eq!(report.tokens.total(), 1500 + 200 + 300 + 1250);
+    }
+
+    #[test]
+    fn test_pricing_matches_longest_prefix() {
+        let pricing = Pricing::builtin();
+        assert_eq!(
+            pricing.price("claude-opus-4-5-20251101").unwrap().input,
+            5.0
+        );
+        assert_eq!(
+            pricing.price("claude-opus-4-1-20250805").unwrap().input,
+            15.0
+        );
+        assert!(pricing.price("llama-3").is_none());
+    }
+}
@@ -0,0 +1,75 @@
+//! Shell quoting
+//!
+//! Pane commands are built as strings and run by a shell, either typed into
+//! the pane or written to a wrapper script. Every value that comes from the
+//! manifest or the user (models, tool lists, paths, prompts) goes through
+//! these helpers so quotes, backticks, `$`, newlines and globs reach the
+//! program unchanged.
+//!
+//! Quoting uses POSIX single quotes, with `'` written as `'\''`. That form
+//! also works in fish, which doesn't understand `$'...'`.
+
+use std::borrow::Cow;
+
+/// Characters that never need quoting
+const SAFE_CHARS: &str = "-_./=:@%+,";
+
+/// Quote `arg` as one shell word, leaving it bare when every character is
+/// safe.
+pub fn quote(arg: &str) -> Cow<'_, str> {
+    if !arg.is_empty()
+        && arg
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || SAFE_CHARS.contains(c))
+    {
+        Cow::Borrowed(arg)
+    } else {
+        Cow::Owned(single_quote(arg))
+    }
+}
+
+/// Single-quote `text` even when it would be safe bare, for free text like
+/// prompts.
+pub fn single_quote(text: &str) -> String {
+    format!("'{}'", text.replace('\'', "'\\''"))
+}
+
+/// Join argv into a command line, quoting arguments that need it.
+pub fn join<S: AsRef<str>>(args: &[S]) -> String {
+    args.iter()
+        .map(|arg| quote(arg.as_ref()))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quote() {
+        assert_eq!(quote("claude-opus-4"), "claude-opus-4");
+        assert_eq!(quote("Read,Write"), "Read,Write");
+        assert_eq!(quote("Bash(git:*)"), "'Bash(git:*)'");
+        assert_eq!(quote("my proj"), "'my proj'");
+        assert_eq!(quote(""), "''");
+        assert_eq!(quote("it's"), "'it'\\''s'");
+        assert_eq!(single_quote("Hello"), "'Hello'");
+        assert_eq!(join(&["tmux", "-t", "my proj"]), "tmux -t 'my proj'");
+    }
+
+    #[test]
+    fn test_quoted_text_survives_the_shell() {
+        let text = "it's `whoami` and $HOME\n\"quoted\" \\ *";
+        let output = std::process::Command::new("sh")
+            .arg("-c")
+            .arg(format!("printf %s {}", single_quote(text)))
+            .output()
+            .unwrap();
+        assert_eq!(String::from_utf8(output.stdout).unwrap(), text);
+    }
+}
@@ -0,0 +1,229 @@
+//! Detecting agents that stopped making progress
+//!
+//! An AI pane's `stuck:` sets how long the agent may go without activity
+//! in the middle of a task:
+//!
+//! ```yaml
+//! layouts:
+//!   panes:
+//!     - type: claude
+//!       stuck:
+//!         minutes: 15        # default 10
+//!         nudge: "You've been quiet for a while. Where are you at?"
+//! ```
+//!
+//! Like panes with a budget (see [`crate::budget`]), these panes report
+//! their hooks and OTEL telemetry to the session's event server under their
+//! name. A task is in flight from the agent's first activity (a tool hook,
+//! OTEL logs or traces) until it finishes its turn or waits on a permission
+//! prompt. Periodic OTEL metrics exports don't count as activity. When an
+//! in-flight pane stays quiet past its threshold, the server logs a `stuck`
+//! event, raises a `Stuck` notification and pastes the `nudge` prompt into
+//! the pane, if there is one. A pane is reported once per stall.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    config::{PaneConfig, WorkspaceConfig},
+    server::{OtelEventType, TimestampedEvent},
+};
+
+/// Hook events after which the agent waits on someone else
+const WAITING_HOOKS: &[&str] = &["Stop", "SessionEnd", "PermissionRequest"];
+
+/// `stuck:` of an AI pane
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StuckConfig {
+    /// Minutes without activity before the pane counts as stuck
+    #[serde(default = "default_minutes")]
+    pub minutes: u64,
+    /// Prompt pasted into the pane when it is stuck
+    #[serde(default)]
+    pub nudge: Option<String>,
+}
+
+fn default_minutes() -> u64 {
+    10
+}
+
+/// A pane that went quiet in the middle of a task
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StuckPane {
+    pub pane_id: String,
+    /// Whole minutes since its last activity
+    pub idle_minutes: i64,
+    pub nudge: Option<String>,
+}
+
+/// Activity of one watched pane
+#[derive(Debug, Clone)]
+struct PaneActivity {
+    last_activity: DateTime<Utc>,
+    in_flight: bool,
+    reported: bool,
+}
+
+/// Thresholds of the panes a server watches, and their latest activity
+#[derive(Debug, Default)]
+pub struct StuckTracker {
+    /// Thresholds by pane id
+    thresholds: HashMap<String, StuckConfig>,
+    panes: HashMap<String, PaneActivity>,
+}
+
+impl StuckTracker {
+    pub fn new(thresholds: HashMap<String, StuckConfig>) -> Self {
+        Self {
+            thresholds,
+            panes: HashMap::new(),
+        }
+    }
+
+    /// Fold `event` into its pane's activity
+    pub fn record(&mut self, event: &TimestampedEvent) {
+        if !self.thresholds.contains_key(&event.pane_id) {
+            return;
+        }
+        let in_flight = match event.hook_name() {
+            Some(name) => !WAITING_HOOKS.contains(&name),
+            None if event.event_type == OtelEventType::Logs.to_string()
+                || event.event_type == OtelEventType::Traces.to_string() =>
+            {
+                true
+            }
+            None => return,
+        };
+        self.panes.insert(
+            event.pane_id.clone(),
+            PaneActivity {
+                last_activity: event.timestamp,
+                in_flight,
+                reported: false,
+            },
+        );
+    }
+
+    /// Panes with a task in flight and no activity for longer than their
+    /// threshold at `now`, which aren't reported again until they are active
+    pub fn stuck(&mut self, now: DateTime<Utc>) -> Vec<StuckPane> {
+        let mut stuck = Vec::new();
+        for (pane_id, activity) in &mut self.panes {
+            let Some(config) = self.thresholds.get(pane_id) else {
+                continue;
+            };
+            let idle_minutes = (now - activity.last_activity).num_minutes();
+            if activity.in_flight && !activity.reported && idle_minutes >= config.minutes as i64 {
+                activity.reported = true;
+                stuck.push(StuckPane {
+                    pane_id: pane_id.clone(),
+                    idle_minutes,
+                    nudge: config.nudge.clone(),
+                });
+            }
+        }
+        stuck.sort_by(|a, b| a.pane_id.cmp(&b.pane_id));
+        stuck
+    }
+}
+
+impl WorkspaceConfig {
+    /// Stuck thresholds of the AI panes that set one, by pane name
+    pub fn stuck_thresholds(&self) -> HashMap<String, StuckConfig> {
+        self.layouts
+            .panes
+            .iter()
+            .filter_map(|pane| match pane {
+                PaneConfig::Claude(c)
+                | PaneConfig::Codex(c)
+                | PaneConfig::Opencode(c)
+                | PaneConfig::Antigravity(c)
+                | PaneConfig::External(c) => {
+                    c.stuck.clone().map(|s| (pane.pane_type().to_string(), s))
+                }
+                PaneConfig::Custom(_) => None,
+            })
+            .collect()
+    }
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+    use serde_json::json;
+
+    use super::*;
+
+    fn event(pane: &str, event_type: &str, payload: serde_json::Value) -> TimestampedEvent {
+        TimestampedEvent::new(event_type, pane, payload)
+    }
+
+    #[test]
+    fn test_stuck_tracker() {
+        let mut tracker = StuckTracker::new(HashMap::from([(
+            "claude".to_string(),
+            StuckConfig {
+                minutes: 10,
+                nudge: Some("Still there?".to_string()),
+            },
+        )]));
+        let tool = event(
+            "claude",
+            "unknown_hook",
+            json!({"hook_event_name": "PreToolUse"}),
+        );
+        let start = tool.timestamp;
+        tracker.record(&tool);
+        // Other panes and periodic metrics exports are not activity
+        tracker.record(&event(
+            "shell",
+            "unknown_hook",
+            json!({"hook_event_name": "PreToolUse"}),
+        ));
+        let mut metrics = event("claude", "otel_metrics", json!({}));
+        metrics.timestamp = start + Duration::minutes(8);
+        tracker.record(&metrics);
+
+        assert!(tracker.stuck(start + Duration::minutes(9)).is_empty());
+        assert_eq!(
+            tracker.stuck(start + Duration::minutes(11)),
+            vec![StuckPane {
+                pane_id: "claude".to_string(),
+                idle_minutes: 11,
+                nudge: Some("Still there?".to_string()),
+            }]
+        );
+        // Reported once per stall
+        assert!(tracker.stuck(start + Duration::minutes(30)).is_empty());
+
+        // A finished turn is not a stall
+        let mut stop = event("claude", "unknown_hook", json!({"hook_event_name": "Stop"}));
+        stop.timestamp = start + Duration::minutes(31);
+        tracker.record(&stop);
+        assert!(tracker.stuck(start + Duration::minutes(60)).is_empty());
+    }
+
+    #[test]
+    fn test_stuck_thresholds() {
+        let config: WorkspaceConfig = serde_yaml::from_str(
+            "workspace: test\nlayouts:\n  panes:\n    - type: claude\n      stuck: {}\n    - type: codex\n",
+        )
+        .unwrap();
+        assert_eq!(
+            config.stuck_thresholds(),
+            HashMap::from([(
+                "claude".to_string(),
+                StuckConfig {
+                    minutes: 10,
+                    nudge: None,
+                }
+            )])
+        );
+    }
+}
@@ -0,0 +1,191 @@
+//! Terminal application launchers.
+//!
+//! Opens a new window of a terminal application running a given command,
+//! used to attach to a tmux session without taking over the current shell.
+//!
+//! Supported terminals:
+//! - **iTerm** and **Terminal** (macOS, via AppleScript)
+//! - **WezTerm** and **kitty** (cross-platform, via their CLIs)
+
+use std::{fmt, process::Command, str::FromStr};
+
+use anyhow::{Context, Result};
+
+use crate::shell;
+
+/// A terminal application that can open a new window running a command
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Terminal {
+    /// iTerm2 (macOS)
+    ITerm,
+    /// Terminal.app (macOS)
+    Terminal,
+    /// WezTerm
+    WezTerm,
+    /// kitty
+    Kitty,
+}
+
+impl Terminal {
+    /// Name used on the command line
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Terminal::ITerm => "iterm",
+            Terminal::Terminal => "terminal",
+            Terminal::WezTerm => "wezterm",
+            Terminal::Kitty => "kitty",
+        }
+    }
+
+    /// Build the argv that opens a new window running `command`
+    pub fn launch_args(&self, command: &[&str]) -> Vec<String> {
+        match self {
+            Terminal::ITerm => {
+                let script = format!(
+                    "tell application \"iTerm\" to create window with default profile command \"{}\"",
+                    applescript_escape(&shell::join(command))
+                );
+                vec!["osascript".into(), "-e".into(), script]
+            }
+            Terminal::Terminal => {
+                let script = format!(
+                    "tell application \"Terminal\" to do script \"{}\"",
+                    applescript_escape(&shell::join(command))
+                );
+                vec![
+                    "osascript".into(),
+                    "-e".into(),
+                    script,
+                    "-e".into(),
+                    "tell application \"Terminal\" to activate".into(),
+                ]
+            }
+            Terminal::WezTerm => ["wezterm", "start", "--"]
+                .into_iter()
+                .chain(command.iter().copied())
+                .map(String::from)
+                .collect(),
+            Terminal::Kitty => ["kitty", "--detach"]
+                .into_iter()
+                .chain(command.iter().copied())
+                .map(String::from)
+                .collect(),
+        }
+    }
+
+    /// Open a new terminal window running `command` (does not wait for it)
+    pub fn open(&self, command: &[&str]) -> Result<()> {
+        let args = self.launch_args(command);
+        Command::new(&args[0])
+            .args(&args[1..])
+            .spawn()
+            .with_context(|| format!("Failed to launch {} ({})", self, args[0]))?;
+        Ok(())
+    }
+}
+
+impl fmt::Display for Terminal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Terminal {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "iterm" => Ok(Terminal::ITerm),
+            "terminal" => Ok(Terminal::Terminal),
+            "wezterm" => Ok(Terminal::WezTerm),
+            "kitty" => Ok(Terminal::Kitty),
+            _ => Err(format!(
+                "unknown terminal: {} (expected iterm, terminal, wezterm, or kitty)",
+                s
+            )),
+        }
+    }
+}
+
+/// Escape text for use inside an AppleScript string literal
+fn applescript_escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ATTACH: &[&str] = &["tmux", "attach-session", "-t", "my proj"];
+
+    #[test]
+    fn test_launch_args_per_terminal() {
+        assert_eq!(
+            Terminal::ITerm.launch_args(ATTACH),
+            vec![
+                "osascript",
+                "-e",
+                "tell application \"iTerm\" to create window with default profile command \"tmux attach-session -t 'my proj'\"",
+            ]
+        );
+        assert_eq!(
+            Terminal::Terminal.launch_args(ATTACH),
+            vec![
+                "osascript",
+                "-e",
+                "tell application \"Terminal\" to do script \"tmux attach-session -t 'my proj'\"",
+                "-e",
+                "tell application \"Terminal\" to activate",
+            ]
+        );
+        assert_eq!(
+            Terminal::WezTerm.launch_args(ATTACH),
+            vec![
+                "wezterm",
+                "start",
+                "--",
+                "tmux",
+                "attach-session",
+                "-t",
+                "my proj"
+            ]
+        );
+        assert_eq!(
+            Terminal::Kitty.launch_args(ATTACH),
+            vec![
+                "kitty",
+                "--detach",
+                "tmux",
+                "attach-session",
+                "-t",
+                "my proj"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_applescript_quoting() {
+        let args = Terminal::Terminal.launch_args(&["echo", "say \"hi\""]);
+        assert_eq!(
+            args[2],
+            "tell application \"Terminal\" to do script \"echo 'say \\\"hi\\\"'\""
+        );
+    }
+
+    #[test]
+    fn test_parse_terminal_names() {
+        for terminal in [
+            Terminal::ITerm,
+            Terminal::Terminal,
+            Terminal::WezTerm,
+            Terminal::Kitty,
+        ] {
+            assert_eq!(terminal.as_str().parse::<Terminal>(), Ok(terminal));
+        }
+        assert!("alacritty".parse::<Terminal>().is_err());
+    }
+}
@@ -0,0 +1,137 @@
+//! Launch phase timing
+//!
+//! A small accumulator for `--profile-timing`. The launch path calls
+//! [`Timings::lap`] after each phase; the time since the previous lap is
+//! charged to that phase, so the phases add up to the whole launch.
+//! Repeated phase names are summed.
+
+use std::time::{Duration, Instant};
+
+/// Accumulated durations per launch phase, in first-recorded order
+#[derive(Debug)]
+pub struct Timings {
+    phases: Vec<(String, Duration)>,
+    last: Instant,
+}
+
+impl Timings {
+    /// Start timing from now
+    pub fn start() -> Self {
+        Self {
+            phases: Vec::new(),
+            last: Instant::now(),
+        }
+    }
+
+    /// Add `duration` to `phase`
+    pub fn record(&mut self, phase: &str, duration: Duration) {
+        match self.phases.iter_mut().find(|(name, _)| name == phase) {
+            Some((_, total)) => *total += duration,
+            None => self.phases.push((phase.to_string(), duration)),
+        }
+    }
+
+    /// Charge the time since the previous lap to `phase`
+    pub fn lap(&mut self, phase: &str) {
+        let now = Instant::now();
+        self.record(phase, now - self.last);
+        self.last = now;
+    }
+
+    /// Recorded phases and their total durations
+    pub fn phases(&self) -> &[(String, Duration)] {
+        &self.phases
+    }
+
+    /// Sum of all recorded phases
+    pub fn total(&self) -> Duration {
+        self.phases.iter().map(|(_, d)| *d).sum()
+    }
+
+    /// Render a summary table: one row per phase with its share of the total
+    pub fn report(&self) -> String {
+        let total = self.total();
+        let width = self
+            .phases
+            .iter()
+            .map(|(name, _)| name.len())
+            .max()
+            .unwrap_or(0)
+            .max("total".len());
+
+        let mut out = String::new();
+        for (name, duration) in &self.phases {
+            let share = if total.is_zero() {
+                0.0
+            } else {
+                duration.as_secs_f64() / total.as_secs_f64() * 100.0
+            };
+            out.push_str(&format!(
+                "{:<width$}  {:>9.1} ms  {:>5.1}%\n",
+                name,
+                millis(*duration),
+                share
+            ));
+        }
+        out.push_str(&format!("{:<width$}  {:>9.1} ms\n", "total", millis(total)));
+        out
+    }
+}
+
+fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_sums_repeated_phases() {
+        let mut timings = Timings::start();
+        timings.record("config load", Duration::from_millis(10));
+        timings.record("send claude", Duration::from_millis(200));
+        timings.record("config load", Duration::from_millis(5));
+
+        assert_eq!(
+            timings.phases(),
+            &[
+                ("config load".to_string(), Duration::from_millis(15)),
+                ("send claude".to_string(), Duration::from_millis(200)),
+            ]
+        );
+        assert_eq!(timings.total(), Duration::from_millis(215));
+    }
+
+    #[test]
+    fn test_report_table() {
+        let mut timings = Timings::start();
+        timings.record("skill install", Duration::from_millis(30));
+        timings.record("configure", Duration::from_millis(10));
+
+        assert_eq!(
+            timings.report(),
+            "skill install       30.0 ms   75.0%\n\
+             configure           10.0 ms   25.0%\n\
+             total               40.0 ms\n"
+        );
+        assert_eq!(Timings::start().report(), "total        0.0 ms\n");
+    }
+
+    #[test]
+    fn test_lap_charges_elapsed_time() {
+        let mut timings = Timings::start();
+        std::thread::sleep(Duration::from_millis(5));
+        timings.lap("first");
+        timings.lap("second");
+
+        let phases = timings.phases();
+        assert_eq!(phases[0].0, "first");
+        assert!(phases[0].1 >= Duration::from_millis(5));
+        assert!(phases[1].1 < phases[0].1);
+    }
+}
@@ -1,15 +1,83 @@
 //! Low-level tmux command wrappers
 //!
 //! This module provides builder-pattern wrappers for common tmux commands.
-
-use std::process::{Command, Output};
+//!
+//! Each builder's `run` blocks on the tmux client; `run_async` runs it with
+//! tokio instead, so independent commands (options, titles, typed input) can
+//! be issued concurrently. Free functions used while setting up a session
+//! have `_async` variants for the same reason.
+
+use std::{
+    path::Path,
+    process::{Command, Output},
+    sync::RwLock,
+    time::{Duration, Instant},
+};
 
 use anyhow::{Context, Result};
 use serde::Serialize;
 
+/// Environment variable naming the tmux server socket (`tmux -L`) axel uses
+/// when none was chosen with [`use_socket`]
+pub const AXEL_TMUX_SOCKET_ENV: &str = "AXEL_TMUX_SOCKET";
+
+/// Socket of the tmux server sessions with `tmux.isolation: socket` run on
+pub const AXEL_SOCKET: &str = "axel";
+
+/// Socket chosen with [`use_socket`]
+static SOCKET: RwLock<Option<String>> = RwLock::new(None);
+
+/// Talk to the tmux server on `socket` (`tmux -L`) from now on, or with
+/// `None`, to the one named by `AXEL_TMUX_SOCKET` (the default server when
+/// that is unset)
+pub fn use_socket(socket: Option<&str>) {
+    if let Ok(mut current) = SOCKET.write() {
+        *current = socket.map(str::to_string);
+    }
+}
+
+/// Socket of the tmux server axel talks to; `None` for the default server
+/// (or the one of the tmux session axel runs in)
+pub fn socket() -> Option<String> {
+    SOCKET
+        .read()
+        .ok()
+        .and_then(|socket| socket.clone())
+        .or_else(|| std::env::var(AXEL_TMUX_SOCKET_ENV).ok())
+        .filter(|socket| !socket.is_empty())
+}
+
+/// `tmux`, with `-L <socket>` when axel uses a server of its own, for
+/// commands run by other programs (terminals, key bindings)
+pub fn tmux_program() -> Vec<String> {
+    let mut program = vec!["tmux".to_string()];
+    if let Some(socket) = socket() {
+        program.extend(["-L".to_string(), socket]);
+    }
+    program
+}
+
+/// A `tmux` command talking to axel's server (see [`socket`])
+pub fn tmux_command() -> Command {
+    let mut command = Command::new("tmux");
+    if let Some(socket) = socket() {
+        command.args(["-L", &socket]);
+    }
+    command
+}
+
+/// Async [`tmux_command`]
+fn tmux_command_async() -> tokio::process::Command {
+    let mut command = tokio::process::Command::new("tmux");
+    if let Some(socket) = socket() {
+        command.args(["-L", &socket]);
+    }
+    command
+}
+
 /// Execute a tmux command and return the output
 fn tmux(args: &[&str]) -> Result<Output> {
-    Command::new("tmux")
+    tmux_command()
         .args(args)
         .output()
         .context("Failed to execute tmux command")
@@ -17,7 +85,7 @@ fn tmux(args: &[&str]) -> Result<Output> {
 
 /// Execute a tmux command and check if it succeeded (suppressing stderr)
 fn tmux_status(args: &[&str]) -> Result<bool> {
-    Ok(Command::new("tmux")
+    Ok(tmux_command()
         .args(args)
         .stderr(std::process::Stdio::null())
         .status()?
@@ -26,13 +94,51 @@ fn tmux_status(args: &[&str]) -> Result<bool> {
 
 /// Execute a tmux command, returning an error if it fails
 fn tmux_run(args: &[&str]) -> Result<()> {
-    let status = Command::new("tmux").args(args).status()?;
+    let status = tmux_command().args(args).status()?;
     if !status.success() {
         anyhow::bail!("tmux command failed: {:?}", args);
     }
     Ok(())
 }
 
+/// Async [`tmux`]
+async fn tmux_async(args: &[&str]) -> Result<Output> {
+    tmux_command_async()
+        .args(args)
+        .output()
+        .await
+        .context("Failed to execute tmux command")
+}
+
+/// Async [`tmux_run`]
+async fn tmux_run_async(args: &[&str]) -> Result<()> {
+    let status = tmux_command_async().args(args).status().await?;
+    if !status.success() {
+        anyhow::bail!("tmux command failed: {:?}", args);
+    }
+    Ok(())
+}
+
+/// Run `future` to completion on a single-threaded runtime, for the
+/// blocking entry points built on the async commands
+pub(super) fn block_on<F: Future>(future: F) -> Result<F::Output> {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .context("Failed to start tokio runtime")?;
+    Ok(runtime.block_on(future))
+}
+
+/// Borrow owned arguments for [`tmux`] and friends
+fn as_args(args: &[String]) -> Vec<&str> {
+    args.iter().map(String::as_str).collect()
+}
+
+/// First line of a command's output, for `-P -F` and `display-message -p`
+fn stdout_line(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stdout).trim().to_string()
+}
+
 // =============================================================================
 // Session Commands
 // =============================================================================
@@ -78,6 +184,9 @@ pub struct SessionInfo {
     /// Axel pane ID (from AXEL_PANE_ID environment)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub axel_pane_id: Option<String>,
+    /// Branch and dirty state when the working directory is a git checkout
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub git: Option<crate::git::RepoStatus>,
 }
 
 /// Get the total number of panes in a session
@@ -128,6 +237,9 @@ pub fn list_sessions(axel_only: bool) -> Result<Vec<SessionInfo>> {
             let axel_pane_id = get_environment(&name, "AXEL_PANE_ID");
 
             let panes = count_session_panes(&name);
+            let git = working_dir
+                .as_deref()
+                .and_then(|dir| crate::git::status(Path::new(dir)).ok());
 
             sessions.push(SessionInfo {
                 name,
@@ -138,6 +250,7 @@ pub fn list_sessions(axel_only: bool) -> Result<Vec<SessionInfo>> {
                 working_dir,
                 port,
                 axel_pane_id,
+                git,
             });
         }
     }
@@ -155,6 +268,11 @@ pub fn set_environment(session: &str, key: &str, value: &str) -> Result<()> {
     tmux_run(&["set-environment", "-t", session, key, value])
 }
 
+/// Async [`set_environment`]
+pub async fn set_environment_async(session: &str, key: &str, value: &str) -> Result<()> {
+    tmux_run_async(&["set-environment", "-t", session, key, value]).await
+}
+
 /// Get an environment variable from a tmux session
 pub fn get_environment(session: &str, key: &str) -> Option<String> {
     let output = tmux(&["show-environment", "-t", session, key]).ok()?;
@@ -166,9 +284,40 @@ pub fn get_environment(session: &str, key: &str) -> Option<String> {
         .map(|v| v.to_string())
 }
 
+/// Parse a `KEY=VALUE` session environment assignment.
+///
+/// The key must be a valid variable name; the value may be empty or contain `=`.
+pub fn parse_env_assignment(assignment: &str) -> Result<(&str, &str)> {
+    let Some((key, value)) = assignment.split_once('=') else {
+        anyhow::bail!("Expected KEY=VALUE, got '{}'", assignment);
+    };
+    let valid_key = key
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if !valid_key {
+        anyhow::bail!("Invalid environment variable name '{}'", key);
+    }
+    Ok((key, value))
+}
+
+/// Format session environment variables one per line.
+///
+/// Set variables print as `KEY=value`; unset ones as `-KEY`, matching
+/// `tmux show-environment`.
+pub fn format_environment(vars: &[(&str, Option<String>)]) -> String {
+    vars.iter()
+        .map(|(key, value)| match value {
+            Some(value) => format!("{}={}\n", key, value),
+            None => format!("-{}\n", key),
+        })
+        .collect()
+}
+
 /// Attach to a tmux session
 pub fn attach_session(name: &str) -> Result<()> {
-    Command::new("tmux")
+    tmux_command()
         .args(["attach-session", "-t", name])
         .status()?;
     Ok(())
@@ -177,7 +326,7 @@ pub fn attach_session(name: &str) -> Result<()> {
 /// Detach all clients from a tmux session
 pub fn detach_session(name: &str) -> Result<()> {
     // Detach all clients from the session (silently ignore if no clients attached)
-    Command::new("tmux")
+    tmux_command()
         .args(["detach-client", "-s", name])
         .stderr(std::process::Stdio::null())
         .status()
@@ -232,8 +381,8 @@ impl<'a> NewSession<'a> {
         self
     }
 
-    /// Execute the new-session command
-    pub fn run(self) -> Result<()> {
+    /// Build the tmux arguments for this command
+    fn args(&self) -> Vec<&'a str> {
         let mut args = vec!["new-session"];
 
         if self.detached {
@@ -260,7 +409,17 @@ impl<'a> NewSession<'a> {
             args.push(cmd);
         }
 
-        tmux_run(&args)
+        args
+    }
+
+    /// Execute the new-session command
+    pub fn run(self) -> Result<()> {
+        tmux_run(&self.args())
+    }
+
+    /// Execute the new-session command without blocking
+    pub async fn run_async(self) -> Result<()> {
+        tmux_run_async(&self.args()).await
     }
 }
 
@@ -273,6 +432,197 @@ pub fn rename_window(target: &str, new_name: &str) -> Result<()> {
     tmux_run(&["rename-window", "-t", target, new_name])
 }
 
+/// Async [`rename_window`]
+pub async fn rename_window_async(target: &str, new_name: &str) -> Result<()> {
+    tmux_run_async(&["rename-window", "-t", target, new_name]).await
+}
+
+/// Make the window holding `target` its session's current window
+pub fn select_window(target: &str) -> Result<()> {
+    tmux_run(&["select-window", "-t", target])
+}
+
+/// Async [`select_window`]
+pub async fn select_window_async(target: &str) -> Result<()> {
+    tmux_run_async(&["select-window", "-t", target]).await
+}
+
+/// Zoom a pane to fill its window
+pub async fn zoom_pane_async(target: &str) -> Result<()> {
+    tmux_run_async(&["resize-pane", "-Z", "-t", target]).await
+}
+
+/// Builder for adding windows to an existing session
+#[derive(Default)]
+pub struct NewWindow<'a> {
+    target: Option<&'a str>,
+    name: Option<&'a str>,
+    start_dir: Option<&'a str>,
+    shell_command: Option<&'a str>,
+}
+
+impl<'a> NewWindow<'a> {
+    /// Create a new window builder
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the session the window is added to
+    pub fn target(mut self, target: &'a str) -> Self {
+        self.target = Some(target);
+        self
+    }
+
+    /// Set the window name
+    pub fn name(mut self, name: &'a str) -> Self {
+        self.name = Some(name);
+        self
+    }
+
+    /// Set the starting directory
+    pub fn start_directory(mut self, dir: &'a str) -> Self {
+        self.start_dir = Some(dir);
+        self
+    }
+
+    /// Set the command to run in the window's first pane
+    pub fn command(mut self, cmd: &'a str) -> Self {
+        self.shell_command = Some(cmd);
+        self
+    }
+
+    /// Build the tmux arguments for this command
+    fn args(&self) -> Vec<String> {
+        // -d keeps the current window selected; -t session: appends a window
+        let mut args = vec!["new-window".to_string(), "-d".to_string()];
+
+        if let Some(target) = self.target {
+            args.push("-t".to_string());
+            args.push(format!("{}:", target));
+        }
+
+        if let Some(name) = self.name {
+            args.push("-n".to_string());
+            args.push(name.to_string());
+        }
+
+        if let Some(dir) = self.start_dir {
+            args.push("-c".to_string());
+            args.push(dir.to_string());
+        }
+
+        args.extend(["-P", "-F", "#{pane_id}"].map(String::from));
+
+        // Shell command must come last
+        if let Some(cmd) = self.shell_command {
+            args.push(cmd.to_string());
+        }
+
+        args
+    }
+
+    /// Run the new-window command and return the ID of its first pane
+    pub fn run(self) -> Result<String> {
+        Ok(stdout_line(&tmux(&as_args(&self.args()))?))
+    }
+
+    /// Run the new-window command without blocking
+    pub async fn run_async(self) -> Result<String> {
+        Ok(stdout_line(&tmux_async(&as_args(&self.args())).await?))
+    }
+}
+
+/// A window in a tmux session
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WindowInfo {
+    /// Window ID (e.g. `@2`)
+    pub id: String,
+    /// Window name
+    pub name: String,
+    /// Layout string accepted by `select-layout`
+    pub layout: String,
+}
+
+/// List the windows of a session, in order
+pub fn list_windows(session: &str) -> Result<Vec<WindowInfo>> {
+    let output = tmux(&[
+        "list-windows",
+        "-t",
+        session,
+        "-F",
+        "#{window_id}\t#{window_layout}\t#{window_name}",
+    ])?;
+    if !output.status.success() {
+        anyhow::bail!("Failed to list windows for session '{}'", session);
+    }
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.splitn(3, '\t');
+            Some(WindowInfo {
+                id: fields.next()?.to_string(),
+                layout: fields.next()?.to_string(),
+                name: fields.next()?.to_string(),
+            })
+        })
+        .collect())
+}
+
+/// Apply a named layout (e.g. `tiled`) to a window
+pub fn select_layout(target: &str, layout: &str) -> Result<()> {
+    SelectLayout::new().target(target).layout(layout).run()
+}
+
+/// Builder for applying one of tmux's preset layouts to a window
+#[derive(Default)]
+pub struct SelectLayout<'a> {
+    target: Option<&'a str>,
+    layout: Option<&'a str>,
+}
+
+impl<'a> SelectLayout<'a> {
+    /// Create a new select layout builder
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the target window
+    pub fn target(mut self, target: &'a str) -> Self {
+        self.target = Some(target);
+        self
+    }
+
+    /// Set the layout: a name (even-horizontal, main-vertical, tiled, ...)
+    /// or a layout string
+    pub fn layout(mut self, layout: &'a str) -> Self {
+        self.layout = Some(layout);
+        self
+    }
+
+    /// Build the tmux arguments for this command
+    fn args(&self) -> Vec<&'a str> {
+        let mut args = vec!["select-layout"];
+        if let Some(target) = self.target {
+            args.push("-t");
+            args.push(target);
+        }
+        if let Some(layout) = self.layout {
+            args.push(layout);
+        }
+        args
+    }
+
+    /// Execute the select-layout command
+    pub fn run(self) -> Result<()> {
+        tmux_run(&self.args())
+    }
+
+    /// Execute the select-layout command without blocking
+    pub async fn run_async(self) -> Result<()> {
+        tmux_run_async(&self.args()).await
+    }
+}
+
 // =============================================================================
 // Pane Commands
 // =============================================================================
@@ -337,8 +687,8 @@ impl<'a> SplitWindow<'a> {
         self
     }
 
-    /// Run the split-window command and return the new pane ID
-    pub fn run(self) -> Result<String> {
+    /// Build the tmux arguments for this command
+    fn args(&self) -> Vec<String> {
         let mut args = vec!["split-window".to_string()];
 
         if let Some(target) = self.target {
@@ -371,9 +721,17 @@ impl<'a> SplitWindow<'a> {
             args.push(cmd.to_string());
         }
 
-        let args_ref: Vec<&str> = args.iter().map(|s| s.as_str()).collect();
-        let output = tmux(&args_ref)?;
-        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+        args
+    }
+
+    /// Run the split-window command and return the new pane ID
+    pub fn run(self) -> Result<String> {
+        Ok(stdout_line(&tmux(&as_args(&self.args()))?))
+    }
+
+    /// Run the split-window command without blocking
+    pub async fn run_async(self) -> Result<String> {
+        Ok(stdout_line(&tmux_async(&as_args(&self.args())).await?))
     }
 }
 
@@ -409,55 +767,455 @@ impl<'a> SelectPane<'a> {
         self
     }
 
-    /// Execute the select-pane command
-    pub fn run(self) -> Result<()> {
-        // Apply style if set
-        if let Some(style) = &self.style {
+    /// Build the select-pane invocations: the style, then the title, or a
+    /// plain select when neither is set
+    fn commands(&self) -> Vec<Vec<&str>> {
+        let select = || {
             let mut args = vec!["select-pane"];
             if let Some(target) = self.target {
                 args.push("-t");
                 args.push(target);
             }
+            args
+        };
+        let mut commands = Vec::new();
+
+        if let Some(style) = &self.style {
+            let mut args = select();
             args.push("-P");
             args.push(style);
-            tmux_run(&args)?;
+            commands.push(args);
         }
 
-        // Apply title if set
         if let Some(title) = self.title {
-            let mut args = vec!["select-pane"];
-            if let Some(target) = self.target {
-                args.push("-t");
-                args.push(target);
-            }
+            let mut args = select();
             args.push("-T");
             args.push(title);
-            tmux_run(&args)?;
+            commands.push(args);
         }
 
-        // Just select if no style or title
-        if self.style.is_none() && self.title.is_none() {
-            let mut args = vec!["select-pane"];
-            if let Some(target) = self.target {
-                args.push("-t");
-                args.push(target);
-            }
+        if commands.is_empty() {
+            commands.push(select());
+        }
+        commands
+    }
+
+    /// Execute the select-pane command
+    pub fn run(self) -> Result<()> {
+        for args in self.commands() {
             tmux_run(&args)?;
         }
+        Ok(())
+    }
 
+    /// Execute the select-pane command without blocking
+    pub async fn run_async(self) -> Result<()> {
+        for args in self.commands() {
+            tmux_run_async(&args).await?;
+        }
         Ok(())
     }
 }
 
 /// Get the pane ID for a target
 pub fn get_pane_id(target: &str) -> Result<String> {
-    let output = tmux(&["display-message", "-t", target, "-p", "#{pane_id}"])?;
-    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    Ok(stdout_line(&tmux(&[
+        "display-message",
+        "-t",
+        target,
+        "-p",
+        "#{pane_id}",
+    ])?))
+}
+
+/// Async [`get_pane_id`]
+pub async fn get_pane_id_async(target: &str) -> Result<String> {
+    Ok(stdout_line(
+        &tmux_async(&["display-message", "-t", target, "-p", "#{pane_id}"]).await?,
+    ))
+}
+
+/// Type text into a pane literally (no key-name lookup), then press Enter.
+///
+/// Commands are typed with this rather than passed as key names, so one
+/// that happens to match a key ("Enter", "C-c") is still typed as text.
+pub fn send_text(target: &str, text: &str) -> Result<()> {
+    tmux_run(&["send-keys", "-t", target, "-l", text])?;
+    tmux_run(&["send-keys", "-t", target, "Enter"])
+}
+
+/// Async [`send_text`]
+pub async fn send_text_async(target: &str, text: &str) -> Result<()> {
+    tmux_run_async(&["send-keys", "-t", target, "-l", text]).await?;
+    tmux_run_async(&["send-keys", "-t", target, "Enter"]).await
+}
+
+/// Pipe a pane's output to `command`, unless it is already piped somewhere
+pub async fn pipe_pane_async(target: &str, command: &str) -> Result<()> {
+    tmux_run_async(&["pipe-pane", "-o", "-t", target, command]).await
+}
+
+/// Pipe a pane's output to `command`, replacing its current pipe, or stop
+/// piping it with no command
+pub fn pipe_pane(target: &str, command: Option<&str>) -> Result<()> {
+    let mut args = vec!["pipe-pane", "-t", target];
+    args.extend(command);
+    tmux_run(&args)
+}
+
+/// Columns and rows of a pane
+pub fn pane_size(target: &str) -> Result<(u16, u16)> {
+    let output = tmux(&[
+        "display-message",
+        "-p",
+        "-t",
+        target,
+        "#{pane_width} #{pane_height}",
+    ])?;
+    let size = String::from_utf8_lossy(&output.stdout);
+    match size.trim().split_once(' ') {
+        Some((width, height)) if output.status.success() => Ok((width.parse()?, height.parse()?)),
+        _ => anyhow::bail!("Pane '{}' not found", target),
+    }
+}
+
+/// Columns and rows of the window holding `target`
+pub async fn window_size_async(target: &str) -> Result<(u32, u32)> {
+    let output = tmux_async(&[
+        "display-message",
+        "-p",
+        "-t",
+        target,
+        "#{window_width} #{window_height}",
+    ])
+    .await?;
+    let size = String::from_utf8_lossy(&output.stdout);
+    match size.trim().split_once(' ') {
+        Some((width, height)) if output.status.success() => Ok((width.parse()?, height.parse()?)),
+        _ => anyhow::bail!("Window of '{}' not found", target),
+    }
+}
+
+/// Tmux buffer [`paste_text`] goes through
+const PASTE_BUFFER: &str = "axel-paste";
+
+/// Paste text into a pane as one bracketed paste, then press Enter.
+///
+/// Unlike [`send_text`], a multi-line prompt arrives as a single input
+/// instead of submitting each line on its own.
+pub fn paste_text(target: &str, text: &str) -> Result<()> {
+    let mut child = tmux_command()
+        .args(["load-buffer", "-b", PASTE_BUFFER, "-"])
+        .stdin(std::process::Stdio::piped())
+        .spawn()
+        .context("Failed to execute tmux command")?;
+    if let Some(mut stdin) = child.stdin.take() {
+        use std::io::Write;
+        stdin.write_all(text.as_bytes())?;
+    }
+    if !child.wait()?.success() {
+        anyhow::bail!("Failed to load text into a tmux buffer");
+    }
+    tmux_run(&["paste-buffer", "-d", "-p", "-b", PASTE_BUFFER, "-t", target])?;
+    tmux_run(&["send-keys", "-t", target, "Enter"])
+}
+
+/// Press Escape in a pane, which interrupts a working agent
+pub fn interrupt_pane(target: &str) -> Result<()> {
+    tmux_run(&["send-keys", "-t", target, "Escape"])
+}
+
+/// Name of the session holding `pane`
+pub fn pane_session(pane: &str) -> Option<String> {
+    let output = tmux(&["display-message", "-p", "-t", pane, "#{session_name}"]).ok()?;
+    let name = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (output.status.success() && !name.is_empty()).then_some(name)
+}
+
+// =============================================================================
+// Pane Readiness
+// =============================================================================
+
+/// How long to wait for a pane's prompt before typing into it anyway
+pub const PANE_READY_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Interval between readiness checks
+const PANE_READY_POLL: Duration = Duration::from_millis(20);
+
+/// Shells recognized as a pane's interactive shell, besides `$SHELL`
+const KNOWN_SHELLS: &[&str] = &["bash", "zsh", "fish", "sh", "dash", "ksh", "tcsh", "nu"];
+
+/// Characters prompts commonly end with
+const PROMPT_MARKERS: &[char] = &['$', '%', '#', '>', '❯', '➜', 'λ', '»'];
+
+/// Foreground command, then cursor position, of a pane
+const CURSOR_FORMAT: &str = "#{pane_current_command}\t#{cursor_x}\t#{cursor_y}";
+
+/// What a pane shows, as far as readiness goes
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct PromptState {
+    /// Name of the pane's foreground process
+    command: String,
+    /// Text left of the cursor on its line
+    prompt: String,
+}
+
+impl PromptState {
+    /// Parse the [`CURSOR_FORMAT`] output of a pane
+    fn parse(cursor: &str) -> Option<(String, usize, usize)> {
+        let mut fields = cursor.trim_end().splitn(3, '\t');
+        let command = fields.next()?.to_string();
+        let x = fields.next()?.parse().ok()?;
+        let y = fields.next()?.parse().ok()?;
+        Some((command, x, y))
+    }
+
+    fn new(command: String, line: &str, x: usize) -> Self {
+        Self {
+            command,
+            prompt: line.chars().take(x).collect(),
+        }
+    }
+
+    /// Whether the pane's shell has drawn its prompt.
+    ///
+    /// The foreground process must be a shell, and the cursor must follow
+    /// text that either ends like a prompt or hasn't changed since the
+    /// `previous` check (custom prompts end in anything).
+    fn is_ready(&self, previous: Option<&PromptState>) -> bool {
+        let prompt = self.prompt.trim_end();
+        is_shell(&self.command)
+            && !prompt.is_empty()
+            && (prompt.ends_with(PROMPT_MARKERS) || previous == Some(self))
+    }
+}
+
+/// Whether `command`, a pane's foreground process, is an interactive shell
+pub fn is_shell(command: &str) -> bool {
+    // Login shells are reported as `-zsh`
+    let command = command.trim_start_matches('-');
+    KNOWN_SHELLS.contains(&command)
+        || std::env::var("SHELL").is_ok_and(|shell| {
+            Path::new(&shell)
+                .file_name()
+                .is_some_and(|name| name == command)
+        })
 }
 
-/// Send keys to a pane
-pub fn send_keys(target: &str, keys: &str) -> Result<()> {
-    tmux_run(&["send-keys", "-t", target, keys, "Enter"])
+/// Query what a pane shows for [`PromptState::is_ready`]
+fn prompt_state(target: &str) -> Option<PromptState> {
+    let output = tmux(&["display-message", "-t", target, "-p", CURSOR_FORMAT]).ok()?;
+    let (command, x, y) = PromptState::parse(&String::from_utf8_lossy(&output.stdout))?;
+    let y = y.to_string();
+    let line = tmux(&["capture-pane", "-p", "-t", target, "-S", &y, "-E", &y]).ok()?;
+    Some(PromptState::new(
+        command,
+        &String::from_utf8_lossy(&line.stdout),
+        x,
+    ))
+}
+
+/// Async [`prompt_state`]
+async fn prompt_state_async(target: &str) -> Option<PromptState> {
+    let output = tmux_async(&["display-message", "-t", target, "-p", CURSOR_FORMAT])
+        .await
+        .ok()?;
+    let (command, x, y) = PromptState::parse(&String::from_utf8_lossy(&output.stdout))?;
+    let y = y.to_string();
+    let line = tmux_async(&["capture-pane", "-p", "-t", target, "-S", &y, "-E", &y])
+        .await
+        .ok()?;
+    Some(PromptState::new(
+        command,
+        &String::from_utf8_lossy(&line.stdout),
+        x,
+    ))
+}
+
+/// Wait until a pane's shell shows its prompt, so typed input isn't lost
+/// to a shell still loading its rc files.
+///
+/// Polls the pane's foreground command (`#{pane_current_command}`) and the
+/// text before its cursor. Returns false if `timeout` passes first.
+pub fn wait_for_pane_ready(target: &str, timeout: Duration) -> bool {
+    let deadline = Instant::now() + timeout;
+    let mut previous = None;
+    loop {
+        let current = prompt_state(target);
+        if current
+            .as_ref()
+            .is_some_and(|c| c.is_ready(previous.as_ref()))
+        {
+            return true;
+        }
+        if Instant::now() >= deadline {
+            return false;
+        }
+        previous = current;
+        std::thread::sleep(PANE_READY_POLL);
+    }
+}
+
+/// Async [`wait_for_pane_ready`]
+pub async fn wait_for_pane_ready_async(target: &str, timeout: Duration) -> bool {
+    let deadline = Instant::now() + timeout;
+    let mut previous = None;
+    loop {
+        let current = prompt_state_async(target).await;
+        if current
+            .as_ref()
+            .is_some_and(|c| c.is_ready(previous.as_ref()))
+        {
+            return true;
+        }
+        if Instant::now() >= deadline {
+            return false;
+        }
+        previous = current;
+        tokio::time::sleep(PANE_READY_POLL).await;
+    }
+}
+
+/// A pane in a tmux session
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PaneInfo {
+    /// Pane ID (e.g. `%3`)
+    pub id: String,
+    /// Pane title (axel sets this to the pane name)
+    pub title: String,
+}
+
+/// List all panes in a session, across all windows
+pub fn list_panes(session: &str) -> Result<Vec<PaneInfo>> {
+    let output = tmux(&[
+        "list-panes",
+        "-s",
+        "-t",
+        session,
+        "-F",
+        "#{pane_id}\t#{pane_title}",
+    ])?;
+    if !output.status.success() {
+        anyhow::bail!("Failed to list panes for session '{}'", session);
+    }
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let (id, title) = line.split_once('\t')?;
+            Some(PaneInfo {
+                id: id.to_string(),
+                title: title.to_string(),
+            })
+        })
+        .collect())
+}
+
+/// A pane's liveness, as reported by `list-panes`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PaneStatus {
+    /// Pane ID (e.g. `%3`)
+    pub id: String,
+    /// Pane title (axel sets this to the pane name)
+    pub title: String,
+    /// The pane's process exited (kept by `remain-on-exit`)
+    pub dead: bool,
+    /// Name of the pane's foreground process
+    pub current_command: String,
+    /// Name of the window holding the pane
+    pub window_name: String,
+}
+
+/// List the status of every pane in a session, across all windows
+pub fn list_pane_status(session: &str) -> Result<Vec<PaneStatus>> {
+    let output = tmux(&[
+        "list-panes",
+        "-s",
+        "-t",
+        session,
+        "-F",
+        "#{pane_id}\t#{pane_dead}\t#{pane_current_command}\t#{window_name}\t#{pane_title}",
+    ])?;
+    if !output.status.success() {
+        anyhow::bail!("Failed to list panes for session '{}'", session);
+    }
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.splitn(5, '\t');
+            Some(PaneStatus {
+                id: fields.next()?.to_string(),
+                dead: fields.next()? == "1",
+                current_command: fields.next()?.to_string(),
+                window_name: fields.next()?.to_string(),
+                title: fields.next()?.to_string(),
+            })
+        })
+        .collect())
+}
+
+/// Where a pane lives and what it runs, as reported by `list-panes`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PaneDetail {
+    /// Pane ID (e.g. `%3`)
+    pub id: String,
+    /// ID of the window holding the pane
+    pub window_id: String,
+    /// Pane title (axel sets this to the pane name)
+    pub title: String,
+    /// Working directory of the pane's foreground process
+    pub current_path: String,
+    /// Name of the pane's foreground process
+    pub current_command: String,
+}
+
+/// List every pane in a session with its window and working directory
+pub fn list_pane_details(session: &str) -> Result<Vec<PaneDetail>> {
+    let output = tmux(&[
+        "list-panes",
+        "-s",
+        "-t",
+        session,
+        "-F",
+        "#{pane_id}\t#{window_id}\t#{pane_current_path}\t#{pane_current_command}\t#{pane_title}",
+    ])?;
+    if !output.status.success() {
+        anyhow::bail!("Failed to list panes for session '{}'", session);
+    }
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.splitn(5, '\t');
+            Some(PaneDetail {
+                id: fields.next()?.to_string(),
+                window_id: fields.next()?.to_string(),
+                current_path: fields.next()?.to_string(),
+                current_command: fields.next()?.to_string(),
+                title: fields.next()?.to_string(),
+            })
+        })
+        .collect())
+}
+
+/// Capture a pane's visible content and up to `history` lines of scrollback
+/// as plain text, with wrapped lines joined
+pub fn capture_pane(target: &str, history: usize) -> Result<String> {
+    let start = format!("-{}", history);
+    let output = tmux(&["capture-pane", "-p", "-J", "-t", target, "-S", &start])?;
+    if !output.status.success() {
+        anyhow::bail!("Failed to capture pane '{}'", target);
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Restart a pane in place, killing whatever it runs, with `command` (or a
+/// plain shell) started in `start_dir`
+pub fn respawn_pane(target: &str, start_dir: &str, command: Option<&str>) -> Result<()> {
+    let mut args = vec!["respawn-pane", "-k", "-t", target, "-c", start_dir];
+    // Shell command must come last
+    if let Some(cmd) = command {
+        args.push(cmd);
+    }
+    tmux_run(&args)
 }
 
 /// Bind a key in a specific key table
@@ -467,6 +1225,13 @@ pub fn bind_key(table: &str, key: &str, command: &[&str]) -> Result<()> {
     tmux_run(&args)
 }
 
+/// Async [`bind_key`]
+pub async fn bind_key_async(table: &str, key: &str, command: &[&str]) -> Result<()> {
+    let mut args = vec!["bind-key", "-T", table, key];
+    args.extend(command);
+    tmux_run_async(&args).await
+}
+
 // =============================================================================
 // Option Commands
 // =============================================================================
@@ -478,6 +1243,7 @@ pub struct SetOption<'a> {
     global: bool,
     server: bool,
     window: bool,
+    pane: bool,
     option: Option<&'a str>,
     value: Option<&'a str>,
 }
@@ -513,6 +1279,12 @@ impl<'a> SetOption<'a> {
         self
     }
 
+    /// Set as a pane option (target must be a pane)
+    pub fn pane(mut self) -> Self {
+        self.pane = true;
+        self
+    }
+
     /// Set the option name
     pub fn option(mut self, opt: &'a str) -> Self {
         self.option = Some(opt);
@@ -525,8 +1297,8 @@ impl<'a> SetOption<'a> {
         self
     }
 
-    /// Execute the set-option command
-    pub fn run(self) -> Result<()> {
+    /// Build the tmux arguments for this command
+    fn args(&self) -> Vec<&'a str> {
         let cmd = if self.window {
             "set-window-option"
         } else {
@@ -543,6 +1315,10 @@ impl<'a> SetOption<'a> {
             args.push("-s");
         }
 
+        if self.pane {
+            args.push("-p");
+        }
+
         if let Some(target) = self.target {
             args.push("-t");
             args.push(target);
@@ -556,6 +1332,149 @@ impl<'a> SetOption<'a> {
             args.push(val);
         }
 
-        tmux_run(&args)
+        args
+    }
+
+    /// Execute the set-option command
+    pub fn run(self) -> Result<()> {
+        tmux_run(&self.args())
+    }
+
+    /// Execute the set-option command without blocking
+    pub async fn run_async(self) -> Result<()> {
+        tmux_run_async(&self.args()).await
+    }
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_env_assignment() {
+        assert_eq!(
+            parse_env_assignment("AXEL_PORT=4320").unwrap(),
+            ("AXEL_PORT", "4320")
+        );
+        assert_eq!(parse_env_assignment("OPTS=a=b").unwrap(), ("OPTS", "a=b"));
+        assert_eq!(parse_env_assignment("EMPTY=").unwrap(), ("EMPTY", ""));
+
+        assert!(parse_env_assignment("NO_EQUALS").is_err());
+        assert!(parse_env_assignment("=value").is_err());
+        assert!(parse_env_assignment("1KEY=value").is_err());
+        assert!(parse_env_assignment("BAD-KEY=value").is_err());
+    }
+
+    #[test]
+    fn test_format_environment() {
+        let vars = [
+            ("AXEL_MANIFEST", Some("/code/app/AXEL.md".to_string())),
+            ("AXEL_PORT", Some("4318".to_string())),
+            ("AXEL_PANE_ID", None),
+        ];
+        assert_eq!(
+            format_environment(&vars),
+            "AXEL_MANIFEST=/code/app/AXEL.md\nAXEL_PORT=4318\n-AXEL_PANE_ID\n"
+        );
+        assert_eq!(format_environment(&[]), "");
+    }
+
+    #[test]
+    fn test_builder_args() {
+        let args = NewWindow::new()
+            .target("ws")
+            .name("logs")
+            .command("tail -f log")
+            .args();
+        assert_eq!(
+            args,
+            vec![
+                "new-window",
+                "-d",
+                "-t",
+                "ws:",
+                "-n",
+                "logs",
+                "-P",
+                "-F",
+                "#{pane_id}",
+                "tail -f log"
+            ]
+        );
+
+        let select = SelectPane::new()
+            .target("%1")
+            .title("claude")
+            .background("colour17");
+        assert_eq!(
+            select.commands(),
+            vec![
+                vec!["select-pane", "-t", "%1", "-P", "bg=colour17"],
+                vec!["select-pane", "-t", "%1", "-T", "claude"],
+            ]
+        );
+        assert_eq!(
+            SelectPane::new().target("%1").commands(),
+            vec![vec!["select-pane", "-t", "%1"]]
+        );
+
+        let args = SetOption::new()
+            .window()
+            .target("%1")
+            .option("allow-rename")
+            .value("off")
+            .args();
+        assert_eq!(
+            args,
+            vec!["set-window-option", "-t", "%1", "allow-rename", "off"]
+        );
+    }
+
+    #[test]
+    fn test_prompt_readiness() {
+        assert_eq!(
+            PromptState::parse("zsh\t12\t3\n"),
+            Some(("zsh".to_string(), 12, 3))
+        );
+        assert_eq!(PromptState::parse("zsh\t12"), None);
+
+        let state = |command: &str, line: &str, x| PromptState::new(command.into(), line, x);
+        assert!(state("bash", "me@box:~/app$ ", 14).is_ready(None));
+        assert!(state("-zsh", "box% ", 5).is_ready(None));
+        assert!(state("fish", "~/app ❯ ", 8).is_ready(None));
+
+        // Still in the wrapper script, or the shell hasn't drawn anything
+        assert!(!state("axel_ws_2", "- claude - $", 12).is_ready(None));
+        assert!(!state("bash", "", 0).is_ready(None));
+        assert!(!state("bash", "- claude -", 0).is_ready(None));
+
+        // A custom prompt counts once it stops changing
+        let custom = state("zsh", "app (main) ", 11);
+        assert!(!custom.is_ready(None));
+        assert!(custom.is_ready(Some(&custom.clone())));
+        assert!(!custom.is_ready(Some(&state("zsh", "app ", 4))));
+    }
+
+    #[test]
+    fn test_is_shell() {
+        assert!(is_shell("zsh"));
+        assert!(is_shell("-bash"));
+        assert!(is_shell("fish"));
+        assert!(!is_shell("axel_ws_3"));
+        assert!(!is_shell("node"));
+        assert!(!is_shell(""));
+    }
+
+    #[test]
+    fn test_select_layout_args() {
+        let args = SelectLayout::new().target("ws").layout("tiled").args();
+        assert_eq!(args, vec!["select-layout", "-t", "ws", "tiled"]);
+
+        let args = SelectLayout::new().layout("main-vertical").args();
+        assert_eq!(args, vec!["select-layout", "main-vertical"]);
     }
 }
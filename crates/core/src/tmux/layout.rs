@@ -0,0 +1,457 @@
+//! Grid layout for tmux windows.
+//!
+//! A window's cells are arranged as a tree: cells whose columns don't overlap
+//! are placed side by side, and within each group cells whose rows don't
+//! overlap are stacked, recursively. Without spans this is the usual grid of
+//! columns, each with its own rows; `colspan` and `rowspan` let a cell cover
+//! several, like one tall pane beside two, or a wide pane under three:
+//!
+//! ```yaml
+//! grids:
+//!   default:
+//!     claude: { col: 0, row: 0, rowspan: 2, width: 60 }
+//!     server: { col: 1, row: 0 }
+//!     tests:  { col: 1, row: 1 }
+//!     logs:   { col: 0, row: 2, colspan: 2, height: 20 }
+//! ```
+//!
+//! Panes are created by splitting along the tree, then sized all at once by
+//! a layout string applied with `select-layout`. A percentage split sizes
+//! the new pane against the pane it splits, less the border between them,
+//! so sizes made by splits alone drift from the ones asked for (48/52 for
+//! 50/50):
+//!
+//! ```text
+//! 7170,160x40,0,0{80x40,0,0,1,79x40,81,0[79x20,81,0,2,79x19,81,21,3]}
+//! ```
+//!
+//! Widths and heights are percentages of the space shared with the cell's
+//! neighbours: the window for a plain grid of columns, the column for the
+//! rows in it. A group of columns (or rows) takes the size of a cell that
+//! spans all of them; groups without a size share what is left, in
+//! proportion to how many columns (or rows) they cover.
+
+use std::collections::HashMap;
+
+use anyhow::{Result, bail};
+
+use crate::config::ResolvedPane;
+
+/// How a window's panes are arranged
+#[derive(Debug)]
+pub(crate) enum LayoutNode<'a> {
+    Pane(&'a ResolvedPane),
+    /// Parts side by side, left to right
+    Columns(Vec<LayoutPart<'a>>),
+    /// Parts stacked, top to bottom
+    Rows(Vec<LayoutPart<'a>>),
+}
+
+/// One part of a [`LayoutNode::Columns`] or [`LayoutNode::Rows`]
+#[derive(Debug)]
+pub(crate) struct LayoutPart<'a> {
+    /// Percentage of the node's space, if the cells set one
+    share: Option<u32>,
+    /// Grid columns (or rows) the part covers
+    span: u32,
+    node: LayoutNode<'a>,
+}
+
+/// A split creating `pane` from the already created `target`
+#[derive(Debug)]
+pub(crate) struct LayoutSplit<'a> {
+    pub target: &'a ResolvedPane,
+    pub pane: &'a ResolvedPane,
+    /// Side by side (`split-window -h`) rather than stacked
+    pub horizontal: bool,
+    /// Share of `target` the new pane takes
+    pub percentage: u32,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Axis {
+    Columns,
+    Rows,
+}
+
+impl Axis {
+    /// Grid lines `pane` covers along the axis, end excluded
+    fn range(self, pane: &ResolvedPane) -> (u32, u32) {
+        match self {
+            Axis::Columns => (pane.col, pane.col + pane.colspan),
+            Axis::Rows => (pane.row, pane.row + pane.rowspan),
+        }
+    }
+
+    fn share(self, pane: &ResolvedPane) -> Option<u32> {
+        match self {
+            Axis::Columns => pane.width,
+            Axis::Rows => pane.height,
+        }
+    }
+}
+
+/// Arrange a window's panes, or fail when their cells overlap or can't be
+/// divided into columns and rows
+pub(crate) fn layout_tree<'a>(panes: &[&'a ResolvedPane]) -> Result<LayoutNode<'a>> {
+    if let [pane] = panes {
+        return Ok(LayoutNode::Pane(pane));
+    }
+    for axis in [Axis::Columns, Axis::Rows] {
+        let groups = groups(panes, axis);
+        if groups.len() < 2 {
+            continue;
+        }
+        let parts = groups
+            .iter()
+            .map(|group| layout_part(group, axis))
+            .collect::<Result<Vec<_>>>()?;
+        return Ok(match axis {
+            Axis::Columns => LayoutNode::Columns(parts),
+            Axis::Rows => LayoutNode::Rows(parts),
+        });
+    }
+    let names: Vec<&str> = panes.iter().map(|p| p.name.as_str()).collect();
+    bail!(
+        "Cells {} overlap or can't be divided into columns and rows",
+        names.join(", ")
+    )
+}
+
+/// Split `panes` into groups that don't overlap along `axis`, in order
+fn groups<'a>(panes: &[&'a ResolvedPane], axis: Axis) -> Vec<Vec<&'a ResolvedPane>> {
+    let mut sorted = panes.to_vec();
+    sorted.sort_by_key(|pane| axis.range(pane));
+
+    let mut groups: Vec<(u32, Vec<&ResolvedPane>)> = Vec::new();
+    for pane in sorted {
+        let (start, end) = axis.range(pane);
+        match groups.last_mut() {
+            Some((group_end, group)) if start < *group_end => {
+                *group_end = (*group_end).max(end);
+                group.push(pane);
+            }
+            _ => groups.push((end, vec![pane])),
+        }
+    }
+    groups.into_iter().map(|(_, group)| group).collect()
+}
+
+fn layout_part<'a>(group: &[&'a ResolvedPane], axis: Axis) -> Result<LayoutPart<'a>> {
+    let start = group.iter().map(|p| axis.range(p).0).min().unwrap_or(0);
+    let end = group.iter().map(|p| axis.range(p).1).max().unwrap_or(start);
+    Ok(LayoutPart {
+        share: group
+            .iter()
+            .filter(|p| axis.range(p) == (start, end))
+            .find_map(|p| axis.share(p)),
+        span: end - start,
+        node: layout_tree(group)?,
+    })
+}
+
+impl<'a> LayoutNode<'a> {
+    /// Top-left pane, the one the window starts with
+    pub(crate) fn first(&self) -> &'a ResolvedPane {
+        match self {
+            LayoutNode::Pane(pane) => pane,
+            LayoutNode::Columns(parts) | LayoutNode::Rows(parts) => parts[0].node.first(),
+        }
+    }
+
+    /// Splits creating every pane but the first, in order.
+    ///
+    /// Each node's parts are split off one another before any part is
+    /// divided further, so tmux keeps the panes in the order the layout
+    /// string lists them.
+    pub(crate) fn splits(&self) -> Vec<LayoutSplit<'a>> {
+        let mut splits = Vec::new();
+        self.push_splits(&mut splits);
+        splits
+    }
+
+    fn push_splits(&self, splits: &mut Vec<LayoutSplit<'a>>) {
+        let (parts, horizontal) = match self {
+            LayoutNode::Pane(_) => return,
+            LayoutNode::Columns(parts) => (parts, true),
+            LayoutNode::Rows(parts) => (parts, false),
+        };
+        // Each split leaves room for the parts still to come; the layout
+        // string sets the real sizes afterwards
+        for (index, pair) in parts.windows(2).enumerate() {
+            let remaining = (parts.len() - index - 1) as u32;
+            splits.push(LayoutSplit {
+                target: pair[0].node.first(),
+                pane: pair[1].node.first(),
+                horizontal,
+                percentage: remaining * 100 / (remaining + 1),
+            });
+        }
+        for part in parts {
+            part.node.push_splits(splits);
+        }
+    }
+
+    /// Layout string for a `width`x`height` window, with `ids` mapping pane
+    /// names to tmux pane IDs
+    pub(crate) fn layout_string(
+        &self,
+        width: u32,
+        height: u32,
+        ids: &HashMap<&str, String>,
+    ) -> String {
+        let body = self.describe(width, height, 0, 0, ids);
+        format!("{:04x},{body}", checksum(&body))
+    }
+
+    fn describe(
+        &self,
+        width: u32,
+        height: u32,
+        x: u32,
+        y: u32,
+        ids: &HashMap<&str, String>,
+    ) -> String {
+        let (parts, horizontal) = match self {
+            LayoutNode::Pane(pane) => {
+                let id = ids
+                    .get(pane.name.as_str())
+                    .map_or("0", |id| id.trim_start_matches('%'));
+                return format!("{width}x{height},{x},{y},{id}");
+            }
+            LayoutNode::Columns(parts) => (parts, true),
+            LayoutNode::Rows(parts) => (parts, false),
+        };
+
+        let sizes = part_sizes(if horizontal { width } else { height }, parts);
+        let mut offset = if horizontal { x } else { y };
+        let children: Vec<String> = parts
+            .iter()
+            .zip(sizes)
+            .map(|(part, size)| {
+                let child = if horizontal {
+                    part.node.describe(size, height, offset, y, ids)
+                } else {
+                    part.node.describe(width, size, x, offset, ids)
+                };
+                offset += size + 1;
+                child
+            })
+            .collect();
+
+        let (open, close) = if horizontal { ('{', '}') } else { ('[', ']') };
+        format!(
+            "{width}x{height},{x},{y}{open}{}{close}",
+            children.join(",")
+        )
+    }
+}
+
+/// Sizes of `parts` sharing `total` cells.
+///
+/// Space is divided per grid column (or row), so parts covering the same
+/// columns line up across the grid, then each part gets its columns and the
+/// borders between them.
+fn part_sizes(total: u32, parts: &[LayoutPart]) -> Vec<u32> {
+    let units: Vec<Option<f64>> = parts
+        .iter()
+        .flat_map(|part| {
+            let share = part
+                .share
+                .map(|share| f64::from(share) / f64::from(part.span));
+            std::iter::repeat_n(share, part.span as usize)
+        })
+        .collect();
+    let mut sizes = distribute(total, &units).into_iter();
+    parts
+        .iter()
+        .map(|part| sizes.by_ref().take(part.span as usize).sum::<u32>() + part.span - 1)
+        .collect()
+}
+
+/// Split `total` cells between slots separated by one-cell borders.
+///
+/// Slots with a percentage get that share of the space; the others split
+/// what is left evenly. Every slot gets at least one cell, and rounding
+/// leftovers go to the slots that lost the most to it, so the sizes always
+/// add up exactly.
+fn distribute(total: u32, shares: &[Option<f64>]) -> Vec<u32> {
+    let count = shares.len() as u32;
+    if count == 0 {
+        return Vec::new();
+    }
+    let space = total.saturating_sub(count - 1);
+
+    let explicit: f64 = shares.iter().flatten().sum();
+    let open = shares.iter().filter(|share| share.is_none()).count().max(1);
+    let rest = (100.0 - explicit).max(0.0) / open as f64;
+    let mut weights: Vec<f64> = shares.iter().map(|share| share.unwrap_or(rest)).collect();
+    if weights.iter().sum::<f64>() <= 0.0 {
+        weights = vec![1.0; shares.len()];
+    }
+    let sum: f64 = weights.iter().sum();
+
+    let ideal: Vec<f64> = weights
+        .iter()
+        .map(|weight| f64::from(space) * weight / sum)
+        .collect();
+    let mut sizes: Vec<u32> = ideal.iter().map(|size| (*size as u32).max(1)).collect();
+
+    let shortfall = |sizes: &[u32], i: usize| ideal[i] - f64::from(sizes[i]);
+    while sizes.iter().sum::<u32>() < space {
+        let i = (0..sizes.len())
+            .max_by(|&a, &b| {
+                shortfall(&sizes, a)
+                    .total_cmp(&shortfall(&sizes, b))
+                    .then(b.cmp(&a))
+            })
+            .unwrap_or(0);
+        sizes[i] += 1;
+    }
+    while sizes.iter().sum::<u32>() > space {
+        let Some(i) = (0..sizes.len())
+            .filter(|&i| sizes[i] > 1)
+            .min_by(|&a, &b| shortfall(&sizes, a).total_cmp(&shortfall(&sizes, b)))
+        else {
+            break;
+        };
+        sizes[i] -= 1;
+    }
+    sizes
+}
+
+/// tmux's layout checksum: a 16-bit rotate-and-add over the layout
+fn checksum(layout: &str) -> u16 {
+    layout.bytes().fold(0u16, |sum, byte| {
+        sum.rotate_right(1).wrapping_add(u16::from(byte))
+    })
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::PaneConfig;
+
+    fn pane(
+        name: &str,
+        (col, row): (u32, u32),
+        (colspan, rowspan): (u32, u32),
+        (width, height): (Option<u32>, Option<u32>),
+    ) -> ResolvedPane {
+        ResolvedPane {
+            name: name.to_string(),
+            col,
+            row,
+            width,
+            height,
+            colspan,
+            rowspan,
+            window: None,
+            config: PaneConfig::Custom(Default::default()),
+        }
+    }
+
+    fn ids(panes: &[ResolvedPane]) -> HashMap<&str, String> {
+        panes
+            .iter()
+            .enumerate()
+            .map(|(i, p)| (p.name.as_str(), format!("%{}", i + 1)))
+            .collect()
+    }
+
+    #[test]
+    fn test_distribute() {
+        // 50/50 of 160 columns, less the border
+        assert_eq!(distribute(160, &[Some(50.0), Some(50.0)]), [80, 79]);
+        assert_eq!(distribute(160, &[None, None]), [80, 79]);
+        // Unsized slots share what the sized ones leave
+        assert_eq!(distribute(101, &[Some(50.0), None, None]), [49, 25, 25]);
+        assert_eq!(distribute(40, &[None, None, None]), [13, 13, 12]);
+        // Oversized percentages still leave every slot a cell
+        assert_eq!(distribute(10, &[Some(100.0), None]), [8, 1]);
+        assert_eq!(distribute(80, &[None]), [80]);
+    }
+
+    #[test]
+    fn test_grid_layout() {
+        let panes = [
+            pane("a", (0, 0), (1, 1), (Some(50), None)),
+            pane("b", (1, 0), (1, 1), (None, None)),
+            pane("c", (1, 1), (1, 1), (None, None)),
+        ];
+        let refs: Vec<&ResolvedPane> = panes.iter().collect();
+        let tree = layout_tree(&refs).unwrap();
+        assert_eq!(
+            tree.layout_string(160, 40, &ids(&panes)),
+            "7170,160x40,0,0{80x40,0,0,1,79x40,81,0[79x20,81,0,2,79x19,81,21,3]}"
+        );
+
+        // Columns first, then the rows of each column
+        let splits: Vec<(&str, &str, bool)> = tree
+            .splits()
+            .iter()
+            .map(|s| (s.target.name.as_str(), s.pane.name.as_str(), s.horizontal))
+            .collect();
+        assert_eq!(splits, [("a", "b", true), ("b", "c", false)]);
+
+        let tree = layout_tree(&refs[..1]).unwrap();
+        assert!(
+            tree.layout_string(80, 24, &ids(&panes))
+                .ends_with(",80x24,0,0,1")
+        );
+    }
+
+    #[test]
+    fn test_spanning_cells() {
+        // A tall pane beside two rows, over a pane as wide as the grid
+        let panes = [
+            pane("tall", (0, 0), (1, 2), (Some(60), None)),
+            pane("top", (1, 0), (1, 1), (None, None)),
+            pane("bottom", (1, 1), (1, 1), (None, None)),
+            pane("wide", (0, 2), (2, 1), (None, Some(25))),
+        ];
+        let refs: Vec<&ResolvedPane> = panes.iter().collect();
+        let tree = layout_tree(&refs).unwrap();
+        assert_eq!(tree.first().name, "tall");
+        assert_eq!(
+            &tree.layout_string(101, 41, &ids(&panes))[5..],
+            "101x41,0,0[101x30,0,0{60x30,0,0,1,40x30,61,0[40x15,61,0,2,40x14,61,16,3]},101x10,0,31,4]"
+        );
+
+        // Rows spanned on one side line up with the rows on the other
+        let panes = [
+            pane("a", (0, 0), (1, 1), (None, None)),
+            pane("b", (0, 1), (1, 1), (None, None)),
+            pane("c", (0, 2), (1, 1), (None, None)),
+            pane("d", (1, 0), (1, 2), (None, None)),
+            pane("e", (1, 2), (1, 1), (None, None)),
+        ];
+        let refs: Vec<&ResolvedPane> = panes.iter().collect();
+        let layout = layout_tree(&refs)
+            .unwrap()
+            .layout_string(81, 32, &ids(&panes));
+        assert_eq!(
+            &layout[5..],
+            "81x32,0,0{40x32,0,0[40x10,0,0,1,40x10,0,11,2,40x10,0,22,3],40x32,41,0[40x21,41,0,4,40x10,41,22,5]}"
+        );
+
+        // Four cells wound around a center can't be split into columns and rows
+        let panes = [
+            pane("a", (0, 0), (2, 1), (None, None)),
+            pane("b", (2, 0), (1, 2), (None, None)),
+            pane("c", (1, 2), (2, 1), (None, None)),
+            pane("d", (0, 1), (1, 2), (None, None)),
+            pane("e", (1, 1), (1, 1), (None, None)),
+        ];
+        let refs: Vec<&ResolvedPane> = panes.iter().collect();
+        let err = layout_tree(&refs).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("can't be divided into columns and rows")
+        );
+    }
+}
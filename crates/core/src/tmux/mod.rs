@@ -7,6 +7,9 @@
 //!
 //! - [`commands`]: Low-level tmux command builders (NewSession, SplitWindow, etc.)
 //! - [`session`]: High-level workspace creation from axel configuration
+//! - `layout`: Layout strings sizing a grid's panes exactly
+//! - [`snapshot`]: Saving a running session and recreating it later
+//! - [`theme`]: The manifest's `tmux:` colors, status line and key bindings
 //!
 //! # Usage
 //!
@@ -21,11 +24,21 @@
 //! attach_session("my-project")?;
 //! ```
 //!
+//! [`create_workspace_async`] and [`reload_workspace_async`] do the same
+//! from inside a tokio runtime; the blocking versions run them on a runtime
+//! of their own.
+//!
 //! For session management, use [`has_session`], [`attach_session`], [`kill_session`],
 //! and [`current_session`] to query and control tmux sessions.
 
 mod commands;
+mod layout;
 mod session;
+mod snapshot;
+mod theme;
 
 pub use commands::*;
+pub(crate) use layout::layout_tree;
 pub use session::*;
+pub use snapshot::*;
+pub use theme::{TmuxBinding, TmuxConfig, TmuxIsolation};
@@ -7,10 +7,14 @@
 //! # Layout Algorithm
 //!
 //! Panes are organized in a column-major grid:
-//! 1. Panes are sorted by (col, row)
+//! 1. Cells are grouped into columns, and each column into rows; cells
+//!    spanning several columns or rows nest the grouping (see `layout`)
 //! 2. Columns are created via horizontal splits from left to right
 //! 3. Rows within each column are created via vertical splits
-//! 4. Width/height percentages are applied during splits
+//! 4. A layout string for the whole grid then sizes every pane exactly
+//!
+//! Cells with a `window:` name are laid out the same way in extra windows of
+//! the session, created in the order the names first appear in the grid.
 //!
 //! # Session Features
 //!
@@ -19,24 +23,45 @@
 //! - Color-coded panes based on shell configuration
 //! - Automatic skill installation per driver type
 //! - Manifest path stored in session environment for cleanup
+//! - AI pane output recorded to transcripts (see [`crate::transcript`])
+
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, HashMap, HashSet},
+    path::{Path, PathBuf},
+    sync::{
+        Mutex, Once,
+        atomic::{AtomicBool, Ordering},
+    },
+};
 
-use std::{collections::HashMap, io::Write};
-
-use anyhow::Result;
+use anyhow::{Context, Result};
 use colored::Colorize;
-
-use super::commands::{
-    NewSession, SelectPane, SetOption, SplitWindow, bind_key, get_pane_id, rename_window,
-    send_keys, set_environment,
+use futures_util::future::{join_all, try_join_all};
+
+use super::{
+    commands::{
+        NewSession, NewWindow, PANE_READY_TIMEOUT, PaneInfo, SelectLayout, SelectPane, SetOption,
+        SplitWindow, bind_key_async, block_on, get_environment, get_pane_id_async, has_session,
+        kill_session, list_pane_status, pipe_pane_async, rename_window_async, respawn_pane,
+        select_window_async, send_text_async, set_environment_async, wait_for_pane_ready_async,
+        window_size_async, zoom_pane_async,
+    },
+    layout::{LayoutNode, layout_tree},
+    theme::{TmuxConfig, TmuxIsolation},
 };
 use crate::{
     claude::ClaudeCommand,
     config::{
-        AiPaneConfig, PaneConfig, ResolvedPane, WorkspaceConfig, WorkspaceIndex, expand_path,
-        to_fg_rgb, to_tmux_color,
+        AiPaneConfig, PaneConfig, ResolvedPane, ResumeConfig, SKILLS_LOCK_FILE, SkillsLock,
+        TmuxLayout, WorkspaceConfig, WorkspaceIndex, expand_path, group_by_window, to_fg_rgb,
+        to_tmux_color,
     },
     drivers,
-    hooks::{otel_logs_endpoint, otel_metrics_endpoint, otel_traces_endpoint},
+    hooks::events_endpoint,
+    shell,
+    timing::Timings,
+    transcript::{rotate_transcript, transcript_path},
 };
 
 /// OTEL configuration for pane commands (used by macOS app integration)
@@ -60,6 +85,7 @@ const OPT_PANE_ACTIVE_BORDER_STYLE: &str = "pane-active-border-style";
 const OPT_STATUS_STYLE: &str = "status-style";
 const OPT_STATUS_RIGHT: &str = "status-right";
 const OPT_ALLOW_RENAME: &str = "allow-rename";
+const OPT_PREFIX: &str = "prefix";
 
 // =============================================================================
 // Tmux option values
@@ -75,15 +101,25 @@ const VAL_TOP: &str = "top";
 
 const KEY_TABLE_COPY_MODE: &str = "copy-mode";
 const KEY_TABLE_ROOT: &str = "root";
+const KEY_TABLE_PREFIX: &str = "prefix";
 const KEY_MOUSE_DRAG_END: &str = "MouseDragEnd1Pane";
 const KEY_WHEEL_UP: &str = "WheelUpPane";
 const KEY_WHEEL_DOWN: &str = "WheelDownPane";
+/// `prefix B` prompts for text to send to every AI pane of the session
+const KEY_BROADCAST: &str = "B";
+/// `prefix Tab` focuses the next AI pane of the session
+const KEY_FOCUS_NEXT: &str = "Tab";
+/// `prefix BTab` (Shift-Tab) focuses the previous AI pane
+const KEY_FOCUS_PREV: &str = "BTab";
+
+/// Tmux buffer the broadcast prompt's text goes through
+const BROADCAST_BUFFER: &str = "axel-broadcast";
 
 // =============================================================================
 // Axel-specific constants
 // =============================================================================
 
-/// Axel accent color (blue)
+/// Axel accent color (blue), unless the manifest sets `tmux.accent`
 const AXEL_COLOR: &str = "#85A2FF";
 /// Pane border format template
 const PANE_BORDER_FORMAT: &str = "#[align=centre] #{pane_title} ";
@@ -97,45 +133,132 @@ pub const AXEL_PORT_ENV: &str = "AXEL_PORT";
 /// Environment variable name for storing the pane ID in tmux session
 pub const AXEL_PANE_ID_ENV: &str = "AXEL_PANE_ID";
 
+/// Environment variable name for storing the tmux pane ID of each pane,
+/// by name, in tmux session (`claude=%1 shell=%2`)
+pub const AXEL_PANES_ENV: &str = "AXEL_PANES";
+
+/// Session environment variables managed by axel
+pub const AXEL_ENV_VARS: &[&str] = &[
+    AXEL_MANIFEST_ENV,
+    AXEL_PORT_ENV,
+    AXEL_PANE_ID_ENV,
+    AXEL_PANES_ENV,
+];
+
 /// Build the command string for an AI pane (Claude or OpenCode).
 ///
 /// Both Claude Code and OpenCode use similar CLI interfaces, so this function
 /// handles both by parameterizing the command name. The command is built using
 /// `ClaudeCommand` builder which handles argument escaping and formatting.
 ///
+/// For OpenCode, the command is prefixed with the OTEL variables when
+/// `otel_config` is given and with `OPENCODE_CONFIG` when the pane has a
+/// generated config (MCP servers and permissions).
+///
+/// With `one_shot`, the command runs the prompt non-interactively and prints
+/// the reply (`claude -p`, `opencode run`).
+///
 /// Note: The `_index` parameter is unused because index content is handled via
-/// CLAUDE.md symlink for Claude (installed by the driver).
+/// CLAUDE.md symlink for Claude (installed by the driver). Context files are
+/// passed as `@path` references in the prompt.
 fn build_ai_command(
     command_name: &str,
     config: &AiPaneConfig,
+    workspace_dir: Option<&std::path::Path>,
     _index: Option<&WorkspaceIndex>,
+    otel_config: Option<&OtelConfig>,
+    one_shot: bool,
 ) -> String {
     let mut cmd = ClaudeCommand::new();
+    if one_shot && command_name == "claude" {
+        cmd = cmd.print();
+    }
 
     if let Some(model) = &config.model {
         cmd = cmd.model(model);
     }
-    if !config.allowed_tools.is_empty() {
-        cmd = cmd.allowed_tools(config.allowed_tools.clone());
-    }
-    if !config.disallowed_tools.is_empty() {
-        cmd = cmd.disallowed_tools(config.disallowed_tools.clone());
+    // These flags are Claude-only; the resumed conversation already has its
+    // prompt. OpenCode gets its permissions from its generated config.
+    let mut resuming = false;
+    if command_name == "claude" {
+        if !config.allowed_tools.is_empty() {
+            cmd = cmd.allowed_tools(config.allowed_tools.clone());
+        }
+        if !config.disallowed_tools.is_empty() {
+            cmd = cmd.disallowed_tools(config.disallowed_tools.clone());
+        }
+        if let Some(mode) = &config.permission_mode {
+            cmd = cmd.permission_mode(mode);
+        }
+        if let Some(format) = &config.output_format {
+            cmd = cmd.output_format(format);
+        }
+        if let Some(turns) = config.max_turns {
+            cmd = cmd.max_turns(turns);
+        }
+        for dir in &config.add_dirs {
+            cmd = cmd.add_dir(dir);
+        }
+        match &config.resume {
+            Some(ResumeConfig::Last(true)) => cmd = cmd.continue_last(),
+            Some(ResumeConfig::Session(id)) => cmd = cmd.resume(id),
+            _ => {}
+        }
+        resuming = cmd.continue_last || cmd.resume.is_some();
+        if let Some(id) = config.session_id.as_ref().filter(|_| !resuming) {
+            cmd = cmd.session_id(id);
+        }
     }
     // Only use explicit prompt - index is handled via CLAUDE.md symlink for Claude
-    if let Some(prompt) = &config.prompt {
+    if (one_shot || !resuming)
+        && let Some(prompt) = config.prompt_with_context(config.prompt.as_deref(), workspace_dir)
+    {
         cmd = cmd.prompt(prompt);
     }
     for arg in &config.args {
         cmd = cmd.extra_arg(arg);
     }
 
+    // MCP servers (and OpenCode permissions) are written to a per-pane
+    // config when skills are installed
+    let pane_name = config.name.as_deref().unwrap_or(&config.pane_type);
+    if command_name == "claude"
+        && let Some(dir) = workspace_dir.filter(|_| !config.mcp.is_empty())
+    {
+        cmd = cmd.mcp_config(drivers::mcp_config_path(dir, pane_name).to_string_lossy());
+    }
+
     let built = cmd.build();
+    if command_name == "claude" {
+        return built;
+    }
+
     // Replace "claude" with actual command if different
-    if command_name != "claude" {
-        built.replacen("claude", command_name, 1)
-    } else {
-        built
+    let mut parts = Vec::new();
+    if let Some(otel) = otel_config
+        && let Some(driver) = drivers::get_driver(command_name)
+    {
+        parts.extend(
+            driver
+                .otel_env_vars(otel.port, &otel.pane_id)
+                .iter()
+                .map(|(key, value)| format!("{}={}", key, shell::quote(value))),
+        );
+    }
+    if let Some(dir) = workspace_dir.filter(|_| drivers::opencode_config(config).is_some()) {
+        let path = drivers::opencode_config_path(dir, pane_name);
+        parts.push(format!(
+            "OPENCODE_CONFIG={}",
+            shell::quote(&path.to_string_lossy())
+        ));
     }
+    let program = if one_shot && command_name == "opencode" {
+        "opencode run"
+    } else {
+        command_name
+    };
+    parts.push(built.replacen("claude", program, 1));
+    parts.join(" ")
 }
 
 /// Build the command string for Antigravity CLI.
@@ -145,26 +268,29 @@ fn build_ai_command(
 ///
 /// The CLI interface supports:
 /// - `-m` for model selection
-/// - Initial prompt as a positional argument
-fn build_antigravity_command(config: &AiPaneConfig, index: Option<&WorkspaceIndex>) -> String {
+/// - Initial prompt as a positional argument (context files are inlined into it)
+fn build_antigravity_command(
+    config: &AiPaneConfig,
+    workspace_dir: Option<&std::path::Path>,
+    index: Option<&WorkspaceIndex>,
+) -> String {
     let mut parts = vec!["antigravity".to_string()];
 
     if let Some(model) = &config.model {
         parts.push("-m".to_string());
-        parts.push(model.clone());
+        parts.push(shell::quote(model).into_owned());
     }
 
     for arg in &config.args {
         parts.push(arg.clone());
     }
 
-    // Use single quotes for shell safety
-    if let Some(prompt) = &config.prompt {
-        let escaped = prompt.replace('\'', "'\\''");
-        parts.push(format!("'{}'", escaped));
-    } else if let Some(idx) = index {
-        let escaped = idx.to_initial_prompt().replace('\'', "'\\''");
-        parts.push(format!("'{}'", escaped));
+    let prompt = config
+        .prompt
+        .clone()
+        .or_else(|| index.map(|idx| idx.to_initial_prompt()));
+    if let Some(prompt) = config.prompt_with_context(prompt.as_deref(), workspace_dir) {
+        parts.push(shell::single_quote(&prompt));
     }
 
     parts.join(" ")
@@ -173,75 +299,63 @@ fn build_antigravity_command(config: &AiPaneConfig, index: Option<&WorkspaceInde
 /// Build the command string for Codex CLI.
 ///
 /// Codex has a different CLI interface than Claude/OpenCode. Key differences:
-/// - Uses `-c` for config options instead of dedicated flags
+/// - Settings come from a config profile instead of dedicated flags
 /// - Agents are discovered via `project_doc_fallback_filenames` config
-/// - Initial prompt is passed as a positional argument
+/// - Initial prompt is passed as a positional argument, with context files
+///   inlined into it
 ///
-/// The command includes `-c 'project_doc_fallback_filenames=[".codex/AGENTS.md"]'`
-/// to ensure Codex discovers the merged skills file created by the driver.
+/// The pane's profile (skills file and, with `otel_config`, telemetry) is
+/// written to Codex's config at `codex_config` (see
+/// [`drivers::codex_config_path`]) and selected with `--profile`. Without
+/// one, or if it can't be written, the same settings are passed as `-c`
+/// overrides. With `one_shot`, the prompt runs through `codex exec`.
 fn build_codex_command(
     config: &AiPaneConfig,
-    _workspace_dir: Option<&std::path::Path>,
+    workspace_dir: Option<&std::path::Path>,
     index: Option<&WorkspaceIndex>,
     otel_config: Option<&OtelConfig>,
+    codex_config: Option<&std::path::Path>,
+    one_shot: bool,
 ) -> String {
     let mut parts = vec!["codex".to_string()];
+    if one_shot {
+        parts.push("exec".to_string());
+    }
 
-    // Add .codex/AGENTS.md to fallback filenames so Codex discovers it
-    parts.push("-c".to_string());
-    parts.push("'project_doc_fallback_filenames=[\".codex/AGENTS.md\"]'".to_string());
-
-    // Add OTEL configuration if provided (macOS app integration)
-    if let Some(otel) = otel_config {
-        let logs_endpoint = otel_logs_endpoint(otel.port, &otel.pane_id);
-        let traces_endpoint = otel_traces_endpoint(otel.port, &otel.pane_id);
-        let metrics_endpoint = otel_metrics_endpoint(otel.port, &otel.pane_id);
-
-        // Enable analytics (required for metrics export)
-        parts.push("-c".to_string());
-        parts.push("'analytics_enabled=true'".to_string());
-        // Enable bell notifications for approvals (allows tmux to detect them)
-        parts.push("-c".to_string());
-        parts.push("'tui_notifications=\"always\"'".to_string());
-        parts.push("-c".to_string());
-        parts.push("'tui_notification_method=\"bel\"'".to_string());
-        // Disable paste burst detection so tmux send-keys works correctly
-        parts.push("-c".to_string());
-        parts.push("'disable_paste_burst=true'".to_string());
-        // Configure OTEL exporters
-        parts.push("-c".to_string());
-        parts.push(format!(
-            r#"'otel.exporter={{otlp-http={{endpoint="{}",protocol="json"}}}}'"#,
-            logs_endpoint
-        ));
-        parts.push("-c".to_string());
-        parts.push(format!(
-            r#"'otel.trace_exporter={{otlp-http={{endpoint="{}",protocol="json"}}}}'"#,
-            traces_endpoint
-        ));
-        parts.push("-c".to_string());
-        parts.push(format!(
-            r#"'otel.metrics_exporter={{otlp-http={{endpoint="{}",protocol="json"}}}}'"#,
-            metrics_endpoint
-        ));
+    let pane_name = config.name.as_deref().unwrap_or(&config.pane_type);
+    let dir = workspace_dir.unwrap_or(std::path::Path::new("."));
+    let profile_name = drivers::codex_profile_name(dir, pane_name);
+    let profile =
+        drivers::codex_profile(otel_config.map(|otel| (otel.port, otel.pane_id.as_str())));
+    let written =
+        codex_config.map(|path| drivers::write_codex_profile(path, &profile_name, profile.clone()));
+    match written {
+        Some(Ok(())) => {
+            parts.push("--profile".to_string());
+            parts.push(shell::quote(&profile_name).into_owned());
+        }
+        _ => parts.extend(
+            drivers::config_overrides(&profile)
+                .iter()
+                .map(|arg| shell::quote(arg).into_owned()),
+        ),
     }
 
     if let Some(model) = &config.model {
         parts.push("-m".to_string());
-        parts.push(model.clone());
+        parts.push(shell::quote(model).into_owned());
     }
 
     for arg in &config.args {
         parts.push(arg.clone());
     }
 
-    // Use single quotes for shell safety
-    if let Some(prompt) = &config.prompt {
-        let escaped = prompt.replace('\'', "'\\''");
-        parts.push(format!("'{}'", escaped));
-    } else if let Some(idx) = index {
-        let escaped = idx.to_initial_prompt().replace('\'', "'\\''");
-        parts.push(format!("'{}'", escaped));
+    let prompt = config
+        .prompt
+        .clone()
+        .or_else(|| index.map(|idx| idx.to_initial_prompt()));
+    if let Some(prompt) = config.prompt_with_context(prompt.as_deref(), workspace_dir) {
+        parts.push(shell::single_quote(&prompt));
     }
 
     parts.join(" ")
@@ -254,20 +368,118 @@ pub fn build_pane_command(
     index: Option<&WorkspaceIndex>,
     otel_config: Option<&OtelConfig>,
 ) -> Option<String> {
-    match &pane.config {
-        PaneConfig::Claude(config) => Some(build_ai_command("claude", config, index)),
+    pane_command(&pane.config, workspace_dir, index, otel_config, None)
+}
+
+/// Build the command to run for a pane config, with `prompt` replacing the
+/// pane's initial prompt when given
+pub fn pane_command(
+    pane: &PaneConfig,
+    workspace_dir: Option<&std::path::Path>,
+    index: Option<&WorkspaceIndex>,
+    otel_config: Option<&OtelConfig>,
+    prompt: Option<&str>,
+) -> Option<String> {
+    match pane {
+        PaneConfig::Claude(config) => Some(build_ai_command(
+            "claude",
+            &with_prompt(config, prompt),
+            workspace_dir,
+            index,
+            None,
+            false,
+        )),
         PaneConfig::Codex(config) => Some(build_codex_command(
-            config,
+            &with_prompt(config, prompt),
+            workspace_dir,
+            index,
+            otel_config,
+            drivers::codex_config_path().as_deref(),
+            false,
+        )),
+        PaneConfig::Opencode(config) => Some(build_ai_command(
+            "opencode",
+            &with_prompt(config, prompt),
             workspace_dir,
             index,
             otel_config,
+            false,
+        )),
+        PaneConfig::Antigravity(config) => Some(build_antigravity_command(
+            &with_prompt(config, prompt),
+            workspace_dir,
+            index,
         )),
-        PaneConfig::Opencode(config) => Some(build_ai_command("opencode", config, index)),
-        PaneConfig::Antigravity(config) => Some(build_antigravity_command(config, index)),
+        PaneConfig::External(config) => {
+            let prompt = prompt
+                .map(str::to_string)
+                .or_else(|| config.prompt.clone())
+                .or_else(|| index.map(|idx| idx.to_initial_prompt()));
+            let command =
+                drivers::external_driver(&config.pane_type)?.command(prompt.as_deref())?;
+            Some(
+                std::iter::once(command)
+                    .chain(config.args.iter().cloned())
+                    .collect::<Vec<_>>()
+                    .join(" "),
+            )
+        }
         PaneConfig::Custom(config) => config.command.clone(),
     }
 }
 
+/// Build the command running `prompt` once in an AI pane's agent, without
+/// its interactive UI: `claude -p`, `codex exec` or `opencode run`. The
+/// reply is printed to stdout and the command exits when the agent is done.
+pub fn one_shot_command(
+    pane: &PaneConfig,
+    workspace_dir: Option<&std::path::Path>,
+    prompt: &str,
+) -> Result<String> {
+    let prompt = Some(prompt);
+    match pane {
+        PaneConfig::Claude(config) => Ok(build_ai_command(
+            "claude",
+            &with_prompt(config, prompt),
+            workspace_dir,
+            None,
+            None,
+            true,
+        )),
+        PaneConfig::Codex(config) => Ok(build_codex_command(
+            &with_prompt(config, prompt),
+            workspace_dir,
+            None,
+            None,
+            drivers::codex_config_path().as_deref(),
+            true,
+        )),
+        PaneConfig::Opencode(config) => Ok(build_ai_command(
+            "opencode",
+            &with_prompt(config, prompt),
+            workspace_dir,
+            None,
+            None,
+            true,
+        )),
+        _ => anyhow::bail!(
+            "Pane '{}' has no one-shot mode (claude, codex and opencode panes do)",
+            pane.pane_type()
+        ),
+    }
+}
+
+/// `config` with its initial prompt replaced by `prompt`, if given
+fn with_prompt<'a>(config: &'a AiPaneConfig, prompt: Option<&str>) -> Cow<'a, AiPaneConfig> {
+    match prompt {
+        Some(prompt) => Cow::Owned(AiPaneConfig {
+            prompt: Some(prompt.to_string()),
+            ..config.clone()
+        }),
+        None => Cow::Borrowed(config),
+    }
+}
+
 /// Create a tmux workspace from a configuration.
 ///
 /// This is the main entry point for workspace creation. It:
@@ -277,40 +489,478 @@ pub fn build_pane_command(
 /// 3. **Creates the tmux session** with the first pane
 /// 4. **Configures session options** (mouse, clipboard, styling)
 /// 5. **Builds the grid layout** via horizontal/vertical splits
-/// 6. **Sends commands** to each pane to launch the shells
+/// 6. **Sends commands** to each pane once its shell is up
 ///
 /// The layout algorithm groups panes by column, creates columns via horizontal
-/// splits, then creates rows within each column via vertical splits. Width/height
-/// percentages are applied during the split operations.
+/// splits, then creates rows within each column via vertical splits. Once a
+/// window's panes exist, `select-layout` sizes them: with a layout string
+/// computed from the cells' width/height percentages, or with the grid's named
+/// `layout`.
 ///
 /// The optional `otel_config` parameter enables OTEL telemetry for non-Claude
 /// AI panes (Codex, OpenCode) when launched from the macOS app.
+///
+/// Each phase is charged to `timings` (reported by `--profile-timing`).
 pub fn create_workspace(
     session_name: &str,
     config: &WorkspaceConfig,
     profile: Option<&str>,
     otel_config: Option<OtelConfig>,
+    timings: &mut Timings,
+) -> Result<()> {
+    block_on(create_workspace_async(
+        session_name,
+        config,
+        profile,
+        otel_config,
+        timings,
+    ))?
+}
+
+/// [`create_workspace`] for callers already running a tokio runtime.
+///
+/// Splits run in order, since each one's size depends on the panes before
+/// it. Session options, typed commands and pane titles don't depend on each
+/// other and are issued concurrently.
+pub async fn create_workspace_async(
+    session_name: &str,
+    config: &WorkspaceConfig,
+    profile: Option<&str>,
+    otel_config: Option<OtelConfig>,
+    timings: &mut Timings,
 ) -> Result<()> {
     let mut panes = config.resolve_panes(profile);
     let workspace_dir = config.workspace_dir();
-    let index = config.load_index();
+    let index = config.load_index()?;
+    timings.lap("index load");
 
     if panes.is_empty() {
         anyhow::bail!("No panes defined");
     }
 
+    if let Some(ref workspace_dir) = workspace_dir {
+        install_workspace_skills(config, &panes, workspace_dir, timings);
+    }
+
+    // Sort panes by col, then row, and split them into windows
+    panes.sort_by(|a, b| a.col.cmp(&b.col).then(a.row.cmp(&b.row)));
+    // Arrange each window before anything is created, so a grid that can't
+    // be laid out fails without leaving a half-built session
+    let windows = group_by_window(&panes)
+        .into_iter()
+        .map(|(name, window_panes)| Ok((name, layout_tree(&window_panes)?)))
+        .collect::<Result<Vec<_>>>()?;
+
+    // A named layout replaces manual sizing once all cells exist
+    let layout = config.grid_layout(profile);
+
+    // Create session with the first pane of the first window
+    let (first_window, first_tree) = &windows[0];
+    let first_pane = first_tree.first();
+    let first_path = first_pane
+        .path()
+        .map(expand_path)
+        .unwrap_or_else(|| ".".to_string());
+    let first_command = build_pane_command(
+        first_pane,
+        workspace_dir.as_deref(),
+        index.as_ref(),
+        otel_config.as_ref(),
+    );
+    // The first pane never has a wrapper script; `wrapper: false` only
+    // changes whether its command is typed in or run as the pane's process
+    let first_direct = if config.uses_wrapper(first_pane) {
+        None
+    } else {
+        direct_command(first_pane, first_command.clone())
+    };
+
+    let mut new_session = NewSession::new()
+        .name(session_name)
+        .detached()
+        .start_directory(&first_path);
+    if let Some(cmd) = &first_direct {
+        new_session = new_session.shell_command(cmd);
+    }
+    new_session.run_async().await?;
+    timings.lap("session create");
+
+    // From here until setup finishes, Ctrl-C removes the half-built session
+    let _setup_guard = SetupGuard::arm(session_name);
+
+    // Store the manifest path (for cleanup on kill) and OTEL config (port
+    // and pane_id, for recovery) in the session environment
+    let mut env = Vec::new();
+    if let Some(path) = config.manifest_path.as_ref().and_then(|p| p.to_str()) {
+        env.push((AXEL_MANIFEST_ENV, path.to_string()));
+    }
+    if let Some(ref otel) = otel_config {
+        env.push((AXEL_PORT_ENV, otel.port.to_string()));
+        env.push((AXEL_PANE_ID_ENV, otel.pane_id.clone()));
+    }
+
+    let first_pane_target = format!("{}:0.0", session_name);
+    let (_, options, rename, first_id) = tokio::join!(
+        join_all(
+            env.iter()
+                .map(|(key, value)| set_environment_async(session_name, key, value))
+        ),
+        apply_session_options_async(session_name, &config.tmux),
+        rename_window_async(session_name, first_window.unwrap_or(&config.workspace)),
+        get_pane_id_async(&first_pane_target),
+    );
+    options?;
+    rename?;
+    let first_id = first_id?;
+    timings.lap("session options");
+
+    let mut builder = WindowBuilder {
+        config,
+        workspace_dir: workspace_dir.as_deref(),
+        index: index.as_ref(),
+        otel_config: otel_config.as_ref(),
+        layout,
+        pane_counter: 1,
+        all_panes: Vec::new(),
+    };
+
+    // The first pane has no wrapper script, so its startup command is typed
+    // in along with its command
+    let typed = if first_direct.is_none() {
+        first_pane
+            .startup_command()
+            .map(str::to_string)
+            .into_iter()
+            .chain(first_command)
+            .collect()
+    } else {
+        Vec::new()
+    };
+    builder.all_panes.push(StartedPane {
+        id: first_id.clone(),
+        pane: first_pane.clone(),
+        at_shell: first_direct.is_none(),
+        typed,
+    });
+    builder.build_window(&first_id, first_tree, timings).await?;
+
+    // Further windows are added in the background, each from its first pane
+    for (name, tree) in &windows[1..] {
+        let name = name.unwrap_or(&config.workspace);
+        let window_id = builder
+            .new_window(session_name, name, tree.first(), timings)
+            .await?;
+        builder.build_window(&window_id, tree, timings).await?;
+    }
+
+    if let Some(ref workspace_dir) = workspace_dir {
+        builder
+            .record_transcripts(session_name, workspace_dir)
+            .await?;
+    }
+    builder.finish().await?;
+    set_environment_async(
+        session_name,
+        AXEL_PANES_ENV,
+        &format_pane_map(
+            builder
+                .all_panes
+                .iter()
+                .map(|p| (p.pane.name.as_str(), p.id.as_str())),
+        ),
+    )
+    .await?;
+
+    // Select the grid's focus cell (the first pane by default), bringing
+    // its window to the front
+    let (focus, zoomed) = config.grid_focus(profile);
+    let focus_id = focus
+        .and_then(|name| builder.all_panes.iter().find(|p| p.pane.name == name))
+        .map_or(first_id.as_str(), |p| p.id.as_str());
+    select_window_async(focus_id).await?;
+    SelectPane::new().target(focus_id).run_async().await?;
+    if zoomed {
+        zoom_pane_async(focus_id).await?;
+    }
+    timings.lap("configure");
+
+    Ok(())
+}
+
+/// Apply axel's tmux options and key bindings to a session.
+///
+/// Enables the mouse and clipboard, shows pane titles in the borders, styles
+/// the status line, sets up mouse wheel scrolling, binds `prefix B` to
+/// broadcast a prompt to the session's AI panes and `prefix Tab` and
+/// `prefix BTab` to cycle focus between them. The manifest's `tmux:` section
+/// replaces the prefix, accent color and status line, and adds bindings.
+/// With `tmux.isolation: session`, nothing outside the session is changed.
+pub(super) fn apply_session_options(session_name: &str, tmux: &TmuxConfig) -> Result<()> {
+    block_on(apply_session_options_async(session_name, tmux))?
+}
+
+/// Async [`apply_session_options`], setting every option concurrently
+async fn apply_session_options_async(session_name: &str, tmux: &TmuxConfig) -> Result<()> {
+    let accent = tmux.accent.as_deref().unwrap_or(AXEL_COLOR);
+    let active_border_style = format!("fg={}", accent);
+    let status_style = format!("bg={},fg=#000000", accent);
+    let status_right = tmux
+        .status_right
+        .clone()
+        .unwrap_or_else(|| format!(" axel v{} ", env!("CARGO_PKG_VERSION")));
+
+    let options = try_join_all(
+        [
+            SetOption::new()
+                .target(session_name)
+                .option(OPT_MOUSE)
+                .value(VAL_ON),
+            SetOption::new()
+                .target(session_name)
+                .option(OPT_SET_CLIPBOARD)
+                .value(VAL_ON),
+            SetOption::new()
+                .target(session_name)
+                .option(OPT_PANE_BORDER_STATUS)
+                .value(VAL_TOP),
+            SetOption::new()
+                .target(session_name)
+                .option(OPT_PANE_BORDER_FORMAT)
+                .value(PANE_BORDER_FORMAT),
+            SetOption::new()
+                .target(session_name)
+                .option(OPT_PANE_ACTIVE_BORDER_STYLE)
+                .value(&active_border_style),
+            SetOption::new()
+                .target(session_name)
+                .option(OPT_STATUS_STYLE)
+                .value(&status_style),
+            SetOption::new()
+                .window()
+                .target(session_name)
+                .option(OPT_ALLOW_RENAME)
+                .value(VAL_OFF),
+            SetOption::new()
+                .target(session_name)
+                .option(OPT_STATUS_RIGHT)
+                .value(&status_right),
+        ]
+        .map(SetOption::run_async),
+    );
+
+    // Not supported by every tmux version
+    let optional_option = SetOption::new()
+        .target(session_name)
+        .option(OPT_EXTENDED_KEYS)
+        .value(VAL_ON)
+        .run_async();
+
+    let global = async {
+        match tmux.isolation {
+            TmuxIsolation::Session => Ok(()),
+            TmuxIsolation::Global | TmuxIsolation::Socket => apply_global_options_async().await,
+        }
+    };
+
+    let (options, _, global) = tokio::join!(options, optional_option, global);
+    options?;
+    global?;
+
+    // The manifest's prefix and bindings go last, so they win over axel's
+    if let Some(ref prefix) = tmux.prefix {
+        SetOption::new()
+            .target(session_name)
+            .option(OPT_PREFIX)
+            .value(prefix)
+            .run_async()
+            .await
+            .with_context(|| format!("Invalid tmux.prefix '{}'", prefix))?;
+    }
+    try_join_all(tmux.bindings.iter().map(|binding| async move {
+        bind_key_async(&binding.table, &binding.key, &[binding.command.as_str()])
+            .await
+            .with_context(|| format!("Failed to bind tmux key '{}'", binding.key))
+    }))
+    .await?;
+    Ok(())
+}
+
+/// The server-wide half of [`apply_session_options`]: the mouse and
+/// passthrough defaults, and axel's key bindings, which every session of
+/// the server shares
+async fn apply_global_options_async() -> Result<()> {
+    let options = try_join_all(
+        [
+            SetOption::new().server().option(OPT_MOUSE).value(VAL_ON),
+            SetOption::new().global().option(OPT_MOUSE).value(VAL_ON),
+        ]
+        .map(SetOption::run_async),
+    );
+
+    // Not supported by every tmux version
+    let optional_option = SetOption::new()
+        .global()
+        .option(OPT_ALLOW_PASSTHROUGH)
+        .value(VAL_ON)
+        .run_async();
+
+    // Fix mouse behavior after copy
+    let copy_binding = bind_key_async(
+        KEY_TABLE_COPY_MODE,
+        KEY_MOUSE_DRAG_END,
+        &["send-keys", "-X", "copy-pipe-and-cancel"],
+    );
+
+    // Slow down mouse wheel scroll in copy-mode, and enable mouse wheel
+    // scrolling in root mode:
+    // - If in alternate screen (vim, less, etc.), send mouse events to the app
+    // - Otherwise, enter copy-mode and scroll the scrollback buffer
+    let root_wheel = [
+        "if-shell",
+        "-F",
+        "#{alternate_on}",
+        "send-keys -M",
+        "copy-mode -e; send-keys -M",
+    ];
+    let wheel_bindings = join_all([
+        bind_key_async(
+            KEY_TABLE_COPY_MODE,
+            KEY_WHEEL_UP,
+            &["send-keys", "-X", "scroll-up"],
+        ),
+        bind_key_async(
+            KEY_TABLE_COPY_MODE,
+            KEY_WHEEL_DOWN,
+            &["send-keys", "-X", "scroll-down"],
+        ),
+        bind_key_async(KEY_TABLE_ROOT, KEY_WHEEL_UP, &root_wheel),
+        bind_key_async(KEY_TABLE_ROOT, KEY_WHEEL_DOWN, &root_wheel),
+    ]);
+
+    // Without a path to axel there is nothing to run the broadcast or
+    // focus commands with
+    let axel_bindings = async {
+        if let Ok(exe) = std::env::current_exe() {
+            let axel = exe.to_string_lossy();
+            let template = broadcast_prompt_command(&axel);
+            let broadcast = ["command-prompt", "-p", "Broadcast:", template.as_str()];
+            let next = focus_command(&axel, false);
+            let prev = focus_command(&axel, true);
+            join_all([
+                bind_key_async(KEY_TABLE_PREFIX, KEY_BROADCAST, &broadcast),
+                bind_key_async(
+                    KEY_TABLE_PREFIX,
+                    KEY_FOCUS_NEXT,
+                    &["run-shell", "-b", &next],
+                ),
+                bind_key_async(
+                    KEY_TABLE_PREFIX,
+                    KEY_FOCUS_PREV,
+                    &["run-shell", "-b", &prev],
+                ),
+            ])
+            .await;
+        }
+    };
+
+    let (options, _, copy_binding, _, _) = tokio::join!(
+        options,
+        optional_option,
+        copy_binding,
+        wheel_bindings,
+        axel_bindings
+    );
+    options?;
+    copy_binding?;
+    Ok(())
+}
+
+/// The `command-prompt` template that broadcasts the entered text with `axel`.
+///
+/// The text goes through a tmux buffer and reaches `axel broadcast` on
+/// stdin, so quotes and `$` in it never meet a shell.
+fn broadcast_prompt_command(axel: &str) -> String {
+    let shell_command = format!(
+        "tmux show-buffer -b {buffer} | {axel} broadcast --session #{{q:session_name}} - >/dev/null 2>&1",
+        buffer = BROADCAST_BUFFER,
+        axel = shell::quote(axel),
+    );
+    format!(
+        "set-buffer -b {} -- \"%%%\" ; run-shell -b {}",
+        BROADCAST_BUFFER,
+        shell::single_quote(&shell_command)
+    )
+}
+
+/// The `run-shell` command moving focus to the next (or with `prev`, the
+/// previous) AI pane with `axel`
+fn focus_command(axel: &str, prev: bool) -> String {
+    format!(
+        "{} session focus --session #{{q:session_name}}{} >/dev/null 2>&1",
+        shell::quote(axel),
+        if prev { " --prev" } else { "" }
+    )
+}
+
+/// The `pipe-pane` command recording a pane's output to the transcript at
+/// `path` with `axel`
+pub fn transcript_command(axel: &str, path: &Path, session_name: &str) -> String {
+    format!(
+        "exec {} transcript --record {} --session {}",
+        shell::quote(axel),
+        shell::quote(&path.to_string_lossy()),
+        shell::quote(session_name)
+    )
+}
+
+/// Print the outcome of installing a driver's skills.
+///
+/// A failed install is reported without stopping the launch: the pane
+/// starts with the skills it had before.
+pub fn report_skill_install(driver: &str, installed: Result<usize>) {
+    match installed {
+        Ok(0) => {}
+        Ok(count) => {
+            let skills_word = if count == 1 { "skill" } else { "skills" };
+            eprintln!(
+                "{} {} {} {} for {}",
+                "✔".green(),
+                "Installed".dimmed(),
+                count,
+                skills_word,
+                driver
+            );
+        }
+        Err(e) => eprintln!("{} {}", "✘".red(), e),
+    }
+}
+
+/// Install skills and index files for every driver with panes in the grid.
+///
+/// Shared by all backends, which call it before creating their session.
+pub fn install_workspace_skills(
+    config: &WorkspaceConfig,
+    panes: &[ResolvedPane],
+    workspace_dir: &Path,
+    timings: &mut Timings,
+) {
     // Collect skill names per driver type from AI panes
     let mut claude_skills: Vec<String> = Vec::new();
     let mut codex_skills: Vec<String> = Vec::new();
     let mut opencode_skills: Vec<String> = Vec::new();
     let mut antigravity_skills: Vec<String> = Vec::new();
+    let mut external_skills: BTreeMap<&str, Vec<String>> = BTreeMap::new();
 
-    for pane in &panes {
+    for pane in panes {
         match &pane.config {
             PaneConfig::Claude(c) => claude_skills.extend(c.skills.iter().cloned()),
             PaneConfig::Codex(c) => codex_skills.extend(c.skills.iter().cloned()),
             PaneConfig::Opencode(c) => opencode_skills.extend(c.skills.iter().cloned()),
             PaneConfig::Antigravity(c) => antigravity_skills.extend(c.skills.iter().cloned()),
+            PaneConfig::External(c) => external_skills
+                .entry(c.pane_type.as_str())
+                .or_default()
+                .extend(c.skills.iter().cloned()),
             PaneConfig::Custom(_) => {}
         }
     }
@@ -318,457 +968,1810 @@ pub fn create_workspace(
     codex_skills.dedup();
     opencode_skills.dedup();
     antigravity_skills.dedup();
+    external_skills.values_mut().for_each(Vec::dedup);
+
+    // Install skills for each driver that has panes, external ones by type
+    for (driver_name, skill_names) in [
+        ("claude", &claude_skills),
+        ("codex", &codex_skills),
+        ("opencode", &opencode_skills),
+        ("antigravity", &antigravity_skills),
+    ]
+    .into_iter()
+    .chain(external_skills.iter().map(|(name, skills)| (*name, skills)))
+    {
+        if skill_names.is_empty() {
+            continue;
+        }
+        let Some(driver) = drivers::get_driver(driver_name) else {
+            continue;
+        };
+        let skill_paths = render_skills(
+            config,
+            config.resolve_skills(skill_names),
+            workspace_dir,
+            driver.name(),
+        );
+        timings.lap("skill resolve");
 
-    // Install skills for each driver that has panes
-    if let Some(ref workspace_dir) = workspace_dir {
-        for (driver_name, skill_names) in [
-            ("claude", &claude_skills),
-            ("codex", &codex_skills),
-            ("opencode", &opencode_skills),
-            ("antigravity", &antigravity_skills),
-        ] {
-            if skill_names.is_empty() {
-                continue;
+        let installed = driver.install_skills(workspace_dir, &skill_paths);
+        timings.lap("skill install");
+        report_skill_install(driver.name(), installed);
+    }
+
+    for pane in panes {
+        install_pane_mcp(&pane.config, workspace_dir);
+    }
+    timings.lap("mcp install");
+
+    // Install index files (CLAUDE.md, AGENTS.md, etc.) for each driver type with panes
+    let driver_names: Vec<&str> = panes
+        .iter()
+        .filter_map(|p| match &p.config {
+            PaneConfig::Claude(_) => Some("claude"),
+            PaneConfig::Codex(_) => Some("codex"),
+            PaneConfig::Opencode(_) => Some("opencode"),
+            PaneConfig::Antigravity(_) => Some("antigravity"),
+            PaneConfig::External(c) => {
+                drivers::external_driver(&c.pane_type).map(drivers::SkillDriver::name)
             }
-            let Some(driver) = drivers::get_driver(driver_name) else {
-                continue;
-            };
-            let skill_paths = config.resolve_skills(skill_names);
-
-            if let Some(count) = driver
-                .install_skills(workspace_dir, &skill_paths)
-                .ok()
-                .filter(|&c| c > 0)
-            {
-                let skills_word = if count == 1 { "skill" } else { "skills" };
-                eprintln!(
-                    "{} {} {} {} for {}",
+            PaneConfig::Custom(_) => None,
+        })
+        .collect::<std::collections::HashSet<_>>()
+        .into_iter()
+        .collect();
+
+    for driver_name in driver_names {
+        if let Some(driver) = drivers::get_driver(driver_name)
+            && let Some(filename) = driver.index_filename()
+            && driver.install_index(config, workspace_dir).unwrap_or(false)
+        {
+            eprintln!(
+                "{} {} index in {}",
+                "✔".green(),
+                "Installed".dimmed(),
+                filename
+            );
+        }
+    }
+    timings.lap("skill install");
+
+    if let Err(e) = check_skills_lock(config, workspace_dir) {
+        eprintln!("{} {}: {}", "!".yellow(), SKILLS_LOCK_FILE, e);
+    }
+    timings.lap("skill lock");
+}
+
+/// Write the MCP config of an AI pane that lists `mcp:` servers, or the
+/// generated config (MCP servers and permissions) of an OpenCode pane
+pub fn install_pane_mcp(pane: &PaneConfig, workspace_dir: &Path) {
+    let (driver_name, config) = match pane {
+        PaneConfig::Claude(c) => ("claude", c),
+        PaneConfig::Codex(c) => ("codex", c),
+        PaneConfig::Opencode(c) => {
+            let pane_name = c.name.as_deref().unwrap_or("opencode");
+            match drivers::install_opencode_config(workspace_dir, pane_name, c) {
+                Ok(true) => eprintln!(
+                    "{} {} OpenCode config for {}",
                     "✔".green(),
-                    "Installed".dimmed(),
-                    count,
-                    skills_word,
-                    driver.name()
-                );
+                    "Generated".dimmed(),
+                    pane_name
+                ),
+                Ok(false) => {}
+                Err(e) => eprintln!("{} Failed to write OpenCode config: {}", "!".yellow(), e),
             }
+            return;
+        }
+        PaneConfig::Antigravity(c) => ("antigravity", c),
+        PaneConfig::External(c) => (c.pane_type.as_str(), c),
+        PaneConfig::Custom(_) => return,
+    };
+    let Some(driver) = drivers::get_driver(driver_name) else {
+        return;
+    };
+    let pane_name = config.name.as_deref().unwrap_or(driver_name);
+    match drivers::install_mcp_config(driver.as_ref(), workspace_dir, pane_name, &config.mcp) {
+        Ok(true) => {
+            let count = config.mcp.len();
+            eprintln!(
+                "{} {} {} MCP {} for {}",
+                "✔".green(),
+                "Configured".dimmed(),
+                count,
+                if count == 1 { "server" } else { "servers" },
+                pane_name
+            );
         }
+        Ok(false) if !config.mcp.is_empty() => {
+            eprintln!(
+                "{} {} does not support mcp:, ignoring it for {}",
+                "!".yellow(),
+                driver_name,
+                pane_name
+            );
+        }
+        Ok(false) => {}
+        Err(e) => eprintln!("{} Failed to write MCP config: {}", "!".yellow(), e),
+    }
+}
 
-        // Install index files (CLAUDE.md, AGENTS.md, etc.) for each driver type with panes
-        let driver_names: Vec<&str> = panes
-            .iter()
-            .filter_map(|p| match &p.config {
-                PaneConfig::Claude(_) => Some("claude"),
-                PaneConfig::Codex(_) => Some("codex"),
-                PaneConfig::Opencode(_) => Some("opencode"),
-                PaneConfig::Antigravity(_) => Some("antigravity"),
-                PaneConfig::Custom(_) => None,
-            })
-            .collect::<std::collections::HashSet<_>>()
-            .into_iter()
-            .collect();
+/// Render templated skills and apply driver overrides for installation,
+/// warning about placeholders without a value. Falls back to the skills as
+/// written if rendering fails.
+pub fn render_skills(
+    config: &WorkspaceConfig,
+    skill_paths: Vec<PathBuf>,
+    workspace_dir: &Path,
+    driver: &str,
+) -> Vec<PathBuf> {
+    match config.render_skills(&skill_paths, workspace_dir, driver) {
+        Ok(rendered) => {
+            for (path, names) in &rendered.undefined {
+                eprintln!(
+                    "{} {} uses undefined {} (set them in skills_vars: or with --skill-var)",
+                    "!".yellow(),
+                    path.display(),
+                    names
+                        .iter()
+                        .map(|n| format!("{{{{{}}}}}", n))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                );
+            }
+            rendered.paths
+        }
+        Err(e) => {
+            eprintln!("{} Failed to render skills: {}", "!".yellow(), e);
+            skill_paths
+        }
+    }
+}
 
-        for driver_name in driver_names {
-            if let Some(driver) = drivers::get_driver(driver_name)
-                && let Some(filename) = driver.index_filename()
-                && driver.install_index(config, workspace_dir).unwrap_or(false)
-            {
+/// Write `skills.lock` if the workspace has none, or warn when the skills
+/// on disk no longer match it
+fn check_skills_lock(config: &WorkspaceConfig, workspace_dir: &Path) -> Result<()> {
+    let current = config.current_skills_lock()?;
+    match SkillsLock::load(workspace_dir)? {
+        None if current.skills.is_empty() => {}
+        None => {
+            current.save(workspace_dir)?;
+            eprintln!(
+                "{} {} {}",
+                "✔".green(),
+                "Created".dimmed(),
+                SKILLS_LOCK_FILE
+            );
+        }
+        Some(locked) => {
+            let drift = locked.drift(&current);
+            if !drift.is_empty() {
+                let names: Vec<&str> = drift.iter().map(|(name, _)| name.as_str()).collect();
                 eprintln!(
-                    "{} {} {} symlink",
-                    "✔".green(),
-                    "Created".dimmed(),
-                    filename
+                    "{} {} {} {}",
+                    "⚠".yellow(),
+                    "Skills differ from skills.lock:".dimmed(),
+                    names.join(", "),
+                    "(see `axel skill status`)".dimmed()
                 );
             }
         }
     }
+    Ok(())
+}
 
-    // Sort panes by col, then row
-    panes.sort_by(|a, b| a.col.cmp(&b.col).then(a.row.cmp(&b.row)));
+/// Lays out each window's grid, tracking every pane it creates
+struct WindowBuilder<'a> {
+    config: &'a WorkspaceConfig,
+    workspace_dir: Option<&'a Path>,
+    index: Option<&'a WorkspaceIndex>,
+    otel_config: Option<&'a OtelConfig>,
+    layout: Option<TmuxLayout>,
+    /// ID for the next wrapper script
+    pane_counter: usize,
+    /// Panes created so far, started once every window exists
+    all_panes: Vec<StartedPane>,
+}
 
-    // Group panes by column
-    let mut columns: HashMap<u32, Vec<&ResolvedPane>> = HashMap::new();
-    let mut col_widths: HashMap<u32, u32> = HashMap::new();
-    let mut max_col = 0;
+/// A pane created by [`WindowBuilder`], waiting for [`WindowBuilder::finish`]
+struct StartedPane {
+    id: String,
+    pane: ResolvedPane,
+    /// The pane starts at a shell (possibly through its wrapper script)
+    /// rather than running its command directly
+    at_shell: bool,
+    /// Lines typed into the shell once it is up
+    typed: Vec<String>,
+}
 
-    for pane in &panes {
-        columns.entry(pane.col).or_default().push(pane);
-        if let Some(width) = pane.width {
-            col_widths.insert(pane.col, width);
+impl StartedPane {
+    /// Type the pane's lines once its shell shows a prompt, then set its
+    /// title and color
+    async fn finish(&self) -> Result<()> {
+        if self.at_shell {
+            wait_for_pane_ready_async(&self.id, PANE_READY_TIMEOUT).await;
         }
-        if pane.col > max_col {
-            max_col = pane.col;
+        for line in &self.typed {
+            send_text_async(&self.id, line).await?;
         }
+        configure_pane_async(&self.id, &self.pane).await
     }
+}
 
-    // Create session with first pane
-    let first_pane = &panes[0];
-    let first_path = first_pane
-        .path()
-        .map(expand_path)
-        .unwrap_or_else(|| ".".to_string());
+impl WindowBuilder<'_> {
+    /// Decide how the next split or window starts
+    fn start(&mut self, pane: &ResolvedPane) -> Result<PaneStart> {
+        let command = build_pane_command(pane, self.workspace_dir, self.index, self.otel_config);
+        let start = pane_start(
+            self.pane_counter,
+            pane,
+            command,
+            self.config.uses_wrapper(pane),
+        )?;
+        self.pane_counter += 1;
+        Ok(start)
+    }
 
-    NewSession::new()
-        .name(session_name)
-        .detached()
-        .start_directory(&first_path)
-        .run()?;
+    /// Record a created pane; its command is typed by [`Self::finish`]
+    fn started(&mut self, pane_id: String, pane: &ResolvedPane, start: &PaneStart) {
+        self.all_panes.push(StartedPane {
+            id: pane_id,
+            pane: pane.clone(),
+            at_shell: start.wrapper().is_some(),
+            typed: start
+                .typed_command()
+                .map(str::to_string)
+                .into_iter()
+                .collect(),
+        });
+    }
 
-    // Store manifest path in session environment for cleanup on kill
-    if let Some(manifest_path) = &config.manifest_path
-        && let Some(path_str) = manifest_path.to_str()
-    {
-        set_environment(session_name, AXEL_MANIFEST_ENV, path_str).ok();
+    /// Record each AI pane's output to its transcript, archiving the one
+    /// from the previous session
+    async fn record_transcripts(&self, session_name: &str, workspace_dir: &Path) -> Result<()> {
+        let Ok(axel) = std::env::current_exe() else {
+            return Ok(());
+        };
+        let commands: Vec<(&str, String)> = self
+            .all_panes
+            .iter()
+            .filter(|started| started.pane.config.is_ai())
+            .map(|started| {
+                let name = &started.pane.name;
+                rotate_transcript(workspace_dir, name).ok();
+                let path = transcript_path(workspace_dir, name);
+                (
+                    started.id.as_str(),
+                    transcript_command(&axel.to_string_lossy(), &path, session_name),
+                )
+            })
+            .collect();
+
+        try_join_all(
+            commands
+                .iter()
+                .map(|(id, command)| pipe_pane_async(id, command)),
+        )
+        .await?;
+        Ok(())
     }
 
-    // Store OTEL config (port and pane_id) in session environment for recovery
-    if let Some(ref otel) = otel_config {
-        set_environment(session_name, AXEL_PORT_ENV, &otel.port.to_string()).ok();
-        set_environment(session_name, AXEL_PANE_ID_ENV, &otel.pane_id).ok();
+    /// Start every created pane, concurrently, so the slowest shell sets
+    /// the pace rather than a fixed delay per pane
+    async fn finish(&self) -> Result<()> {
+        try_join_all(self.all_panes.iter().map(StartedPane::finish)).await?;
+        Ok(())
     }
 
-    // Configure session options
-    SetOption::new()
-        .server()
-        .option(OPT_MOUSE)
-        .value(VAL_ON)
-        .run()?;
+    /// Add a named window holding `pane`, returning the pane's ID
+    async fn new_window(
+        &mut self,
+        session_name: &str,
+        name: &str,
+        pane: &ResolvedPane,
+        timings: &mut Timings,
+    ) -> Result<String> {
+        let path = pane
+            .path()
+            .map(expand_path)
+            .unwrap_or_else(|| ".".to_string());
+        let start = self.start(pane)?;
+
+        let mut new_window = NewWindow::new()
+            .target(session_name)
+            .name(name)
+            .start_directory(&path);
+        if let Some(cmd) = start.split_command() {
+            new_window = new_window.command(cmd);
+        }
+        let pane_id = new_window.run_async().await?;
+
+        SetOption::new()
+            .window()
+            .target(&pane_id)
+            .option(OPT_ALLOW_RENAME)
+            .value(VAL_OFF)
+            .run_async()
+            .await?;
+
+        self.started(pane_id.clone(), pane, &start);
+        timings.lap(&format!("split {}", pane.name));
+        Ok(pane_id)
+    }
 
-    SetOption::new()
-        .global()
-        .option(OPT_MOUSE)
-        .value(VAL_ON)
-        .run()?;
+    /// Split a window into its grid, starting from its first pane `first_id`.
+    ///
+    /// The first pane of `tree` is already running in `first_id`.
+    async fn build_window(
+        &mut self,
+        first_id: &str,
+        tree: &LayoutNode<'_>,
+        timings: &mut Timings,
+    ) -> Result<()> {
+        let mut ids: HashMap<&str, String> =
+            HashMap::from([(tree.first().name.as_str(), first_id.to_string())]);
+
+        for split in tree.splits() {
+            let pane = split.pane;
+            let path = pane
+                .path()
+                .map(expand_path)
+                .unwrap_or_else(|| ".".to_string());
+            let start = self.start(pane)?;
+
+            let mut command = SplitWindow::new()
+                .target(&ids[split.target.name.as_str()])
+                .percentage(split.percentage)
+                .start_directory(&path);
+            command = if split.horizontal {
+                command.horizontal()
+            } else {
+                command.vertical()
+            };
+            if let Some(cmd) = start.split_command() {
+                command = command.command(cmd);
+            }
+            let new_id = command.run_async().await?;
 
-    SetOption::new()
-        .target(session_name)
-        .option(OPT_MOUSE)
-        .value(VAL_ON)
-        .run()?;
+            self.started(new_id.clone(), pane, &start);
+            timings.lap(&format!("split {}", pane.name));
+            ids.insert(pane.name.as_str(), new_id);
+        }
 
-    SetOption::new()
-        .target(session_name)
-        .option(OPT_SET_CLIPBOARD)
-        .value(VAL_ON)
-        .run()?;
+        // Size every pane at once, as the splits above only approximate the
+        // grid's widths and heights
+        let layout = match self.layout {
+            Some(layout) => layout.as_str().to_string(),
+            None if ids.len() > 1 => {
+                let (width, height) = window_size_async(first_id).await?;
+                tree.layout_string(width, height, &ids)
+            }
+            None => return Ok(()),
+        };
+        SelectLayout::new()
+            .target(first_id)
+            .layout(&layout)
+            .run_async()
+            .await?;
 
-    SetOption::new()
-        .global()
-        .option(OPT_ALLOW_PASSTHROUGH)
-        .value(VAL_ON)
-        .run()
-        .ok();
+        Ok(())
+    }
+}
 
-    SetOption::new()
-        .target(session_name)
-        .option(OPT_EXTENDED_KEYS)
-        .value(VAL_ON)
-        .run()
-        .ok();
+/// Column widths from the grid cells, keyed by column.
+///
+/// Empty when a named layout is set, since `select-layout` resizes every
+/// pane afterwards and explicit widths would only be overwritten.
+pub(crate) fn column_widths(
+    panes: &[&ResolvedPane],
+    layout: Option<TmuxLayout>,
+) -> HashMap<u32, u32> {
+    if layout.is_some() {
+        return HashMap::new();
+    }
+    panes
+        .iter()
+        .filter_map(|p| p.width.map(|width| (p.col, width)))
+        .collect()
+}
 
-    SetOption::new()
-        .target(session_name)
-        .option(OPT_PANE_BORDER_STATUS)
-        .value(VAL_TOP)
-        .run()?;
+/// Height percentage for the split that creates row `row_idx` of a column.
+///
+/// Uses the cell's explicit height unless a named layout is set; otherwise
+/// splits the remaining space evenly between this row and those below it.
+pub(crate) fn row_height(
+    pane: &ResolvedPane,
+    row_idx: usize,
+    num_rows: usize,
+    layout: Option<TmuxLayout>,
+) -> u32 {
+    let even = || {
+        let remaining = (num_rows - row_idx) as u32;
+        (remaining * 100) / (remaining + 1)
+    };
+    match layout {
+        Some(_) => even(),
+        None => pane.height.unwrap_or_else(even),
+    }
+}
 
-    SetOption::new()
-        .target(session_name)
-        .option(OPT_PANE_BORDER_FORMAT)
-        .value(PANE_BORDER_FORMAT)
-        .run()?;
+/// Configure a pane's title and background color.
+///
+/// Called after all panes are created to set visual properties. The title
+/// appears in the pane border, and the background color is set if configured.
+pub(super) fn configure_pane(target: &str, pane: &ResolvedPane) -> Result<()> {
+    pane_style(target, pane).run()
+}
 
-    SetOption::new()
-        .target(session_name)
-        .option(OPT_PANE_ACTIVE_BORDER_STYLE)
-        .value(&format!("fg={}", AXEL_COLOR))
-        .run()?;
+/// Async [`configure_pane`]
+async fn configure_pane_async(target: &str, pane: &ResolvedPane) -> Result<()> {
+    pane_style(target, pane).run_async().await
+}
 
-    SetOption::new()
-        .target(session_name)
-        .option(OPT_STATUS_STYLE)
-        .value(&format!("bg={},fg=#000000", AXEL_COLOR))
-        .run()?;
+/// The `select-pane` applying a pane's title and background color
+fn pane_style<'a>(target: &'a str, pane: &'a ResolvedPane) -> SelectPane<'a> {
+    let mut select = SelectPane::new().target(target).title(&pane.name);
 
-    SetOption::new()
-        .window()
-        .target(session_name)
-        .option(OPT_ALLOW_RENAME)
-        .value(VAL_OFF)
-        .run()?;
+    if let Some(color) = pane.color() {
+        let tmux_color = to_tmux_color(color);
+        if tmux_color != "default" {
+            select = select.background(tmux_color);
+        }
+    }
 
-    SetOption::new()
-        .target(session_name)
-        .option(OPT_STATUS_RIGHT)
-        .value(&format!(" axel v{} ", env!("CARGO_PKG_VERSION")))
-        .run()?;
+    select
+}
 
-    // Fix mouse behavior after copy
-    bind_key(
-        KEY_TABLE_COPY_MODE,
-        KEY_MOUSE_DRAG_END,
-        &["send-keys", "-X", "copy-pipe-and-cancel"],
-    )?;
+/// How a split pane is started
+#[derive(Debug, PartialEq, Eq)]
+enum PaneStart {
+    /// Run the wrapper script, then type `command` into the shell it execs
+    Wrapper {
+        script: String,
+        command: Option<String>,
+    },
+    /// Run the command as the pane's own process (`wrapper: false`)
+    Direct(Option<String>),
+}
 
-    // Slow down mouse wheel scroll in copy-mode
-    bind_key(
-        KEY_TABLE_COPY_MODE,
-        KEY_WHEEL_UP,
-        &["send-keys", "-X", "scroll-up"],
-    )
-    .ok();
-    bind_key(
-        KEY_TABLE_COPY_MODE,
-        KEY_WHEEL_DOWN,
-        &["send-keys", "-X", "scroll-down"],
-    )
-    .ok();
+impl PaneStart {
+    /// Wrapper script the pane runs, if any
+    fn wrapper(&self) -> Option<&str> {
+        match self {
+            PaneStart::Wrapper { script, .. } => Some(script),
+            PaneStart::Direct(_) => None,
+        }
+    }
 
-    // Enable mouse wheel scrolling in root mode
-    // - If in alternate screen (vim, less, etc.), send mouse events to the app
-    // - Otherwise, enter copy-mode and scroll the scrollback buffer
-    bind_key(
-        KEY_TABLE_ROOT,
-        KEY_WHEEL_UP,
-        &[
-            "if-shell",
-            "-F",
-            "#{alternate_on}",
-            "send-keys -M",
-            "copy-mode -e; send-keys -M",
-        ],
-    )
-    .ok();
-    bind_key(
-        KEY_TABLE_ROOT,
-        KEY_WHEEL_DOWN,
-        &[
-            "if-shell",
-            "-F",
-            "#{alternate_on}",
-            "send-keys -M",
-            "copy-mode -e; send-keys -M",
-        ],
-    )
-    .ok();
+    /// Command passed to `split-window` (a plain shell when `None`)
+    fn split_command(&self) -> Option<&str> {
+        match self {
+            PaneStart::Wrapper { script, .. } => Some(script),
+            PaneStart::Direct(command) => command.as_deref(),
+        }
+    }
 
-    rename_window(session_name, &config.workspace)?;
+    /// Command typed into the pane once its shell is up
+    fn typed_command(&self) -> Option<&str> {
+        match self {
+            PaneStart::Wrapper { command, .. } => command.as_deref(),
+            PaneStart::Direct(_) => None,
+        }
+    }
+}
 
-    // Track pane IDs per column and collect all panes for later configuration
-    let mut col_first_ids: HashMap<u32, String> = HashMap::new();
-    let mut col_last_ids: HashMap<u32, String> = HashMap::new();
-    let mut all_panes: Vec<(String, ResolvedPane)> = Vec::new();
+/// Decide how a split pane starts, writing its wrapper script if it uses one.
+fn pane_start(
+    id: usize,
+    pane: &ResolvedPane,
+    command: Option<String>,
+    use_wrapper: bool,
+) -> Result<PaneStart> {
+    if use_wrapper {
+        Ok(PaneStart::Wrapper {
+            script: create_wrapper_script(id, pane)?,
+            command,
+        })
+    } else {
+        Ok(PaneStart::Direct(direct_command(pane, command)))
+    }
+}
 
-    // Get first pane ID and send command if needed
-    let first_pane_target = format!("{}:0.0", session_name);
-    let first_id = get_pane_id(&first_pane_target)?;
+/// The command a pane runs without a wrapper script.
+///
+/// Notes and `clear` are skipped. A `startup_command` is chained in front;
+/// with no pane command it is followed by the user's shell so the pane stays.
+pub(crate) fn direct_command(pane: &ResolvedPane, command: Option<String>) -> Option<String> {
+    match (pane.startup_command(), command) {
+        (Some(startup), Some(command)) => Some(format!("{}; {}", startup, command)),
+        (Some(startup), None) => Some(format!("{}; exec \"$SHELL\"", startup)),
+        (None, command) => command,
+    }
+}
 
-    if let Some(cmd) = build_pane_command(
-        first_pane,
-        workspace_dir.as_deref(),
-        index.as_ref(),
-        otel_config.as_ref(),
-    ) {
-        std::thread::sleep(std::time::Duration::from_millis(200));
-        send_keys(&first_id, &cmd)?;
+/// Create a temporary bash wrapper script for a pane.
+///
+/// The wrapper script:
+/// 1. Clears the terminal (unless the pane sets `clear: false` or `--no-clear`)
+/// 2. Displays pane notes (if configured) or a simple title. File and
+///    command notes are evaluated here, once, in the pane's directory
+/// 3. Runs the pane's `startup_command` (if configured); a failure prints a
+///    warning but still starts the shell
+/// 4. Removes itself from disk (self-cleaning)
+/// 5. Execs into fish shell with greeting and title disabled
+///
+/// This approach allows displaying startup information before the shell
+/// takes over, while keeping the pane in a clean state. Variables exported
+/// by the startup command are inherited by the shell.
+fn create_wrapper_script(id: usize, pane: &ResolvedPane) -> Result<String> {
+    let wrapper_path = format!("/tmp/axel_ws_{}", id);
+    std::fs::write(&wrapper_path, wrapper_script(&wrapper_path, pane))?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&wrapper_path, std::fs::Permissions::from_mode(0o755))?;
     }
-    col_first_ids.insert(0, first_id.clone());
-    col_last_ids.insert(0, first_id.clone());
-    all_panes.push((first_id, first_pane.clone()));
 
-    let mut pane_counter = 1;
+    Ok(wrapper_path)
+}
 
-    // Create columns (horizontal splits)
-    for col in 1..=max_col {
-        let Some(col_panes) = columns.get(&col) else {
-            continue;
-        };
-        let first_col_pane = col_panes[0];
+/// Build the contents of a pane's wrapper script (see [`create_wrapper_script`])
+fn wrapper_script(wrapper_path: &str, pane: &ResolvedPane) -> String {
+    let mut lines = vec!["#!/bin/bash".to_string()];
+    if pane.clear() {
+        lines.push("clear".to_string());
+    }
 
-        let path = first_col_pane
-            .path()
-            .map(expand_path)
-            .unwrap_or_else(|| ".".to_string());
+    let fg_rgb = pane.color().map(to_fg_rgb).unwrap_or("255;255;255");
+    let dir = pane
+        .path()
+        .map(|p| PathBuf::from(expand_path(p)))
+        .unwrap_or_else(|| std::env::current_dir().unwrap_or_default());
+    let notes: Vec<String> = pane.notes().iter().flat_map(|n| n.lines(&dir)).collect();
+    lines.extend(wrapper_header_lines(&pane.name, &notes, fg_rgb));
+
+    if let Some(startup) = pane.startup_command() {
+        // Grouped rather than subshelled so `source`/`export` affect the exec'd shell
+        lines.push("{".to_string());
+        lines.push(startup.to_string());
+        lines.push(
+            "} || printf '\\e[33mstartup_command failed (exit %s)\\e[0m\\n' \"$?\"".to_string(),
+        );
+    }
 
-        let wrapper = create_wrapper_script(pane_counter, first_col_pane)?;
+    lines.push(format!("rm '{}'", wrapper_path));
+    lines.push("if command -v fish >/dev/null 2>&1; then".to_string());
+    lines.push("  exec fish -C 'set fish_greeting; function fish_title; end'".to_string());
+    lines.push("else".to_string());
+    lines.push("  exec \"$SHELL\"".to_string());
+    lines.push("fi".to_string());
 
-        let prev_col = col - 1;
-        let target_id = col_first_ids.get(&prev_col).unwrap();
+    let mut script = lines.join("\n");
+    script.push('\n');
+    script
+}
 
-        let mut split = SplitWindow::new()
-            .target(target_id)
-            .horizontal()
-            .start_directory(&path)
-            .command(&wrapper);
+/// Build the shell lines that print a pane's header in its wrapper script.
+///
+/// With notes, prints a ` notes | ...` block in the pane's foreground color;
+/// otherwise prints a simple `- name -` title.
+fn wrapper_header_lines(name: &str, notes: &[String], fg_rgb: &str) -> Vec<String> {
+    if notes.is_empty() {
+        return vec![format!(
+            "printf '%b\\n' $'\\e'\"[38;2;{}m- {} -\"$'\\e'\"[0m\"",
+            fg_rgb, name
+        )];
+    }
 
-        if let Some(width) = col_widths.get(&col) {
-            split = split.percentage(*width);
-        }
+    let mut lines = vec![
+        "COLS=$(tput cols)".to_string(),
+        format!("printf '\\e[38;2;{}m'", fg_rgb),
+    ];
+    for (i, note) in notes.iter().enumerate() {
+        let note = note.trim();
+        let prefix = if i == 0 { " notes |" } else { "       |" };
+        lines.push(format!(
+            "printf '{} {}%*s\\n' \"$((COLS - {} - 10))\" ''",
+            prefix,
+            printf_escape(note),
+            note.chars().count()
+        ));
+    }
+    lines.push("printf '\\e[0m'".to_string());
+    lines
+}
 
-        let new_id = split.run()?;
-        all_panes.push((new_id.clone(), first_col_pane.clone()));
+/// Escape text for use inside a single-quoted printf format string
+fn printf_escape(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('%', "%%")
+        .replace('\'', "'\\''")
+}
 
-        if let Some(cmd) = build_pane_command(
-            first_col_pane,
-            workspace_dir.as_deref(),
-            index.as_ref(),
-            otel_config.as_ref(),
-        ) {
-            std::thread::sleep(std::time::Duration::from_millis(200));
-            send_keys(&new_id, &cmd)?;
-        }
+// =============================================================================
+// Broadcast
+// =============================================================================
+
+/// Select the AI panes of a session to broadcast input to.
+///
+/// A pane's driver is looked up by its title (the pane name) in the session's
+/// manifest, falling back to the title itself for default-named AI panes
+/// (built-in agents and external drivers, see [`PaneConfig::of_type`]).
+/// A non-empty `only` keeps panes whose driver type or name is listed.
+pub fn broadcast_targets(
+    panes: &[PaneInfo],
+    config: Option<&WorkspaceConfig>,
+    only: &[String],
+) -> Vec<PaneInfo> {
+    panes
+        .iter()
+        .filter(|pane| {
+            let configured =
+                config.and_then(|c| c.layouts.panes.iter().find(|p| p.pane_type() == pane.title));
+            let driver = match configured {
+                Some(p) if p.is_ai() => p.actual_type(),
+                Some(_) => return false,
+                None if PaneConfig::of_type(&pane.title).is_some_and(|p| p.is_ai()) => &pane.title,
+                None => return false,
+            };
+            only.is_empty() || only.iter().any(|o| o == driver || *o == pane.title)
+        })
+        .cloned()
+        .collect()
+}
+
+/// The AI pane after `active` among `targets`, wrapping around, or the one
+/// before it with `reverse`.
+///
+/// From a pane that isn't an AI pane, this is the first (or last) AI pane.
+pub fn next_ai_pane<'a>(
+    targets: &'a [PaneInfo],
+    active: &str,
+    reverse: bool,
+) -> Option<&'a PaneInfo> {
+    let len = targets.len();
+    let next = match targets.iter().position(|pane| pane.id == active) {
+        Some(i) if reverse => (i + len - 1) % len,
+        Some(i) => (i + 1) % len,
+        None if reverse => len.checked_sub(1)?,
+        None => 0,
+    };
+    targets.get(next)
+}
+
+// =============================================================================
+// Pane Health
+// =============================================================================
+
+/// Shells an AI pane drops back to when its command exits
+const SHELL_COMMANDS: &[&str] = &["fish", "bash", "zsh"];
+
+/// Tmux pane option that keeps a pane open after its process exits
+const OPT_REMAIN_ON_EXIT: &str = "remain-on-exit";
+
+/// Restarts AI panes whose process has exited.
+///
+/// A pane counts as exited when it is dead (its command was the pane's own
+/// process) or when it is back at a shell after the watchdog has seen it
+/// running something else (its command was typed into a wrapper shell).
+/// Panes are matched to the manifest by title and respawned with their
+/// original command, run directly, and keep `remain-on-exit` so a later
+/// exit is caught too.
+#[derive(Debug, Default)]
+pub struct PaneWatchdog {
+    /// AI panes checked at least once, by pane ID
+    seen: HashSet<String>,
+    /// Panes seen running their AI command, by pane ID
+    running: HashSet<String>,
+}
 
-        col_first_ids.insert(col, new_id.clone());
-        col_last_ids.insert(col, new_id);
-        pane_counter += 1;
+impl PaneWatchdog {
+    /// Create a watchdog that has not seen any pane yet
+    pub fn new() -> Self {
+        Self::default()
     }
 
-    // Create rows within each column (vertical splits)
-    for col in 0..=max_col {
-        let Some(col_panes) = columns.get(&col) else {
-            continue;
-        };
+    /// Check a session's AI panes once, respawning any that exited.
+    ///
+    /// Returns the names of the respawned panes. When the session stores an
+    /// event server port, a `pane_respawned` event is posted for each.
+    pub fn check(&mut self, session: &str, config: &WorkspaceConfig) -> Result<Vec<String>> {
+        let otel_config = session_otel_config(session);
+        let index = config.load_index()?;
+        let workspace_dir = config.workspace_dir();
+        let mut respawned = Vec::new();
+
+        for status in list_pane_status(session)? {
+            let Some(pane) = ai_pane_named(config, &status.title) else {
+                continue;
+            };
+
+            // Keep direct panes open when they exit so they can be respawned
+            if self.seen.insert(status.id.clone()) {
+                set_remain_on_exit(&status.id);
+            }
 
-        let num_rows = col_panes.len();
+            let at_shell = SHELL_COMMANDS.contains(&status.current_command.as_str());
+            if !status.dead && !at_shell {
+                self.running.insert(status.id.clone());
+                continue;
+            }
+            if !status.dead && !self.running.contains(&status.id) {
+                // Still starting up, or never launched its command
+                continue;
+            }
 
-        for (row_idx, &pane) in col_panes.iter().enumerate().skip(1) {
+            let command = build_pane_command(
+                &pane,
+                workspace_dir.as_deref(),
+                index.as_ref(),
+                otel_config.as_ref(),
+            );
             let path = pane
                 .path()
                 .map(expand_path)
                 .unwrap_or_else(|| ".".to_string());
+            respawn_pane(&status.id, &path, direct_command(&pane, command).as_deref())?;
+            set_remain_on_exit(&status.id);
+            // The respawned process is tracked again once it shows up
+            self.running.remove(&status.id);
 
-            let wrapper = create_wrapper_script(pane_counter, pane)?;
+            if let Some(otel) = &otel_config {
+                notify_respawn(otel, session, &status.id, &pane.name);
+            }
+            respawned.push(pane.name);
+        }
 
-            let target_id = col_last_ids.get(&col).unwrap();
+        Ok(respawned)
+    }
+}
 
-            let height_pct = pane.height.unwrap_or_else(|| {
-                let remaining = num_rows - row_idx;
-                (remaining as u32 * 100) / (remaining as u32 + 1)
-            });
+/// Keep a pane (and the exit status of its process) after the process exits
+fn set_remain_on_exit(pane_id: &str) {
+    SetOption::new()
+        .pane()
+        .target(pane_id)
+        .option(OPT_REMAIN_ON_EXIT)
+        .value(VAL_ON)
+        .run()
+        .ok();
+}
 
-            let new_id = SplitWindow::new()
-                .target(target_id)
-                .vertical()
-                .percentage(height_pct)
-                .start_directory(&path)
-                .command(&wrapper)
-                .run()?;
+/// Find the AI pane named `name` in the manifest's grids ("default" first)
+fn ai_pane_named(config: &WorkspaceConfig, name: &str) -> Option<ResolvedPane> {
+    find_pane(config, |p| p.name == name && p.config.is_ai())
+}
 
-            all_panes.push((new_id.clone(), pane.clone()));
+/// Find the pane named `name` in the manifest's grids ("default" first)
+pub(super) fn pane_named(config: &WorkspaceConfig, name: &str) -> Option<ResolvedPane> {
+    find_pane(config, |p| p.name == name)
+}
 
-            if let Some(cmd) = build_pane_command(
-                pane,
-                workspace_dir.as_deref(),
-                index.as_ref(),
-                otel_config.as_ref(),
-            ) {
-                std::thread::sleep(std::time::Duration::from_millis(200));
-                send_keys(&new_id, &cmd)?;
-            }
+/// First pane matching `predicate` across the manifest's grids
+fn find_pane(
+    config: &WorkspaceConfig,
+    predicate: impl Fn(&ResolvedPane) -> bool,
+) -> Option<ResolvedPane> {
+    let mut grids: Vec<&String> = config.layouts.grids.keys().collect();
+    grids.sort_by_key(|g| (g.as_str() != "default", g.as_str()));
+    grids.into_iter().find_map(|grid| {
+        config
+            .resolve_panes(Some(grid))
+            .into_iter()
+            .find(|p| predicate(p))
+    })
+}
+
+/// Read the event server port and pane ID stored in a session's environment
+fn session_otel_config(session: &str) -> Option<OtelConfig> {
+    Some(OtelConfig {
+        port: get_environment(session, AXEL_PORT_ENV)?.parse().ok()?,
+        pane_id: get_environment(session, AXEL_PANE_ID_ENV)?,
+    })
+}
+
+/// Value of [`AXEL_PANES_ENV`] for panes given as (name, tmux pane ID),
+/// sorted by name
+fn format_pane_map<'a>(panes: impl Iterator<Item = (&'a str, &'a str)>) -> String {
+    let mut pairs: Vec<String> = panes.map(|(name, id)| format!("{}={}", name, id)).collect();
+    pairs.sort();
+    pairs.join(" ")
+}
+
+/// Parse a value of [`AXEL_PANES_ENV`] into tmux pane IDs by pane name
+pub fn parse_pane_map(value: &str) -> HashMap<String, String> {
+    value
+        .split_whitespace()
+        .filter_map(|pair| pair.rsplit_once('='))
+        .map(|(name, id)| (name.to_string(), id.to_string()))
+        .collect()
+}
+
+/// Tmux pane IDs of a session's panes by name, as recorded when the
+/// session was created or last reloaded
+pub fn session_pane_map(session: &str) -> HashMap<String, String> {
+    get_environment(session, AXEL_PANES_ENV)
+        .map(|value| parse_pane_map(&value))
+        .unwrap_or_default()
+}
+
+/// Post a `pane_respawned` event to the event server (best effort)
+fn notify_respawn(otel: &OtelConfig, session: &str, tmux_pane: &str, name: &str) {
+    let payload = serde_json::json!({
+        "type": "pane_respawned",
+        "session": session,
+        "pane": name,
+        "tmux_pane": tmux_pane,
+    });
+    std::process::Command::new("curl")
+        .args([
+            "-s",
+            "-X",
+            "POST",
+            "-H",
+            "Content-Type: application/json",
+            "-d",
+        ])
+        .arg(payload.to_string())
+        .arg(events_endpoint(otel.port, &otel.pane_id))
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .status()
+        .ok();
+}
+
+// =============================================================================
+// Reload
+// =============================================================================
+
+/// What [`reload_workspace`] changed in a running session
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ReloadSummary {
+    /// Panes created because they are new in the manifest
+    pub added: Vec<String>,
+    /// Running panes whose title and color were refreshed
+    pub updated: Vec<String>,
+    /// Running panes the manifest no longer lists (left untouched)
+    pub orphaned: Vec<String>,
+}
+
+/// Where a pane missing from the running session is created
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Placement {
+    /// In a new window with this name
+    Window(String),
+    /// Split below the named pane, which is in the same column
+    Below(String),
+    /// Split to the right of the named pane
+    Right(String),
+}
+
+/// Apply manifest changes to a running session without restarting it.
+///
+/// Panes are matched to the manifest by title. Skills and index files are
+/// re-installed, the `tmux:` options re-applied, existing panes get their
+/// title and color refreshed, and
+/// panes new to the manifest are split into their window (or a new window)
+/// next to their grid neighbours. Running panes the manifest no longer lists
+/// are reported as orphaned and left alone.
+pub fn reload_workspace(
+    session_name: &str,
+    config: &WorkspaceConfig,
+    profile: Option<&str>,
+    timings: &mut Timings,
+) -> Result<ReloadSummary> {
+    block_on(reload_workspace_async(
+        session_name,
+        config,
+        profile,
+        timings,
+    ))?
+}
+
+/// [`reload_workspace`] for callers already running a tokio runtime
+pub async fn reload_workspace_async(
+    session_name: &str,
+    config: &WorkspaceConfig,
+    profile: Option<&str>,
+    timings: &mut Timings,
+) -> Result<ReloadSummary> {
+    let running = list_pane_status(session_name)?;
+    let mut panes = config.resolve_panes(profile);
+    let workspace_dir = config.workspace_dir();
+    let index = config.load_index()?;
+    timings.lap("index load");
+
+    if panes.is_empty() {
+        anyhow::bail!("No panes defined");
+    }
 
-            col_last_ids.insert(col, new_id);
-            pane_counter += 1;
+    if let Some(ref workspace_dir) = workspace_dir {
+        install_workspace_skills(config, &panes, workspace_dir, timings);
+    }
+
+    // Pick up changes to the manifest's `tmux:` section
+    apply_session_options_async(session_name, &config.tmux).await?;
+    timings.lap("session options");
+
+    panes.sort_by(|a, b| a.col.cmp(&b.col).then(a.row.cmp(&b.row)));
+    let running_titles: Vec<(&str, &str)> = running
+        .iter()
+        .map(|s| (s.window_name.as_str(), s.title.as_str()))
+        .collect();
+    let plan = plan_reload(&panes, &running_titles, &config.workspace);
+
+    let mut summary = ReloadSummary {
+        orphaned: running
+            .iter()
+            .filter(|s| !panes.iter().any(|p| p.name == s.title))
+            .map(|s| s.title.clone())
+            .collect(),
+        ..Default::default()
+    };
+
+    // Refresh panes that are already running
+    let mut pane_ids: HashMap<String, String> = running
+        .iter()
+        .map(|s| (s.title.clone(), s.id.clone()))
+        .collect();
+    let refreshed: Vec<(&str, &ResolvedPane)> = running
+        .iter()
+        .filter_map(|status| {
+            let pane = panes.iter().find(|p| p.name == status.title)?;
+            Some((status.id.as_str(), pane))
+        })
+        .collect();
+    try_join_all(refreshed.iter().map(|&(id, pane)| async move {
+        configure_pane_async(id, pane).await?;
+        if pane.color().is_none_or(|c| to_tmux_color(c) == "default") {
+            SelectPane::new()
+                .target(id)
+                .background("default")
+                .run_async()
+                .await?;
         }
+        anyhow::Ok(())
+    }))
+    .await?;
+    summary.updated = refreshed
+        .iter()
+        .map(|(_, pane)| pane.name.clone())
+        .collect();
+    timings.lap("refresh");
+
+    let otel_config = session_otel_config(session_name);
+    let mut builder = WindowBuilder {
+        config,
+        workspace_dir: workspace_dir.as_deref(),
+        index: index.as_ref(),
+        otel_config: otel_config.as_ref(),
+        layout: None,
+        pane_counter: 1,
+        all_panes: Vec::new(),
+    };
+
+    for (pane, placement) in &plan {
+        let pane_id = match placement {
+            Placement::Window(name) => {
+                builder
+                    .new_window(session_name, name, pane, timings)
+                    .await?
+            }
+            Placement::Below(neighbour) | Placement::Right(neighbour) => {
+                let Some(target) = pane_ids.get(neighbour) else {
+                    continue;
+                };
+                let path = pane
+                    .path()
+                    .map(expand_path)
+                    .unwrap_or_else(|| ".".to_string());
+                let start = builder.start(pane)?;
+
+                let mut split = SplitWindow::new().target(target).start_directory(&path);
+                split = if matches!(placement, Placement::Below(_)) {
+                    split.vertical()
+                } else {
+                    split.horizontal()
+                };
+                if let Some(cmd) = start.split_command() {
+                    split = split.command(cmd);
+                }
+                let new_id = split.run_async().await?;
+                builder.started(new_id.clone(), pane, &start);
+                timings.lap(&format!("split {}", pane.name));
+                new_id
+            }
+        };
+        pane_ids.insert(pane.name.clone(), pane_id);
+        summary.added.push(pane.name.clone());
     }
 
-    // Wait for all shells to initialize, then configure panes
-    std::thread::sleep(std::time::Duration::from_millis(500));
-    for (pane_id, pane) in &all_panes {
-        configure_pane(pane_id, pane)?;
+    builder.finish().await?;
+    set_environment_async(
+        session_name,
+        AXEL_PANES_ENV,
+        &format_pane_map(
+            pane_ids
+                .iter()
+                .map(|(name, id)| (name.as_str(), id.as_str())),
+        ),
+    )
+    .await?;
+    timings.lap("configure");
+
+    Ok(summary)
+}
+
+/// Decide where each manifest pane missing from the session is created.
+///
+/// `panes` are sorted by (col, row) and `running` holds the (window name,
+/// title) of every running pane. A new pane goes below the last pane of its
+/// column, to the right of the nearest column on its left when its column is
+/// new, or into a new window when its window does not exist yet.
+fn plan_reload<'a>(
+    panes: &'a [ResolvedPane],
+    running: &[(&str, &str)],
+    workspace: &str,
+) -> Vec<(&'a ResolvedPane, Placement)> {
+    let window_of = |pane: &ResolvedPane| pane.window.clone().unwrap_or(workspace.to_string());
+
+    // (window, col, name) of every pane that exists or will exist
+    let mut placed: Vec<(String, u32, String)> = panes
+        .iter()
+        .filter(|p| running.iter().any(|(_, title)| *title == p.name))
+        .map(|p| (window_of(p), p.col, p.name.clone()))
+        .collect();
+
+    let mut plan = Vec::new();
+    for pane in panes {
+        if placed.iter().any(|(_, _, name)| *name == pane.name) {
+            continue;
+        }
+        let window = window_of(pane);
+        let in_window: Vec<&(String, u32, String)> =
+            placed.iter().filter(|(w, _, _)| *w == window).collect();
+
+        let placement = if let Some((_, _, name)) =
+            in_window.iter().rev().find(|(_, col, _)| *col == pane.col)
+        {
+            Placement::Below(name.clone())
+        } else if let Some(left_col) = in_window
+            .iter()
+            .map(|(_, col, _)| *col)
+            .filter(|col| *col < pane.col)
+            .max()
+            .or_else(|| in_window.first().map(|(_, col, _)| *col))
+        {
+            // Split off the top of the nearest column to the left
+            let (_, _, name) = in_window
+                .iter()
+                .find(|(_, col, _)| *col == left_col)
+                .unwrap();
+            Placement::Right(name.clone())
+        } else if let Some((_, title)) = running.iter().find(|(w, _)| *w == window) {
+            // The window holds only panes the manifest no longer lists
+            Placement::Right(title.to_string())
+        } else {
+            Placement::Window(window.clone())
+        };
+
+        plan.push((pane, placement));
+        placed.push((window, pane.col, pane.name.clone()));
     }
+    plan
+}
 
-    // Select first pane
-    SelectPane::new()
-        .target(&format!("{}:0.0", session_name))
-        .run()?;
+// =============================================================================
+// Interrupt Handling
+// =============================================================================
 
-    Ok(())
+/// Session currently being set up by [`create_workspace`], if any
+static SETUP_SESSION: Mutex<Option<String>> = Mutex::new(None);
+
+/// Whether setup installs the Ctrl-C handler, see [`exit_on_interrupt`]
+static EXIT_ON_INTERRUPT: AtomicBool = AtomicBool::new(false);
+
+/// Make Ctrl-C during [`create_workspace`] remove the half-created session
+/// and exit with status 130.
+///
+/// Off by default so applications embedding axel keep their own signal
+/// handling; they can call [`cleanup_interrupted_setup`] from it instead.
+pub fn exit_on_interrupt() {
+    EXIT_ON_INTERRUPT.store(true, Ordering::Relaxed);
 }
 
-/// Configure a pane's title and background color.
+/// Marks a session as mid-setup for the lifetime of the guard.
 ///
-/// Called after all panes are created to set visual properties. The title
-/// appears in the pane border, and the background color is set if configured.
-fn configure_pane(target: &str, pane: &ResolvedPane) -> Result<()> {
-    let mut select = SelectPane::new().target(target).title(&pane.name);
+/// With [`exit_on_interrupt`], Ctrl-C while armed kills the session (see
+/// [`cleanup_interrupted_setup`]) instead of leaving a detached,
+/// half-configured session behind. Dropping the guard disarms it.
+struct SetupGuard;
+
+impl SetupGuard {
+    fn arm(session_name: &str) -> Self {
+        static HANDLER: Once = Once::new();
+        if EXIT_ON_INTERRUPT.load(Ordering::Relaxed) {
+            HANDLER.call_once(install_interrupt_handler);
+        }
 
-    if let Some(color) = pane.color() {
-        let tmux_color = to_tmux_color(color);
-        if tmux_color != "default" {
-            select = select.background(tmux_color);
+        if let Ok(mut current) = SETUP_SESSION.lock() {
+            *current = Some(session_name.to_string());
         }
+        SetupGuard
     }
+}
 
-    select.run()
+/// Kill the session being set up on Ctrl-C, then exit
+fn install_interrupt_handler() {
+    let result = ctrlc::set_handler(|| {
+        if let Some(name) = cleanup_interrupted_setup() {
+            eprintln!(
+                "\n{} Interrupted, removed partially created session '{}'",
+                "✘".red(),
+                name
+            );
+        }
+        std::process::exit(130);
+    });
+    if let Err(e) = result {
+        eprintln!("{} Could not install Ctrl-C handler: {}", "⚠".yellow(), e);
+    }
 }
 
-/// Create a temporary bash wrapper script for a pane.
-///
-/// The wrapper script:
-/// 1. Clears the terminal
-/// 2. Displays pane notes (if configured) or a simple title
-/// 3. Removes itself from disk (self-cleaning)
-/// 4. Execs into fish shell with greeting and title disabled
+impl Drop for SetupGuard {
+    fn drop(&mut self) {
+        if let Ok(mut current) = SETUP_SESSION.lock() {
+            current.take();
+        }
+    }
+}
+
+/// Kill the session currently being set up, returning its name.
 ///
-/// This approach allows displaying startup information before the shell
-/// takes over, while keeping the pane in a clean state.
-fn create_wrapper_script(id: usize, pane: &ResolvedPane) -> Result<String> {
-    let wrapper_path = format!("/tmp/axel_ws_{}", id);
-    let mut file = std::fs::File::create(&wrapper_path)?;
+/// Called from the Ctrl-C handler; returns `None` when no setup is in progress.
+pub fn cleanup_interrupted_setup() -> Option<String> {
+    let name = SETUP_SESSION.lock().ok()?.take()?;
+    if has_session(&name) {
+        kill_session(&name).ok();
+    }
+    Some(name)
+}
 
-    writeln!(file, "#!/bin/bash")?;
-    writeln!(file, "clear")?;
+// =============================================================================
+// Tests
+// =============================================================================
 
-    let fg_rgb = pane.color().map(to_fg_rgb).unwrap_or("255;255;255");
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Note;
+
+    #[test]
+    fn test_pane_map() {
+        let value = format_pane_map([("shell", "%2"), ("claude", "%1")].into_iter());
+        assert_eq!(value, "claude=%1 shell=%2");
+        assert_eq!(
+            parse_pane_map(&value),
+            HashMap::from([
+                ("claude".to_string(), "%1".to_string()),
+                ("shell".to_string(), "%2".to_string()),
+            ])
+        );
+        assert!(parse_pane_map("").is_empty());
+    }
 
-    if !pane.notes().is_empty() {
-        writeln!(file, "COLS=$(tput cols)")?;
-        writeln!(file, "printf '\\e[38;2;{}m'", fg_rgb)?;
-
-        let first_note = pane.notes().first().map(|s| s.trim()).unwrap_or("");
-        let first_note_len = first_note.chars().count();
-        writeln!(
-            file,
-            "printf ' notes | {}%*s\\n' \"$((COLS - {} - 10))\" ''",
-            first_note.replace('\'', "'\\''"),
-            first_note_len
-        )?;
+    #[test]
+    fn test_file_note_wrapper_lines() {
+        let dir = std::env::temp_dir().join("axel-test-notes-file");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("NOTES.md"), "Ship v2\n\nFix 100% of bugs\n").unwrap();
+
+        let note = Note::File {
+            file: "NOTES.md".to_string(),
+        };
+        let lines = note.lines(&dir);
+        assert_eq!(lines, vec!["Ship v2", "Fix 100% of bugs"]);
+
+        let header = wrapper_header_lines("shell", &lines, "1;2;3");
+        assert_eq!(
+            header,
+            vec![
+                "COLS=$(tput cols)",
+                "printf '\\e[38;2;1;2;3m'",
+                "printf ' notes | Ship v2%*s\\n' \"$((COLS - 7 - 10))\" ''",
+                "printf '       | Fix 100%% of bugs%*s\\n' \"$((COLS - 16 - 10))\" ''",
+                "printf '\\e[0m'",
+            ]
+        );
+
+        let missing = Note::File {
+            file: "MISSING.md".to_string(),
+        };
+        assert!(missing.lines(&dir)[0].starts_with("(MISSING.md: "));
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_cmd_note_wrapper_lines() {
+        let dir = std::env::temp_dir();
+        let note = Note::Cmd {
+            cmd: "echo \"it's\"; echo; echo done".to_string(),
+        };
+        let lines = note.lines(&dir);
+        assert_eq!(lines, vec!["it's", "done"]);
+
+        let header = wrapper_header_lines("shell", &lines, "1;2;3");
+        assert_eq!(
+            header[2],
+            "printf ' notes | it'\\''s%*s\\n' \"$((COLS - 4 - 10))\" ''"
+        );
+        assert_eq!(
+            header[3],
+            "printf '       | done%*s\\n' \"$((COLS - 4 - 10))\" ''"
+        );
+
+        let failing = Note::Cmd {
+            cmd: "exit 3".to_string(),
+        };
+        assert_eq!(failing.lines(&dir), vec!["(exit 3: exit status: 3)"]);
+    }
+
+    #[test]
+    fn test_notes_deserialize_mixed_forms() {
+        let yaml = r#"
+type: shell
+notes:
+  - plain note
+  - file: NOTES.md
+  - cmd: git log --oneline -5
+"#;
+        let pane: PaneConfig = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(
+            pane.notes(),
+            &[
+                Note::Text("plain note".to_string()),
+                Note::File {
+                    file: "NOTES.md".to_string()
+                },
+                Note::Cmd {
+                    cmd: "git log --oneline -5".to_string()
+                },
+            ]
+        );
+    }
 
-        for note in pane.notes().iter().skip(1) {
-            let note = note.trim();
-            let note_len = note.chars().count();
-            writeln!(
-                file,
-                "printf '       | {}%*s\\n' \"$((COLS - {} - 10))\" ''",
-                note.replace('\'', "'\\''"),
-                note_len
-            )?;
+    fn resolved(col: u32, row: u32, width: Option<u32>, height: Option<u32>) -> ResolvedPane {
+        ResolvedPane {
+            name: format!("p{}{}", col, row),
+            col,
+            row,
+            width,
+            height,
+            colspan: 1,
+            rowspan: 1,
+            window: None,
+            config: PaneConfig::Custom(Default::default()),
         }
+    }
 
-        writeln!(file, "printf '\\e[0m'")?;
-    } else {
-        writeln!(
-            file,
-            "printf '%b\\n' $'\\e'\"[38;2;{}m- {} -\"$'\\e'\"[0m\"",
-            fg_rgb, pane.name
-        )?;
+    #[test]
+    fn test_named_layout_skips_manual_sizes() {
+        let yaml = r#"
+workspace: test
+layouts:
+  panes:
+    - type: claude
+    - type: shell
+  grids:
+    default:
+      layout: tiled
+      claude: { col: 0, row: 0, width: 70 }
+      shell: { col: 1, row: 0 }
+    manual:
+      claude: { col: 0, row: 0, width: 70 }
+"#;
+        let config: WorkspaceConfig = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(config.grid_layout(None), Some(TmuxLayout::Tiled));
+        assert_eq!(config.grid_layout(Some("manual")), None);
+
+        let panes = [
+            resolved(0, 0, Some(70), None),
+            resolved(0, 1, None, Some(20)),
+        ];
+
+        // Manual path: explicit sizes are used
+        let refs: Vec<&ResolvedPane> = panes.iter().collect();
+        assert_eq!(column_widths(&refs, None), HashMap::from([(0, 70)]));
+        assert_eq!(row_height(&panes[1], 1, 2, None), 20);
+
+        // Named layout path: sizes are left to select-layout
+        let layout = config.grid_layout(None);
+        assert!(column_widths(&refs, layout).is_empty());
+        assert_eq!(row_height(&panes[1], 1, 2, layout), 50);
     }
 
-    writeln!(file, "rm '{}'", wrapper_path)?;
-    writeln!(file, "if command -v fish >/dev/null 2>&1; then")?;
-    writeln!(
-        file,
-        "  exec fish -C 'set fish_greeting; function fish_title; end'"
-    )?;
-    writeln!(file, "else")?;
-    writeln!(file, "  exec \"$SHELL\"")?;
-    writeln!(file, "fi")?;
+    #[test]
+    fn test_unknown_layout_is_rejected() {
+        let yaml = "layout: diagonal\nclaude: { col: 0, row: 0 }\n";
+        let err = serde_yaml::from_str::<crate::config::Grid>(yaml).unwrap_err();
+        assert!(err.to_string().contains("unknown layout: diagonal"));
+    }
 
-    drop(file);
+    #[test]
+    fn test_wrapper_runs_startup_command_before_exec() {
+        let mut pane = resolved(0, 1, None, None);
+        pane.config =
+            serde_yaml::from_str("type: claude\nstartup_command: source .venv/bin/activate\n")
+                .unwrap();
+
+        let script = wrapper_script("/tmp/axel_ws_test", &pane);
+        let lines: Vec<&str> = script.lines().collect();
+        let pos = |needle: &str| lines.iter().position(|l| l.starts_with(needle)).unwrap();
+
+        assert_eq!(lines[pos("{") + 1], "source .venv/bin/activate");
+        assert!(lines[pos("{") + 2].starts_with("} || printf"));
+        assert!(pos("{") < pos("rm '/tmp/axel_ws_test'"));
+        assert!(pos("rm '/tmp/axel_ws_test'") < pos("  exec fish"));
+    }
 
-    #[cfg(unix)]
-    {
-        use std::os::unix::fs::PermissionsExt;
-        std::fs::set_permissions(&wrapper_path, std::fs::Permissions::from_mode(0o755))?;
+    #[test]
+    fn test_wrapper_without_startup_command() {
+        let script = wrapper_script("/tmp/axel_ws_test", &resolved(0, 1, None, None));
+        assert!(!script.lines().any(|l| l == "{"));
+        assert!(script.starts_with("#!/bin/bash\nclear\n"));
+        assert!(script.ends_with("  exec \"$SHELL\"\nfi\n"));
     }
 
-    Ok(wrapper_path)
+    #[test]
+    fn test_wrapper_omits_clear_when_disabled() {
+        let mut pane = resolved(0, 1, None, None);
+        pane.config = serde_yaml::from_str("type: shell\nclear: false\n").unwrap();
+        let script = wrapper_script("/tmp/axel_ws_test", &pane);
+        assert!(!script.lines().any(|l| l == "clear"));
+
+        // --no-clear overrides panes that would otherwise clear
+        let yaml = r#"
+workspace: test
+layouts:
+  panes:
+    - type: claude
+    - type: shell
+      clear: true
+  grids:
+    default:
+      claude: { col: 0, row: 0 }
+      shell: { col: 1, row: 0 }
+"#;
+        let mut config: WorkspaceConfig = serde_yaml::from_str(yaml).unwrap();
+        config.disable_clear();
+        let panes = config.resolve_panes(None);
+        assert_eq!(panes.len(), 2);
+        for pane in panes {
+            let script = wrapper_script("/tmp/axel_ws_test", &pane);
+            assert!(script.starts_with("#!/bin/bash\nprintf"), "{}", script);
+        }
+    }
+
+    #[test]
+    fn test_pane_start_without_wrapper() {
+        let yaml = r#"
+workspace: test
+wrapper: false
+layouts:
+  panes:
+    - type: custom
+      name: logs
+      command: tail -f app.log
+    - type: custom
+      name: api
+      command: npm start
+      startup_command: nvm use
+    - type: custom
+      name: notes
+      wrapper: true
+  grids:
+    default:
+      logs: { col: 0, row: 0 }
+      api: { col: 1, row: 0 }
+      notes: { col: 1, row: 1 }
+"#;
+        let config: WorkspaceConfig = serde_yaml::from_str(yaml).unwrap();
+        let panes = config.resolve_panes(None);
+        let id = 90_000 + std::process::id() as usize % 10_000;
+        let script = format!("/tmp/axel_ws_{}", id);
+
+        let logs = &panes[0];
+        assert!(!config.uses_wrapper(logs));
+        let start = pane_start(id, logs, Some("tail -f app.log".into()), false).unwrap();
+        assert_eq!(start, PaneStart::Direct(Some("tail -f app.log".into())));
+        assert_eq!(start.split_command(), Some("tail -f app.log"));
+        assert_eq!(start.typed_command(), None);
+        assert_eq!(start.wrapper(), None);
+        assert!(!std::path::Path::new(&script).exists());
+
+        let api = &panes[1];
+        assert_eq!(
+            direct_command(api, Some("npm start".into())),
+            Some("nvm use; npm start".into())
+        );
+
+        // A pane-level `wrapper: true` wins over the workspace setting
+        let notes = &panes[2];
+        assert!(config.uses_wrapper(notes));
+        let start = pane_start(id, notes, None, true).unwrap();
+        assert_eq!(start.split_command(), Some(script.as_str()));
+        assert_eq!(start.wrapper(), Some(script.as_str()));
+        assert!(std::path::Path::new(&script).exists());
+        std::fs::remove_file(&script).ok();
+    }
+
+    #[test]
+    fn test_ai_command_points_at_mcp_config() {
+        let pane: AiPaneConfig = serde_yaml::from_str(
+            "type: claude\nname: reviewer\nmcp:\n  github:\n    command: npx\n",
+        )
+        .unwrap();
+        let dir = std::path::Path::new("/code/app");
+        assert_eq!(
+            build_ai_command("claude", &pane, Some(dir), None, None, false),
+            "claude --mcp-config /code/app/.axel/mcp/reviewer.json"
+        );
+        assert_eq!(
+            build_ai_command("opencode", &pane, Some(dir), None, None, false),
+            "OPENCODE_CONFIG=/code/app/.axel/opencode/reviewer.json opencode"
+        );
+
+        // OpenCode reports telemetry through environment variables
+        let otel = OtelConfig {
+            port: 4318,
+            pane_id: "p1".to_string(),
+        };
+        let command = build_ai_command("opencode", &pane, Some(dir), None, Some(&otel), false);
+        assert!(command.starts_with(
+            r#"OPENCODE_CONFIG_CONTENT='{"experimental":{"openTelemetry":true}}' OTEL_TRACES_EXPORTER=otlp"#
+        ));
+        assert!(
+            command.contains(
+                " OTEL_EXPORTER_OTLP_TRACES_ENDPOINT=http://localhost:4318/v1/traces/p1 "
+            )
+        );
+        assert!(
+            command.ends_with(" OPENCODE_CONFIG=/code/app/.axel/opencode/reviewer.json opencode")
+        );
+
+        let plain: AiPaneConfig = serde_yaml::from_str("type: claude\n").unwrap();
+        assert_eq!(
+            build_ai_command("claude", &plain, Some(dir), None, None, false),
+            "claude"
+        );
+    }
+
+    #[test]
+    fn test_ai_command_resumes_conversation() {
+        let pane = |yaml: &str| -> AiPaneConfig { serde_yaml::from_str(yaml).unwrap() };
+
+        let last = pane("type: claude\nprompt: Start\nresume: true\n");
+        assert_eq!(
+            build_ai_command("claude", &last, None, None, None, false),
+            "claude --continue"
+        );
+        // OpenCode doesn't take Claude's resume flags
+        assert_eq!(
+            build_ai_command("opencode", &last, None, None, None, false),
+            "opencode 'Start'"
+        );
+
+        let by_id = pane("type: claude\nresume: abc\nsession_id: abc\n");
+        assert_eq!(
+            build_ai_command("claude", &by_id, None, None, None, false),
+            "claude --resume abc"
+        );
+
+        let planner = pane("type: claude\npermission_mode: plan\nadd_dirs: [../docs]\n");
+        assert_eq!(
+            build_ai_command("claude", &planner, None, None, None, false),
+            "claude --permission-mode plan --add-dir ../docs"
+        );
+        assert_eq!(
+            build_ai_command("opencode", &planner, None, None, None, false),
+            "opencode"
+        );
+
+        let fresh = pane("type: claude\nresume: false\nsession_id: abc\n");
+        assert_eq!(
+            build_ai_command("claude", &fresh, None, None, None, false),
+            "claude --session-id abc"
+        );
+    }
+
+    #[test]
+    fn test_one_shot_command() {
+        let pane = |yaml: &str| -> PaneConfig { serde_yaml::from_str(yaml).unwrap() };
+
+        let claude = pane("type: claude\nmodel: opus\nprompt: Start\nresume: true\n");
+        assert_eq!(
+            one_shot_command(&claude, None, "Fix it").unwrap(),
+            "claude -p --model opus --continue 'Fix it'"
+        );
+        assert_eq!(
+            one_shot_command(&pane("type: opencode\n"), None, "Fix it").unwrap(),
+            "opencode run 'Fix it'"
+        );
+        assert!(one_shot_command(&pane("type: antigravity\n"), None, "Fix it").is_err());
+    }
+
+    #[test]
+    fn test_codex_command_writes_profile() {
+        let dir = std::env::temp_dir().join("axel-test-codex-command");
+        std::fs::remove_dir_all(&dir).ok();
+        let workspace = dir.join("app");
+        std::fs::create_dir_all(&workspace).unwrap();
+        let codex_config = dir.join("codex-home/config.toml");
+        let pane: AiPaneConfig = serde_yaml::from_str("type: codex\nname: reviewer\n").unwrap();
+
+        // The profile goes to Codex's own config, not the workspace
+        assert_eq!(
+            build_codex_command(
+                &pane,
+                Some(&workspace),
+                None,
+                None,
+                Some(&codex_config),
+                true
+            ),
+            "codex exec --profile axel-app-reviewer"
+        );
+        let written = std::fs::read_to_string(&codex_config).unwrap();
+        assert!(written.contains("[profiles.axel-app-reviewer]"));
+        assert!(!workspace.join(drivers::CODEX_CONFIG).exists());
+
+        // Without a config to write to, the settings are passed inline
+        let inline = build_codex_command(&pane, Some(&workspace), None, None, None, false);
+        assert!(inline.starts_with("codex -c "));
+        assert!(inline.contains("project_doc_fallback_filenames="));
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_pane_command_quotes_prompts() {
+        let pane: PaneConfig =
+            serde_yaml::from_str("type: antigravity\nmodel: gemini 3\n").unwrap();
+        assert_eq!(
+            pane_command(&pane, None, None, None, Some("it's `date`\n$HOME")),
+            Some("antigravity -m 'gemini 3' 'it'\\''s `date`\n$HOME'".to_string())
+        );
+    }
+
+    #[test]
+    fn test_failing_startup_command_still_execs_shell() {
+        let mut pane = resolved(0, 1, None, None);
+        pane.config =
+            serde_yaml::from_str("type: shell\nstartup_command: exit_not_found_cmd\n").unwrap();
+
+        // Replace the exec tail with a marker so the script can run under bash
+        let script = wrapper_script("/dev/null", &pane)
+            .replace("rm '/dev/null'", "echo reached-exec; exit 0")
+            .replace("clear", "true");
+        let output = std::process::Command::new("bash")
+            .args(["-c", &script])
+            .env("TERM", "dumb")
+            .output()
+            .unwrap();
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(stdout.contains("startup_command failed (exit 127)"));
+        assert!(stdout.contains("reached-exec"));
+    }
+
+    #[test]
+    fn test_interrupt_cleanup_kills_setup_session() {
+        let tmux_available = std::process::Command::new("tmux")
+            .arg("-V")
+            .output()
+            .is_ok_and(|o| o.status.success());
+        if !tmux_available {
+            return;
+        }
+
+        let name = format!("axel-test-interrupt-{}", std::process::id());
+        NewSession::new().name(&name).detached().run().unwrap();
+        assert!(has_session(&name));
+
+        let guard = SetupGuard::arm(&name);
+        assert_eq!(cleanup_interrupted_setup(), Some(name.clone()));
+        assert!(!has_session(&name));
+        drop(guard);
+
+        // Nothing left to clean up once the guard is gone
+        assert_eq!(cleanup_interrupted_setup(), None);
+    }
+
+    #[test]
+    fn test_ai_pane_named_prefers_default_grid() {
+        let yaml = r#"
+workspace: test
+layouts:
+  panes:
+    - type: claude
+      path: /code/app
+    - type: custom
+      name: logs
+  grids:
+    default:
+      claude: { col: 0 }
+      logs: { col: 1 }
+    review:
+      reviewer: { pane_type: claude, col: 0 }
+"#;
+        let config: WorkspaceConfig = serde_yaml::from_str(yaml).unwrap();
+
+        let claude = ai_pane_named(&config, "claude").unwrap();
+        assert_eq!((claude.col, claude.path()), (0, Some("/code/app")));
+        assert!(ai_pane_named(&config, "reviewer").is_some());
+        // Custom panes are never respawned
+        assert!(ai_pane_named(&config, "logs").is_none());
+        assert!(ai_pane_named(&config, "zsh").is_none());
+    }
+
+    #[test]
+    fn test_broadcast_prompt_command() {
+        assert_eq!(
+            broadcast_prompt_command("/opt/my tools/axel"),
+            "set-buffer -b axel-broadcast -- \"%%%\" ; run-shell -b 'tmux show-buffer -b \
+             axel-broadcast | '\\''/opt/my tools/axel'\\'' broadcast --session \
+             #{q:session_name} - >/dev/null 2>&1'"
+        );
+    }
+
+    #[test]
+    fn test_transcript_command() {
+        assert_eq!(
+            transcript_command(
+                "/usr/local/bin/axel",
+                Path::new("/work/my app/.axel/transcripts/claude.log"),
+                "my-app"
+            ),
+            "exec /usr/local/bin/axel transcript --record \
+             '/work/my app/.axel/transcripts/claude.log' --session my-app"
+        );
+    }
+
+    #[test]
+    fn test_broadcast_targets() {
+        let yaml = r#"
+workspace: test
+layouts:
+  panes:
+    - type: claude
+      name: reviewer
+    - type: codex
+    - type: custom
+      name: logs
+      command: tail -f log
+"#;
+        let config: WorkspaceConfig = serde_yaml::from_str(yaml).unwrap();
+        let panes: Vec<PaneInfo> = ["reviewer", "codex", "logs", "opencode", "zsh"]
+            .iter()
+            .enumerate()
+            .map(|(i, title)| PaneInfo {
+                id: format!("%{}", i),
+                title: title.to_string(),
+            })
+            .collect();
+        let ids =
+            |targets: Vec<PaneInfo>| -> Vec<String> { targets.into_iter().map(|p| p.id).collect() };
+
+        // Custom panes and unknown titles are never targeted
+        assert_eq!(
+            ids(broadcast_targets(&panes, Some(&config), &[])),
+            vec!["%0", "%1", "%3"]
+        );
+        // Filter by driver type or pane name
+        let only = ["claude".to_string(), "opencode".to_string()];
+        assert_eq!(
+            ids(broadcast_targets(&panes, Some(&config), &only)),
+            vec!["%0", "%3"]
+        );
+        assert_eq!(
+            ids(broadcast_targets(
+                &panes,
+                Some(&config),
+                &["codex".to_string()]
+            )),
+            vec!["%1"]
+        );
+        // Without a manifest, only default-named AI panes are recognized
+        assert_eq!(ids(broadcast_targets(&panes, None, &[])), vec!["%1", "%3"]);
+
+        // External drivers' panes are AI panes, filtered by their driver
+        let mut config = config;
+        config
+            .layouts
+            .panes
+            .push(PaneConfig::External(AiPaneConfig {
+                pane_type: "aider".to_string(),
+                name: Some("helper".to_string()),
+                ..Default::default()
+            }));
+        let panes = vec![PaneInfo {
+            id: "%5".to_string(),
+            title: "helper".to_string(),
+        }];
+        assert_eq!(
+            ids(broadcast_targets(
+                &panes,
+                Some(&config),
+                &["aider".to_string()]
+            )),
+            vec!["%5"]
+        );
+    }
+
+    #[test]
+    fn test_next_ai_pane() {
+        let targets: Vec<PaneInfo> = ["%1", "%3", "%4"]
+            .iter()
+            .map(|id| PaneInfo {
+                id: id.to_string(),
+                title: "claude".to_string(),
+            })
+            .collect();
+        let next = |active: &str, reverse: bool| {
+            next_ai_pane(&targets, active, reverse).map(|pane| pane.id.as_str())
+        };
+
+        assert_eq!(next("%1", false), Some("%3"));
+        assert_eq!(next("%4", false), Some("%1"));
+        assert_eq!(next("%1", true), Some("%4"));
+        assert_eq!(next("%3", true), Some("%1"));
+        // From a shell or other non-AI pane
+        assert_eq!(next("%2", false), Some("%1"));
+        assert_eq!(next("%2", true), Some("%4"));
+        assert_eq!(next_ai_pane(&[], "%2", true), None);
+    }
+
+    #[test]
+    fn test_wrapper_header_without_notes() {
+        let header = wrapper_header_lines("logs", &[], "1;2;3");
+        assert_eq!(
+            header,
+            vec!["printf '%b\\n' $'\\e'\"[38;2;1;2;3m- logs -\"$'\\e'\"[0m\""]
+        );
+    }
+
+    #[test]
+    fn test_plan_reload_places_new_panes() {
+        let mut panes = vec![
+            resolved(0, 0, None, None),
+            resolved(0, 1, None, None),
+            resolved(1, 0, None, None),
+            resolved(2, 0, None, None),
+        ];
+        panes.push(ResolvedPane {
+            window: Some("logs".to_string()),
+            ..resolved(0, 0, None, None)
+        });
+        panes[4].name = "tail".to_string();
+
+        // p00 and p10 are running; p01, p20 and the logs window are new
+        let running = [("ws", "p00"), ("ws", "p10"), ("ws", "old")];
+        let names = |plan: Vec<(&ResolvedPane, Placement)>| -> Vec<(String, Placement)> {
+            plan.into_iter()
+                .map(|(p, at)| (p.name.clone(), at))
+                .collect()
+        };
+        assert_eq!(
+            names(plan_reload(&panes, &running, "ws")),
+            vec![
+                ("p01".to_string(), Placement::Below("p00".to_string())),
+                ("p20".to_string(), Placement::Right("p10".to_string())),
+                ("tail".to_string(), Placement::Window("logs".to_string())),
+            ]
+        );
+
+        // Nothing to add when every pane runs
+        let all: Vec<(&str, &str)> = panes.iter().map(|p| ("ws", p.name.as_str())).collect();
+        assert!(plan_reload(&panes, &all, "ws").is_empty());
+    }
 }
@@ -0,0 +1,327 @@
+//! Workspace snapshots.
+//!
+//! A [`Snapshot`] records a running session's windows (with their tmux
+//! layout strings), and for each pane its title, working directory,
+//! foreground command and scrollback. It is saved as JSON, by default to
+//! `.axel/snapshot.json` in the workspace.
+//!
+//! [`restore_snapshot`] recreates the session, e.g. after a reboot: every
+//! pane comes back in its directory with its scrollback replayed, and panes
+//! whose title names a manifest pane are restarted with their manifest
+//! command, colors and skills. Other programs are not restarted; their
+//! panes come back at a shell.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::{
+    commands::{
+        NewSession, NewWindow, PANE_READY_TIMEOUT, SelectLayout, SelectPane, SplitWindow,
+        capture_pane, get_environment, has_session, list_pane_details, list_windows, send_text,
+        set_environment, wait_for_pane_ready,
+    },
+    session::{
+        AXEL_MANIFEST_ENV, apply_session_options, build_pane_command, configure_pane,
+        install_workspace_skills, pane_named,
+    },
+};
+use crate::{
+    config::{ResolvedPane, WorkspaceConfig},
+    timing::Timings,
+};
+
+/// Default snapshot location, relative to the workspace directory
+pub const SNAPSHOT_FILE: &str = ".axel/snapshot.json";
+
+/// A saved session
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Snapshot {
+    /// Name of the captured session
+    pub session: String,
+    /// Manifest the session was created from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub manifest: Option<PathBuf>,
+    pub created_at: DateTime<Utc>,
+    pub windows: Vec<WindowSnapshot>,
+}
+
+/// A saved window
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WindowSnapshot {
+    pub name: String,
+    /// tmux layout string, reapplied with `select-layout`
+    pub layout: String,
+    pub panes: Vec<PaneSnapshot>,
+}
+
+/// A saved pane
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PaneSnapshot {
+    pub title: String,
+    /// Working directory
+    pub path: String,
+    /// Foreground command when captured
+    pub command: String,
+    /// Visible content and scrollback
+    #[serde(default)]
+    pub scrollback: String,
+}
+
+impl Snapshot {
+    /// Capture a running session, keeping up to `scrollback` lines of
+    /// history per pane
+    pub fn capture(session: &str, scrollback: usize) -> Result<Self> {
+        if !has_session(session) {
+            anyhow::bail!("Session '{}' not found", session);
+        }
+        let panes = list_pane_details(session)?;
+
+        let mut windows = Vec::new();
+        for window in list_windows(session)? {
+            let mut window_panes = Vec::new();
+            for pane in panes.iter().filter(|p| p.window_id == window.id) {
+                let scrollback = if scrollback > 0 {
+                    capture_pane(&pane.id, scrollback)?
+                } else {
+                    String::new()
+                };
+                window_panes.push(PaneSnapshot {
+                    title: pane.title.clone(),
+                    path: pane.current_path.clone(),
+                    command: pane.current_command.clone(),
+                    scrollback,
+                });
+            }
+            windows.push(WindowSnapshot {
+                name: window.name,
+                layout: window.layout,
+                panes: window_panes,
+            });
+        }
+
+        Ok(Self {
+            session: session.to_string(),
+            manifest: get_environment(session, AXEL_MANIFEST_ENV).map(PathBuf::from),
+            created_at: Utc::now(),
+            windows,
+        })
+    }
+
+    /// Read a snapshot from `path`
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read snapshot {}", path.display()))?;
+        serde_json::from_str(&content)
+            .with_context(|| format!("Invalid snapshot {}", path.display()))
+    }
+
+    /// Write the snapshot to `path`, creating its directory
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Number of panes across all windows
+    pub fn pane_count(&self) -> usize {
+        self.windows.iter().map(|w| w.panes.len()).sum()
+    }
+}
+
+/// Recreate a snapshot as the session `session_name`.
+///
+/// `config` is the manifest used to restart axel panes; without it every
+/// pane comes back at a shell. Returns the names of the restarted panes.
+pub fn restore_snapshot(
+    snapshot: &Snapshot,
+    session_name: &str,
+    config: Option<&WorkspaceConfig>,
+    timings: &mut Timings,
+) -> Result<Vec<String>> {
+    if snapshot.pane_count() == 0 {
+        anyhow::bail!("Snapshot has no panes");
+    }
+    if has_session(session_name) {
+        anyhow::bail!("Session '{}' already exists", session_name);
+    }
+
+    // Manifest panes, by position in the snapshot
+    let manifest_panes: Vec<Vec<Option<ResolvedPane>>> = snapshot
+        .windows
+        .iter()
+        .map(|w| {
+            w.panes
+                .iter()
+                .map(|p| config.and_then(|c| pane_named(c, &p.title)))
+                .collect()
+        })
+        .collect();
+
+    let workspace_dir = config.and_then(WorkspaceConfig::workspace_dir);
+    let index = match config {
+        Some(config) => config.load_index()?,
+        None => None,
+    };
+    if let (Some(config), Some(workspace_dir)) = (config, &workspace_dir) {
+        let panes: Vec<ResolvedPane> = manifest_panes.iter().flatten().flatten().cloned().collect();
+        install_workspace_skills(config, &panes, workspace_dir, timings);
+    }
+
+    let tmux = config.map(|c| c.tmux.clone()).unwrap_or_default();
+    let mut restored = Vec::new();
+    let mut scrollback_counter = 0;
+    let mut created: Vec<(String, &PaneSnapshot, Option<&ResolvedPane>)> = Vec::new();
+
+    for (window_idx, window) in snapshot.windows.iter().enumerate() {
+        let mut window_first: Option<String> = None;
+        let mut last_id: Option<String> = None;
+
+        for (pane_idx, pane) in window.panes.iter().enumerate() {
+            scrollback_counter += 1;
+            let command = replay_command(scrollback_counter, &pane.scrollback)?;
+
+            let pane_id = match (&last_id, created.is_empty()) {
+                (Some(last), _) => SplitWindow::new()
+                    .target(last)
+                    .start_directory(&pane.path)
+                    .command(&command)
+                    .run()?,
+                (None, true) => {
+                    NewSession::new()
+                        .name(session_name)
+                        .detached()
+                        .start_directory(&pane.path)
+                        .window_name(&window.name)
+                        .shell_command(&command)
+                        .run()?;
+                    apply_session_options(session_name, &tmux)?;
+                    list_pane_details(session_name)?
+                        .into_iter()
+                        .next()
+                        .map(|p| p.id)
+                        .context("Restored session has no pane")?
+                }
+                (None, false) => NewWindow::new()
+                    .target(session_name)
+                    .name(&window.name)
+                    .start_directory(&pane.path)
+                    .command(&command)
+                    .run()?,
+            };
+
+            window_first.get_or_insert_with(|| pane_id.clone());
+            last_id = Some(pane_id.clone());
+            created.push((pane_id, pane, manifest_panes[window_idx][pane_idx].as_ref()));
+        }
+
+        if let Some(first) = &window_first {
+            SelectLayout::new()
+                .target(first)
+                .layout(&window.layout)
+                .run()
+                .ok();
+        }
+    }
+    timings.lap("session create");
+
+    if let Some(manifest) = snapshot.manifest.as_ref().and_then(|p| p.to_str()) {
+        set_environment(session_name, AXEL_MANIFEST_ENV, manifest).ok();
+    }
+
+    // Restart axel panes once their shell has replayed the scrollback
+    for (pane_id, pane, manifest_pane) in created {
+        match manifest_pane {
+            Some(manifest_pane) => {
+                configure_pane(&pane_id, manifest_pane)?;
+                let command = build_pane_command(
+                    manifest_pane,
+                    workspace_dir.as_deref(),
+                    index.as_ref(),
+                    None,
+                );
+                if let Some(command) = command {
+                    wait_for_pane_ready(&pane_id, PANE_READY_TIMEOUT);
+                    send_text(&pane_id, &command)?;
+                    restored.push(manifest_pane.name.clone());
+                }
+            }
+            None => SelectPane::new()
+                .target(&pane_id)
+                .title(&pane.title)
+                .run()?,
+        }
+    }
+    timings.lap("configure");
+
+    Ok(restored)
+}
+
+/// Shell command that prints a pane's saved scrollback, then starts the
+/// user's shell. The scrollback is written to a temporary file that the
+/// command removes.
+fn replay_command(id: usize, scrollback: &str) -> Result<String> {
+    let scrollback = scrollback.trim_end();
+    if scrollback.is_empty() {
+        return Ok("exec \"$SHELL\"".to_string());
+    }
+    let path = format!("/tmp/axel_restore_{}_{}", std::process::id(), id);
+    std::fs::write(&path, format!("{}\n", scrollback))?;
+    Ok(format!("cat '{0}'; rm -f '{0}'; exec \"$SHELL\"", path))
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snapshot_round_trip_and_replay() {
+        let snapshot = Snapshot {
+            session: "ws".to_string(),
+            manifest: Some(PathBuf::from("/tmp/ws/AXEL.md")),
+            created_at: Utc::now(),
+            windows: vec![WindowSnapshot {
+                name: "ws".to_string(),
+                layout: "a1b2,80x24,0,0{40x24,0,0,1,39x24,41,0,2}".to_string(),
+                panes: vec![
+                    PaneSnapshot {
+                        title: "claude".to_string(),
+                        path: "/tmp/ws".to_string(),
+                        command: "claude".to_string(),
+                        scrollback: "hello\n\n\n".to_string(),
+                    },
+                    PaneSnapshot {
+                        title: "logs".to_string(),
+                        path: "/tmp".to_string(),
+                        command: "fish".to_string(),
+                        scrollback: String::new(),
+                    },
+                ],
+            }],
+        };
+
+        let path = std::env::temp_dir().join("axel-test-snapshot/.axel/snapshot.json");
+        snapshot.save(&path).unwrap();
+        let loaded = Snapshot::load(&path).unwrap();
+        assert_eq!(loaded, snapshot);
+        assert_eq!(loaded.pane_count(), 2);
+
+        // Empty scrollback starts a plain shell; otherwise it is replayed
+        assert_eq!(replay_command(1, "  \n").unwrap(), "exec \"$SHELL\"");
+        let command = replay_command(2, "hello\n\n\n").unwrap();
+        let file = command.split('\'').nth(1).unwrap();
+        assert_eq!(std::fs::read_to_string(file).unwrap(), "hello\n");
+        assert!(command.ends_with("exec \"$SHELL\""));
+
+        std::fs::remove_file(file).ok();
+        std::fs::remove_dir_all(std::env::temp_dir().join("axel-test-snapshot")).ok();
+    }
+}
@@ -0,0 +1,131 @@
+//! Session appearance and key bindings from the manifest
+//!
+//! axel styles its sessions with its own accent color and status line. The
+//! `tmux:` section overrides them, so a workspace can match an existing
+//! tmux theme, and adds key bindings of its own:
+//!
+//! ```yaml
+//! tmux:
+//!   prefix: C-a                        # Session prefix key
+//!   accent: "#a6e3a1"                  # Active border and status line
+//!   status_right: " #{session_name} | %H:%M "
+//!   bindings:
+//!     - key: g
+//!       command: display-popup -E -d "#{pane_current_path}" lazygit
+//!     - key: M-h
+//!       table: root                    # Without the prefix
+//!       command: select-pane -L
+//!   isolation: socket                  # global (default), session or socket
+//! ```
+//!
+//! `prefix`, `accent` and `status_right` only apply to the workspace's
+//! session. Key tables are shared by the whole tmux server, and axel also
+//! turns the mouse on server-wide, so by default its bindings (like
+//! `prefix B`) and options reach every session once one is created.
+//! `isolation` keeps them away from the rest of your tmux:
+//!
+//! - `session`: only session and window options are set. axel's key
+//!   bindings are left out; the manifest's own `bindings` still apply.
+//! - `socket`: the workspace runs on a tmux server of its own
+//!   (`tmux -L axel`), which loads your tmux.conf but shares nothing else
+//!   with your sessions. Commands run outside the workspace directory find
+//!   it with `AXEL_TMUX_SOCKET=axel`.
+
+use serde::Deserialize;
+
+use super::commands::{AXEL_SOCKET, use_socket};
+
+/// `tmux:` section of the manifest
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct TmuxConfig {
+    /// Prefix key of the session (tmux's `prefix` option)
+    #[serde(default)]
+    pub prefix: Option<String>,
+    /// Color of the active pane border and the status line background
+    #[serde(default)]
+    pub accent: Option<String>,
+    /// Format of the right side of the status line
+    #[serde(default)]
+    pub status_right: Option<String>,
+    /// Extra key bindings
+    #[serde(default)]
+    pub bindings: Vec<TmuxBinding>,
+    /// How far axel's options and bindings reach beyond its sessions
+    #[serde(default)]
+    pub isolation: TmuxIsolation,
+}
+
+impl TmuxConfig {
+    /// Point axel's tmux commands at the server these sessions run on
+    pub fn use_server(&self) {
+        match self.isolation {
+            TmuxIsolation::Socket => use_socket(Some(AXEL_SOCKET)),
+            TmuxIsolation::Global | TmuxIsolation::Session => use_socket(None),
+        }
+    }
+}
+
+/// `tmux.isolation`: what axel changes outside its own sessions
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TmuxIsolation {
+    /// Server-wide mouse options and key bindings on the user's tmux server
+    #[default]
+    Global,
+    /// Session and window options only, and no axel key bindings
+    Session,
+    /// A tmux server of axel's own (`tmux -L axel`)
+    Socket,
+}
+
+/// One key binding under `tmux.bindings`
+#[derive(Debug, Clone, Deserialize)]
+pub struct TmuxBinding {
+    /// Key, in tmux's notation (`g`, `C-h`, `M-Left`, `F5`)
+    pub key: String,
+    /// Tmux command line run by the key, parsed by tmux
+    pub command: String,
+    /// Key table (default `prefix`; `root` binds the key without the prefix)
+    #[serde(default = "default_table")]
+    pub table: String,
+}
+
+fn default_table() -> String {
+    "prefix".to_string()
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tmux_config() {
+        let config: TmuxConfig = serde_yaml::from_str(
+            r#"
+prefix: C-a
+status_right: " %H:%M "
+bindings:
+  - key: g
+    command: display-popup -E lazygit
+  - key: M-h
+    table: root
+    command: select-pane -L
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(config.prefix.as_deref(), Some("C-a"));
+        assert_eq!(config.accent, None);
+        assert_eq!(config.isolation, TmuxIsolation::Global);
+        let bindings: Vec<_> = config
+            .bindings
+            .iter()
+            .map(|b| (b.table.as_str(), b.key.as_str()))
+            .collect();
+        assert_eq!(bindings, [("prefix", "g"), ("root", "M-h")]);
+    }
+}
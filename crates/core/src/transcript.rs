@@ -0,0 +1,372 @@
+//! Pane transcripts
+//!
+//! Every AI pane's output is recorded to `.axel/transcripts/<pane>.log` in
+//! the workspace, so a long agent run can be reviewed after the scrollback
+//! is gone. Tmux pipes the pane's output (`pipe-pane`) into
+//! `axel transcript --record`, which drops terminal escape sequences and
+//! stamps each line with the time it was written.
+//!
+//! Each new session starts fresh transcripts: the previous session's are
+//! archived next to them as `<pane>.<YYYYmmdd-HHMMSS>.log`, keeping the
+//! most recent [`KEEP_ARCHIVES`].
+
+use std::{
+    fs::{self, File, OpenOptions},
+    io::{Read, Write},
+    path::{Path, PathBuf},
+};
+
+use anyhow::Result;
+use chrono::{DateTime, Local};
+
+use crate::recording::{AsciicastWriter, record_pane};
+
+/// Directory, relative to the workspace, holding the transcripts
+pub const TRANSCRIPTS_DIR: &str = ".axel/transcripts";
+
+/// Archived transcripts kept per pane
+pub const KEEP_ARCHIVES: usize = 10;
+
+/// Extension of transcript files
+const EXTENSION: &str = "log";
+
+/// Path of the current transcript of `pane`
+pub fn transcript_path(workspace_dir: &Path, pane: &str) -> PathBuf {
+    workspace_dir
+        .join(TRANSCRIPTS_DIR)
+        .join(format!("{}.{}", pane, EXTENSION))
+}
+
+/// Archived transcripts of `pane`, newest first
+pub fn transcript_archives(workspace_dir: &Path, pane: &str) -> Vec<PathBuf> {
+    let prefix = format!("{}.", pane);
+    let suffix = format!(".{}", EXTENSION);
+    let Ok(entries) = fs::read_dir(workspace_dir.join(TRANSCRIPTS_DIR)) else {
+        return Vec::new();
+    };
+
+    let mut archives: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| {
+            path.file_name()
+                .and_then(|n| n.to_str())
+                .and_then(|n| n.strip_prefix(&prefix))
+                .and_then(|rest| rest.strip_suffix(&suffix))
+                .is_some_and(is_archive_stamp)
+        })
+        .collect();
+    // The timestamp format sorts chronologically
+    archives.sort();
+    archives.reverse();
+    archives
+}
+
+/// Names of the panes with a transcript, current or archived
+pub fn transcript_panes(workspace_dir: &Path) -> Vec<String> {
+    let Ok(entries) = fs::read_dir(workspace_dir.join(TRANSCRIPTS_DIR)) else {
+        return Vec::new();
+    };
+    let mut panes: Vec<String> = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let name = entry.file_name().to_str()?.to_string();
+            let stem = name.strip_suffix(&format!(".{}", EXTENSION))?;
+            Some(match stem.rsplit_once('.') {
+                Some((pane, stamp)) if is_archive_stamp(stamp) => pane.to_string(),
+                _ => stem.to_string(),
+            })
+        })
+        .collect();
+    panes.sort();
+    panes.dedup();
+    panes
+}
+
+/// Archive the current transcript of `pane`, if any, so a new session
+/// starts an empty one. Only the newest [`KEEP_ARCHIVES`] archives are kept.
+pub fn rotate_transcript(workspace_dir: &Path, pane: &str) -> Result<()> {
+    let path = transcript_path(workspace_dir, pane);
+    let Ok(metadata) = fs::metadata(&path) else {
+        return Ok(());
+    };
+    if metadata.len() == 0 {
+        fs::remove_file(&path)?;
+        return Ok(());
+    }
+
+    let modified: DateTime<Local> = metadata.modified()?.into();
+    let archive = path.with_file_name(format!(
+        "{}.{}.{}",
+        pane,
+        modified.format("%Y%m%d-%H%M%S"),
+        EXTENSION
+    ));
+    fs::rename(&path, &archive)?;
+
+    for old in transcript_archives(workspace_dir, pane)
+        .iter()
+        .skip(KEEP_ARCHIVES)
+    {
+        fs::remove_file(old).ok();
+    }
+    Ok(())
+}
+
+/// Whether `stamp` looks like an archive's `YYYYmmdd-HHMMSS`
+fn is_archive_stamp(stamp: &str) -> bool {
+    stamp.len() == 15
+        && stamp
+            .char_indices()
+            .all(|(i, c)| if i == 8 { c == '-' } else { c.is_ascii_digit() })
+}
+
+/// Open the transcript at `path` for appending.
+///
+/// A header line with `label` and the date marks where the recording
+/// starts.
+pub fn open_transcript(path: &Path, label: &str) -> Result<TranscriptWriter<File>> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(
+        file,
+        "=== {} — {} ===",
+        label,
+        Local::now().format("%Y-%m-%d %H:%M:%S")
+    )?;
+    Ok(TranscriptWriter::new(file))
+}
+
+/// Append everything read from `input` to the transcript at `path` until
+/// the input ends (tmux closes it when the pane goes away).
+pub fn record_transcript(input: impl Read, path: &Path, label: &str) -> Result<()> {
+    record_pane(
+        input,
+        Some(open_transcript(path, label)?),
+        None::<AsciicastWriter<File>>,
+    )
+}
+
+/// Most spaces a single cursor movement turns into
+const MAX_CURSOR_MOVE: usize = 200;
+
+/// Where the writer is within a terminal escape sequence
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Escape {
+    None,
+    /// After ESC
+    Start,
+    /// In a control sequence (`ESC [`), until its final byte
+    Csi,
+    /// In an operating system command (`ESC ]`) or other string, until BEL
+    /// or `ESC \`
+    Osc,
+    /// ESC seen inside a string, possibly ending it
+    OscEsc,
+}
+
+/// Turns raw terminal output into timestamped plain-text lines.
+///
+/// Full-screen programs position the cursor instead of printing spaces and
+/// newlines, so moving right becomes spaces and moving to another row ends
+/// the line. Other escape sequences are dropped.
+pub struct TranscriptWriter<W> {
+    out: W,
+    line: Vec<u8>,
+    escape: Escape,
+    /// Parameter bytes of the control sequence being read
+    params: Vec<u8>,
+}
+
+impl<W: Write> TranscriptWriter<W> {
+    pub fn new(out: W) -> Self {
+        Self {
+            out,
+            line: Vec::new(),
+            escape: Escape::None,
+            params: Vec::new(),
+        }
+    }
+
+    /// Feed raw pane output, writing every line it completes
+    pub fn write(&mut self, bytes: &[u8]) -> Result<()> {
+        for &byte in bytes {
+            self.escape = match (self.escape, byte) {
+                (Escape::None, 0x1b) => Escape::Start,
+                (Escape::None, b'\n') => {
+                    self.flush_line()?;
+                    Escape::None
+                }
+                // Tabs are kept; other control characters (\r, BEL, ...) dropped
+                (Escape::None, b'\t' | 0x20..=0x7e | 0x80..) => {
+                    self.line.push(byte);
+                    Escape::None
+                }
+                (Escape::None, _) => Escape::None,
+                (Escape::Start, b'[') => {
+                    self.params.clear();
+                    Escape::Csi
+                }
+                (Escape::Start, b']' | b'P' | b'_' | b'^' | b'X') => Escape::Osc,
+                (Escape::Start, _) => Escape::None,
+                (Escape::Csi, 0x40..=0x7e) => {
+                    self.control_sequence(byte)?;
+                    Escape::None
+                }
+                (Escape::Csi, _) => {
+                    self.params.push(byte);
+                    Escape::Csi
+                }
+                (Escape::Osc, 0x07) => Escape::None,
+                (Escape::Osc, 0x1b) => Escape::OscEsc,
+                (Escape::Osc, _) => Escape::Osc,
+                (Escape::OscEsc, b'\\') => Escape::None,
+                (Escape::OscEsc, _) => Escape::Osc,
+            };
+        }
+        self.out.flush()?;
+        Ok(())
+    }
+
+    /// Apply the cursor movement of a control sequence ending in `last`
+    fn control_sequence(&mut self, last: u8) -> Result<()> {
+        let count = std::str::from_utf8(&self.params)
+            .ok()
+            .and_then(|n| n.parse().ok())
+            .unwrap_or(1usize)
+            .min(MAX_CURSOR_MOVE);
+        match last {
+            // Cursor forward
+            b'C' => self.line.extend(std::iter::repeat_n(b' ', count)),
+            // Cursor to a column: pad up to it, or at least separate words
+            b'G' | b'`' => {
+                let column = String::from_utf8_lossy(&self.line).chars().count();
+                let pad = count.saturating_sub(1).saturating_sub(column).max(1);
+                self.line.extend(std::iter::repeat_n(b' ', pad));
+            }
+            // Cursor up, down, next/previous line, to a row or position
+            b'A' | b'B' | b'E' | b'F' | b'd' | b'H' | b'f' => self.flush_line()?,
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Write the last, unterminated line
+    pub fn finish(mut self) -> Result<()> {
+        self.flush_line()?;
+        self.out.flush()?;
+        Ok(())
+    }
+
+    /// Write the pending line with a timestamp, skipping blank ones
+    fn flush_line(&mut self) -> Result<()> {
+        let line = String::from_utf8_lossy(&self.line);
+        if !line.trim().is_empty() {
+            writeln!(
+                self.out,
+                "[{}] {}",
+                Local::now().format("%H:%M:%S"),
+                line.trim_end()
+            )?;
+        }
+        self.line.clear();
+        Ok(())
+    }
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Drop the `[HH:MM:SS] ` prefix of each line
+    fn untimed(output: &[u8]) -> Vec<String> {
+        String::from_utf8_lossy(output)
+            .lines()
+            .map(|line| line[11..].to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_transcript_writer() {
+        let mut out = Vec::new();
+        let mut writer = TranscriptWriter::new(&mut out);
+        writer
+            .write(b"\x1b[1;32m> hello\x1b[0m world\r\n\x1b]0;claude\x07\n   \n")
+            .unwrap();
+        // Cursor movement stands in for spaces and newlines
+        writer
+            .write(b"Yes,\x1b[1CI\x1b[2Ctrust\x1b[3;1Hnext")
+            .unwrap();
+        writer.write(b"\x1b[Bline\x1b[9Gat\x1b[2Gcol\n").unwrap();
+        // Sequences split across reads are still recognized
+        writer.write(b"tab\there \x1b[3").unwrap();
+        writer
+            .write(b"8;5;12mblue\x1b]8;;http://x\x1b\\link")
+            .unwrap();
+        writer.finish().unwrap();
+
+        assert_eq!(
+            untimed(&out),
+            vec![
+                "> hello world",
+                "Yes, I  trust",
+                "next",
+                "line    at col",
+                "tab\there bluelink"
+            ]
+        );
+        let first = String::from_utf8_lossy(&out)
+            .lines()
+            .next()
+            .unwrap()
+            .to_string();
+        assert!(first.starts_with('[') && first.as_bytes()[9] == b']');
+    }
+
+    #[test]
+    fn test_rotate_transcript() {
+        let dir = std::env::temp_dir().join("axel-test-rotate-transcript");
+        fs::remove_dir_all(&dir).ok();
+        let path = transcript_path(&dir, "claude");
+
+        record_transcript(&b"first run\n"[..], &path, "test session").unwrap();
+        let content = fs::read_to_string(&path).unwrap();
+        assert!(content.starts_with("=== test session — "));
+        assert!(content.ends_with("] first run\n"));
+        assert_eq!(transcript_panes(&dir), vec!["claude"]);
+
+        rotate_transcript(&dir, "claude").unwrap();
+        assert!(!path.exists());
+        let archives = transcript_archives(&dir, "claude");
+        assert_eq!(archives.len(), 1);
+        assert_eq!(fs::read_to_string(&archives[0]).unwrap(), content);
+        assert_eq!(transcript_panes(&dir), vec!["claude"]);
+
+        // Old archives beyond the limit are removed; other panes are untouched
+        let transcripts = dir.join(TRANSCRIPTS_DIR);
+        for day in 10..10 + KEEP_ARCHIVES {
+            fs::write(
+                transcripts.join(format!("claude.202401{}-120000.log", day)),
+                "",
+            )
+            .unwrap();
+        }
+        fs::write(transcripts.join("claude.other.log"), "").unwrap();
+        fs::write(&path, "second run\n").unwrap();
+        rotate_transcript(&dir, "claude").unwrap();
+
+        let archives = transcript_archives(&dir, "claude");
+        assert_eq!(archives.len(), KEEP_ARCHIVES);
+        assert_eq!(archives[0], archives.iter().max().unwrap().clone());
+        assert!(!transcripts.join("claude.20240110-120000.log").exists());
+        assert!(transcripts.join("claude.other.log").exists());
+        assert_eq!(transcript_panes(&dir), vec!["claude", "claude.other"]);
+
+        fs::remove_dir_all(&dir).ok();
+    }
+}